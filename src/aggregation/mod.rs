@@ -0,0 +1,441 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L136
+/// Self-Hosted Aggregation Server
+/// On-prem federation aggregation, enterprise policy distribution and marketplace catalog hosting
+
+use crate::api::server::ApiResponse;
+use crate::enterprise::{EnterpriseAdminConsole, PolicyBundle};
+use crate::federated::{AnonymizedPatternTemplate, FederatedLearningCoordinator, FederationSnapshot};
+use crate::marketplace::{AutomationMarketplace, MarketplacePlugin};
+use crate::privacy::ConsentLedger;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::info;
+
+/// Server configuration, loaded from JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationServerConfig {
+    pub bind_addr: String,
+    pub admin_token: String,                         // Required to publish policies and catalog entries
+    pub tenants: HashMap<String, String>,            // Tenant token -> tenant key
+    #[serde(default)]
+    pub trusted_publishers: HashMap<String, String>, // publisher_id -> Ed25519 public key, hex
+    #[serde(default)]
+    pub state_path: Option<PathBuf>,                 // Policies, catalog and federation templates are persisted here when set
+}
+
+impl AggregationServerConfig {
+    /// Load configuration from a JSON file
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let config: Self = serde_json::from_str(&json).map_err(|e| format!("Invalid server config {}: {}", path.display(), e))?;
+        if config.admin_token.trim().is_empty() {
+            return Err("admin_token must not be empty".to_string());
+        }
+        Ok(config)
+    }
+}
+
+/// Persisted server state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AggregationState {
+    policies: HashMap<String, PolicyBundle>, // tenant key -> current bundle
+    catalog: Vec<MarketplacePlugin>,
+    #[serde(default)]
+    federation: FederationSnapshot,
+}
+
+/// Body of `POST /v1/federation/templates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateUpload {
+    pub team_id: String,
+    pub templates: Vec<AnonymizedPatternTemplate>,
+}
+
+/// Response of `GET /v1/federation/templates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDownload {
+    pub team: Vec<AnonymizedPatternTemplate>,
+    pub global: Vec<AnonymizedPatternTemplate>,
+}
+
+/// Federation query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateQuery {
+    pub team_id: String,
+}
+
+/// Shared state behind the HTTP transport
+pub struct AggregationServer {
+    config: AggregationServerConfig,
+    federation: Mutex<FederatedLearningCoordinator>,
+    marketplace: Mutex<AutomationMarketplace>,
+    policies: Mutex<HashMap<String, PolicyBundle>>,
+    writes: Mutex<()>, // Serializes changes; each is persisted before it takes effect in memory
+}
+
+impl AggregationServer {
+    /// Create server state, restoring policies, catalog and federation templates from `state_path` when present
+    pub fn new(config: AggregationServerConfig) -> Result<Self, String> {
+        info!("AggregationServer::new: Creating aggregation server for {} tenants", config.tenants.len());
+        let mut marketplace = AutomationMarketplace::new();
+        for (publisher_id, key) in &config.trusted_publishers {
+            marketplace.trust_publisher(publisher_id, key)?;
+        }
+        let state = match &config.state_path {
+            Some(path) if path.exists() => {
+                let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                serde_json::from_str(&json).map_err(|e| format!("Invalid server state {}: {}", path.display(), e))?
+            }
+            _ => AggregationState::default(),
+        };
+        for plugin in state.catalog {
+            marketplace.add_plugin(plugin);
+        }
+        Ok(Self {
+            config,
+            federation: Mutex::new(Self::coordinator(state.federation)),
+            marketplace: Mutex::new(marketplace),
+            policies: Mutex::new(state.policies),
+            writes: Mutex::new(()),
+        })
+    }
+
+    fn coordinator(snapshot: FederationSnapshot) -> FederatedLearningCoordinator {
+        // Clients only upload templates after their own cloud_sync consent check
+        let mut federation = FederatedLearningCoordinator::new(ConsentLedger::new());
+        federation.restore(snapshot);
+        federation
+    }
+
+    fn current_state(&self) -> AggregationState {
+        AggregationState {
+            policies: self.policies.lock().unwrap().clone(),
+            catalog: self.marketplace.lock().unwrap().catalog().into_iter().cloned().collect(),
+            federation: self.federation.lock().unwrap().snapshot(),
+        }
+    }
+
+    /// Write `state` to `state_path`; callers apply a change in memory only once this succeeds
+    fn persist(&self, state: &AggregationState) -> Result<(), String> {
+        let Some(path) = &self.config.state_path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(state).map_err(|e| format!("Failed to encode server state: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Resolve a tenant token to its tenant key
+    pub fn authenticate_tenant(&self, token: Option<&str>) -> Result<String, ApiResponse> {
+        let token = token.ok_or_else(|| ApiResponse::error(401, "Missing tenant token"))?;
+        self.config
+            .tenants
+            .iter()
+            .find(|(candidate, _)| crate::api::server::secrets_match(candidate, token))
+            .map(|(_, tenant)| tenant.clone())
+            .ok_or_else(|| ApiResponse::error(401, "Invalid tenant token"))
+    }
+
+    fn require_admin(&self, admin_token: Option<&str>) -> Result<(), ApiResponse> {
+        if admin_token.map(|token| crate::api::server::secrets_match(token, &self.config.admin_token)).unwrap_or(false) {
+            Ok(())
+        } else {
+            Err(ApiResponse::error(401, "Invalid admin token"))
+        }
+    }
+
+    /// `POST /v1/federation/templates` (tenant); shared globally when the tenant's policy permits
+    pub fn upload_templates(&self, tenant: &str, body: &str) -> ApiResponse {
+        let upload: TemplateUpload = match serde_json::from_str(body) {
+            Ok(upload) => upload,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid template upload: {}", e)),
+        };
+        if upload.team_id.trim().is_empty() {
+            return ApiResponse::error(400, "team_id is required");
+        }
+        let accepted = upload.templates.len();
+        let _writes = self.writes.lock().unwrap();
+        let mut state = self.current_state();
+        let mut federation = Self::coordinator(state.federation);
+        federation.aggregate_team_templates(tenant, &upload.team_id, upload.templates);
+
+        let mut policy = EnterpriseAdminConsole::new();
+        if let Some(bundle) = state.policies.get(tenant) {
+            policy.apply_policy_bundle(bundle);
+        }
+        let shared_globally = federation.share_tenant_globally(tenant, &policy).unwrap_or(0);
+        state.federation = federation.snapshot();
+        if let Err(e) = self.persist(&state) {
+            return ApiResponse::error(500, &e);
+        }
+        *self.federation.lock().unwrap() = federation;
+        ApiResponse::json(202, &serde_json::json!({ "accepted": accepted, "shared_globally": shared_globally }))
+    }
+
+    /// `GET /v1/federation/templates?team_id=` (tenant)
+    pub fn download_templates(&self, tenant: &str, query: &TemplateQuery) -> ApiResponse {
+        let federation = self.federation.lock().unwrap();
+        ApiResponse::json(200, &TemplateDownload {
+            team: federation.get_team_templates(tenant, &query.team_id).to_vec(),
+            global: federation.get_aggregated_templates().to_vec(),
+        })
+    }
+
+    /// `PUT /v1/policies/{tenant}` (admin); the server assigns the next version
+    pub fn publish_policy(&self, admin_token: Option<&str>, tenant: &str, body: &str, now: i64) -> ApiResponse {
+        if let Err(response) = self.require_admin(admin_token) {
+            return response;
+        }
+        if !self.config.tenants.values().any(|t| t == tenant) {
+            return ApiResponse::error(404, &format!("Unknown tenant: {}", tenant));
+        }
+        let mut bundle: PolicyBundle = match serde_json::from_str(body) {
+            Ok(bundle) => bundle,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid policy bundle: {}", e)),
+        };
+        let _writes = self.writes.lock().unwrap();
+        let mut state = self.current_state();
+        bundle.version = state.policies.get(tenant).map(|b| b.version + 1).unwrap_or(1);
+        bundle.updated_at = now;
+        state.policies.insert(tenant.to_string(), bundle.clone());
+        if let Err(e) = self.persist(&state) {
+            return ApiResponse::error(500, &e);
+        }
+        info!("AggregationServer::publish_policy: Publishing policy v{} for {}", bundle.version, tenant);
+        self.policies.lock().unwrap().insert(tenant.to_string(), bundle.clone());
+        ApiResponse::json(200, &bundle)
+    }
+
+    /// `GET /v1/policies` (tenant): the tenant's current bundle
+    pub fn fetch_policy(&self, tenant: &str) -> ApiResponse {
+        match self.policies.lock().unwrap().get(tenant) {
+            Some(bundle) => ApiResponse::json(200, bundle),
+            None => ApiResponse::error(404, "No policy published for this tenant"),
+        }
+    }
+
+    /// `POST /v1/marketplace/plugins` (admin); verification comes from the signature, not the listing
+    pub fn publish_plugin(&self, admin_token: Option<&str>, body: &str) -> ApiResponse {
+        if let Err(response) = self.require_admin(admin_token) {
+            return response;
+        }
+        let plugin: MarketplacePlugin = match serde_json::from_str(body) {
+            Ok(plugin) => plugin,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid plugin listing: {}", e)),
+        };
+        let _writes = self.writes.lock().unwrap();
+        let listed = self.marketplace.lock().unwrap().verify_listing(plugin);
+        let mut state = self.current_state();
+        state.catalog.retain(|p| p.metadata.id != listed.metadata.id);
+        state.catalog.push(listed.clone());
+        if let Err(e) = self.persist(&state) {
+            return ApiResponse::error(500, &e);
+        }
+        self.marketplace.lock().unwrap().add_plugin(listed.clone());
+        ApiResponse::json(201, &listed)
+    }
+
+    /// `GET /v1/marketplace/catalog` (public)
+    pub fn catalog(&self) -> ApiResponse {
+        let marketplace = self.marketplace.lock().unwrap();
+        ApiResponse::json(200, &marketplace.catalog())
+    }
+}
+
+// HTTP transport (axum)
+
+/// Serve on the configured address until the task is cancelled
+pub async fn serve(server: std::sync::Arc<AggregationServer>) -> Result<(), String> {
+    http::serve(server).await
+}
+
+mod http {
+    use super::{AggregationServer, ApiResponse, TemplateQuery};
+    use axum::extract::{Path, Query, Request, State};
+    use axum::http::HeaderMap;
+    use axum::middleware::{self, Next};
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post, put};
+    use axum::{Extension, Router};
+    use std::sync::Arc;
+    use tracing::info;
+
+    /// Tenant identified by the auth middleware
+    #[derive(Debug, Clone)]
+    struct Tenant(String);
+
+    fn bearer(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(|token| token.trim().to_string())
+    }
+
+    fn admin_token(headers: &HeaderMap) -> Option<&str> {
+        headers.get("x-admin-token").and_then(|v| v.to_str().ok())
+    }
+
+    /// Auth middleware: resolves `Authorization: Bearer <tenant token>` to a tenant
+    async fn require_tenant(State(server): State<Arc<AggregationServer>>, mut request: Request, next: Next) -> Response {
+        match server.authenticate_tenant(bearer(request.headers()).as_deref()) {
+            Ok(tenant) => {
+                request.extensions_mut().insert(Tenant(tenant));
+                next.run(request).await
+            }
+            Err(response) => response.into_response(),
+        }
+    }
+
+    async fn upload_templates(State(server): State<Arc<AggregationServer>>, Extension(tenant): Extension<Tenant>, body: String) -> ApiResponse {
+        server.upload_templates(&tenant.0, &body)
+    }
+
+    async fn download_templates(State(server): State<Arc<AggregationServer>>, Extension(tenant): Extension<Tenant>, Query(query): Query<TemplateQuery>) -> ApiResponse {
+        server.download_templates(&tenant.0, &query)
+    }
+
+    async fn fetch_policy(State(server): State<Arc<AggregationServer>>, Extension(tenant): Extension<Tenant>) -> ApiResponse {
+        server.fetch_policy(&tenant.0)
+    }
+
+    async fn publish_policy(State(server): State<Arc<AggregationServer>>, Path(tenant): Path<String>, headers: HeaderMap, body: String) -> ApiResponse {
+        server.publish_policy(admin_token(&headers), &tenant, &body, chrono::Utc::now().timestamp())
+    }
+
+    async fn publish_plugin(State(server): State<Arc<AggregationServer>>, headers: HeaderMap, body: String) -> ApiResponse {
+        server.publish_plugin(admin_token(&headers), &body)
+    }
+
+    async fn catalog(State(server): State<Arc<AggregationServer>>) -> ApiResponse {
+        server.catalog()
+    }
+
+    /// Build the HTTP router; tenant routes go through tenant-token auth, admin routes check the admin token
+    pub fn router(server: Arc<AggregationServer>) -> Router {
+        let tenant = Router::new()
+            .route("/v1/federation/templates", post(upload_templates).get(download_templates))
+            .route("/v1/policies", get(fetch_policy))
+            .route_layer(middleware::from_fn_with_state(server.clone(), require_tenant));
+        Router::new()
+            .route("/v1/policies/:tenant", put(publish_policy))
+            .route("/v1/marketplace/plugins", post(publish_plugin))
+            .route("/v1/marketplace/catalog", get(catalog))
+            .merge(tenant)
+            .with_state(server)
+    }
+
+    pub async fn serve(server: Arc<AggregationServer>) -> Result<(), String> {
+        let addr = server.config.bind_addr.clone();
+        let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| format!("Failed to bind {}: {}", addr, e))?;
+        info!("AggregationServer::serve: Listening on {}", addr);
+        axum::serve(listener, router(server)).await.map_err(|e| format!("Aggregation server failed: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enterprise::{ApprovalMode, LicensePolicy};
+    use crate::types::PatternType;
+
+    fn config(state_path: Option<PathBuf>) -> AggregationServerConfig {
+        AggregationServerConfig {
+            bind_addr: "127.0.0.1:0".to_string(),
+            admin_token: "admin-secret".to_string(),
+            tenants: HashMap::from([("acme-token".to_string(), "acme".to_string()), ("globex-token".to_string(), "globex".to_string())]),
+            trusted_publishers: HashMap::new(),
+            state_path,
+        }
+    }
+
+    fn bundle(global_template_sharing: bool) -> String {
+        serde_json::to_string(&PolicyBundle {
+            version: 99, // Ignored; the server assigns versions
+            approval_mode: ApprovalMode::TwoPerson,
+            global_template_sharing,
+            license_policy: LicensePolicy::default(),
+            compliance_policies: Vec::new(),
+            updated_at: 0,
+        })
+        .unwrap()
+    }
+
+    fn upload(team_id: &str) -> String {
+        serde_json::to_string(&TemplateUpload {
+            team_id: team_id.to_string(),
+            templates: vec![AnonymizedPatternTemplate {
+                pattern_type: PatternType::WorkflowSequence,
+                sequence_length: 3,
+                frequency: 4,
+                avg_time_saved_min: 2.0,
+                confidence_score: 0.9,
+                signature: "ide>terminal".to_string(),
+            }],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tenant_auth_and_isolated_federation() {
+        let server = AggregationServer::new(config(None)).unwrap();
+        assert_eq!(server.authenticate_tenant(Some("acme-token")).unwrap(), "acme");
+        assert_eq!(server.authenticate_tenant(Some("forged")).unwrap_err().status, 401);
+        assert_eq!(server.authenticate_tenant(None).unwrap_err().status, 401);
+
+        assert_eq!(server.upload_templates("acme", &upload("core")).status, 202);
+        assert_eq!(server.upload_templates("acme", &upload("")).status, 400);
+        let acme: TemplateDownload = serde_json::from_str(&server.download_templates("acme", &TemplateQuery { team_id: "core".to_string() }).body).unwrap();
+        let globex: TemplateDownload = serde_json::from_str(&server.download_templates("globex", &TemplateQuery { team_id: "core".to_string() }).body).unwrap();
+        assert_eq!(acme.team.len(), 1);
+        assert!(globex.team.is_empty());
+        // No policy published: templates stay inside the tenant
+        assert!(acme.global.is_empty());
+    }
+
+    #[test]
+    fn test_policy_distribution_versions_and_sharing() {
+        let dir = std::env::temp_dir().join(format!("athenos_aggregation_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let state_path = dir.join("state.json");
+        let server = AggregationServer::new(config(Some(state_path.clone()))).unwrap();
+
+        assert_eq!(server.publish_policy(Some("wrong"), "acme", &bundle(true), 10).status, 401);
+        assert_eq!(server.publish_policy(Some("admin-secret"), "initech", &bundle(true), 10).status, 404);
+        assert_eq!(server.fetch_policy("acme").status, 404);
+        server.publish_policy(Some("admin-secret"), "acme", &bundle(false), 10);
+        let published: PolicyBundle = serde_json::from_str(&server.publish_policy(Some("admin-secret"), "acme", &bundle(true), 20).body).unwrap();
+        assert_eq!((published.version, published.updated_at), (2, 20));
+
+        let fetched: PolicyBundle = serde_json::from_str(&server.fetch_policy("acme").body).unwrap();
+        let mut console = EnterpriseAdminConsole::new();
+        console.apply_policy_bundle(&fetched);
+        assert_eq!(console.get_approval_mode(), ApprovalMode::TwoPerson);
+
+        let response = server.upload_templates("acme", &upload("core"));
+        assert!(response.body.contains(r#""shared_globally":1"#));
+
+        // Policies and templates survive a restart
+        let restarted = AggregationServer::new(config(Some(state_path))).unwrap();
+        assert_eq!(restarted.fetch_policy("acme").status, 200);
+        let download: TemplateDownload = serde_json::from_str(&restarted.download_templates("acme", &TemplateQuery { team_id: "core".to_string() }).body).unwrap();
+        assert_eq!((download.team.len(), download.global.len()), (1, 1));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_failed_persist_leaves_state_unchanged() {
+        // The state path's parent does not exist, so every write fails
+        let state_path = std::env::temp_dir().join(format!("athenos_aggregation_missing_{}", std::process::id())).join("state.json");
+        let server = AggregationServer::new(config(Some(state_path))).unwrap();
+
+        assert_eq!(server.publish_policy(Some("admin-secret"), "acme", &bundle(true), 10).status, 500);
+        assert_eq!(server.fetch_policy("acme").status, 404);
+        assert_eq!(server.upload_templates("acme", &upload("core")).status, 500);
+        let acme: TemplateDownload = serde_json::from_str(&server.download_templates("acme", &TemplateQuery { team_id: "core".to_string() }).body).unwrap();
+        assert!(acme.team.is_empty());
+    }
+}
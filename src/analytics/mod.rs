@@ -3,9 +3,14 @@
 /// Integrate analytics dashboard for ops, safety, and product teams
 
 use crate::types::*;
+use crate::bus::EventSubscriber;
+use crate::cache::{BoundedLog, CacheStats, InstrumentedCache};
 use crate::cohort::CohortStatistics;
+use crate::edge::{OSEvent, OSEventType};
+use crate::models::ScoreBreakdown;
+use crate::plugin::PluginRegistry;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::VecDeque;
 use tracing::info;
 
 /// Analytics metric
@@ -30,17 +35,34 @@ pub enum MetricCategory {
 /// Source: Athenos_AI_Strategy.md#L127
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsDashboard {
-    pub ops_metrics: Vec<AnalyticsMetric>,
-    pub safety_metrics: Vec<AnalyticsMetric>,
-    pub product_metrics: Vec<AnalyticsMetric>,
+    pub ops_metrics: VecDeque<AnalyticsMetric>,
+    pub safety_metrics: VecDeque<AnalyticsMetric>,
+    pub product_metrics: VecDeque<AnalyticsMetric>,
     pub cohort_stats: Option<CohortStatistics>,
+    #[serde(default)]
+    pub ranking_breakdown: Vec<ScoreBreakdown>,
+}
+
+/// Raw metrics are kept for a week (weekly reflections sum over it), however many there are
+const METRIC_RETENTION_SECS: i64 = 7 * 86400;
+
+/// Metrics kept per dashboard category
+const DASHBOARD_CAPACITY: usize = 1_000;
+
+/// Append to a dashboard series, dropping the oldest point when full
+fn push_bounded(series: &mut VecDeque<AnalyticsMetric>, metric: AnalyticsMetric) {
+    if series.len() >= DASHBOARD_CAPACITY {
+        series.pop_front();
+    }
+    series.push_back(metric);
 }
 
 /// Analytics aggregator
 /// Source: Athenos_AI_Strategy.md#L127
 pub struct AnalyticsAggregator {
-    metrics: Vec<AnalyticsMetric>,
+    metrics: BoundedLog<AnalyticsMetric>,
     dashboard: AnalyticsDashboard,
+    newest: i64, // Latest metric timestamp; retention is measured back from it
 }
 
 impl AnalyticsAggregator {
@@ -48,35 +70,45 @@ impl AnalyticsAggregator {
     pub fn new() -> Self {
         info!("AnalyticsAggregator::new: Creating analytics aggregator");
         Self {
-            metrics: Vec::new(),
+            metrics: BoundedLog::new("analytics.metrics", usize::MAX),
             dashboard: AnalyticsDashboard {
-                ops_metrics: Vec::new(),
-                safety_metrics: Vec::new(),
-                product_metrics: Vec::new(),
+                ops_metrics: VecDeque::new(),
+                safety_metrics: VecDeque::new(),
+                product_metrics: VecDeque::new(),
                 cohort_stats: None,
+                ranking_breakdown: Vec::new(),
             },
+            newest: i64::MIN,
         }
     }
 
     /// Record metric
     /// Source: Athenos_AI_Strategy.md#L127
     pub fn record_metric(&mut self, name: String, value: f64, category: MetricCategory) {
+        self.record_metric_at(name, value, category, chrono::Utc::now().timestamp());
+    }
+
+    /// Record metric with an explicit timestamp (backfill and replay)
+    pub fn record_metric_at(&mut self, name: String, value: f64, category: MetricCategory, timestamp: i64) {
         info!("AnalyticsAggregator::record_metric: Recording {} = {} ({:?})", name, value, category);
         
         let metric = AnalyticsMetric {
             name: name.clone(),
             value,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp,
             category: category.clone(),
         };
         
+        self.newest = self.newest.max(timestamp);
+        let cutoff = self.newest - METRIC_RETENTION_SECS;
+        self.metrics.evict_while(|m| m.timestamp < cutoff);
         self.metrics.push(metric.clone());
         
         // Add to appropriate dashboard category
         match category {
-            MetricCategory::Operations => self.dashboard.ops_metrics.push(metric),
-            MetricCategory::Safety => self.dashboard.safety_metrics.push(metric),
-            MetricCategory::Product => self.dashboard.product_metrics.push(metric),
+            MetricCategory::Operations => push_bounded(&mut self.dashboard.ops_metrics, metric),
+            MetricCategory::Safety => push_bounded(&mut self.dashboard.safety_metrics, metric),
+            MetricCategory::Product => push_bounded(&mut self.dashboard.product_metrics, metric),
             _ => {}
         }
     }
@@ -87,6 +119,39 @@ impl AnalyticsAggregator {
         self.dashboard.cohort_stats = Some(stats);
     }
 
+    /// Update the latest explained ranking (for ranker tuning)
+    pub fn update_ranking_breakdown(&mut self, breakdown: Vec<ScoreBreakdown>) {
+        info!("AnalyticsAggregator::update_ranking_breakdown: {} candidates", breakdown.len());
+        self.dashboard.ranking_breakdown = breakdown;
+    }
+
+    /// Record per-plugin resource usage as operations metrics
+    pub fn record_plugin_usage(&mut self, registry: &PluginRegistry) {
+        info!("AnalyticsAggregator::record_plugin_usage: Recording usage for {} plugins", registry.get_all_usage().len());
+        let mut plugin_ids: Vec<&String> = registry.get_all_usage().keys().collect();
+        plugin_ids.sort();
+        
+        for plugin_id in plugin_ids {
+            let usage = &registry.get_all_usage()[plugin_id];
+            self.record_metric(format!("plugin.{}.cpu_time_ms", plugin_id), usage.cpu_time_ms, MetricCategory::Operations);
+            self.record_metric(format!("plugin.{}.memory_high_water_bytes", plugin_id), usage.memory_high_water_bytes as f64, MetricCategory::Operations);
+            self.record_metric(format!("plugin.{}.action_count", plugin_id), usage.action_count as f64, MetricCategory::Operations);
+            self.record_metric(format!("plugin.{}.error_rate", plugin_id), usage.error_rate(), MetricCategory::Operations);
+        }
+        self.record_metric("plugins.disabled".to_string(), registry.get_disabled_plugins().len() as f64, MetricCategory::Safety);
+    }
+
+    /// Record cache size, hit-rate and eviction gauges (picked up by the analytics exporter)
+    pub fn record_cache_stats(&mut self, stats: &[CacheStats]) {
+        let mut stats = stats.to_vec();
+        stats.push(self.metrics.cache_stats());
+        for cache in stats {
+            self.record_metric(format!("cache.{}.size", cache.name), cache.size as f64, MetricCategory::Operations);
+            self.record_metric(format!("cache.{}.hit_rate", cache.name), cache.hit_rate(), MetricCategory::Operations);
+            self.record_metric(format!("cache.{}.evictions", cache.name), cache.evictions as f64, MetricCategory::Operations);
+        }
+    }
+
     /// Get dashboard data
     pub fn get_dashboard(&self) -> &AnalyticsDashboard {
         &self.dashboard
@@ -100,10 +165,31 @@ impl AnalyticsAggregator {
             .collect()
     }
 
+    /// Sum a metric's values recorded within [since, until)
+    pub fn sum_metric(&self, name: &str, since: i64, until: i64) -> f64 {
+        self.metrics
+            .iter()
+            .filter(|m| m.name == name && m.timestamp >= since && m.timestamp < until)
+            .map(|m| m.value)
+            .sum()
+    }
+
     /// Get recent metrics
     pub fn get_recent_metrics(&self, limit: usize) -> Vec<&AnalyticsMetric> {
-        let start = self.metrics.len().saturating_sub(limit);
-        self.metrics[start..].iter().collect()
+        self.metrics.recent(limit).collect()
+    }
+}
+
+impl EventSubscriber for AnalyticsAggregator {
+    fn subscriber_name(&self) -> &str {
+        "analytics"
+    }
+
+    /// Event throughput and app-switch volume from the live stream
+    fn on_events(&mut self, events: &[OSEvent]) {
+        let switches = events.iter().filter(|e| e.event_type == OSEventType::AppSwitch).count();
+        self.record_metric("bus.events".to_string(), events.len() as f64, MetricCategory::Operations);
+        self.record_metric("bus.app_switches".to_string(), switches as f64, MetricCategory::UserEngagement);
     }
 }
 
@@ -116,6 +202,7 @@ impl Default for AnalyticsAggregator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::plugin::{InternalPlugin, Plugin};
 
     #[test]
     fn test_analytics_aggregator_creation() {
@@ -132,6 +219,33 @@ mod tests {
         assert_eq!(aggregator.dashboard.product_metrics.len(), 1);
     }
 
+    #[test]
+    fn test_sum_metric_respects_window() {
+        let mut aggregator = AnalyticsAggregator::new();
+        aggregator.record_metric_at("nudge.delivered".to_string(), 1.0, MetricCategory::UserEngagement, 100);
+        aggregator.record_metric_at("nudge.delivered".to_string(), 2.0, MetricCategory::UserEngagement, 200);
+        aggregator.record_metric_at("nudge.dismissed".to_string(), 1.0, MetricCategory::UserEngagement, 150);
+        
+        assert_eq!(aggregator.sum_metric("nudge.delivered", 0, 300), 3.0);
+        assert_eq!(aggregator.sum_metric("nudge.delivered", 150, 200), 0.0);
+        assert_eq!(aggregator.get_dashboard().ops_metrics.len(), 0);
+    }
+
+    #[test]
+    fn test_metrics_kept_for_retention_window() {
+        let mut aggregator = AnalyticsAggregator::new();
+        for i in 0..20_000 {
+            aggregator.record_metric_at("bus.events".to_string(), 1.0, MetricCategory::Operations, i);
+        }
+        // Nothing inside the week is dropped, however many metrics arrive
+        assert_eq!(aggregator.sum_metric("bus.events", 0, 20_000), 20_000.0);
+        assert_eq!(aggregator.get_dashboard().ops_metrics.len(), DASHBOARD_CAPACITY);
+        
+        aggregator.record_metric_at("bus.events".to_string(), 1.0, MetricCategory::Operations, METRIC_RETENTION_SECS + 10);
+        assert_eq!(aggregator.sum_metric("bus.events", 0, 10), 0.0);
+        assert_eq!(aggregator.sum_metric("bus.events", 10, 20_000), 19_990.0);
+    }
+
     #[test]
     fn test_get_metrics_by_category() {
         let mut aggregator = AnalyticsAggregator::new();
@@ -141,5 +255,55 @@ mod tests {
         let ops_metrics = aggregator.get_metrics_by_category(MetricCategory::Operations);
         assert_eq!(ops_metrics.len(), 1);
     }
-}
 
+    #[test]
+    fn test_record_plugin_usage() {
+        let mut aggregator = AnalyticsAggregator::new();
+        let mut registry = PluginRegistry::new();
+        let plugin = InternalPlugin::new("Test Plugin".to_string(), "Test Author".to_string());
+        let id = plugin.metadata().id.clone();
+        registry.register_plugin(plugin.metadata().clone());
+        registry.record_execution(&id, 3.0, 2048, false).unwrap();
+        
+        aggregator.record_plugin_usage(&registry);
+        
+        assert_eq!(aggregator.dashboard.ops_metrics.len(), 4);
+        assert!(aggregator.metrics.iter().any(|m| m.name == format!("plugin.{}.error_rate", id) && m.value == 1.0));
+        assert_eq!(aggregator.dashboard.safety_metrics.len(), 1);
+    }
+
+    #[test]
+    fn test_ranking_breakdown_serialized_in_dashboard() {
+        let mut aggregator = AnalyticsAggregator::new();
+        aggregator.update_ranking_breakdown(vec![ScoreBreakdown {
+            observation_id: "obs_1".to_string(),
+            action_type: ActionType::FocusMode,
+            pattern_score: 0.5,
+            predicted_time_saved_min: 20.0,
+            savings_decay: 0.5,
+            time_saved_min: 10.0,
+            confidence_multiplier: 1.0,
+            risk_penalty: 0.8,
+            workload_factor: 1.0,
+            edit_factor: 1.0,
+            score: 0.208,
+        }]);
+        
+        let json = serde_json::to_value(aggregator.get_dashboard()).unwrap();
+        assert_eq!(json["ranking_breakdown"][0]["observation_id"], "obs_1");
+        assert_eq!(json["ranking_breakdown"][0]["savings_decay"], 0.5);
+    }
+
+    #[test]
+    fn test_record_cache_stats() {
+        let mut aggregator = AnalyticsAggregator::new();
+        aggregator.record_metric("time_saved".to_string(), 11.0, MetricCategory::Product);
+        let critiques = CacheStats { name: "reflection.critiques".to_string(), size: 3, capacity: 10, hits: 3, misses: 1, evictions: 2 };
+        aggregator.record_cache_stats(&[critiques]);
+        
+        let value = |name: &str| aggregator.metrics.iter().find(|m| m.name == name).map(|m| m.value);
+        assert_eq!(value("cache.reflection.critiques.hit_rate"), Some(0.75));
+        assert_eq!(value("cache.reflection.critiques.evictions"), Some(2.0));
+        assert_eq!(value("cache.analytics.metrics.size"), Some(1.0));
+    }
+}
@@ -0,0 +1,562 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L127
+/// SQL-queryable Analytics Export
+/// Materialize metrics, funnels and anonymized cohort stats into Parquet files or a DuckDB database on a schedule
+
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Export target format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Parquet, // One file per table
+    DuckDb,  // Single database file
+}
+
+/// Funnel over recorded metrics (latest value of each step)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunnelDefinition {
+    pub name: String,
+    pub steps: Vec<String>, // Metric names, widest step first
+}
+
+/// Export configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExportConfig {
+    pub format: ExportFormat,
+    pub output_path: PathBuf, // Directory for Parquet, database file for DuckDB
+    pub interval_secs: i64,
+    pub min_cohort_size: usize, // Cohort stats below this size are not exported (k-anonymity)
+    pub funnels: Vec<FunnelDefinition>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Parquet,
+            output_path: PathBuf::from("./analytics"),
+            interval_secs: 24 * 3600,
+            min_cohort_size: 10,
+            funnels: Vec::new(),
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Reject schedules and cohort thresholds the exporter cannot honour
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs <= 0 {
+            return Err(format!("analytics_export.interval_secs must be positive, got {}", self.interval_secs));
+        }
+        if self.min_cohort_size == 0 {
+            return Err("analytics_export.min_cohort_size must be at least 1".to_string());
+        }
+        if self.output_path.as_os_str().is_empty() {
+            return Err("analytics_export.output_path must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Column type (maps to Parquet physical types and DuckDB SQL types)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    Text,
+    Float,
+    Int,
+}
+
+impl ColumnType {
+    /// DuckDB SQL type
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            ColumnType::Text => "VARCHAR",
+            ColumnType::Float => "DOUBLE",
+            ColumnType::Int => "BIGINT",
+        }
+    }
+}
+
+/// Materialized table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTable {
+    pub name: String,
+    pub columns: Vec<(String, ColumnType)>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+}
+
+impl ExportTable {
+    fn new(name: &str, columns: &[(&str, ColumnType)]) -> Self {
+        Self {
+            name: name.to_string(),
+            columns: columns.iter().map(|(column, kind)| (column.to_string(), *kind)).collect(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// Destination for materialized tables
+pub trait TableSink: Send {
+    /// Write (replace) a table; returns its location
+    fn write_table(&mut self, table: &ExportTable) -> Result<String, String>;
+}
+
+/// Parquet directory sink: one file per table, replaced atomically
+pub struct ParquetSink {
+    dir: PathBuf,
+}
+
+impl ParquetSink {
+    /// Create Parquet sink
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Parquet schema of a table (all columns required)
+    pub fn message_type(table: &ExportTable) -> String {
+        let fields: Vec<String> = table
+            .columns
+            .iter()
+            .map(|(name, kind)| match kind {
+                ColumnType::Text => format!("REQUIRED BYTE_ARRAY {} (UTF8);", name),
+                ColumnType::Float => format!("REQUIRED DOUBLE {};", name),
+                ColumnType::Int => format!("REQUIRED INT64 {};", name),
+            })
+            .collect();
+        format!("message {} {{ {} }}", table.name, fields.join(" "))
+    }
+}
+
+impl TableSink for ParquetSink {
+    fn write_table(&mut self, table: &ExportTable) -> Result<String, String> {
+        let location = self.dir.join(format!("{}.parquet", table.name));
+        info!("ParquetSink::write_table: Writing {:?} ({} rows)", location, table.rows.len());
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create {:?}: {}", self.dir, e))?;
+        let tmp = location.with_extension("parquet.tmp");
+        parquet_file::write(&tmp, table)?;
+        std::fs::rename(&tmp, &location).map_err(|e| format!("Failed to replace {:?}: {}", location, e))?;
+        Ok(location.to_string_lossy().to_string())
+    }
+}
+
+/// DuckDB database sink: each table is replaced in one transaction
+pub struct DuckDbSink {
+    db_path: PathBuf,
+    statements: Vec<String>, // SQL of the last written table
+}
+
+impl DuckDbSink {
+    /// Create DuckDB sink
+    pub fn new(db_path: PathBuf) -> Self {
+        Self { db_path, statements: Vec::new() }
+    }
+
+    /// SQL executed for the last written table
+    pub fn get_statements(&self) -> &[String] {
+        &self.statements
+    }
+
+    /// Statements that replace a table with its rows
+    pub fn table_sql(table: &ExportTable) -> Vec<String> {
+        let columns: Vec<String> = table.columns.iter().map(|(name, kind)| format!("{} {}", name, kind.sql_type())).collect();
+        let mut statements = vec![format!("CREATE OR REPLACE TABLE {} ({})", table.name, columns.join(", "))];
+        for row in &table.rows {
+            let values: Vec<String> = row.iter().map(Self::literal).collect();
+            statements.push(format!("INSERT INTO {} VALUES ({})", table.name, values.join(", ")));
+        }
+        statements
+    }
+
+    fn literal(value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::Null => "NULL".to_string(),
+            serde_json::Value::String(s) => format!("'{}'", s.replace('\'', "''")),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl TableSink for DuckDbSink {
+    fn write_table(&mut self, table: &ExportTable) -> Result<String, String> {
+        self.statements = Self::table_sql(table);
+        info!("DuckDbSink::write_table: {} ({} rows) in {:?}", table.name, table.rows.len(), self.db_path);
+        if let Some(parent) = self.db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        duckdb_file::execute(&self.db_path, &self.statements)?;
+        Ok(format!("{}#{}", self.db_path.to_string_lossy(), table.name))
+    }
+}
+
+/// Create the sink for a configured format
+pub fn sink_for(config: &ExportConfig) -> Box<dyn TableSink> {
+    match config.format {
+        ExportFormat::Parquet => Box::new(ParquetSink::new(config.output_path.clone())),
+        ExportFormat::DuckDb => Box::new(DuckDbSink::new(config.output_path.clone())),
+    }
+}
+
+#[cfg(feature = "analytics-export")]
+mod parquet_file {
+    use super::{ColumnType, ExportTable, ParquetSink};
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Write a table as a single row group
+    pub fn write(path: &Path, table: &ExportTable) -> Result<(), String> {
+        let failed = |e: parquet::errors::ParquetError| format!("Failed to write {:?}: {}", path, e);
+        let schema = Arc::new(parse_message_type(&ParquetSink::message_type(table)).map_err(failed)?);
+        let file = std::fs::File::create(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+        let mut writer = SerializedFileWriter::new(file, schema, Arc::new(WriterProperties::builder().build())).map_err(failed)?;
+        let mut row_group = writer.next_row_group().map_err(failed)?;
+        for (index, (name, kind)) in table.columns.iter().enumerate() {
+            let mut column = row_group.next_column().map_err(failed)?.ok_or(format!("Missing column {} in {:?}", name, path))?;
+            let values = table.rows.iter().map(|row| &row[index]);
+            match kind {
+                ColumnType::Text => {
+                    let values: Vec<ByteArray> = values.map(|v| ByteArray::from(v.as_str().unwrap_or_default())).collect();
+                    column.typed::<ByteArrayType>().write_batch(&values, None, None).map_err(failed)?;
+                }
+                ColumnType::Float => {
+                    let values: Vec<f64> = values.map(|v| v.as_f64().unwrap_or_default()).collect();
+                    column.typed::<DoubleType>().write_batch(&values, None, None).map_err(failed)?;
+                }
+                ColumnType::Int => {
+                    let values: Vec<i64> = values.map(|v| v.as_i64().unwrap_or_default()).collect();
+                    column.typed::<Int64Type>().write_batch(&values, None, None).map_err(failed)?;
+                }
+            }
+            column.close().map_err(failed)?;
+        }
+        row_group.close().map_err(failed)?;
+        writer.close().map_err(failed)?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "analytics-export"))]
+mod parquet_file {
+    use super::ExportTable;
+    use std::path::Path;
+
+    pub fn write(path: &Path, table: &ExportTable) -> Result<(), String> {
+        Err(format!("Cannot write {} to {:?}: built without the analytics-export feature", table.name, path))
+    }
+}
+
+#[cfg(feature = "analytics-export")]
+mod duckdb_file {
+    use std::path::Path;
+
+    /// Run the statements in one transaction
+    pub fn execute(db_path: &Path, statements: &[String]) -> Result<(), String> {
+        let mut conn = duckdb::Connection::open(db_path).map_err(|e| format!("Failed to open {:?}: {}", db_path, e))?;
+        let tx = conn.transaction().map_err(|e| format!("Failed to start transaction on {:?}: {}", db_path, e))?;
+        tx.execute_batch(&statements.join(";\n")).map_err(|e| format!("Failed to write {:?}: {}", db_path, e))?;
+        tx.commit().map_err(|e| format!("Failed to commit {:?}: {}", db_path, e))
+    }
+}
+
+#[cfg(not(feature = "analytics-export"))]
+mod duckdb_file {
+    use std::path::Path;
+
+    pub fn execute(db_path: &Path, _statements: &[String]) -> Result<(), String> {
+        Err(format!("Cannot write {:?}: built without the analytics-export feature", db_path))
+    }
+}
+
+/// Record of one export run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRun {
+    pub at: i64,
+    pub locations: Vec<String>,
+    pub row_counts: HashMap<String, usize>,
+    pub cohort_suppressed: bool,
+    pub error: Option<String>,
+}
+
+/// Scheduled exporter for SQL analysis without a server component
+pub struct AnalyticsExporter {
+    config: ExportConfig,
+    last_export: Option<i64>,
+    runs: Vec<ExportRun>,
+}
+
+impl AnalyticsExporter {
+    /// Create exporter
+    pub fn new(config: ExportConfig) -> Self {
+        info!("AnalyticsExporter::new: {:?} export to {:?}", config.format, config.output_path);
+        Self {
+            config,
+            last_export: None,
+            runs: Vec::new(),
+        }
+    }
+
+    /// Export configuration
+    pub fn config(&self) -> &ExportConfig {
+        &self.config
+    }
+
+    /// Whether an export is due
+    pub fn is_due(&self, now: i64) -> bool {
+        self.last_export.map(|last| now - last >= self.config.interval_secs).unwrap_or(true)
+    }
+
+    /// Build the metrics, funnels and cohort tables
+    /// Cohort-derived rows are omitted when the cohort is smaller than min_cohort_size
+    pub fn build_tables(&self, aggregator: &AnalyticsAggregator) -> (Vec<ExportTable>, bool) {
+        let metrics = aggregator.get_recent_metrics(usize::MAX);
+        let mut metrics_table = ExportTable::new(
+            "metrics",
+            &[("name", ColumnType::Text), ("category", ColumnType::Text), ("value", ColumnType::Float), ("timestamp", ColumnType::Int)],
+        );
+        let mut latest: HashMap<&str, f64> = HashMap::new();
+        for metric in &metrics {
+            let category = match metric.category {
+                MetricCategory::Operations => "operations",
+                MetricCategory::Safety => "safety",
+                MetricCategory::Product => "product",
+                MetricCategory::UserEngagement => "user_engagement",
+            };
+            metrics_table.rows.push(vec![metric.name.clone().into(), category.into(), metric.value.into(), metric.timestamp.into()]);
+            latest.insert(metric.name.as_str(), metric.value);
+        }
+
+        let cohort = aggregator.get_dashboard().cohort_stats.as_ref();
+        let cohort_suppressed = cohort.map(|s| s.total_members < self.config.min_cohort_size).unwrap_or(false);
+        let cohort = cohort.filter(|_| !cohort_suppressed);
+
+        let mut funnels_table = ExportTable::new(
+            "funnels",
+            &[("funnel", ColumnType::Text), ("step_index", ColumnType::Int), ("step", ColumnType::Text), ("count", ColumnType::Float), ("conversion", ColumnType::Float)],
+        );
+        let mut funnels: Vec<(String, Vec<(String, f64)>)> = self
+            .config
+            .funnels
+            .iter()
+            .map(|f| (f.name.clone(), f.steps.iter().map(|step| (step.clone(), latest.get(step.as_str()).copied().unwrap_or(0.0))).collect()))
+            .collect();
+        if let Some(stats) = cohort {
+            let accepted = (stats.total_interventions as f64 * stats.acceptance_rate).round();
+            funnels.push((
+                "intervention".to_string(),
+                vec![
+                    ("observed".to_string(), stats.total_observations as f64),
+                    ("intervened".to_string(), stats.total_interventions as f64),
+                    ("accepted".to_string(), accepted),
+                ],
+            ));
+        }
+        for (funnel, steps) in funnels {
+            let mut previous: Option<f64> = None;
+            for (index, (step, count)) in steps.into_iter().enumerate() {
+                let conversion = match previous {
+                    Some(prev) if prev > 0.0 => count / prev,
+                    Some(_) => 0.0,
+                    None => 1.0,
+                };
+                funnels_table.rows.push(vec![funnel.clone().into(), (index as i64).into(), step.into(), count.into(), conversion.into()]);
+                previous = Some(count);
+            }
+        }
+
+        // Aggregates only; member pseudonyms never leave the cohort manager
+        let mut cohort_table = ExportTable::new("cohort_stats", &[("stat", ColumnType::Text), ("value", ColumnType::Float)]);
+        if let Some(stats) = cohort {
+            let values: [(&str, f64); 5] = [
+                ("total_members", stats.total_members as f64),
+                ("total_observations", stats.total_observations as f64),
+                ("total_interventions", stats.total_interventions as f64),
+                ("acceptance_rate", stats.acceptance_rate),
+                ("avg_time_saved_per_user", stats.avg_time_saved_per_user.round()),
+            ];
+            cohort_table.rows = values.iter().map(|(stat, value)| vec![(*stat).into(), (*value).into()]).collect();
+        }
+
+        (vec![metrics_table, funnels_table, cohort_table], cohort_suppressed)
+    }
+
+    /// Export every table if the schedule says so
+    pub fn run_if_due(&mut self, now: i64, aggregator: &AnalyticsAggregator, sink: &mut dyn TableSink) -> Option<ExportRun> {
+        if !self.is_due(now) {
+            return None;
+        }
+        let (tables, cohort_suppressed) = self.build_tables(aggregator);
+        let mut run = ExportRun {
+            at: now,
+            locations: Vec::new(),
+            row_counts: HashMap::new(),
+            cohort_suppressed,
+            error: None,
+        };
+        for table in &tables {
+            match sink.write_table(table) {
+                Ok(location) => {
+                    run.locations.push(location);
+                    run.row_counts.insert(table.name.clone(), table.rows.len());
+                }
+                Err(e) => {
+                    run.error = Some(e);
+                    break;
+                }
+            }
+        }
+        // Failed exports are retried on the next run
+        if run.error.is_none() {
+            self.last_export = Some(now);
+        }
+        info!("AnalyticsExporter::run_if_due: Exported {} tables", run.locations.len());
+        self.runs.push(run.clone());
+        Some(run)
+    }
+
+    /// Get export history
+    pub fn get_runs(&self) -> &[ExportRun] {
+        &self.runs
+    }
+}
+
+impl Default for AnalyticsExporter {
+    fn default() -> Self {
+        Self::new(ExportConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cohort::CohortStatistics;
+
+    fn aggregator(members: usize) -> AnalyticsAggregator {
+        let mut aggregator = AnalyticsAggregator::new();
+        aggregator.record_metric("inference.completed".to_string(), 8.0, MetricCategory::Operations);
+        aggregator.record_metric("inference.failed".to_string(), 2.0, MetricCategory::Operations);
+        aggregator.update_cohort_stats(CohortStatistics {
+            total_members: members,
+            target_size: 200,
+            total_observations: 400,
+            total_interventions: 100,
+            acceptance_rate: 0.25,
+            total_time_saved_min: 900.0,
+            avg_time_saved_per_user: 37.4,
+        });
+        aggregator
+    }
+
+    #[test]
+    fn test_tables_include_metrics_funnels_and_cohort() {
+        let config = ExportConfig {
+            funnels: vec![FunnelDefinition {
+                name: "inference".to_string(),
+                steps: vec!["inference.completed".to_string(), "inference.failed".to_string()],
+            }],
+            ..ExportConfig::default()
+        };
+        let exporter = AnalyticsExporter::new(config);
+        let (tables, suppressed) = exporter.build_tables(&aggregator(24));
+        assert!(!suppressed);
+        assert_eq!(tables[0].rows.len(), 2);
+
+        let funnels = &tables[1];
+        assert_eq!(funnels.rows.len(), 5); // 2 inference steps + 3 intervention steps
+        assert_eq!(funnels.rows[1][4], serde_json::json!(0.25));
+        assert_eq!(funnels.rows[4][3], serde_json::json!(25.0));
+        assert_eq!(tables[2].rows.len(), 5);
+    }
+
+    #[test]
+    fn test_small_cohort_suppressed() {
+        let exporter = AnalyticsExporter::default();
+        let (tables, suppressed) = exporter.build_tables(&aggregator(3));
+        assert!(suppressed);
+        assert!(tables[1].rows.is_empty());
+        assert!(tables[2].rows.is_empty());
+    }
+
+    /// Records table names, failing once `fail_after` tables were written
+    struct RecordingSink {
+        written: Vec<String>,
+        fail_after: usize,
+    }
+
+    impl TableSink for RecordingSink {
+        fn write_table(&mut self, table: &ExportTable) -> Result<String, String> {
+            if self.written.len() >= self.fail_after {
+                return Err(format!("Disk full writing {}", table.name));
+            }
+            self.written.push(table.name.clone());
+            Ok(table.name.clone())
+        }
+    }
+
+    #[test]
+    fn test_duckdb_sql() {
+        let (tables, _) = AnalyticsExporter::default().build_tables(&aggregator(24));
+        let statements = DuckDbSink::table_sql(&tables[0]);
+        assert_eq!(statements[0], "CREATE OR REPLACE TABLE metrics (name VARCHAR, category VARCHAR, value DOUBLE, timestamp BIGINT)");
+        assert!(statements.iter().any(|s| s.starts_with("INSERT INTO metrics VALUES ('inference.completed', 'operations', 8.0")));
+        assert_eq!(
+            ParquetSink::message_type(&tables[2]),
+            "message cohort_stats { REQUIRED BYTE_ARRAY stat (UTF8); REQUIRED DOUBLE value; }"
+        );
+    }
+
+    #[test]
+    fn test_scheduled_export_retries_failures() {
+        let mut exporter = AnalyticsExporter::default();
+        let mut failing = RecordingSink { written: Vec::new(), fail_after: 1 };
+        let run = exporter.run_if_due(0, &aggregator(24), &mut failing).unwrap();
+        assert_eq!(run.error.as_deref(), Some("Disk full writing funnels"));
+        assert!(exporter.is_due(60)); // Failed exports are retried
+
+        let mut sink = RecordingSink { written: Vec::new(), fail_after: usize::MAX };
+        let run = exporter.run_if_due(60, &aggregator(24), &mut sink).unwrap();
+        assert_eq!(run.locations.len(), 3);
+        assert_eq!(run.row_counts["metrics"], 2);
+
+        // Not due again until the interval has passed
+        assert!(exporter.run_if_due(3600, &aggregator(24), &mut sink).is_none());
+        assert!(exporter.run_if_due(24 * 3600 + 60, &aggregator(24), &mut sink).is_some());
+    }
+
+    #[cfg(not(feature = "analytics-export"))]
+    #[test]
+    fn test_sinks_need_feature() {
+        let dir = std::env::temp_dir().join(format!("athenos_export_off_{}", std::process::id()));
+        let (tables, _) = AnalyticsExporter::default().build_tables(&aggregator(24));
+        assert!(ParquetSink::new(dir.clone()).write_table(&tables[0]).unwrap_err().contains("analytics-export"));
+        assert!(DuckDbSink::new(dir.join("athenos.duckdb")).write_table(&tables[0]).unwrap_err().contains("analytics-export"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "analytics-export")]
+    #[test]
+    fn test_export_writes_parquet_and_duckdb() {
+        let dir = std::env::temp_dir().join(format!("athenos_export_{}", std::process::id()));
+        let (tables, _) = AnalyticsExporter::default().build_tables(&aggregator(24));
+
+        let location = ParquetSink::new(dir.clone()).write_table(&tables[0]).unwrap();
+        let reader = parquet::file::reader::SerializedFileReader::new(std::fs::File::open(&location).unwrap()).unwrap();
+        assert_eq!(parquet::file::reader::FileReader::metadata(&reader).file_metadata().num_rows(), 2);
+
+        let db_path = dir.join("athenos.duckdb");
+        let mut sink = DuckDbSink::new(db_path.clone());
+        sink.write_table(&tables[0]).unwrap();
+        sink.write_table(&tables[0]).unwrap(); // Replaced, not appended
+        let conn = duckdb::Connection::open(&db_path).unwrap();
+        let rows: i64 = conn.query_row("SELECT COUNT(*) FROM metrics", [], |row| row.get(0)).unwrap();
+        assert_eq!(rows, 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
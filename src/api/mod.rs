@@ -3,10 +3,22 @@
 /// Release developer API for custom observation hooks and interventions
 
 use crate::types::*;
+use crate::approval::ApprovalQueue;
+use crate::auto_action::AutoActionSynthesizer;
+use crate::consent::MicroConsentManager;
+use crate::enterprise::ApproverRole;
+use crate::sandbox::SandboxResult;
+use crate::privacy::ConsentLedger;
+use crate::simulation::{self, SimulationHarness};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::info;
 
+pub mod server;
+pub mod webhooks;
+
+use webhooks::{HookEvent, WebhookDispatcher, WebhookJob};
+
 /// API key for developer access
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APIKey {
@@ -24,6 +36,7 @@ pub enum APIPermission {
     WriteInterventions,
     ReadMetrics,
     WriteHooks,
+    ManageApprovals,
 }
 
 /// Custom observation hook
@@ -36,6 +49,8 @@ pub struct ObservationHook {
     pub callback_url: Option<String>,
     pub filter: HashMap<String, String>, // Filter criteria
     pub active: bool,
+    #[serde(default)]
+    pub schema_versions: Vec<u32>, // Payload schema versions the consumer accepts; empty = legacy v1
 }
 
 /// Hook type
@@ -54,6 +69,66 @@ pub struct CustomIntervention {
     pub intervention_type: String,
     pub action: Action,
     pub conditions: HashMap<String, f64>, // Conditions for triggering
+    #[serde(default)]
+    pub callback_url: Option<String>, // Result webhook (falls back to OnActionExecuted hooks)
+}
+
+impl CustomIntervention {
+    /// Trigger conditions: `metric` requires metric >= value, `max:metric` requires metric <= value
+    /// Every condition must hold and missing metrics never match; no conditions never triggers
+    pub fn matches(&self, observation: &Observation) -> bool {
+        !self.conditions.is_empty()
+            && self.conditions.iter().all(|(key, threshold)| {
+                let (metric, at_most) = match key.strip_prefix("max:") {
+                    Some(metric) => (metric, true),
+                    None => (key.as_str(), false),
+                };
+                match observation.metrics.get(metric).filter(|v| v.is_finite()) {
+                    Some(value) if at_most => value <= threshold,
+                    Some(value) => value >= threshold,
+                    None => false,
+                }
+            })
+    }
+}
+
+/// Outcome of routing a triggered intervention
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InterventionStatus {
+    Executed,
+    PendingApproval,
+    Rejected, // No automation consent for the action type, or an approver rejected it
+    Failed,
+}
+
+/// Structured execution result delivered to the developer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterventionResult {
+    pub intervention_id: String,
+    pub developer_id: String,
+    pub observation_id: String,
+    pub status: InterventionStatus,
+    pub action_id: Option<String>,
+    pub sandbox: Option<SandboxResult>,
+    pub approval_item_id: Option<String>,
+    pub error: Option<String>,
+    pub at: i64,
+}
+
+/// Data tenant the API operates on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApiTenant {
+    Production,
+    Sandbox, // Synthetic simulation data only; partners never see real observations
+}
+
+/// Webhook delivery record (Phase D: payload built, HTTP POST simulated)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub url: String,
+    pub payload: String,
+    pub delivered_at: i64,
 }
 
 /// Developer API manager
@@ -62,6 +137,11 @@ pub struct DeveloperAPIManager {
     api_keys: HashMap<String, APIKey>,
     hooks: HashMap<String, ObservationHook>,
     interventions: HashMap<String, CustomIntervention>,
+    webhook_deliveries: Vec<WebhookDelivery>,
+    tenant: ApiTenant,
+    dispatcher: Option<WebhookDispatcher>,
+    synthetic_ids: HashSet<String>, // Synthetic observations (and interventions derived from them) seen by this tenant
+    awaiting_approval: HashMap<String, InterventionResult>, // Pending results by approval item id
 }
 
 impl DeveloperAPIManager {
@@ -72,16 +152,105 @@ impl DeveloperAPIManager {
             api_keys: HashMap::new(),
             hooks: HashMap::new(),
             interventions: HashMap::new(),
+            webhook_deliveries: Vec::new(),
+            tenant: ApiTenant::Production,
+            dispatcher: None,
+            synthetic_ids: HashSet::new(),
+            awaiting_approval: HashMap::new(),
         }
     }
 
+    /// Create developer sandbox tenant: hooks and interventions only ever see synthetic data
+    pub fn new_sandbox() -> Self {
+        info!("DeveloperAPIManager::new_sandbox: Creating developer sandbox tenant");
+        Self {
+            tenant: ApiTenant::Sandbox,
+            ..Self::new()
+        }
+    }
+
+    /// Send webhooks through an async delivery worker (retries, dead-letter queue)
+    pub fn attach_dispatcher(&mut self, dispatcher: WebhookDispatcher) {
+        info!("DeveloperAPIManager::attach_dispatcher: Webhooks now delivered asynchronously");
+        self.dispatcher = Some(dispatcher);
+    }
+
+    /// Detach the delivery worker (e.g. to drain it on shutdown)
+    pub fn take_dispatcher(&mut self) -> Option<WebhookDispatcher> {
+        self.dispatcher.take()
+    }
+
+    /// Fire hooks matching an event; returns the number of deliveries queued
+    /// Nothing is delivered without webhooks::HOOK_CONSENT_CAPABILITY
+    pub fn publish_event(&mut self, event: &HookEvent, consent: &MicroConsentManager, now: i64) -> usize {
+        if !self.admits_event(event) {
+            info!("DeveloperAPIManager::publish_event: Sandbox tenant ignoring event for non-synthetic data");
+            return 0;
+        }
+        let mut hooks: Vec<&ObservationHook> = self.hooks.values().collect();
+        hooks.sort_by(|a, b| a.id.cmp(&b.id));
+        let jobs = webhooks::jobs_for(hooks, event, consent, now);
+        let count = jobs.len();
+        for job in jobs {
+            self.send_webhook(job, now);
+        }
+        count
+    }
+
+    /// Hand a delivery to the worker, or record it when no worker is attached
+    fn send_webhook(&mut self, job: WebhookJob, now: i64) {
+        match &self.dispatcher {
+            Some(dispatcher) => {
+                dispatcher.enqueue(job);
+            }
+            None => {
+                info!("DeveloperAPIManager::send_webhook: POST {} ({})", job.url, job.hook_id);
+                self.webhook_deliveries.push(WebhookDelivery { url: job.url, payload: job.payload, delivered_at: now });
+            }
+        }
+    }
+
+    /// Tenant this manager serves
+    pub fn tenant(&self) -> ApiTenant {
+        self.tenant
+    }
+
+    /// Whether an observation may be exposed to this tenant
+    pub fn admits(&self, observation: &Observation) -> bool {
+        self.tenant == ApiTenant::Production || simulation::is_synthetic(observation)
+    }
+
+    /// Whether an event may be exposed to this tenant
+    /// Actions and outcomes carry only an observation id, so the sandbox admits those it has seen as synthetic
+    fn admits_event(&mut self, event: &HookEvent) -> bool {
+        if self.tenant == ApiTenant::Production {
+            return true;
+        }
+        let observation_id = match event {
+            HookEvent::PatternDetected { observation, .. } => {
+                if !simulation::is_synthetic(observation) {
+                    return false;
+                }
+                self.synthetic_ids.insert(observation.id.clone());
+                return true;
+            }
+            HookEvent::ActionExecuted { observation_id, .. } => observation_id,
+            HookEvent::OutcomeRecorded { outcome } => &outcome.observation_id,
+        };
+        self.synthetic_ids.contains(observation_id)
+    }
+
     /// Register API key
     /// Source: Athenos_AI_Strategy.md#L140
     pub fn register_api_key(&mut self, developer_id: String, permissions: Vec<APIPermission>) -> APIKey {
         info!("DeveloperAPIManager::register_api_key: Registering API key for developer {}", developer_id);
         
+        let prefix = match self.tenant {
+            ApiTenant::Production => "athenos",
+            ApiTenant::Sandbox => "athenos_sandbox",
+        };
         let api_key = APIKey {
-            key: format!("athenos_{}", chrono::Utc::now().timestamp()),
+            key: format!("{}_{:032x}", prefix, rand::random::<u128>()),
             developer_id: developer_id.clone(),
             permissions,
             created_at: chrono::Utc::now().timestamp(),
@@ -100,9 +269,171 @@ impl DeveloperAPIManager {
     }
 
     /// Register custom intervention
-    pub fn register_intervention(&mut self, intervention: CustomIntervention) {
+    pub fn register_intervention(&mut self, intervention: CustomIntervention) -> Result<(), String> {
         info!("DeveloperAPIManager::register_intervention: Registering intervention {}", intervention.id);
+        if let Some(url) = &intervention.callback_url {
+            if !url.starts_with("https://") {
+                return Err("Intervention callback URL must use https".to_string());
+            }
+        }
         self.interventions.insert(intervention.id.clone(), intervention);
+        Ok(())
+    }
+
+    /// Evaluate custom interventions against an incoming observation
+    /// Triggered actions are routed like native ones: consent check, approval queue or sandboxed execution
+    pub fn evaluate_interventions(
+        &mut self,
+        observation: &Observation,
+        synthesizer: &mut AutoActionSynthesizer,
+        approvals: &mut ApprovalQueue,
+        now: i64,
+    ) -> Vec<InterventionResult> {
+        if !self.admits(observation) {
+            info!("DeveloperAPIManager::evaluate_interventions: Sandbox tenant ignoring non-synthetic observation {}", observation.id);
+            return Vec::new();
+        }
+        if self.tenant == ApiTenant::Sandbox {
+            self.synthetic_ids.insert(observation.id.clone());
+        }
+        let mut triggered: Vec<CustomIntervention> = self.interventions.values().filter(|i| i.matches(observation)).cloned().collect();
+        triggered.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut results = Vec::new();
+        for intervention in triggered {
+            info!("DeveloperAPIManager::evaluate_interventions: {} triggered by {}", intervention.id, observation.id);
+            let mut synthesized = observation.clone();
+            synthesized.id = format!("{}_{}", intervention.id, observation.id);
+            synthesized.action = intervention.action.clone();
+            synthesized.source = format!("developer:{}", intervention.developer_id);
+            if self.tenant == ApiTenant::Sandbox {
+                self.synthetic_ids.insert(synthesized.id.clone());
+            }
+
+            let mut result = InterventionResult {
+                intervention_id: intervention.id.clone(),
+                developer_id: intervention.developer_id.clone(),
+                observation_id: observation.id.clone(),
+                status: InterventionStatus::Failed,
+                action_id: None,
+                sandbox: None,
+                approval_item_id: None,
+                error: None,
+                at: now,
+            };
+            if !synthesizer.has_automation_consent(&synthesized.action.action_type) {
+                result.status = InterventionStatus::Rejected;
+                result.error = Some(format!("Automation consent not granted for {:?}", synthesized.action.action_type));
+            } else if synthesizer.requires_approval(&synthesized.action) {
+                let reason = format!("Custom intervention {} from {}", intervention.intervention_type, intervention.developer_id);
+                result.status = InterventionStatus::PendingApproval;
+                let item_id = approvals.enqueue_escalated(&synthesized, reason, now);
+                result.approval_item_id = Some(item_id.clone());
+                self.awaiting_approval.insert(item_id, result.clone());
+            } else {
+                match synthesizer.synthesize_and_execute(&synthesized) {
+                    Ok(executed) => {
+                        result.status = InterventionStatus::Executed;
+                        result.action_id = Some(executed.id);
+                        result.sandbox = executed.execution_result;
+                    }
+                    Err(e) => result.error = Some(e),
+                }
+            }
+
+            self.deliver_result(&intervention, &result, now);
+            results.push(result);
+        }
+        results
+    }
+
+    /// Run registered interventions against a synthetic stream (sandbox tenant only)
+    /// Actions execute in a throwaway synthesizer and approval queue, never the user's
+    pub fn run_sandbox(&mut self, harness: &mut SimulationHarness, count: usize, now: i64) -> Result<Vec<InterventionResult>, String> {
+        if self.tenant != ApiTenant::Sandbox {
+            return Err("Simulation runs are only available in the sandbox tenant".to_string());
+        }
+        info!("DeveloperAPIManager::run_sandbox: Replaying {} synthetic observations", count);
+        // Synthetic observations carry no user data, so every action type may be exercised
+        let mut consent = ConsentLedger::new();
+        consent.automation_scopes = ActionType::all();
+        let mut synthesizer = AutoActionSynthesizer::new();
+        synthesizer.apply_consent(&consent);
+        let mut approvals = ApprovalQueue::new();
+        let mut results = Vec::new();
+        for observation in harness.generate(count, now) {
+            results.extend(self.evaluate_interventions(&observation, &mut synthesizer, &mut approvals, observation.timestamp));
+        }
+        // Nobody decides the throwaway queue, so its items never resolve
+        for item_id in results.iter().filter_map(|r| r.approval_item_id.as_ref()) {
+            self.awaiting_approval.remove(item_id);
+        }
+        Ok(results)
+    }
+
+    /// Deliver the final result of interventions whose approval item has left the queue
+    /// An approved action shows up in the synthesizer's history; anything else was rejected. Items still queued
+    /// (pending, deferred or awaiting a second approver) keep waiting
+    pub fn resolve_approvals(&mut self, approvals: &ApprovalQueue, synthesizer: &AutoActionSynthesizer, now: i64) -> Vec<InterventionResult> {
+        let mut resolved: Vec<String> = self.awaiting_approval.keys().filter(|id| approvals.get(id).is_none()).cloned().collect();
+        resolved.sort();
+
+        let mut results = Vec::new();
+        for item_id in resolved {
+            let Some(mut result) = self.awaiting_approval.remove(&item_id) else {
+                continue;
+            };
+            let action_id = format!("action_{}_{}", result.intervention_id, result.observation_id);
+            match synthesizer.get_all_executed().into_iter().find(|a| a.id == action_id) {
+                Some(executed) => {
+                    result.status = InterventionStatus::Executed;
+                    result.action_id = Some(executed.id.clone());
+                    result.sandbox = executed.execution_result.clone();
+                }
+                None => {
+                    result.status = InterventionStatus::Rejected;
+                    result.error = Some(format!("Approval item {} was rejected", item_id));
+                }
+            }
+            result.at = now;
+            info!("DeveloperAPIManager::resolve_approvals: {} resolved as {:?}", item_id, result.status);
+            if let Some(intervention) = self.interventions.get(&result.intervention_id).cloned() {
+                self.deliver_result(&intervention, &result, now);
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Deliver a result to the intervention callback, or the developer's OnActionExecuted hooks
+    fn deliver_result(&mut self, intervention: &CustomIntervention, result: &InterventionResult, now: i64) {
+        let urls: Vec<String> = match &intervention.callback_url {
+            Some(url) => vec![url.clone()],
+            None => {
+                let mut urls: Vec<String> = self
+                    .get_developer_hooks(&intervention.developer_id)
+                    .into_iter()
+                    .filter(|h| h.hook_type == HookType::OnActionExecuted)
+                    .filter_map(|h| h.callback_url.clone())
+                    .filter(|url| url.starts_with("https://"))
+                    .collect();
+                urls.sort();
+                urls
+            }
+        };
+        let payload = match serde_json::to_string(result) {
+            Ok(payload) => payload,
+            Err(_) => return,
+        };
+        for url in urls {
+            info!("DeveloperAPIManager::deliver_result: {} result {:?}", intervention.id, result.status);
+            self.send_webhook(WebhookJob { hook_id: intervention.id.clone(), url, payload: payload.clone() }, now);
+        }
+    }
+
+    /// Get webhook deliveries
+    pub fn get_webhook_deliveries(&self) -> &[WebhookDelivery] {
+        &self.webhook_deliveries
     }
 
     /// Validate API key
@@ -110,6 +441,17 @@ impl DeveloperAPIManager {
         self.api_keys.get(key)
     }
 
+    /// Handle an approval queue request (JSON command body) for an API key
+    /// Developers decide as `Integration` approvers under their developer ID; their approvals never count as the user's
+    pub fn handle_approval_request(&self, key: &str, queue: &mut ApprovalQueue, body: &str, now: i64) -> Result<String, String> {
+        let api_key = self.validate_api_key(key).ok_or_else(|| "Invalid API key".to_string())?;
+        if !api_key.permissions.contains(&APIPermission::ManageApprovals) {
+            return Err("API key lacks ManageApprovals permission".to_string());
+        }
+        info!("DeveloperAPIManager::handle_approval_request: Approval request from {}", api_key.developer_id);
+        Ok(queue.handle_json(body, &api_key.developer_id, ApproverRole::Integration, now))
+    }
+
     /// Get hooks for developer
     pub fn get_developer_hooks(&self, developer_id: &str) -> Vec<&ObservationHook> {
         self.hooks
@@ -129,6 +471,22 @@ impl Default for DeveloperAPIManager {
 mod tests {
     use super::*;
 
+    /// Accepts every delivery
+    struct AcceptingTransport;
+
+    impl webhooks::WebhookTransport for AcceptingTransport {
+        fn post(&self, _url: &str, _payload: &str) -> Result<u16, String> {
+            Ok(200)
+        }
+    }
+
+    fn consented(mut synthesizer: AutoActionSynthesizer) -> AutoActionSynthesizer {
+        let mut ledger = ConsentLedger::new();
+        ledger.automation_scopes = ActionType::all();
+        synthesizer.apply_consent(&ledger);
+        synthesizer
+    }
+
     #[test]
     fn test_api_manager_creation() {
         let manager = DeveloperAPIManager::new();
@@ -157,11 +515,222 @@ mod tests {
             callback_url: Some("https://example.com/webhook".to_string()),
             filter: HashMap::new(),
             active: true,
+            schema_versions: Vec::new(),
         };
         
         manager.register_hook(hook);
         assert_eq!(manager.hooks.len(), 1);
         assert_eq!(manager.get_developer_hooks("dev_001").len(), 1);
     }
-}
 
+    #[test]
+    fn test_approval_request_requires_permission() {
+        let mut manager = DeveloperAPIManager::new();
+        let mut queue = ApprovalQueue::new();
+        let read_only = manager.register_api_key("dev_001".to_string(), vec![APIPermission::ReadMetrics]);
+        assert!(manager.handle_approval_request(&read_only.key, &mut queue, r#"{"op":"list"}"#, 0).is_err());
+
+        let mut manager = DeveloperAPIManager::new();
+        let approver = manager.register_api_key("dev_002".to_string(), vec![APIPermission::ManageApprovals]);
+        let response = manager.handle_approval_request(&approver.key, &mut queue, r#"{"op":"list"}"#, 0).unwrap();
+        assert!(response.contains(r#""status":"items""#));
+    }
+
+    fn intervention(id: &str, risk: RiskCategory, callback_url: Option<String>) -> CustomIntervention {
+        let mut conditions = HashMap::new();
+        conditions.insert("context_switch_count".to_string(), 10.0);
+        conditions.insert("max:focus_duration_min".to_string(), 15.0);
+        CustomIntervention {
+            id: id.to_string(),
+            developer_id: "dev_001".to_string(),
+            intervention_type: "focus_guard".to_string(),
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Enable focus mode".to_string(),
+                confidence: Confidence::High,
+                risk,
+            },
+            conditions,
+            callback_url,
+        }
+    }
+
+    fn fragmented(switches: f64) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("context_switch_count".to_string(), switches);
+        metrics.insert("focus_duration_min".to_string(), 8.0);
+        Observation {
+            id: "obs_001".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Slack".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::DetectPattern,
+            action: Action {
+                action_type: ActionType::MicroNudge,
+                description: "Native nudge".to_string(),
+                confidence: Confidence::Medium,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_intervention_conditions() {
+        let custom = intervention("int_001", RiskCategory::None, None);
+        assert!(custom.matches(&fragmented(12.0)));
+        assert!(!custom.matches(&fragmented(4.0)));
+        assert!(!CustomIntervention { conditions: HashMap::new(), ..custom }.matches(&fragmented(12.0)));
+    }
+
+    #[test]
+    fn test_triggered_intervention_executes_and_delivers_result() {
+        let mut manager = DeveloperAPIManager::new();
+        manager.register_intervention(intervention("int_001", RiskCategory::None, Some("https://dev.example.com/results".to_string()))).unwrap();
+        assert!(manager.register_intervention(intervention("int_bad", RiskCategory::None, Some("http://insecure".to_string()))).is_err());
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let mut queue = ApprovalQueue::new();
+
+        let results = manager.evaluate_interventions(&fragmented(12.0), &mut synthesizer, &mut queue, 100);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, InterventionStatus::Executed);
+        assert!(results[0].sandbox.as_ref().unwrap().success);
+
+        let deliveries = manager.get_webhook_deliveries();
+        assert_eq!(deliveries.len(), 1);
+        let delivered: InterventionResult = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(delivered.action_id.as_deref(), Some("action_int_001_obs_001"));
+        assert!(manager.evaluate_interventions(&fragmented(2.0), &mut synthesizer, &mut queue, 200).is_empty());
+    }
+
+    #[test]
+    fn test_sandbox_tenant_only_sees_synthetic_data() {
+        let mut manager = DeveloperAPIManager::new_sandbox();
+        assert!(manager.register_api_key("dev_001".to_string(), vec![]).key.starts_with("athenos_sandbox_"));
+        manager.register_intervention(intervention("int_001", RiskCategory::None, Some("https://dev.example.com/results".to_string()))).unwrap();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let mut queue = ApprovalQueue::new();
+
+        let real = fragmented(12.0);
+        assert!(!manager.admits(&real));
+        assert!(manager.evaluate_interventions(&real, &mut synthesizer, &mut queue, 100).is_empty());
+
+        let mut harness = SimulationHarness::new(crate::simulation::SimulationConfig::default());
+        let results = manager.run_sandbox(&mut harness, 50, 1_000).unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.observation_id.starts_with("sim_")));
+        assert_eq!(manager.get_webhook_deliveries().len(), results.len());
+        assert!(synthesizer.get_execution_history().is_empty()); // Caller's synthesizer untouched
+
+        // Outcomes carry only an observation id; the sandbox fires hooks only for ids it saw as synthetic
+        manager.register_hook(ObservationHook {
+            id: "hook_outcomes".to_string(),
+            developer_id: "dev_001".to_string(),
+            hook_type: HookType::OnOutcomeRecorded,
+            callback_url: Some("https://dev.example.com/outcomes".to_string()),
+            filter: HashMap::new(),
+            active: true,
+            schema_versions: Vec::new(),
+        });
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(webhooks::HOOK_CONSENT_CAPABILITY.to_string(), "Share events with developer webhooks".to_string());
+        consent.grant_consent(webhooks::HOOK_CONSENT_CAPABILITY).unwrap();
+        let outcome = |observation_id: &str| HookEvent::OutcomeRecorded {
+            outcome: Outcome {
+                observation_id: observation_id.to_string(),
+                accepted: true,
+                ignored: false,
+                modified: false,
+                time_saved_minutes: None,
+                error_rate_change: None,
+                timestamp: 1_000,
+            },
+        };
+        assert_eq!(manager.publish_event(&outcome(&real.id), &consent, 1_000), 0);
+        assert_eq!(manager.publish_event(&outcome(&results[0].observation_id), &consent, 1_000), 1);
+
+        let mut production = DeveloperAPIManager::new();
+        assert!(production.run_sandbox(&mut harness, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_events_fire_matching_hooks_through_worker() {
+        let mut manager = DeveloperAPIManager::new();
+        manager.register_hook(ObservationHook {
+            id: "hook_patterns".to_string(),
+            developer_id: "dev_001".to_string(),
+            hook_type: HookType::OnPatternDetected,
+            callback_url: Some("https://dev.example.com/patterns".to_string()),
+            filter: [("pattern".to_string(), "context_switching".to_string())].into_iter().collect(),
+            active: true,
+            schema_versions: Vec::new(),
+        });
+        let event = |pattern| HookEvent::PatternDetected { pattern, observation: fragmented(12.0) };
+        let mut consent = MicroConsentManager::new();
+        assert_eq!(manager.publish_event(&event(PatternType::ContextSwitching), &consent, 100), 0);
+        consent.request_consent(webhooks::HOOK_CONSENT_CAPABILITY.to_string(), "Share events with developer webhooks".to_string());
+        consent.grant_consent(webhooks::HOOK_CONSENT_CAPABILITY).unwrap();
+        assert_eq!(manager.publish_event(&event(PatternType::ContextSwitching), &consent, 100), 1);
+        assert_eq!(manager.publish_event(&event(PatternType::DebuggingLoop), &consent, 100), 0);
+        assert!(manager.get_webhook_deliveries()[0].payload.contains(r#""hook_id":"hook_patterns""#));
+        assert!(!manager.get_webhook_deliveries()[0].payload.contains("Slack")); // Raw steps need their own consent
+
+        // Project context leaves the device only with its own consent
+        let mut in_project = fragmented(12.0);
+        in_project.project = Some("git:athenos-ai".to_string());
+        manager.publish_event(&HookEvent::PatternDetected { pattern: PatternType::ContextSwitching, observation: in_project }, &consent, 150);
+        assert!(!manager.get_webhook_deliveries()[1].payload.contains("athenos-ai"));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        manager.attach_dispatcher(WebhookDispatcher::spawn(runtime.handle(), std::sync::Arc::new(AcceptingTransport), webhooks::RetryPolicy::default()));
+        assert_eq!(manager.publish_event(&event(PatternType::ContextSwitching), &consent, 200), 1);
+        let stats = runtime.block_on(manager.take_dispatcher().unwrap().shutdown());
+        assert_eq!(stats.delivered, 1);
+        assert_eq!(manager.get_webhook_deliveries().len(), 2); // Worker deliveries are not recorded as simulated
+    }
+
+    #[test]
+    fn test_risky_intervention_routed_to_approval() {
+        let mut manager = DeveloperAPIManager::new();
+        manager.register_hook(ObservationHook {
+            id: "hook_001".to_string(),
+            developer_id: "dev_001".to_string(),
+            hook_type: HookType::OnActionExecuted,
+            callback_url: Some("https://dev.example.com/hook".to_string()),
+            filter: HashMap::new(),
+            active: true,
+            schema_versions: Vec::new(),
+        });
+        manager.register_intervention(intervention("int_002", RiskCategory::High, None)).unwrap();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let mut queue = ApprovalQueue::new();
+
+        let results = manager.evaluate_interventions(&fragmented(12.0), &mut synthesizer, &mut queue, 100);
+        assert_eq!(results[0].status, InterventionStatus::PendingApproval);
+        assert!(results[0].approval_item_id.is_some());
+        assert_eq!(queue.list(&crate::approval::ApprovalFilter::default(), 100).len(), 1);
+        assert_eq!(manager.get_webhook_deliveries()[0].url, "https://dev.example.com/hook");
+
+        // Still queued: nothing to deliver yet
+        assert!(manager.resolve_approvals(&queue, &synthesizer, 150).is_empty());
+
+        let item_id = results[0].approval_item_id.clone().unwrap();
+        queue.approve(&item_id, "alice", ApproverRole::User).unwrap();
+        let mut shortcuts = crate::shortcut::ShortcutGenerator::new();
+        let mut calendar = crate::scheduling::CalendarNegotiationAgent::new();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer)[0].error.is_none());
+
+        let resolved = manager.resolve_approvals(&queue, &synthesizer, 200);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].status, InterventionStatus::Executed);
+        assert_eq!(resolved[0].action_id.as_deref(), Some("action_int_002_obs_001"));
+        let deliveries = manager.get_webhook_deliveries();
+        assert_eq!(deliveries.len(), 2);
+        let delivered: InterventionResult = serde_json::from_str(&deliveries[1].payload).unwrap();
+        assert_eq!(delivered.status, InterventionStatus::Executed);
+        assert!(manager.resolve_approvals(&queue, &synthesizer, 300).is_empty());
+    }
+}
@@ -0,0 +1,627 @@
+/// Phase: D | Step: 9 | Source: Athenos_AI_Strategy.md#L140
+/// Developer API Server
+/// REST endpoints over DeveloperAPIManager: key registration, hooks, observation queries, intervention submission, approvals, webhook inbox
+
+use super::{APIKey, APIPermission, CustomIntervention, DeveloperAPIManager, ObservationHook};
+use crate::approval::{ApprovalCommand, ApprovalQueue, ApprovalResponse, ItemResult};
+use crate::auto_action::AutoActionSynthesizer;
+use crate::consent::MicroConsentManager;
+use crate::enterprise::ApproverRole;
+use crate::inbox::{InboxSource, WebhookInbox, INBOX_PATH_PREFIX};
+use crate::scheduling::CalendarNegotiationAgent;
+use crate::shortcut::ShortcutGenerator;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// Micro-consent capability required before real observations are exposed to third-party API keys
+pub const DEVELOPER_API_CAPABILITY: &str = "developer_api_observations";
+
+/// Server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiServerConfig {
+    pub bind_addr: String,
+    pub admin_token: String,       // Required to register API keys
+    pub max_observations: usize,   // Observations kept for queries
+    pub max_query_limit: usize,
+}
+
+impl ApiServerConfig {
+    /// Localhost-only server with the given admin token
+    pub fn new(admin_token: String) -> Self {
+        Self {
+            bind_addr: "127.0.0.1:7878".to_string(),
+            admin_token,
+            max_observations: 10_000,
+            max_query_limit: 1_000,
+        }
+    }
+}
+
+/// Transport-independent response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String, // JSON
+}
+
+impl ApiResponse {
+    pub(crate) fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self { status, body },
+            Err(e) => Self::error(500, &format!("Failed to encode response: {}", e)),
+        }
+    }
+
+    pub(crate) fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// Body of a key registration request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRequest {
+    pub developer_id: String,
+    pub permissions: Vec<APIPermission>,
+}
+
+/// Body of an inbox source registration request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxSourceRequest {
+    pub source: InboxSource,
+}
+
+/// Inbox source registration; the token is shown only in this response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxSourceRegistration {
+    pub source: InboxSource,
+    pub path: String,
+    pub token: String,
+}
+
+/// Observation query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObservationQuery {
+    pub since: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+/// Compare secrets without leaking the mismatch position through timing
+pub(crate) fn secrets_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Shared state behind the HTTP transport
+pub struct ApiServer {
+    config: ApiServerConfig,
+    manager: Mutex<DeveloperAPIManager>,
+    observations: Mutex<VecDeque<Observation>>,
+    approvals: Mutex<ApprovalQueue>,
+    inbox: Mutex<WebhookInbox>,
+}
+
+impl ApiServer {
+    /// Create server state around a developer API manager
+    pub fn new(config: ApiServerConfig, manager: DeveloperAPIManager) -> Self {
+        info!("ApiServer::new: Creating developer API server for {}", config.bind_addr);
+        Self {
+            config,
+            manager: Mutex::new(manager),
+            observations: Mutex::new(VecDeque::new()),
+            approvals: Mutex::new(ApprovalQueue::new()),
+            inbox: Mutex::new(WebhookInbox::default()),
+        }
+    }
+
+    /// Serve this approval queue on `POST /v1/approvals`
+    pub fn with_approval_queue(mut self, queue: ApprovalQueue) -> Self {
+        self.approvals = Mutex::new(queue);
+        self
+    }
+
+    /// Serve this webhook inbox on `POST /inbox/<source>`
+    pub fn with_webhook_inbox(mut self, inbox: WebhookInbox) -> Self {
+        self.inbox = Mutex::new(inbox);
+        self
+    }
+
+    /// Make observations available to `GET /v1/observations`; the oldest are dropped past capacity
+    /// A sandbox tenant only ever publishes synthetic observations; real ones need DEVELOPER_API_CAPABILITY
+    pub fn publish_observations(&self, consent: &MicroConsentManager, observations: &[Observation]) {
+        let consented = consent.has_consent(DEVELOPER_API_CAPABILITY);
+        let manager = self.manager.lock().unwrap();
+        let mut buffer = self.observations.lock().unwrap();
+        buffer.extend(
+            observations
+                .iter()
+                .filter(|o| manager.admits(o) && (consented || crate::simulation::is_synthetic(o)))
+                .cloned(),
+        );
+        while buffer.len() > self.config.max_observations {
+            buffer.pop_front();
+        }
+    }
+
+    /// Resolve the API key of a request (auth middleware)
+    pub fn authenticate(&self, key: Option<&str>, now: i64) -> Result<APIKey, ApiResponse> {
+        let key = key.ok_or_else(|| ApiResponse::error(401, "Missing API key"))?;
+        let manager = self.manager.lock().unwrap();
+        match manager.validate_api_key(key) {
+            Some(api_key) if api_key.expires_at.map(|at| now >= at).unwrap_or(false) => Err(ApiResponse::error(401, "API key expired")),
+            Some(api_key) => Ok(api_key.clone()),
+            None => Err(ApiResponse::error(401, "Invalid API key")),
+        }
+    }
+
+    fn require(key: &APIKey, permission: APIPermission) -> Result<(), ApiResponse> {
+        if key.permissions.contains(&permission) {
+            Ok(())
+        } else {
+            Err(ApiResponse::error(403, &format!("API key lacks {:?} permission", permission)))
+        }
+    }
+
+    /// `POST /v1/keys` (admin token)
+    pub fn register_key(&self, admin_token: Option<&str>, body: &str) -> ApiResponse {
+        if self.config.admin_token.is_empty() || !admin_token.map(|token| secrets_match(token, &self.config.admin_token)).unwrap_or(false) {
+            return ApiResponse::error(401, "Invalid admin token");
+        }
+        let request: KeyRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid key request: {}", e)),
+        };
+        if request.developer_id.trim().is_empty() {
+            return ApiResponse::error(400, "developer_id is required");
+        }
+        let api_key = self.manager.lock().unwrap().register_api_key(request.developer_id, request.permissions);
+        ApiResponse::json(201, &api_key)
+    }
+
+    /// `POST /v1/inbox/sources` (admin token); returns the bearer token the external system posts with
+    pub fn register_inbox_source(&self, admin_token: Option<&str>, body: &str) -> ApiResponse {
+        if self.config.admin_token.is_empty() || !admin_token.map(|token| secrets_match(token, &self.config.admin_token)).unwrap_or(false) {
+            return ApiResponse::error(401, "Invalid admin token");
+        }
+        let request: InboxSourceRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid inbox source request: {}", e)),
+        };
+        let token = self.inbox.lock().unwrap().register_source(request.source);
+        ApiResponse::json(201, &InboxSourceRegistration {
+            source: request.source,
+            path: format!("{}{}", INBOX_PATH_PREFIX, request.source.path_segment()),
+            token,
+        })
+    }
+
+    /// `POST /inbox/<source>` (the source's bearer token; needs webhook inbox consent)
+    pub fn receive_inbox_event(&self, segment: &str, token: Option<&str>, body: &str, now: i64) -> ApiResponse {
+        let path = format!("{}{}", INBOX_PATH_PREFIX, segment);
+        let mut inbox = self.inbox.lock().unwrap();
+        if InboxSource::from_segment(segment).is_none() {
+            return ApiResponse::error(404, &format!("Unknown inbox path {}", path));
+        }
+        if !inbox.is_consented() {
+            return ApiResponse::error(403, "Webhook inbox consent not granted");
+        }
+        let token = token.unwrap_or_default();
+        if let Err(e) = inbox.authenticate(&path, token) {
+            return ApiResponse::error(401, &e);
+        }
+        match inbox.handle_request(&path, token, body, now) {
+            Ok(Some(observation)) => ApiResponse::json(202, &serde_json::json!({ "accepted": true, "observation_id": observation.id })),
+            Ok(None) => ApiResponse::json(200, &serde_json::json!({ "accepted": false })),
+            Err(e) => ApiResponse::error(400, &e),
+        }
+    }
+
+    /// External workload reported through the inbox (0.0 to 1.0)
+    pub fn external_load(&self, now: i64) -> f64 {
+        self.inbox.lock().unwrap().external_load(now)
+    }
+
+    /// `POST /v1/hooks` (WriteHooks); the hook is owned by the key's developer
+    pub fn register_hook(&self, key: &APIKey, body: &str) -> ApiResponse {
+        if let Err(response) = Self::require(key, APIPermission::WriteHooks) {
+            return response;
+        }
+        let mut hook: ObservationHook = match serde_json::from_str(body) {
+            Ok(hook) => hook,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid hook: {}", e)),
+        };
+        if hook.callback_url.as_ref().map(|url| !url.starts_with("https://")).unwrap_or(false) {
+            return ApiResponse::error(400, "Hook callback URL must use https");
+        }
+        if let Err(e) = hook.schema_version() {
+            return ApiResponse::error(400, &e);
+        }
+        hook.developer_id = key.developer_id.clone();
+        self.manager.lock().unwrap().register_hook(hook.clone());
+        ApiResponse::json(201, &hook)
+    }
+
+    /// `GET /v1/schemas/{version}`: JSON Schema of hook and plugin payloads
+    pub fn payload_schema(&self, version: u32) -> ApiResponse {
+        match crate::event_schema::json_schema(version) {
+            Ok(schema) => ApiResponse::json(200, &schema),
+            Err(e) => ApiResponse::error(404, &e),
+        }
+    }
+
+    /// `GET /v1/observations` (ReadObservations), oldest first
+    pub fn query_observations(&self, key: &APIKey, query: &ObservationQuery) -> ApiResponse {
+        if let Err(response) = Self::require(key, APIPermission::ReadObservations) {
+            return response;
+        }
+        let limit = query.limit.unwrap_or(100).min(self.config.max_query_limit);
+        let observations: Vec<Observation> = self
+            .observations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|o| query.since.map(|since| o.timestamp >= since).unwrap_or(true))
+            .take(limit)
+            .cloned()
+            .collect();
+        ApiResponse::json(200, &observations)
+    }
+
+    /// `POST /v1/interventions` (WriteInterventions); the intervention is owned by the key's developer
+    pub fn submit_intervention(&self, key: &APIKey, body: &str) -> ApiResponse {
+        if let Err(response) = Self::require(key, APIPermission::WriteInterventions) {
+            return response;
+        }
+        let mut intervention: CustomIntervention = match serde_json::from_str(body) {
+            Ok(intervention) => intervention,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid intervention: {}", e)),
+        };
+        intervention.developer_id = key.developer_id.clone();
+        match self.manager.lock().unwrap().register_intervention(intervention.clone()) {
+            Ok(()) => ApiResponse::json(201, &intervention),
+            Err(e) => ApiResponse::error(400, &e),
+        }
+    }
+
+    /// `POST /v1/approvals` (ManageApprovals): list, approve, reject or defer queue items as an `Integration` approver
+    /// An integration's approval is recorded on the item but leaves it pending for the user
+    pub fn handle_approvals(&self, key: &APIKey, body: &str, now: i64) -> ApiResponse {
+        if let Err(response) = Self::require(key, APIPermission::ManageApprovals) {
+            return response;
+        }
+        let command: ApprovalCommand = match serde_json::from_str(body) {
+            Ok(command) => command,
+            Err(e) => return ApiResponse::error(400, &format!("Invalid approval command: {}", e)),
+        };
+        info!("ApiServer::handle_approvals: Approval command from {}", key.developer_id);
+        match self.approvals.lock().unwrap().handle_command(command, &key.developer_id, ApproverRole::Integration, now) {
+            ApprovalResponse::Error { message } => ApiResponse::error(400, &message),
+            response => ApiResponse::json(200, &response),
+        }
+    }
+
+    /// Apply decided approval items to their owning modules and deliver the results of interventions they resolve
+    pub fn apply_approvals(
+        &self,
+        shortcuts: &mut ShortcutGenerator,
+        calendar: &mut CalendarNegotiationAgent,
+        synthesizer: &mut AutoActionSynthesizer,
+        now: i64,
+    ) -> Vec<ItemResult> {
+        let mut approvals = self.approvals.lock().unwrap();
+        let applied = approvals.apply_decisions(shortcuts, calendar, synthesizer);
+        self.manager.lock().unwrap().resolve_approvals(&approvals, synthesizer, now);
+        applied
+    }
+
+    /// Run a closure against the served approval queue (applying decisions, enqueuing items)
+    pub fn with_approvals<R>(&self, f: impl FnOnce(&mut ApprovalQueue) -> R) -> R {
+        f(&mut self.approvals.lock().unwrap())
+    }
+
+    /// Run a closure against the wrapped manager (intervention evaluation, webhook inspection)
+    pub fn with_manager<R>(&self, f: impl FnOnce(&mut DeveloperAPIManager) -> R) -> R {
+        f(&mut self.manager.lock().unwrap())
+    }
+}
+
+// HTTP transport (axum)
+
+/// Serve the API on the configured address until the task is cancelled
+#[cfg(feature = "api-server")]
+pub async fn serve(server: Arc<ApiServer>) -> Result<(), String> {
+    http::serve(server).await
+}
+
+#[cfg(not(feature = "api-server"))]
+pub async fn serve(server: Arc<ApiServer>) -> Result<(), String> {
+    Err(format!("Cannot serve {}: built without the api-server feature", server.config.bind_addr))
+}
+
+#[cfg(feature = "api-server")]
+mod http {
+    use super::{ApiResponse, ApiServer, ObservationQuery};
+    use crate::api::APIKey;
+    use axum::extract::{Path, Query, Request, State};
+    use axum::http::{header, HeaderMap, StatusCode};
+    use axum::middleware::{self, Next};
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post};
+    use axum::{Extension, Router};
+    use std::sync::Arc;
+    use tracing::info;
+
+    impl IntoResponse for ApiResponse {
+        fn into_response(self) -> Response {
+            let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, [(header::CONTENT_TYPE, "application/json")], self.body).into_response()
+        }
+    }
+
+    /// API key from `Authorization: Bearer <key>` or `X-Api-Key`
+    fn api_key_header(headers: &HeaderMap) -> Option<String> {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        bearer
+            .or_else(|| headers.get("x-api-key").and_then(|v| v.to_str().ok()))
+            .map(|key| key.trim().to_string())
+    }
+
+    /// Auth middleware: rejects requests without a valid key and hands the key to handlers
+    async fn require_api_key(State(server): State<Arc<ApiServer>>, mut request: Request, next: Next) -> Response {
+        let key = api_key_header(request.headers());
+        match server.authenticate(key.as_deref(), chrono::Utc::now().timestamp()) {
+            Ok(api_key) => {
+                request.extensions_mut().insert(api_key);
+                next.run(request).await
+            }
+            Err(response) => response.into_response(),
+        }
+    }
+
+    async fn register_key(State(server): State<Arc<ApiServer>>, headers: HeaderMap, body: String) -> ApiResponse {
+        server.register_key(headers.get("x-admin-token").and_then(|v| v.to_str().ok()), &body)
+    }
+
+    async fn register_inbox_source(State(server): State<Arc<ApiServer>>, headers: HeaderMap, body: String) -> ApiResponse {
+        server.register_inbox_source(headers.get("x-admin-token").and_then(|v| v.to_str().ok()), &body)
+    }
+
+    async fn receive_inbox_event(State(server): State<Arc<ApiServer>>, Path(source): Path<String>, headers: HeaderMap, body: String) -> ApiResponse {
+        server.receive_inbox_event(&source, api_key_header(&headers).as_deref(), &body, chrono::Utc::now().timestamp())
+    }
+
+    async fn register_hook(State(server): State<Arc<ApiServer>>, Extension(key): Extension<APIKey>, body: String) -> ApiResponse {
+        server.register_hook(&key, &body)
+    }
+
+    async fn payload_schema(State(server): State<Arc<ApiServer>>, Path(version): Path<u32>) -> ApiResponse {
+        server.payload_schema(version)
+    }
+
+    async fn query_observations(State(server): State<Arc<ApiServer>>, Extension(key): Extension<APIKey>, Query(query): Query<ObservationQuery>) -> ApiResponse {
+        server.query_observations(&key, &query)
+    }
+
+    async fn submit_intervention(State(server): State<Arc<ApiServer>>, Extension(key): Extension<APIKey>, body: String) -> ApiResponse {
+        server.submit_intervention(&key, &body)
+    }
+
+    async fn handle_approvals(State(server): State<Arc<ApiServer>>, Extension(key): Extension<APIKey>, body: String) -> ApiResponse {
+        server.handle_approvals(&key, &body, chrono::Utc::now().timestamp())
+    }
+
+    /// Build the HTTP router; everything but key and inbox registration, schemas and inbox posts goes through API-key auth
+    /// Inbox posts carry their source's own bearer token
+    pub fn router(server: Arc<ApiServer>) -> Router {
+        let authenticated = Router::new()
+            .route("/v1/hooks", post(register_hook))
+            .route("/v1/observations", get(query_observations))
+            .route("/v1/interventions", post(submit_intervention))
+            .route("/v1/approvals", post(handle_approvals))
+            .route_layer(middleware::from_fn_with_state(server.clone(), require_api_key));
+        Router::new()
+            .route("/v1/keys", post(register_key))
+            .route("/v1/schemas/:version", get(payload_schema))
+            .route("/v1/inbox/sources", post(register_inbox_source))
+            .route("/inbox/:source", post(receive_inbox_event))
+            .merge(authenticated)
+            .with_state(server)
+    }
+
+    pub async fn serve(server: Arc<ApiServer>) -> Result<(), String> {
+        let addr = server.config.bind_addr.clone();
+        let listener = tokio::net::TcpListener::bind(&addr).await.map_err(|e| format!("Failed to bind {}: {}", addr, e))?;
+        info!("ApiServer::serve: Listening on {}", addr);
+        axum::serve(listener, router(server)).await.map_err(|e| format!("API server failed: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn server() -> ApiServer {
+        ApiServer::new(ApiServerConfig::new("admin-secret".to_string()), DeveloperAPIManager::new())
+    }
+
+    fn observation(id: &str, timestamp: i64) -> Observation {
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Focus mode".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp,
+            project: None,
+        }
+    }
+
+    fn consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(DEVELOPER_API_CAPABILITY.to_string(), "Share observations with developer API keys".to_string());
+        consent.grant_consent(DEVELOPER_API_CAPABILITY).unwrap();
+        consent
+    }
+
+    fn inbox_consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(crate::inbox::WEBHOOK_INBOX_CAPABILITY.to_string(), "Accept CI and ticketing events".to_string());
+        consent.grant_consent(crate::inbox::WEBHOOK_INBOX_CAPABILITY).unwrap();
+        consent
+    }
+
+    fn issue_key(server: &ApiServer, permissions: &[APIPermission]) -> APIKey {
+        let body = serde_json::to_string(&KeyRequest { developer_id: "dev_001".to_string(), permissions: permissions.to_vec() }).unwrap();
+        let response = server.register_key(Some("admin-secret"), &body);
+        assert_eq!(response.status, 201);
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn test_key_registration_requires_admin_token() {
+        let server = server();
+        let body = r#"{"developer_id":"dev_001","permissions":["ReadObservations"]}"#;
+        assert_eq!(server.register_key(None, body).status, 401);
+        assert_eq!(server.register_key(Some("admin-secreT"), body).status, 401);
+        assert_eq!(server.register_key(Some("admin-secret"), "{}").status, 400);
+
+        let first = issue_key(&server, &[APIPermission::ReadObservations]);
+        let second = issue_key(&server, &[APIPermission::ReadObservations]);
+        assert_ne!(first.key, second.key); // Keys issued in the same second stay distinct
+        assert!(server.authenticate(Some(&first.key), 0).is_ok());
+        assert_eq!(server.authenticate(Some("athenos_forged"), 0).unwrap_err().status, 401);
+        assert_eq!(server.authenticate(None, 0).unwrap_err().status, 401);
+
+        let unconfigured = ApiServer::new(ApiServerConfig::new(String::new()), DeveloperAPIManager::new());
+        assert_eq!(unconfigured.register_key(Some(""), body).status, 401);
+    }
+
+    #[test]
+    fn test_real_observations_require_consent() {
+        let server = server();
+        server.publish_observations(&MicroConsentManager::new(), &[observation("obs_1", 100)]);
+        let reader = issue_key(&server, &[APIPermission::ReadObservations]);
+        let observations: Vec<Observation> = serde_json::from_str(&server.query_observations(&reader, &ObservationQuery::default()).body).unwrap();
+        assert!(observations.is_empty());
+
+        server.publish_observations(&consenting(), &[observation("obs_2", 200)]);
+        let observations: Vec<Observation> = serde_json::from_str(&server.query_observations(&reader, &ObservationQuery::default()).body).unwrap();
+        assert_eq!(observations.len(), 1);
+    }
+
+    #[test]
+    fn test_observation_query_enforces_permission_and_limits() {
+        let server = server();
+        server.publish_observations(&consenting(), &[observation("obs_1", 100), observation("obs_2", 200), observation("obs_3", 300)]);
+        let reader = issue_key(&server, &[APIPermission::ReadObservations]);
+        let hooks_only = issue_key(&server, &[APIPermission::WriteHooks]);
+
+        assert_eq!(server.query_observations(&hooks_only, &ObservationQuery::default()).status, 403);
+        let response = server.query_observations(&reader, &ObservationQuery { since: Some(150), limit: Some(1) });
+        let observations: Vec<Observation> = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(observations.len(), 1);
+        assert_eq!(observations[0].id, "obs_2");
+    }
+
+    #[test]
+    fn test_sandbox_server_never_publishes_real_observations() {
+        let server = ApiServer::new(ApiServerConfig::new("admin-secret".to_string()), DeveloperAPIManager::new_sandbox());
+        let mut harness = crate::simulation::SimulationHarness::new(crate::simulation::SimulationConfig::default());
+        server.publish_observations(&consenting(), &[observation("real_1", 100)]);
+        server.publish_observations(&MicroConsentManager::new(), &harness.generate(3, 200));
+        let reader = issue_key(&server, &[APIPermission::ReadObservations]);
+
+        let observations: Vec<Observation> = serde_json::from_str(&server.query_observations(&reader, &ObservationQuery::default()).body).unwrap();
+        assert_eq!(observations.len(), 3);
+        assert!(observations.iter().all(crate::simulation::is_synthetic));
+    }
+
+    #[test]
+    fn test_hooks_and_interventions_owned_by_key_developer() {
+        let server = server();
+        let key = issue_key(&server, &[APIPermission::WriteHooks, APIPermission::WriteInterventions]);
+
+        let hook = r#"{"id":"hook_1","developer_id":"someone_else","hook_type":"OnActionExecuted","callback_url":"https://dev.example.com/hook","filter":{},"active":true}"#;
+        assert_eq!(server.register_hook(&key, hook).status, 201);
+        assert_eq!(server.register_hook(&key, &hook.replace("https://", "http://")).status, 400);
+        assert_eq!(server.register_hook(&key, &hook.replace(r#""active":true"#, r#""active":true,"schema_versions":[9]"#)).status, 400);
+        assert_eq!(server.payload_schema(2).status, 200);
+        assert_eq!(server.payload_schema(1).status, 404);
+        assert_eq!(server.with_manager(|m| m.get_developer_hooks("dev_001").len()), 1);
+
+        let intervention = serde_json::json!({
+            "id": "guard_1",
+            "developer_id": "someone_else",
+            "intervention_type": "focus_guard",
+            "action": observation("x", 0).action,
+            "conditions": { "context_switch_count": 10.0 },
+        });
+        let response = server.submit_intervention(&key, &intervention.to_string());
+        assert_eq!(response.status, 201);
+        assert!(response.body.contains(r#""developer_id":"dev_001""#));
+        assert_eq!(server.submit_intervention(&key, "not json").status, 400);
+    }
+
+    #[test]
+    fn test_approvals_route_decides_queue_items() {
+        let mut queue = ApprovalQueue::new();
+        let mut escalated = observation("obs_1", 0);
+        escalated.action.risk = RiskCategory::High;
+        let id = queue.enqueue_escalated(&escalated, "High risk".to_string(), 0);
+        let server = server().with_approval_queue(queue);
+        let approver = issue_key(&server, &[APIPermission::ManageApprovals]);
+        let reader = issue_key(&server, &[APIPermission::ReadObservations]);
+
+        assert_eq!(server.handle_approvals(&reader, r#"{"op":"list"}"#, 0).status, 403);
+        assert_eq!(server.handle_approvals(&approver, "{}", 0).status, 400);
+        let listed = server.handle_approvals(&approver, r#"{"op":"list"}"#, 0);
+        assert!(listed.status == 200 && listed.body.contains(&id));
+        assert_eq!(server.handle_approvals(&approver, &format!(r#"{{"op":"approve","id":"{}"}}"#, id), 0).status, 200);
+        assert_eq!(server.handle_approvals(&approver, r#"{"op":"approve","id":"missing"}"#, 0).status, 400);
+
+        // An API key's approval is recorded but does not stand in for the user
+        let pending = server.with_approvals(|queue| queue.list(&Default::default(), 0).into_iter().cloned().collect::<Vec<_>>());
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].approvals, vec![(approver.developer_id.clone(), ApproverRole::Integration)]);
+        assert_eq!(server.handle_approvals(&approver, &format!(r#"{{"op":"reject","id":"{}"}}"#, id), 0).status, 200);
+        assert!(server.with_approvals(|queue| queue.list(&Default::default(), 0).is_empty()));
+    }
+
+    #[test]
+    fn test_inbox_route_feeds_external_load() {
+        let body = r#"{"event": {"event_type": "incident.triggered", "data": {"urgency": "high"}}}"#;
+        assert_eq!(server().receive_inbox_event("pagerduty", Some("token"), body, 0).status, 403);
+
+        let mut inbox = WebhookInbox::default();
+        inbox.apply_consent(&inbox_consenting());
+        let server = server().with_webhook_inbox(inbox);
+        assert_eq!(server.register_inbox_source(None, r#"{"source":"pager_duty"}"#).status, 401);
+        let registered = server.register_inbox_source(Some("admin-secret"), r#"{"source":"pager_duty"}"#);
+        assert_eq!(registered.status, 201);
+        let registration: InboxSourceRegistration = serde_json::from_str(&registered.body).unwrap();
+        assert_eq!(registration.path, "/inbox/pagerduty");
+
+        assert_eq!(server.receive_inbox_event("email", Some(&registration.token), body, 0).status, 404);
+        assert_eq!(server.receive_inbox_event("pagerduty", None, body, 0).status, 401);
+        assert_eq!(server.receive_inbox_event("pagerduty", Some(&registration.token), "not json", 0).status, 400);
+        assert_eq!(server.receive_inbox_event("pagerduty", Some(&registration.token), body, 0).status, 202);
+        assert_eq!(server.receive_inbox_event("pagerduty", Some(&registration.token), body, 10).status, 202);
+        assert_eq!(server.external_load(10), 1.0);
+    }
+}
@@ -0,0 +1,618 @@
+/// Phase: D | Step: 9 | Source: Athenos_AI_Strategy.md#L140
+/// Webhook Delivery Worker
+/// Fires ObservationHooks: filter matching, async POST with retries, exponential backoff and a dead-letter queue
+
+use super::{HookType, ObservationHook};
+use crate::consent::MicroConsentManager;
+use crate::event_schema::{self, ConsentScope, EventData, EventEnvelope, EventProvenance, OutcomeV2};
+use crate::project_context::PROJECT_CONTEXT_CAPABILITY;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{info, warn};
+
+/// Permission under which hook deliveries are shared, reported in the payload's consent scope
+pub const HOOK_CONSENT_CAPABILITY: &str = "developer_api.hooks";
+
+/// Permission under which the raw step sequence of an observation is included in hook payloads
+pub const HOOK_STEPS_CAPABILITY: &str = "developer_api.hooks.steps";
+
+/// Event that can fire observation hooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HookEvent {
+    PatternDetected { pattern: PatternType, observation: Observation },
+    ActionExecuted { action_id: String, observation_id: String, action_type: ActionType, success: bool },
+    OutcomeRecorded { outcome: Outcome },
+}
+
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+impl HookEvent {
+    /// Hook type this event fires
+    pub fn hook_type(&self) -> HookType {
+        match self {
+            HookEvent::PatternDetected { .. } => HookType::OnPatternDetected,
+            HookEvent::ActionExecuted { .. } => HookType::OnActionExecuted,
+            HookEvent::OutcomeRecorded { .. } => HookType::OnOutcomeRecorded,
+        }
+    }
+
+    /// Attributes hook filters are matched against
+    pub fn attributes(&self) -> HashMap<String, String> {
+        let mut attributes = HashMap::new();
+        match self {
+            HookEvent::PatternDetected { pattern, observation } => {
+                attributes.insert("pattern".to_string(), label(pattern));
+                attributes.insert("profile".to_string(), label(&observation.profile));
+                attributes.insert("intent".to_string(), label(&observation.intent));
+                attributes.insert("source".to_string(), observation.source.clone());
+            }
+            HookEvent::ActionExecuted { action_type, success, .. } => {
+                attributes.insert("action_type".to_string(), label(action_type));
+                attributes.insert("success".to_string(), success.to_string());
+            }
+            HookEvent::OutcomeRecorded { outcome } => {
+                attributes.insert("accepted".to_string(), outcome.accepted.to_string());
+                attributes.insert("ignored".to_string(), outcome.ignored.to_string());
+                attributes.insert("modified".to_string(), outcome.modified.to_string());
+            }
+        }
+        attributes
+    }
+
+    /// Subsystem the event originated from
+    pub fn source(&self) -> &str {
+        match self {
+            HookEvent::PatternDetected { observation, .. } => &observation.source,
+            HookEvent::ActionExecuted { .. } => "auto_action",
+            HookEvent::OutcomeRecorded { .. } => "outcome_tracker",
+        }
+    }
+
+    /// Body in the stable external schema
+    pub fn to_event_data(&self) -> EventData {
+        match self {
+            HookEvent::PatternDetected { pattern, observation } => EventData::pattern_detected(pattern, observation),
+            HookEvent::ActionExecuted { action_id, observation_id, action_type, success } => EventData::ActionExecuted {
+                action_id: action_id.clone(),
+                observation_id: observation_id.clone(),
+                action_type: label(action_type),
+                success: *success,
+            },
+            HookEvent::OutcomeRecorded { outcome } => EventData::OutcomeRecorded { outcome: OutcomeV2::from(outcome) },
+        }
+    }
+}
+
+impl ObservationHook {
+    /// Active hook of the event's type whose filter entries all equal the event attributes
+    pub fn matches(&self, event: &HookEvent) -> bool {
+        if !self.active || self.hook_type != event.hook_type() {
+            return false;
+        }
+        let attributes = event.attributes();
+        self.filter.iter().all(|(key, value)| attributes.get(key) == Some(value))
+    }
+
+    /// Payload schema version negotiated from the versions the hook accepts
+    pub fn schema_version(&self) -> Result<u32, String> {
+        event_schema::negotiate_schema(&self.schema_versions)
+    }
+}
+
+/// Body POSTed to a hook callback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookPayload {
+    pub hook_id: String,
+    pub occurred_at: i64,
+    #[serde(flatten)]
+    pub event: HookEvent,
+}
+
+/// Body POSTed to a hook that negotiated schema v2 or later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedHookPayload {
+    pub hook_id: String,
+    #[serde(flatten)]
+    pub envelope: EventEnvelope,
+}
+
+/// Serialize an event in the hook's negotiated schema; nothing is shared without HOOK_CONSENT_CAPABILITY
+pub fn render_payload(hook: &ObservationHook, event: &HookEvent, consent: &MicroConsentManager, now: i64) -> Result<String, String> {
+    if !consent.has_consent(HOOK_CONSENT_CAPABILITY) {
+        return Err(format!("{} consent not granted", HOOK_CONSENT_CAPABILITY));
+    }
+    // The payload claims only capabilities the ledger grants; project context without consent is stripped
+    let mut event = event.clone();
+    let mut capabilities = vec![HOOK_CONSENT_CAPABILITY.to_string()];
+    if let HookEvent::PatternDetected { observation, .. } = &mut event {
+        // Internal outcome estimates never leave the daemon; the raw step sequence needs its own grant
+        observation.expected_outcome.clear();
+        if consent.has_consent(HOOK_STEPS_CAPABILITY) {
+            capabilities.push(HOOK_STEPS_CAPABILITY.to_string());
+        } else {
+            observation.observation.clear();
+        }
+        if observation.project.is_some() {
+            if consent.has_consent(PROJECT_CONTEXT_CAPABILITY) {
+                capabilities.push(PROJECT_CONTEXT_CAPABILITY.to_string());
+            } else {
+                observation.project = None;
+            }
+        }
+    }
+    let payload = match hook.schema_version()? {
+        event_schema::SCHEMA_V1 => serde_json::to_string(&HookPayload { hook_id: hook.id.clone(), occurred_at: now, event }),
+        _ => {
+            let consent = ConsentScope { recipient: hook.developer_id.clone(), capabilities };
+            let envelope = EventEnvelope::new(event.to_event_data(), now, EventProvenance::new(event.source()), consent);
+            serde_json::to_string(&VersionedHookPayload { hook_id: hook.id.clone(), envelope })
+        }
+    };
+    payload.map_err(|e| format!("Failed to encode payload for {}: {}", hook.id, e))
+}
+
+/// Deliveries for every matching hook with an https callback
+/// Hooks without a schema version in common with the daemon are skipped
+pub fn jobs_for<'a>(hooks: impl IntoIterator<Item = &'a ObservationHook>, event: &HookEvent, consent: &MicroConsentManager, now: i64) -> Vec<WebhookJob> {
+    hooks
+        .into_iter()
+        .filter(|hook| hook.matches(event))
+        .filter_map(|hook| {
+            let url = hook.callback_url.clone().filter(|url| url.starts_with("https://"))?;
+            match render_payload(hook, event, consent, now) {
+                Ok(payload) => Some(WebhookJob { hook_id: hook.id.clone(), url, payload }),
+                Err(e) => {
+                    warn!("jobs_for: Skipping hook {}: {}", hook.id, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// HTTP transport used by the worker
+pub trait WebhookTransport: Send + Sync + 'static {
+    /// POST a JSON body, returning the response status
+    fn post(&self, url: &str, payload: &str) -> Result<u16, String>;
+}
+
+/// Create the HTTPS transport hook deliveries are POSTed through
+#[cfg(feature = "webhooks")]
+pub fn https_transport() -> Result<Arc<dyn WebhookTransport>, String> {
+    Ok(Arc::new(http::HttpsTransport::new()))
+}
+
+#[cfg(not(feature = "webhooks"))]
+pub fn https_transport() -> Result<Arc<dyn WebhookTransport>, String> {
+    Err("Cannot POST hook deliveries: built without the webhooks feature".to_string())
+}
+
+#[cfg(feature = "webhooks")]
+mod http {
+    use super::WebhookTransport;
+    use std::time::Duration;
+    use tracing::info;
+
+    /// Blocking HTTPS client; error statuses are returned so the worker can retry them
+    pub struct HttpsTransport {
+        agent: ureq::Agent,
+    }
+
+    impl HttpsTransport {
+        pub fn new() -> Self {
+            Self { agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build() }
+        }
+    }
+
+    impl WebhookTransport for HttpsTransport {
+        fn post(&self, url: &str, payload: &str) -> Result<u16, String> {
+            if !url.starts_with("https://") {
+                return Err(format!("Refusing to POST hook delivery over plain HTTP: {}", url));
+            }
+            info!("HttpsTransport::post: POST {} ({} bytes)", url, payload.len());
+            match self.agent.post(url).set("Content-Type", "application/json").send_string(payload) {
+                Ok(response) => Ok(response.status()),
+                Err(ureq::Error::Status(status, _)) => Ok(status),
+                Err(e) => Err(format!("POST {} failed: {}", url, e)),
+            }
+        }
+    }
+}
+
+/// Retry schedule for failed deliveries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (1-based), capped at `max_backoff_ms`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.initial_backoff_ms as f64 * self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        Duration::from_millis(delay.min(self.max_backoff_ms as f64) as u64)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 60_000,
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// Delivery queued for the worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookJob {
+    pub hook_id: String,
+    pub url: String,
+    pub payload: String,
+}
+
+/// Delivery that exhausted its retries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub job: WebhookJob,
+    pub attempts: u32,
+    pub last_error: String,
+    pub failed_at: i64,
+}
+
+/// Delivery counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeliveryStats {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+#[derive(Default)]
+struct WorkerState {
+    dead_letters: Vec<DeadLetter>,
+    stats: DeliveryStats,
+    in_flight: usize, // Queued deliveries not yet delivered or dead-lettered
+    path: Option<PathBuf>, // Dead letters are persisted here when set
+}
+
+impl WorkerState {
+    /// Persist the dead letters so they survive a restart
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        let saved = serde_json::to_string(&self.dead_letters).map_err(|e| e.to_string()).and_then(|json| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let tmp = path.with_extension("tmp");
+            std::fs::write(&tmp, json).and_then(|_| std::fs::rename(&tmp, path)).map_err(|e| e.to_string())
+        });
+        if let Err(e) = saved {
+            warn!("WebhookDispatcher: Failed to persist dead letters to {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Async webhook delivery worker; each delivery retries independently so one slow endpoint never blocks others
+pub struct WebhookDispatcher {
+    sender: mpsc::UnboundedSender<WebhookJob>,
+    state: Arc<Mutex<WorkerState>>,
+    worker: JoinHandle<()>,
+}
+
+impl WebhookDispatcher {
+    /// Spawn the worker on the runtime
+    pub fn spawn(runtime: &Handle, transport: Arc<dyn WebhookTransport>, policy: RetryPolicy) -> Self {
+        Self::spawn_with_state(runtime, transport, policy, WorkerState::default())
+    }
+
+    /// Spawn the worker with dead letters persisted at `path`, restoring any left by a previous run
+    pub fn open(runtime: &Handle, transport: Arc<dyn WebhookTransport>, policy: RetryPolicy, path: PathBuf) -> Result<Self, String> {
+        info!("WebhookDispatcher::open: Opening dead letters at {:?}", path);
+        let dead_letters = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid dead letters: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read dead letters: {}", e)),
+        };
+        let state = WorkerState { dead_letters, path: Some(path), ..WorkerState::default() };
+        Ok(Self::spawn_with_state(runtime, transport, policy, state))
+    }
+
+    fn spawn_with_state(runtime: &Handle, transport: Arc<dyn WebhookTransport>, policy: RetryPolicy, state: WorkerState) -> Self {
+        info!("WebhookDispatcher::spawn: Starting webhook worker (max_attempts={}, {} dead letters)", policy.max_attempts, state.dead_letters.len());
+        let (sender, mut receiver) = mpsc::unbounded_channel::<WebhookJob>();
+        let state = Arc::new(Mutex::new(state));
+        let worker_state = state.clone();
+        let worker = runtime.spawn(async move {
+            let mut deliveries = JoinSet::new();
+            while let Some(job) = receiver.recv().await {
+                deliveries.spawn(deliver(job, transport.clone(), policy.clone(), worker_state.clone()));
+                while deliveries.try_join_next().is_some() {}
+            }
+            while deliveries.join_next().await.is_some() {}
+        });
+        Self { sender, state, worker }
+    }
+
+    /// Queue POSTs of an event to every matching hook; returns the number queued
+    pub fn dispatch<'a>(&self, hooks: impl IntoIterator<Item = &'a ObservationHook>, event: &HookEvent, consent: &MicroConsentManager, now: i64) -> usize {
+        jobs_for(hooks, event, consent, now).into_iter().filter(|job| self.enqueue(job.clone())).count()
+    }
+
+    /// Queue a prepared delivery
+    pub fn enqueue(&self, job: WebhookJob) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let queued = self.sender.send(job).is_ok();
+        if queued {
+            state.in_flight += 1;
+        }
+        queued
+    }
+
+    /// Re-queue every dead letter (e.g. after an endpoint outage); returns the number re-queued
+    pub fn replay_dead_letters(&self) -> usize {
+        let letters = {
+            let mut state = self.state.lock().unwrap();
+            let letters = std::mem::take(&mut state.dead_letters);
+            state.save();
+            letters
+        };
+        info!("WebhookDispatcher::replay_dead_letters: Re-queueing {} deliveries", letters.len());
+        letters.into_iter().filter(|letter| self.enqueue(letter.job.clone())).count()
+    }
+
+    /// Deliveries that exhausted their retries
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.state.lock().unwrap().dead_letters.clone()
+    }
+
+    /// Delivery counters so far
+    pub fn stats(&self) -> DeliveryStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    /// Wait until every queued delivery was delivered or dead-lettered
+    pub async fn wait_idle(&self) {
+        while self.state.lock().unwrap().in_flight > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Stop accepting jobs and wait for queued deliveries to finish
+    pub async fn shutdown(self) -> DeliveryStats {
+        info!("WebhookDispatcher::shutdown: Draining webhook worker");
+        drop(self.sender);
+        if let Err(e) = self.worker.await {
+            warn!("WebhookDispatcher::shutdown: Worker failed: {}", e);
+        }
+        let stats = self.state.lock().unwrap().stats.clone();
+        stats
+    }
+}
+
+async fn deliver(job: WebhookJob, transport: Arc<dyn WebhookTransport>, policy: RetryPolicy, state: Arc<Mutex<WorkerState>>) {
+    let max_attempts = policy.max_attempts.max(1);
+    let mut last_error = String::new();
+    for attempt in 1..=max_attempts {
+        let result = {
+            let transport = transport.clone();
+            let (url, payload) = (job.url.clone(), job.payload.clone());
+            tokio::task::spawn_blocking(move || transport.post(&url, &payload)).await
+        };
+        match result {
+            Ok(Ok(status)) if (200..300).contains(&status) => {
+                info!("WebhookDispatcher: {} delivered to {} (attempt {})", job.hook_id, job.url, attempt);
+                let mut state = state.lock().unwrap();
+                state.stats.delivered += 1;
+                state.in_flight -= 1;
+                return;
+            }
+            Ok(Ok(status)) => last_error = format!("HTTP {}", status),
+            Ok(Err(e)) => last_error = e,
+            Err(e) => last_error = format!("Transport task failed: {}", e),
+        }
+        if attempt < max_attempts {
+            warn!("WebhookDispatcher: {} to {} failed ({}), retrying", job.hook_id, job.url, last_error);
+            state.lock().unwrap().stats.retried += 1;
+            tokio::time::sleep(policy.backoff(attempt)).await;
+        }
+    }
+    warn!("WebhookDispatcher: {} to {} dead-lettered after {} attempts", job.hook_id, job.url, max_attempts);
+    let mut state = state.lock().unwrap();
+    state.stats.dead_lettered += 1;
+    state.in_flight -= 1;
+    state.dead_letters.push(DeadLetter { job, attempts: max_attempts, last_error, failed_at: chrono::Utc::now().timestamp() });
+    state.save();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` posts, then accepts
+    struct FlakyTransport {
+        failures: u32,
+        calls: AtomicU32,
+        received: Mutex<Vec<String>>,
+    }
+
+    impl WebhookTransport for FlakyTransport {
+        fn post(&self, url: &str, payload: &str) -> Result<u16, String> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Ok(503);
+            }
+            self.received.lock().unwrap().push(format!("{} {}", url, payload));
+            Ok(200)
+        }
+    }
+
+    fn flaky(failures: u32) -> Arc<FlakyTransport> {
+        Arc::new(FlakyTransport { failures, calls: AtomicU32::new(0), received: Mutex::new(Vec::new()) })
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy { max_attempts, initial_backoff_ms: 1, max_backoff_ms: 5, multiplier: 2.0 }
+    }
+
+    fn hook(id: &str, hook_type: HookType, filter: &[(&str, &str)]) -> ObservationHook {
+        ObservationHook {
+            id: id.to_string(),
+            developer_id: "dev_001".to_string(),
+            hook_type,
+            callback_url: Some(format!("https://dev.example.com/{}", id)),
+            filter: filter.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            active: true,
+            schema_versions: Vec::new(),
+        }
+    }
+
+    fn executed(success: bool) -> HookEvent {
+        HookEvent::ActionExecuted {
+            action_id: "action_1".to_string(),
+            observation_id: "obs_1".to_string(),
+            action_type: ActionType::FocusMode,
+            success,
+        }
+    }
+
+    fn consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(HOOK_CONSENT_CAPABILITY.to_string(), "Share events with developer webhooks".to_string());
+        consent.grant_consent(HOOK_CONSENT_CAPABILITY).unwrap();
+        consent
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_millis(2_000));
+        assert_eq!(policy.backoff(20), Duration::from_millis(60_000));
+    }
+
+    #[test]
+    fn test_filters_select_matching_hooks() {
+        let hooks = [
+            hook("all_actions", HookType::OnActionExecuted, &[]),
+            hook("focus_failures", HookType::OnActionExecuted, &[("action_type", "focus_mode"), ("success", "false")]),
+            hook("patterns", HookType::OnPatternDetected, &[]),
+        ];
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let transport = flaky(0);
+        let dispatcher = WebhookDispatcher::spawn(runtime.handle(), transport.clone(), fast_policy(3));
+
+        assert_eq!(dispatcher.dispatch(&hooks, &executed(true), &consenting(), 100), 1);
+        assert_eq!(dispatcher.dispatch(&hooks, &executed(false), &consenting(), 200), 2);
+        let stats = runtime.block_on(dispatcher.shutdown());
+        assert_eq!(stats.delivered, 3);
+
+        let received = transport.received.lock().unwrap();
+        let payloads: Vec<HookPayload> = received.iter().map(|r| serde_json::from_str(r.split_once(' ').unwrap().1).unwrap()).collect();
+        assert_eq!(payloads.iter().filter(|p| p.hook_id == "all_actions").count(), 2);
+        assert!(received.iter().any(|r| r.starts_with("https://dev.example.com/focus_failures ") && r.contains(r#""event":"action_executed""#)));
+    }
+
+    #[test]
+    fn test_retries_then_dead_letters_and_replays() {
+        let hooks = [hook("outcomes", HookType::OnOutcomeRecorded, &[("accepted", "true")])];
+        let event = HookEvent::OutcomeRecorded {
+            outcome: Outcome {
+                observation_id: "obs_1".to_string(),
+                accepted: true,
+                ignored: false,
+                modified: false,
+                time_saved_minutes: Some(4.0),
+                error_rate_change: None,
+                timestamp: 100,
+            },
+        };
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        // Two failures then success within three attempts
+        let transport = flaky(2);
+        let dispatcher = WebhookDispatcher::spawn(runtime.handle(), transport.clone(), fast_policy(3));
+        dispatcher.dispatch(&hooks, &event, &consenting(), 100);
+        runtime.block_on(dispatcher.wait_idle());
+        assert_eq!(dispatcher.stats().delivered, 1);
+        assert_eq!(dispatcher.stats().retried, 2);
+
+        // Endpoint down for longer than the retry budget
+        let transport = flaky(3);
+        let dispatcher = WebhookDispatcher::spawn(runtime.handle(), transport.clone(), fast_policy(2));
+        dispatcher.dispatch(&hooks, &event, &consenting(), 100);
+        runtime.block_on(dispatcher.wait_idle());
+        let dead = dispatcher.dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_error, "HTTP 503");
+
+        assert_eq!(dispatcher.replay_dead_letters(), 1);
+        runtime.block_on(dispatcher.wait_idle());
+        assert!(dispatcher.dead_letters().is_empty());
+        assert_eq!(dispatcher.stats().delivered, 1);
+        assert_eq!(transport.received.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_dead_letters_survive_restart() {
+        let path = std::env::temp_dir().join(format!("athenos_dead_letters_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let hooks = [hook("actions", HookType::OnActionExecuted, &[])];
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+
+        let dispatcher = WebhookDispatcher::open(runtime.handle(), flaky(u32::MAX), fast_policy(1), path.clone()).unwrap();
+        dispatcher.dispatch(&hooks, &executed(true), &consenting(), 100);
+        runtime.block_on(dispatcher.shutdown());
+
+        let transport = flaky(0);
+        let dispatcher = WebhookDispatcher::open(runtime.handle(), transport.clone(), fast_policy(1), path.clone()).unwrap();
+        assert_eq!(dispatcher.dead_letters().len(), 1);
+        assert_eq!(dispatcher.replay_dead_letters(), 1);
+        runtime.block_on(dispatcher.wait_idle());
+        assert_eq!(transport.received.lock().unwrap().len(), 1);
+        assert!(WebhookDispatcher::open(runtime.handle(), flaky(0), fast_policy(1), path.clone()).unwrap().dead_letters().is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_payload_schema_negotiated_per_hook() {
+        let legacy = hook("legacy", HookType::OnActionExecuted, &[]);
+        let mut current = hook("current", HookType::OnActionExecuted, &[]);
+        current.schema_versions = vec![1, 2];
+        let mut future = hook("future", HookType::OnActionExecuted, &[]);
+        future.schema_versions = vec![9];
+
+        assert!(jobs_for([&legacy, &current, &future], &executed(true), &MicroConsentManager::new(), 100).is_empty());
+        let jobs = jobs_for([&legacy, &current, &future], &executed(true), &consenting(), 100);
+        assert_eq!(jobs.len(), 2);
+        let legacy_payload: serde_json::Value = serde_json::from_str(&jobs[0].payload).unwrap();
+        assert_eq!(legacy_payload["event"], "action_executed");
+        assert_eq!(legacy_payload["action_type"], "focus_mode");
+
+        let envelope: VersionedHookPayload = serde_json::from_str(&jobs[1].payload).unwrap();
+        assert_eq!(envelope.hook_id, "current");
+        assert_eq!(envelope.envelope.schema_version, event_schema::SCHEMA_V2);
+        assert_eq!(envelope.envelope.consent.recipient, "dev_001");
+        assert_eq!(envelope.envelope.provenance.source, "auto_action");
+        assert!(matches!(envelope.envelope.data, EventData::ActionExecuted { ref action_type, success: true, .. } if action_type == "focus_mode"));
+    }
+}
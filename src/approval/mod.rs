@@ -0,0 +1,637 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L111
+/// Unified Approval Queue
+/// Single queue for shortcut proposals, schedule suggestions and escalated actions with batch operations
+
+use crate::types::*;
+use crate::attention::{AttentionService, InterruptionPriority};
+use crate::shortcut::{ShortcutGenerator, ShortcutPreview, ShortcutProposal};
+use crate::scheduling::{CalendarNegotiationAgent, ScheduleSuggestion};
+use crate::auto_action::AutoActionSynthesizer;
+use crate::enterprise::ApproverRole;
+use crate::plugin::wasm::PluginIntervention;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Module an approval item originates from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalSource {
+    Shortcut,
+    ScheduleSuggestion,
+    EscalatedAction,
+    PluginIntervention,
+}
+
+/// Queue item status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueItemStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Deferred,
+}
+
+/// Source-specific data needed to apply a decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ApprovalPayload {
+    Shortcut { shortcut_id: String },
+    Schedule { suggestion: ScheduleSuggestion },
+    EscalatedAction { observation: Box<Observation> },
+}
+
+/// Item awaiting a decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalItem {
+    pub id: String,
+    pub source: ApprovalSource,
+    pub title: String,
+    pub reason: String,
+    pub risk: RiskCategory,
+    pub confidence: Confidence,
+    pub payload: ApprovalPayload,
+    pub status: QueueItemStatus,
+    pub created_at: i64,
+    pub deferred_until: Option<i64>,
+    pub decided_by: Option<(String, ApproverRole)>,
+    #[serde(default)]
+    pub approvals: Vec<(String, ApproverRole)>, // Approvals recorded while a two-person quorum is incomplete
+    pub preview: Option<ShortcutPreview>,
+}
+
+/// Listing filter (all fields optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalFilter {
+    pub source: Option<ApprovalSource>,
+    pub min_risk: Option<RiskCategory>,
+    pub max_risk: Option<RiskCategory>,
+}
+
+impl ApprovalFilter {
+    fn matches(&self, item: &ApprovalItem) -> bool {
+        self.source.map(|s| s == item.source).unwrap_or(true)
+            && self.min_risk.as_ref().map(|r| item.risk >= *r).unwrap_or(true)
+            && self.max_risk.as_ref().map(|r| item.risk <= *r).unwrap_or(true)
+    }
+}
+
+/// Decision applied by a batch operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum BatchDecision {
+    Approve,
+    Reject,
+    Defer { until: i64 },
+}
+
+/// Per-item result of a batch operation or applied decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemResult {
+    pub id: String,
+    pub error: Option<String>,
+}
+
+/// Queue command (JSON body for IPC and the HTTP API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ApprovalCommand {
+    List { #[serde(default)] filter: ApprovalFilter },
+    Approve { id: String },
+    Reject { id: String },
+    Defer { id: String, until: i64 },
+    Batch { ids: Vec<String>, decision: BatchDecision },
+}
+
+/// Queue command response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ApprovalResponse {
+    Items { items: Vec<ApprovalItem> },
+    Results { results: Vec<ItemResult> },
+    Error { message: String },
+}
+
+/// Unified approval queue
+pub struct ApprovalQueue {
+    items: HashMap<String, ApprovalItem>,
+}
+
+impl ApprovalQueue {
+    /// Create empty approval queue
+    pub fn new() -> Self {
+        info!("ApprovalQueue::new: Creating approval queue");
+        Self {
+            items: HashMap::new(),
+        }
+    }
+
+    fn enqueue(&mut self, item: ApprovalItem) -> String {
+        let id = item.id.clone();
+        if !self.items.contains_key(&id) {
+            info!("ApprovalQueue::enqueue: Queuing {} ({:?})", id, item.source);
+            self.items.insert(id.clone(), item);
+        }
+        id
+    }
+
+    /// Queue a shortcut proposal (re-queuing the same proposal is a no-op)
+    pub fn enqueue_shortcut(&mut self, proposal: &ShortcutProposal) -> String {
+        self.enqueue(ApprovalItem {
+            id: format!("shortcut:{}", proposal.id),
+            source: ApprovalSource::Shortcut,
+            title: proposal.description.clone(),
+            reason: format!("Expected to save {:.1} minutes", proposal.expected_time_saved_min),
+            risk: proposal.risk.clone(),
+            confidence: proposal.confidence.clone(),
+            payload: ApprovalPayload::Shortcut { shortcut_id: proposal.id.clone() },
+            status: QueueItemStatus::Pending,
+            created_at: proposal.created_at,
+            deferred_until: None,
+            decided_by: None,
+            approvals: Vec::new(),
+            preview: None,
+        })
+    }
+
+    /// Queue a schedule suggestion
+    pub fn enqueue_schedule(&mut self, suggestion: &ScheduleSuggestion, now: i64) -> String {
+        self.enqueue(ApprovalItem {
+            id: format!("schedule:{}:{}", suggestion.event_id, suggestion.suggested_start),
+            source: ApprovalSource::ScheduleSuggestion,
+            title: suggestion.reason.clone(),
+            reason: suggestion.expected_benefit.clone(),
+            risk: RiskCategory::Low,
+            confidence: suggestion.provenance.confidence.clone(),
+            payload: ApprovalPayload::Schedule { suggestion: suggestion.clone() },
+            status: QueueItemStatus::Pending,
+            created_at: now,
+            deferred_until: None,
+            decided_by: None,
+            approvals: Vec::new(),
+            preview: None,
+        })
+    }
+
+    /// Queue an action escalated for human approval
+    pub fn enqueue_escalated(&mut self, observation: &Observation, reason: String, now: i64) -> String {
+        self.enqueue(ApprovalItem {
+            id: format!("action:{}", observation.id),
+            source: ApprovalSource::EscalatedAction,
+            title: observation.action.description.clone(),
+            reason,
+            risk: observation.action.risk.clone(),
+            confidence: observation.action.confidence.clone(),
+            payload: ApprovalPayload::EscalatedAction { observation: Box::new(observation.clone()) },
+            status: QueueItemStatus::Pending,
+            created_at: now,
+            deferred_until: None,
+            decided_by: None,
+            approvals: Vec::new(),
+            preview: None,
+        })
+    }
+
+    /// Queue a plugin's proposed intervention on `observation`; approving it runs the plugin's action
+    /// Plugins never act directly, so every intervention waits here whatever its risk
+    pub fn enqueue_plugin_intervention(&mut self, intervention: &PluginIntervention, observation: &Observation, now: i64) -> Result<String, String> {
+        if intervention.observation_id != observation.id {
+            return Err(format!("Intervention targets {}, not {}", intervention.observation_id, observation.id));
+        }
+        let mut proposed = observation.clone();
+        proposed.id = format!("{}_{}", intervention.plugin_id, observation.id);
+        proposed.action = intervention.action.clone();
+        Ok(self.enqueue(ApprovalItem {
+            id: format!("plugin:{}:{}", intervention.plugin_id, observation.id),
+            source: ApprovalSource::PluginIntervention,
+            title: intervention.action.description.clone(),
+            reason: format!("Proposed by plugin {}: {}", intervention.plugin_id, intervention.rationale),
+            risk: intervention.action.risk.clone(),
+            confidence: intervention.action.confidence.clone(),
+            payload: ApprovalPayload::EscalatedAction { observation: Box::new(proposed) },
+            status: QueueItemStatus::Pending,
+            created_at: now,
+            deferred_until: None,
+            decided_by: None,
+            approvals: Vec::new(),
+            preview: None,
+        }))
+    }
+
+    /// Attach a dry-run preview to a shortcut item
+    pub fn attach_preview(&mut self, id: &str, preview: ShortcutPreview) -> Result<(), String> {
+        let item = self.items.get_mut(id).ok_or_else(|| format!("Approval item {} not found", id))?;
+        match &item.payload {
+            ApprovalPayload::Shortcut { shortcut_id } if *shortcut_id == preview.proposal_id => {
+                item.preview = Some(preview);
+                Ok(())
+            }
+            _ => Err(format!("Preview does not belong to approval item {}", id)),
+        }
+    }
+
+    /// Look up an item by id, whatever its status
+    pub fn get(&self, id: &str) -> Option<&ApprovalItem> {
+        self.items.get(id)
+    }
+
+    /// Items awaiting a decision, highest risk first then oldest
+    /// Deferred items reappear once their deferral has passed
+    pub fn list(&self, filter: &ApprovalFilter, now: i64) -> Vec<&ApprovalItem> {
+        let mut items: Vec<&ApprovalItem> = self.items
+            .values()
+            .filter(|item| match item.status {
+                QueueItemStatus::Pending => true,
+                QueueItemStatus::Deferred => item.deferred_until.map(|until| until <= now).unwrap_or(true),
+                _ => false,
+            })
+            .filter(|item| filter.matches(item))
+            .collect();
+        items.sort_by(|a, b| b.risk.cmp(&a.risk).then(a.created_at.cmp(&b.created_at)).then(a.id.cmp(&b.id)));
+        items
+    }
+
+    /// Items worth prompting for now given the cost of interrupting the user
+    /// Escalated and high-risk items prompt at normal priority, the rest only when interrupting is cheap
+    pub fn prompts_due(&self, attention: &AttentionService, now: i64) -> Vec<&ApprovalItem> {
+        self.list(&ApprovalFilter::default(), now)
+            .into_iter()
+            .filter(|item| {
+                let priority = if item.source == ApprovalSource::EscalatedAction || item.risk == RiskCategory::High {
+                    InterruptionPriority::Normal
+                } else {
+                    InterruptionPriority::Low
+                };
+                attention.should_interrupt_at(priority, now)
+            })
+            .collect()
+    }
+
+    fn decide(&mut self, id: &str, status: QueueItemStatus, approver_id: &str, role: ApproverRole) -> Result<(), String> {
+        let item = self.items.get_mut(id).ok_or_else(|| format!("Approval item {} not found", id))?;
+        if !matches!(item.status, QueueItemStatus::Pending | QueueItemStatus::Deferred) {
+            return Err(format!("Approval item {} already decided ({:?})", id, item.status));
+        }
+        if status == QueueItemStatus::Approved && role == ApproverRole::Integration {
+            // Recorded for the user to see; the item stays pending until a person approves it
+            info!("ApprovalQueue::decide: {} approval by integration {} recorded", id, approver_id);
+            if !item.approvals.iter().any(|(existing, _)| existing == approver_id) {
+                item.approvals.push((approver_id.to_string(), role));
+            }
+            return Ok(());
+        }
+        info!("ApprovalQueue::decide: {} -> {:?} by {}", id, status, approver_id);
+        item.status = status;
+        item.deferred_until = None;
+        item.decided_by = Some((approver_id.to_string(), role));
+        Ok(())
+    }
+
+    /// Approve an item
+    pub fn approve(&mut self, id: &str, approver_id: &str, role: ApproverRole) -> Result<(), String> {
+        self.decide(id, QueueItemStatus::Approved, approver_id, role)
+    }
+
+    /// Reject an item
+    pub fn reject(&mut self, id: &str, approver_id: &str, role: ApproverRole) -> Result<(), String> {
+        self.decide(id, QueueItemStatus::Rejected, approver_id, role)
+    }
+
+    /// Hide an item from listings until `until`
+    pub fn defer(&mut self, id: &str, until: i64) -> Result<(), String> {
+        let item = self.items.get_mut(id).ok_or_else(|| format!("Approval item {} not found", id))?;
+        if !matches!(item.status, QueueItemStatus::Pending | QueueItemStatus::Deferred) {
+            return Err(format!("Approval item {} already decided ({:?})", id, item.status));
+        }
+        info!("ApprovalQueue::defer: {} until {}", id, until);
+        item.status = QueueItemStatus::Deferred;
+        item.deferred_until = Some(until);
+        Ok(())
+    }
+
+    /// Apply one decision to many items; failures are reported per item
+    pub fn batch(&mut self, ids: &[String], decision: &BatchDecision, approver_id: &str, role: ApproverRole) -> Vec<ItemResult> {
+        info!("ApprovalQueue::batch: {:?} on {} items", decision, ids.len());
+        ids.iter()
+            .map(|id| {
+                let result = match decision {
+                    BatchDecision::Approve => self.approve(id, approver_id, role),
+                    BatchDecision::Reject => self.reject(id, approver_id, role),
+                    BatchDecision::Defer { until } => self.defer(id, *until),
+                };
+                ItemResult { id: id.clone(), error: result.err() }
+            })
+            .collect()
+    }
+
+    /// Route decided items to their owning modules, removing each once its decision has been applied
+    /// Approved escalated actions execute on the synthesizer with the recorded approver; in two-person mode an item
+    /// goes back to pending until the second approver decides. Items whose decision failed also go back to pending.
+    /// Rejected schedule suggestions and actions need no follow-up
+    pub fn apply_decisions(
+        &mut self,
+        shortcuts: &mut ShortcutGenerator,
+        calendar: &mut CalendarNegotiationAgent,
+        synthesizer: &mut AutoActionSynthesizer,
+    ) -> Vec<ItemResult> {
+        let decided: Vec<String> = self.items
+            .values()
+            .filter(|item| matches!(item.status, QueueItemStatus::Approved | QueueItemStatus::Rejected))
+            .map(|item| item.id.clone())
+            .collect();
+
+        let mut results = Vec::new();
+        for id in decided {
+            let Some(item) = self.items.get_mut(&id) else {
+                continue;
+            };
+            let approved = item.status == QueueItemStatus::Approved;
+            // Ok(true): applied, Ok(false): approval recorded but the quorum is incomplete
+            let result = match &item.payload {
+                ApprovalPayload::Shortcut { shortcut_id } if approved => shortcuts.approve_shortcut(shortcut_id).map(|_| true),
+                ApprovalPayload::Shortcut { shortcut_id } => shortcuts.reject_shortcut(shortcut_id).map(|_| true),
+                ApprovalPayload::Schedule { suggestion } if approved => {
+                    calendar.apply_suggestion(suggestion);
+                    Ok(true)
+                }
+                ApprovalPayload::EscalatedAction { observation } if approved => match &item.decided_by {
+                    Some((approver_id, role)) => synthesizer.execute_approved(observation, approver_id, *role).map(|executed| executed.is_some()),
+                    None => Err("Approval has no approver".to_string()),
+                },
+                _ => Ok(true),
+            };
+            match &result {
+                Ok(true) => {
+                    self.items.remove(&id);
+                }
+                Ok(false) => {
+                    item.approvals.extend(item.decided_by.take());
+                    item.status = QueueItemStatus::Pending;
+                }
+                Err(e) => {
+                    info!("ApprovalQueue::apply_decisions: {} reopened: {}", id, e);
+                    item.decided_by = None;
+                    item.status = QueueItemStatus::Pending;
+                }
+            }
+            results.push(ItemResult { id, error: result.err() });
+        }
+        results
+    }
+
+    /// Execute a queue command on behalf of an approver
+    pub fn handle_command(&mut self, command: ApprovalCommand, approver_id: &str, role: ApproverRole, now: i64) -> ApprovalResponse {
+        let single = |id: String, result: Result<(), String>| match result {
+            Ok(()) => ApprovalResponse::Results { results: vec![ItemResult { id, error: None }] },
+            Err(message) => ApprovalResponse::Error { message },
+        };
+        match command {
+            ApprovalCommand::List { filter } => ApprovalResponse::Items {
+                items: self.list(&filter, now).into_iter().cloned().collect(),
+            },
+            ApprovalCommand::Approve { id } => {
+                let result = self.approve(&id, approver_id, role);
+                single(id, result)
+            }
+            ApprovalCommand::Reject { id } => {
+                let result = self.reject(&id, approver_id, role);
+                single(id, result)
+            }
+            ApprovalCommand::Defer { id, until } => {
+                let result = self.defer(&id, until);
+                single(id, result)
+            }
+            ApprovalCommand::Batch { ids, decision } => ApprovalResponse::Results {
+                results: self.batch(&ids, &decision, approver_id, role),
+            },
+        }
+    }
+
+    /// JSON request/response handler shared by the IPC channel and the HTTP API
+    pub fn handle_json(&mut self, body: &str, approver_id: &str, role: ApproverRole, now: i64) -> String {
+        let response = match serde_json::from_str::<ApprovalCommand>(body) {
+            Ok(command) => self.handle_command(command, approver_id, role, now),
+            Err(e) => ApprovalResponse::Error { message: format!("Invalid approval command: {}", e) },
+        };
+        serde_json::to_string(&response)
+            .unwrap_or_else(|e| format!("{{\"status\":\"error\",\"message\":\"{}\"}}", e))
+    }
+}
+
+impl Default for ApprovalQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::SandboxRunner;
+
+    fn observation(id: &str, risk: RiskCategory) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: format!("Macro {}", id),
+                confidence: Confidence::Medium,
+                risk,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 0,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_list_filters_and_defer() {
+        let mut shortcuts = ShortcutGenerator::new();
+        let proposal = shortcuts.generate_shortcut(&observation("obs1", RiskCategory::Low)).unwrap();
+        let mut queue = ApprovalQueue::new();
+        let shortcut_id = queue.enqueue_shortcut(&proposal);
+        queue.enqueue_shortcut(&proposal);
+        queue.attach_preview(&shortcut_id, shortcuts.preview(&proposal.id, &SandboxRunner::default()).unwrap()).unwrap();
+        let action_id = queue.enqueue_escalated(&observation("obs2", RiskCategory::High), "High risk".to_string(), 10);
+
+        let all = queue.list(&ApprovalFilter::default(), 100);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, action_id); // Highest risk first
+
+        let shortcuts_only = ApprovalFilter { source: Some(ApprovalSource::Shortcut), ..ApprovalFilter::default() };
+        assert_eq!(queue.list(&shortcuts_only, 100)[0].id, shortcut_id);
+        assert_eq!(queue.list(&shortcuts_only, 100)[0].preview.as_ref().unwrap().steps.len(), 3);
+        let high_only = ApprovalFilter { min_risk: Some(RiskCategory::High), ..ApprovalFilter::default() };
+        assert_eq!(queue.list(&high_only, 100).len(), 1);
+
+        queue.defer(&action_id, 500).unwrap();
+        assert_eq!(queue.list(&ApprovalFilter::default(), 100).len(), 1);
+        assert_eq!(queue.list(&ApprovalFilter::default(), 500).len(), 2);
+    }
+
+    #[test]
+    fn test_prompts_wait_for_cheap_interruptions() {
+        let mut shortcuts = ShortcutGenerator::new();
+        let proposal = shortcuts.generate_shortcut(&observation("obs1", RiskCategory::Low)).unwrap();
+        let mut queue = ApprovalQueue::new();
+        queue.enqueue_shortcut(&proposal);
+        let action_id = queue.enqueue_escalated(&observation("obs2", RiskCategory::High), "High risk".to_string(), 10);
+        let attention = AttentionService::new();
+
+        assert_eq!(queue.prompts_due(&attention, 100).len(), 2);
+        attention.set_emotional_state(Some(EmotionalState::Stressed));
+        let due = queue.prompts_due(&attention, 100);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, action_id);
+        attention.set_state(crate::attention::AttentionState::InMeeting, "calendar");
+        assert!(queue.prompts_due(&attention, 100).is_empty());
+
+        // Do-not-disturb windows are judged at the given time, not the wall clock
+        let attention = AttentionService::new();
+        attention.enable_dnd("focus_session", Some(200));
+        attention.set_state(crate::attention::AttentionState::Available, "user");
+        assert!(queue.prompts_due(&attention, 100).is_empty());
+        assert_eq!(queue.prompts_due(&attention, 200).len(), 2);
+    }
+
+    #[test]
+    fn test_batch_and_apply_decisions() {
+        let mut shortcuts = ShortcutGenerator::new();
+        let mut calendar = CalendarNegotiationAgent::new();
+        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut queue = ApprovalQueue::new();
+
+        let proposal = shortcuts.generate_shortcut(&observation("obs1", RiskCategory::Low)).unwrap();
+        let buffer = ScheduleSuggestion {
+            event_id: "recovery_3600".to_string(),
+            suggested_start: 3600,
+            suggested_end: 4500,
+            reason: "Recovery buffer".to_string(),
+            expected_benefit: "Refocus".to_string(),
+            requires_approval: true,
+            provenance: Provenance {
+                triggering_pattern: "test".to_string(),
+                data_used: Vec::new(),
+                confidence: Confidence::Medium,
+                consent_scopes: Vec::new(),
+            },
+        };
+        let ids = vec![queue.enqueue_shortcut(&proposal), queue.enqueue_schedule(&buffer, 0), "missing".to_string()];
+
+        let results = queue.batch(&ids, &BatchDecision::Approve, "alice", ApproverRole::User);
+        assert!(results[0].error.is_none() && results[1].error.is_none());
+        assert!(results[2].error.is_some());
+
+        let applied = queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer);
+        assert_eq!(applied.len(), 2);
+        assert!(applied.iter().all(|r| r.error.is_none()));
+        assert_eq!(shortcuts.get_approved_shortcuts().len(), 1);
+        assert_eq!(calendar.holds().len(), 1);
+        assert!(calendar.meeting_blocks(0).is_empty());
+        assert!(queue.list(&ApprovalFilter::default(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_escalated_actions_execute_once_approved() {
+        let mut shortcuts = ShortcutGenerator::new();
+        let mut calendar = CalendarNegotiationAgent::new();
+        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut consent = crate::privacy::ConsentLedger::new();
+        consent.automation_scopes = vec![ActionType::AutomationMacro, ActionType::FocusMode];
+        synthesizer.apply_consent(&consent);
+        let mut queue = ApprovalQueue::new();
+
+        // Single user: one approval runs an action that was not safe to auto-execute
+        let mut low = observation("obs1", RiskCategory::Low);
+        low.action.action_type = ActionType::FocusMode;
+        let id = queue.enqueue_escalated(&low, "Medium confidence".to_string(), 0);
+        queue.approve(&id, "alice", ApproverRole::User).unwrap();
+        let applied = queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer);
+        assert!(applied[0].error.is_none());
+        assert_eq!(synthesizer.get_execution_history()[0].approval_chain[0].approver_id, "alice");
+        assert!(queue.list(&ApprovalFilter::default(), 0).is_empty());
+
+        // Two-person: the item stays queued until a distinct admin approves
+        synthesizer.set_approval_mode(crate::enterprise::ApprovalMode::TwoPerson);
+        let mut high = observation("obs2", RiskCategory::High);
+        high.action.action_type = ActionType::FocusMode;
+        let id = queue.enqueue_escalated(&high, "High risk".to_string(), 0);
+        // An integration's approval is recorded but neither decides the item nor counts toward the quorum
+        queue.approve(&id, "dev_001", ApproverRole::Integration).unwrap();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer).is_empty());
+        assert!(synthesizer.execute_approved(&high, "dev_001", ApproverRole::Integration).is_err());
+        queue.approve(&id, "alice", ApproverRole::User).unwrap();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer)[0].error.is_none());
+        let waiting = queue.list(&ApprovalFilter::default(), 0);
+        assert_eq!((waiting.len(), waiting[0].approvals.len()), (1, 2));
+        assert_eq!(synthesizer.get_execution_history().len(), 1);
+
+        // A failed decision keeps the item instead of dropping it
+        queue.approve(&id, "alice", ApproverRole::Admin).unwrap();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer)[0].error.is_some());
+        assert_eq!(queue.list(&ApprovalFilter::default(), 0).len(), 1);
+
+        queue.approve(&id, "bob", ApproverRole::Admin).unwrap();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer)[0].error.is_none());
+        assert_eq!(synthesizer.get_execution_history().len(), 2);
+        assert!(queue.list(&ApprovalFilter::default(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_plugin_interventions_wait_for_approval() {
+        let mut shortcuts = ShortcutGenerator::new();
+        let mut calendar = CalendarNegotiationAgent::new();
+        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut consent = crate::privacy::ConsentLedger::new();
+        consent.automation_scopes = vec![ActionType::FocusMode];
+        synthesizer.apply_consent(&consent);
+        let mut queue = ApprovalQueue::new();
+
+        let source = observation("obs1", RiskCategory::None);
+        let intervention = PluginIntervention {
+            plugin_id: "focus_guard".to_string(),
+            observation_id: "obs1".to_string(),
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Start a focus block".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            rationale: "Long uninterrupted editing".to_string(),
+        };
+        assert!(queue.enqueue_plugin_intervention(&intervention, &observation("obs2", RiskCategory::None), 0).is_err());
+        let id = queue.enqueue_plugin_intervention(&intervention, &source, 0).unwrap();
+        let plugins_only = ApprovalFilter { source: Some(ApprovalSource::PluginIntervention), ..ApprovalFilter::default() };
+        assert_eq!(queue.list(&plugins_only, 0)[0].id, id);
+
+        // Nothing runs until the user approves
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer).is_empty());
+        assert!(synthesizer.get_execution_history().is_empty());
+        queue.approve(&id, "alice", ApproverRole::User).unwrap();
+        assert!(queue.apply_decisions(&mut shortcuts, &mut calendar, &mut synthesizer)[0].error.is_none());
+        assert_eq!(synthesizer.get_execution_history().len(), 1);
+    }
+
+    #[test]
+    fn test_json_commands() {
+        let mut queue = ApprovalQueue::new();
+        let id = queue.enqueue_escalated(&observation("obs1", RiskCategory::High), "High risk".to_string(), 0);
+
+        let listed = queue.handle_json(r#"{"op":"list","filter":{"source":"escalated_action"}}"#, "alice", ApproverRole::Admin, 0);
+        assert!(listed.contains(r#""status":"items""#) && listed.contains(&id));
+
+        let rejected = queue.handle_json(&format!(r#"{{"op":"reject","id":"{}"}}"#, id), "alice", ApproverRole::Admin, 0);
+        assert!(rejected.contains(r#""status":"results""#));
+        let again = queue.handle_json(&format!(r#"{{"op":"approve","id":"{}"}}"#, id), "alice", ApproverRole::Admin, 0);
+        assert!(again.contains("already decided"));
+        assert!(queue.handle_json("{\"op\":\"explode\"}", "alice", ApproverRole::Admin, 0).contains("Invalid approval command"));
+    }
+}
@@ -0,0 +1,231 @@
+/// Phase: B | Source: Athenos_AI_Strategy.md#L19-24
+/// Async Manager API
+/// Async variants of the storage, sandbox and LLM traits, plus a shared handle that runs synchronous managers off the runtime's worker threads
+
+use crate::types::*;
+use crate::inference::LlmBackend;
+use crate::local_stack::{EmbeddingRecord, MetricsRecord, StorageBackend};
+use crate::sandbox::{MacroCommand, SandboxResult, SandboxRunner};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+/// Boxed future returned by the async traits (object safe, Send across await points)
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Async feature-store persistence
+pub trait AsyncStorageBackend: Send + Sync {
+    fn save_metrics(&self, record: MetricsRecord) -> BoxFuture<'_, Result<(), String>>;
+    fn save_embedding(&self, record: EmbeddingRecord) -> BoxFuture<'_, Result<(), String>>;
+    fn load_metrics(&self, from: i64, to: i64) -> BoxFuture<'_, Result<Vec<MetricsRecord>, String>>;
+    fn load_embeddings(&self, from: i64, to: i64) -> BoxFuture<'_, Result<Vec<EmbeddingRecord>, String>>;
+}
+
+/// Async LLM completion
+pub trait AsyncLlmBackend: Send + Sync {
+    fn generate(&self, prompt: String) -> BoxFuture<'_, Result<String, String>>;
+}
+
+/// Async sandbox runs
+pub trait AsyncSandbox: Send + Sync {
+    fn test_automation(&self, action: Action) -> BoxFuture<'_, Result<SandboxResult, String>>;
+    fn execute_in(&self, action_type: ActionType, commands: Vec<MacroCommand>, work_dir: PathBuf) -> BoxFuture<'_, Result<String, String>>;
+}
+
+/// Shared handle to a synchronous manager
+/// Calls run on tokio's blocking pool under the manager's lock, so slow I/O never stalls the async workers
+pub struct AsyncManager<T> {
+    name: String,
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T> Clone for AsyncManager<T> {
+    fn clone(&self) -> Self {
+        Self { name: self.name.clone(), inner: self.inner.clone() }
+    }
+}
+
+impl<T: Send + 'static> AsyncManager<T> {
+    /// Wrap a manager; `name` labels errors and logs
+    pub fn new(name: &str, manager: T) -> Self {
+        info!("AsyncManager::new: Wrapping {}", name);
+        Self { name: name.to_string(), inner: Arc::new(Mutex::new(manager)) }
+    }
+
+    /// Run `f` against the manager on the blocking pool
+    pub async fn call<R, F>(&self, f: F) -> Result<R, String>
+    where
+        F: FnOnce(&mut T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let inner = self.inner.clone();
+        let name = self.name.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut manager = inner.lock().map_err(|_| format!("{} lock poisoned", name))?;
+            Ok(f(&mut manager))
+        })
+        .await
+        .map_err(|e| format!("{} task failed: {}", self.name, e))?
+    }
+
+    /// Run `f` against the manager from synchronous code
+    pub fn blocking_call<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, String> {
+        let mut manager = self.inner.lock().map_err(|_| format!("{} lock poisoned", self.name))?;
+        Ok(f(&mut manager))
+    }
+
+    /// Recover the manager once every other handle is dropped
+    pub fn into_inner(self) -> Result<T, String> {
+        let name = self.name;
+        Arc::try_unwrap(self.inner)
+            .map_err(|_| format!("{} is still shared", name))?
+            .into_inner()
+            .map_err(|_| format!("{} lock poisoned", name))
+    }
+}
+
+impl<B: StorageBackend + 'static> AsyncStorageBackend for AsyncManager<B> {
+    fn save_metrics(&self, record: MetricsRecord) -> BoxFuture<'_, Result<(), String>> {
+        Box::pin(async move { self.call(move |backend| backend.save_metrics(&record)).await? })
+    }
+
+    fn save_embedding(&self, record: EmbeddingRecord) -> BoxFuture<'_, Result<(), String>> {
+        Box::pin(async move { self.call(move |backend| backend.save_embedding(&record)).await? })
+    }
+
+    fn load_metrics(&self, from: i64, to: i64) -> BoxFuture<'_, Result<Vec<MetricsRecord>, String>> {
+        Box::pin(async move { self.call(move |backend| backend.load_metrics(from, to)).await? })
+    }
+
+    fn load_embeddings(&self, from: i64, to: i64) -> BoxFuture<'_, Result<Vec<EmbeddingRecord>, String>> {
+        Box::pin(async move { self.call(move |backend| backend.load_embeddings(from, to)).await? })
+    }
+}
+
+impl<B: LlmBackend + 'static> AsyncLlmBackend for AsyncManager<B> {
+    fn generate(&self, prompt: String) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(async move { self.call(move |backend| backend.generate(&prompt)).await? })
+    }
+}
+
+/// SandboxRunner guards its own state, so runs share it without a manager lock
+impl AsyncSandbox for Arc<SandboxRunner> {
+    fn test_automation(&self, action: Action) -> BoxFuture<'_, Result<SandboxResult, String>> {
+        let runner = self.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || SandboxRunner::test_automation(&runner, &action))
+                .await
+                .map_err(|e| format!("Sandbox task failed: {}", e))
+        })
+    }
+
+    fn execute_in(&self, action_type: ActionType, commands: Vec<MacroCommand>, work_dir: PathBuf) -> BoxFuture<'_, Result<String, String>> {
+        let runner = self.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || SandboxRunner::execute_in(&runner, &action_type, &commands, &work_dir))
+                .await
+                .map_err(|e| format!("Sandbox task failed: {}", e))?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{OSEvent, OSEventType};
+    use crate::inference::TemplateBackend;
+    use crate::local_stack::{MemoryBackend, TemporalMetrics};
+    use crate::pattern_miner::PatternMiner;
+    use std::collections::HashMap;
+
+    /// Backend that blocks like a slow local model
+    struct SlowBackend;
+
+    impl LlmBackend for SlowBackend {
+        fn generate(&mut self, prompt: &str) -> Result<String, String> {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            Ok(prompt.to_uppercase())
+        }
+    }
+
+    fn events() -> Vec<OSEvent> {
+        (0..30)
+            .map(|i| OSEvent {
+                event_type: OSEventType::AppSwitch,
+                app_name: ["Slack", "IDE", "Terminal"][i % 3].to_string(),
+                window_title: None,
+                timestamp: i as i64 * 10,
+                metadata: HashMap::new(),
+            })
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_mining_interleaves_with_slow_generation() {
+        let llm = AsyncManager::new("llm", SlowBackend);
+        let miner = AsyncManager::new("pattern_miner", PatternMiner::new());
+
+        let generation = {
+            let llm = llm.clone();
+            tokio::spawn(async move { llm.generate("focus".to_string()).await })
+        };
+        let started = std::time::Instant::now();
+        let events = events();
+        let mined = miner.call(move |m| m.mine_patterns(&events).len()).await.unwrap();
+        // Mining finished while the slow completion was still running
+        assert!(started.elapsed() < std::time::Duration::from_millis(200));
+        assert!(mined <= 30);
+
+        assert_eq!(generation.await.unwrap().unwrap(), "FOCUS");
+        assert_eq!(llm.into_inner().map(|_| ()), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_storage_and_llm_through_trait_objects() {
+        let storage: Box<dyn AsyncStorageBackend> = Box::new(AsyncManager::new("storage", MemoryBackend::new()));
+        let record = MetricsRecord {
+            observation_id: "obs_1".to_string(),
+            recorded_at: 100,
+            metrics: TemporalMetrics {
+                time_to_first_action_min: 1.0,
+                focus_duration_min: 25.0,
+                context_switch_count: 3,
+                repeat_count: 2,
+                session_duration_min: 40.0,
+            },
+        };
+        storage.save_metrics(record).await.unwrap();
+        assert_eq!(storage.load_metrics(0, 200).await.unwrap().len(), 1);
+        assert!(storage.load_metrics(200, 300).await.unwrap().is_empty());
+
+        let llm: Arc<dyn AsyncLlmBackend> = Arc::new(AsyncManager::new("llm", TemplateBackend));
+        assert_eq!(llm.generate("Prompt\nShip it".to_string()).await.unwrap(), "Reflecting on: Ship it");
+        assert!(llm.generate(String::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_shared_handle_reports_contention() {
+        let miner = AsyncManager::new("pattern_miner", PatternMiner::new());
+        let other = miner.clone();
+        assert_eq!(miner.blocking_call(|m| m.latest_patterns().len()), Ok(0));
+        assert!(miner.into_inner().err().unwrap().contains("still shared"));
+        assert!(other.into_inner().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_sandbox_runs_share_cache() {
+        let runner: Arc<SandboxRunner> = Arc::new(SandboxRunner::default());
+        let action = Action {
+            action_type: ActionType::AutomationMacro,
+            description: "Dev startup macro".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+        };
+        let (first, second) = tokio::join!(runner.test_automation(action.clone()), runner.test_automation(action));
+        assert_eq!(first.unwrap().success, second.unwrap().success);
+        let stats = SandboxRunner::cache_stats(&runner);
+        assert_eq!(stats.hits + stats.misses, 2);
+    }
+}
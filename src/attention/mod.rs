@@ -0,0 +1,326 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L121-124
+/// Attention State Service
+/// Shared attention state (focused / in-meeting / away) coordinating interruptions
+
+use crate::types::EmotionalState;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// User attention state
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionState {
+    Available,
+    Focused,
+    InMeeting,
+    Away,
+}
+
+/// Priority of an interruption competing for user attention
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InterruptionPriority {
+    Low,
+    Normal,
+    Critical,
+}
+
+/// Point-in-time view of the shared attention state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttentionSnapshot {
+    pub state: AttentionState,
+    pub dnd_enabled: bool,
+    pub source: String, // Component that set the state
+    pub since: i64,
+    pub dnd_until: Option<i64>,
+    #[serde(default)]
+    pub emotional_state: Option<EmotionalState>, // Latest estimate, if emotion detection is on
+}
+
+/// Minutes of uninterrupted focus after which focus depth saturates
+const FULL_FOCUS_DEPTH_MIN: f64 = 45.0;
+
+impl InterruptionPriority {
+    /// Highest interruption cost this priority may be delivered at
+    pub fn max_cost(&self) -> f64 {
+        match self {
+            InterruptionPriority::Low => 0.25,
+            InterruptionPriority::Normal => 0.5,
+            InterruptionPriority::Critical => 1.0,
+        }
+    }
+}
+
+/// Estimated cost of interrupting the user right now (0.0 free to 1.0 never)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterruptionCost {
+    pub score: f64,
+    pub focus_depth: f64,       // 0.0 to 1.0, grows with time spent focused
+    pub emotional_penalty: f64, // Added for stressed, fatigued or in-flow users
+    pub in_meeting: bool,
+    pub away: bool,
+}
+
+impl InterruptionCost {
+    /// Estimate from an attention snapshot
+    pub fn estimate(snapshot: &AttentionSnapshot, dnd_active: bool, now: i64) -> Self {
+        let focused_min = (now - snapshot.since).max(0) as f64 / 60.0;
+        let focus_depth = match snapshot.state {
+            AttentionState::Focused => (focused_min / FULL_FOCUS_DEPTH_MIN).min(1.0),
+            _ => 0.0,
+        };
+        let base = match snapshot.state {
+            AttentionState::Available => 0.1,
+            AttentionState::Focused => 0.4 + 0.4 * focus_depth,
+            AttentionState::InMeeting => 0.8,
+            AttentionState::Away => 1.0,
+        };
+        let emotional_penalty = match snapshot.emotional_state {
+            Some(EmotionalState::Stressed) => 0.2,
+            Some(EmotionalState::CreativeFlow) => 0.2,
+            Some(EmotionalState::Fatigued) | Some(EmotionalState::Focused) => 0.1,
+            _ => 0.0,
+        };
+        let dnd_penalty = if dnd_active { 0.1 } else { 0.0 };
+
+        Self {
+            score: (base + emotional_penalty + dnd_penalty).min(1.0),
+            focus_depth,
+            emotional_penalty,
+            in_meeting: snapshot.state == AttentionState::InMeeting,
+            away: snapshot.state == AttentionState::Away,
+        }
+    }
+
+    /// Whether an interruption of this priority is worth its cost
+    pub fn acceptable_for(&self, priority: InterruptionPriority) -> bool {
+        !self.away && self.score <= priority.max_cost()
+    }
+}
+
+/// Shared attention state service
+/// Cloning yields a handle to the same underlying state
+#[derive(Debug, Clone)]
+pub struct AttentionService {
+    snapshot: Arc<RwLock<AttentionSnapshot>>,
+}
+
+impl AttentionService {
+    /// Create new attention service (available, DND off)
+    pub fn new() -> Self {
+        info!("AttentionService::new: Creating attention service");
+        Self {
+            snapshot: Arc::new(RwLock::new(AttentionSnapshot {
+                state: AttentionState::Available,
+                dnd_enabled: false,
+                source: "default".to_string(),
+                since: chrono::Utc::now().timestamp(),
+                dnd_until: None,
+                emotional_state: None,
+            })),
+        }
+    }
+
+    /// Set attention state
+    pub fn set_state(&self, state: AttentionState, source: &str) {
+        info!("AttentionService::set_state: {:?} (source: {})", state, source);
+        let mut snapshot = self.snapshot.write().unwrap();
+        if snapshot.state != state {
+            snapshot.since = chrono::Utc::now().timestamp();
+        }
+        snapshot.state = state;
+        snapshot.source = source.to_string();
+    }
+
+    /// Enable do-not-disturb (called by the focus mode executor)
+    pub fn enable_dnd(&self, source: &str, until: Option<i64>) {
+        info!("AttentionService::enable_dnd: Enabling DND (source: {})", source);
+        let mut snapshot = self.snapshot.write().unwrap();
+        snapshot.dnd_enabled = true;
+        snapshot.dnd_until = until;
+        if snapshot.state == AttentionState::Available {
+            snapshot.state = AttentionState::Focused;
+            snapshot.since = chrono::Utc::now().timestamp();
+        }
+        snapshot.source = source.to_string();
+    }
+
+    /// Disable do-not-disturb
+    pub fn disable_dnd(&self, source: &str) {
+        info!("AttentionService::disable_dnd: Disabling DND (source: {})", source);
+        let mut snapshot = self.snapshot.write().unwrap();
+        snapshot.dnd_enabled = false;
+        snapshot.dnd_until = None;
+        if snapshot.state == AttentionState::Focused {
+            snapshot.state = AttentionState::Available;
+            snapshot.since = chrono::Utc::now().timestamp();
+        }
+        snapshot.source = source.to_string();
+    }
+
+    /// End a timed DND whose `dnd_until` has passed, reverting the Focused state it set like `disable_dnd`
+    fn expire_dnd(&self, now: i64) {
+        let mut snapshot = self.snapshot.write().unwrap();
+        let Some(until) = snapshot.dnd_until.filter(|until| snapshot.dnd_enabled && now >= *until) else {
+            return;
+        };
+        info!("AttentionService::expire_dnd: DND ended at {}", until);
+        snapshot.dnd_enabled = false;
+        snapshot.dnd_until = None;
+        if snapshot.state == AttentionState::Focused {
+            snapshot.state = AttentionState::Available;
+            snapshot.since = until;
+        }
+    }
+
+    /// Record the latest emotional state estimate (None clears it, e.g. after consent is revoked)
+    pub fn set_emotional_state(&self, emotional_state: Option<EmotionalState>) {
+        self.snapshot.write().unwrap().emotional_state = emotional_state;
+    }
+
+    /// Get current attention snapshot
+    pub fn current(&self) -> AttentionSnapshot {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// Check whether DND is active at the given time
+    pub fn is_dnd_active(&self, now: i64) -> bool {
+        let snapshot = self.snapshot.read().unwrap();
+        snapshot.dnd_enabled && snapshot.dnd_until.map(|until| now < until).unwrap_or(true)
+    }
+
+    /// Check whether an interruption of the given priority may be delivered now
+    pub fn allows_interruption(&self, priority: InterruptionPriority) -> bool {
+        self.allows_interruption_at(priority, chrono::Utc::now().timestamp())
+    }
+
+    /// Check whether an interruption of the given priority may be delivered at `now`
+    pub fn allows_interruption_at(&self, priority: InterruptionPriority, now: i64) -> bool {
+        self.expire_dnd(now);
+        let state = self.snapshot.read().unwrap().state;
+
+        if priority == InterruptionPriority::Critical {
+            return state != AttentionState::Away;
+        }
+
+        if self.is_dnd_active(now) {
+            return false;
+        }
+
+        state == AttentionState::Available
+    }
+
+    /// Estimated cost of interrupting the user at `now`
+    pub fn interruption_cost(&self, now: i64) -> InterruptionCost {
+        self.expire_dnd(now);
+        InterruptionCost::estimate(&self.current(), self.is_dnd_active(now), now)
+    }
+
+    /// Whether to deliver an interruption now: allowed by the attention state and worth its cost
+    /// Nudges, co-pilot messages and approval prompts consult this to pick delivery moments
+    pub fn should_interrupt(&self, priority: InterruptionPriority) -> bool {
+        self.should_interrupt_at(priority, chrono::Utc::now().timestamp())
+    }
+
+    /// `should_interrupt` evaluated at `now`
+    pub fn should_interrupt_at(&self, priority: InterruptionPriority, now: i64) -> bool {
+        self.allows_interruption_at(priority, now) && self.interruption_cost(now).acceptable_for(priority)
+    }
+}
+
+impl Default for AttentionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attention_service_creation() {
+        let service = AttentionService::new();
+        let snapshot = service.current();
+        assert_eq!(snapshot.state, AttentionState::Available);
+        assert!(!snapshot.dnd_enabled);
+        assert!(service.allows_interruption(InterruptionPriority::Low));
+    }
+
+    #[test]
+    fn test_dnd_blocks_non_critical_interruptions() {
+        let service = AttentionService::new();
+        service.enable_dnd("focus_mode", None);
+
+        assert_eq!(service.current().state, AttentionState::Focused);
+        assert!(!service.allows_interruption(InterruptionPriority::Normal));
+        assert!(service.allows_interruption(InterruptionPriority::Critical));
+
+        service.disable_dnd("focus_mode");
+        assert_eq!(service.current().state, AttentionState::Available);
+        assert!(service.allows_interruption(InterruptionPriority::Low));
+    }
+
+    #[test]
+    fn test_timed_dnd_lapses_after_until() {
+        let service = AttentionService::new();
+        service.enable_dnd("focus_mode", Some(1_000));
+
+        assert!(!service.allows_interruption_at(InterruptionPriority::Normal, 999));
+        assert_eq!(service.current().state, AttentionState::Focused);
+
+        assert!(service.allows_interruption_at(InterruptionPriority::Normal, 1_000));
+        let snapshot = service.current();
+        assert_eq!(snapshot.state, AttentionState::Available);
+        assert_eq!(snapshot.since, 1_000);
+        assert!(!snapshot.dnd_enabled);
+    }
+
+    #[test]
+    fn test_clones_share_state() {
+        let service = AttentionService::new();
+        let handle = service.clone();
+
+        handle.set_state(AttentionState::InMeeting, "calendar");
+        assert_eq!(service.current().state, AttentionState::InMeeting);
+        assert_eq!(service.current().source, "calendar");
+    }
+
+    #[test]
+    fn test_interruption_cost_grows_with_focus_depth_and_stress() {
+        let service = AttentionService::new();
+        let now = service.current().since;
+        assert!(service.interruption_cost(now).acceptable_for(InterruptionPriority::Low));
+
+        service.set_emotional_state(Some(EmotionalState::Stressed));
+        let stressed = service.interruption_cost(now);
+        assert!((stressed.score - 0.3).abs() < 1e-9);
+        assert!(!stressed.acceptable_for(InterruptionPriority::Low));
+        assert!(stressed.acceptable_for(InterruptionPriority::Normal));
+
+        service.set_emotional_state(None);
+        service.set_state(AttentionState::Focused, "focus_session");
+        let since = service.current().since;
+        let shallow = service.interruption_cost(since + 5 * 60);
+        let deep = service.interruption_cost(since + 60 * 60);
+        assert!(shallow.acceptable_for(InterruptionPriority::Normal));
+        assert_eq!(deep.focus_depth, 1.0);
+        assert!(!deep.acceptable_for(InterruptionPriority::Normal));
+        assert!(deep.acceptable_for(InterruptionPriority::Critical));
+    }
+
+    #[test]
+    fn test_meeting_and_away_costs() {
+        let service = AttentionService::new();
+        let now = chrono::Utc::now().timestamp();
+        service.set_state(AttentionState::InMeeting, "calendar");
+        let meeting = service.interruption_cost(now);
+        assert!(meeting.in_meeting);
+        assert!(!meeting.acceptable_for(InterruptionPriority::Normal));
+        assert!(service.should_interrupt(InterruptionPriority::Critical));
+
+        service.set_state(AttentionState::Away, "idle");
+        assert!(!service.interruption_cost(now).acceptable_for(InterruptionPriority::Critical));
+        assert!(!service.should_interrupt(InterruptionPriority::Critical));
+    }
+}
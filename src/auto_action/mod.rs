@@ -3,10 +3,16 @@
 /// Introduce auto-action synthesizer with sandboxed execution and rollback
 
 use crate::types::*;
-use crate::sandbox::{SandboxRunner, SandboxResult};
+use crate::sandbox::{snapshot_dir, MacroCommand, SandboxRunner, SandboxResult};
+use crate::journal::{ActionJournal, JournalRecord};
+use crate::forecast::ForecastPoint;
+use crate::enterprise::{Approval, ApprovalMode, ApproverRole};
+use crate::privacy::ConsentLedger;
+use crate::gate_policy::{GateInput, SharedGatePolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use std::path::PathBuf;
+use tracing::{info, warn};
 
 /// Action execution state
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -18,6 +24,24 @@ pub enum ActionState {
     Failed,
 }
 
+/// Side effect an automation applies when it executes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActionEffect {
+    WriteFile { path: PathBuf, content: Vec<u8> },
+    SetSetting { key: String, value: String },
+    RunMacro { work_dir: PathBuf, commands: Vec<MacroCommand>, inverse: Vec<MacroCommand> },
+}
+
+/// Reverse of one applied effect, journaled before the effect runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReverseOperation {
+    FileRestore { path: PathBuf, original: Option<Vec<u8>> }, // None: the file did not exist
+    SettingRevert { key: String, previous: Option<String> },
+    MacroInverse { work_dir: PathBuf, commands: Vec<MacroCommand>, expected: HashMap<String, (u64, u64)> }, // Directory snapshot before the macro
+}
+
 /// Executed action with rollback capability
 /// Source: Athenos_AI_Strategy.md#L120
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,8 +51,11 @@ pub struct ExecutedAction {
     pub state: ActionState,
     pub execution_result: Option<SandboxResult>,
     pub rollback_diff: Option<String>,
+    #[serde(default)]
+    pub reverse_ops: Vec<ReverseOperation>, // In application order; rollback applies them last-first
     pub executed_at: Option<i64>,
     pub rolled_back_at: Option<i64>,
+    pub approval_chain: Vec<Approval>,
 }
 
 /// Auto-action synthesizer
@@ -37,6 +64,23 @@ pub struct AutoActionSynthesizer {
     sandbox_runner: SandboxRunner,
     executed_actions: HashMap<String, ExecutedAction>,
     rollback_stack: Vec<String>, // Action IDs in execution order
+    journal: Option<ActionJournal>,
+    forecast: Vec<ForecastPoint>,
+    deferred: Vec<Observation>,
+    approval_mode: ApprovalMode,
+    approvals: HashMap<String, Vec<Approval>>, // action_id -> approval chain
+    settings: HashMap<String, String>, // Settings changed by automations
+    observe_only: bool, // Guided onboarding: nothing executes until unlocked
+    gate_policy: SharedGatePolicy,
+}
+
+/// Outcome of crash-recovery replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryReport {
+    pub completed: Vec<String>,
+    pub rolled_back: Vec<String>,
+    #[serde(default)]
+    pub failed: Vec<String>, // Rollback could not restore the pre-action state; left in flight and retried next recovery
 }
 
 impl AutoActionSynthesizer {
@@ -47,37 +91,385 @@ impl AutoActionSynthesizer {
             sandbox_runner: SandboxRunner::default(),
             executed_actions: HashMap::new(),
             rollback_stack: Vec::new(),
+            journal: None,
+            forecast: Vec::new(),
+            deferred: Vec::new(),
+            approval_mode: ApprovalMode::SingleUser,
+            approvals: HashMap::new(),
+            settings: HashMap::new(),
+            observe_only: false,
+            gate_policy: SharedGatePolicy::default(),
+        }
+    }
+
+    /// Create auto-action synthesizer backed by a write-ahead journal
+    pub fn with_journal(journal: ActionJournal) -> Self {
+        let mut synthesizer = Self::new();
+        synthesizer.journal = Some(journal);
+        synthesizer
+    }
+
+    fn journal_record(&mut self, action_id: &str, record: JournalRecord) -> Result<(), String> {
+        match self.journal.as_mut() {
+            Some(journal) => journal.append(action_id, record),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse every automation while onboarding is observe-only
+    pub fn set_observe_only(&mut self, observe_only: bool) {
+        self.observe_only = observe_only;
+    }
+
+    /// Share the gate policy auto-executed actions must also pass
+    pub fn set_gate_policy(&mut self, gate_policy: SharedGatePolicy) {
+        self.gate_policy = gate_policy;
+    }
+
+    /// Gate input for an action: its risk and how often earlier actions of its type were kept rather than rolled back
+    fn gate_input(&self, action: &Action) -> GateInput {
+        let same_type = || self.executed_actions.values().filter(|a| a.action.action_type == action.action_type);
+        GateInput {
+            risk: Some(action.risk.clone()),
+            accepted: same_type().filter(|a| a.state == ActionState::Completed).count() as f64,
+            decided: same_type().filter(|a| matches!(a.state, ActionState::Completed | ActionState::RolledBack)).count() as f64,
+            ..GateInput::default()
+        }
+    }
+
+    /// Update the emotional state forecast used to defer risky automations
+    pub fn update_forecast(&mut self, forecast: Vec<ForecastPoint>) {
+        self.forecast = forecast;
+    }
+
+    /// Predicted low-focus window containing `now`, if any
+    fn low_focus_window(&self, now: i64) -> Option<&ForecastPoint> {
+        self.forecast.iter().find(|p| p.low_focus && p.start <= now && now < p.end)
+    }
+
+    /// System-altering automations are deferred while focus is predicted to be low
+    fn should_defer(&self, action: &Action, now: i64) -> bool {
+        let risky = matches!(action.action_type, ActionType::SandboxPatch | ActionType::SystemHygiene);
+        risky && self.low_focus_window(now).is_some()
+    }
+
+    /// Take deferred observations whose low-focus window has passed
+    pub fn take_ready_deferred(&mut self, now: i64) -> Vec<Observation> {
+        if self.low_focus_window(now).is_some() {
+            return Vec::new();
+        }
+        std::mem::take(&mut self.deferred)
+    }
+
+    /// Apply per-ActionType automation consent to sandbox gating
+    pub fn apply_consent(&mut self, consent: &ConsentLedger) {
+        self.sandbox_runner.set_consent_scopes(consent.automation_scopes.clone());
+    }
+
+    /// Whether the user consented to automating this action type
+    pub fn has_automation_consent(&self, action_type: &ActionType) -> bool {
+        self.sandbox_runner.has_consent_for(action_type)
+    }
+
+    /// Whether an action must go through the approval queue instead of auto-executing
+    pub fn requires_approval(&self, action: &Action) -> bool {
+        let two_person = self.approval_mode == ApprovalMode::TwoPerson && action.risk == RiskCategory::High;
+        two_person || !self.sandbox_runner.is_safe_to_auto_execute(action)
+    }
+
+    /// Set approval mode (enterprise deployments use two-person approval)
+    pub fn set_approval_mode(&mut self, mode: ApprovalMode) {
+        info!("AutoActionSynthesizer::set_approval_mode: {:?}", mode);
+        self.approval_mode = mode;
+    }
+
+    /// Approve a pending High-risk action for the observation
+    /// Two-person mode needs one user and one admin approval from distinct approvers
+    pub fn approve_action(&mut self, observation_id: &str, approver_id: &str, role: ApproverRole) -> Result<(), String> {
+        info!("AutoActionSynthesizer::approve_action: {} approves {} as {:?}", approver_id, observation_id, role);
+        if self.approval_mode != ApprovalMode::TwoPerson {
+            return Err("Two-person approval mode is not enabled".to_string());
+        }
+        if role == ApproverRole::Integration {
+            return Err("Integrations cannot approve actions".to_string());
+        }
+        
+        let chain = self.approvals.entry(format!("action_{}", observation_id)).or_default();
+        if chain.iter().any(|a| a.approver_id == approver_id) {
+            return Err(format!("{} has already approved this action", approver_id));
+        }
+        if chain.iter().any(|a| a.role == role) {
+            return Err(format!("Action already has a {:?} approval", role));
+        }
+        
+        chain.push(Approval {
+            approver_id: approver_id.to_string(),
+            role,
+            approved_at: chrono::Utc::now().timestamp(),
+        });
+        Ok(())
+    }
+
+    /// Whether the chain has distinct user and admin approvers
+    fn approval_complete(chain: &[Approval]) -> bool {
+        let user = chain.iter().find(|a| a.role == ApproverRole::User);
+        let admin = chain.iter().find(|a| a.role == ApproverRole::Admin);
+        matches!((user, admin), (Some(u), Some(a)) if u.approver_id != a.approver_id)
+    }
+
+    /// Current value of a setting changed by automations
+    pub fn setting(&self, key: &str) -> Option<&String> {
+        self.settings.get(key)
+    }
+
+    /// Capture the reverse of an effect from the current state
+    fn reverse_of(&self, effect: &ActionEffect) -> Result<ReverseOperation, String> {
+        Ok(match effect {
+            ActionEffect::WriteFile { path, .. } => ReverseOperation::FileRestore {
+                path: path.clone(),
+                original: match std::fs::read(path) {
+                    Ok(content) => Some(content),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(format!("Failed to read {:?}: {}", path, e)),
+                },
+            },
+            ActionEffect::SetSetting { key, .. } => ReverseOperation::SettingRevert {
+                key: key.clone(),
+                previous: self.settings.get(key).cloned(),
+            },
+            ActionEffect::RunMacro { work_dir, inverse, .. } => ReverseOperation::MacroInverse {
+                work_dir: work_dir.clone(),
+                commands: inverse.clone(),
+                expected: snapshot_dir(work_dir),
+            },
+        })
+    }
+
+    fn apply_effect(&mut self, action_type: &ActionType, effect: &ActionEffect) -> Result<(), String> {
+        match effect {
+            ActionEffect::WriteFile { path, content } => {
+                std::fs::write(path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+            }
+            ActionEffect::SetSetting { key, value } => {
+                self.settings.insert(key.clone(), value.clone());
+                Ok(())
+            }
+            ActionEffect::RunMacro { work_dir, commands, .. } => self.sandbox_runner.execute_in(action_type, commands, work_dir).map(|_| ()),
+        }
+    }
+
+    fn apply_reverse(&mut self, op: &ReverseOperation) -> Result<(), String> {
+        match op {
+            ReverseOperation::FileRestore { path, original: Some(content) } => {
+                std::fs::write(path, content).map_err(|e| format!("Failed to restore {:?}: {}", path, e))
+            }
+            ReverseOperation::FileRestore { path, original: None } => match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove {:?}: {}", path, e)),
+                _ => Ok(()),
+            },
+            ReverseOperation::SettingRevert { key, previous } => {
+                match previous {
+                    Some(value) => self.settings.insert(key.clone(), value.clone()),
+                    None => self.settings.remove(key),
+                };
+                Ok(())
+            }
+            ReverseOperation::MacroInverse { work_dir, commands, .. } => self.sandbox_runner.undo_in(commands, work_dir).map(|_| ()),
+        }
+    }
+
+    /// Whether the state a reverse operation restores is actually back in place
+    fn verify_reverse(&self, op: &ReverseOperation) -> Result<(), String> {
+        let restored = match op {
+            ReverseOperation::FileRestore { path, original } => std::fs::read(path).ok() == *original,
+            ReverseOperation::SettingRevert { key, previous } => self.settings.get(key) == previous.as_ref(),
+            ReverseOperation::MacroInverse { work_dir, expected, .. } => snapshot_dir(work_dir) == *expected,
+        };
+        if restored {
+            Ok(())
+        } else {
+            Err(format!("Pre-action state not restored: {:?}", op))
         }
     }
 
+    /// Apply reverse operations last-first, verifying each restored its pre-action state before the next runs
+    /// (an earlier op on the same target legitimately overwrites what a later one restored)
+    fn apply_reverse_ops(&mut self, ops: &[ReverseOperation]) -> Result<(), String> {
+        let errors: Vec<String> = ops
+            .iter()
+            .rev()
+            .filter_map(|op| self.apply_reverse(op).and_then(|_| self.verify_reverse(op)).err())
+            .collect();
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors.join("; ")),
+        }
+    }
+
+    /// Undo the effects applied so far after a failed step; returns `error` for the caller
+    /// RolledBack is journaled only once the pre-action state is verified, so recovery retries an incomplete rollback
+    fn abort_effects(&mut self, action_id: &str, reverse_ops: &[ReverseOperation], error: String) -> String {
+        match self.apply_reverse_ops(reverse_ops) {
+            Ok(()) => {
+                if let Err(e) = self.journal_record(action_id, JournalRecord::RolledBack) {
+                    warn!("AutoActionSynthesizer::synthesize_and_execute: Failed to journal rollback of {}: {}", action_id, e);
+                }
+            }
+            Err(e) => warn!("AutoActionSynthesizer::synthesize_and_execute: Rollback of {} incomplete: {}", action_id, e),
+        }
+        error
+    }
+
     /// Synthesize and execute action automatically
     /// Source: Athenos_AI_Strategy.md#L120
     pub fn synthesize_and_execute(&mut self, observation: &Observation) -> Result<ExecutedAction, String> {
+        self.synthesize_and_execute_with_effects(observation, &[])
+    }
+
+    /// Synthesize and execute an action that applies side effects
+    /// Each effect's reverse operation is journaled before the effect runs; a failed effect rolls back the earlier ones
+    pub fn synthesize_and_execute_with_effects(&mut self, observation: &Observation, effects: &[ActionEffect]) -> Result<ExecutedAction, String> {
+        self.execute(observation, effects, None)
+    }
+
+    /// Execute an escalated action a person approved from the approval queue
+    /// In two-person mode a High-risk action runs once its chain has distinct user and admin approvers; until then the
+    /// approval is recorded and Ok(None) returned. Otherwise the approval stands in for the auto-execution safety checks.
+    pub fn execute_approved(&mut self, observation: &Observation, approver_id: &str, role: ApproverRole) -> Result<Option<ExecutedAction>, String> {
+        self.execute_approved_with_effects(observation, &[], approver_id, role)
+    }
+
+    /// Execute an approved action that applies side effects
+    pub fn execute_approved_with_effects(&mut self, observation: &Observation, effects: &[ActionEffect], approver_id: &str, role: ApproverRole) -> Result<Option<ExecutedAction>, String> {
+        if role == ApproverRole::Integration {
+            return Err("Integrations cannot approve actions".to_string());
+        }
+        if self.approval_mode == ApprovalMode::TwoPerson && observation.action.risk == RiskCategory::High {
+            self.approve_action(&observation.id, approver_id, role)?;
+            let complete = self.approvals.get(&format!("action_{}", observation.id)).is_some_and(|chain| Self::approval_complete(chain));
+            if !complete {
+                info!("AutoActionSynthesizer::execute_approved: {} awaits a second approver", observation.id);
+                return Ok(None);
+            }
+            return self.execute(observation, effects, None).map(Some);
+        }
+        let approval = Approval { approver_id: approver_id.to_string(), role, approved_at: chrono::Utc::now().timestamp() };
+        self.execute(observation, effects, Some(approval)).map(Some)
+    }
+
+    fn execute(&mut self, observation: &Observation, effects: &[ActionEffect], approved_by: Option<Approval>) -> Result<ExecutedAction, String> {
         info!("AutoActionSynthesizer::synthesize_and_execute: Synthesizing action for {}", observation.id);
         
-        // Check if safe to auto-execute
-        if !self.sandbox_runner.is_safe_to_auto_execute(&observation.action) {
+        let action_id = format!("action_{}", observation.id);
+        
+        if self.observe_only {
+            return Err("Auto-actions are locked during guided onboarding".to_string());
+        }
+        
+        if !self.sandbox_runner.has_consent_for(&observation.action.action_type) {
+            return Err(format!("Automation consent not granted for {:?}", observation.action.action_type));
+        }
+        
+        // High-risk actions in two-person mode run only with a complete approval chain;
+        // everything else must be safe to auto-execute
+        let requires_two_person = self.approval_mode == ApprovalMode::TwoPerson && observation.action.risk == RiskCategory::High;
+        if requires_two_person {
+            let approved = self.approvals.get(&action_id).map(|c| Self::approval_complete(c)).unwrap_or(false);
+            if !approved {
+                return Err("Two-person approval required (user + admin)".to_string());
+            }
+        } else if approved_by.is_some() {
+            // A person approved this specific action; the automatic safety and quality gates don't apply
+        } else if !self.sandbox_runner.is_safe_to_auto_execute(&observation.action) {
             return Err("Action not safe for auto-execution".to_string());
+        } else {
+            let violations = self.gate_policy.get().violations(&self.gate_input(&observation.action));
+            if !violations.is_empty() {
+                return Err(format!("Quality gate failed: {}", violations.join("; ")));
+            }
+        }
+        
+        let now = chrono::Utc::now().timestamp();
+        if self.should_defer(&observation.action, now) {
+            let until = self.low_focus_window(now).map(|p| p.end).unwrap_or(now);
+            info!("AutoActionSynthesizer::synthesize_and_execute: Deferring {} until {}", observation.id, until);
+            self.deferred.push(observation.clone());
+            return Err(format!("Deferred: predicted low-focus window until {}", until));
         }
         
-        // Test in sandbox first
-        let sandbox_result = self.sandbox_runner.test_automation(&observation.action);
+        self.journal_record(&action_id, JournalRecord::Intent { action: observation.action.clone() })?;
+        
+        // Test in sandbox first; an approved action is tested as approved, and a failed test keeps its pending approvals
+        // Macros are tested by running the commands they will execute in an isolated directory
+        let approved = requires_two_person || approved_by.is_some();
+        let macro_commands: Vec<MacroCommand> = effects
+            .iter()
+            .filter_map(|effect| match effect {
+                ActionEffect::RunMacro { commands, .. } => Some(commands.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let is_macro = observation.action.action_type == ActionType::AutomationMacro || !macro_commands.is_empty();
+        let sandbox_result = match (is_macro, approved) {
+            (true, true) => self.sandbox_runner.test_approved_macro(&observation.action, &macro_commands),
+            (true, false) => self.sandbox_runner.test_macro(&observation.action, &macro_commands),
+            (false, true) => self.sandbox_runner.test_approved_automation(&observation.action),
+            (false, false) => self.sandbox_runner.test_automation(&observation.action),
+        };
         if !sandbox_result.success {
+            self.journal_record(&action_id, JournalRecord::RolledBack)?;
             return Err(format!("Sandbox test failed: {:?}", sandbox_result.error_message));
         }
         
-        // Generate rollback diff
+        // Approval chain is recorded in the audit log before execution
+        let approval_chain = if requires_two_person {
+            self.approvals.remove(&action_id).unwrap_or_default()
+        } else {
+            approved_by.into_iter().collect()
+        };
+        for approval in &approval_chain {
+            self.journal_record(&action_id, JournalRecord::Approved {
+                approver_id: approval.approver_id.clone(),
+                role: approval.role,
+            })?;
+        }
+        
+        // Generate rollback diff and persist it before any side effect
         let rollback_diff = self.sandbox_runner.generate_undo(&observation.action);
+        self.journal_record(&action_id, JournalRecord::SandboxDiff { diff: rollback_diff.clone() })?;
+        
+        // Execute effects, capturing each reverse operation first and marking each step done only once it has run
+        let mut reverse_ops = Vec::new();
+        for (index, effect) in effects.iter().enumerate() {
+            let op = match self.reverse_of(effect) {
+                Ok(op) => op,
+                Err(e) => return Err(self.abort_effects(&action_id, &reverse_ops, format!("Cannot capture reverse of effect {}: {}", index, e))),
+            };
+            if let Err(e) = self.journal_record(&action_id, JournalRecord::ReverseOp { op: op.clone() }) {
+                return Err(self.abort_effects(&action_id, &reverse_ops, e));
+            }
+            reverse_ops.push(op);
+            if let Err(e) = self.apply_effect(&observation.action.action_type, effect) {
+                return Err(self.abort_effects(&action_id, &reverse_ops, format!("Execution failed: {}", e)));
+            }
+            if let Err(e) = self.journal_record(&action_id, JournalRecord::StepCompleted { step: format!("effect_{}", index) }) {
+                return Err(self.abort_effects(&action_id, &reverse_ops, e));
+            }
+        }
+        self.journal_record(&action_id, JournalRecord::StepCompleted { step: "execute".to_string() })?;
+        self.journal_record(&action_id, JournalRecord::Committed)?;
         
-        // Execute action (Phase C: simulated execution)
         let executed_action = ExecutedAction {
-            id: format!("action_{}", observation.id),
+            id: action_id,
             action: observation.action.clone(),
             state: ActionState::Completed,
             execution_result: Some(sandbox_result),
             rollback_diff: Some(rollback_diff),
+            reverse_ops,
             executed_at: Some(chrono::Utc::now().timestamp()),
             rolled_back_at: None,
+            approval_chain,
         };
         
         self.executed_actions.insert(executed_action.id.clone(), executed_action.clone());
@@ -91,38 +483,123 @@ impl AutoActionSynthesizer {
     pub fn rollback_last(&mut self) -> Result<(), String> {
         info!("AutoActionSynthesizer::rollback_last: Rolling back last action");
         
-        if let Some(action_id) = self.rollback_stack.pop() {
-            if let Some(action) = self.executed_actions.get_mut(&action_id) {
-                if action.state == ActionState::Completed {
+        // The entry stays on the stack until its rollback succeeds, so a failed rollback can be retried
+        let action_id = self.rollback_stack.last().cloned().ok_or("No actions to rollback")?;
+        self.rollback_action(&action_id)?;
+        self.rollback_stack.pop();
+        Ok(())
+    }
+
+    /// Rollback specific action by ID, applying its reverse operations and verifying the pre-action state
+    /// An action whose state could not be restored is marked Failed and may be rolled back again
+    pub fn rollback_action(&mut self, action_id: &str) -> Result<(), String> {
+        info!("AutoActionSynthesizer::rollback_action: Rolling back action {}", action_id);
+        
+        let reverse_ops = match self.executed_actions.get(action_id) {
+            Some(action) if matches!(action.state, ActionState::Completed | ActionState::Failed) => action.reverse_ops.clone(),
+            Some(_) => return Err("Action not in completed state".to_string()),
+            None => return Err("Action not found".to_string()),
+        };
+        
+        let outcome = self.apply_reverse_ops(&reverse_ops);
+        if let Some(action) = self.executed_actions.get_mut(action_id) {
+            match &outcome {
+                Ok(()) => {
                     action.state = ActionState::RolledBack;
                     action.rolled_back_at = Some(chrono::Utc::now().timestamp());
-                    Ok(())
-                } else {
-                    Err("Action not in completed state".to_string())
                 }
-            } else {
-                Err("Action not found".to_string())
+                Err(_) => action.state = ActionState::Failed,
             }
-        } else {
-            Err("No actions to rollback".to_string())
+        }
+        match outcome {
+            Ok(()) => self.journal_record(action_id, JournalRecord::RolledBack),
+            Err(e) => Err(format!("Rollback verification failed: {}", e)),
         }
     }
 
-    /// Rollback specific action by ID
-    pub fn rollback_action(&mut self, action_id: &str) -> Result<(), String> {
-        info!("AutoActionSynthesizer::rollback_action: Rolling back action {}", action_id);
+    /// Reverse operations recovery must apply for an interrupted action
+    /// Effects with a completed step ran; the effect after them may have run before the crash. Restores are idempotent,
+    /// but a macro inverse only runs if the directory no longer matches its pre-macro snapshot.
+    fn interrupted_reverse_ops(ops: &[ReverseOperation], completed_steps: &[String]) -> Vec<ReverseOperation> {
+        let applied = (0..ops.len()).take_while(|i| completed_steps.contains(&format!("effect_{}", i))).count();
+        let mut reverse: Vec<ReverseOperation> = ops[..applied].to_vec();
+        match ops.get(applied) {
+            Some(ReverseOperation::MacroInverse { work_dir, expected, .. }) if snapshot_dir(work_dir) == *expected => {}
+            Some(op) => reverse.push(op.clone()),
+            None => {}
+        }
+        reverse
+    }
+
+    /// Replay the journal after a crash: complete actions whose execution step finished,
+    /// roll back everything else by applying the journaled reverse operations
+    /// An action whose rollback fails is marked Failed and stays in flight so the next recovery retries it
+    pub fn recover_from_journal(&mut self) -> Result<RecoveryReport, String> {
+        let in_flight = match self.journal.as_ref() {
+            Some(journal) => journal.in_flight()?,
+            None => return Err("No journal attached".to_string()),
+        };
+        info!("AutoActionSynthesizer::recover_from_journal: Recovering {} in-flight actions", in_flight.len());
         
-        if let Some(action) = self.executed_actions.get_mut(action_id) {
-            if action.state == ActionState::Completed {
-                action.state = ActionState::RolledBack;
-                action.rolled_back_at = Some(chrono::Utc::now().timestamp());
-                Ok(())
+        let mut report = RecoveryReport {
+            completed: Vec::new(),
+            rolled_back: Vec::new(),
+            failed: Vec::new(),
+        };
+        
+        for entry in in_flight {
+            let Some(action) = entry.action else {
+                self.journal_record(&entry.action_id, JournalRecord::RolledBack)?;
+                report.rolled_back.push(entry.action_id);
+                continue;
+            };
+            
+            let executed = entry.completed_steps.iter().any(|s| s == "execute");
+            let now = chrono::Utc::now().timestamp();
+            
+            if executed && entry.sandbox_diff.is_some() {
+                self.journal_record(&entry.action_id, JournalRecord::Committed)?;
+                self.executed_actions.insert(entry.action_id.clone(), ExecutedAction {
+                    id: entry.action_id.clone(),
+                    action,
+                    state: ActionState::Completed,
+                    execution_result: None,
+                    rollback_diff: entry.sandbox_diff,
+                    reverse_ops: entry.reverse_ops,
+                    executed_at: Some(now),
+                    rolled_back_at: None,
+                    approval_chain: entry.approvals,
+                });
+                self.rollback_stack.push(entry.action_id.clone());
+                report.completed.push(entry.action_id);
             } else {
-                Err("Action not in completed state".to_string())
+                let reverse_ops = Self::interrupted_reverse_ops(&entry.reverse_ops, &entry.completed_steps);
+                info!("AutoActionSynthesizer::recover_from_journal: Rolling back {} ({} reverse operations)", entry.action_id, reverse_ops.len());
+                let outcome = self.apply_reverse_ops(&reverse_ops);
+                if let Err(e) = &outcome {
+                    warn!("AutoActionSynthesizer::recover_from_journal: Rollback of {} failed: {}", entry.action_id, e);
+                } else {
+                    self.journal_record(&entry.action_id, JournalRecord::RolledBack)?;
+                }
+                self.executed_actions.insert(entry.action_id.clone(), ExecutedAction {
+                    id: entry.action_id.clone(),
+                    action,
+                    state: if outcome.is_ok() { ActionState::RolledBack } else { ActionState::Failed },
+                    execution_result: None,
+                    rollback_diff: entry.sandbox_diff,
+                    reverse_ops: entry.reverse_ops,
+                    executed_at: None,
+                    rolled_back_at: outcome.is_ok().then_some(now),
+                    approval_chain: entry.approvals,
+                });
+                match outcome {
+                    Ok(()) => report.rolled_back.push(entry.action_id),
+                    Err(_) => report.failed.push(entry.action_id),
+                }
             }
-        } else {
-            Err("Action not found".to_string())
         }
+        
+        Ok(report)
     }
 
     /// Get execution history
@@ -132,6 +609,11 @@ impl AutoActionSynthesizer {
             .filter_map(|id| self.executed_actions.get(id))
             .collect()
     }
+
+    /// Every executed action, including those since rolled back
+    pub fn get_all_executed(&self) -> Vec<&ExecutedAction> {
+        self.executed_actions.values().collect()
+    }
 }
 
 impl Default for AutoActionSynthesizer {
@@ -145,6 +627,13 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    fn consented(mut synthesizer: AutoActionSynthesizer) -> AutoActionSynthesizer {
+        let mut ledger = ConsentLedger::new();
+        ledger.automation_scopes = ActionType::all();
+        synthesizer.apply_consent(&ledger);
+        synthesizer
+    }
+
     #[test]
     fn test_auto_action_synthesizer_creation() {
         let synthesizer = AutoActionSynthesizer::new();
@@ -153,7 +642,7 @@ mod tests {
 
     #[test]
     fn test_synthesize_and_execute_safe_action() {
-        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
         let observation = Observation {
             id: "test_001".to_string(),
             profile: UserProfile::Developer,
@@ -161,14 +650,15 @@ mod tests {
             metrics: HashMap::new(),
             intent: Intent::AutomateAction,
             action: Action {
-                action_type: ActionType::AutomationMacro,
-                description: "Safe macro".to_string(),
+                action_type: ActionType::FocusMode,
+                description: "Safe focus mode".to_string(),
                 confidence: Confidence::High,
                 risk: RiskCategory::None,
             },
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let result = synthesizer.synthesize_and_execute(&observation);
@@ -176,11 +666,17 @@ mod tests {
         let executed = result.unwrap();
         assert_eq!(executed.state, ActionState::Completed);
         assert!(executed.rollback_diff.is_some());
+        
+        // A macro is tested by running its commands, so one without any cannot pass the sandbox
+        let mut macro_observation = observation.clone();
+        macro_observation.id = "test_001_macro".to_string();
+        macro_observation.action.action_type = ActionType::AutomationMacro;
+        assert!(synthesizer.synthesize_and_execute(&macro_observation).unwrap_err().contains("no commands"));
     }
 
     #[test]
     fn test_rollback_last_action() {
-        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
         let observation = Observation {
             id: "test_002".to_string(),
             profile: UserProfile::Developer,
@@ -188,7 +684,7 @@ mod tests {
             metrics: HashMap::new(),
             intent: Intent::AutomateAction,
             action: Action {
-                action_type: ActionType::AutomationMacro,
+                action_type: ActionType::FocusMode,
                 description: "Test".to_string(),
                 confidence: Confidence::High,
                 risk: RiskCategory::None,
@@ -196,6 +692,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         synthesizer.synthesize_and_execute(&observation).unwrap();
@@ -206,9 +703,44 @@ mod tests {
         assert_eq!(action.state, ActionState::RolledBack);
     }
 
+    #[test]
+    fn test_gate_policy_blocks_frequently_rolled_back_types() {
+        use crate::gate_policy::{GatePolicy, SharedGatePolicy};
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let policy = SharedGatePolicy::default();
+        synthesizer.set_gate_policy(policy.clone());
+        let observation = |id: &str| Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["App1".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        for id in ["test_a", "test_b"] {
+            synthesizer.synthesize_and_execute(&observation(id)).unwrap();
+            synthesizer.rollback_last().unwrap();
+        }
+        assert!(synthesizer.synthesize_and_execute(&observation("test_c")).is_ok());
+        
+        // Tightening the shared policy applies without touching the synthesizer
+        policy.set(GatePolicy { min_acceptance_rate: 0.5, min_acceptance_samples: 3, ..GatePolicy::default() }).unwrap();
+        let err = synthesizer.synthesize_and_execute(&observation("test_d")).unwrap_err();
+        assert!(err.contains("Acceptance rate 0.33"));
+    }
+
     #[test]
     fn test_unsafe_action_rejected() {
-        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
         let observation = Observation {
             id: "test_003".to_string(),
             profile: UserProfile::Developer,
@@ -224,10 +756,327 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let result = synthesizer.synthesize_and_execute(&observation);
         assert!(result.is_err());
     }
-}
 
+    #[test]
+    fn test_action_type_consent_enforced() {
+        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut ledger = ConsentLedger::new();
+        ledger.grant_automation_scope(ActionType::FocusMode);
+        synthesizer.apply_consent(&ledger);
+        
+        let mut observation = Observation {
+            id: "test_006".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Dev startup macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        assert!(synthesizer.synthesize_and_execute(&observation).unwrap_err().contains("consent"));
+        
+        observation.action.action_type = ActionType::FocusMode;
+        assert!(synthesizer.synthesize_and_execute(&observation).is_ok());
+    }
+
+    #[test]
+    fn test_recover_in_flight_actions_from_journal() {
+        let path = std::env::temp_dir().join(format!("athenos_auto_action_journal_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let action = Action {
+            action_type: ActionType::AutomationMacro,
+            description: "Recovered macro".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+        };
+        
+        // Simulate a crash: one action executed but not committed, one only diffed
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("action_a", JournalRecord::Intent { action: action.clone() }).unwrap();
+        journal.append("action_a", JournalRecord::SandboxDiff { diff: "Undo a".to_string() }).unwrap();
+        journal.append("action_a", JournalRecord::StepCompleted { step: "execute".to_string() }).unwrap();
+        journal.append("action_b", JournalRecord::Intent { action }).unwrap();
+        journal.append("action_b", JournalRecord::SandboxDiff { diff: "Undo b".to_string() }).unwrap();
+        
+        let mut synthesizer = consented(AutoActionSynthesizer::with_journal(ActionJournal::open(path.clone()).unwrap()));
+        let report = synthesizer.recover_from_journal().unwrap();
+        assert_eq!(report.completed, vec!["action_a".to_string()]);
+        assert_eq!(report.rolled_back, vec!["action_b".to_string()]);
+        assert_eq!(synthesizer.executed_actions.get("action_b").unwrap().state, ActionState::RolledBack);
+        
+        // Recovery is idempotent
+        let report = synthesizer.recover_from_journal().unwrap();
+        assert!(report.completed.is_empty() && report.rolled_back.is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_risky_action_deferred_during_low_focus() {
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let now = chrono::Utc::now().timestamp();
+        synthesizer.update_forecast(vec![ForecastPoint {
+            start: now - 60,
+            end: now + 3600,
+            stress: 0.8,
+            fatigue: 0.5,
+            low_focus: true,
+        }]);
+        let observation = Observation {
+            id: "hygiene_001".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["no_shutdown".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::SystemHygiene,
+                description: "Clear temp files".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        let result = synthesizer.synthesize_and_execute(&observation);
+        assert!(result.unwrap_err().starts_with("Deferred"));
+        assert!(synthesizer.take_ready_deferred(now).is_empty());
+        assert_eq!(synthesizer.take_ready_deferred(now + 7200).len(), 1);
+    }
+
+    #[test]
+    fn test_two_person_approval_for_high_risk() {
+        let path = std::env::temp_dir().join(format!("athenos_two_person_journal_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut synthesizer = consented(AutoActionSynthesizer::with_journal(ActionJournal::open(path.clone()).unwrap()));
+        let observation = Observation {
+            id: "test_005".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Terminal".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::SandboxPatch,
+                description: "Patch production config".to_string(),
+                confidence: Confidence::Medium,
+                risk: RiskCategory::High,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        assert!(synthesizer.approve_action("test_005", "alice", ApproverRole::User).is_err());
+        synthesizer.set_approval_mode(ApprovalMode::TwoPerson);
+        
+        synthesizer.approve_action("test_005", "alice", ApproverRole::User).unwrap();
+        assert!(synthesizer.approve_action("test_005", "alice", ApproverRole::Admin).is_err());
+        assert!(synthesizer.synthesize_and_execute(&observation).unwrap_err().contains("Two-person"));
+        
+        synthesizer.approve_action("test_005", "bob", ApproverRole::Admin).unwrap();
+        let executed = synthesizer.synthesize_and_execute(&observation).unwrap();
+        assert_eq!(executed.approval_chain.len(), 2);
+        
+        let journal = ActionJournal::open(path.clone()).unwrap();
+        let approved = journal
+            .read_entries()
+            .unwrap()
+            .into_iter()
+            .filter(|e| matches!(e.record, JournalRecord::Approved { .. }))
+            .count();
+        assert_eq!(approved, 2);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_approved_high_risk_macro_passes_sandbox() {
+        let dir = std::env::temp_dir().join(format!("athenos_approved_macro_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let effect = |file: &str| ActionEffect::RunMacro {
+            work_dir: dir.clone(),
+            commands: vec![MacroCommand::new("touch", &[file])],
+            inverse: vec![MacroCommand::new("rm", &[file])],
+        };
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let mut observation = effect_observation("test_approved_macro");
+        observation.action.action_type = ActionType::AutomationMacro;
+        observation.action.risk = RiskCategory::High;
+        assert!(synthesizer.synthesize_and_execute_with_effects(&observation, &[effect("first.txt")]).is_err());
+
+        let executed = synthesizer.execute_approved_with_effects(&observation, &[effect("first.txt")], "alice", ApproverRole::User).unwrap().unwrap();
+        assert!(executed.execution_result.unwrap().success);
+        assert_eq!(executed.approval_chain.len(), 1);
+        assert!(dir.join("first.txt").exists());
+
+        // A complete two-person chain carries the macro through the sandbox too
+        synthesizer.set_approval_mode(ApprovalMode::TwoPerson);
+        let mut second = observation.clone();
+        second.id = "test_two_person_macro".to_string();
+        assert!(synthesizer.execute_approved_with_effects(&second, &[effect("second.txt")], "alice", ApproverRole::User).unwrap().is_none());
+        let executed = synthesizer.execute_approved_with_effects(&second, &[effect("second.txt")], "bob", ApproverRole::Admin).unwrap().unwrap();
+        assert!(executed.execution_result.unwrap().success);
+        assert_eq!(executed.approval_chain.len(), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    fn effect_observation(id: &str) -> Observation {
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Project setup".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_rollback_restores_file_and_setting() {
+        let dir = std::env::temp_dir().join(format!("athenos_rollback_file_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("config.toml");
+        let created = dir.join("notes.md");
+        std::fs::write(&existing, "theme = \"light\"").unwrap();
+        let _ = std::fs::remove_file(&created);
+        
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let effects = vec![
+            ActionEffect::WriteFile { path: existing.clone(), content: b"theme = \"dark\"".to_vec() },
+            ActionEffect::WriteFile { path: created.clone(), content: b"# Today".to_vec() },
+            ActionEffect::SetSetting { key: "dnd".to_string(), value: "on".to_string() },
+        ];
+        let executed = synthesizer.synthesize_and_execute_with_effects(&effect_observation("fx_001"), &effects).unwrap();
+        assert_eq!(executed.reverse_ops.len(), 3);
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "theme = \"dark\"");
+        assert_eq!(synthesizer.setting("dnd").map(String::as_str), Some("on"));
+        
+        synthesizer.rollback_action("action_fx_001").unwrap();
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "theme = \"light\"");
+        assert!(!created.exists());
+        assert!(synthesizer.setting("dnd").is_none());
+        assert_eq!(synthesizer.executed_actions["action_fx_001"].state, ActionState::RolledBack);
+        assert!(synthesizer.rollback_action("action_fx_001").is_err());
+        
+        // Two writes to one file: each restore is verified as it runs, so the later one doesn't fail the rollback
+        let twice = vec![
+            ActionEffect::WriteFile { path: existing.clone(), content: b"theme = \"dark\"".to_vec() },
+            ActionEffect::WriteFile { path: existing.clone(), content: b"theme = \"solarized\"".to_vec() },
+        ];
+        synthesizer.synthesize_and_execute_with_effects(&effect_observation("fx_004"), &twice).unwrap();
+        synthesizer.rollback_action("action_fx_004").unwrap();
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "theme = \"light\"");
+        
+        // An effect whose reverse cannot be captured undoes the effects already applied
+        let unreadable = vec![
+            ActionEffect::SetSetting { key: "dnd".to_string(), value: "on".to_string() },
+            ActionEffect::WriteFile { path: dir.clone(), content: Vec::new() },
+        ];
+        assert!(synthesizer.synthesize_and_execute_with_effects(&effect_observation("fx_005"), &unreadable).is_err());
+        assert!(synthesizer.setting("dnd").is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_macro_inverse_verified_against_pre_action_state() {
+        let dir = std::env::temp_dir().join(format!("athenos_rollback_macro_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        
+        let undoable = ActionEffect::RunMacro {
+            work_dir: dir.clone(),
+            commands: vec![MacroCommand::new("mkdir", &["build"]), MacroCommand::new("touch", &["build/out.log"])],
+            inverse: vec![MacroCommand::new("rm", &["-r", "build"])],
+        };
+        synthesizer.synthesize_and_execute_with_effects(&effect_observation("fx_002"), &[undoable]).unwrap();
+        assert!(dir.join("build/out.log").exists());
+        synthesizer.rollback_last().unwrap();
+        assert!(!dir.join("build").exists());
+        
+        // An inverse that leaves files behind fails verification
+        let incomplete = ActionEffect::RunMacro {
+            work_dir: dir.clone(),
+            commands: vec![MacroCommand::new("touch", &["a.txt", "b.txt"])],
+            inverse: vec![MacroCommand::new("rm", &["a.txt"])],
+        };
+        synthesizer.synthesize_and_execute_with_effects(&effect_observation("fx_003"), &[incomplete]).unwrap();
+        let error = synthesizer.rollback_last().unwrap_err();
+        assert!(error.contains("verification failed"));
+        assert_eq!(synthesizer.executed_actions["action_fx_003"].state, ActionState::Failed);
+        assert_eq!(synthesizer.rollback_stack.last().map(String::as_str), Some("action_fx_003")); // Kept for a retry
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recovery_applies_journaled_reverse_ops() {
+        let path = std::env::temp_dir().join(format!("athenos_reverse_journal_{}.jsonl", std::process::id()));
+        let file = std::env::temp_dir().join(format!("athenos_reverse_target_{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        std::fs::write(&file, "original").unwrap();
+        
+        // Crash after the reverse op was journaled and the write happened, before "execute" completed
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("action_c", JournalRecord::Intent { action: effect_observation("c").action }).unwrap();
+        journal.append("action_c", JournalRecord::ReverseOp {
+            op: ReverseOperation::FileRestore { path: file.clone(), original: Some(b"original".to_vec()) },
+        }).unwrap();
+        std::fs::write(&file, "half-written").unwrap();
+        
+        let mut synthesizer = consented(AutoActionSynthesizer::with_journal(ActionJournal::open(path.clone()).unwrap()));
+        let report = synthesizer.recover_from_journal().unwrap();
+        assert_eq!(report.rolled_back, vec!["action_c".to_string()]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "original");
+        
+        // Crash after the write completed but before the macro ran: the write is undone, the macro inverse is not run
+        let dir = std::env::temp_dir().join(format!("athenos_reverse_macro_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("keep.txt"), "keep").unwrap();
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("action_d", JournalRecord::Intent { action: effect_observation("d").action }).unwrap();
+        journal.append("action_d", JournalRecord::ReverseOp {
+            op: ReverseOperation::FileRestore { path: file.clone(), original: Some(b"original".to_vec()) },
+        }).unwrap();
+        std::fs::write(&file, "written").unwrap();
+        journal.append("action_d", JournalRecord::StepCompleted { step: "effect_0".to_string() }).unwrap();
+        journal.append("action_d", JournalRecord::ReverseOp {
+            op: ReverseOperation::MacroInverse { work_dir: dir.clone(), commands: vec![MacroCommand::new("rm", &["keep.txt"])], expected: snapshot_dir(&dir) },
+        }).unwrap();
+        
+        let mut synthesizer = consented(AutoActionSynthesizer::with_journal(ActionJournal::open(path.clone()).unwrap()));
+        let report = synthesizer.recover_from_journal().unwrap();
+        assert_eq!(report.rolled_back, vec!["action_d".to_string()]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "original");
+        assert!(dir.join("keep.txt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&file);
+    }
+}
@@ -0,0 +1,396 @@
+/// Phase: D | Source: athenos-rules.mdc#L12-15
+/// End-to-End Encrypted Cloud Backup (opt-in)
+/// Client-side encrypted snapshot backups to a user-supplied S3/WebDAV endpoint; nothing readable server-side
+
+use crate::dataset;
+use crate::local_stack::FeatureStore;
+use crate::privacy::{ConsentLedger, EncryptionManager};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+pub mod transport;
+
+pub use transport::BackupCredentials;
+
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// Backup endpoint kind
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupEndpointKind {
+    S3,
+    WebDav,
+}
+
+/// Backup configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    pub endpoint_kind: BackupEndpointKind,
+    pub endpoint_url: String,
+    pub interval_secs: i64,
+    pub retain: usize, // Number of backups kept on the endpoint
+    pub kdf_iterations: u32,
+    pub s3_region: String, // SigV4 signing region (S3 only)
+}
+
+impl BackupConfig {
+    /// Daily backups, keep the last 7
+    pub fn new(endpoint_kind: BackupEndpointKind, endpoint_url: String) -> Self {
+        Self {
+            endpoint_kind,
+            endpoint_url,
+            interval_secs: 24 * 3600,
+            retain: 7,
+            kdf_iterations: 100_000,
+            s3_region: "us-east-1".to_string(),
+        }
+    }
+
+    /// Pick the backend from the URL scheme: `s3://host/bucket` for S3, `webdav://` or plain `https://` for WebDAV
+    /// Both are reached over https
+    pub fn from_url(url: &str) -> Result<Self, String> {
+        let (kind, rest) = match url.split_once("://") {
+            Some(("s3", rest)) => (BackupEndpointKind::S3, rest),
+            Some(("webdav" | "davs" | "https", rest)) => (BackupEndpointKind::WebDav, rest),
+            _ => return Err(format!("Unsupported backup URL {} (use s3://, webdav:// or https://)", url)),
+        };
+        Ok(Self::new(kind, format!("https://{}", rest)))
+    }
+}
+
+/// Remote object store holding encrypted backups
+pub trait BackupTransport: Send {
+    /// Upload an object
+    fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String>;
+    /// Download an object
+    fn get(&self, name: &str) -> Result<Vec<u8>, String>;
+    /// Delete an object
+    fn delete(&mut self, name: &str) -> Result<(), String>;
+}
+
+/// Create the HTTPS transport for a configured endpoint
+#[cfg(feature = "cloud-backup")]
+pub fn transport_for(config: &BackupConfig, credentials: BackupCredentials) -> Result<Box<dyn BackupTransport>, String> {
+    let http = Box::new(transport::http::HttpClient::new());
+    match config.endpoint_kind {
+        BackupEndpointKind::S3 => Ok(Box::new(transport::S3Transport::new(config.endpoint_url.clone(), credentials, config.s3_region.clone(), http)?)),
+        BackupEndpointKind::WebDav => Ok(Box::new(transport::WebDavTransport::new(config.endpoint_url.clone(), credentials, http)?)),
+    }
+}
+
+#[cfg(not(feature = "cloud-backup"))]
+pub fn transport_for(config: &BackupConfig, _credentials: BackupCredentials) -> Result<Box<dyn BackupTransport>, String> {
+    Err(format!("Cannot back up to {}: built without the cloud-backup feature", config.endpoint_url))
+}
+
+/// Local record of an uploaded backup (digest never leaves the device)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRecord {
+    pub name: String,
+    pub created_at: i64,
+    pub size_bytes: usize,
+    pub sha256: String, // Of the plaintext archive
+}
+
+/// Salt and backup records kept on the device, so backups stay restorable across restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BackupManifest {
+    salt: Vec<u8>,
+    records: Vec<BackupRecord>,
+}
+
+/// Build the snapshot archive (JSONL export of all observations)
+pub fn snapshot_archive(store: &FeatureStore) -> Result<Vec<u8>, String> {
+    let mut archive = Vec::new();
+    dataset::export_jsonl(&mut archive, store.get_observations())?;
+    Ok(archive)
+}
+
+/// Client-side encrypted backup manager
+/// Objects are `salt || nonce || secretbox(archive)`; the key is derived locally from the passphrase
+pub struct CloudBackupManager {
+    config: BackupConfig,
+    transport: Box<dyn BackupTransport>,
+    salt: Vec<u8>,
+    encryption: Option<EncryptionManager>,
+    records: Vec<BackupRecord>,
+    manifest_path: Option<PathBuf>, // Salt and records are saved here after every change when set
+}
+
+impl CloudBackupManager {
+    /// Create backup manager with a fresh random salt (locked until `unlock`)
+    pub fn new(config: BackupConfig, transport: Box<dyn BackupTransport>) -> Self {
+        use rand::Rng;
+        let salt: [u8; SALT_LEN] = rand::thread_rng().gen();
+        Self::with_salt(config, transport, salt.to_vec())
+    }
+
+    /// Create backup manager with a previously stored salt (same passphrase -> same key)
+    pub fn with_salt(config: BackupConfig, transport: Box<dyn BackupTransport>, salt: Vec<u8>) -> Self {
+        info!("CloudBackupManager::new: Creating encrypted backup manager ({:?})", config.endpoint_kind);
+        Self {
+            config,
+            transport,
+            salt,
+            encryption: None,
+            records: Vec::new(),
+            manifest_path: None,
+        }
+    }
+
+    /// Open the manager whose salt and records are kept at `manifest_path`, creating the manifest on first use
+    pub fn open(config: BackupConfig, transport: Box<dyn BackupTransport>, manifest_path: PathBuf) -> Result<Self, String> {
+        let mut manager = match std::fs::read_to_string(&manifest_path) {
+            Ok(json) => {
+                let manifest: BackupManifest = serde_json::from_str(&json).map_err(|e| format!("Invalid backup manifest {}: {}", manifest_path.display(), e))?;
+                if manifest.salt.len() != SALT_LEN {
+                    return Err(format!("Backup manifest {} has an invalid salt", manifest_path.display()));
+                }
+                let mut manager = Self::with_salt(config, transport, manifest.salt);
+                manager.records = manifest.records;
+                manager
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::new(config, transport),
+            Err(e) => return Err(format!("Failed to read backup manifest {}: {}", manifest_path.display(), e)),
+        };
+        manager.manifest_path = Some(manifest_path);
+        manager.save_manifest()?;
+        info!("CloudBackupManager::open: {} backups on record", manager.records.len());
+        Ok(manager)
+    }
+
+    fn save_manifest(&self) -> Result<(), String> {
+        let Some(path) = &self.manifest_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let manifest = BackupManifest { salt: self.salt.clone(), records: self.records.clone() };
+        let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to encode backup manifest: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Derive the backup key from a passphrase (PBKDF2-HMAC-SHA256, on device)
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), String> {
+        if passphrase.len() < 8 {
+            return Err("Backup passphrase must be at least 8 characters".to_string());
+        }
+        let iterations = NonZeroU32::new(self.config.kdf_iterations).ok_or("KDF iterations must be non-zero")?;
+        let mut key = [0u8; KEY_LEN];
+        ring::pbkdf2::derive(ring::pbkdf2::PBKDF2_HMAC_SHA256, iterations, &self.salt, passphrase.as_bytes(), &mut key);
+        self.encryption = Some(EncryptionManager::from_key(&key)?);
+        info!("CloudBackupManager::unlock: Backup key derived");
+        Ok(())
+    }
+
+    fn sha256_hex(data: &[u8]) -> String {
+        ring::digest::digest(&ring::digest::SHA256, data)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Encrypt and upload an archive; requires cloud_sync opt-in
+    pub fn backup(&mut self, consent: &ConsentLedger, archive: &[u8], now: i64) -> Result<BackupRecord, String> {
+        if !consent.can_sync_to_cloud() {
+            return Err("Cloud backup requires cloud_sync opt-in".to_string());
+        }
+        let encryption = self.encryption.as_ref().ok_or("Backup key locked; call unlock first")?;
+
+        let mut object = self.salt.clone();
+        object.extend(encryption.encrypt(archive)?);
+        let name = format!("athenos-backup-{}.bin", now);
+        self.transport.put(&name, &object)?;
+
+        let record = BackupRecord {
+            name,
+            created_at: now,
+            size_bytes: archive.len(),
+            sha256: Self::sha256_hex(archive),
+        };
+        info!("CloudBackupManager::backup: Uploaded {} ({} bytes encrypted)", record.name, object.len());
+        self.records.push(record.clone());
+        self.save_manifest()?;
+        if let Err(e) = self.prune() {
+            warn!("CloudBackupManager::backup: Pruning deferred to the next backup: {}", e);
+        }
+        Ok(record)
+    }
+
+    /// Drop the oldest backups beyond the retention count
+    /// A record is forgotten only once its object is deleted, so a failed delete is retried next time
+    fn prune(&mut self) -> Result<(), String> {
+        while self.records.len() > self.config.retain {
+            info!("CloudBackupManager::prune: Removing {}", self.records[0].name);
+            self.transport.delete(&self.records[0].name)?;
+            self.records.remove(0);
+            self.save_manifest()?;
+        }
+        Ok(())
+    }
+
+    /// Whether a scheduled backup is due
+    pub fn is_due(&self, now: i64) -> bool {
+        self.records.last().map(|r| now - r.created_at >= self.config.interval_secs).unwrap_or(true)
+    }
+
+    /// Back up if the interval has elapsed
+    pub fn run_scheduled(&mut self, consent: &ConsentLedger, archive: &[u8], now: i64) -> Result<Option<BackupRecord>, String> {
+        if !self.is_due(now) {
+            return Ok(None);
+        }
+        self.backup(consent, archive, now).map(Some)
+    }
+
+    /// Download, decrypt and verify a backup against its local digest
+    pub fn restore(&self, name: &str) -> Result<Vec<u8>, String> {
+        let record = self.records.iter().find(|r| r.name == name).ok_or_else(|| format!("No local record for backup {}", name))?;
+        let encryption = self.encryption.as_ref().ok_or("Backup key locked; call unlock first")?;
+
+        let object = self.transport.get(name)?;
+        if object.len() < SALT_LEN || object[..SALT_LEN] != self.salt[..] {
+            return Err(format!("Backup {} was encrypted with a different salt", name));
+        }
+        let archive = encryption.decrypt(&object[SALT_LEN..])?;
+        if Self::sha256_hex(&archive) != record.sha256 {
+            return Err(format!("Backup {} failed digest verification", name));
+        }
+        info!("CloudBackupManager::restore: Restored and verified {}", name);
+        Ok(archive)
+    }
+
+    /// Verify the latest backup can be restored
+    pub fn verify_latest(&self) -> Result<BackupRecord, String> {
+        let latest = self.records.last().ok_or("No backups recorded")?;
+        self.restore(&latest.name)?;
+        Ok(latest.clone())
+    }
+
+    /// Get local backup records (oldest first)
+    pub fn get_records(&self) -> &[BackupRecord] {
+        &self.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Object store shared with the test after the manager takes ownership; deletes can be made to fail
+    #[derive(Clone, Default)]
+    struct SharedTransport {
+        objects: std::sync::Arc<std::sync::Mutex<HashMap<String, Vec<u8>>>>,
+        fail_deletes: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl BackupTransport for SharedTransport {
+        fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+            self.objects.lock().unwrap().insert(name.to_string(), data.to_vec());
+            Ok(())
+        }
+
+        fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+            self.objects.lock().unwrap().get(name).cloned().ok_or_else(|| format!("Object {} not found", name))
+        }
+
+        fn delete(&mut self, name: &str) -> Result<(), String> {
+            if self.fail_deletes.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err("endpoint unavailable".to_string());
+            }
+            self.objects.lock().unwrap().remove(name);
+            Ok(())
+        }
+    }
+
+    fn manager() -> CloudBackupManager {
+        let config = BackupConfig { kdf_iterations: 1_000, retain: 2, ..BackupConfig::new(BackupEndpointKind::S3, "https://s3.example.com/athenos".to_string()) };
+        CloudBackupManager::with_salt(config, Box::new(SharedTransport::default()), vec![7u8; SALT_LEN])
+    }
+
+    fn opted_in() -> ConsentLedger {
+        let mut consent = ConsentLedger::new();
+        consent.opt_in_cloud_sync = true;
+        consent
+    }
+
+    #[test]
+    fn test_backup_requires_opt_in_and_key() {
+        let mut backups = manager();
+        assert!(backups.backup(&ConsentLedger::new(), b"archive", 0).is_err());
+        assert!(backups.backup(&opted_in(), b"archive", 0).is_err()); // Locked
+        assert!(backups.unlock("short").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip_and_retention() {
+        let mut backups = manager();
+        backups.unlock("correct horse battery").unwrap();
+        let consent = opted_in();
+
+        let record = backups.backup(&consent, b"{\"id\":\"obs1\"}\n", 1_000).unwrap();
+        let stored = backups.transport.get(&record.name).unwrap();
+        assert!(!stored.windows(4).any(|w| w == b"obs1")); // Nothing readable server-side
+        assert_eq!(backups.restore(&record.name).unwrap(), b"{\"id\":\"obs1\"}\n");
+
+        assert!(backups.run_scheduled(&consent, b"later", 2_000).unwrap().is_none());
+        backups.run_scheduled(&consent, b"day2", 1_000 + 24 * 3600).unwrap().unwrap();
+        backups.run_scheduled(&consent, b"day3", 1_000 + 48 * 3600).unwrap().unwrap();
+        assert_eq!(backups.get_records().len(), 2);
+        assert!(backups.transport.get(&record.name).is_err());
+        assert_eq!(backups.verify_latest().unwrap().size_bytes, 4);
+    }
+
+    #[test]
+    fn test_manifest_survives_restart_and_prune_keeps_undeleted_records() {
+        let path = std::env::temp_dir().join(format!("athenos_backup_manifest_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = BackupConfig { kdf_iterations: 1_000, retain: 1, ..BackupConfig::from_url("webdav://dav.example.com/athenos").unwrap() };
+        let transport = SharedTransport::default();
+
+        let mut backups = CloudBackupManager::open(config.clone(), Box::new(transport.clone()), path.clone()).unwrap();
+        backups.unlock("correct horse battery").unwrap();
+        let first = backups.backup(&opted_in(), b"day1", 0).unwrap();
+
+        // After a restart the same passphrase restores the backup
+        let mut reopened = CloudBackupManager::open(config.clone(), Box::new(transport.clone()), path.clone()).unwrap();
+        reopened.unlock("correct horse battery").unwrap();
+        assert_eq!(reopened.restore(&first.name).unwrap(), b"day1");
+        assert!(!reopened.is_due(3600));
+
+        transport.fail_deletes.store(true, std::sync::atomic::Ordering::SeqCst);
+        reopened.backup(&opted_in(), b"day2", 24 * 3600).unwrap();
+        assert_eq!(reopened.get_records().len(), 2);
+        transport.fail_deletes.store(false, std::sync::atomic::Ordering::SeqCst);
+        reopened.backup(&opted_in(), b"day3", 48 * 3600).unwrap();
+        assert_eq!(reopened.get_records().len(), 1);
+        assert_eq!(transport.objects.lock().unwrap().len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_backend_chosen_by_scheme() {
+        let s3 = BackupConfig::from_url("s3://s3.example.com/bucket").unwrap();
+        assert_eq!((s3.endpoint_kind, s3.endpoint_url.as_str()), (BackupEndpointKind::S3, "https://s3.example.com/bucket"));
+        assert_eq!(BackupConfig::from_url("https://dav.example.com/s3-archive").unwrap().endpoint_kind, BackupEndpointKind::WebDav);
+        assert!(BackupConfig::from_url("http://dav.example.com").is_err());
+        assert!(BackupConfig::from_url("ftp://example.com").is_err());
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails_restore() {
+        let mut backups = manager();
+        backups.unlock("correct horse battery").unwrap();
+        let record = backups.backup(&opted_in(), b"archive", 0).unwrap();
+
+        backups.unlock("wrong passphrase!").unwrap();
+        assert!(backups.restore(&record.name).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+/// Phase: C | Step: 10 | Source: Athenos_AI_Strategy.md#L129
+/// Beta Feedback Themes
+/// Keyword tags plus embedding clustering, so recurring issues surface without reading every comment
+
+use super::{BetaFeedback, FeedbackType};
+use crate::rag::{cosine_similarity, EmbeddingProvider, HashingEmbedder};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{info, warn};
+
+/// Tag -> keyword stems; a token starting with a stem carries the tag
+const TAG_KEYWORDS: [(&str, &[&str]); 8] = [
+    ("performance", &["slow", "lag", "cpu", "battery", "memory", "freez", "sluggish", "fan"]),
+    ("crash", &["crash", "panic", "hang", "stuck", "restart"]),
+    ("notifications", &["notif", "nudge", "popup", "interrupt", "ping", "alert"]),
+    ("privacy", &["privacy", "private", "consent", "track", "data", "permission"]),
+    ("onboarding", &["onboard", "setup", "install", "tutorial", "signup"]),
+    ("automation", &["shortcut", "automat", "macro", "rollback"]),
+    ("focus", &["focus", "distract", "dnd", "concentrat", "deep"]),
+    ("ui", &["button", "screen", "confus", "layout", "dark", "font", "menu", "design"]),
+];
+
+/// Words that carry no theme and would otherwise dominate short comments
+const STOP_WORDS: [&str; 40] = [
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "can", "do", "for", "from", "i", "if", "in", "is", "it", "its",
+    "me", "my", "of", "on", "or", "so", "that", "the", "this", "to", "too", "very", "was", "when", "with", "would", "you",
+    "just", "really", "after", "every",
+];
+
+/// Lowercased content words
+fn content_tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|token| token.to_lowercase())
+        .filter(|token| token.len() > 1 && !STOP_WORDS.contains(&token.as_str()))
+        .collect()
+}
+
+/// Theme tags mentioned in a feedback text, sorted
+pub fn extract_tags(text: &str) -> Vec<String> {
+    let tokens = content_tokens(text);
+    let mut tags: Vec<String> = TAG_KEYWORDS
+        .iter()
+        .filter(|(_, stems)| tokens.iter().any(|token| stems.iter().any(|stem| token.starts_with(stem))))
+        .map(|(tag, _)| tag.to_string())
+        .collect();
+    tags.sort();
+    tags
+}
+
+/// Group of similar feedback items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackCluster {
+    pub id: usize,
+    pub label: String,                     // Dominant tags, or frequent words when untagged
+    pub tags: HashMap<String, usize>,      // Tag -> items carrying it
+    pub count: usize,                      // Items in the cluster
+    pub user_count: usize,                 // Distinct users reporting it
+    pub types: HashMap<String, usize>,     // FeedbackType -> items
+    pub representative: String,            // First item's text
+    pub avg_rating: Option<f64>,
+    #[serde(skip)]
+    centroid: Vec<f32>,
+    #[serde(skip)]
+    users: HashSet<String>,
+    #[serde(skip)]
+    words: HashMap<String, usize>,
+    #[serde(skip)]
+    ratings: Vec<u8>,
+}
+
+impl FeedbackCluster {
+    /// Whether any item was something other than praise
+    pub fn is_issue(&self) -> bool {
+        self.types.iter().any(|(kind, count)| *count > 0 && kind != &format!("{:?}", FeedbackType::PositiveFeedback))
+    }
+
+    fn add(&mut self, feedback: &BetaFeedback, tags: &[String], embedding: &[f32]) {
+        let weight = self.count as f32;
+        for (c, e) in self.centroid.iter_mut().zip(embedding) {
+            *c = (*c * weight + e) / (weight + 1.0);
+        }
+        self.count += 1;
+        self.users.insert(feedback.user_id.clone());
+        self.user_count = self.users.len();
+        for tag in tags {
+            *self.tags.entry(tag.clone()).or_insert(0) += 1;
+        }
+        *self.types.entry(format!("{:?}", feedback.feedback_type)).or_insert(0) += 1;
+        for word in content_tokens(&feedback.content) {
+            *self.words.entry(word).or_insert(0) += 1;
+        }
+        if let Some(rating) = feedback.rating {
+            self.ratings.push(rating);
+            self.avg_rating = Some(self.ratings.iter().map(|r| *r as f64).sum::<f64>() / self.ratings.len() as f64);
+        }
+        self.label = self.build_label();
+    }
+
+    /// Up to three most common tags (ties alphabetical), else the three most common words
+    fn build_label(&self) -> String {
+        let top = |counts: &HashMap<String, usize>| {
+            let mut ranked: Vec<(&String, &usize)> = counts.iter().collect();
+            ranked.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            ranked.into_iter().take(3).map(|(k, _)| k.as_str()).collect::<Vec<&str>>().join(", ")
+        };
+        if self.tags.is_empty() { top(&self.words) } else { top(&self.tags) }
+    }
+}
+
+/// Online clustering of beta feedback by embedding similarity
+pub struct FeedbackClusterer {
+    provider: Box<dyn EmbeddingProvider>,
+    similarity_threshold: f32, // Minimum cosine similarity to join an existing cluster
+    clusters: Vec<FeedbackCluster>,
+}
+
+impl FeedbackClusterer {
+    pub fn new(provider: Box<dyn EmbeddingProvider>, similarity_threshold: f32) -> Self {
+        info!("FeedbackClusterer::new: Creating feedback clusterer ({}, threshold {})", provider.provider_name(), similarity_threshold);
+        Self { provider, similarity_threshold, clusters: Vec::new() }
+    }
+
+    /// Tag a feedback item and add it to the closest cluster sharing its tags, or start a new one
+    /// Returns the tags and cluster id
+    pub fn add(&mut self, feedback: &BetaFeedback) -> (Vec<String>, usize) {
+        let tags = extract_tags(&feedback.content);
+        let embedding = match self.provider.embed(&content_tokens(&feedback.content).join(" ")) {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                warn!("FeedbackClusterer::add: Embedding failed, clustering by tags only: {}", e);
+                Vec::new()
+            }
+        };
+        let same_tags = |cluster: &FeedbackCluster| {
+            let mut cluster_tags: Vec<&String> = cluster.tags.keys().collect();
+            cluster_tags.sort();
+            cluster_tags == tags.iter().collect::<Vec<&String>>()
+        };
+        // Tagged items must share the cluster's tags; untagged ones join on similarity alone
+        let best = self
+            .clusters
+            .iter()
+            .filter(|c| tags.is_empty() == c.tags.is_empty() && (tags.is_empty() || same_tags(c)))
+            .map(|c| (c.id, if embedding.is_empty() { 1.0 } else { cosine_similarity(&c.centroid, &embedding) }))
+            .filter(|(_, similarity)| *similarity >= self.similarity_threshold || !tags.is_empty())
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let id = match best {
+            Some((id, _)) => id,
+            None => {
+                let id = self.clusters.len();
+                self.clusters.push(FeedbackCluster {
+                    id,
+                    label: String::new(),
+                    tags: HashMap::new(),
+                    count: 0,
+                    user_count: 0,
+                    types: HashMap::new(),
+                    representative: feedback.content.clone(),
+                    avg_rating: None,
+                    centroid: vec![0.0; embedding.len()],
+                    users: HashSet::new(),
+                    words: HashMap::new(),
+                    ratings: Vec::new(),
+                });
+                id
+            }
+        };
+        self.clusters[id].add(feedback, &tags, &embedding);
+        (tags, id)
+    }
+
+    pub fn clusters(&self) -> &[FeedbackCluster] {
+        &self.clusters
+    }
+
+    /// Largest issue clusters by distinct reporters, then item count; praise-only clusters are excluded
+    pub fn top_issues(&self, n: usize) -> Vec<&FeedbackCluster> {
+        let mut issues: Vec<&FeedbackCluster> = self.clusters.iter().filter(|c| c.is_issue()).collect();
+        issues.sort_by(|a, b| b.user_count.cmp(&a.user_count).then(b.count.cmp(&a.count)).then(a.id.cmp(&b.id)));
+        issues.truncate(n);
+        issues
+    }
+
+    /// Items per tag across all clusters
+    pub fn tag_counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for cluster in &self.clusters {
+            for (tag, count) in &cluster.tags {
+                *counts.entry(tag.clone()).or_insert(0) += count;
+            }
+        }
+        counts
+    }
+}
+
+impl Default for FeedbackClusterer {
+    fn default() -> Self {
+        Self::new(Box::new(HashingEmbedder::default()), 0.35)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feedback(user: &str, feedback_type: FeedbackType, content: &str) -> BetaFeedback {
+        BetaFeedback {
+            user_id: user.to_string(),
+            feedback_type,
+            content: content.to_string(),
+            rating: None,
+            timestamp: 0,
+            tags: Vec::new(),
+            cluster_id: None,
+        }
+    }
+
+    #[test]
+    fn test_extract_tags() {
+        assert_eq!(extract_tags("The app is slow and crashes after the nudge popup"), vec!["crash", "notifications", "performance"]);
+        assert_eq!(extract_tags("Love it"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_untagged_feedback_clusters_by_similarity() {
+        let mut clusterer = FeedbackClusterer::default();
+        let (_, first) = clusterer.add(&feedback("u1", FeedbackType::FeatureRequest, "Please add Linear integration"));
+        let (_, second) = clusterer.add(&feedback("u2", FeedbackType::FeatureRequest, "Add a Linear integration please"));
+        let (_, other) = clusterer.add(&feedback("u3", FeedbackType::FeatureRequest, "Calendar export to Outlook"));
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_eq!(clusterer.clusters()[first].label, "add, integration, linear");
+    }
+}
@@ -0,0 +1,375 @@
+/// Phase: C | Step: 10 | Source: Athenos_AI_Strategy.md#L129
+/// Onboarding Funnel
+/// Per-user step events (started/completed/abandoned) against the onboarding playbook, reported as a drop-off funnel
+
+use super::{OnboardingConfig, OnboardingProgress};
+use crate::launch::{OnboardingPlaybook, OnboardingStep};
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Started steps not completed within this long count as abandoned
+const DEFAULT_ABANDON_AFTER_SECS: i64 = 7 * 86400;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Started,
+    Completed,
+    Abandoned,
+}
+
+/// One step transition for one user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StepEvent {
+    pub user_id: String,
+    pub step_number: usize,
+    pub status: StepStatus,
+    pub at: i64,
+}
+
+/// Where one user is on one step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserStepState {
+    pub status: StepStatus,
+    pub started_at: i64,
+    pub finished_at: Option<i64>, // Completed or abandoned
+}
+
+/// Funnel numbers for one playbook step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunnelStep {
+    pub step_number: usize,
+    pub title: String,
+    pub required: bool,
+    pub entered: usize,        // Users who started the step
+    pub completed: usize,
+    pub abandoned: usize,
+    pub in_progress: usize,
+    pub drop_off_rate: f64,    // Share of entrants who abandoned
+    pub conversion_rate: f64,  // Completed / users in the funnel
+    pub median_secs_to_complete: Option<i64>,
+}
+
+/// Drop-off per step across all users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunnelReport {
+    pub users: usize,
+    pub fully_onboarded: usize, // Completed every required step
+    pub steps: Vec<FunnelStep>,
+}
+
+impl FunnelReport {
+    /// Step losing the largest share of its entrants
+    pub fn worst_step(&self) -> Option<&FunnelStep> {
+        self.steps.iter().filter(|s| s.entered > 0).max_by(|a, b| a.drop_off_rate.total_cmp(&b.drop_off_rate))
+    }
+}
+
+/// Persisted funnel progress; the playbook itself comes from configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FunnelState {
+    progress: HashMap<String, BTreeMap<usize, UserStepState>>,
+    events: Vec<StepEvent>,
+}
+
+/// Tracks each user's progress through the onboarding playbook
+pub struct OnboardingFunnel {
+    steps: Vec<OnboardingStep>,
+    progress: HashMap<String, BTreeMap<usize, UserStepState>>, // user_id -> step_number -> state
+    events: Vec<StepEvent>,
+    abandon_after_secs: i64,
+    path: Option<PathBuf>, // Progress is saved here after every change when set
+}
+
+impl OnboardingFunnel {
+    pub fn new(playbook: &OnboardingPlaybook) -> Self {
+        info!("OnboardingFunnel::new: Tracking {} onboarding steps", playbook.steps.len());
+        Self {
+            steps: playbook.steps.clone(),
+            progress: HashMap::new(),
+            events: Vec::new(),
+            abandon_after_secs: DEFAULT_ABANDON_AFTER_SECS,
+            path: None,
+        }
+    }
+
+    /// Open a persistent funnel at path (created on first write), so stale steps expire across restarts
+    pub fn open(playbook: &OnboardingPlaybook, path: PathBuf) -> Result<Self, String> {
+        info!("OnboardingFunnel::open: Opening onboarding funnel at {:?}", path);
+        let state: FunnelState = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid onboarding funnel {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => FunnelState::default(),
+            Err(e) => return Err(format!("Failed to read onboarding funnel {}: {}", path.display(), e)),
+        };
+        let mut funnel = Self::new(playbook);
+        funnel.progress = state.progress;
+        funnel.events = state.events;
+        funnel.path = Some(path);
+        Ok(funnel)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let state = FunnelState { progress: self.progress.clone(), events: self.events.clone() };
+        let json = serde_json::to_string_pretty(&state).map_err(|e| format!("Failed to encode onboarding funnel: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Started steps older than this are abandoned by expire_stale
+    pub fn set_abandon_after(&mut self, secs: i64) {
+        self.abandon_after_secs = secs;
+    }
+
+    /// Switch to a new playbook; progress on step numbers it still has is kept
+    pub fn set_playbook(&mut self, playbook: &OnboardingPlaybook) {
+        self.steps = playbook.steps.clone();
+        let numbers: Vec<usize> = self.steps.iter().map(|s| s.step_number).collect();
+        for steps in self.progress.values_mut() {
+            steps.retain(|number, _| numbers.contains(number));
+        }
+        if let Err(e) = self.save() {
+            warn!("OnboardingFunnel::set_playbook: {}", e);
+        }
+    }
+
+    /// Add a user to the funnel without starting any step
+    pub fn enroll(&mut self, user_id: &str) {
+        if self.progress.contains_key(user_id) {
+            return;
+        }
+        self.progress.insert(user_id.to_string(), BTreeMap::new());
+        if let Err(e) = self.save() {
+            warn!("OnboardingFunnel::enroll: {}", e);
+        }
+    }
+
+    /// Record a step transition
+    /// Completing or abandoning an unstarted step starts it at the same time; finished steps can be restarted
+    pub fn record(&mut self, user_id: &str, step_number: usize, status: StepStatus, now: i64) -> Result<(), String> {
+        if !self.steps.iter().any(|s| s.step_number == step_number) {
+            return Err(format!("Unknown onboarding step {}", step_number));
+        }
+        let state = self.progress.entry(user_id.to_string()).or_default().entry(step_number);
+        match (status, state) {
+            (StepStatus::Started, Entry::Occupied(mut entry)) => {
+                if entry.get().status == StepStatus::Started {
+                    return Ok(()); // Already in progress
+                }
+                *entry.get_mut() = UserStepState { status, started_at: now, finished_at: None };
+            }
+            (_, Entry::Occupied(mut entry)) => {
+                if entry.get().status == status {
+                    return Ok(());
+                }
+                let current = entry.get_mut();
+                current.status = status;
+                current.finished_at = Some(now);
+            }
+            (_, Entry::Vacant(entry)) => {
+                let finished_at = if status == StepStatus::Started { None } else { Some(now) };
+                entry.insert(UserStepState { status, started_at: now, finished_at });
+            }
+        }
+        info!("OnboardingFunnel::record: Step {} {:?}", step_number, status);
+        self.events.push(StepEvent { user_id: user_id.to_string(), step_number, status, at: now });
+        self.save()
+    }
+
+    /// Mirror guided onboarding progress: unlocked stages complete their step, the first pending stage is started
+    /// A step starts when the stage before it unlocked (the first when onboarding started) and completes when its
+    /// own stage unlocked; `now` stands in for unlock times the progress does not carry
+    /// Assumes the playbook was built with OnboardingPlaybook::guided from the same config
+    pub fn sync_guided(&mut self, user_id: &str, config: &OnboardingConfig, progress: &OnboardingProgress, now: i64) -> Result<(), String> {
+        self.enroll(user_id);
+        let mut step_start = progress.started_at;
+        for (i, stage) in config.stages.iter().enumerate() {
+            let step_number = i + 1;
+            let status = self.progress.get(user_id).and_then(|steps| steps.get(&step_number)).map(|s| s.status);
+            let unlocked_at = progress.unlocked_at.get(&stage.feature).copied();
+            if progress.unlocked.contains(&stage.feature) {
+                let completed_at = unlocked_at.unwrap_or(now);
+                if status.is_none() {
+                    self.record(user_id, step_number, StepStatus::Started, step_start.min(completed_at))?;
+                }
+                self.record(user_id, step_number, StepStatus::Completed, completed_at)?;
+            } else if status.is_none() && progress.pending.first().is_some_and(|p| p.feature == stage.feature) {
+                self.record(user_id, step_number, StepStatus::Started, step_start)?;
+            }
+            if let Some(at) = unlocked_at {
+                step_start = step_start.max(at);
+            }
+        }
+        Ok(())
+    }
+
+    /// Abandon steps started more than abandon_after_secs ago; returns the abandonment events
+    pub fn expire_stale(&mut self, now: i64) -> Vec<StepEvent> {
+        let stale: Vec<(String, usize)> = self
+            .progress
+            .iter()
+            .flat_map(|(user, steps)| {
+                steps
+                    .iter()
+                    .filter(|(_, s)| s.status == StepStatus::Started && now - s.started_at > self.abandon_after_secs)
+                    .map(move |(number, _)| (user.clone(), *number))
+            })
+            .collect();
+        let mut abandoned = Vec::new();
+        for (user, number) in stale {
+            if self.record(&user, number, StepStatus::Abandoned, now).is_ok() {
+                abandoned.extend(self.events.last().cloned());
+            }
+        }
+        abandoned
+    }
+
+    /// Every required step completed
+    pub fn is_onboarded(&self, user_id: &str) -> bool {
+        let Some(steps) = self.progress.get(user_id) else {
+            return false;
+        };
+        self.steps
+            .iter()
+            .filter(|s| s.required)
+            .all(|s| steps.get(&s.step_number).is_some_and(|state| state.status == StepStatus::Completed))
+    }
+
+    pub fn get_user_progress(&self, user_id: &str) -> Option<&BTreeMap<usize, UserStepState>> {
+        self.progress.get(user_id)
+    }
+
+    /// Step events in recording order
+    pub fn get_events(&self) -> &[StepEvent] {
+        &self.events
+    }
+
+    pub fn report(&self) -> FunnelReport {
+        let users = self.progress.len();
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                let states: Vec<&UserStepState> = self.progress.values().filter_map(|p| p.get(&step.step_number)).collect();
+                let count = |status: StepStatus| states.iter().filter(|s| s.status == status).count();
+                let (completed, abandoned) = (count(StepStatus::Completed), count(StepStatus::Abandoned));
+                let mut durations: Vec<i64> = states
+                    .iter()
+                    .filter(|s| s.status == StepStatus::Completed)
+                    .filter_map(|s| s.finished_at.map(|at| at - s.started_at))
+                    .collect();
+                durations.sort();
+                FunnelStep {
+                    step_number: step.step_number,
+                    title: step.title.clone(),
+                    required: step.required,
+                    entered: states.len(),
+                    completed,
+                    abandoned,
+                    in_progress: count(StepStatus::Started),
+                    drop_off_rate: if states.is_empty() { 0.0 } else { abandoned as f64 / states.len() as f64 },
+                    conversion_rate: if users > 0 { completed as f64 / users as f64 } else { 0.0 },
+                    median_secs_to_complete: durations.get(durations.len() / 2).copied(),
+                }
+            })
+            .collect();
+        FunnelReport {
+            users,
+            fully_onboarded: self.progress.keys().filter(|u| self.is_onboarded(u)).count(),
+            steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beta::GuidedOnboarding;
+    use crate::consent::MicroConsentManager;
+
+    #[test]
+    fn test_funnel_reports_drop_off_per_step() {
+        let mut funnel = OnboardingFunnel::new(&OnboardingPlaybook::guided(&OnboardingConfig::default()));
+        for (i, user) in ["u1", "u2", "u3", "u4"].iter().enumerate() {
+            funnel.record(user, 1, StepStatus::Started, 100 * i as i64).unwrap();
+            funnel.record(user, 1, StepStatus::Completed, 100 * i as i64 + 60).unwrap();
+        }
+        funnel.record("u1", 2, StepStatus::Completed, 5_000).unwrap();
+        funnel.record("u2", 2, StepStatus::Started, 1_000).unwrap();
+        funnel.record("u3", 2, StepStatus::Abandoned, 1_000).unwrap();
+        funnel.set_abandon_after(86400);
+        let expired = funnel.expire_stale(1_000 + 2 * 86400);
+        assert_eq!(expired.len(), 1);
+        assert_eq!((expired[0].user_id.as_str(), expired[0].step_number), ("u2", 2));
+        assert!(funnel.record("u1", 9, StepStatus::Started, 0).is_err());
+
+        let report = funnel.report();
+        assert_eq!(report.users, 4);
+        assert_eq!((report.steps[0].entered, report.steps[0].completed), (4, 4));
+        assert_eq!(report.steps[0].median_secs_to_complete, Some(60));
+        let second = &report.steps[1];
+        assert_eq!((second.entered, second.completed, second.abandoned), (3, 1, 2));
+        assert!((second.drop_off_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(second.conversion_rate, 0.25);
+        assert_eq!(report.worst_step().unwrap().step_number, 2);
+        assert_eq!(report.fully_onboarded, 4); // Only the first step is required without consent
+    }
+
+    #[test]
+    fn test_sync_guided_progress() {
+        let config = OnboardingConfig::default();
+        let mut funnel = OnboardingFunnel::new(&OnboardingPlaybook::guided(&config));
+        let mut guided = GuidedOnboarding::start(0, config.clone());
+        let consent = MicroConsentManager::new();
+        let progress = guided.progress(0, &consent);
+        funnel.sync_guided("u1", &config, &progress, 0).unwrap();
+        let steps = funnel.get_user_progress("u1").unwrap();
+        assert_eq!(steps.get(&1).map(|s| s.status), Some(StepStatus::Completed));
+        assert_eq!(steps.get(&2).map(|s| s.status), Some(StepStatus::Started));
+        assert!(!steps.contains_key(&3));
+        assert!(funnel.is_onboarded("u1"));
+    }
+
+    #[test]
+    fn test_sync_guided_uses_unlock_times_and_persists() {
+        let path = std::env::temp_dir().join(format!("athenos_funnel_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = OnboardingConfig::default();
+        let playbook = OnboardingPlaybook::guided(&config);
+        let mut guided = GuidedOnboarding::start(0, config.clone());
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent("behavioral_logging".to_string(), "Log workflow patterns".to_string());
+        consent.grant_consent("behavioral_logging").unwrap();
+
+        let mut funnel = OnboardingFunnel::open(&playbook, path.clone()).unwrap();
+        funnel.sync_guided("u1", &config, &guided.progress(3600, &consent), 3600).unwrap();
+        guided.update_observations(250);
+        let day_eight = 8 * 86400;
+        // Synced a day after Nudges unlocked: the step still completes at the unlock time
+        let progress = guided.progress(day_eight, &consent);
+        funnel.sync_guided("u1", &config, &progress, day_eight + 86400).unwrap();
+        funnel.sync_guided("u1", &config, &progress, day_eight + 86400).unwrap();
+        let report = funnel.report();
+        assert_eq!(report.steps[0].median_secs_to_complete, Some(3600));
+        assert_eq!(report.steps[1].median_secs_to_complete, Some(day_eight - 3600));
+        assert_eq!(funnel.get_events().len(), 5);
+
+        // A restart keeps the started step, so it still expires
+        let mut reopened = OnboardingFunnel::open(&playbook, path.clone()).unwrap();
+        assert_eq!(reopened.get_user_progress("u1").unwrap()[&3].started_at, day_eight);
+        let expired = reopened.expire_stale(day_eight + DEFAULT_ABANDON_AFTER_SECS + 1);
+        assert_eq!((expired.len(), expired[0].step_number), (1, 3));
+        let reopened = OnboardingFunnel::open(&playbook, path.clone()).unwrap();
+        assert_eq!(reopened.report().steps[2].abandoned, 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -3,11 +3,25 @@
 /// Onboard 500 beta users, gather structured feedback, iterate
 
 use crate::types::*;
+use crate::auto_action::AutoActionSynthesizer;
 use crate::cohort::{CohortManager, CohortMember};
+use crate::consent::MicroConsentManager;
+use crate::launch::OnboardingPlaybook;
+use crate::microlearning::MicrolearningNudgeGenerator;
+use crate::notify::{Notification, NotificationSeverity, NotificationSource};
+use crate::rag::EmbeddingProvider;
+use crate::shortcut::ShortcutGenerator;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+pub mod feedback;
+pub mod funnel;
+
+use feedback::{FeedbackCluster, FeedbackClusterer};
+use funnel::{FunnelReport, OnboardingFunnel, StepEvent, StepStatus};
+
 /// Beta user feedback
 /// Source: Athenos_AI_Strategy.md#L129
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +31,10 @@ pub struct BetaFeedback {
     pub content: String,
     pub rating: Option<u8>, // 1-10
     pub timestamp: i64,
+    #[serde(default)]
+    pub tags: Vec<String>, // Theme tags extracted from content
+    #[serde(default)]
+    pub cluster_id: Option<usize>,
 }
 
 /// Feedback type
@@ -34,7 +52,8 @@ pub enum FeedbackType {
 pub struct BetaOnboardingManager {
     cohort_manager: CohortManager,
     feedback: Vec<BetaFeedback>,
-    onboarding_complete: HashMap<String, bool>,
+    funnel: OnboardingFunnel, // Per-step onboarding progress
+    clusterer: FeedbackClusterer,
 }
 
 impl BetaOnboardingManager {
@@ -44,16 +63,54 @@ impl BetaOnboardingManager {
         Self {
             cohort_manager: CohortManager::new(500),
             feedback: Vec::new(),
-            onboarding_complete: HashMap::new(),
+            funnel: OnboardingFunnel::new(&OnboardingPlaybook::guided(&OnboardingConfig::default())),
+            clusterer: FeedbackClusterer::default(),
         }
     }
 
+    /// Create beta onboarding manager whose onboarding funnel is persisted at `funnel_path`
+    pub fn open(funnel_path: PathBuf) -> Result<Self, String> {
+        let mut manager = Self::new();
+        manager.funnel = OnboardingFunnel::open(&OnboardingPlaybook::guided(&OnboardingConfig::default()), funnel_path)?;
+        Ok(manager)
+    }
+
     /// Onboard beta user
     /// Source: Athenos_AI_Strategy.md#L129
     pub fn onboard_user(&mut self, user_id: String, profile: UserProfile) {
         info!("BetaOnboardingManager::onboard_user: Onboarding user {}", user_id);
         self.cohort_manager.add_member(user_id.clone(), profile);
-        self.onboarding_complete.insert(user_id, true);
+        self.funnel.enroll(&user_id);
+    }
+
+    /// Track onboarding steps against a different playbook
+    pub fn set_onboarding_playbook(&mut self, playbook: &OnboardingPlaybook) {
+        self.funnel.set_playbook(playbook);
+    }
+
+    /// Record a user starting, completing or abandoning an onboarding step
+    pub fn record_onboarding_step(&mut self, user_id: &str, step_number: usize, status: StepStatus, now: i64) -> Result<(), String> {
+        self.funnel.record(user_id, step_number, status, now)
+    }
+
+    /// Mirror a user's guided onboarding progress into the funnel
+    pub fn sync_guided_onboarding(&mut self, user_id: &str, onboarding: &GuidedOnboarding, progress: &OnboardingProgress, now: i64) -> Result<(), String> {
+        self.funnel.sync_guided(user_id, onboarding.config(), progress, now)
+    }
+
+    /// Abandon onboarding steps left unfinished too long
+    pub fn expire_stale_onboarding(&mut self, now: i64) -> Vec<StepEvent> {
+        self.funnel.expire_stale(now)
+    }
+
+    /// Whether the user completed every required onboarding step
+    pub fn is_onboarded(&self, user_id: &str) -> bool {
+        self.funnel.is_onboarded(user_id)
+    }
+
+    /// Drop-off per onboarding step
+    pub fn get_onboarding_funnel(&self) -> FunnelReport {
+        self.funnel.report()
     }
 
     /// Simulate onboarding 500 beta users
@@ -69,17 +126,42 @@ impl BetaOnboardingManager {
     pub fn collect_feedback(&mut self, user_id: String, feedback_type: FeedbackType, content: String, rating: Option<u8>) {
         info!("BetaOnboardingManager::collect_feedback: Collecting feedback from {}", user_id);
         
-        let feedback = BetaFeedback {
+        let mut feedback = BetaFeedback {
             user_id,
             feedback_type,
             content,
             rating,
             timestamp: chrono::Utc::now().timestamp(),
+            tags: Vec::new(),
+            cluster_id: None,
         };
+        let (tags, cluster_id) = self.clusterer.add(&feedback);
+        feedback.tags = tags;
+        feedback.cluster_id = Some(cluster_id);
         
         self.feedback.push(feedback);
     }
 
+    /// Cluster feedback with a different embedding provider (e.g. a local model); existing feedback is re-clustered
+    pub fn set_embedding_provider(&mut self, provider: Box<dyn EmbeddingProvider>, similarity_threshold: f32) {
+        self.clusterer = FeedbackClusterer::new(provider, similarity_threshold);
+        for feedback in self.feedback.iter_mut() {
+            let (tags, cluster_id) = self.clusterer.add(feedback);
+            feedback.tags = tags;
+            feedback.cluster_id = Some(cluster_id);
+        }
+    }
+
+    /// Most widely reported recurring issues
+    pub fn top_issues(&self, n: usize) -> Vec<&FeedbackCluster> {
+        self.clusterer.top_issues(n)
+    }
+
+    /// All feedback clusters, including praise
+    pub fn get_feedback_clusters(&self) -> &[FeedbackCluster] {
+        self.clusterer.clusters()
+    }
+
     /// Get feedback summary
     pub fn get_feedback_summary(&self) -> FeedbackSummary {
         let total_feedback = self.feedback.len();
@@ -105,6 +187,8 @@ impl BetaOnboardingManager {
             avg_rating,
             feedback_by_type,
             total_beta_users: self.cohort_manager.get_statistics().total_members,
+            tag_counts: self.clusterer.tag_counts(),
+            cluster_count: self.clusterer.clusters().len(),
         }
     }
 
@@ -121,6 +205,10 @@ pub struct FeedbackSummary {
     pub avg_rating: f64,
     pub feedback_by_type: HashMap<String, usize>,
     pub total_beta_users: usize,
+    #[serde(default)]
+    pub tag_counts: HashMap<String, usize>,
+    #[serde(default)]
+    pub cluster_count: usize,
 }
 
 impl Default for BetaOnboardingManager {
@@ -129,6 +217,250 @@ impl Default for BetaOnboardingManager {
     }
 }
 
+/// Feature gated by guided onboarding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingFeature {
+    Reports,
+    Nudges,
+    ShortcutSuggestions,
+    AutoActions,
+}
+
+/// Consent a stage waits for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingConsent {
+    BehavioralLogging,
+    AnyAutomationScope, // At least one action type allowed to be automated
+}
+
+impl OnboardingConsent {
+    fn satisfied(&self, consent: &MicroConsentManager) -> bool {
+        let ledger = consent.consent_ledger();
+        match self {
+            OnboardingConsent::BehavioralLogging => ledger.opt_in_behavioral_logging,
+            OnboardingConsent::AnyAutomationScope => !ledger.automation_scopes.is_empty(),
+        }
+    }
+}
+
+/// Requirements for unlocking one feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStage {
+    pub feature: OnboardingFeature,
+    pub min_days: i64,
+    pub min_observations: usize, // Baseline data collected since onboarding started
+    pub consents: Vec<OnboardingConsent>,
+}
+
+/// Guided onboarding schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingConfig {
+    pub observe_only_days: i64, // Nothing beyond reports unlocks before this
+    pub stages: Vec<OnboardingStage>,
+}
+
+impl OnboardingConfig {
+    /// Earliest day a stage can unlock; only reports are exempt from the observe-only period
+    pub fn stage_min_days(&self, stage: &OnboardingStage) -> i64 {
+        if stage.feature == OnboardingFeature::Reports { stage.min_days } else { stage.min_days.max(self.observe_only_days) }
+    }
+}
+
+impl Default for OnboardingConfig {
+    fn default() -> Self {
+        let stage = |feature, min_days, min_observations, consents| OnboardingStage { feature, min_days, min_observations, consents };
+        Self {
+            observe_only_days: 7,
+            stages: vec![
+                stage(OnboardingFeature::Reports, 0, 0, vec![]),
+                stage(OnboardingFeature::Nudges, 7, 200, vec![OnboardingConsent::BehavioralLogging]),
+                stage(OnboardingFeature::ShortcutSuggestions, 10, 300, vec![OnboardingConsent::BehavioralLogging]),
+                stage(OnboardingFeature::AutoActions, 14, 500, vec![OnboardingConsent::AnyAutomationScope]),
+            ],
+        }
+    }
+}
+
+/// What still blocks a locked feature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StageProgress {
+    pub feature: OnboardingFeature,
+    pub days_remaining: i64,
+    pub observations_remaining: usize,
+    pub missing_consents: Vec<OnboardingConsent>,
+}
+
+/// Onboarding progress shown to the user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OnboardingProgress {
+    pub day: i64, // Days since onboarding started
+    pub observe_only: bool,
+    pub unlocked: Vec<OnboardingFeature>,
+    pub pending: Vec<StageProgress>,
+    pub percent_complete: f64,
+    #[serde(default)]
+    pub started_at: i64,
+    #[serde(default)]
+    pub unlocked_at: HashMap<OnboardingFeature, i64>, // When each unlocked feature was first seen unlocked
+}
+
+impl OnboardingProgress {
+    /// Plain-text progress summary
+    pub fn render(&self) -> String {
+        let mut out = format!("Onboarding day {} — {:.0}% complete\n", self.day + 1, self.percent_complete * 100.0);
+        if self.observe_only {
+            out.push_str("Observing only: no nudges or automations yet, just reports.\n");
+        }
+        for feature in &self.unlocked {
+            out.push_str(&format!("- {:?}: unlocked\n", feature));
+        }
+        for stage in &self.pending {
+            let mut waiting = Vec::new();
+            if stage.days_remaining > 0 {
+                waiting.push(format!("{} more days", stage.days_remaining));
+            }
+            if stage.observations_remaining > 0 {
+                waiting.push(format!("{} more observations", stage.observations_remaining));
+            }
+            for consent in &stage.missing_consents {
+                waiting.push(format!("{:?} consent", consent));
+            }
+            out.push_str(&format!("- {:?}: waiting for {}\n", stage.feature, waiting.join(", ")));
+        }
+        out
+    }
+
+    pub fn to_notification(&self, now: i64) -> Notification {
+        Notification {
+            source: NotificationSource::Report,
+            severity: NotificationSeverity::Info,
+            title: "Getting started".to_string(),
+            body: self.render(),
+            created_at: now,
+        }
+    }
+}
+
+/// Guided first-weeks onboarding: observe and report first, then unlock features as data and consents accumulate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuidedOnboarding {
+    pub started_at: i64,
+    pub observations: usize,
+    pub completed: bool, // Every stage unlocked once, or the user skipped onboarding
+    config: OnboardingConfig,
+    #[serde(default)]
+    unlocked_at: HashMap<OnboardingFeature, i64>,
+}
+
+impl GuidedOnboarding {
+    /// Start onboarding at `now`
+    pub fn start(now: i64, config: OnboardingConfig) -> Self {
+        info!("GuidedOnboarding::start: Starting guided onboarding ({} observe-only days)", config.observe_only_days);
+        Self { started_at: now, observations: 0, completed: false, config, unlocked_at: HashMap::new() }
+    }
+
+    /// Load saved onboarding state, or start it when none exists
+    pub fn load_or_start(path: impl AsRef<Path>, now: i64, config: OnboardingConfig) -> Result<Self, String> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid onboarding state {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::start(now, config)),
+            Err(e) => Err(format!("Failed to read onboarding state {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to encode onboarding state: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn config(&self) -> &OnboardingConfig {
+        &self.config
+    }
+
+    /// Update the number of baseline observations collected so far (never decreases)
+    pub fn update_observations(&mut self, total: usize) {
+        self.observations = self.observations.max(total);
+    }
+
+    /// Leave onboarding early; every feature is then governed by its usual consent checks only
+    pub fn skip(&mut self) {
+        info!("GuidedOnboarding::skip: User skipped guided onboarding");
+        self.completed = true;
+    }
+
+    fn day(&self, now: i64) -> i64 {
+        ((now - self.started_at).max(0)) / 86400
+    }
+
+    fn stage_progress(&self, stage: &OnboardingStage, now: i64, consent: &MicroConsentManager) -> StageProgress {
+        let min_days = self.config.stage_min_days(stage);
+        StageProgress {
+            feature: stage.feature,
+            days_remaining: (min_days - self.day(now)).max(0),
+            observations_remaining: stage.min_observations.saturating_sub(self.observations),
+            missing_consents: stage.consents.iter().filter(|c| !c.satisfied(consent)).cloned().collect(),
+        }
+    }
+
+    /// Progress at `now`; marks onboarding complete once every stage has unlocked
+    /// Features are stamped unlocked at the first call that sees them unlocked
+    pub fn progress(&mut self, now: i64, consent: &MicroConsentManager) -> OnboardingProgress {
+        let mut unlocked = Vec::new();
+        let mut pending = Vec::new();
+        for stage in &self.config.stages {
+            let progress = self.stage_progress(stage, now, consent);
+            if self.completed || (progress.days_remaining == 0 && progress.observations_remaining == 0 && progress.missing_consents.is_empty()) {
+                self.unlocked_at.entry(stage.feature).or_insert(now);
+                unlocked.push(stage.feature);
+            } else {
+                pending.push(progress);
+            }
+        }
+        if pending.is_empty() && !self.completed {
+            info!("GuidedOnboarding::progress: All features unlocked");
+            self.completed = true;
+        }
+        let total = self.config.stages.len().max(1);
+        OnboardingProgress {
+            day: self.day(now),
+            observe_only: unlocked.iter().all(|f| *f == OnboardingFeature::Reports),
+            percent_complete: unlocked.len() as f64 / total as f64,
+            started_at: self.started_at,
+            unlocked_at: unlocked.iter().filter_map(|f| self.unlocked_at.get(f).map(|at| (*f, *at))).collect(),
+            unlocked,
+            pending,
+        }
+    }
+
+    /// Whether a feature is unlocked at `now`
+    pub fn is_unlocked(&mut self, feature: OnboardingFeature, now: i64, consent: &MicroConsentManager) -> bool {
+        self.progress(now, consent).unlocked.contains(&feature)
+    }
+
+    /// Gate nudges, shortcut suggestions and auto-actions according to current progress
+    pub fn apply(
+        &mut self,
+        now: i64,
+        consent: &MicroConsentManager,
+        synthesizer: &mut AutoActionSynthesizer,
+        nudges: &mut MicrolearningNudgeGenerator,
+        shortcuts: &mut ShortcutGenerator,
+    ) -> OnboardingProgress {
+        let progress = self.progress(now, consent);
+        nudges.set_observe_only(!progress.unlocked.contains(&OnboardingFeature::Nudges));
+        shortcuts.set_observe_only(!progress.unlocked.contains(&OnboardingFeature::ShortcutSuggestions));
+        synthesizer.set_observe_only(!progress.unlocked.contains(&OnboardingFeature::AutoActions));
+        progress
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +478,10 @@ mod tests {
         
         let stats = manager.get_cohort_stats();
         assert_eq!(stats.total_members, 1);
+        assert_eq!(manager.get_onboarding_funnel().users, 1);
+        assert!(!manager.is_onboarded("beta_001"));
+        manager.record_onboarding_step("beta_001", 1, StepStatus::Completed, 0).unwrap();
+        assert!(manager.is_onboarded("beta_001"));
     }
 
     #[test]
@@ -172,5 +508,87 @@ mod tests {
         assert_eq!(summary.total_feedback, 1);
         assert_eq!(summary.avg_rating, 9.0);
     }
+
+    #[test]
+    fn test_top_issues_group_recurring_themes() {
+        let mut manager = BetaOnboardingManager::new();
+        let reports = [
+            ("beta_001", FeedbackType::BugReport, "App gets slow while indexing"),
+            ("beta_002", FeedbackType::BugReport, "Very sluggish, CPU at 90%"),
+            ("beta_003", FeedbackType::UsabilityIssue, "Laptop fan spins, everything lags"),
+            ("beta_001", FeedbackType::UsabilityIssue, "Too many nudge popups during meetings"),
+            ("beta_004", FeedbackType::UsabilityIssue, "Notifications interrupt my calls"),
+            ("beta_005", FeedbackType::PositiveFeedback, "Focus mode is wonderful"),
+            ("beta_006", FeedbackType::PositiveFeedback, "Focus sessions changed my week"),
+            ("beta_007", FeedbackType::FeatureRequest, "Export reports as PDF"),
+        ];
+        for (user, feedback_type, content) in reports {
+            manager.collect_feedback(user.to_string(), feedback_type, content.to_string(), Some(5));
+        }
+        
+        let issues = manager.top_issues(2);
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].label.as_str(), issues[0].user_count), ("performance", 3));
+        assert_eq!((issues[1].label.as_str(), issues[1].count), ("notifications", 2));
+        assert!(manager.top_issues(10).iter().all(|c| c.label != "focus")); // Praise is not an issue
+        
+        let summary = manager.get_feedback_summary();
+        assert_eq!(summary.tag_counts["performance"], 3);
+        assert_eq!(summary.cluster_count, 4);
+        assert_eq!(manager.feedback[1].cluster_id, manager.feedback[0].cluster_id);
+    }
+
+    #[test]
+    fn test_guided_onboarding_unlocks_gradually() {
+        let day = 86400;
+        let mut consent = MicroConsentManager::new();
+        let mut onboarding = GuidedOnboarding::start(0, OnboardingConfig::default());
+        let mut synthesizer = AutoActionSynthesizer::new();
+        let mut nudges = MicrolearningNudgeGenerator::new();
+        let mut shortcuts = ShortcutGenerator::new();
+        let observation = Observation {
+            id: "obs_001".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics: HashMap::from([("repeat_count".to_string(), 8.0)]),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::from([("time_saved_min".to_string(), 11.0)]),
+            source: "test".to_string(),
+            timestamp: 0,
+            project: None,
+        };
+
+        // First week: observe and report only, however much data arrives
+        onboarding.update_observations(1_000);
+        let progress = onboarding.apply(3 * day, &consent, &mut synthesizer, &mut nudges, &mut shortcuts);
+        assert!(progress.observe_only);
+        assert_eq!(progress.unlocked, vec![OnboardingFeature::Reports]);
+        assert!(nudges.generate_inefficiency_nudge("Manual copy", "Use a macro").is_none());
+        assert!(shortcuts.generate_shortcut_at(&observation, 3 * day).is_none());
+        assert_eq!(progress.pending[0].days_remaining, 4);
+        assert_eq!(progress.pending[0].missing_consents, vec![OnboardingConsent::BehavioralLogging]);
+
+        // Day 8 with logging consent: nudges unlock, automation still waits for a scope and day 14
+        consent.request_consent("behavioral_logging".to_string(), "Log workflow patterns".to_string());
+        consent.grant_consent("behavioral_logging").unwrap();
+        let progress = onboarding.apply(8 * day, &consent, &mut synthesizer, &mut nudges, &mut shortcuts);
+        assert!(!progress.observe_only);
+        assert!(progress.unlocked.contains(&OnboardingFeature::Nudges));
+        assert!(!progress.unlocked.contains(&OnboardingFeature::AutoActions));
+        assert!(shortcuts.generate_shortcut_at(&observation, 8 * day).is_none());
+        assert!(progress.render().contains("AutoActions: waiting for 6 more days"));
+        assert!(!onboarding.completed);
+
+        onboarding.skip();
+        assert!(onboarding.is_unlocked(OnboardingFeature::AutoActions, 8 * day, &consent));
+        onboarding.apply(8 * day, &consent, &mut synthesizer, &mut nudges, &mut shortcuts);
+        assert!(shortcuts.generate_shortcut_at(&observation, 8 * day).is_some());
+    }
 }
 
@@ -0,0 +1,35 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L136
+/// Athenos AI - Self-Hosted Aggregation Server
+/// On-prem entry point: federation aggregation, enterprise policy distribution, marketplace catalog
+
+use athenos_ai::aggregation::{self, AggregationServer, AggregationServerConfig};
+use std::sync::Arc;
+use tracing::{error, info};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+    
+    let config_path = std::env::args().nth(1).unwrap_or_else(|| "./athenos-server.json".to_string());
+    info!("Athenos aggregation server starting (config {})", config_path);
+    
+    let config = match AggregationServerConfig::load(std::path::Path::new(&config_path)) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Athenos aggregation server config rejected: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let server = match AggregationServer::new(config) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            error!("Athenos aggregation server failed to start: {}", e);
+            std::process::exit(1);
+        }
+    };
+    
+    if let Err(e) = aggregation::serve(server).await {
+        error!("Athenos aggregation server stopped: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,171 @@
+/// Phase: B | Source: Athenos_AI_Strategy.md#L19-24
+/// Async Event Bus
+/// Streams OSEvents from the edge observer to downstream consumers (pattern mining, emotion, analytics)
+
+use crate::edge::OSEvent;
+use tokio::runtime::Handle;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Message carried on the bus
+#[derive(Debug, Clone)]
+pub enum BusMessage {
+    Event(OSEvent),
+    Flush,    // Deliver partial batches now
+    Shutdown, // Deliver remaining events and stop
+}
+
+/// Consumer of OS events, fed in batches by its own task
+pub trait EventSubscriber: Send + 'static {
+    fn subscriber_name(&self) -> &str;
+    fn on_events(&mut self, events: &[OSEvent]);
+}
+
+/// Broadcast bus; cloning yields another publisher on the same bus
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<BusMessage>,
+}
+
+impl EventBus {
+    /// Create bus buffering up to `capacity` undelivered messages per subscriber
+    pub fn new(capacity: usize) -> Self {
+        info!("EventBus::new: Creating event bus with capacity={}", capacity);
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    fn send(&self, message: BusMessage) -> usize {
+        // No subscribers is not an error; the event simply has no consumers
+        self.sender.send(message).unwrap_or(0)
+    }
+
+    /// Publish an event; returns the number of subscribers it reached
+    pub fn publish(&self, event: OSEvent) -> usize {
+        self.send(BusMessage::Event(event))
+    }
+
+    /// Ask subscribers to process partial batches
+    pub fn flush(&self) {
+        self.send(BusMessage::Flush);
+    }
+
+    /// Stop all subscribers after they process what they have
+    pub fn shutdown(&self) {
+        info!("EventBus::shutdown: Stopping {} subscribers", self.subscriber_count());
+        self.send(BusMessage::Shutdown);
+    }
+
+    /// Subscribers currently attached
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Run a subscriber on the runtime, delivering events in batches of `batch_size` (partial batches on flush)
+    /// The task returns the subscriber after shutdown so its state can be used afterwards
+    pub fn spawn_subscriber<S: EventSubscriber>(&self, runtime: &Handle, mut subscriber: S, batch_size: usize) -> JoinHandle<S> {
+        info!("EventBus::spawn_subscriber: {} (batch_size={})", subscriber.subscriber_name(), batch_size);
+        let mut receiver = self.sender.subscribe();
+        let batch_size = batch_size.max(1);
+        runtime.spawn(async move {
+            let mut batch: Vec<OSEvent> = Vec::with_capacity(batch_size);
+            loop {
+                let (flush, stop) = match receiver.recv().await {
+                    Ok(BusMessage::Event(event)) => {
+                        batch.push(event);
+                        (batch.len() >= batch_size, false)
+                    }
+                    Ok(BusMessage::Flush) => (true, false),
+                    Ok(BusMessage::Shutdown) | Err(RecvError::Closed) => (true, true),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("EventBus: {} fell behind, {} events dropped", subscriber.subscriber_name(), skipped);
+                        (false, false)
+                    }
+                };
+                if flush && !batch.is_empty() {
+                    subscriber.on_events(&batch);
+                    batch.clear();
+                }
+                if stop {
+                    return subscriber;
+                }
+            }
+        })
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::{AnalyticsAggregator, MetricCategory};
+    use crate::edge::{EdgeObserver, OSEventType};
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct Recorder {
+        batches: Vec<usize>,
+    }
+
+    impl EventSubscriber for Recorder {
+        fn subscriber_name(&self) -> &str {
+            "recorder"
+        }
+
+        fn on_events(&mut self, events: &[OSEvent]) {
+            self.batches.push(events.len());
+        }
+    }
+
+    fn event(app: &str, timestamp: i64) -> OSEvent {
+        OSEvent {
+            event_type: OSEventType::AppSwitch,
+            app_name: app.to_string(),
+            window_title: None,
+            timestamp,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_subscriber_receives_all_events() {
+        let bus = EventBus::new(64);
+        let first = bus.spawn_subscriber(&Handle::current(), Recorder::default(), 3);
+        let second = bus.spawn_subscriber(&Handle::current(), Recorder::default(), 100);
+        assert_eq!(bus.subscriber_count(), 2);
+
+        for i in 0..7 {
+            assert_eq!(bus.publish(event("IDE", i)), 2);
+        }
+        bus.shutdown();
+
+        let first = first.await.unwrap();
+        let second = second.await.unwrap();
+        assert_eq!(first.batches, vec![3, 3, 1]);
+        assert_eq!(second.batches, vec![7]);
+    }
+
+    #[tokio::test]
+    async fn test_edge_observer_feeds_analytics_end_to_end() {
+        let bus = EventBus::new(64);
+        let analytics = bus.spawn_subscriber(&Handle::current(), AnalyticsAggregator::new(), 10);
+        let mut observer = EdgeObserver::new(100);
+        observer.attach_bus(&bus);
+
+        for (i, app) in ["Slack", "IDE", "Terminal"].iter().enumerate() {
+            observer.record_event(event(app, i as i64));
+        }
+        bus.shutdown();
+
+        let analytics = analytics.await.unwrap();
+        let ops = analytics.get_metrics_by_category(MetricCategory::Operations);
+        assert_eq!(ops.iter().filter(|m| m.name == "bus.events").map(|m| m.value).sum::<f64>(), 3.0);
+        assert_eq!(EventBus::new(1).publish(event("IDE", 0)), 0);
+    }
+}
@@ -0,0 +1,291 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L132
+/// Bounded Caches
+/// Size-bounded LRU map and append log with hit, size and eviction instrumentation
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+use tracing::info;
+
+/// Cache gauges exported to analytics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub name: String,
+    pub size: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Hits over lookups (0.0 before the first lookup)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Caches that report gauges to analytics
+pub trait InstrumentedCache {
+    fn cache_stats(&self) -> CacheStats;
+}
+
+/// Least-recently-used map holding at most `capacity` entries
+/// `get`/`get_mut`/`insert` refresh recency and count hits; `peek` and iteration do not
+#[derive(Debug, Clone)]
+pub struct BoundedCache<K, V> {
+    name: String,
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>, // key -> (value, last use tick)
+    recency: BTreeMap<u64, K>,     // last use tick -> key, oldest first
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<K: Eq + Hash + Clone, V> BoundedCache<K, V> {
+    /// Create cache; a zero capacity is raised to one
+    pub fn new(name: &str, capacity: usize) -> Self {
+        info!("BoundedCache::new: Creating {} cache (capacity {})", name, capacity);
+        Self {
+            name: name.to_string(),
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    fn touch<Q: Hash + Eq + ?Sized>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+    {
+        let owned = match self.entries.get_key_value(key) {
+            Some((owned, _)) => owned.clone(),
+            None => return,
+        };
+        if let Some((_, last_used)) = self.entries.get_mut(key) {
+            self.recency.remove(last_used);
+            self.tick += 1;
+            *last_used = self.tick;
+            self.recency.insert(self.tick, owned);
+        }
+    }
+
+    /// Insert or replace; evicts the least recently used entry when full
+    /// Returns the evicted entry, if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some((existing, _)) = self.entries.get_mut(&key) {
+            *existing = value;
+            self.touch(&key);
+            return None;
+        }
+        let evicted = if self.entries.len() >= self.capacity { self.evict_oldest() } else { None };
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+        evicted
+    }
+
+    fn evict_oldest(&mut self) -> Option<(K, V)> {
+        let (_, key) = self.recency.pop_first()?;
+        let (value, _) = self.entries.remove(&key)?;
+        self.evictions += 1;
+        Some((key, value))
+    }
+
+    /// Look up and refresh an entry
+    pub fn get<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    /// Look up and refresh an entry for modification
+    pub fn get_mut<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        if self.entries.contains_key(key) {
+            self.hits += 1;
+            self.touch(key);
+            self.entries.get_mut(key).map(|(value, _)| value)
+        } else {
+            self.misses += 1;
+            None
+        }
+    }
+
+    /// Read without refreshing recency or counting a lookup
+    pub fn peek<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    /// Remove an entry
+    pub fn remove<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        let (value, last_used) = self.entries.remove(key)?;
+        self.recency.remove(&last_used);
+        Some(value)
+    }
+
+    pub fn contains_key<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.entries.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries in no particular order
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.values().map(|(value, _)| value)
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> InstrumentedCache for BoundedCache<K, V> {
+    fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            name: self.name.clone(),
+            size: self.entries.len(),
+            capacity: self.capacity,
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+        }
+    }
+}
+
+/// Append-only log keeping the newest `capacity` items
+#[derive(Debug, Clone)]
+pub struct BoundedLog<T> {
+    name: String,
+    capacity: usize,
+    items: VecDeque<T>,
+    evictions: u64,
+}
+
+impl<T> BoundedLog<T> {
+    /// Create log; a zero capacity is raised to one
+    pub fn new(name: &str, capacity: usize) -> Self {
+        info!("BoundedLog::new: Creating {} log (capacity {})", name, capacity);
+        Self { name: name.to_string(), capacity: capacity.max(1), items: VecDeque::new(), evictions: 0 }
+    }
+
+    /// Append, dropping the oldest item when full
+    pub fn push(&mut self, item: T) {
+        if self.items.len() >= self.capacity {
+            self.items.pop_front();
+            self.evictions += 1;
+        }
+        self.items.push_back(item);
+    }
+
+    /// Drop items from the oldest end while `expired` holds; they count as evictions
+    pub fn evict_while(&mut self, expired: impl Fn(&T) -> bool) {
+        while self.items.front().is_some_and(&expired) {
+            self.items.pop_front();
+            self.evictions += 1;
+        }
+    }
+
+    /// The newest `limit` items, oldest first
+    pub fn recent(&self, limit: usize) -> impl Iterator<Item = &T> {
+        self.items.iter().skip(self.items.len().saturating_sub(limit))
+    }
+
+    /// Oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> InstrumentedCache for BoundedLog<T> {
+    /// Logs are append-only, so hits and misses stay zero
+    fn cache_stats(&self) -> CacheStats {
+        CacheStats {
+            name: self.name.clone(),
+            size: self.items.len(),
+            capacity: self.capacity,
+            hits: 0,
+            misses: 0,
+            evictions: self.evictions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let mut cache = BoundedCache::new("critiques", 2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+        assert!(cache.contains_key(&"a") && cache.contains_key(&"c"));
+        assert_eq!(cache.get(&"b"), None);
+
+        // Replacing keeps the size and refreshes recency
+        assert_eq!(cache.insert("a", 10), None);
+        assert_eq!(cache.insert("d", 4), Some(("c", 3)));
+        assert_eq!(cache.peek(&"a"), Some(&10));
+
+        let stats = cache.cache_stats();
+        assert_eq!((stats.size, stats.capacity, stats.hits, stats.misses, stats.evictions), (2, 2, 1, 1, 2));
+        assert_eq!(stats.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_remove_frees_slot() {
+        let mut cache = BoundedCache::new("pending", 1);
+        cache.insert(1, "x");
+        assert_eq!(cache.remove(&1), Some("x"));
+        assert_eq!(cache.insert(2, "y"), None);
+        assert_eq!(cache.cache_stats().evictions, 0);
+    }
+
+    #[test]
+    fn test_log_keeps_newest() {
+        let mut log = BoundedLog::new("messages", 3);
+        for i in 0..5 {
+            log.push(i);
+        }
+        assert_eq!(log.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(log.recent(2).copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(log.cache_stats().evictions, 2);
+    }
+}
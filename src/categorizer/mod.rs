@@ -0,0 +1,150 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L135
+/// App Categorizer
+/// Map raw app names to coarse categories so shared artifacts carry no app identities
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Coarse app category
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AppCategory {
+    Communication,
+    Email,
+    CodeEditor,
+    Terminal,
+    Browser,
+    Documents,
+    Spreadsheet,
+    Design,
+    Other,
+}
+
+impl AppCategory {
+    /// Stable label used in anonymized sequences
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppCategory::Communication => "communication",
+            AppCategory::Email => "email",
+            AppCategory::CodeEditor => "code_editor",
+            AppCategory::Terminal => "terminal",
+            AppCategory::Browser => "browser",
+            AppCategory::Documents => "documents",
+            AppCategory::Spreadsheet => "spreadsheet",
+            AppCategory::Design => "design",
+            AppCategory::Other => "other_app",
+        }
+    }
+}
+
+/// Keyword-based app categorizer
+pub struct AppCategorizer {
+    rules: Vec<(String, AppCategory)>, // (lowercase keyword, category)
+}
+
+impl AppCategorizer {
+    /// Create categorizer with default rules
+    pub fn new() -> Self {
+        info!("AppCategorizer::new: Creating app categorizer");
+        let rules = vec![
+            ("teams", AppCategory::Communication),
+            ("slack", AppCategory::Communication),
+            ("zoom", AppCategory::Communication),
+            ("discord", AppCategory::Communication),
+            ("gmail", AppCategory::Email),
+            ("outlook", AppCategory::Email),
+            ("mail", AppCategory::Email),
+            ("ide", AppCategory::CodeEditor),
+            ("code", AppCategory::CodeEditor),
+            ("intellij", AppCategory::CodeEditor),
+            ("vim", AppCategory::CodeEditor),
+            ("terminal", AppCategory::Terminal),
+            ("powershell", AppCategory::Terminal),
+            ("cmd", AppCategory::Terminal),
+            ("chrome", AppCategory::Browser),
+            ("firefox", AppCategory::Browser),
+            ("edge", AppCategory::Browser),
+            ("safari", AppCategory::Browser),
+            ("word", AppCategory::Documents),
+            ("notion", AppCategory::Documents),
+            ("docs", AppCategory::Documents),
+            ("excel", AppCategory::Spreadsheet),
+            ("sheets", AppCategory::Spreadsheet),
+            ("figma", AppCategory::Design),
+            ("photoshop", AppCategory::Design),
+        ]
+        .into_iter()
+        .map(|(k, c)| (k.to_string(), c))
+        .collect();
+
+        Self { rules }
+    }
+
+    /// Add or override a categorization rule
+    pub fn add_rule(&mut self, keyword: &str, category: AppCategory) {
+        info!("AppCategorizer::add_rule: {} -> {:?}", keyword, category);
+        // Custom rules take precedence over defaults
+        self.rules.insert(0, (keyword.to_lowercase(), category));
+    }
+
+    /// Categorize a raw app name
+    /// Exact matches win; keywords shorter than 4 chars never match substrings
+    pub fn categorize(&self, app_name: &str) -> AppCategory {
+        let app_lower = app_name.to_lowercase();
+        self.rules
+            .iter()
+            .find(|(keyword, _)| app_lower == *keyword)
+            .or_else(|| {
+                self.rules
+                    .iter()
+                    .find(|(keyword, _)| keyword.len() >= 4 && app_lower.contains(keyword.as_str()))
+            })
+            .map(|(_, category)| *category)
+            .unwrap_or(AppCategory::Other)
+    }
+
+    /// Replace app names in a sequence with category labels
+    pub fn anonymize_sequence(&self, sequence: &[String]) -> Vec<String> {
+        sequence
+            .iter()
+            .map(|app| self.categorize(app).label().to_string())
+            .collect()
+    }
+}
+
+impl Default for AppCategorizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categorize_known_apps() {
+        let categorizer = AppCategorizer::new();
+        assert_eq!(categorizer.categorize("Teams"), AppCategory::Communication);
+        assert_eq!(categorizer.categorize("Gmail"), AppCategory::Email);
+        assert_eq!(categorizer.categorize("Visual Studio Code"), AppCategory::CodeEditor);
+        assert_eq!(categorizer.categorize("Google Slides"), AppCategory::Other);
+        assert_eq!(categorizer.categorize("AcmeInternalTool"), AppCategory::Other);
+    }
+
+    #[test]
+    fn test_anonymize_sequence() {
+        let categorizer = AppCategorizer::new();
+        let sequence = vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()];
+
+        let anonymized = categorizer.anonymize_sequence(&sequence);
+        assert_eq!(anonymized, vec!["communication", "email", "code_editor"]);
+    }
+
+    #[test]
+    fn test_custom_rule_precedence() {
+        let mut categorizer = AppCategorizer::new();
+        categorizer.add_rule("AcmeInternalTool", AppCategory::Spreadsheet);
+        assert_eq!(categorizer.categorize("AcmeInternalTool"), AppCategory::Spreadsheet);
+    }
+}
@@ -14,7 +14,6 @@ use tracing::info;
 pub struct CognitiveTwin {
     pub user_id: String,
     pub persona: UserProfile,
-    pub wisdom_engine: WisdomEngine,
     pub behavioral_model: HashMap<String, f64>,
     pub created_at: i64,
 }
@@ -24,6 +23,7 @@ pub struct CognitiveTwin {
 pub struct CognitiveTwinManager {
     twins: HashMap<String, CognitiveTwin>,
     persona_coaches: HashMap<UserProfile, String>, // Persona -> coach description
+    wisdom_engine: WisdomEngine, // Shared by all twins; the persona coach sets the context
 }
 
 impl CognitiveTwinManager {
@@ -42,6 +42,7 @@ impl CognitiveTwinManager {
         Self {
             twins: HashMap::new(),
             persona_coaches,
+            wisdom_engine: WisdomEngine::new(),
         }
     }
 
@@ -53,7 +54,6 @@ impl CognitiveTwinManager {
         let twin = CognitiveTwin {
             user_id: user_id.clone(),
             persona: persona.clone(),
-            wisdom_engine: WisdomEngine::new(),
             behavioral_model: HashMap::new(),
             created_at: chrono::Utc::now().timestamp(),
         };
@@ -85,7 +85,7 @@ impl CognitiveTwinManager {
                 .map(|s| s.as_str())
                 .unwrap_or("General coach");
             
-            let insight = twin.wisdom_engine.generate_insight(observation, coach_desc);
+            let insight = self.wisdom_engine.generate_insight(observation, coach_desc);
             Some(format!("[{}] {}", coach_desc, insight))
         } else {
             None
@@ -146,6 +146,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let insight = manager.get_persona_insight("user_001", &observation);
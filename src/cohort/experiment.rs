@@ -0,0 +1,482 @@
+/// Phase: B | Step: 10 | Source: Athenos_AI_Strategy.md#L117
+/// Cohort A/B Experiments
+/// Deterministic variant assignment, per-variant acceptance, chi-squared and bootstrap significance
+
+use super::CohortManager;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::info;
+
+/// One arm of an experiment (e.g. nudge wording A)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: String,
+    pub weight: f64, // Relative share of members assigned
+}
+
+impl Variant {
+    pub fn new(name: &str, weight: f64) -> Self {
+        Self { name: name.to_string(), weight }
+    }
+}
+
+/// Experiment definition; the first variant is the control
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Experiment {
+    pub id: String,
+    pub description: String,
+    pub variants: Vec<Variant>,
+    pub started_at: i64,
+    pub stopped_at: Option<i64>,
+}
+
+/// Acceptance observed for one variant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VariantStats {
+    pub variant: String,
+    pub members: usize, // Distinct members assigned
+    pub accepted: usize,
+    pub rejected: usize,
+    pub acceptance_rate: f64,
+}
+
+/// Bootstrap comparison of one variant against the control
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapComparison {
+    pub variant: String,
+    pub rate_diff: f64,      // Observed variant - control acceptance rate
+    pub ci_low: f64,         // 95% bootstrap interval of the difference
+    pub ci_high: f64,
+    pub prob_better: f64,    // Share of resamples where the variant beat the control
+}
+
+/// Significance of the differences between variants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentAnalysis {
+    pub experiment_id: String,
+    pub variants: Vec<VariantStats>,
+    pub chi_squared: f64,
+    pub degrees_of_freedom: usize,
+    pub p_value: f64,
+    pub significant: bool, // p_value < alpha
+    pub comparisons: Vec<BootstrapComparison>,
+}
+
+/// Persisted experiments, assignments and outcomes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExperimentState {
+    experiments: HashMap<String, Experiment>,
+    assignments: HashMap<String, HashMap<String, String>>,
+    outcomes: HashMap<String, HashMap<String, (usize, usize)>>,
+}
+
+/// Runs A/B experiments over cohort members
+/// Members are identified by their cohort pseudonym; raw user ids are never stored
+pub struct ExperimentManager {
+    experiments: HashMap<String, Experiment>,
+    assignments: HashMap<String, HashMap<String, String>>, // experiment_id -> pseudonym -> variant
+    outcomes: HashMap<String, HashMap<String, (usize, usize)>>, // experiment_id -> variant -> (accepted, rejected)
+    alpha: f64,
+    bootstrap_samples: usize,
+    path: Option<PathBuf>, // State is saved here after every change when set
+}
+
+impl ExperimentManager {
+    pub fn new() -> Self {
+        info!("ExperimentManager::new: Creating experiment manager");
+        Self {
+            experiments: HashMap::new(),
+            assignments: HashMap::new(),
+            outcomes: HashMap::new(),
+            alpha: 0.05,
+            bootstrap_samples: 2000,
+            path: None,
+        }
+    }
+
+    /// Open persistent experiments at path (created on first write)
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        info!("ExperimentManager::open: Opening experiments at {:?}", path);
+        let state: ExperimentState = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| format!("Invalid experiment state {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ExperimentState::default(),
+            Err(e) => return Err(format!("Failed to read experiment state {}: {}", path.display(), e)),
+        };
+        let mut manager = Self::new();
+        manager.experiments = state.experiments;
+        manager.assignments = state.assignments;
+        manager.outcomes = state.outcomes;
+        manager.path = Some(path);
+        Ok(manager)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let state = ExperimentState {
+            experiments: self.experiments.clone(),
+            assignments: self.assignments.clone(),
+            outcomes: self.outcomes.clone(),
+        };
+        let json = serde_json::to_string_pretty(&state).map_err(|e| format!("Failed to encode experiment state: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Start an experiment; needs at least two uniquely named variants with positive weights
+    pub fn create_experiment(&mut self, id: &str, description: &str, variants: Vec<Variant>) -> Result<(), String> {
+        info!("ExperimentManager::create_experiment: Creating {} with {} variants", id, variants.len());
+        if self.experiments.contains_key(id) {
+            return Err(format!("Experiment {} already exists", id));
+        }
+        if variants.len() < 2 {
+            return Err("An experiment needs at least two variants".to_string());
+        }
+        if variants.iter().any(|v| !v.weight.is_finite() || v.weight <= 0.0) {
+            return Err("Variant weights must be positive".to_string());
+        }
+        let mut names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        if names.len() != variants.len() {
+            return Err("Variant names must be unique".to_string());
+        }
+        self.experiments.insert(id.to_string(), Experiment {
+            id: id.to_string(),
+            description: description.to_string(),
+            variants,
+            started_at: chrono::Utc::now().timestamp(),
+            stopped_at: None,
+        });
+        self.save()
+    }
+
+    /// Stop assigning members and recording outcomes; analysis stays available
+    pub fn stop_experiment(&mut self, id: &str) -> Result<(), String> {
+        let experiment = self.experiments.get_mut(id).ok_or(format!("Experiment {} not found", id))?;
+        info!("ExperimentManager::stop_experiment: Stopping {}", id);
+        experiment.stopped_at.get_or_insert(chrono::Utc::now().timestamp());
+        self.save()
+    }
+
+    pub fn get_experiment(&self, id: &str) -> Option<&Experiment> {
+        self.experiments.get(id)
+    }
+
+    /// Variant of a cohort member; the same member always lands in the same variant of an experiment
+    pub fn assign(&mut self, experiment_id: &str, user_id: &str, cohort: &CohortManager) -> Result<String, String> {
+        let experiment = self.experiments.get(experiment_id).ok_or(format!("Experiment {} not found", experiment_id))?;
+        if experiment.stopped_at.is_some() {
+            return Err(format!("Experiment {} is stopped", experiment_id));
+        }
+        let pseudonym = cohort.pseudonym_of(user_id).ok_or("Not a cohort member")?;
+        let variant = Self::bucket(experiment, &pseudonym).to_string();
+        let previous = self.assignments.entry(experiment_id.to_string()).or_default().insert(pseudonym, variant.clone());
+        if previous.is_none() {
+            self.save()?;
+        }
+        Ok(variant)
+    }
+
+    /// Weighted bucket from SHA-256 of experiment id and pseudonym
+    fn bucket<'a>(experiment: &'a Experiment, pseudonym: &str) -> &'a str {
+        let digest = ring::digest::digest(&ring::digest::SHA256, format!("{}:{}", experiment.id, pseudonym).as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest.as_ref()[..8]);
+        let total: f64 = experiment.variants.iter().map(|v| v.weight).sum();
+        let mut point = u64::from_be_bytes(bytes) as f64 / u64::MAX as f64 * total;
+        for variant in &experiment.variants {
+            if point < variant.weight {
+                return &variant.name;
+            }
+            point -= variant.weight;
+        }
+        &experiment.variants[experiment.variants.len() - 1].name
+    }
+
+    /// Record whether a member accepted the intervention they were shown
+    /// The outcome also counts toward the member's cohort statistics
+    pub fn record_outcome(
+        &mut self,
+        experiment_id: &str,
+        user_id: &str,
+        accepted: bool,
+        time_saved_min: f64,
+        cohort: &mut CohortManager,
+    ) -> Result<String, String> {
+        let variant = self.assign(experiment_id, user_id, cohort)?;
+        let counts = self.outcomes.entry(experiment_id.to_string()).or_default().entry(variant.clone()).or_default();
+        if accepted {
+            counts.0 += 1;
+        } else {
+            counts.1 += 1;
+        }
+        cohort.record_intervention(user_id, accepted, time_saved_min);
+        self.save()?;
+        Ok(variant)
+    }
+
+    /// Per-variant acceptance, control first
+    pub fn variant_stats(&self, experiment_id: &str) -> Result<Vec<VariantStats>, String> {
+        let experiment = self.experiments.get(experiment_id).ok_or(format!("Experiment {} not found", experiment_id))?;
+        let assignments = self.assignments.get(experiment_id);
+        let outcomes = self.outcomes.get(experiment_id);
+        Ok(experiment
+            .variants
+            .iter()
+            .map(|v| {
+                let (accepted, rejected) = outcomes.and_then(|o| o.get(&v.name)).copied().unwrap_or((0, 0));
+                let decided = accepted + rejected;
+                VariantStats {
+                    variant: v.name.clone(),
+                    members: assignments.map(|a| a.values().filter(|name| **name == v.name).count()).unwrap_or(0),
+                    accepted,
+                    rejected,
+                    acceptance_rate: if decided > 0 { accepted as f64 / decided as f64 } else { 0.0 },
+                }
+            })
+            .collect())
+    }
+
+    /// Chi-squared test of independence over variant × accepted/rejected, plus bootstrap intervals against the control
+    /// Resampling is seeded from the experiment id, so the same data always gives the same intervals
+    pub fn analyze(&self, experiment_id: &str) -> Result<ExperimentAnalysis, String> {
+        let variants = self.variant_stats(experiment_id)?;
+        let (chi_squared, degrees_of_freedom) = chi_squared(&variants);
+        let p_value = if degrees_of_freedom == 0 { 1.0 } else { chi_squared_survival(chi_squared, degrees_of_freedom) };
+
+        let digest = ring::digest::digest(&ring::digest::SHA256, experiment_id.as_bytes());
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(digest.as_ref());
+        let mut rng = StdRng::from_seed(seed);
+        let control = &variants[0];
+        let comparisons = variants[1..].iter().map(|v| self.bootstrap(control, v, &mut rng)).collect();
+
+        let analysis = ExperimentAnalysis {
+            experiment_id: experiment_id.to_string(),
+            chi_squared,
+            degrees_of_freedom,
+            p_value,
+            significant: p_value < self.alpha,
+            comparisons,
+            variants,
+        };
+        info!("ExperimentManager::analyze: {} χ² {:.3} (df {}), p {:.4}", experiment_id, chi_squared, degrees_of_freedom, p_value);
+        Ok(analysis)
+    }
+
+    fn bootstrap(&self, control: &VariantStats, variant: &VariantStats, rng: &mut StdRng) -> BootstrapComparison {
+        let mut resample = |stats: &VariantStats| {
+            let n = stats.accepted + stats.rejected;
+            if n == 0 {
+                return 0.0;
+            }
+            (0..n).filter(|_| rng.gen::<f64>() < stats.acceptance_rate).count() as f64 / n as f64
+        };
+        let mut diffs: Vec<f64> = (0..self.bootstrap_samples).map(|_| resample(variant) - resample(control)).collect();
+        diffs.sort_by(|a, b| a.total_cmp(b));
+        let quantile = |q: f64| diffs.get(((diffs.len().saturating_sub(1)) as f64 * q).round() as usize).copied().unwrap_or(0.0);
+        BootstrapComparison {
+            variant: variant.variant.clone(),
+            rate_diff: variant.acceptance_rate - control.acceptance_rate,
+            ci_low: quantile(0.025),
+            ci_high: quantile(0.975),
+            prob_better: diffs.iter().filter(|d| **d > 0.0).count() as f64 / diffs.len().max(1) as f64,
+        }
+    }
+}
+
+impl Default for ExperimentManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pearson χ² over variants with any outcomes; (statistic, degrees of freedom)
+fn chi_squared(variants: &[VariantStats]) -> (f64, usize) {
+    let rows: Vec<(f64, f64)> = variants
+        .iter()
+        .filter(|v| v.accepted + v.rejected > 0)
+        .map(|v| (v.accepted as f64, v.rejected as f64))
+        .collect();
+    let accepted: f64 = rows.iter().map(|r| r.0).sum();
+    let rejected: f64 = rows.iter().map(|r| r.1).sum();
+    let total = accepted + rejected;
+    if rows.len() < 2 || accepted == 0.0 || rejected == 0.0 {
+        return (0.0, rows.len().saturating_sub(1));
+    }
+    let statistic = rows
+        .iter()
+        .map(|(a, r)| {
+            let n = a + r;
+            let expected_a = n * accepted / total;
+            let expected_r = n * rejected / total;
+            (a - expected_a).powi(2) / expected_a + (r - expected_r).powi(2) / expected_r
+        })
+        .sum();
+    (statistic, rows.len() - 1)
+}
+
+/// P(X ≥ x) for X ~ χ²(df), i.e. the regularized upper incomplete gamma Q(df/2, x/2)
+fn chi_squared_survival(x: f64, df: usize) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let a = df as f64 / 2.0;
+    let x = x / 2.0;
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series for the lower function P
+        let (mut term, mut sum, mut n) = (1.0 / a, 1.0 / a, a);
+        while term.abs() > sum.abs() * 1e-12 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+        }
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q (modified Lentz)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..200 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            d = if d.abs() < tiny { 1.0 / tiny } else { 1.0 / d };
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-12 {
+                break;
+            }
+        }
+        (prefix * h).clamp(0.0, 1.0)
+    }
+}
+
+/// ln Γ(z) (Lanczos approximation, g = 7)
+fn ln_gamma(z: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if z < 0.5 {
+        return (std::f64::consts::PI / (std::f64::consts::PI * z).sin()).ln() - ln_gamma(1.0 - z);
+    }
+    let z = z - 1.0;
+    let t = z + 7.5;
+    let series: f64 = COEFFICIENTS[0] + COEFFICIENTS[1..].iter().enumerate().map(|(i, c)| c / (z + i as f64 + 1.0)).sum::<f64>();
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (z + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UserProfile;
+
+    fn cohort(size: usize) -> CohortManager {
+        let mut cohort = CohortManager::with_pseudonymizer(size, crate::privacy::Pseudonymizer::with_key(b"experiment-test"));
+        cohort.simulate_expansion(&[UserProfile::Developer, UserProfile::Designer]);
+        cohort
+    }
+
+    fn nudge_experiment() -> ExperimentManager {
+        let mut manager = ExperimentManager::new();
+        manager
+            .create_experiment("nudge_wording", "Nudge wording A vs B", vec![Variant::new("A", 1.0), Variant::new("B", 1.0)])
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_assignment_is_deterministic_and_balanced() {
+        let cohort = cohort(200);
+        let mut manager = nudge_experiment();
+        let first: Vec<String> = (0..200).map(|i| manager.assign("nudge_wording", &format!("user_{:03}", i), &cohort).unwrap()).collect();
+        let again: Vec<String> = (0..200).map(|i| manager.assign("nudge_wording", &format!("user_{:03}", i), &cohort).unwrap()).collect();
+        assert_eq!(first, again);
+
+        let b_share = first.iter().filter(|v| *v == "B").count();
+        assert!((70..=130).contains(&b_share));
+        assert_eq!(manager.variant_stats("nudge_wording").unwrap().iter().map(|s| s.members).sum::<usize>(), 200);
+        assert!(manager.assign("nudge_wording", "stranger", &cohort).is_err());
+        assert!(manager.create_experiment("solo", "One arm", vec![Variant::new("A", 1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_significance_detects_better_variant() {
+        let mut cohort = cohort(200);
+        let mut manager = nudge_experiment();
+        for i in 0..200 {
+            let user = format!("user_{:03}", i);
+            let variant = manager.assign("nudge_wording", &user, &cohort).unwrap();
+            // B is accepted 70% of the time, A 30%
+            let accepted = if variant == "B" { i % 10 < 7 } else { i % 10 < 3 };
+            manager.record_outcome("nudge_wording", &user, accepted, 2.0, &mut cohort).unwrap();
+        }
+        let analysis = manager.analyze("nudge_wording").unwrap();
+        assert_eq!(analysis.degrees_of_freedom, 1);
+        assert!(analysis.significant);
+        assert!(analysis.p_value < 0.001);
+        let comparison = &analysis.comparisons[0];
+        assert_eq!(comparison.variant, "B");
+        assert!(comparison.rate_diff > 0.3 && comparison.ci_low > 0.0);
+        assert!(comparison.prob_better > 0.99);
+        assert_eq!(cohort.get_statistics().total_interventions, 200);
+        assert_eq!(manager.analyze("nudge_wording").unwrap().comparisons[0].ci_low, comparison.ci_low);
+    }
+
+    #[test]
+    fn test_assignments_and_outcomes_survive_restart() {
+        let path = std::env::temp_dir().join(format!("athenos_experiments_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut cohort = cohort(10);
+        let mut manager = ExperimentManager::open(path.clone()).unwrap();
+        manager
+            .create_experiment("nudge_wording", "Nudge wording A vs B", vec![Variant::new("A", 1.0), Variant::new("B", 1.0)])
+            .unwrap();
+        let variant = manager.record_outcome("nudge_wording", "user_000", true, 1.0, &mut cohort).unwrap();
+
+        let reopened = ExperimentManager::open(path.clone()).unwrap();
+        assert!(reopened.get_experiment("nudge_wording").is_some());
+        let stats = reopened.variant_stats("nudge_wording").unwrap();
+        let arm = stats.iter().find(|s| s.variant == variant).unwrap();
+        assert_eq!((arm.members, arm.accepted), (1, 1));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_chi_squared_survival_matches_tables() {
+        assert!((chi_squared_survival(3.841, 1) - 0.05).abs() < 1e-3);
+        assert!((chi_squared_survival(5.991, 2) - 0.05).abs() < 1e-3);
+        assert!((chi_squared_survival(1.0, 3) - 0.8013).abs() < 1e-3);
+
+        let mut manager = nudge_experiment();
+        let mut cohort = cohort(20);
+        for i in 0..20 {
+            manager.record_outcome("nudge_wording", &format!("user_{:03}", i), i % 2 == 0, 0.0, &mut cohort).unwrap();
+        }
+        assert!(!manager.analyze("nudge_wording").unwrap().significant);
+    }
+}
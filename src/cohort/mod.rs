@@ -3,58 +3,81 @@
 /// Expand cohort to 200 users, capture intervention acceptance data
 
 use crate::types::*;
+use crate::privacy::Pseudonymizer;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+pub mod experiment;
+
 /// User cohort member
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CohortMember {
-    pub user_id: String,
+    pub pseudonymous_id: String, // Keyed HMAC of the user id; real ids never leave the cohort boundary
     pub profile: UserProfile,
     pub joined_at: i64,
     pub observations_count: usize,
     pub interventions_accepted: usize,
     pub interventions_rejected: usize,
     pub total_time_saved_min: f64,
+    #[serde(default)]
+    pub canary_opt_in: bool, // Receives new automation behaviors before everyone else
+}
+
+/// Rollout segment of a cohort member
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CohortSegment {
+    Canary,
+    Control,
 }
 
 /// Cohort manager for alpha/beta testing
 /// Source: Athenos_AI_Strategy.md#L117
 pub struct CohortManager {
-    members: HashMap<String, CohortMember>,
+    members: HashMap<String, CohortMember>, // pseudonymous_id -> member
     target_size: usize,
+    pseudonymizer: Pseudonymizer,
 }
 
 impl CohortManager {
     /// Create new cohort manager
     pub fn new(target_size: usize) -> Self {
+        Self::with_pseudonymizer(target_size, Pseudonymizer::new())
+    }
+
+    /// Create cohort manager with a specific pseudonymizer (e.g. a persisted key)
+    pub fn with_pseudonymizer(target_size: usize, pseudonymizer: Pseudonymizer) -> Self {
         info!("CohortManager::new: Creating cohort manager with target size {}", target_size);
         Self {
             members: HashMap::new(),
             target_size,
+            pseudonymizer,
         }
     }
 
     /// Add user to cohort
     /// Source: Athenos_AI_Strategy.md#L117
     pub fn add_member(&mut self, user_id: String, profile: UserProfile) {
-        info!("CohortManager::add_member: Adding user {} to cohort", user_id);
+        let pseudonymous_id = self.pseudonymizer.pseudonymize(&user_id);
+        info!("CohortManager::add_member: Adding user {} to cohort", pseudonymous_id);
         let member = CohortMember {
-            user_id: user_id.clone(),
+            pseudonymous_id: pseudonymous_id.clone(),
             profile,
             joined_at: chrono::Utc::now().timestamp(),
             observations_count: 0,
             interventions_accepted: 0,
             interventions_rejected: 0,
             total_time_saved_min: 0.0,
+            canary_opt_in: false,
         };
-        self.members.insert(user_id, member);
+        self.members.insert(pseudonymous_id, member);
     }
 
     /// Record intervention outcome
     pub fn record_intervention(&mut self, user_id: &str, accepted: bool, time_saved_min: f64) {
-        if let Some(member) = self.members.get_mut(user_id) {
+        let pseudonymous_id = self.pseudonymizer.pseudonym_of(user_id);
+        if let Some(member) = self.members.get_mut(&pseudonymous_id) {
             if accepted {
                 member.interventions_accepted += 1;
                 member.total_time_saved_min += time_saved_min;
@@ -64,13 +87,53 @@ impl CohortManager {
         }
     }
 
+    /// Opt a member in to (or out of) the canary segment
+    pub fn set_canary_opt_in(&mut self, user_id: &str, opt_in: bool) -> Result<(), String> {
+        let pseudonymous_id = self.pseudonymizer.pseudonym_of(user_id);
+        let member = self.members.get_mut(&pseudonymous_id).ok_or(format!("{} is not a cohort member", pseudonymous_id))?;
+        info!("CohortManager::set_canary_opt_in: {} -> {}", pseudonymous_id, opt_in);
+        member.canary_opt_in = opt_in;
+        Ok(())
+    }
+
+    /// Rollout segment of a user (None for non-members)
+    pub fn segment_of(&self, user_id: &str) -> Option<CohortSegment> {
+        self.members.get(&self.pseudonymizer.pseudonym_of(user_id)).map(|m| {
+            if m.canary_opt_in {
+                CohortSegment::Canary
+            } else {
+                CohortSegment::Control
+            }
+        })
+    }
+
+    /// Pseudonym of a member (None for non-members)
+    pub fn pseudonym_of(&self, user_id: &str) -> Option<String> {
+        let pseudonym = self.pseudonymizer.pseudonym_of(user_id);
+        self.members.contains_key(&pseudonym).then_some(pseudonym)
+    }
+
     /// Record observation
     pub fn record_observation(&mut self, user_id: &str) {
-        if let Some(member) = self.members.get_mut(user_id) {
+        let pseudonymous_id = self.pseudonymizer.pseudonym_of(user_id);
+        if let Some(member) = self.members.get_mut(&pseudonymous_id) {
             member.observations_count += 1;
         }
     }
 
+    /// Remove a user and unlink their pseudonym (right to erasure)
+    pub fn remove_member(&mut self, user_id: &str) -> Result<bool, String> {
+        let pseudonymous_id = self.pseudonymizer.pseudonym_of(user_id);
+        info!("CohortManager::remove_member: Removing {} from cohort", pseudonymous_id);
+        self.pseudonymizer.forget(user_id)?;
+        Ok(self.members.remove(&pseudonymous_id).is_some())
+    }
+
+    /// Get pseudonymized members for analytics and exports
+    pub fn get_members(&self) -> Vec<&CohortMember> {
+        self.members.values().collect()
+    }
+
     /// Get cohort statistics
     pub fn get_statistics(&self) -> CohortStatistics {
         let total_members = self.members.len();
@@ -139,7 +202,19 @@ mod tests {
         manager.add_member("user_001".to_string(), UserProfile::Developer);
         
         assert_eq!(manager.members.len(), 1);
-        assert!(manager.members.contains_key("user_001"));
+        assert!(!manager.members.contains_key("user_001"));
+        let pseudonym = manager.pseudonymizer.pseudonym_of("user_001");
+        assert!(manager.members.contains_key(&pseudonym));
+    }
+
+    #[test]
+    fn test_remove_member() {
+        let mut manager = CohortManager::new(200);
+        manager.add_member("user_001".to_string(), UserProfile::Developer);
+        
+        assert!(manager.remove_member("user_001").unwrap());
+        assert!(manager.pseudonym_of("user_001").is_none());
+        assert!(!manager.remove_member("user_001").unwrap());
     }
 
     #[test]
@@ -150,7 +225,8 @@ mod tests {
         manager.record_intervention("user_001", true, 11.0);
         manager.record_intervention("user_001", false, 0.0);
         
-        let member = manager.members.get("user_001").unwrap();
+        let pseudonym = manager.pseudonymizer.pseudonym_of("user_001");
+        let member = manager.members.get(&pseudonym).unwrap();
         assert_eq!(member.interventions_accepted, 1);
         assert_eq!(member.interventions_rejected, 1);
         assert_eq!(member.total_time_saved_min, 11.0);
@@ -185,5 +261,14 @@ mod tests {
         assert_eq!(stats.acceptance_rate, 2.0 / 3.0);
         assert_eq!(stats.total_time_saved_min, 16.0);
     }
+
+    #[test]
+    fn test_members_expose_no_raw_ids() {
+        let mut manager = CohortManager::new(200);
+        manager.add_member("alice@example.com".to_string(), UserProfile::Designer);
+        
+        let exported = serde_json::to_string(&manager.get_members()).unwrap();
+        assert!(!exported.contains("alice"));
+    }
 }
 
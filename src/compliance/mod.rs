@@ -2,6 +2,7 @@
 /// SOC2 Readiness + Differential Privacy
 /// Achieve SOC2 readiness; implement differential privacy for aggregated metrics
 
+use crate::config::{AthenosConfig, ConfigListener};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -104,6 +105,11 @@ impl DifferentialPrivacy {
         Self { epsilon }
     }
 
+    /// Privacy parameter in use
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
     /// Add noise to aggregated metric
     /// Source: Athenos_AI_Strategy.md#L137
     pub fn add_noise(&self, value: f64) -> f64 {
@@ -116,6 +122,15 @@ impl DifferentialPrivacy {
         value + noise
     }
 
+    /// Add Laplace noise calibrated to a query's L1 sensitivity: the most one user can change the whole result,
+    /// summed over every released value. Use the same sensitivity for each value of a multi-value release.
+    pub fn add_laplace_noise(&self, value: f64, l1_sensitivity: f64) -> f64 {
+        use rand::Rng;
+        let scale = l1_sensitivity / self.epsilon;
+        let u: f64 = rand::thread_rng().gen_range(-0.5..0.5);
+        value - scale * u.signum() * (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE).ln()
+    }
+
     /// Aggregate metrics with differential privacy
     pub fn aggregate_with_privacy(&self, values: &[f64]) -> f64 {
         let sum: f64 = values.iter().sum();
@@ -125,6 +140,20 @@ impl DifferentialPrivacy {
     }
 }
 
+impl ConfigListener for DifferentialPrivacy {
+    fn config_name(&self) -> &str {
+        "differential_privacy"
+    }
+
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        if config.dp_epsilon != self.epsilon {
+            info!("DifferentialPrivacy::on_config_change: ε {} -> {}", self.epsilon, config.dp_epsilon);
+        }
+        self.epsilon = config.dp_epsilon;
+        Ok(())
+    }
+}
+
 impl Default for SOC2ReadinessTracker {
     fn default() -> Self {
         Self::new()
@@ -170,5 +199,15 @@ mod tests {
         // Should be close to average (20.0) but with noise
         assert!((aggregated - 20.0).abs() < 10.0); // Allow reasonable noise
     }
+
+    #[test]
+    fn test_laplace_noise_scales_with_sensitivity() {
+        let privacy = DifferentialPrivacy::new(2.0);
+        let samples = 4_000;
+        let mean_abs = |sensitivity: f64| (0..samples).map(|_| privacy.add_laplace_noise(10.0, sensitivity) - 10.0).map(f64::abs).sum::<f64>() / samples as f64;
+        // E|Lap(b)| = b = sensitivity / ε
+        assert!((mean_abs(1.0) - 0.5).abs() < 0.1);
+        assert!((mean_abs(168.0) - 84.0).abs() < 17.0);
+    }
 }
 
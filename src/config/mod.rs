@@ -0,0 +1,452 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L126-136
+/// Live Configuration Reload
+/// Watch the configuration file, validate changes and apply them to running modules without a restart
+
+use crate::analytics_export::ExportConfig;
+use crate::gate_policy::GatePolicy;
+use crate::scheduling::timezone::parse_timezone;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// How often a running daemon re-reads the configuration file
+pub const POLL_INTERVAL_SECS: u64 = 5;
+
+/// Edge observer settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ObserverConfig {
+    pub max_events: usize,
+}
+
+impl Default for ObserverConfig {
+    fn default() -> Self {
+        Self { max_events: 1000 }
+    }
+}
+
+/// Rate limits for shared resources
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub inference_requests_per_minute: usize,
+    pub inference_max_concurrent: usize,
+    pub telemetry_max_sends_per_day: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            inference_requests_per_minute: 30,
+            inference_max_concurrent: 1,
+            telemetry_max_sends_per_day: 2,
+        }
+    }
+}
+
+/// Runtime configuration file (JSON; missing sections use defaults)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AthenosConfig {
+    pub observer: ObserverConfig,
+    pub focus_hours: Vec<(u8, u8)>, // (start_hour, end_hour), local to `timezone`
+    pub timezone: String,           // IANA name, e.g. "Europe/Paris"
+    pub dp_epsilon: f64,
+    pub rate_limits: RateLimitConfig,
+    pub gate_policy: GatePolicy,
+    pub analytics_export: ExportConfig,
+}
+
+impl Default for AthenosConfig {
+    fn default() -> Self {
+        Self {
+            observer: ObserverConfig::default(),
+            focus_hours: vec![(9, 11), (14, 16)],
+            timezone: "UTC".to_string(),
+            dp_epsilon: 1.0,
+            rate_limits: RateLimitConfig::default(),
+            gate_policy: GatePolicy::default(),
+            analytics_export: ExportConfig::default(),
+        }
+    }
+}
+
+impl AthenosConfig {
+    /// Parse and validate configuration JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json).map_err(|e| format!("Invalid config: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reject values no module can safely apply
+    pub fn validate(&self) -> Result<(), String> {
+        if self.observer.max_events == 0 || self.observer.max_events > 1_000_000 {
+            return Err(format!("observer.max_events must be 1..=1000000, got {}", self.observer.max_events));
+        }
+        if let Some((start, end)) = self.focus_hours.iter().find(|(start, end)| start >= end || *end > 24) {
+            return Err(format!("focus_hours ({}, {}) must satisfy start < end <= 24", start, end));
+        }
+        parse_timezone(&self.timezone)?;
+        if !self.dp_epsilon.is_finite() || self.dp_epsilon <= 0.0 || self.dp_epsilon > 10.0 {
+            return Err(format!("dp_epsilon must be in (0, 10], got {}", self.dp_epsilon));
+        }
+        let limits = &self.rate_limits;
+        if limits.inference_requests_per_minute == 0 || limits.inference_max_concurrent == 0 || limits.telemetry_max_sends_per_day == 0 {
+            return Err("rate_limits must all be positive".to_string());
+        }
+        self.analytics_export.validate()?;
+        self.gate_policy.validate()
+    }
+
+    /// Flatten to dotted field paths for change detection
+    fn fields(&self) -> BTreeMap<String, String> {
+        fn flatten(prefix: &str, value: &serde_json::Value, out: &mut BTreeMap<String, String>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    for (key, child) in map {
+                        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                        flatten(&path, child, out);
+                    }
+                }
+                other => {
+                    out.insert(prefix.to_string(), other.to_string());
+                }
+            }
+        }
+        let mut out = BTreeMap::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            flatten("", &value, &mut out);
+        }
+        out
+    }
+}
+
+/// Module that reacts to configuration changes
+pub trait ConfigListener {
+    /// Module name recorded in the audit log
+    fn config_name(&self) -> &str;
+
+    /// Apply the new (validated) configuration
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String>;
+}
+
+/// Audit record of an applied or rejected configuration change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigAuditEntry {
+    pub at: i64,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub applied: bool,
+    pub handlers: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Polling watcher for the configuration file
+pub struct ConfigWatcher {
+    path: PathBuf,
+    current: AthenosConfig,
+    last_fingerprint: Option<u64>,
+    audit_log: Vec<ConfigAuditEntry>,
+    state_path: Option<PathBuf>,       // Last applied configuration, kept across restarts
+    audit_path: Option<PathBuf>,       // Audit log, one JSON entry per line, kept across restarts
+    restored: Option<AthenosConfig>,   // Handed to listeners on the first poll
+}
+
+impl ConfigWatcher {
+    /// Create watcher starting from the default configuration
+    pub fn new(path: PathBuf) -> Self {
+        info!("ConfigWatcher::new: Watching {:?}", path);
+        Self {
+            path,
+            current: AthenosConfig::default(),
+            last_fingerprint: None,
+            audit_log: Vec::new(),
+            state_path: None,
+            audit_path: None,
+            restored: None,
+        }
+    }
+
+    /// Watch `path`, resuming from the configuration a previous run applied (kept at `state_path`)
+    /// and the audit log it recorded (kept at `audit_path`)
+    /// The first poll hands that configuration to listeners without auditing it again; only later changes are audited
+    pub fn open(path: PathBuf, state_path: PathBuf, audit_path: PathBuf) -> Self {
+        let mut watcher = Self::new(path);
+        match std::fs::read_to_string(&audit_path) {
+            Ok(contents) => {
+                for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                    match serde_json::from_str(line) {
+                        Ok(entry) => watcher.audit_log.push(entry),
+                        Err(e) => warn!("ConfigWatcher::open: Skipping unreadable audit entry: {}", e),
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("ConfigWatcher::open: Failed to read {:?}: {}", audit_path, e),
+        }
+        watcher.audit_path = Some(audit_path);
+        match std::fs::read_to_string(&state_path) {
+            Ok(json) => match AthenosConfig::from_json(&json) {
+                Ok(config) => {
+                    watcher.current = config.clone();
+                    watcher.restored = Some(config);
+                }
+                Err(e) => warn!("ConfigWatcher::open: Ignoring applied-config state: {}", e),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("ConfigWatcher::open: Failed to read {:?}: {}", state_path, e),
+        }
+        watcher.state_path = Some(state_path);
+        watcher
+    }
+
+    fn save_state(&self) -> Result<(), String> {
+        let Some(path) = &self.state_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string(&self.current).map_err(|e| format!("Failed to encode config: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Append entries to the audit log (and its file, when persisted)
+    fn record(&mut self, entries: &[ConfigAuditEntry]) {
+        self.audit_log.extend(entries.iter().cloned());
+        let Some(path) = &self.audit_path else {
+            return;
+        };
+        let appended = entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).map(|line| line + "\n"))
+            .collect::<Result<String, _>>()
+            .map_err(|e| format!("Failed to encode audit entry: {}", e))
+            .and_then(|lines| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(lines.as_bytes()))
+                    .map_err(|e| format!("Failed to append to {}: {}", path.display(), e))
+            });
+        if let Err(e) = appended {
+            warn!("ConfigWatcher::record: Failed to persist audit log: {}", e);
+        }
+    }
+
+    /// Check the file for changes; valid changes are applied to every listener
+    pub fn poll(&mut self, now: i64, listeners: &mut [&mut dyn ConfigListener]) -> Vec<ConfigAuditEntry> {
+        if let Some(restored) = self.restored.take() {
+            for listener in listeners.iter_mut() {
+                if let Err(e) = listener.on_config_change(&restored) {
+                    warn!("ConfigWatcher::poll: {} rejected the restored config: {}", listener.config_name(), e);
+                }
+            }
+        }
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(_) => return Vec::new(), // No config file: keep current settings
+        };
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        let fingerprint = hasher.finish();
+        if self.last_fingerprint == Some(fingerprint) {
+            return Vec::new();
+        }
+        self.last_fingerprint = Some(fingerprint);
+
+        match AthenosConfig::from_json(&contents) {
+            Ok(config) => self.apply(config, now, listeners),
+            Err(e) => {
+                warn!("ConfigWatcher::poll: Rejecting config change: {}", e);
+                let entry = ConfigAuditEntry {
+                    at: now,
+                    field: "*".to_string(),
+                    old_value: None,
+                    new_value: None,
+                    applied: false,
+                    handlers: Vec::new(),
+                    error: Some(e),
+                };
+                self.record(std::slice::from_ref(&entry));
+                vec![entry]
+            }
+        }
+    }
+
+    /// Apply a validated configuration, recording one audit entry per changed field
+    /// Entries are marked applied only when every listener accepted the change
+    pub fn apply(&mut self, config: AthenosConfig, now: i64, listeners: &mut [&mut dyn ConfigListener]) -> Vec<ConfigAuditEntry> {
+        let old_fields = self.current.fields();
+        let new_fields = config.fields();
+        let changed: Vec<&String> = new_fields.keys().filter(|field| old_fields.get(*field) != new_fields.get(*field)).collect();
+        if changed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut handlers = Vec::new();
+        let mut errors = Vec::new();
+        for listener in listeners.iter_mut() {
+            handlers.push(listener.config_name().to_string());
+            if let Err(e) = listener.on_config_change(&config) {
+                errors.push(format!("{}: {}", listener.config_name(), e));
+            }
+        }
+        let error = if errors.is_empty() { None } else { Some(errors.join("; ")) };
+
+        let entries: Vec<ConfigAuditEntry> = changed
+            .into_iter()
+            .map(|field| ConfigAuditEntry {
+                at: now,
+                field: field.clone(),
+                old_value: old_fields.get(field).cloned(),
+                new_value: new_fields.get(field).cloned(),
+                applied: error.is_none(),
+                handlers: handlers.clone(),
+                error: error.clone(),
+            })
+            .collect();
+        match &error {
+            None => info!("ConfigWatcher::apply: Applied {} changed fields to {} modules", entries.len(), handlers.len()),
+            Some(e) => warn!("ConfigWatcher::apply: {} changed fields not applied everywhere: {}", entries.len(), e),
+        }
+
+        self.current = config;
+        if let Err(e) = self.save_state() {
+            warn!("ConfigWatcher::apply: Failed to persist applied config: {}", e);
+        }
+        self.record(&entries);
+        entries
+    }
+
+    /// Currently applied configuration
+    pub fn current(&self) -> &AthenosConfig {
+        &self.current
+    }
+
+    /// Get configuration audit log
+    pub fn get_audit_log(&self) -> &[ConfigAuditEntry] {
+        &self.audit_log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::DifferentialPrivacy;
+    use crate::edge::EdgeObserver;
+    use crate::scheduling::CalendarNegotiationAgent;
+
+    fn temp_config(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("athenos_config_{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_validation_rejects_bad_values() {
+        assert!(AthenosConfig::from_json(r#"{"dp_epsilon": 0.0}"#).is_err());
+        assert!(AthenosConfig::from_json(r#"{"focus_hours": [[11, 9]]}"#).is_err());
+        assert!(AthenosConfig::from_json(r#"{"observer": {"max_events": 0}}"#).is_err());
+        assert!(AthenosConfig::from_json(r#"{"timezone": "Mars/Olympus_Mons"}"#).is_err());
+        assert!(AthenosConfig::from_json(r#"{"analytics_export": {"interval_secs": 0}}"#).is_err());
+        let partial = AthenosConfig::from_json(r#"{"dp_epsilon": 0.5, "analytics_export": {"format": "duck_db"}}"#).unwrap();
+        assert_eq!(partial.observer.max_events, 1000);
+        assert_eq!(partial.analytics_export.interval_secs, 24 * 3600);
+    }
+
+    #[test]
+    fn test_reload_applies_and_audits_changes() {
+        let path = temp_config("reload");
+        let mut watcher = ConfigWatcher::new(path.clone());
+        let mut observer = EdgeObserver::new(1000);
+        let mut agent = CalendarNegotiationAgent::new();
+        let mut privacy = DifferentialPrivacy::new(1.0);
+        assert!(watcher.poll(0, &mut [&mut observer, &mut agent, &mut privacy]).is_empty()); // No file yet
+
+        std::fs::write(&path, r#"{"observer": {"max_events": 2}, "focus_hours": [[8, 10]], "dp_epsilon": 0.5}"#).unwrap();
+        let entries = watcher.poll(100, &mut [&mut observer, &mut agent, &mut privacy]);
+        let fields: Vec<&str> = entries.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["dp_epsilon", "focus_hours", "observer.max_events"]);
+        assert!(entries.iter().all(|e| e.applied && e.handlers.len() == 3));
+        assert_eq!(entries[0].old_value.as_deref(), Some("1.0"));
+        assert_eq!(privacy.epsilon(), 0.5);
+
+        // Unchanged file is not re-applied
+        assert!(watcher.poll(200, &mut [&mut observer, &mut agent, &mut privacy]).is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_restart_reapplies_without_re_auditing() {
+        let path = temp_config("restart");
+        let state_path = temp_config("restart_state");
+        let audit_path = temp_config("restart_audit");
+        std::fs::write(&path, r#"{"dp_epsilon": 0.5}"#).unwrap();
+        let mut privacy = DifferentialPrivacy::new(1.0);
+        assert_eq!(ConfigWatcher::open(path.clone(), state_path.clone(), audit_path.clone()).poll(100, &mut [&mut privacy]).len(), 1);
+
+        // A restarted process gets the applied config back, but nothing changed, so nothing is audited
+        let mut privacy = DifferentialPrivacy::new(1.0);
+        let mut restarted = ConfigWatcher::open(path.clone(), state_path.clone(), audit_path.clone());
+        assert!(restarted.poll(200, &mut [&mut privacy]).is_empty());
+        assert_eq!(privacy.epsilon(), 0.5);
+        assert_eq!(restarted.get_audit_log().len(), 1); // Audit log survives the restart
+
+        // Edits made while stopped are audited against what was applied before
+        std::fs::write(&path, r#"{"dp_epsilon": 0.25}"#).unwrap();
+        let entries = ConfigWatcher::open(path.clone(), state_path.clone(), audit_path.clone()).poll(300, &mut [&mut privacy]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].old_value.as_deref(), Some("0.5"));
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&state_path);
+        let _ = std::fs::remove_file(&audit_path);
+    }
+
+    #[test]
+    fn test_invalid_reload_keeps_current_config() {
+        let path = temp_config("invalid");
+        let mut watcher = ConfigWatcher::new(path.clone());
+        std::fs::write(&path, r#"{"dp_epsilon": -1}"#).unwrap();
+
+        let entries = watcher.poll(100, &mut []);
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].applied);
+        assert_eq!(watcher.current().dp_epsilon, 1.0);
+        assert_eq!(watcher.get_audit_log().len(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_listener_error_not_audited_as_applied() {
+        struct Rejecting;
+        impl ConfigListener for Rejecting {
+            fn config_name(&self) -> &str {
+                "rejecting"
+            }
+            fn on_config_change(&mut self, _config: &AthenosConfig) -> Result<(), String> {
+                Err("unsupported".to_string())
+            }
+        }
+        let mut watcher = ConfigWatcher::new(temp_config("rejecting"));
+        let mut privacy = DifferentialPrivacy::new(1.0);
+        let config = AthenosConfig { dp_epsilon: 0.5, ..AthenosConfig::default() };
+
+        let entries = watcher.apply(config, 100, &mut [&mut privacy, &mut Rejecting]);
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].applied);
+        assert_eq!(entries[0].error.as_deref(), Some("rejecting: unsupported"));
+    }
+}
@@ -2,8 +2,10 @@
 /// Micro-consent UX + Transparency Timeline
 /// Integrate micro-consent UX and transparency timeline
 
-use crate::privacy::ConsentLedger;
+use crate::privacy::{ConsentLedger, EncryptionManager};
+use crate::types::ActionType;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use tracing::info;
 
 /// Micro-consent request
@@ -14,6 +16,41 @@ pub struct MicroConsent {
     pub requested_at: i64,
     pub granted_at: Option<i64>,
     pub revoked_at: Option<i64>,
+    #[serde(default)]
+    pub ttl_secs: Option<i64>,   // Grant lifetime; None never expires
+    #[serde(default)]
+    pub expires_at: Option<i64>, // Set on grant when a TTL applies
+}
+
+impl MicroConsent {
+    /// Granted, not revoked and not past its expiry
+    pub fn is_active(&self, now: i64) -> bool {
+        self.granted_at.is_some() && self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > now)
+    }
+}
+
+/// Capability covering workflow/app-usage logging and everything derived from it
+pub const BEHAVIORAL_LOGGING_CAPABILITY: &str = "behavioral_logging";
+
+/// Time before expiry at which a consent is offered for renewal
+pub const RENEWAL_WINDOW_SECS: i64 = 3 * 86400;
+
+/// Why a consent should be re-prompted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RenewalReason {
+    ExpiringSoon,
+    Expired,
+}
+
+/// Consent the UX layer should re-prompt for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentRenewal {
+    pub capability: String,
+    pub description: String,
+    pub ttl_secs: i64,
+    pub expires_at: i64,
+    pub reason: RenewalReason,
 }
 
 /// Transparency timeline entry
@@ -26,29 +63,108 @@ pub struct TimelineEntry {
     pub action_taken: Option<String>,
 }
 
+/// What a module did with data derived under a revoked capability
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataDisposition {
+    NotAffected, // Module holds nothing derived under the capability
+    Purged,      // Deleted
+    Quarantined, // Withheld from all use pending purge
+    Anonymized,  // Kept with identifying fields stripped
+    Failed,
+}
+
+/// Module hook run when a consent scope is revoked
+pub trait RevocationHandler {
+    fn revocation_handler_name(&self) -> &str;
+    /// Purge or quarantine data derived under `capability`; returns the disposition and item count
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String>;
+}
+
+/// Completion receipt from one handler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandlerReceipt {
+    pub handler: String,
+    pub disposition: DataDisposition,
+    pub items: usize,
+    pub error: Option<String>,
+}
+
+/// Completion receipt for one revocation across every handler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationReceipt {
+    pub capability: String,
+    pub revoked_at: i64,
+    pub handlers: Vec<HandlerReceipt>,
+    pub complete: bool, // Every handler succeeded
+}
+
 /// Micro-consent manager with transparency timeline
 /// Source: Athenos_AI_Strategy.md#L112
 pub struct MicroConsentManager {
     consent_ledger: ConsentLedger,
     micro_consents: Vec<MicroConsent>,
     timeline: Vec<TimelineEntry>,
+    revocation_receipts: Vec<RevocationReceipt>,
 }
 
 impl MicroConsentManager {
     /// Create new micro-consent manager
     pub fn new() -> Self {
+        Self::with_ledger(ConsentLedger::new())
+    }
+
+    /// Create micro-consent manager over a persisted consent ledger
+    pub fn with_ledger(consent_ledger: ConsentLedger) -> Self {
         info!("MicroConsentManager::new: Creating micro-consent manager");
         Self {
-            consent_ledger: ConsentLedger::new(),
+            consent_ledger,
             micro_consents: Vec::new(),
             timeline: Vec::new(),
+            revocation_receipts: Vec::new(),
         }
     }
 
+    /// Restore micro-consents saved by `save` so grants, TTLs and expiries survive restarts
+    /// A missing file starts with no micro-consents
+    pub fn load(consent_ledger: ConsentLedger, path: impl AsRef<Path>, encryption: &EncryptionManager) -> Result<Self, String> {
+        let path = path.as_ref();
+        let mut manager = Self::with_ledger(consent_ledger);
+        let encrypted = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(manager),
+            Err(e) => return Err(format!("Failed to read micro-consents {}: {}", path.display(), e)),
+        };
+        let plaintext = encryption
+            .decrypt(&encrypted)
+            .map_err(|e| format!("Micro-consents {} failed verification: {}", path.display(), e))?;
+        manager.micro_consents = serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid micro-consents: {}", e))?;
+        info!("MicroConsentManager::load: Loaded {} micro-consents", manager.micro_consents.len());
+        Ok(manager)
+    }
+
+    /// Encrypt micro-consents to `path`, next to the consent ledger they extend
+    pub fn save(&self, path: impl AsRef<Path>, encryption: &EncryptionManager) -> Result<(), String> {
+        let path = path.as_ref();
+        let plaintext = serde_json::to_vec(&self.micro_consents).map_err(|e| format!("Failed to encode micro-consents: {}", e))?;
+        crate::privacy::write_atomic(path, &encryption.encrypt(&plaintext)?)?;
+        info!("MicroConsentManager::save: Saved {} micro-consents to {}", self.micro_consents.len(), path.display());
+        Ok(())
+    }
+
     /// Request micro-consent for a capability
     /// Source: Athenos_AI_Strategy.md#L112
     pub fn request_consent(&mut self, capability: String, description: String) -> MicroConsent {
-        info!("MicroConsentManager::request_consent: Requesting consent for {}", capability);
+        self.request(capability, description, None)
+    }
+
+    /// Request micro-consent that lapses `ttl_secs` after it is granted
+    pub fn request_consent_with_expiry(&mut self, capability: String, description: String, ttl_secs: i64) -> MicroConsent {
+        self.request(capability, description, Some(ttl_secs.max(1)))
+    }
+
+    fn request(&mut self, capability: String, description: String, ttl_secs: Option<i64>) -> MicroConsent {
+        info!("MicroConsentManager::request_consent: Requesting consent for {} (ttl {:?})", capability, ttl_secs);
         
         let consent = MicroConsent {
             capability: capability.clone(),
@@ -56,6 +172,8 @@ impl MicroConsentManager {
             requested_at: chrono::Utc::now().timestamp(),
             granted_at: None,
             revoked_at: None,
+            ttl_secs,
+            expires_at: None,
         };
         
         self.micro_consents.push(consent.clone());
@@ -75,15 +193,26 @@ impl MicroConsentManager {
         info!("MicroConsentManager::grant_consent: Granting consent for {}", capability);
         
         if let Some(consent) = self.micro_consents.iter_mut().find(|c| c.capability == capability && c.granted_at.is_none()) {
-            consent.granted_at = Some(chrono::Utc::now().timestamp());
+            let now = chrono::Utc::now().timestamp();
+            consent.granted_at = Some(now);
+            consent.expires_at = consent.ttl_secs.map(|ttl| now + ttl);
             
             // Update consent ledger
             match capability {
                 "cloud_sync" => self.consent_ledger.opt_in_cloud_sync = true,
                 "behavioral_logging" => self.consent_ledger.opt_in_behavioral_logging = true,
                 "emotion_detection" => self.consent_ledger.opt_in_emotion_detection = true,
-                "automation" => self.consent_ledger.opt_in_automation = true,
-                _ => {}
+                "automation" => {
+                    // Legacy blanket consent covers every action type
+                    for action_type in ActionType::all() {
+                        self.consent_ledger.grant_automation_scope(action_type);
+                    }
+                }
+                scope => {
+                    if let Some(action_type) = ActionType::from_consent_scope(scope) {
+                        self.consent_ledger.grant_automation_scope(action_type);
+                    }
+                }
             }
             
             self.add_timeline_entry(
@@ -103,7 +232,8 @@ impl MicroConsentManager {
     pub fn revoke_consent(&mut self, capability: &str, reason: Option<String>) -> Result<(), String> {
         info!("MicroConsentManager::revoke_consent: Revoking consent for {}", capability);
         
-        if let Some(consent) = self.micro_consents.iter_mut().find(|c| c.capability == capability) {
+        // Latest request wins; earlier ones may have been renewed
+        if let Some(consent) = self.micro_consents.iter_mut().rev().find(|c| c.capability == capability) {
             consent.revoked_at = Some(chrono::Utc::now().timestamp());
             self.consent_ledger.revoke_consent(capability.to_string(), reason.clone());
             
@@ -120,6 +250,125 @@ impl MicroConsentManager {
         }
     }
 
+    /// Revoke every granted consent past its expiry; returns the expired capabilities
+    pub fn expire_consents(&mut self, now: i64) -> Vec<String> {
+        let mut expired = Vec::new();
+        for consent in &mut self.micro_consents {
+            let lapsed = consent.granted_at.is_some() && consent.revoked_at.is_none() && consent.expires_at.is_some_and(|at| at <= now);
+            if lapsed {
+                consent.revoked_at = Some(now);
+                expired.push(consent.capability.clone());
+            }
+        }
+        for capability in &expired {
+            info!("MicroConsentManager::expire_consents: {} expired", capability);
+            self.consent_ledger.revoke_consent(capability.clone(), Some("expired".to_string()));
+            self.add_timeline_entry(
+                "consent_expired".to_string(),
+                format!("Consent for {} expired and was revoked", capability),
+                vec![capability.clone()],
+                Some("Re-prompt to renew".to_string()),
+            );
+        }
+        expired
+    }
+
+    /// Expiring consents due for renewal, and expired ones not yet renewed, so the UX layer can re-prompt
+    pub fn consents_needing_renewal(&self, now: i64) -> Vec<ConsentRenewal> {
+        let mut renewals: Vec<ConsentRenewal> = Vec::new();
+        for (i, consent) in self.micro_consents.iter().enumerate() {
+            let (Some(ttl_secs), Some(expires_at)) = (consent.ttl_secs, consent.expires_at) else {
+                continue;
+            };
+            // A later request for the capability supersedes this one
+            if self.micro_consents[i + 1..].iter().any(|c| c.capability == consent.capability) {
+                continue;
+            }
+            let reason = if expires_at <= now && consent.revoked_at.is_none_or(|at| at >= expires_at) {
+                RenewalReason::Expired
+            } else if consent.is_active(now) && expires_at - now <= RENEWAL_WINDOW_SECS {
+                RenewalReason::ExpiringSoon
+            } else {
+                continue;
+            };
+            renewals.push(ConsentRenewal {
+                capability: consent.capability.clone(),
+                description: consent.description.clone(),
+                ttl_secs,
+                expires_at,
+                reason,
+            });
+        }
+        renewals
+    }
+
+    /// Revoke micro-consent and propagate the revocation to every module holding derived data
+    /// Handler failures do not stop propagation; they are recorded and the receipt is marked incomplete
+    pub fn revoke_and_propagate(
+        &mut self,
+        capability: &str,
+        reason: Option<String>,
+        handlers: &mut [&mut dyn RevocationHandler],
+    ) -> Result<RevocationReceipt, String> {
+        self.revoke_consent(capability, reason)?;
+        Ok(self.propagate_revocation(capability, handlers))
+    }
+
+    /// Withdraw a capability from the ledger when no micro-consent for it was requested this session
+    pub fn revoke_ledger_consent(&mut self, capability: &str, reason: Option<String>) {
+        info!("MicroConsentManager::revoke_ledger_consent: Revoking ledger consent for {}", capability);
+        self.consent_ledger.revoke_consent(capability.to_string(), reason.clone());
+        self.add_timeline_entry(
+            "consent_revoked".to_string(),
+            format!("Revoked consent for: {} - reason: {:?}", capability, reason),
+            vec![capability.to_string()],
+            None,
+        );
+    }
+
+    /// Tell every module holding data derived under an already revoked capability to drop it
+    pub fn propagate_revocation(&mut self, capability: &str, handlers: &mut [&mut dyn RevocationHandler]) -> RevocationReceipt {
+        let handlers: Vec<HandlerReceipt> = handlers
+            .iter_mut()
+            .map(|handler| {
+                let name = handler.revocation_handler_name().to_string();
+                match handler.on_consent_revoked(capability) {
+                    Ok((disposition, items)) => HandlerReceipt { handler: name, disposition, items, error: None },
+                    Err(e) => {
+                        info!("MicroConsentManager::propagate_revocation: {} failed: {}", name, e);
+                        HandlerReceipt { handler: name, disposition: DataDisposition::Failed, items: 0, error: Some(e) }
+                    }
+                }
+            })
+            .collect();
+        let receipt = RevocationReceipt {
+            capability: capability.to_string(),
+            revoked_at: chrono::Utc::now().timestamp(),
+            complete: handlers.iter().all(|h| h.error.is_none()),
+            handlers,
+        };
+
+        let affected: Vec<String> = receipt
+            .handlers
+            .iter()
+            .filter(|h| h.disposition != DataDisposition::NotAffected)
+            .map(|h| format!("{}: {:?} {}", h.handler, h.disposition, h.items))
+            .collect();
+        self.add_timeline_entry(
+            "consent_revocation_propagated".to_string(),
+            format!("Propagated revocation of {} to {} modules", capability, receipt.handlers.len()),
+            vec![capability.to_string()],
+            Some(if affected.is_empty() { "No derived data held".to_string() } else { affected.join("; ") }),
+        );
+        self.revocation_receipts.push(receipt.clone());
+        receipt
+    }
+
+    /// Receipts of propagated revocations, oldest first
+    pub fn get_revocation_receipts(&self) -> &[RevocationReceipt] {
+        &self.revocation_receipts
+    }
+
     /// Add timeline entry for transparency
     /// Source: Strategic_Reinforcements_Gap_Closures.md#L14
     pub fn add_timeline_entry(&mut self, event_type: String, description: String, data_accessed: Vec<String>, action_taken: Option<String>) {
@@ -146,11 +395,22 @@ impl MicroConsentManager {
         self.timeline[start..].iter().collect()
     }
 
-    /// Check if capability has consent
+    /// Check if an action type may be automated (per-ActionType scope)
+    pub fn allows_action(&self, action_type: &ActionType) -> bool {
+        self.consent_ledger.allows_action(action_type)
+    }
+
+    /// Get the consent ledger backing micro-consents
+    pub fn consent_ledger(&self) -> &ConsentLedger {
+        &self.consent_ledger
+    }
+
+    /// Check if capability has consent; an expired grant no longer counts even before it is swept
     pub fn has_consent(&self, capability: &str) -> bool {
+        let now = chrono::Utc::now().timestamp();
         self.micro_consents
             .iter()
-            .any(|c| c.capability == capability && c.granted_at.is_some() && c.revoked_at.is_none())
+            .any(|c| c.capability == capability && c.is_active(now))
     }
 }
 
@@ -198,6 +458,84 @@ mod tests {
         assert!(!manager.has_consent("cloud_sync"));
     }
 
+    #[test]
+    fn test_action_type_automation_consent() {
+        let mut manager = MicroConsentManager::new();
+        let scope = ActionType::FocusMode.consent_scope();
+        manager.request_consent(scope.clone(), "Let Athenos toggle focus mode".to_string());
+        manager.grant_consent(&scope).unwrap();
+        
+        assert!(manager.allows_action(&ActionType::FocusMode));
+        assert!(!manager.allows_action(&ActionType::AutomationMacro));
+        
+        manager.revoke_consent(&scope, None).unwrap();
+        assert!(!manager.allows_action(&ActionType::FocusMode));
+    }
+
+    #[test]
+    fn test_expiring_consent_renewal() {
+        let mut manager = MicroConsentManager::new();
+        let consent = manager.request_consent_with_expiry("cloud_sync".to_string(), "Sync for a week".to_string(), 7 * 86400);
+        assert_eq!(consent.ttl_secs, Some(7 * 86400));
+        manager.grant_consent("cloud_sync").unwrap();
+        let expires_at = manager.micro_consents[0].expires_at.unwrap();
+        assert!(manager.has_consent("cloud_sync"));
+        assert!(manager.consent_ledger().can_sync_to_cloud());
+        
+        assert!(manager.consents_needing_renewal(expires_at - 4 * 86400).is_empty());
+        let soon = manager.consents_needing_renewal(expires_at - 86400);
+        assert_eq!(soon[0].reason, RenewalReason::ExpiringSoon);
+        
+        assert!(manager.expire_consents(expires_at - 1).is_empty());
+        assert_eq!(manager.expire_consents(expires_at), vec!["cloud_sync"]);
+        assert!(!manager.has_consent("cloud_sync"));
+        assert!(!manager.consent_ledger().can_sync_to_cloud());
+        assert_eq!(manager.get_timeline(Some(1))[0].event_type, "consent_expired");
+        assert_eq!(manager.consents_needing_renewal(expires_at + 10)[0].reason, RenewalReason::Expired);
+        
+        // Renewing supersedes the expired grant
+        manager.request_consent_with_expiry("cloud_sync".to_string(), "Sync for a week".to_string(), 7 * 86400);
+        manager.grant_consent("cloud_sync").unwrap();
+        assert!(manager.has_consent("cloud_sync"));
+        let renewals = manager.consents_needing_renewal(expires_at - 86400);
+        assert_eq!(renewals.len(), 1);
+        assert_eq!(renewals[0].reason, RenewalReason::ExpiringSoon);
+        
+        // Manual revocation is not re-prompted, and consents without a TTL never expire
+        manager.revoke_consent("cloud_sync", None).unwrap();
+        assert!(manager.consents_needing_renewal(expires_at - 86400).is_empty());
+        manager.request_consent("behavioral_logging".to_string(), "Test".to_string());
+        manager.grant_consent("behavioral_logging").unwrap();
+        assert!(manager.expire_consents(i64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_expired_grant_revoked_after_restart() {
+        let dir = std::env::temp_dir().join(format!("athenos_micro_consents_{}", std::process::id()));
+        let path = dir.join("micro_consents.enc");
+        let encryption = EncryptionManager::new().unwrap();
+
+        let mut manager = MicroConsentManager::new();
+        manager.request_consent_with_expiry("cloud_sync".to_string(), "Sync for a day".to_string(), 86400);
+        manager.grant_consent("cloud_sync").unwrap();
+        let expires_at = manager.micro_consents[0].expires_at.unwrap();
+        manager.save(&path, &encryption).unwrap();
+        let ledger = manager.consent_ledger().clone();
+        drop(manager);
+
+        // The next session sees the grant and its expiry, and the lapsed grant is revoked
+        let mut restarted = MicroConsentManager::load(ledger, &path, &encryption).unwrap();
+        assert!(restarted.has_consent("cloud_sync"));
+        assert_eq!(restarted.consents_needing_renewal(expires_at - 3600)[0].reason, RenewalReason::ExpiringSoon);
+        assert_eq!(restarted.expire_consents(expires_at), vec!["cloud_sync"]);
+        assert!(!restarted.consent_ledger().can_sync_to_cloud());
+        assert_eq!(restarted.consents_needing_renewal(expires_at + 10)[0].reason, RenewalReason::Expired);
+
+        assert!(MicroConsentManager::load(ConsentLedger::new(), &path, &EncryptionManager::new().unwrap()).is_err());
+        assert!(MicroConsentManager::load(ConsentLedger::new(), dir.join("missing.enc"), &encryption).unwrap().micro_consents.is_empty());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_timeline_entries() {
         let mut manager = MicroConsentManager::new();
@@ -213,5 +551,49 @@ mod tests {
         let timeline = manager.get_timeline(Some(10));
         assert!(timeline.len() >= 2);
     }
-}
 
+    struct Holder {
+        items: usize,
+        fails: bool,
+    }
+
+    impl RevocationHandler for Holder {
+        fn revocation_handler_name(&self) -> &str {
+            if self.fails { "flaky" } else { "holder" }
+        }
+
+        fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+            if self.fails {
+                return Err("store locked".to_string());
+            }
+            if capability != "emotion_detection" {
+                return Ok((DataDisposition::NotAffected, 0));
+            }
+            let purged = self.items;
+            self.items = 0;
+            Ok((DataDisposition::Purged, purged))
+        }
+    }
+
+    #[test]
+    fn test_revocation_propagates_with_receipts() {
+        let mut manager = MicroConsentManager::new();
+        manager.request_consent("emotion_detection".to_string(), "Test".to_string());
+        manager.grant_consent("emotion_detection").unwrap();
+
+        let mut holder = Holder { items: 4, fails: false };
+        let mut flaky = Holder { items: 1, fails: true };
+        let receipt = manager.revoke_and_propagate("emotion_detection", None, &mut [&mut holder, &mut flaky]).unwrap();
+
+        assert_eq!(holder.items, 0);
+        assert!(!manager.has_consent("emotion_detection"));
+        assert_eq!(receipt.handlers[0].disposition, DataDisposition::Purged);
+        assert_eq!(receipt.handlers[0].items, 4);
+        assert_eq!(receipt.handlers[1].disposition, DataDisposition::Failed);
+        assert!(!receipt.complete);
+        assert_eq!(manager.get_revocation_receipts().len(), 1);
+        assert_eq!(manager.get_timeline(Some(1))[0].event_type, "consent_revocation_propagated");
+
+        assert!(manager.revoke_and_propagate("unknown", None, &mut [&mut holder]).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+/// Phase: B | Step: 2 | Source: TRAINING CONCEPT.txt#L40-57
+/// Fine-Tuning Corpus Loader
+/// Stream athenos_seed.jsonl-style lines into validated, deduplicated observation/insight pairs split into train and eval sets
+
+use super::{validate_observation, ImportError};
+use crate::types::Observation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+use tracing::info;
+
+/// One supervised example
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingExample {
+    pub line: usize,
+    pub observation: Observation,
+    pub insight: String,          // Target text: the line's `insight`, else the action description
+    pub explicit_insight: bool,   // False when the target was derived from the action
+}
+
+/// Split settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpusConfig {
+    pub eval_fraction: f64, // Share of examples held out, assigned by a hash of the id so splits are stable across runs
+}
+
+impl Default for CorpusConfig {
+    fn default() -> Self {
+        Self { eval_fraction: 0.2 }
+    }
+}
+
+/// What happened to each input line
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorpusReport {
+    pub loaded: usize,
+    pub errors: Vec<ImportError>,     // Invalid lines
+    pub duplicates: Vec<ImportError>, // Valid lines dropped as a repeated id or repeated content
+}
+
+/// Deduplicated examples ready for a training backend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorpusDataset {
+    pub train: Vec<TrainingExample>,
+    pub eval: Vec<TrainingExample>,
+    pub report: CorpusReport,
+}
+
+impl CorpusDataset {
+    pub fn len(&self) -> usize {
+        self.train.len() + self.eval.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Training examples in fixed-size batches
+    pub fn train_batches(&self, batch_size: usize) -> std::slice::Chunks<'_, TrainingExample> {
+        self.train.chunks(batch_size.max(1))
+    }
+}
+
+/// Outcome of a training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingSummary {
+    pub backend: String,
+    pub train_examples: usize,
+    pub eval_examples: usize,
+    pub eval_loss: Option<f64>, // None when the backend does not evaluate
+}
+
+/// Consumes a corpus to train or adapt a model
+pub trait TrainingBackend {
+    fn backend_name(&self) -> &str;
+    fn train(&mut self, dataset: &CorpusDataset) -> Result<TrainingSummary, String>;
+}
+
+/// Parse one corpus line (1-based `line`) into an example
+pub fn parse_example(line: usize, text: &str) -> Result<TrainingExample, String> {
+    let mut value: serde_json::Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let insight = match value.as_object_mut().and_then(|object| object.remove("insight")) {
+        Some(serde_json::Value::String(insight)) => Some(insight),
+        Some(serde_json::Value::Null) | None => None,
+        Some(_) => return Err("Field 'insight' must be a string".to_string()),
+    };
+
+    // Corpus lines have no capture time; 0 keeps the dataset identical across loads
+    let observation = validate_observation(value, 0)?;
+    if observation.observation.is_empty() {
+        return Err("Observation sequence must not be empty".to_string());
+    }
+    let explicit_insight = insight.is_some();
+    let insight = insight.unwrap_or_else(|| observation.action.description.clone()).trim().to_string();
+    if insight.is_empty() {
+        return Err("Insight must not be empty".to_string());
+    }
+    Ok(TrainingExample { line, observation, insight, explicit_insight })
+}
+
+/// Lazily parses a corpus one line at a time; blank lines are skipped
+pub struct CorpusReader<R: BufRead> {
+    lines: Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> CorpusReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { lines: reader.lines(), line: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for CorpusReader<R> {
+    type Item = Result<TrainingExample, ImportError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = self.lines.next()?;
+            self.line += 1;
+            let line = self.line;
+            match text {
+                Ok(text) if text.trim().is_empty() => continue,
+                Ok(text) => return Some(parse_example(line, &text).map_err(|message| ImportError { line, message })),
+                Err(e) => return Some(Err(ImportError { line, message: format!("Failed to read line: {}", e) })),
+            }
+        }
+    }
+}
+
+/// Position of an id in [0, 1), stable across runs and platforms
+fn split_bucket(id: &str) -> f64 {
+    let digest = ring::digest::digest(&ring::digest::SHA256, id.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest.as_ref()[..8]);
+    u64::from_be_bytes(bytes) as f64 / (u64::MAX as f64 + 1.0)
+}
+
+/// Identical steps, intent and target text count as the same example whatever the id
+fn content_key(example: &TrainingExample) -> String {
+    format!("{:?}|{:?}|{}", example.observation.observation, example.observation.intent, example.insight.to_lowercase())
+}
+
+/// Load a corpus, reporting bad and duplicate lines instead of failing on them
+pub fn load_corpus<R: BufRead>(reader: R, config: &CorpusConfig) -> CorpusDataset {
+    let mut report = CorpusReport::default();
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+    let mut seen_content: HashMap<String, usize> = HashMap::new();
+    let mut examples = Vec::new();
+
+    for parsed in CorpusReader::new(reader) {
+        let example = match parsed {
+            Ok(example) => example,
+            Err(error) => {
+                report.errors.push(error);
+                continue;
+            }
+        };
+        let line = example.line;
+        if let Some(first) = seen_ids.get(&example.observation.id) {
+            report.duplicates.push(ImportError { line, message: format!("Duplicate id {} (first on line {})", example.observation.id, first) });
+            continue;
+        }
+        let key = content_key(&example);
+        if let Some(first) = seen_content.get(&key) {
+            report.duplicates.push(ImportError { line, message: format!("Duplicate of the example on line {}", first) });
+            continue;
+        }
+        seen_ids.insert(example.observation.id.clone(), line);
+        seen_content.insert(key, line);
+        examples.push(example);
+    }
+    report.loaded = examples.len();
+
+    let fraction = config.eval_fraction.clamp(0.0, 1.0);
+    let buckets: Vec<f64> = examples.iter().map(|example| split_bucket(&example.observation.id)).collect();
+    let mut is_eval: Vec<bool> = buckets.iter().map(|bucket| *bucket < fraction).collect();
+    // Small corpora can hash entirely into train; hold out the lowest bucket so there is always something to evaluate on
+    if fraction > 0.0 && examples.len() >= 2 && !is_eval.contains(&true) {
+        if let Some(lowest) = (0..buckets.len()).min_by(|a, b| buckets[*a].total_cmp(&buckets[*b])) {
+            is_eval[lowest] = true;
+        }
+    }
+    let (eval, train): (Vec<_>, Vec<_>) = examples.into_iter().zip(is_eval).partition(|(_, eval)| *eval);
+    let dataset = CorpusDataset {
+        train: train.into_iter().map(|(example, _)| example).collect(),
+        eval: eval.into_iter().map(|(example, _)| example).collect(),
+        report,
+    };
+
+    info!(
+        "corpus::load_corpus: {} examples ({} train, {} eval), {} invalid, {} duplicate",
+        dataset.report.loaded, dataset.train.len(), dataset.eval.len(), dataset.report.errors.len(), dataset.report.duplicates.len()
+    );
+    dataset
+}
+
+/// Load a corpus file
+pub fn load_corpus_file(path: &Path, config: &CorpusConfig) -> Result<CorpusDataset, String> {
+    info!("corpus::load_corpus_file: Loading {:?}", path);
+    let file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    Ok(load_corpus(BufReader::new(file), config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(id: &str, steps: &str, insight: Option<&str>) -> String {
+        let insight = insight.map(|text| format!(r#","insight":"{}""#, text)).unwrap_or_default();
+        format!(
+            r#"{{"id":"{}","profile":"developer","observation":[{}],"metrics":{{"repeat_count":8}},"intent":"suggest_shortcut","action":{{"type":"automation_macro","description":"Dev Startup macro","confidence":"high","risk":"none"}},"source":"seed"{}}}"#,
+            id, steps, insight
+        )
+    }
+
+    #[test]
+    fn test_load_validates_dedups_and_splits() {
+        let input = [
+            line("unit_0001", r#""Teams","Gmail","IDE""#, None),
+            String::new(),
+            line("unit_0002", r#""Slack","IDE""#, Some("You check Slack before coding.")),
+            "{not json".to_string(),
+            line("unit_0003", "", None),
+            line("unit_0002", r#""Jira""#, None),
+            line("unit_0004", r#""teams","gmail""#, Some("Batch your inboxes.")),
+            line("unit_0005", r#""teams","gmail""#, Some("batch your inboxes.")),
+        ]
+        .join("\n");
+
+        let dataset = load_corpus(input.as_bytes(), &CorpusConfig::default());
+        assert_eq!(dataset.report.loaded, 3);
+        assert_eq!(dataset.len(), 3);
+        let error_lines: Vec<usize> = dataset.report.errors.iter().map(|e| e.line).collect();
+        assert_eq!(error_lines, vec![4, 5]);
+        assert!(dataset.report.errors[1].message.contains("must not be empty"));
+        let duplicate_lines: Vec<usize> = dataset.report.duplicates.iter().map(|e| e.line).collect();
+        assert_eq!(duplicate_lines, vec![6, 8]);
+        assert!(dataset.report.duplicates[0].message.contains("first on line 3"));
+
+        let all: Vec<&TrainingExample> = dataset.train.iter().chain(&dataset.eval).collect();
+        let first = all.iter().find(|e| e.observation.id == "unit_0001").unwrap();
+        assert_eq!((first.insight.as_str(), first.explicit_insight), ("Dev Startup macro", false));
+        assert!(all.iter().any(|e| e.insight == "You check Slack before coding." && e.explicit_insight));
+
+        // Split is stable and never leaves the eval set empty
+        assert!(!dataset.eval.is_empty() && !dataset.train.is_empty());
+        let again = load_corpus(input.as_bytes(), &CorpusConfig::default());
+        let ids = |examples: &[TrainingExample]| examples.iter().map(|e| e.observation.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&again.eval), ids(&dataset.eval));
+        assert_eq!(dataset.train_batches(2).map(|batch| batch.len()).sum::<usize>(), dataset.train.len());
+    }
+}
@@ -0,0 +1,209 @@
+/// Phase: B | Source: TRAINING CONCEPT.txt#L40-57
+/// Observation Dataset Import/Export
+/// Streaming JSONL import with schema validation, and JSONL export of observations
+
+use crate::labeling::{self, LabelStore};
+use crate::local_stack::FeatureStore;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use tracing::info;
+
+pub mod corpus;
+
+const REQUIRED_FIELDS: [&str; 7] = ["id", "profile", "observation", "metrics", "intent", "action", "source"];
+
+/// Rejected input line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Import summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub errors: Vec<ImportError>,
+}
+
+/// Validate a JSON value against the observation schema
+/// Accepts seed-format actions (`type`) and fills a missing timestamp with `default_timestamp`
+pub fn validate_observation(value: serde_json::Value, default_timestamp: i64) -> Result<Observation, String> {
+    let mut value = value;
+    let object = value.as_object_mut().ok_or("Observation must be a JSON object")?;
+
+    for field in REQUIRED_FIELDS {
+        if !object.contains_key(field) {
+            return Err(format!("Missing required field: {}", field));
+        }
+    }
+
+    if let Some(action) = object.get_mut("action").and_then(|a| a.as_object_mut()) {
+        if !action.contains_key("action_type") {
+            if let Some(action_type) = action.remove("type") {
+                action.insert("action_type".to_string(), action_type);
+            }
+        }
+    }
+    object.entry("expected_outcome").or_insert_with(|| serde_json::json!({}));
+
+    // Boolean outcome flags (e.g. "quality_maintained": true) are stored as 1.0 / 0.0
+    for field in ["metrics", "expected_outcome"] {
+        if let Some(map) = object.get_mut(field).and_then(|m| m.as_object_mut()) {
+            for entry in map.values_mut() {
+                if let Some(flag) = entry.as_bool() {
+                    *entry = serde_json::json!(if flag { 1.0 } else { 0.0 });
+                }
+            }
+        }
+    }
+    object.entry("timestamp").or_insert_with(|| serde_json::json!(default_timestamp));
+
+    let observation: Observation = serde_json::from_value(value).map_err(|e| format!("Schema violation: {}", e))?;
+    if observation.id.trim().is_empty() {
+        return Err("Observation id must not be empty".to_string());
+    }
+    Ok(observation)
+}
+
+/// Stream observations from a JSONL reader, passing each valid one to `sink`
+/// Blank lines are skipped; invalid lines are reported without aborting the import
+pub fn import_jsonl<R: BufRead, F: FnMut(Observation)>(reader: R, mut sink: F) -> Result<ImportReport, String> {
+    let now = chrono::Utc::now().timestamp();
+    let mut report = ImportReport::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read line {}: {}", index + 1, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parsed = serde_json::from_str::<serde_json::Value>(&line)
+            .map_err(|e| format!("Invalid JSON: {}", e))
+            .and_then(|value| validate_observation(value, now));
+        match parsed {
+            Ok(observation) => {
+                sink(observation);
+                report.imported += 1;
+            }
+            Err(message) => report.errors.push(ImportError { line: index + 1, message }),
+        }
+    }
+
+    info!("dataset::import_jsonl: Imported {} observations ({} rejected)", report.imported, report.errors.len());
+    Ok(report)
+}
+
+/// Write observations as JSONL
+pub fn export_jsonl<W: Write>(writer: W, observations: &[Observation]) -> Result<usize, String> {
+    let mut writer = BufWriter::new(writer);
+    for observation in observations {
+        let line = serde_json::to_string(observation).map_err(|e| format!("Failed to encode {}: {}", observation.id, e))?;
+        writeln!(writer, "{}", line).map_err(|e| format!("Failed to write {}: {}", observation.id, e))?;
+    }
+    writer.flush().map_err(|e| format!("Failed to flush export: {}", e))?;
+
+    info!("dataset::export_jsonl: Exported {} observations", observations.len());
+    Ok(observations.len())
+}
+
+/// Import a JSONL file into the feature store
+pub fn import_file(path: &Path, store: &mut FeatureStore) -> Result<ImportReport, String> {
+    info!("dataset::import_file: Importing {:?}", path);
+    let file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    import_jsonl(BufReader::new(file), |observation| store.ingest_observation(observation))
+}
+
+/// Export all feature store observations to a JSONL file
+pub fn export_file(path: &Path, store: &FeatureStore) -> Result<usize, String> {
+    info!("dataset::export_file: Exporting to {:?}", path);
+    let file = File::create(path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    export_jsonl(file, store.get_observations())
+}
+
+/// Run `import <file>` / `export <file>` / `label <labels file>` commands (in order) against the feature store
+/// Returns the number of commands executed
+pub fn run_commands(args: &[String], store: &mut FeatureStore) -> Result<usize, String> {
+    let mut executed = 0;
+    let mut iter = args.iter();
+
+    while let Some(command) = iter.next() {
+        let path = iter.next().ok_or(format!("Missing file argument for '{}'", command))?;
+        match command.as_str() {
+            "import" => {
+                let report = import_file(Path::new(path), store)?;
+                for error in &report.errors {
+                    info!("Import {}:{} rejected: {}", path, error.line, error.message);
+                }
+                info!("Imported {} observations from {}", report.imported, path);
+            }
+            "export" => {
+                let count = export_file(Path::new(path), store)?;
+                info!("Exported {} observations to {}", count, path);
+            }
+            "label" => {
+                let mut labels = LabelStore::open(path)?;
+                let annotator = std::env::var("ATHENOS_ANNOTATOR").unwrap_or_else(|_| "local".to_string());
+                let stdin = std::io::stdin();
+                let count = labeling::run_session(stdin.lock(), std::io::stdout(), store.get_observations(), &mut labels, &annotator, 20)?;
+                info!("Recorded {} labels to {}", count, path);
+            }
+            other => return Err(format!("Unknown command: {} (expected import/export/label)", other)),
+        }
+        executed += 1;
+    }
+
+    Ok(executed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED_LINE: &str = r#"{"id":"unit_0001","profile":"developer","observation":["Teams","Gmail","IDE"],"metrics":{"repeat_count":8,"time_to_first_code_min":12},"intent":"suggest_shortcut","action":{"type":"automation_macro","description":"Dev Startup macro","confidence":"high","risk":"none"},"expected_outcome":{"time_saved_min":11},"source":"seed"}"#;
+
+    #[test]
+    fn test_import_seed_format() {
+        let input = format!("{}\n\n", SEED_LINE);
+        let mut imported = Vec::new();
+
+        let report = import_jsonl(input.as_bytes(), |obs| imported.push(obs)).unwrap();
+        assert_eq!(report.imported, 1);
+        assert!(report.errors.is_empty());
+        assert_eq!(imported[0].action.action_type, ActionType::AutomationMacro);
+        assert!(imported[0].timestamp > 0);
+    }
+
+    #[test]
+    fn test_invalid_lines_reported() {
+        let input = format!(
+            "{}\nnot json\n{}\n",
+            SEED_LINE,
+            r#"{"id":"unit_0002","profile":"developer","observation":[],"metrics":{},"intent":"suggest_shortcut","action":{"type":"unknown_action","description":"x","confidence":"high","risk":"none"},"source":"seed"}"#
+        );
+
+        let report = import_jsonl(input.as_bytes(), |_| {}).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.errors[0].line, 2);
+        assert!(report.errors[1].message.contains("Schema violation"));
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut store = FeatureStore::new();
+        import_jsonl(SEED_LINE.as_bytes(), |obs| store.ingest_observation(obs)).unwrap();
+
+        let mut buffer = Vec::new();
+        assert_eq!(export_jsonl(&mut buffer, store.get_observations()).unwrap(), 1);
+
+        let mut reimported = Vec::new();
+        let report = import_jsonl(buffer.as_slice(), |obs| reimported.push(obs)).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(reimported[0].id, "unit_0001");
+        assert_eq!(reimported[0].timestamp, store.get_observations()[0].timestamp);
+    }
+}
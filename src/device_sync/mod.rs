@@ -0,0 +1,709 @@
+/// Phase: D | Source: athenos-rules.mdc#L12-14
+/// Cross-Device Sync
+/// Opt-in, end-to-end encrypted sync of selected state between a user's paired devices through a dumb relay
+
+use crate::privacy::{ConsentLedger, EncryptionManager};
+use crate::shortcut::{ShortcutGenerator, ShortcutProposal};
+use crate::suppression::SuppressionList;
+use crate::victory::{Victory, VictoryStream};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// State category a user can choose to sync
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncCategory {
+    Shortcuts,    // Approved shortcuts (last-writer-wins)
+    Suppressions, // Suppressed pattern signatures (last-writer-wins)
+    Victories,    // Victory history (append-only, merged)
+}
+
+impl SyncCategory {
+    /// Append-only categories merge entries instead of overwriting them
+    pub fn is_append_only(&self) -> bool {
+        matches!(self, SyncCategory::Victories)
+    }
+}
+
+/// One synced key; `value: None` is a deletion tombstone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncEntry {
+    pub category: SyncCategory,
+    pub key: String,
+    pub value: Option<serde_json::Value>,
+    pub updated_at: i64,
+    pub device_id: String, // Writer; breaks last-writer-wins ties deterministically
+}
+
+impl SyncEntry {
+    fn supersedes(&self, other: &SyncEntry) -> bool {
+        (self.updated_at, &self.device_id) > (other.updated_at, &other.device_id)
+    }
+}
+
+/// Shared secret exchanged out of band (QR code / typed) when pairing devices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingCode {
+    pub group_id: String,
+    pub key: Vec<u8>,
+}
+
+impl PairingCode {
+    /// Encode as `group_id.hexkey`
+    pub fn encode(&self) -> String {
+        let hex: String = self.key.iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}.{}", self.group_id, hex)
+    }
+
+    /// Parse an encoded pairing code
+    pub fn parse(code: &str) -> Result<Self, String> {
+        let (group_id, hex) = code.trim().split_once('.').ok_or("Malformed pairing code")?;
+        // Group ids name relay directories, so only plain identifiers are accepted
+        if group_id.is_empty() || !group_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') || hex.len() % 2 != 0 {
+            return Err("Malformed pairing code".to_string());
+        }
+        let key = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "Malformed pairing code".to_string()))
+            .collect::<Result<Vec<u8>, String>>()?;
+        Ok(Self { group_id: group_id.to_string(), key })
+    }
+}
+
+/// Untrusted relay: stores and forwards opaque ciphertext per sync group
+pub trait SyncRelay {
+    /// Append an envelope, returning its sequence number
+    fn push(&mut self, group_id: &str, envelope: Vec<u8>) -> Result<u64, String>;
+
+    /// Envelopes with sequence numbers above `after`
+    fn pull(&self, group_id: &str, after: u64) -> Result<Vec<(u64, Vec<u8>)>, String>;
+}
+
+/// In-process relay (tests and single-host setups)
+#[derive(Debug, Default)]
+pub struct InMemoryRelay {
+    groups: HashMap<String, Vec<Vec<u8>>>,
+}
+
+impl InMemoryRelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SyncRelay for InMemoryRelay {
+    fn push(&mut self, group_id: &str, envelope: Vec<u8>) -> Result<u64, String> {
+        let log = self.groups.entry(group_id.to_string()).or_default();
+        log.push(envelope);
+        Ok(log.len() as u64)
+    }
+
+    fn pull(&self, group_id: &str, after: u64) -> Result<Vec<(u64, Vec<u8>)>, String> {
+        Ok(self
+            .groups
+            .get(group_id)
+            .map(|log| log.iter().enumerate().skip(after as usize).map(|(i, e)| (i as u64 + 1, e.clone())).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Relay over a shared directory (network share or synced folder): one file per envelope
+#[derive(Debug, Clone)]
+pub struct DirectoryRelay {
+    root: PathBuf,
+}
+
+impl DirectoryRelay {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn sequences(&self, group_id: &str) -> Result<Vec<u64>, String> {
+        let entries = match std::fs::read_dir(self.root.join(group_id)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read relay directory: {}", e)),
+        };
+        let mut sequences: Vec<u64> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".bin")?.parse().ok())
+            .collect();
+        sequences.sort_unstable();
+        Ok(sequences)
+    }
+}
+
+impl SyncRelay for DirectoryRelay {
+    fn push(&mut self, group_id: &str, envelope: Vec<u8>) -> Result<u64, String> {
+        use std::io::Write;
+        let dir = self.root.join(group_id);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create relay directory: {}", e))?;
+        let mut seq = self.sequences(group_id)?.last().copied().unwrap_or(0) + 1;
+        // create_new claims the sequence number; another device pushing concurrently moves on to the next one
+        loop {
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(dir.join(format!("{:020}.bin", seq))) {
+                Ok(mut file) => {
+                    file.write_all(&envelope).map_err(|e| format!("Failed to write envelope: {}", e))?;
+                    return Ok(seq);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => seq += 1,
+                Err(e) => return Err(format!("Failed to write envelope: {}", e)),
+            }
+        }
+    }
+
+    fn pull(&self, group_id: &str, after: u64) -> Result<Vec<(u64, Vec<u8>)>, String> {
+        self.sequences(group_id)?
+            .into_iter()
+            .filter(|seq| *seq > after)
+            .map(|seq| {
+                std::fs::read(self.root.join(group_id).join(format!("{:020}.bin", seq)))
+                    .map(|envelope| (seq, envelope))
+                    .map_err(|e| format!("Failed to read envelope {}: {}", seq, e))
+            })
+            .collect()
+    }
+}
+
+/// Result of one sync round
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub pushed: usize,
+    pub received: usize,
+    pub skipped: usize,          // Envelopes that failed to decrypt or decode
+    pub applied: Vec<SyncEntry>, // Remote entries that changed local state
+}
+
+/// Persisted pairing and sync progress of a device
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedSync {
+    device_id: String,
+    group_id: String,
+    key: Vec<u8>,
+    categories: Vec<SyncCategory>,
+    state: Vec<SyncEntry>,
+    cursor: u64,
+}
+
+/// A decoded remote entry, ready to apply
+enum Decoded {
+    Shortcut(Box<ShortcutProposal>),
+    Suppress(String, i64),
+    Unsuppress(String),
+    Victory(Box<Victory>),
+}
+
+/// Sync endpoint of one paired device
+pub struct DeviceSync {
+    device_id: String,
+    group_id: String,
+    key: Vec<u8>, // Group key from the pairing code
+    encryption: EncryptionManager,
+    categories: HashSet<SyncCategory>,
+    state: BTreeMap<(SyncCategory, String), SyncEntry>,
+    outbox: Vec<SyncEntry>, // Local writes not yet pushed; persisted beside the sync state on every change
+    cursor: u64,            // Last relay sequence number seen
+    path: Option<PathBuf>,  // Where pairing and progress persist (in-memory if None)
+}
+
+impl DeviceSync {
+    /// Start a new sync group on this device; share the returned code with the other device
+    pub fn create_group(device_id: &str) -> Result<(Self, PairingCode), String> {
+        use rand::Rng;
+        let key: [u8; 32] = rand::thread_rng().gen();
+        let group_id = format!("sync_{:016x}", rand::thread_rng().gen::<u64>());
+        let code = PairingCode { group_id, key: key.to_vec() };
+        info!("DeviceSync::create_group: {} created sync group {}", device_id, code.group_id);
+        Ok((Self::join(device_id, &code)?, code))
+    }
+
+    /// Pair this device into an existing sync group
+    pub fn join(device_id: &str, code: &PairingCode) -> Result<Self, String> {
+        info!("DeviceSync::join: {} joining sync group {}", device_id, code.group_id);
+        Ok(Self {
+            device_id: device_id.to_string(),
+            group_id: code.group_id.clone(),
+            key: code.key.clone(),
+            encryption: EncryptionManager::from_key(&code.key)?,
+            categories: HashSet::new(),
+            state: BTreeMap::new(),
+            outbox: Vec::new(),
+            cursor: 0,
+            path: None,
+        })
+    }
+
+    /// Open the pairing persisted at path; `None` when this device has not been paired
+    pub fn open(path: impl AsRef<Path>) -> Result<Option<Self>, String> {
+        let path = path.as_ref();
+        let persisted: PersistedSync = match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid sync state: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read sync state: {}", e)),
+        };
+        info!("DeviceSync::open: {} resuming sync group {} at {}", persisted.device_id, persisted.group_id, persisted.cursor);
+        let code = PairingCode { group_id: persisted.group_id, key: persisted.key };
+        let mut sync = Self::join(&persisted.device_id, &code)?;
+        sync.categories = persisted.categories.into_iter().collect();
+        sync.state = persisted.state.into_iter().map(|e| ((e.category, e.key.clone()), e)).collect();
+        sync.cursor = persisted.cursor;
+        sync.path = Some(path.to_path_buf());
+        let outbox_path = sync.outbox_path().ok_or("Sync state path has no outbox")?;
+        let outbox: Vec<SyncEntry> = match std::fs::read_to_string(&outbox_path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid sync outbox: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read sync outbox: {}", e)),
+        };
+        // Pending local writes may be newer than the state saved by the last apply
+        for entry in &outbox {
+            sync.state.insert((entry.category, entry.key.clone()), entry.clone());
+        }
+        sync.outbox = outbox;
+        Ok(Some(sync))
+    }
+
+    /// Persist pairing and progress at path from now on
+    pub fn persist_at(&mut self, path: impl Into<PathBuf>) -> Result<(), String> {
+        self.path = Some(path.into());
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let persisted = PersistedSync {
+            device_id: self.device_id.clone(),
+            group_id: self.group_id.clone(),
+            key: self.key.clone(),
+            categories: self.categories.iter().copied().collect(),
+            state: self.state.values().cloned().collect(),
+            cursor: self.cursor,
+        };
+        let json = serde_json::to_vec(&persisted).map_err(|e| format!("Failed to encode sync state: {}", e))?;
+        write_private(path, &json)
+    }
+
+    /// Outbox file beside the sync state (`device_sync.json` -> `device_sync.outbox.json`)
+    fn outbox_path(&self) -> Option<PathBuf> {
+        self.path.as_ref().map(|path| path.with_extension("outbox.json"))
+    }
+
+    /// Persist unpushed writes on their own, so cursor and merged state still only persist through `apply`
+    fn save_outbox(&self) -> Result<(), String> {
+        let Some(path) = self.outbox_path() else { return Ok(()) };
+        let json = serde_json::to_vec(&self.outbox).map_err(|e| format!("Failed to encode sync outbox: {}", e))?;
+        write_private(&path, &json)
+    }
+
+    /// Opt a category in or out of sync (all categories start opted out)
+    pub fn set_category(&mut self, category: SyncCategory, enabled: bool) -> Result<(), String> {
+        info!("DeviceSync::set_category: {:?} sync {}", category, if enabled { "enabled" } else { "disabled" });
+        if enabled {
+            self.categories.insert(category);
+        } else {
+            self.categories.remove(&category);
+        }
+        self.save()
+    }
+
+    /// Whether a category is synced
+    pub fn is_enabled(&self, category: SyncCategory) -> bool {
+        self.categories.contains(&category)
+    }
+
+    /// Record a local write; returns false when the category is not synced or nothing changed
+    pub fn record(&mut self, category: SyncCategory, key: &str, value: Option<serde_json::Value>, now: i64) -> bool {
+        if !self.is_enabled(category) {
+            return false;
+        }
+        let slot = (category, key.to_string());
+        if let Some(existing) = self.state.get(&slot) {
+            if existing.value == value || (category.is_append_only() && existing.value.is_some()) {
+                return false;
+            }
+        }
+        let entry = SyncEntry { category, key: key.to_string(), value, updated_at: now, device_id: self.device_id.clone() };
+        self.state.insert(slot, entry.clone());
+        self.outbox.push(entry);
+        if let Err(e) = self.save_outbox() {
+            warn!("DeviceSync::record: Outbox not persisted: {}", e);
+        }
+        true
+    }
+
+    /// Merge a remote entry: newest write wins per key, append-only categories keep every key
+    fn merge(&mut self, entry: SyncEntry) -> bool {
+        if !self.is_enabled(entry.category) {
+            return false;
+        }
+        let slot = (entry.category, entry.key.clone());
+        let apply = match self.state.get(&slot) {
+            None => true,
+            Some(existing) if entry.category.is_append_only() => existing.value.is_none() && entry.value.is_some(),
+            Some(existing) => entry.supersedes(existing) && existing.value != entry.value,
+        };
+        if apply {
+            self.state.insert(slot, entry);
+        }
+        apply
+    }
+
+    /// Push local writes and merge remote ones; requires the user's cloud sync opt-in
+    /// Progress is only persisted by `apply`, so merged entries that never reach the modules are pulled again
+    /// Envelopes this device cannot decrypt or decode (foreign or corrupted on the relay) are skipped
+    pub fn sync(&mut self, relay: &mut dyn SyncRelay, consent: &ConsentLedger) -> Result<SyncReport, String> {
+        if !consent.can_sync_to_cloud() {
+            return Err("Cross-device sync requires cloud sync consent".to_string());
+        }
+        let mut report = SyncReport::default();
+        if !self.outbox.is_empty() {
+            let plaintext = serde_json::to_vec(&self.outbox).map_err(|e| format!("Failed to encode sync batch: {}", e))?;
+            relay.push(&self.group_id, self.encryption.encrypt(&plaintext)?)?;
+            report.pushed = self.outbox.len();
+            self.outbox.clear();
+            self.save_outbox()?;
+        }
+
+        for (seq, envelope) in relay.pull(&self.group_id, self.cursor)? {
+            self.cursor = seq;
+            let decoded = self
+                .encryption
+                .decrypt(&envelope)
+                .and_then(|plaintext| serde_json::from_slice::<Vec<SyncEntry>>(&plaintext).map_err(|e| format!("Invalid sync batch: {}", e)));
+            let entries = match decoded {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("DeviceSync::sync: Skipping envelope {} in {}: {}", seq, self.group_id, e);
+                    report.skipped += 1;
+                    continue;
+                }
+            };
+            for entry in entries {
+                if entry.device_id == self.device_id {
+                    continue;
+                }
+                report.received += 1;
+                if self.merge(entry.clone()) {
+                    report.applied.push(entry);
+                }
+            }
+        }
+        info!(
+            "DeviceSync::sync: {} pushed {}, received {}, skipped {}, applied {}",
+            self.device_id,
+            report.pushed,
+            report.received,
+            report.skipped,
+            report.applied.len()
+        );
+        Ok(report)
+    }
+
+    /// Current synced entries of a category (tombstones excluded)
+    pub fn entries(&self, category: SyncCategory) -> Vec<&SyncEntry> {
+        self.state.values().filter(|e| e.category == category && e.value.is_some()).collect()
+    }
+
+    /// Capture approved shortcuts
+    pub fn capture_shortcuts(&mut self, shortcuts: &ShortcutGenerator, now: i64) -> usize {
+        shortcuts
+            .get_approved_shortcuts()
+            .into_iter()
+            .filter(|p| self.record(SyncCategory::Shortcuts, &p.id, serde_json::to_value(p).ok(), now))
+            .count()
+    }
+
+    /// Capture active suppressions; suppressions lifted since the last capture become tombstones
+    pub fn capture_suppressions(&mut self, suppression: &SuppressionList, now: i64) -> usize {
+        let active = suppression.list_suppressed(now);
+        let lifted: Vec<String> = self
+            .entries(SyncCategory::Suppressions)
+            .into_iter()
+            .filter(|e| !active.iter().any(|(signature, _)| *signature == e.key))
+            .map(|e| e.key.clone())
+            .collect();
+        let mut changed = 0;
+        for (signature, until) in active {
+            changed += self.record(SyncCategory::Suppressions, &signature, Some(serde_json::json!(until)), now) as usize;
+        }
+        for signature in lifted {
+            changed += self.record(SyncCategory::Suppressions, &signature, None, now) as usize;
+        }
+        changed
+    }
+
+    /// Capture victory history
+    pub fn capture_victories(&mut self, victories: &VictoryStream, now: i64) -> usize {
+        victories
+            .get_recent_victories(usize::MAX)
+            .into_iter()
+            .filter(|v| self.record(SyncCategory::Victories, &format!("{}:{}", v.timestamp, v.title), serde_json::to_value(v).ok(), now))
+            .count()
+    }
+
+    /// Apply merged remote entries to local modules and persist sync progress; returns the number applied
+    /// Every entry is decoded before any is applied, so a malformed batch changes nothing
+    /// Suppressions apply before shortcuts, and shortcuts whose sequence is suppressed here are not imported
+    pub fn apply(
+        &mut self,
+        applied: &[SyncEntry],
+        shortcuts: &mut ShortcutGenerator,
+        suppression: &SuppressionList,
+        victories: &mut VictoryStream,
+        now: i64,
+    ) -> Result<usize, String> {
+        let mut decoded = Vec::new();
+        for entry in applied {
+            decoded.push(match (entry.category, &entry.value) {
+                (SyncCategory::Shortcuts, Some(value)) => {
+                    Decoded::Shortcut(serde_json::from_value(value.clone()).map_err(|e| format!("Invalid synced shortcut: {}", e))?)
+                }
+                (SyncCategory::Suppressions, Some(until)) => {
+                    Decoded::Suppress(entry.key.clone(), until.as_i64().ok_or("Invalid synced suppression")?)
+                }
+                (SyncCategory::Suppressions, None) => Decoded::Unsuppress(entry.key.clone()),
+                (SyncCategory::Victories, Some(value)) => {
+                    Decoded::Victory(serde_json::from_value(value.clone()).map_err(|e| format!("Invalid synced victory: {}", e))?)
+                }
+                (SyncCategory::Shortcuts, None) | (SyncCategory::Victories, None) => continue,
+            });
+        }
+        decoded.sort_by_key(|d| matches!(d, Decoded::Shortcut(_)));
+
+        let mut count = 0;
+        for item in decoded {
+            count += match item {
+                Decoded::Shortcut(proposal) => shortcuts.import_approved(*proposal, now),
+                Decoded::Suppress(signature, until) => suppression
+                    .suppress_until(&signature, until)
+                    .map_err(|e| warn!("DeviceSync::apply: Suppression of {} not persisted: {}", signature, e))
+                    .is_ok(),
+                Decoded::Unsuppress(signature) => suppression
+                    .unsuppress(&signature)
+                    .map_err(|e| warn!("DeviceSync::apply: Lift of {} not persisted: {}", signature, e))
+                    .is_ok(),
+                Decoded::Victory(victory) => victories.import_victory(*victory),
+            } as usize;
+        }
+        self.save()?;
+        Ok(count)
+    }
+}
+
+/// Write the sync state (it holds the group key) readable by the owner only, via a temporary file and rename
+fn write_private(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600)).map_err(|e| format!("Failed to restrict {}: {}", tmp.display(), e))?;
+    }
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::victory::{VictoryCategory, VictoryMetric};
+
+    fn consented() -> ConsentLedger {
+        let mut consent = ConsentLedger::new();
+        consent.opt_in_cloud_sync = true;
+        consent
+    }
+
+    fn paired() -> (DeviceSync, DeviceSync) {
+        let (mut laptop, code) = DeviceSync::create_group("laptop").unwrap();
+        let mut desktop = DeviceSync::join("desktop", &PairingCode::parse(&code.encode()).unwrap()).unwrap();
+        for category in [SyncCategory::Shortcuts, SyncCategory::Suppressions, SyncCategory::Victories] {
+            laptop.set_category(category, true).unwrap();
+            desktop.set_category(category, true).unwrap();
+        }
+        (laptop, desktop)
+    }
+
+    #[test]
+    fn test_sync_requires_consent_and_relay_sees_only_ciphertext() {
+        let (mut laptop, _) = paired();
+        let mut relay = InMemoryRelay::new();
+        assert!(laptop.sync(&mut relay, &ConsentLedger::new()).is_err());
+
+        laptop.record(SyncCategory::Suppressions, "sig_secret_pattern", Some(serde_json::json!(5_000)), 100);
+        assert_eq!(laptop.sync(&mut relay, &consented()).unwrap().pushed, 1);
+        let stored = relay.pull(&laptop.group_id, 0).unwrap();
+        assert!(!String::from_utf8_lossy(&stored[0].1).contains("sig_secret_pattern"));
+
+        let (mut stranger, _) = DeviceSync::create_group("stranger").unwrap();
+        stranger.group_id = laptop.group_id.clone();
+        stranger.set_category(SyncCategory::Suppressions, true).unwrap();
+        let report = stranger.sync(&mut relay, &consented()).unwrap(); // Wrong key cannot decrypt
+        assert_eq!((report.received, report.skipped), (0, 1));
+        assert!(stranger.entries(SyncCategory::Suppressions).is_empty());
+    }
+
+    #[test]
+    fn test_foreign_envelope_is_skipped_and_sync_moves_past_it() {
+        let (mut laptop, mut desktop) = paired();
+        let mut relay = InMemoryRelay::new();
+        relay.push(&laptop.group_id, b"not an envelope".to_vec()).unwrap();
+        laptop.record(SyncCategory::Suppressions, "sig_a", Some(serde_json::json!(5_000)), 100);
+        laptop.sync(&mut relay, &consented()).unwrap();
+
+        let report = desktop.sync(&mut relay, &consented()).unwrap();
+        assert_eq!((report.skipped, report.applied.len()), (1, 1));
+        assert_eq!(desktop.cursor, 2);
+        assert_eq!(desktop.sync(&mut relay, &consented()).unwrap().skipped, 0);
+    }
+
+    #[test]
+    fn test_pending_writes_survive_restart() {
+        let dir = std::env::temp_dir().join(format!("athenos_device_sync_outbox_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let state_path = dir.join("device_sync.json");
+        let (mut laptop, mut desktop) = paired();
+        laptop.persist_at(&state_path).unwrap();
+        laptop.record(SyncCategory::Suppressions, "sig_a", Some(serde_json::json!(5_000)), 100);
+
+        // Restarted before syncing: the write is still pending and is pushed once
+        let mut laptop = DeviceSync::open(&state_path).unwrap().unwrap();
+        assert_eq!(laptop.entries(SyncCategory::Suppressions).len(), 1);
+        let mut relay = InMemoryRelay::new();
+        assert_eq!(laptop.sync(&mut relay, &consented()).unwrap().pushed, 1);
+        assert_eq!(DeviceSync::open(&state_path).unwrap().unwrap().sync(&mut relay, &consented()).unwrap().pushed, 0);
+        assert_eq!(desktop.sync(&mut relay, &consented()).unwrap().applied.len(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_last_writer_wins_per_key() {
+        let (mut laptop, mut desktop) = paired();
+        let mut relay = InMemoryRelay::new();
+        laptop.record(SyncCategory::Suppressions, "sig_a", Some(serde_json::json!(1_000)), 100);
+        desktop.record(SyncCategory::Suppressions, "sig_a", Some(serde_json::json!(2_000)), 200);
+        desktop.record(SyncCategory::Suppressions, "sig_b", Some(serde_json::json!(3_000)), 150);
+        laptop.record(SyncCategory::Suppressions, "sig_b", None, 300); // Later lift wins
+
+        laptop.sync(&mut relay, &consented()).unwrap();
+        desktop.sync(&mut relay, &consented()).unwrap();
+        laptop.sync(&mut relay, &consented()).unwrap();
+
+        for device in [&laptop, &desktop] {
+            let entries = device.entries(SyncCategory::Suppressions);
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].key, "sig_a");
+            assert_eq!(entries[0].value, Some(serde_json::json!(2_000)));
+        }
+    }
+
+    #[test]
+    fn test_victories_merge_and_apply_to_modules() {
+        let (mut laptop, mut desktop) = paired();
+        desktop.set_category(SyncCategory::Shortcuts, false).unwrap();
+        let mut relay = InMemoryRelay::new();
+
+        let mut laptop_victories = VictoryStream::new();
+        laptop_victories.record_victory_at("Saved 10 min".to_string(), "Laptop".to_string(), VictoryMetric::TimeSaved, 10.0, VictoryCategory::Automation, 1_000);
+        let mut desktop_victories = VictoryStream::new();
+        desktop_victories.record_victory_at("Deep focus".to_string(), "Desktop".to_string(), VictoryMetric::FocusIncrease, 1.0, VictoryCategory::Focus, 2_000);
+        let laptop_suppression = SuppressionList::new();
+        laptop_suppression.suppress("sig_noisy", 1_000).unwrap();
+
+        assert_eq!(laptop.capture_victories(&laptop_victories, 1_000), 1);
+        assert_eq!(laptop.capture_suppressions(&laptop_suppression, 1_000), 1);
+        laptop.record(SyncCategory::Shortcuts, "shortcut_1", Some(serde_json::json!({})), 1_000);
+        desktop.capture_victories(&desktop_victories, 2_000);
+        laptop.sync(&mut relay, &consented()).unwrap();
+        let report = desktop.sync(&mut relay, &consented()).unwrap();
+        assert_eq!(report.applied.len(), 2); // Shortcut category is opted out on the desktop
+
+        let desktop_suppression = SuppressionList::new();
+        let applied = desktop.apply(&report.applied, &mut ShortcutGenerator::new(), &desktop_suppression, &mut desktop_victories, 2_000).unwrap();
+        assert_eq!(applied, 2);
+        assert!(desktop_suppression.is_suppressed("sig_noisy", 2_000));
+        assert_eq!(desktop_victories.get_recent_victories(10).len(), 2);
+        assert!(!desktop_victories.import_victory(laptop_victories.get_recent_victories(1)[0].clone()));
+
+        // Re-capturing an imported victory does not echo it back
+        assert_eq!(desktop.capture_victories(&desktop_victories, 3_000), 0);
+        assert_eq!(desktop.entries(SyncCategory::Victories).len(), 2);
+    }
+
+    fn shortcut(id: &str, sequence: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "description": "Synced",
+            "sequence": sequence,
+            "expected_time_saved_min": 2.0,
+            "confidence": "high",
+            "risk": "none",
+            "requires_approval": true,
+            "provenance": { "triggering_pattern": "synced", "data_used": [], "confidence": "high", "consent_scopes": [] },
+            "project": null,
+            "created_at": 0
+        })
+    }
+
+    #[test]
+    fn test_pairing_and_progress_survive_restart_over_directory_relay() {
+        let dir = std::env::temp_dir().join(format!("athenos_device_sync_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let state_path = dir.join("device_sync.json");
+        let mut relay = DirectoryRelay::new(dir.join("relay"));
+        assert!(DeviceSync::open(&state_path).unwrap().is_none()); // Not paired yet
+
+        let (mut laptop, mut desktop) = paired();
+        desktop.persist_at(&state_path).unwrap();
+        laptop.record(SyncCategory::Suppressions, "sig_a", Some(serde_json::json!(5_000)), 100);
+        laptop.sync(&mut relay, &consented()).unwrap();
+
+        // Merged but never applied: a restart pulls the entry again
+        assert_eq!(desktop.sync(&mut relay, &consented()).unwrap().applied.len(), 1);
+        let mut desktop = DeviceSync::open(&state_path).unwrap().unwrap();
+        assert!(desktop.is_enabled(SyncCategory::Suppressions));
+        let report = desktop.sync(&mut relay, &consented()).unwrap();
+        assert_eq!(report.applied.len(), 1);
+        desktop.apply(&report.applied, &mut ShortcutGenerator::new(), &SuppressionList::new(), &mut VictoryStream::new(), 200).unwrap();
+
+        let mut desktop = DeviceSync::open(&state_path).unwrap().unwrap();
+        assert_eq!(desktop.sync(&mut relay, &consented()).unwrap().received, 0);
+        assert_eq!(desktop.entries(SyncCategory::Suppressions).len(), 1);
+        assert!(PairingCode::parse("../escape.00").is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_apply_is_all_or_nothing_and_respects_suppression() {
+        let (mut laptop, mut desktop) = paired();
+        let mut relay = InMemoryRelay::new();
+        let suppressed_sequence = ["Teams", "Gmail", "IDE"];
+        let signature = SuppressionList::sequence_signature(&suppressed_sequence.map(String::from));
+        laptop.record(SyncCategory::Shortcuts, "shortcut_suppressed", Some(shortcut("shortcut_suppressed", &suppressed_sequence)), 100);
+        laptop.record(SyncCategory::Shortcuts, "shortcut_ok", Some(shortcut("shortcut_ok", &["IDE", "Terminal", "Browser"])), 100);
+        laptop.record(SyncCategory::Suppressions, &signature, Some(serde_json::json!(10_000)), 100);
+        laptop.sync(&mut relay, &consented()).unwrap();
+        let report = desktop.sync(&mut relay, &consented()).unwrap();
+
+        let suppression = SuppressionList::new();
+        let mut shortcuts = ShortcutGenerator::new();
+        shortcuts.set_suppression_list(suppression.clone());
+        let mut malformed = report.applied.clone();
+        malformed.push(SyncEntry { category: SyncCategory::Victories, key: "bad".to_string(), value: Some(serde_json::json!(1)), updated_at: 100, device_id: "laptop".to_string() });
+        assert!(desktop.apply(&malformed, &mut shortcuts, &suppression, &mut VictoryStream::new(), 200).is_err());
+        assert!(!suppression.is_suppressed(&signature, 200)); // Nothing applied
+        assert!(shortcuts.get_approved_shortcuts().is_empty());
+
+        // The synced suppression applies first and blocks the shortcut it covers
+        assert_eq!(desktop.apply(&report.applied, &mut shortcuts, &suppression, &mut VictoryStream::new(), 200).unwrap(), 2);
+        assert!(suppression.is_suppressed(&signature, 200));
+        let approved = shortcuts.get_approved_shortcuts();
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].id, "shortcut_ok");
+    }
+}
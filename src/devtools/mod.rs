@@ -0,0 +1,292 @@
+/// Phase: C | Step: 9 | Source: Athenos_AI_Strategy.md#L128
+/// Terminal/IDE Integration Plugin
+/// First-party developer plugin: shell exit codes + IDE focus events drive DebuggingLoop detection
+
+use crate::categorizer::{AppCategorizer, AppCategory};
+use crate::edge::{OSEvent, OSEventType};
+use crate::plugin::{Plugin, PluginCapability, PluginMetadata, PluginRegistry};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::info;
+
+/// Plugin id of the first-party developer integration
+pub const DEVTOOLS_PLUGIN_ID: &str = "athenos_devtools";
+
+/// Sequence token marking a failed shell run (recognized by PatternDetector)
+pub const SHELL_ERROR_TOKEN: &str = "shell_error";
+
+/// Developer signal sent by the shell hook or IDE extension (one JSON object per line)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DevSignal {
+    Shell { command: String, exit_code: i32, timestamp: i64 },
+    IdeFocus { ide: String, timestamp: i64 },
+}
+
+/// Debugging-loop thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebugLoopConfig {
+    pub window_secs: i64,
+    pub min_failures: usize,
+    pub min_ide_returns: usize, // IDE focus events between failures (edit-run-fail cycle)
+}
+
+impl Default for DebugLoopConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 15 * 60,
+            min_failures: 3,
+            min_ide_returns: 1,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct LoopState {
+    failures: Vec<i64>,
+    reported: bool,
+}
+
+/// Tracks failing commands and IDE returns to detect edit-run-fail loops
+pub struct DebugLoopDetector {
+    config: DebugLoopConfig,
+    loops: HashMap<String, LoopState>, // command key -> state
+    ide_focus: Vec<i64>,
+    categorizer: AppCategorizer,
+}
+
+impl DebugLoopDetector {
+    /// Create detector
+    pub fn new(config: DebugLoopConfig) -> Self {
+        Self {
+            config,
+            loops: HashMap::new(),
+            ide_focus: Vec::new(),
+            categorizer: AppCategorizer::new(),
+        }
+    }
+
+    /// Reduce a command line to program and subcommand; arguments may hold paths or secrets
+    pub fn command_key(command: &str) -> String {
+        let mut tokens = command.split_whitespace();
+        let program = tokens.next().unwrap_or("").rsplit('/').next().unwrap_or("");
+        match tokens.next() {
+            Some(sub) if sub.chars().all(|c| c.is_ascii_alphabetic() || c == '-') && !sub.starts_with('-') => format!("{} {}", program, sub),
+            _ => program.to_string(),
+        }
+    }
+
+    /// Record IDE focus from an edge event (code editor gaining focus)
+    pub fn observe_event(&mut self, event: &OSEvent) {
+        let focused = matches!(event.event_type, OSEventType::WindowFocus | OSEventType::AppSwitch | OSEventType::AppLaunch);
+        if focused && self.categorizer.categorize(&event.app_name) == AppCategory::CodeEditor {
+            self.ide_focus.push(event.timestamp);
+        }
+    }
+
+    /// Process a signal; returns an observation when a debugging loop is detected
+    pub fn process(&mut self, signal: &DevSignal) -> Option<Observation> {
+        match signal {
+            DevSignal::IdeFocus { timestamp, .. } => {
+                self.ide_focus.push(*timestamp);
+                None
+            }
+            DevSignal::Shell { command, exit_code, timestamp } => {
+                let key = Self::command_key(command);
+                if key.is_empty() {
+                    return None;
+                }
+                if *exit_code == 0 {
+                    // Loop resolved
+                    self.loops.remove(&key);
+                    return None;
+                }
+                self.record_failure(key, *timestamp)
+            }
+        }
+    }
+
+    fn record_failure(&mut self, key: String, timestamp: i64) -> Option<Observation> {
+        let since = timestamp - self.config.window_secs;
+        self.ide_focus.retain(|t| *t >= since);
+        let state = self.loops.entry(key.clone()).or_default();
+        state.failures.retain(|t| *t >= since);
+        if state.failures.is_empty() {
+            state.reported = false;
+        }
+        state.failures.push(timestamp);
+
+        let first = state.failures[0];
+        let ide_returns = self.ide_focus.iter().filter(|t| **t >= first && **t <= timestamp).count();
+        if state.reported || state.failures.len() < self.config.min_failures || ide_returns < self.config.min_ide_returns {
+            return None;
+        }
+        state.reported = true;
+        let failures = state.failures.len();
+        info!("DebugLoopDetector::record_failure: Debugging loop on '{}' ({} failures)", key, failures);
+
+        let mut metrics = HashMap::new();
+        metrics.insert("failed_run_count".to_string(), failures as f64);
+        metrics.insert("ide_return_count".to_string(), ide_returns as f64);
+        metrics.insert("loop_duration_min".to_string(), (timestamp - first) as f64 / 60.0);
+        let mut expected_outcome = HashMap::new();
+        expected_outcome.insert("time_saved_minutes".to_string(), 5.0);
+
+        Some(Observation {
+            id: format!("devloop_{}_{}", key.replace(' ', "_"), timestamp),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string(), "Terminal".to_string(), SHELL_ERROR_TOKEN.to_string()],
+            metrics,
+            intent: Intent::DetectPattern,
+            action: Action {
+                action_type: ActionType::PreemptiveDebugAssistant,
+                description: format!("Offer debugging assistant for repeated '{}' failures", key),
+                confidence: if failures >= self.config.min_failures * 2 { Confidence::High } else { Confidence::Medium },
+                risk: RiskCategory::None,
+            },
+            expected_outcome,
+            source: format!("plugin:{}", DEVTOOLS_PLUGIN_ID),
+            timestamp,
+            project: None,
+        })
+    }
+}
+
+impl Default for DebugLoopDetector {
+    fn default() -> Self {
+        Self::new(DebugLoopConfig::default())
+    }
+}
+
+/// First-party terminal/IDE plugin
+pub struct DevToolsPlugin {
+    metadata: PluginMetadata,
+    detector: Mutex<DebugLoopDetector>,
+}
+
+impl DevToolsPlugin {
+    /// Create plugin
+    pub fn new(config: DebugLoopConfig) -> Self {
+        info!("DevToolsPlugin::new: Creating terminal/IDE integration plugin");
+        Self {
+            metadata: PluginMetadata {
+                id: DEVTOOLS_PLUGIN_ID.to_string(),
+                name: "Terminal & IDE Integration".to_string(),
+                version: "1.0.0".to_string(),
+                author: "Athenos".to_string(),
+                capabilities: vec![PluginCapability::Observation, PluginCapability::Analysis],
+                description: "Detects edit-run-fail debugging loops from shell exit codes and IDE focus".to_string(),
+            },
+            detector: Mutex::new(DebugLoopDetector::new(config)),
+        }
+    }
+
+    /// Forward an edge event (IDE focus) to the detector
+    pub fn observe_event(&self, event: &OSEvent) {
+        if let Ok(mut detector) = self.detector.lock() {
+            detector.observe_event(event);
+        }
+    }
+
+    /// Ingest signals through the plugin host; execution is accounted against the plugin budget
+    pub fn ingest(&self, registry: &mut PluginRegistry, input: &str) -> Result<Vec<Observation>, String> {
+        registry.check_permission(DEVTOOLS_PLUGIN_ID, PluginCapability::Observation)?;
+        let started = Instant::now();
+        let result = self.execute(input);
+        let cpu_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+        registry.record_execution(DEVTOOLS_PLUGIN_ID, cpu_time_ms, input.len() as u64, result.is_ok())?;
+        serde_json::from_str(&result?).map_err(|e| format!("Invalid plugin output: {}", e))
+    }
+}
+
+impl Default for DevToolsPlugin {
+    fn default() -> Self {
+        Self::new(DebugLoopConfig::default())
+    }
+}
+
+impl Plugin for DevToolsPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    /// Input: JSON lines of DevSignal; output: JSON array of detected observations
+    fn execute(&self, input: &str) -> Result<String, String> {
+        let signals = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<DevSignal>(line).map_err(|e| format!("Invalid signal: {}", e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut detector = self.detector.lock().map_err(|_| "Detector state poisoned".to_string())?;
+        let observations: Vec<Observation> = signals.iter().filter_map(|signal| detector.process(signal)).collect();
+        serde_json::to_string(&observations).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PatternDetector;
+
+    fn shell(command: &str, exit_code: i32, timestamp: i64) -> String {
+        serde_json::to_string(&DevSignal::Shell { command: command.to_string(), exit_code, timestamp }).unwrap()
+    }
+
+    fn ide(timestamp: i64) -> String {
+        serde_json::to_string(&DevSignal::IdeFocus { ide: "Visual Studio Code".to_string(), timestamp }).unwrap()
+    }
+
+    #[test]
+    fn test_edit_run_fail_loop_triggers_debug_assistant() {
+        let plugin = DevToolsPlugin::default();
+        let mut registry = PluginRegistry::new();
+        registry.register_plugin(plugin.metadata().clone());
+
+        let input = [shell("cargo test --lib", 101, 0), ide(30), shell("cargo test", 101, 60), ide(90), shell("cargo test -q", 101, 120)].join("\n");
+        let observations = plugin.ingest(&mut registry, &input).unwrap();
+        assert_eq!(observations.len(), 1);
+        let observation = &observations[0];
+        assert_eq!(observation.action.action_type, ActionType::PreemptiveDebugAssistant);
+        assert!(observation.action.description.contains("cargo test"));
+        assert!(!observation.action.description.contains("--lib"));
+        assert_eq!(PatternDetector::new().detect_pattern(observation), PatternType::DebuggingLoop);
+        assert_eq!(registry.get_usage(DEVTOOLS_PLUGIN_ID).unwrap().action_count, 1);
+
+        // Same loop is reported once
+        assert!(plugin.ingest(&mut registry, &shell("cargo test", 101, 180)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_no_loop_without_ide_returns_or_after_success() {
+        let mut detector = DebugLoopDetector::default();
+        for t in 0..3 {
+            assert!(detector.process(&DevSignal::Shell { command: "make".to_string(), exit_code: 2, timestamp: t * 10 }).is_none());
+        }
+
+        let mut detector = DebugLoopDetector::default();
+        detector.observe_event(&OSEvent {
+            event_type: OSEventType::WindowFocus,
+            app_name: "IntelliJ IDEA".to_string(),
+            window_title: None,
+            timestamp: 5,
+            metadata: HashMap::new(),
+        });
+        detector.process(&DevSignal::Shell { command: "make".to_string(), exit_code: 2, timestamp: 0 });
+        detector.process(&DevSignal::Shell { command: "make".to_string(), exit_code: 0, timestamp: 10 });
+        detector.process(&DevSignal::Shell { command: "make".to_string(), exit_code: 2, timestamp: 20 });
+        assert!(detector.process(&DevSignal::Shell { command: "make".to_string(), exit_code: 2, timestamp: 30 }).is_none());
+    }
+
+    #[test]
+    fn test_invalid_input_counts_as_error() {
+        let plugin = DevToolsPlugin::default();
+        let mut registry = PluginRegistry::new();
+        registry.register_plugin(plugin.metadata().clone());
+        assert!(plugin.ingest(&mut registry, "not json").is_err());
+        assert_eq!(registry.get_usage(DEVTOOLS_PLUGIN_ID).unwrap().error_count, 1);
+        assert_eq!(DebugLoopDetector::command_key("/usr/bin/npm run build"), "npm run");
+    }
+}
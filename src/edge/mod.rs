@@ -3,9 +3,22 @@
 /// Captures OS events, app telemetry, optional sensors
 
 use crate::types::*;
+use crate::bus::EventBus;
+use crate::consent::{DataDisposition, MicroConsentManager, RevocationHandler, BEHAVIORAL_LOGGING_CAPABILITY};
+use crate::sampling::EventSampler;
+use crate::safety_filter::SafetyFilter;
+use crate::title_privacy::{WindowTitleProcessor, RAW_WINDOW_TITLES_CAPABILITY};
+use crate::config::{AthenosConfig, ConfigListener};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tracing::info;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::{info, warn};
+
+pub mod sensors;
+
+use sensors::{SensorInput, SensorKind, SensorReading};
+
+/// Sensor readings retained for forwarding
+const MAX_SENSOR_READINGS: usize = 256;
 
 /// OS event types captured
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -37,6 +50,12 @@ pub struct OSEvent {
 pub struct EdgeObserver {
     events: Vec<OSEvent>,
     max_events: usize,
+    title_processor: WindowTitleProcessor,
+    sampler: EventSampler,
+    bus: Option<EventBus>,
+    sensors: Vec<Box<dyn SensorInput>>,
+    sensor_consent: HashSet<SensorKind>, // Kinds whose capability is currently granted
+    sensor_readings: VecDeque<SensorReading>,
 }
 
 impl EdgeObserver {
@@ -46,13 +65,100 @@ impl EdgeObserver {
         Self {
             events: Vec::with_capacity(max_events),
             max_events,
+            title_processor: WindowTitleProcessor::new(),
+            sampler: EventSampler::default(),
+            bus: None,
+            sensors: Vec::new(),
+            sensor_consent: HashSet::new(),
+            sensor_readings: VecDeque::new(),
+        }
+    }
+
+    /// Replace the window-title processing stage (e.g. with a persisted hashing key)
+    pub fn set_title_processor(&mut self, title_processor: WindowTitleProcessor) {
+        self.title_processor = title_processor;
+    }
+
+    /// Let the generated-text safety filter learn raw titles before they are discarded
+    pub fn set_safety_filter(&mut self, safety_filter: SafetyFilter) {
+        self.title_processor.set_safety_filter(safety_filter);
+    }
+
+    /// Replace the event sampling stage
+    pub fn set_sampler(&mut self, sampler: EventSampler) {
+        self.sampler = sampler;
+    }
+
+    /// Publish every recorded event (after sampling and title processing) on the bus
+    pub fn attach_bus(&mut self, bus: &EventBus) {
+        self.bus = Some(bus.clone());
+    }
+
+    /// Event sampling stage (rate and per-type stats)
+    pub fn get_sampler(&self) -> &EventSampler {
+        &self.sampler
+    }
+
+    /// Retain raw window titles only while `raw_window_titles` consent is granted;
+    /// capture every input event only while `full_input_capture` consent is granted;
+    /// read each sensor only while its own sensor capability is granted
+    pub fn apply_consent(&mut self, consent: &MicroConsentManager) {
+        self.title_processor.apply_consent(consent);
+        self.sampler.apply_consent(consent);
+        self.sensor_consent = SensorKind::all().into_iter().filter(|kind| consent.has_consent(kind.capability())).collect();
+    }
+
+    /// Register an optional sensor; it is not read until its capability is consented
+    pub fn register_sensor(&mut self, sensor: Box<dyn SensorInput>) {
+        info!("EdgeObserver::register_sensor: Registering {} ({})", sensor.sensor_name(), sensor.kind().capability());
+        self.sensors.push(sensor);
+    }
+
+    /// Registered sensors and whether each may currently be read
+    pub fn sensors(&self) -> Vec<(&str, SensorKind, bool)> {
+        self.sensors.iter().map(|s| (s.sensor_name(), s.kind(), self.sensor_consent.contains(&s.kind()))).collect()
+    }
+
+    /// Read every consented sensor; failing sensors are logged and skipped
+    pub fn poll_sensors(&mut self, now: i64) -> Vec<SensorReading> {
+        let mut readings = Vec::new();
+        for sensor in self.sensors.iter_mut().filter(|s| self.sensor_consent.contains(&s.kind())) {
+            match sensor.read(now) {
+                Ok(Some(value)) if value.is_finite() => readings.push(SensorReading {
+                    sensor: sensor.sensor_name().to_string(),
+                    kind: sensor.kind(),
+                    value,
+                    timestamp: now,
+                }),
+                Ok(_) => {}
+                Err(e) => warn!("EdgeObserver::poll_sensors: {} failed: {}", sensor.sensor_name(), e),
+            }
         }
+        self.sensor_readings.extend(readings.iter().cloned());
+        while self.sensor_readings.len() > MAX_SENSOR_READINGS {
+            self.sensor_readings.pop_front();
+        }
+        readings
+    }
+
+    /// Recent sensor readings, oldest first
+    pub fn get_sensor_readings(&self) -> Vec<SensorReading> {
+        self.sensor_readings.iter().cloned().collect()
     }
 
     /// Record an OS event
+    /// Low-information events are sampled and raw window titles are reduced to coarse features before storage
     /// Source: Athenos_AI_Strategy.md#L100
-    pub fn record_event(&mut self, event: OSEvent) {
+    pub fn record_event(&mut self, mut event: OSEvent) {
+        self.sampler.adapt(self.events.len(), self.max_events);
+        if !self.sampler.sample(&mut event) {
+            return;
+        }
         info!("EdgeObserver::record_event: Recording {:?} from {}", event.event_type, event.app_name);
+        self.title_processor.process(&mut event);
+        if let Some(bus) = &self.bus {
+            bus.publish(event.clone());
+        }
         self.events.push(event);
         
         // Rotate if exceeds max
@@ -89,6 +195,55 @@ impl EdgeObserver {
     }
 }
 
+impl RevocationHandler for EdgeObserver {
+    fn revocation_handler_name(&self) -> &str {
+        "edge_observer"
+    }
+
+    /// Logged events exist only under behavioral_logging; raw titles kept under title consent are stripped
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        match capability {
+            BEHAVIORAL_LOGGING_CAPABILITY => {
+                let purged = self.events.len();
+                self.events.clear();
+                info!("EdgeObserver::on_consent_revoked: Purged {} events", purged);
+                Ok((DataDisposition::Purged, purged))
+            }
+            RAW_WINDOW_TITLES_CAPABILITY => {
+                let stripped = self.events.iter_mut().filter_map(|e| e.window_title.take()).count();
+                info!("EdgeObserver::on_consent_revoked: Stripped {} raw window titles", stripped);
+                Ok((DataDisposition::Anonymized, stripped))
+            }
+            sensor if SensorKind::from_capability(sensor).is_some() => {
+                let kind = SensorKind::from_capability(sensor);
+                self.sensor_consent.retain(|k| Some(*k) != kind);
+                let before = self.sensor_readings.len();
+                self.sensor_readings.retain(|r| Some(r.kind) != kind);
+                let purged = before - self.sensor_readings.len();
+                info!("EdgeObserver::on_consent_revoked: Purged {} {} readings", purged, sensor);
+                Ok((DataDisposition::Purged, purged))
+            }
+            _ => Ok((DataDisposition::NotAffected, 0)),
+        }
+    }
+}
+
+impl ConfigListener for EdgeObserver {
+    fn config_name(&self) -> &str {
+        "edge_observer"
+    }
+
+    /// Apply a new event buffer limit, dropping the oldest events if it shrank
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        self.max_events = config.observer.max_events;
+        if self.events.len() > self.max_events {
+            let excess = self.events.len() - self.max_events;
+            self.events.drain(..excess);
+        }
+        Ok(())
+    }
+}
+
 impl Default for EdgeObserver {
     fn default() -> Self {
         Self::new(1000)
@@ -121,6 +276,25 @@ mod tests {
         assert_eq!(observer.events[0].app_name, "Teams");
     }
 
+    #[test]
+    fn test_input_events_sampled_transitions_kept() {
+        let mut observer = EdgeObserver::new(100);
+        for i in 0..10 {
+            observer.record_event(OSEvent {
+                event_type: if i % 5 == 0 { OSEventType::AppSwitch } else { OSEventType::KeyPress },
+                app_name: "IDE".to_string(),
+                window_title: None,
+                timestamp: i * 10, // One sampling window per event
+                metadata: HashMap::new(),
+            });
+        }
+        
+        let events = observer.get_recent_events(100);
+        assert_eq!(events.iter().filter(|e| e.event_type == OSEventType::AppSwitch).count(), 2);
+        assert_eq!(events.iter().filter(|e| e.event_type == OSEventType::KeyPress).count(), 2);
+        assert_eq!(crate::sampling::estimated_count(&events, |e| e.event_type == OSEventType::KeyPress), 8.0);
+    }
+
     #[test]
     fn test_app_sequence() {
         let mut observer = EdgeObserver::new(10);
@@ -171,5 +345,43 @@ mod tests {
         assert_eq!(observer.events[0].app_name, "App3");
         assert_eq!(observer.events[1].app_name, "App4");
     }
-}
 
+    #[test]
+    fn test_window_titles_not_stored_raw() {
+        let mut observer = EdgeObserver::new(10);
+        observer.record_event(OSEvent {
+            event_type: OSEventType::WindowFocus,
+            app_name: "Word".to_string(),
+            window_title: Some("Salary review.docx - Word".to_string()),
+            timestamp: 1,
+            metadata: HashMap::new(),
+        });
+        
+        let stored = &observer.get_recent_events(1)[0];
+        assert!(stored.window_title.is_none());
+        assert_eq!(stored.metadata.get("title.document_type").map(String::as_str), Some("docx"));
+    }
+
+    #[test]
+    fn test_sensors_gated_by_consent() {
+        use sensors::SimulatedSensor;
+        let mut observer = EdgeObserver::new(10);
+        observer.register_sensor(Box::new(SimulatedSensor::new("watch", SensorKind::HeartRate, vec![90.0])));
+        observer.register_sensor(Box::new(SimulatedSensor::new("mic", SensorKind::AmbientNoise, vec![70.0])));
+        assert!(observer.poll_sensors(100).is_empty());
+        
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(SensorKind::HeartRate.capability().to_string(), "Read heart rate from your watch".to_string());
+        consent.grant_consent(SensorKind::HeartRate.capability()).unwrap();
+        observer.apply_consent(&consent);
+        
+        let readings = observer.poll_sensors(200);
+        assert_eq!(readings.len(), 1);
+        assert_eq!((readings[0].kind, readings[0].value), (SensorKind::HeartRate, 90.0));
+        assert_eq!(sensors::normalized_signals(&readings)["sensor_heart_rate_load"], 0.5);
+        
+        let (disposition, purged) = observer.on_consent_revoked(SensorKind::HeartRate.capability()).unwrap();
+        assert_eq!((disposition, purged), (DataDisposition::Purged, 1));
+        assert!(observer.poll_sensors(300).is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+/// Phase: A | Step: 5 | Source: Athenos_AI_Strategy.md#L100
+/// Optional Sensor Inputs
+/// Pluggable biometric/ambient sensors (heart rate, HRV, ambient noise), each gated by its own micro-consent
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Supported sensor kinds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    HeartRate,             // Beats per minute
+    HeartRateVariability,  // RMSSD, milliseconds
+    AmbientNoise,          // dB(A)
+}
+
+impl SensorKind {
+    pub fn all() -> Vec<SensorKind> {
+        vec![SensorKind::HeartRate, SensorKind::HeartRateVariability, SensorKind::AmbientNoise]
+    }
+
+    /// Micro-consent capability that must be granted before readings are taken
+    pub fn capability(&self) -> &'static str {
+        match self {
+            SensorKind::HeartRate => "sensor_heart_rate",
+            SensorKind::HeartRateVariability => "sensor_hrv",
+            SensorKind::AmbientNoise => "sensor_ambient_noise",
+        }
+    }
+
+    pub fn from_capability(capability: &str) -> Option<SensorKind> {
+        Self::all().into_iter().find(|kind| kind.capability() == capability)
+    }
+
+    /// Metric key the normalized signal is forwarded under
+    pub fn signal_key(&self) -> &'static str {
+        match self {
+            SensorKind::HeartRate => "sensor_heart_rate_load",
+            SensorKind::HeartRateVariability => "sensor_hrv_load",
+            SensorKind::AmbientNoise => "sensor_noise_load",
+        }
+    }
+
+    /// Map a raw value to 0.0 (relaxed/quiet) .. 1.0 (strained/loud); non-finite values map to None
+    pub fn normalize(&self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return None;
+        }
+        let load = match self {
+            SensorKind::HeartRate => (value - 60.0) / 60.0,              // 60 bpm resting .. 120 bpm
+            SensorKind::HeartRateVariability => (70.0 - value) / 50.0,   // Low RMSSD indicates stress: 70ms .. 20ms
+            SensorKind::AmbientNoise => (value - 40.0) / 40.0,           // Quiet office 40 dB .. 80 dB
+        };
+        Some(load.clamp(0.0, 1.0))
+    }
+}
+
+/// One raw reading
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SensorReading {
+    pub sensor: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
+/// Optional sensor plugged into the edge observer
+pub trait SensorInput: Send {
+    fn sensor_name(&self) -> &str;
+    fn kind(&self) -> SensorKind;
+    /// Take a reading; Ok(None) when the device has nothing new
+    fn read(&mut self, now: i64) -> Result<Option<f64>, String>;
+}
+
+/// Latest normalized signal per sensor kind, keyed by SensorKind::signal_key
+pub fn normalized_signals(readings: &[SensorReading]) -> HashMap<String, f64> {
+    let mut latest: HashMap<SensorKind, &SensorReading> = HashMap::new();
+    for reading in readings {
+        let newer = latest.get(&reading.kind).is_none_or(|current| reading.timestamp >= current.timestamp);
+        if newer {
+            latest.insert(reading.kind, reading);
+        }
+    }
+    latest
+        .into_iter()
+        .filter_map(|(kind, reading)| kind.normalize(reading.value).map(|load| (kind.signal_key().to_string(), load)))
+        .collect()
+}
+
+/// Sensor replaying fixed values (tests, demos, devices without drivers)
+pub struct SimulatedSensor {
+    name: String,
+    kind: SensorKind,
+    values: Vec<f64>,
+    next: usize,
+}
+
+impl SimulatedSensor {
+    /// Cycles through `values` on each read
+    pub fn new(name: &str, kind: SensorKind, values: Vec<f64>) -> Self {
+        Self { name: name.to_string(), kind, values, next: 0 }
+    }
+}
+
+impl SensorInput for SimulatedSensor {
+    fn sensor_name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> SensorKind {
+        self.kind
+    }
+
+    fn read(&mut self, _now: i64) -> Result<Option<f64>, String> {
+        if self.values.is_empty() {
+            return Ok(None);
+        }
+        let value = self.values[self.next % self.values.len()];
+        self.next += 1;
+        Ok(Some(value))
+    }
+}
@@ -0,0 +1,227 @@
+/// Phase: B | Step: 6 | Source: Athenos_AI_Strategy.md#L113
+/// Emotion History
+/// Exponentially smoothed stress with hysteresis bands and a dwell requirement, so focus mode reacts to sustained changes only
+
+use super::EmotionEstimate;
+use crate::types::EmotionalState;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::info;
+
+/// Smoothing, hysteresis and dwell settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HysteresisConfig {
+    pub alpha: f64,             // Weight of each new stress score in the smoothed value
+    pub stressed_enter: f64,    // Smoothed stress above which Stressed is proposed
+    pub stressed_exit: f64,     // ...and below which it is left (exit < enter)
+    pub fatigued_enter: f64,
+    pub fatigued_exit: f64,
+    pub sustain_samples: usize, // Consecutive proposals needed before the stable state changes
+    pub trend_samples: usize,   // Look-back for get_trend
+    pub trend_delta: f64,       // Smoothed stress change that counts as a trend
+    pub capacity: usize,        // Samples retained
+}
+
+impl Default for HysteresisConfig {
+    fn default() -> Self {
+        Self {
+            alpha: 0.4,
+            stressed_enter: 0.5,
+            stressed_exit: 0.4,
+            fatigued_enter: 0.3,
+            fatigued_exit: 0.2,
+            sustain_samples: 3,
+            trend_samples: 5,
+            trend_delta: 0.05,
+            capacity: 100,
+        }
+    }
+}
+
+/// Direction of smoothed stress over recent samples
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmotionTrend {
+    Improving, // Stress falling
+    Stable,
+    Worsening, // Stress rising
+}
+
+/// One recorded estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmotionSample {
+    pub timestamp: i64,
+    pub raw_state: EmotionalState,
+    pub stress: f64,          // Raw stress score of the estimate
+    pub smoothed_stress: f64, // After exponential smoothing
+}
+
+/// Bounded emotion history with a debounced stable state
+pub struct EmotionHistory {
+    config: HysteresisConfig,
+    samples: VecDeque<EmotionSample>,
+    stable: Option<EmotionalState>,
+    candidate: Option<(EmotionalState, usize)>, // Proposed state and consecutive proposals
+}
+
+impl EmotionHistory {
+    pub fn new(config: HysteresisConfig) -> Self {
+        info!("EmotionHistory::new: Creating emotion history (alpha {}, sustain {})", config.alpha, config.sustain_samples);
+        Self { config, samples: VecDeque::new(), stable: None, candidate: None }
+    }
+
+    /// Record an estimate; returns the new stable state when it changed
+    /// The first estimate sets the stable state directly; later changes must be sustained
+    pub fn record(&mut self, estimate: &EmotionEstimate) -> Option<EmotionalState> {
+        let stress = estimate.confidence.clamp(0.0, 1.0);
+        let smoothed_stress = match self.samples.back() {
+            Some(previous) => previous.smoothed_stress * (1.0 - self.config.alpha) + stress * self.config.alpha,
+            None => stress,
+        };
+        self.samples.push_back(EmotionSample {
+            timestamp: estimate.timestamp,
+            raw_state: estimate.emotional_state.clone(),
+            stress,
+            smoothed_stress,
+        });
+        if self.samples.len() > self.config.capacity {
+            self.samples.pop_front();
+        }
+
+        let proposed = self.propose(smoothed_stress, &estimate.emotional_state);
+        let Some(stable) = self.stable.clone() else {
+            self.stable = Some(proposed.clone());
+            return Some(proposed);
+        };
+        if proposed == stable {
+            self.candidate = None;
+            return None;
+        }
+        let count = match &self.candidate {
+            Some((state, count)) if *state == proposed => count + 1,
+            _ => 1,
+        };
+        if count < self.config.sustain_samples {
+            self.candidate = Some((proposed, count));
+            return None;
+        }
+        info!("EmotionHistory::record: Stable state {:?} -> {:?} (smoothed stress {:.2})", stable, proposed, smoothed_stress);
+        self.candidate = None;
+        self.stable = Some(proposed.clone());
+        Some(proposed)
+    }
+
+    /// State the smoothed stress supports, using the exit threshold for the level currently held
+    fn propose(&self, smoothed: f64, raw: &EmotionalState) -> EmotionalState {
+        let holding = |state: EmotionalState| self.stable.as_ref() == Some(&state);
+        let stressed_threshold = if holding(EmotionalState::Stressed) { self.config.stressed_exit } else { self.config.stressed_enter };
+        let fatigued_threshold = if holding(EmotionalState::Stressed) || holding(EmotionalState::Fatigued) {
+            self.config.fatigued_exit
+        } else {
+            self.config.fatigued_enter
+        };
+        if smoothed > stressed_threshold {
+            EmotionalState::Stressed
+        } else if smoothed > fatigued_threshold {
+            EmotionalState::Fatigued
+        } else if matches!(raw, EmotionalState::Stressed | EmotionalState::Fatigued) {
+            // Stress too low to back a stressed raw reading
+            EmotionalState::Calm
+        } else {
+            raw.clone()
+        }
+    }
+
+    /// Debounced state focus-mode adjustments should follow
+    pub fn stable_state(&self) -> Option<&EmotionalState> {
+        self.stable.as_ref()
+    }
+
+    /// Latest smoothed stress (0.0 with no samples)
+    pub fn smoothed_stress(&self) -> f64 {
+        self.samples.back().map(|s| s.smoothed_stress).unwrap_or(0.0)
+    }
+
+    /// Whether smoothed stress has been rising or falling over the last trend_samples estimates
+    pub fn get_trend(&self) -> EmotionTrend {
+        let len = self.samples.len();
+        if len <= self.config.trend_samples {
+            return EmotionTrend::Stable;
+        }
+        let delta = self.samples[len - 1].smoothed_stress - self.samples[len - 1 - self.config.trend_samples].smoothed_stress;
+        if delta > self.config.trend_delta {
+            EmotionTrend::Worsening
+        } else if delta < -self.config.trend_delta {
+            EmotionTrend::Improving
+        } else {
+            EmotionTrend::Stable
+        }
+    }
+
+    /// Recorded samples, oldest first
+    pub fn samples(&self) -> impl Iterator<Item = &EmotionSample> {
+        self.samples.iter()
+    }
+}
+
+impl Default for EmotionHistory {
+    fn default() -> Self {
+        Self::new(HysteresisConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(state: EmotionalState, stress: f64) -> EmotionEstimate {
+        EmotionEstimate { emotional_state: state, confidence: stress, signals: Vec::new(), timestamp: 0 }
+    }
+
+    #[test]
+    fn test_single_spike_does_not_flap() {
+        let mut history = EmotionHistory::default();
+        assert_eq!(history.record(&estimate(EmotionalState::Calm, 0.0)), Some(EmotionalState::Calm));
+
+        // One stressed reading between calm ones never changes the stable state
+        assert_eq!(history.record(&estimate(EmotionalState::Stressed, 0.9)), None);
+        for _ in 0..5 {
+            assert_eq!(history.record(&estimate(EmotionalState::Calm, 0.0)), None);
+        }
+        assert_eq!(history.stable_state(), Some(&EmotionalState::Calm));
+    }
+
+    #[test]
+    fn test_sustained_stress_enters_and_exits_with_hysteresis() {
+        let mut history = EmotionHistory::default();
+        history.record(&estimate(EmotionalState::Calm, 0.0));
+        let changes: Vec<Option<EmotionalState>> = (0..6).map(|_| history.record(&estimate(EmotionalState::Stressed, 0.9))).collect();
+        assert!(changes.contains(&Some(EmotionalState::Stressed)));
+        assert_eq!(history.get_trend(), EmotionTrend::Worsening);
+
+        // Smoothed stress hovering between exit (0.45) and enter (0.6) holds Stressed
+        for _ in 0..10 {
+            history.record(&estimate(EmotionalState::Fatigued, 0.5));
+        }
+        assert_eq!(history.stable_state(), Some(&EmotionalState::Stressed));
+
+        for _ in 0..15 {
+            history.record(&estimate(EmotionalState::Focused, 0.0));
+        }
+        assert_eq!(history.stable_state(), Some(&EmotionalState::Focused));
+        assert_eq!(history.get_trend(), EmotionTrend::Stable);
+        assert!(history.smoothed_stress() < 0.01);
+    }
+
+    #[test]
+    fn test_trend_improving() {
+        let mut history = EmotionHistory::default();
+        for _ in 0..3 {
+            history.record(&estimate(EmotionalState::Stressed, 0.9));
+        }
+        for _ in 0..5 {
+            history.record(&estimate(EmotionalState::Calm, 0.1));
+        }
+        assert_eq!(history.get_trend(), EmotionTrend::Improving);
+    }
+}
@@ -0,0 +1,271 @@
+/// Phase: B | Step: 6 | Source: Athenos_AI_Strategy.md#L113
+/// Keystroke Dynamics Analyzer
+/// Inter-key latency, burstiness and backspace ratios over sliding windows of raw KeyPress events
+
+use crate::edge::{OSEvent, OSEventType};
+use crate::sampling;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tracing::info;
+
+/// Optional event metadata with a millisecond timestamp; whole-second timestamps are too coarse for key latency
+pub const META_TIMESTAMP_MS: &str = "timestamp_ms";
+
+/// Keys that undo typing (key identity only; typed text is never read)
+const CORRECTION_KEYS: [&str; 3] = ["backspace", "delete", "back"];
+
+/// Sliding-window settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystrokeConfig {
+    pub window_ms: i64,              // Length of each analysis window
+    pub hop_ms: i64,                 // Stride between consecutive windows
+    pub history_ms: i64,             // Keystrokes older than this, relative to the newest, are dropped
+    pub pause_ms: i64,               // Gaps longer than this are pauses, not key latency
+    pub min_keys: f64,               // Estimated keystrokes a window needs to be scored
+    pub min_baseline_windows: usize, // Earlier windows needed before a slowdown is reported
+}
+
+impl Default for KeystrokeConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 60_000,
+            hop_ms: 30_000,
+            history_ms: 30 * 60_000,
+            pause_ms: 2_000,
+            min_keys: 20.0,
+            min_baseline_windows: 3,
+        }
+    }
+}
+
+/// Typing statistics for one window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeystrokeWindow {
+    pub start_ms: i64,
+    pub end_ms: i64,               // Exclusive
+    pub keys: f64,                 // Estimated keystrokes, corrected for sampling
+    pub keys_per_min: f64,
+    pub median_latency_ms: f64,    // Inter-key latency, pauses excluded
+    pub p90_latency_ms: f64,
+    pub burstiness: f64,           // (σ − μ) / (σ + μ) of inter-key gaps: −1 regular, 0 random, 1 bursty
+    pub backspace_ratio: f64,      // Share of keystrokes that were corrections
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Keystroke {
+    at_ms: i64,
+    correction: bool,
+    weight: f64, // Inverse sampling probability
+}
+
+/// Turns raw KeyPress events into stress-relevant typing signals
+/// Keys match EmotionEstimator: typing_speed_decrease_pct, error_rate, keystroke_burstiness, keystroke_latency_increase_pct
+pub struct KeystrokeDynamicsAnalyzer {
+    config: KeystrokeConfig,
+    keystrokes: VecDeque<Keystroke>, // Oldest first, within history_ms of the newest
+}
+
+impl KeystrokeDynamicsAnalyzer {
+    pub fn new(config: KeystrokeConfig) -> Self {
+        info!("KeystrokeDynamicsAnalyzer::new: Creating keystroke analyzer (window {}ms, hop {}ms)", config.window_ms, config.hop_ms);
+        Self { config, keystrokes: VecDeque::new() }
+    }
+
+    /// Add the KeyPress events of a batch; other event types are ignored
+    pub fn ingest(&mut self, events: &[OSEvent]) {
+        let mut added: Vec<Keystroke> = events
+            .iter()
+            .filter(|e| e.event_type == OSEventType::KeyPress)
+            .map(|e| Keystroke {
+                at_ms: e.metadata.get(META_TIMESTAMP_MS).and_then(|ms| ms.parse().ok()).unwrap_or(e.timestamp * 1000),
+                correction: e.metadata.get("key").is_some_and(|k| CORRECTION_KEYS.contains(&k.to_lowercase().as_str())),
+                weight: sampling::sample_weight(e),
+            })
+            .collect();
+        if added.is_empty() {
+            return;
+        }
+        added.sort_by_key(|k| k.at_ms);
+        // Batches normally arrive in order; merge if one does not
+        if self.keystrokes.back().is_some_and(|last| last.at_ms > added[0].at_ms) {
+            added.extend(self.keystrokes.drain(..));
+            added.sort_by_key(|k| k.at_ms);
+        }
+        self.keystrokes.extend(added);
+        let newest = self.keystrokes.back().map(|k| k.at_ms).unwrap_or(0);
+        while self.keystrokes.front().is_some_and(|k| k.at_ms < newest - self.config.history_ms) {
+            self.keystrokes.pop_front();
+        }
+    }
+
+    /// Keystrokes currently held
+    pub fn len(&self) -> usize {
+        self.keystrokes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keystrokes.is_empty()
+    }
+
+    /// Statistics for [start_ms, end_ms), or None when too few keys were typed
+    fn window(&self, start_ms: i64, end_ms: i64) -> Option<KeystrokeWindow> {
+        let keys: Vec<&Keystroke> = self.keystrokes.iter().filter(|k| k.at_ms >= start_ms && k.at_ms < end_ms).collect();
+        let estimated: f64 = keys.iter().map(|k| k.weight).sum();
+        if estimated < self.config.min_keys {
+            return None;
+        }
+        // A sampled keystroke stands for `weight` keystrokes, so its gap spans that many intervals
+        let gaps: Vec<f64> = keys.windows(2).map(|w| (w[1].at_ms - w[0].at_ms) as f64 / w[1].weight).collect();
+        let mut latencies: Vec<f64> = gaps.iter().copied().filter(|g| *g <= self.config.pause_ms as f64).collect();
+        latencies.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| {
+            if latencies.is_empty() {
+                0.0
+            } else {
+                latencies[((latencies.len() - 1) as f64 * p).round() as usize]
+            }
+        };
+        let burstiness = if gaps.is_empty() {
+            0.0
+        } else {
+            let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+            let std_dev = (gaps.iter().map(|g| (g - mean).powi(2)).sum::<f64>() / gaps.len() as f64).sqrt();
+            if std_dev + mean > 0.0 { (std_dev - mean) / (std_dev + mean) } else { 0.0 }
+        };
+        let corrections: f64 = keys.iter().filter(|k| k.correction).map(|k| k.weight).sum();
+        Some(KeystrokeWindow {
+            start_ms,
+            end_ms,
+            keys: estimated,
+            keys_per_min: estimated * 60_000.0 / (end_ms - start_ms) as f64,
+            median_latency_ms: percentile(0.5),
+            p90_latency_ms: percentile(0.9),
+            burstiness,
+            backspace_ratio: corrections / estimated,
+        })
+    }
+
+    /// Scored sliding windows over the held history, oldest first; the last one ends at the newest keystroke
+    /// Earlier windows must lie wholly inside the history so a partial window never skews the baseline
+    pub fn windows(&self) -> Vec<KeystrokeWindow> {
+        let (Some(first), Some(last)) = (self.keystrokes.front(), self.keystrokes.back()) else {
+            return Vec::new();
+        };
+        let mut windows = Vec::new();
+        let mut end_ms = last.at_ms + 1;
+        loop {
+            if let Some(window) = self.window(end_ms - self.config.window_ms, end_ms) {
+                windows.push(window);
+            }
+            end_ms -= self.config.hop_ms.max(1);
+            if end_ms - self.config.window_ms < first.at_ms {
+                break;
+            }
+        }
+        windows.reverse();
+        windows
+    }
+
+    /// Derived signals for EmotionEstimator::estimate_emotion; empty until a window has enough keys
+    /// The slowdown and latency signals compare the latest window with the median of earlier ones
+    pub fn signals(&self) -> HashMap<String, f64> {
+        let mut signals = HashMap::new();
+        let mut windows = self.windows();
+        let Some(latest) = windows.pop() else {
+            return signals;
+        };
+        signals.insert("error_rate".to_string(), latest.backspace_ratio);
+        signals.insert("keystroke_burstiness".to_string(), latest.burstiness);
+        signals.insert("keystroke_median_latency_ms".to_string(), latest.median_latency_ms);
+
+        // Baseline windows must not overlap the latest one
+        let baseline: Vec<&KeystrokeWindow> = windows.iter().filter(|w| w.end_ms <= latest.start_ms).collect();
+        if baseline.len() >= self.config.min_baseline_windows {
+            let median = |mut values: Vec<f64>| {
+                values.sort_by(|a, b| a.total_cmp(b));
+                values[values.len() / 2]
+            };
+            let baseline_rate = median(baseline.iter().map(|w| w.keys_per_min).collect());
+            let baseline_latency = median(baseline.iter().map(|w| w.median_latency_ms).collect());
+            if baseline_rate > 0.0 {
+                signals.insert("typing_speed_decrease_pct".to_string(), ((baseline_rate - latest.keys_per_min) / baseline_rate * 100.0).max(0.0));
+            }
+            if baseline_latency > 0.0 {
+                signals.insert("keystroke_latency_increase_pct".to_string(), ((latest.median_latency_ms - baseline_latency) / baseline_latency * 100.0).max(0.0));
+            }
+        }
+        signals
+    }
+}
+
+impl Default for KeystrokeDynamicsAnalyzer {
+    fn default() -> Self {
+        Self::new(KeystrokeConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(at_ms: i64, key: &str) -> OSEvent {
+        let mut metadata = HashMap::new();
+        metadata.insert(META_TIMESTAMP_MS.to_string(), at_ms.to_string());
+        metadata.insert("key".to_string(), key.to_string());
+        OSEvent {
+            event_type: OSEventType::KeyPress,
+            app_name: "IDE".to_string(),
+            window_title: None,
+            timestamp: at_ms / 1000,
+            metadata,
+        }
+    }
+
+    /// `count` keys every `gap_ms` from `start_ms`; every `correction_every`-th key is a backspace
+    fn typing(start_ms: i64, count: i64, gap_ms: i64, correction_every: i64) -> Vec<OSEvent> {
+        (0..count)
+            .map(|i| key(start_ms + i * gap_ms, if correction_every > 0 && i % correction_every == 0 { "Backspace" } else { "a" }))
+            .collect()
+    }
+
+    #[test]
+    fn test_window_statistics() {
+        let mut analyzer = KeystrokeDynamicsAnalyzer::default();
+        analyzer.ingest(&typing(0, 100, 200, 10));
+        let windows = analyzer.windows();
+        let latest = windows.last().unwrap();
+        assert_eq!(latest.median_latency_ms, 200.0);
+        assert_eq!(latest.keys_per_min, 100.0);
+        assert!((latest.backspace_ratio - 0.1).abs() < 1e-9);
+        assert!((latest.burstiness + 1.0).abs() < 1e-9); // Perfectly regular
+    }
+
+    #[test]
+    fn test_slowdown_and_corrections_feed_stress() {
+        let mut analyzer = KeystrokeDynamicsAnalyzer::default();
+        analyzer.ingest(&typing(0, 1200, 200, 0)); // 4 minutes at 300 keys/min
+        assert!(analyzer.signals()["typing_speed_decrease_pct"] < 1.0);
+
+        // Next minute: slow, hesitant typing with many corrections
+        analyzer.ingest(&typing(240_000, 100, 600, 4));
+        let signals = analyzer.signals();
+        assert!(signals["typing_speed_decrease_pct"] > 60.0);
+        assert!(signals["keystroke_latency_increase_pct"] > 100.0);
+        assert!(signals["error_rate"] > 0.2);
+
+        let estimate = crate::emotion::EmotionEstimator::new().estimate_emotion(&signals);
+        assert!(estimate.signals.contains(&"Slow typing detected".to_string()));
+        assert!(estimate.signals.contains(&"High error rate".to_string()));
+    }
+
+    #[test]
+    fn test_history_pruned_and_sparse_windows_skipped() {
+        let mut analyzer = KeystrokeDynamicsAnalyzer::default();
+        analyzer.ingest(&typing(0, 10, 200, 0));
+        assert!(analyzer.signals().is_empty()); // Below min_keys
+
+        analyzer.ingest(&typing(40 * 60_000, 30, 200, 0));
+        assert_eq!(analyzer.len(), 30);
+        assert!(analyzer.signals().contains_key("error_rate"));
+    }
+}
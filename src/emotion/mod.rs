@@ -3,10 +3,27 @@
 /// Enable mood-adaptive focus mode (emotion estimator + UI adjustments)
 
 use crate::types::*;
+use crate::attention::AttentionService;
+use crate::bus::EventSubscriber;
+use crate::consent::{DataDisposition, RevocationHandler};
+use crate::edge::{OSEvent, OSEventType};
+use crate::edge::sensors::{SensorKind, SensorReading};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+pub mod history;
+pub mod keystroke;
+
+use history::{EmotionHistory, EmotionTrend};
+use keystroke::KeystrokeDynamicsAnalyzer;
+
+/// Inter-key gap burstiness above which typing rhythm counts as erratic
+const ERRATIC_BURSTINESS: f64 = 0.5;
+
+/// Sensor loads older than this (relative to the newest event) no longer join estimates
+const SENSOR_SIGNAL_MAX_AGE_SECS: i64 = 15 * 60;
+
 /// Emotion estimate from behavioral signals
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmotionEstimate {
@@ -20,6 +37,9 @@ pub struct EmotionEstimate {
 /// Source: Athenos_AI_Strategy.md#L113
 pub struct EmotionEstimator {
     signal_weights: HashMap<String, f64>,
+    latest: Option<EmotionEstimate>, // Estimate from the last bus batch
+    keystrokes: KeystrokeDynamicsAnalyzer,
+    sensor_signals: HashMap<SensorKind, (f64, i64)>, // Latest normalized load and reading time per sensor kind
 }
 
 impl EmotionEstimator {
@@ -32,7 +52,46 @@ impl EmotionEstimator {
         signal_weights.insert("context_switch_frequency".to_string(), 0.2);
         signal_weights.insert("session_duration".to_string(), 0.25);
         
-        Self { signal_weights }
+        Self { signal_weights, latest: None, keystrokes: KeystrokeDynamicsAnalyzer::default(), sensor_signals: HashMap::new() }
+    }
+
+    /// Forward consented sensor readings; the latest normalized value per sensor kind joins later estimates
+    pub fn ingest_sensor_readings(&mut self, readings: &[SensorReading]) {
+        for reading in readings {
+            let Some(load) = reading.kind.normalize(reading.value) else { continue };
+            if self.sensor_signals.get(&reading.kind).is_none_or(|(_, at)| reading.timestamp >= *at) {
+                self.sensor_signals.insert(reading.kind, (load, reading.timestamp));
+            }
+        }
+    }
+
+    /// Estimate from a raw event batch, adding keystroke-dynamics signals from the sliding windows seen so far
+    /// Sensor loads older than SENSOR_SIGNAL_MAX_AGE_SECS before the batch's newest event are dropped
+    pub fn estimate_from_events(&mut self, events: &[OSEvent]) -> EmotionEstimate {
+        self.keystrokes.ingest(events);
+        if let Some(now) = events.iter().map(|e| e.timestamp).max() {
+            self.sensor_signals.retain(|_, (_, at)| now - *at <= SENSOR_SIGNAL_MAX_AGE_SECS);
+        }
+        let mut metrics = Self::metrics_from_events(events);
+        metrics.extend(self.keystrokes.signals());
+        metrics.extend(self.sensor_signals.iter().map(|(kind, (load, _))| (kind.signal_key().to_string(), *load)));
+        self.estimate_emotion(&metrics)
+    }
+
+    /// Behavioral metrics derivable from a raw event batch
+    pub fn metrics_from_events(events: &[OSEvent]) -> HashMap<String, f64> {
+        let mut metrics = HashMap::new();
+        let switches = events.iter().filter(|e| e.event_type == OSEventType::AppSwitch).count();
+        metrics.insert("context_switch_count".to_string(), switches as f64);
+        if let (Some(first), Some(last)) = (events.iter().map(|e| e.timestamp).min(), events.iter().map(|e| e.timestamp).max()) {
+            metrics.insert("session_duration_min".to_string(), (last - first) as f64 / 60.0);
+        }
+        metrics
+    }
+
+    /// Estimate from the most recent bus batch
+    pub fn latest_estimate(&self) -> Option<&EmotionEstimate> {
+        self.latest.as_ref()
     }
 
     /// Estimate emotion from behavioral signals
@@ -41,7 +100,7 @@ impl EmotionEstimator {
         info!("EmotionEstimator::estimate_emotion: Estimating emotion from metrics");
         
         let mut signals = Vec::new();
-        let mut stress_score = 0.0;
+        let mut stress_score: f64 = 0.0;
         
         // Check typing speed decrease
         if let Some(speed_decrease) = metrics.get("typing_speed_decrease_pct") {
@@ -59,6 +118,28 @@ impl EmotionEstimator {
             }
         }
         
+        // Check typing rhythm (keystroke dynamics)
+        if let Some(burstiness) = metrics.get("keystroke_burstiness") {
+            if *burstiness > ERRATIC_BURSTINESS {
+                signals.push("Erratic typing rhythm".to_string());
+                stress_score += 0.15;
+            }
+        }
+        
+        // Check optional biometric/ambient sensors (normalized 0.0 to 1.0)
+        if metrics.get("sensor_heart_rate_load").copied().unwrap_or(0.0) > 0.5 {
+            signals.push("Elevated heart rate".to_string());
+            stress_score += 0.15;
+        }
+        if metrics.get("sensor_hrv_load").copied().unwrap_or(0.0) > 0.6 {
+            signals.push("Low heart-rate variability".to_string());
+            stress_score += 0.2;
+        }
+        if metrics.get("sensor_noise_load").copied().unwrap_or(0.0) > 0.6 {
+            signals.push("Noisy environment".to_string());
+            stress_score += 0.1;
+        }
+        
         // Check context switching
         if let Some(context_switches) = metrics.get("context_switch_count") {
             if *context_switches > 10.0 {
@@ -75,7 +156,7 @@ impl EmotionEstimator {
             }
         }
         
-        let emotional_state = if stress_score > 0.6 {
+        let emotional_state = if stress_score > 0.5 {
             EmotionalState::Stressed
         } else if stress_score > 0.3 {
             EmotionalState::Fatigued
@@ -94,6 +175,16 @@ impl EmotionEstimator {
     }
 }
 
+impl EventSubscriber for EmotionEstimator {
+    fn subscriber_name(&self) -> &str {
+        "emotion_estimator"
+    }
+
+    fn on_events(&mut self, events: &[OSEvent]) {
+        self.latest = Some(self.estimate_from_events(events));
+    }
+}
+
 /// Focus mode adjustments based on emotion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FocusModeAdjustments {
@@ -108,27 +199,39 @@ pub struct FocusModeAdjustments {
 /// Source: Athenos_AI_Strategy.md#L113
 pub struct MoodAdaptiveFocusMode {
     emotion_estimator: EmotionEstimator,
+    history: EmotionHistory, // Debounces snapshots so adjustments follow sustained changes only
     current_adjustments: Option<FocusModeAdjustments>,
+    attention: AttentionService,
 }
 
 impl MoodAdaptiveFocusMode {
     /// Create new mood-adaptive focus mode
     pub fn new() -> Self {
+        Self::with_attention(AttentionService::new())
+    }
+
+    /// Create mood-adaptive focus mode sharing an attention service
+    pub fn with_attention(attention: AttentionService) -> Self {
         info!("MoodAdaptiveFocusMode::new: Creating mood-adaptive focus mode");
         Self {
             emotion_estimator: EmotionEstimator::new(),
+            history: EmotionHistory::default(),
             current_adjustments: None,
+            attention,
         }
     }
 
     /// Update focus mode based on emotion estimate
+    /// Adjustments follow the history's stable state, so a single outlying snapshot changes nothing
     /// Source: Athenos_AI_Strategy.md#L113
     pub fn update_focus_mode(&mut self, metrics: &HashMap<String, f64>) -> FocusModeAdjustments {
         info!("MoodAdaptiveFocusMode::update_focus_mode: Updating focus mode");
         
         let emotion = self.emotion_estimator.estimate_emotion(metrics);
+        self.history.record(&emotion);
+        let state = self.history.stable_state().cloned().unwrap_or(emotion.emotional_state);
         
-        let adjustments = match emotion.emotional_state {
+        let adjustments = match state {
             EmotionalState::Stressed => FocusModeAdjustments {
                 reduce_notifications: true,
                 dim_screen: true,
@@ -159,9 +262,28 @@ impl MoodAdaptiveFocusMode {
             },
         };
         
+        // Propagate DND and mood to schedulers and nudges via the shared attention state
+        self.attention.set_emotional_state(Some(state));
+        let snapshot = self.attention.current();
+        if adjustments.reduce_notifications {
+            self.attention.enable_dnd("mood_adaptive_focus", None);
+        } else if snapshot.dnd_enabled && snapshot.source == "mood_adaptive_focus" {
+            self.attention.disable_dnd("mood_adaptive_focus");
+        }
+        
         self.current_adjustments = Some(adjustments.clone());
         adjustments
     }
+
+    /// Whether stress has been improving or worsening over recent updates
+    pub fn get_trend(&self) -> EmotionTrend {
+        self.history.get_trend()
+    }
+
+    /// Smoothed emotion history behind the adjustments
+    pub fn history(&self) -> &EmotionHistory {
+        &self.history
+    }
 }
 
 impl Default for MoodAdaptiveFocusMode {
@@ -170,6 +292,24 @@ impl Default for MoodAdaptiveFocusMode {
     }
 }
 
+impl RevocationHandler for EmotionEstimator {
+    fn revocation_handler_name(&self) -> &str {
+        "emotion_estimator"
+    }
+
+    /// Sensor loads exist only under their sensor capability
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        match SensorKind::from_capability(capability) {
+            Some(kind) => {
+                let purged = self.sensor_signals.remove(&kind).map_or(0, |_| 1);
+                info!("EmotionEstimator::on_consent_revoked: Purged {} {} signals", purged, capability);
+                Ok((DataDisposition::Purged, purged))
+            }
+            None => Ok((DataDisposition::NotAffected, 0)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,6 +333,33 @@ mod tests {
         assert!(!estimate.signals.is_empty());
     }
 
+    #[test]
+    fn test_sensor_readings_raise_stress() {
+        let mut estimator = EmotionEstimator::new();
+        let reading = |kind: SensorKind, value: f64| SensorReading { sensor: "test".to_string(), kind, value, timestamp: 10 };
+        estimator.ingest_sensor_readings(&[reading(SensorKind::HeartRate, 110.0), reading(SensorKind::HeartRateVariability, 15.0)]);
+        
+        let estimate = estimator.estimate_from_events(&[]);
+        assert!(estimate.signals.contains(&"Elevated heart rate".to_string()));
+        assert!(estimate.signals.contains(&"Low heart-rate variability".to_string()));
+        assert_eq!(estimate.emotional_state, EmotionalState::Fatigued);
+
+        assert_eq!(estimator.on_consent_revoked(SensorKind::HeartRate.capability()).unwrap(), (DataDisposition::Purged, 1));
+        let estimate = estimator.estimate_from_events(&[]);
+        assert!(!estimate.signals.contains(&"Elevated heart rate".to_string()));
+        assert!(estimate.signals.contains(&"Low heart-rate variability".to_string()));
+    }
+
+    #[test]
+    fn test_stale_sensor_readings_age_out() {
+        let mut estimator = EmotionEstimator::new();
+        estimator.ingest_sensor_readings(&[SensorReading { sensor: "test".to_string(), kind: SensorKind::HeartRate, value: 110.0, timestamp: 10 }]);
+        let event = |timestamp: i64| OSEvent { event_type: OSEventType::AppLaunch, app_name: "IDE".to_string(), window_title: None, timestamp, metadata: HashMap::new() };
+
+        assert!(estimator.estimate_from_events(&[event(10 + SENSOR_SIGNAL_MAX_AGE_SECS)]).signals.contains(&"Elevated heart rate".to_string()));
+        assert!(!estimator.estimate_from_events(&[event(11 + SENSOR_SIGNAL_MAX_AGE_SECS)]).signals.contains(&"Elevated heart rate".to_string()));
+    }
+
     #[test]
     fn test_mood_adaptive_focus_mode() {
         let mut focus_mode = MoodAdaptiveFocusMode::new();
@@ -205,5 +372,23 @@ mod tests {
         assert!(adjustments.enable_zen_mode);
         assert!(adjustments.suggest_break);
     }
+
+    #[test]
+    fn test_focus_mode_enables_shared_dnd() {
+        let attention = AttentionService::new();
+        let mut focus_mode = MoodAdaptiveFocusMode::with_attention(attention.clone());
+        let mut metrics = HashMap::new();
+        metrics.insert("focus_duration_min".to_string(), 90.0);
+        
+        focus_mode.update_focus_mode(&metrics);
+        assert!(attention.current().dnd_enabled);
+        
+        // Leaving focus must be sustained before DND is released
+        focus_mode.update_focus_mode(&HashMap::new());
+        assert!(attention.current().dnd_enabled);
+        focus_mode.update_focus_mode(&HashMap::new());
+        focus_mode.update_focus_mode(&HashMap::new());
+        assert!(!attention.current().dnd_enabled);
+    }
 }
 
@@ -4,6 +4,10 @@
 
 use crate::types::*;
 use crate::emotion::EmotionEstimator;
+use crate::attention::{AttentionService, InterruptionPriority};
+use crate::cache::{BoundedLog, CacheStats, InstrumentedCache};
+use crate::safety_filter::SafetyFilter;
+use crate::victory::{VictoryEvent, VictoryEventKind, VictoryListener};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -16,6 +20,7 @@ pub struct MotivationalMessage {
     pub message: String,
     pub message_type: MessageType,
     pub emotional_state: EmotionalState,
+    pub provenance: Provenance,
     pub created_at: i64,
 }
 
@@ -37,25 +42,115 @@ pub struct StressIntervention {
     pub break_suggestion: Option<String>,
 }
 
+/// Achievement celebration settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CelebrationConfig {
+    pub enabled: bool, // User opt-out
+    pub cooldown_secs: i64,
+    pub templates: HashMap<VictoryEventKind, String>, // "{value}" is replaced by the milestone value
+}
+
+impl Default for CelebrationConfig {
+    fn default() -> Self {
+        let mut templates = HashMap::new();
+        templates.insert(VictoryEventKind::VictoryCount, "Victory #{value}! Small wins add up.".to_string());
+        templates.insert(VictoryEventKind::TimeSaved, "You've saved {value} minutes so far. That's time back for what matters.".to_string());
+        templates.insert(VictoryEventKind::Streak, "{value}-day streak! Consistency is your superpower.".to_string());
+        Self {
+            enabled: true,
+            cooldown_secs: 3600,
+            templates,
+        }
+    }
+}
+
+/// Delivered messages and interventions kept in memory
+const HISTORY_CAPACITY: usize = 500;
+
 /// Emotional co-pilot
 /// Source: Athenos_AI_Strategy.md#L124
 pub struct EmotionalCoPilot {
     emotion_estimator: EmotionEstimator,
-    messages: Vec<MotivationalMessage>,
-    stress_interventions: Vec<StressIntervention>,
+    messages: BoundedLog<MotivationalMessage>,
+    stress_interventions: BoundedLog<StressIntervention>,
+    attention: AttentionService,
+    safety_filter: SafetyFilter,
+    celebration: CelebrationConfig,
+    last_celebration_at: Option<i64>,
 }
 
 impl EmotionalCoPilot {
     /// Create new emotional co-pilot
     pub fn new() -> Self {
+        Self::with_attention(AttentionService::new())
+    }
+
+    /// Create emotional co-pilot sharing an attention service
+    pub fn with_attention(attention: AttentionService) -> Self {
         info!("EmotionalCoPilot::new: Creating emotional co-pilot");
         Self {
             emotion_estimator: EmotionEstimator::new(),
-            messages: Vec::new(),
-            stress_interventions: Vec::new(),
+            messages: BoundedLog::new("copilot.messages", HISTORY_CAPACITY),
+            stress_interventions: BoundedLog::new("copilot.stress_interventions", HISTORY_CAPACITY),
+            attention,
+            safety_filter: SafetyFilter::new(),
+            celebration: CelebrationConfig::default(),
+            last_celebration_at: None,
         }
     }
 
+    /// Replace celebration templates, cooldown and opt-out
+    pub fn set_celebration_config(&mut self, config: CelebrationConfig) {
+        self.celebration = config;
+    }
+
+    /// Opt in to or out of achievement celebrations
+    pub fn set_celebrations_enabled(&mut self, enabled: bool) {
+        info!("EmotionalCoPilot::set_celebrations_enabled: {}", enabled);
+        self.celebration.enabled = enabled;
+    }
+
+    /// Celebrate a victory milestone or streak
+    /// Skipped when opted out, within the cooldown, or when the user may not be interrupted
+    pub fn celebrate(&mut self, event: &VictoryEvent) -> Option<MotivationalMessage> {
+        if !self.celebration.enabled {
+            return None;
+        }
+        if let Some(last) = self.last_celebration_at {
+            if event.at - last < self.celebration.cooldown_secs {
+                info!("EmotionalCoPilot::celebrate: {:?} within cooldown, skipped", event.kind);
+                return None;
+            }
+        }
+        if !self.attention.should_interrupt(InterruptionPriority::Low) {
+            info!("EmotionalCoPilot::celebrate: Suppressed ({:?})", self.attention.current().state);
+            return None;
+        }
+        let template = self.celebration.templates.get(&event.kind)?;
+
+        let message = MotivationalMessage {
+            id: format!("msg_{}", event.at),
+            message: template.replace("{value}", &format!("{}", event.value.round() as i64)),
+            message_type: MessageType::AchievementCelebration,
+            emotional_state: EmotionalState::Calm,
+            provenance: Provenance {
+                triggering_pattern: format!("Victory milestone {:?} reached ({})", event.kind, event.value),
+                data_used: vec!["victory_stream".to_string()],
+                confidence: Confidence::High,
+                consent_scopes: Vec::new(),
+            },
+            created_at: event.at,
+        };
+        self.last_celebration_at = Some(event.at);
+        self.messages.push(message.clone());
+        Some(message)
+    }
+
+    /// Share a safety filter (e.g. one that knows the user's window titles)
+    pub fn set_safety_filter(&mut self, safety_filter: SafetyFilter) {
+        self.safety_filter = safety_filter;
+    }
+
     /// Detect stress and provide mitigation
     /// Source: Athenos_AI_Strategy.md#L124
     pub fn mitigate_stress(&mut self, metrics: &HashMap<String, f64>) -> Option<StressIntervention> {
@@ -83,7 +178,21 @@ impl EmotionalCoPilot {
     pub fn generate_motivational_message(&mut self, emotional_state: EmotionalState, context: &str) -> MotivationalMessage {
         info!("EmotionalCoPilot::generate_motivational_message: Generating message for {:?}", emotional_state);
         
-        let (message, message_type) = match emotional_state {
+        let (message, message_type) = Self::template_message(&emotional_state);
+        self.record_message(message, message_type, emotional_state, context)
+    }
+
+    /// Surface generated (LLM) message text; unsafe text is replaced by the template message
+    pub fn surface_generated_message(&mut self, emotional_state: EmotionalState, context: &str, generated: &str) -> MotivationalMessage {
+        info!("EmotionalCoPilot::surface_generated_message: Filtering generated message for {:?}", emotional_state);
+        
+        let (template, message_type) = Self::template_message(&emotional_state);
+        let message = self.safety_filter.filter("emotional_copilot", generated, &template);
+        self.record_message(message, message_type, emotional_state, context)
+    }
+
+    fn template_message(emotional_state: &EmotionalState) -> (String, MessageType) {
+        match emotional_state {
             EmotionalState::Stressed => (
                 "You're doing great work. Remember to take breaks and breathe. Your well-being matters.".to_string(),
                 MessageType::StressMitigation,
@@ -104,12 +213,20 @@ impl EmotionalCoPilot {
                 "Keep going. Every step forward counts.".to_string(),
                 MessageType::Encouragement,
             ),
-        };
-        
+        }
+    }
+
+    fn record_message(&mut self, message: String, message_type: MessageType, emotional_state: EmotionalState, context: &str) -> MotivationalMessage {
         let motivational_msg = MotivationalMessage {
             id: format!("msg_{}", chrono::Utc::now().timestamp()),
             message,
             message_type,
+            provenance: Provenance {
+                triggering_pattern: format!("Estimated emotional state {:?} during {}", emotional_state, context),
+                data_used: vec!["typing_speed_decrease_pct".to_string(), "error_rate".to_string(), "context_switch_count".to_string()],
+                confidence: Confidence::Medium,
+                consent_scopes: vec!["emotion_detection".to_string()],
+            },
             emotional_state,
             created_at: chrono::Utc::now().timestamp(),
         };
@@ -118,10 +235,42 @@ impl EmotionalCoPilot {
         motivational_msg
     }
 
+    /// Generate motivational message only if it may interrupt the user now
+    /// Stress mitigation outranks other message types when attention is constrained
+    pub fn deliver_motivational_message(&mut self, emotional_state: EmotionalState, context: &str) -> Option<MotivationalMessage> {
+        let priority = if emotional_state == EmotionalState::Stressed {
+            InterruptionPriority::Normal
+        } else {
+            InterruptionPriority::Low
+        };
+        
+        if !self.attention.should_interrupt(priority) {
+            info!("EmotionalCoPilot::deliver_motivational_message: Suppressed ({:?})", self.attention.current().state);
+            return None;
+        }
+        
+        Some(self.generate_motivational_message(emotional_state, context))
+    }
+
     /// Get recent messages
     pub fn get_recent_messages(&self, limit: usize) -> Vec<&MotivationalMessage> {
-        let start = self.messages.len().saturating_sub(limit);
-        self.messages[start..].iter().collect()
+        self.messages.recent(limit).collect()
+    }
+
+    /// Cache gauges for analytics
+    pub fn cache_stats(&self) -> Vec<CacheStats> {
+        vec![self.messages.cache_stats(), self.stress_interventions.cache_stats()]
+    }
+}
+
+impl VictoryListener for EmotionalCoPilot {
+    fn listener_name(&self) -> &str {
+        "emotional_copilot"
+    }
+
+    fn on_victory_event(&mut self, event: &VictoryEvent) -> Result<(), String> {
+        self.celebrate(event);
+        Ok(())
     }
 }
 
@@ -163,5 +312,71 @@ mod tests {
         assert_eq!(message.emotional_state, EmotionalState::Focused);
         assert!(!message.message.is_empty());
     }
-}
 
+    #[test]
+    fn test_messages_suppressed_while_in_meeting() {
+        let attention = AttentionService::new();
+        let mut copilot = EmotionalCoPilot::with_attention(attention.clone());
+        
+        attention.set_state(crate::attention::AttentionState::InMeeting, "calendar");
+        assert!(copilot.deliver_motivational_message(EmotionalState::Fatigued, "meeting").is_none());
+        
+        attention.set_state(crate::attention::AttentionState::Available, "calendar");
+        assert!(copilot.deliver_motivational_message(EmotionalState::Fatigued, "coding").is_some());
+    }
+
+    #[test]
+    fn test_generated_message_filtered() {
+        let filter = SafetyFilter::new();
+        filter.register_window_title("Q3 layoffs plan - Confidential - Word", "Word");
+        let mut copilot = EmotionalCoPilot::new();
+        copilot.set_safety_filter(filter.clone());
+        
+        let safe = copilot.surface_generated_message(EmotionalState::Focused, "coding", "Nice streak, keep it up.");
+        assert_eq!(safe.message, "Nice streak, keep it up.");
+        
+        let unsafe_msg = copilot.surface_generated_message(EmotionalState::Stressed, "writing", "Working on q3 layoffs plan is stressful.");
+        assert_eq!(unsafe_msg.message_type, MessageType::StressMitigation);
+        assert!(unsafe_msg.message.starts_with("You're doing great work"));
+        assert_eq!(filter.get_violations().len(), 1);
+    }
+
+    #[test]
+    fn test_victory_milestones_celebrated_with_cooldown() {
+        use crate::victory::{VictoryCategory, VictoryMetric, VictoryStream};
+        let mut stream = VictoryStream::new();
+        let mut copilot = EmotionalCoPilot::new();
+        let day = 20_000 * 86400;
+        
+        // First victory is a milestone; three consecutive days make a streak
+        for d in 0..3 {
+            stream.record_victory_at("Focus".to_string(), "Deep work".to_string(), VictoryMetric::FocusIncrease, 30.0, VictoryCategory::Focus, day + d * 86400);
+            stream.publish(&mut [&mut copilot]);
+        }
+        let celebrations: Vec<String> = copilot
+            .get_recent_messages(10)
+            .iter()
+            .filter(|m| m.message_type == MessageType::AchievementCelebration)
+            .map(|m| m.message.clone())
+            .collect();
+        assert_eq!(celebrations, vec!["Victory #1! Small wins add up.", "3-day streak! Consistency is your superpower."]);
+        
+        // Time-saved milestone inside the cooldown is not celebrated
+        stream.record_victory_at("Saved".to_string(), "Macro".to_string(), VictoryMetric::TimeSaved, 60.0, VictoryCategory::Productivity, day + 2 * 86400 + 60);
+        assert_eq!(stream.publish(&mut [&mut copilot]), 1);
+        assert_eq!(copilot.get_recent_messages(10).len(), 2);
+    }
+
+    #[test]
+    fn test_celebrations_opt_out() {
+        let mut copilot = EmotionalCoPilot::new();
+        copilot.set_celebrations_enabled(false);
+        let event = VictoryEvent { kind: VictoryEventKind::VictoryCount, value: 10.0, victory_id: "victory_1".to_string(), at: 1 };
+        assert!(copilot.celebrate(&event).is_none());
+        
+        copilot.set_celebrations_enabled(true);
+        let message = copilot.celebrate(&event).unwrap();
+        assert_eq!(message.message, "Victory #10! Small wins add up.");
+        assert_eq!(message.provenance.data_used, vec!["victory_stream"]);
+    }
+}
@@ -0,0 +1,275 @@
+/// Phase: D | Step: 5 | Source: Athenos_AI_Strategy.md#L136
+/// Team Focus Heatmap
+/// Differentially private weekly heatmap of collective focus, with best focus windows and meeting-safe zones
+
+use crate::compliance::DifferentialPrivacy;
+use crate::focus_session::FocusSession;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Hour-of-week slots (Monday 00:00 UTC first)
+pub const HOURS_PER_WEEK: usize = 7 * 24;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Hour-of-week slot for a timestamp (1970-01-01 was a Thursday)
+fn slot_of(timestamp: i64) -> usize {
+    let weekday = (timestamp.div_euclid(86400) + 3).rem_euclid(7) as usize;
+    let hour = (timestamp.rem_euclid(86400) / 3600) as usize;
+    weekday * 24 + hour
+}
+
+/// One member's focus profile; each slot is scored 0.0 to 1.0 relative to their own busiest focus hour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberFocusProfile {
+    pub user_id: String,
+    pub scores: Vec<f64>, // HOURS_PER_WEEK slots
+}
+
+impl MemberFocusProfile {
+    /// Build a profile from completed focus sessions, spreading each session's focus time over the hours it spanned
+    pub fn from_sessions(user_id: &str, sessions: &[FocusSession]) -> Self {
+        let mut minutes = vec![0.0; HOURS_PER_WEEK];
+        for session in sessions {
+            let Some(ended_at) = session.ended_at else {
+                continue;
+            };
+            let span = ended_at - session.started_at;
+            if span <= 0 || session.focus_secs <= 0 {
+                continue;
+            }
+            let focus_share = session.focus_secs.min(span) as f64 / span as f64;
+            let mut at = session.started_at;
+            while at < ended_at {
+                let hour_end = (at - at.rem_euclid(3600) + 3600).min(ended_at);
+                minutes[slot_of(at)] += (hour_end - at) as f64 / 60.0 * focus_share;
+                at = hour_end;
+            }
+        }
+        let peak = minutes.iter().cloned().fold(0.0, f64::max);
+        let scores = if peak > 0.0 { minutes.iter().map(|m| m / peak).collect() } else { minutes };
+        Self { user_id: user_id.to_string(), scores }
+    }
+
+    fn score(&self, slot: usize) -> f64 {
+        self.scores.get(slot).copied().unwrap_or(0.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Heatmap thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeatmapConfig {
+    pub min_members: usize,     // Smaller teams get no heatmap; one member would be identifiable
+    pub focus_threshold: f64,   // Team score at or above which an hour is a focus window
+    pub safe_threshold: f64,    // Team score at or below which an hour is meeting-safe
+    pub workday_hours: (u8, u8), // Meeting-safe zones are only proposed inside these UTC hours
+}
+
+impl Default for HeatmapConfig {
+    fn default() -> Self {
+        Self {
+            min_members: 5,
+            focus_threshold: 0.5,
+            safe_threshold: 0.25,
+            workday_hours: (9, 18),
+        }
+    }
+}
+
+/// Contiguous run of hours on one weekday
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HeatmapWindow {
+    pub weekday: usize, // 0 = Monday
+    pub start_hour: u8,
+    pub end_hour: u8,   // Exclusive
+    pub mean_score: f64,
+}
+
+impl HeatmapWindow {
+    pub fn label(&self) -> String {
+        format!("{} {:02}:00-{:02}:00", WEEKDAYS[self.weekday], self.start_hour, self.end_hour)
+    }
+}
+
+/// Team-level focus heatmap; cells are noisy team means, so no member's profile can be read back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamFocusHeatmap {
+    pub team_id: String,
+    pub members: usize,
+    pub epsilon: f64,
+    pub cells: Vec<f64>, // HOURS_PER_WEEK noisy mean focus scores, 0.0 to 1.0
+    pub generated_at: i64,
+    config: HeatmapConfig,
+}
+
+impl TeamFocusHeatmap {
+    /// Aggregate member profiles under ε-differential privacy
+    /// A member moves every cell's sum by up to 1.0, so the release's L1 sensitivity is HOURS_PER_WEEK and each cell
+    /// gets Laplace noise at that scale; readable heatmaps need larger teams or a larger ε
+    pub fn build(team_id: &str, profiles: &[MemberFocusProfile], privacy: &DifferentialPrivacy, config: HeatmapConfig, now: i64) -> Result<Self, String> {
+        if profiles.len() < config.min_members {
+            return Err(format!(
+                "Team {} has {} focus profiles; at least {} are required for a private heatmap",
+                team_id,
+                profiles.len(),
+                config.min_members
+            ));
+        }
+        info!("TeamFocusHeatmap::build: Aggregating {} profiles for team {} (ε={})", profiles.len(), team_id, privacy.epsilon());
+        let members = profiles.len() as f64;
+        let cells = (0..HOURS_PER_WEEK)
+            .map(|slot| {
+                let sum: f64 = profiles.iter().map(|p| p.score(slot)).sum();
+                (privacy.add_laplace_noise(sum, HOURS_PER_WEEK as f64) / members).clamp(0.0, 1.0)
+            })
+            .collect();
+        Ok(Self {
+            team_id: team_id.to_string(),
+            members: profiles.len(),
+            epsilon: privacy.epsilon(),
+            cells,
+            generated_at: now,
+            config,
+        })
+    }
+
+    /// Team focus score for a weekday (0 = Monday) and UTC hour
+    pub fn score(&self, weekday: usize, hour: u8) -> f64 {
+        self.cells.get(weekday * 24 + hour as usize).copied().unwrap_or(0.0)
+    }
+
+    /// Mean team focus score over the hours a time range touches
+    pub fn mean_score(&self, start: i64, end: i64) -> f64 {
+        let mut total = 0.0;
+        let mut hours = 0;
+        let mut at = start - start.rem_euclid(3600);
+        while at < end {
+            total += self.cells[slot_of(at)];
+            hours += 1;
+            at += 3600;
+        }
+        if hours == 0 { 0.0 } else { total / hours as f64 }
+    }
+
+    fn windows(&self, include: impl Fn(usize, f64) -> bool) -> Vec<HeatmapWindow> {
+        let mut windows = Vec::new();
+        for weekday in 0..7 {
+            let mut run: Option<(u8, Vec<f64>)> = None;
+            for hour in 0..=24u8 {
+                let hit = hour < 24 && include(hour as usize, self.score(weekday, hour));
+                match (&mut run, hit) {
+                    (Some((_, scores)), true) => scores.push(self.score(weekday, hour)),
+                    (None, true) => run = Some((hour, vec![self.score(weekday, hour)])),
+                    (Some(_), false) => {
+                        if let Some((start_hour, scores)) = run.take() {
+                            windows.push(HeatmapWindow {
+                                weekday,
+                                start_hour,
+                                end_hour: hour,
+                                mean_score: scores.iter().sum::<f64>() / scores.len() as f64,
+                            });
+                        }
+                    }
+                    (None, false) => {}
+                }
+            }
+        }
+        windows
+    }
+
+    /// Best collective focus windows, highest mean score first
+    pub fn best_focus_windows(&self, limit: usize) -> Vec<HeatmapWindow> {
+        let mut windows = self.windows(|_, score| score >= self.config.focus_threshold);
+        windows.sort_by(|a, b| b.mean_score.partial_cmp(&a.mean_score).unwrap_or(std::cmp::Ordering::Equal));
+        windows.truncate(limit);
+        windows
+    }
+
+    /// Working-hour windows where few members are typically focused
+    pub fn meeting_safe_zones(&self) -> Vec<HeatmapWindow> {
+        let (workday_start, workday_end) = self.config.workday_hours;
+        self.windows(|hour, score| hour >= workday_start as usize && hour < workday_end as usize && score <= self.config.safe_threshold)
+    }
+
+    /// Plain-text weekday × hour grid for the enterprise report
+    pub fn render(&self) -> String {
+        const SHADES: [char; 5] = [' ', '.', ':', '*', '#'];
+        let mut out = format!("Team {} focus heatmap ({} members, ε={})\n     ", self.team_id, self.members, self.epsilon);
+        out.push_str(&(0..24).map(|h| format!("{:<3}", h)).collect::<String>());
+        out.push('\n');
+        for (weekday, name) in WEEKDAYS.iter().enumerate() {
+            out.push_str(&format!("{}  ", name));
+            for hour in 0..24u8 {
+                let shade = ((self.score(weekday, hour) * 4.0).round() as usize).min(4);
+                out.push_str(&format!(" {} ", SHADES[shade]));
+            }
+            out.push('\n');
+        }
+        let best: Vec<String> = self.best_focus_windows(3).iter().map(|w| w.label()).collect();
+        let safe: Vec<String> = self.meeting_safe_zones().iter().map(|w| w.label()).collect();
+        out.push_str(&format!("Best focus windows: {}\n", if best.is_empty() { "none".to_string() } else { best.join(", ") }));
+        out.push_str(&format!("Meeting-safe zones: {}\n", if safe.is_empty() { "none".to_string() } else { safe.join(", ") }));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::focus_session::{SessionPhase, SessionTrigger};
+
+    const MONDAY: i64 = 1_699_833_600; // 2023-11-13 00:00 UTC
+
+    fn session(start: i64, hours: i64) -> FocusSession {
+        FocusSession {
+            id: format!("s{}", start),
+            trigger: SessionTrigger::Manual,
+            started_at: start,
+            ended_at: Some(start + hours * 3600),
+            phase: SessionPhase::Work,
+            phase_ends_at: None,
+            completed_pomodoros: 0,
+            focus_secs: hours * 3600,
+        }
+    }
+
+    fn profiles(count: usize) -> Vec<MemberFocusProfile> {
+        (0..count)
+            .map(|i| MemberFocusProfile::from_sessions(&format!("user_{}", i), &[session(MONDAY + 9 * 3600, 2), session(MONDAY + 86400 + 9 * 3600, 2)]))
+            .collect()
+    }
+
+    #[test]
+    fn test_member_profile_spreads_sessions_over_hours() {
+        let profile = MemberFocusProfile::from_sessions("alex", &[session(MONDAY + 9 * 3600 + 1800, 2)]);
+        assert_eq!(profile.score(10), 1.0);
+        assert_eq!(profile.score(9), 0.5);
+        assert_eq!(profile.score(11), 0.5);
+        assert_eq!(profile.score(12), 0.0);
+    }
+
+    #[test]
+    fn test_heatmap_windows_and_safe_zones() {
+        let privacy = DifferentialPrivacy::new(1_000_000.0); // Negligible noise for a deterministic check
+        let heatmap = TeamFocusHeatmap::build("alpha", &profiles(5), &privacy, HeatmapConfig::default(), MONDAY).unwrap();
+
+        let best = heatmap.best_focus_windows(5);
+        assert_eq!(best.len(), 2);
+        assert_eq!(best[0].start_hour, 9);
+        assert_eq!(best[0].end_hour, 11);
+        assert!(heatmap.mean_score(MONDAY + 9 * 3600, MONDAY + 10 * 3600) > 0.99);
+
+        let safe = heatmap.meeting_safe_zones();
+        assert!(safe.iter().any(|w| w.label() == "Mon 11:00-18:00"));
+        assert!(safe.iter().all(|w| w.start_hour >= 9 && w.end_hour <= 18));
+        // Monday and Tuesday tie up to noise, so either may rank first
+        let render = heatmap.render();
+        assert!(render.contains("Mon 09:00-11:00") && render.contains("Tue 09:00-11:00"));
+    }
+
+    #[test]
+    fn test_small_teams_refused() {
+        let privacy = DifferentialPrivacy::new(1.0);
+        assert!(TeamFocusHeatmap::build("tiny", &profiles(4), &privacy, HeatmapConfig::default(), MONDAY).is_err());
+    }
+}
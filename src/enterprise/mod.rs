@@ -4,9 +4,14 @@
 
 use crate::types::*;
 use crate::analytics::AnalyticsAggregator;
+use crate::compliance::DifferentialPrivacy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tracing::info;
+use std::collections::{BTreeSet, HashMap};
+use tracing::{info, warn};
+
+pub mod focus_heatmap;
+
+use focus_heatmap::{HeatmapConfig, MemberFocusProfile, TeamFocusHeatmap};
 
 /// Team member
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +33,137 @@ pub struct CompliancePolicy {
     pub rules: Vec<String>,
 }
 
+/// Compliance policy violation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub policy_id: String,
+    pub policy_name: String,
+    pub description: String,
+    pub detected_at: i64,
+}
+
+/// Approval mode for high-risk automations
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApprovalMode {
+    SingleUser,
+    TwoPerson, // Distinct user + admin approvers required for High-risk actions
+}
+
+/// Approver role in a two-person approval chain
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApproverRole {
+    User,
+    Admin,
+    Integration, // Third-party API key: recorded, but never stands in for the user or an admin
+}
+
+/// Single approval in an approval chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    pub approver_id: String,
+    pub role: ApproverRole,
+    pub approved_at: i64,
+}
+
+/// Seat-license enforcement level
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseEnforcement {
+    Compliant,
+    Warn,     // Usage at or above the warning threshold
+    Grace,    // Over the seat limit, within the grace period
+    Disabled, // Grace expired: new seats and devices are refused
+}
+
+/// Tenant seat-license policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicensePolicy {
+    pub seat_limit: usize,
+    pub max_devices_per_seat: usize,
+    pub warn_at_pct: f64, // Share of seat_limit that triggers a warning
+    pub grace_days: i64,
+    pub disable_after_grace: bool, // false = stay in grace (warn-only tenants)
+    pub inactive_after_days: i64,  // Seats idle this long are reported as inactive
+}
+
+impl Default for LicensePolicy {
+    fn default() -> Self {
+        Self {
+            seat_limit: 25,
+            max_devices_per_seat: 3,
+            warn_at_pct: 0.9,
+            grace_days: 14,
+            disable_after_grace: true,
+            inactive_after_days: 30,
+        }
+    }
+}
+
+/// Assigned license seat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Seat {
+    pub user_id: String,
+    pub assigned_at: i64,
+    pub devices: HashMap<String, i64>, // device_id -> last active
+    pub last_active_at: Option<i64>,
+}
+
+/// Current license state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseStatus {
+    pub seat_limit: usize,
+    pub assigned_seats: usize,
+    pub active_seats: usize,
+    pub active_devices: usize,
+    pub enforcement: LicenseEnforcement,
+    pub over_limit_since: Option<i64>,
+}
+
+/// Enforcement level change, for admin notification hooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseEnforcementEvent {
+    pub at: i64,
+    pub from: LicenseEnforcement,
+    pub to: LicenseEnforcement,
+    pub assigned_seats: usize,
+    pub seat_limit: usize,
+}
+
+/// Per-month usage accumulator
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MonthlyUsage {
+    active_users: BTreeSet<String>,
+    active_devices: BTreeSet<String>,
+    peak_assigned_seats: usize,
+}
+
+/// Monthly seat usage report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseUsageReport {
+    pub month: String, // YYYY-MM
+    pub seat_limit: usize,
+    pub assigned_seats: usize,
+    pub peak_assigned_seats: usize,
+    pub active_users: usize,
+    pub active_devices: usize,
+    pub inactive_seats: Vec<String>, // Assigned but idle for inactive_after_days
+    pub enforcement: LicenseEnforcement,
+}
+
+/// Tenant policy set distributed by the aggregation server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyBundle {
+    #[serde(default)]
+    pub version: u64, // Assigned by the server; clients skip bundles they already applied
+    pub approval_mode: ApprovalMode,
+    pub global_template_sharing: bool,
+    pub license_policy: LicensePolicy,
+    pub compliance_policies: Vec<CompliancePolicy>,
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
 /// Enterprise admin console
 /// Source: Athenos_AI_Strategy.md#L136
 pub struct EnterpriseAdminConsole {
@@ -35,6 +171,15 @@ pub struct EnterpriseAdminConsole {
     compliance_policies: HashMap<String, CompliancePolicy>,
     analytics: AnalyticsAggregator,
     policy_controls: HashMap<String, bool>, // policy_id -> enabled
+    violations: Vec<PolicyViolation>,
+    approval_mode: ApprovalMode,
+    global_template_sharing: bool, // Federated templates may leave the tenant
+    license_policy: LicensePolicy,
+    seats: HashMap<String, Seat>,
+    over_limit_since: Option<i64>,
+    enforcement: LicenseEnforcement,
+    enforcement_log: Vec<LicenseEnforcementEvent>,
+    monthly_usage: HashMap<String, MonthlyUsage>,
 }
 
 impl EnterpriseAdminConsole {
@@ -46,6 +191,15 @@ impl EnterpriseAdminConsole {
             compliance_policies: HashMap::new(),
             analytics: AnalyticsAggregator::new(),
             policy_controls: HashMap::new(),
+            violations: Vec::new(),
+            approval_mode: ApprovalMode::SingleUser,
+            global_template_sharing: false,
+            license_policy: LicensePolicy::default(),
+            seats: HashMap::new(),
+            over_limit_since: None,
+            enforcement: LicenseEnforcement::Compliant,
+            enforcement_log: Vec::new(),
+            monthly_usage: HashMap::new(),
         }
     }
 
@@ -89,6 +243,213 @@ impl EnterpriseAdminConsole {
         }
     }
 
+    /// Record a violation of an enabled compliance policy
+    pub fn record_policy_violation(&mut self, policy_id: &str, description: String) -> Result<PolicyViolation, String> {
+        let policy = self.compliance_policies
+            .get(policy_id)
+            .ok_or(format!("Unknown policy: {}", policy_id))?;
+        if !policy.enabled {
+            return Err(format!("Policy {} is disabled", policy_id));
+        }
+        
+        info!("EnterpriseAdminConsole::record_policy_violation: Violation of {}", policy_id);
+        let violation = PolicyViolation {
+            policy_id: policy_id.to_string(),
+            policy_name: policy.name.clone(),
+            description,
+            detected_at: chrono::Utc::now().timestamp(),
+        };
+        self.violations.push(violation.clone());
+        Ok(violation)
+    }
+
+    /// Get recorded policy violations
+    pub fn get_policy_violations(&self) -> &[PolicyViolation] {
+        &self.violations
+    }
+
+    /// Set approval mode for high-risk automations
+    pub fn set_approval_mode(&mut self, mode: ApprovalMode) {
+        info!("EnterpriseAdminConsole::set_approval_mode: Setting approval mode to {:?}", mode);
+        self.approval_mode = mode;
+    }
+
+    /// Get approval mode for high-risk automations
+    pub fn get_approval_mode(&self) -> ApprovalMode {
+        self.approval_mode
+    }
+
+    /// Permit or forbid sharing tenant-aggregated federated templates globally
+    pub fn set_global_template_sharing(&mut self, allowed: bool) {
+        info!("EnterpriseAdminConsole::set_global_template_sharing: {}", allowed);
+        self.global_template_sharing = allowed;
+    }
+
+    /// Whether tenant-aggregated federated templates may be shared globally
+    pub fn allows_global_template_sharing(&self) -> bool {
+        self.global_template_sharing
+    }
+
+    /// Set the tenant seat-license policy
+    pub fn set_license_policy(&mut self, policy: LicensePolicy) {
+        info!("EnterpriseAdminConsole::set_license_policy: {} seats, {} grace days", policy.seat_limit, policy.grace_days);
+        self.license_policy = policy;
+    }
+
+    /// Apply a distributed policy bundle; policies missing from the bundle are left as they are
+    pub fn apply_policy_bundle(&mut self, bundle: &PolicyBundle) {
+        info!("EnterpriseAdminConsole::apply_policy_bundle: Applying policy bundle v{}", bundle.version);
+        self.set_approval_mode(bundle.approval_mode);
+        self.set_global_template_sharing(bundle.global_template_sharing);
+        self.set_license_policy(bundle.license_policy.clone());
+        for policy in &bundle.compliance_policies {
+            self.add_compliance_policy(policy.clone());
+        }
+    }
+
+    /// Assign a seat; over-limit assignments are allowed until enforcement disables them
+    pub fn assign_seat(&mut self, user_id: &str, now: i64) -> Result<(), String> {
+        if self.seats.contains_key(user_id) {
+            return Ok(());
+        }
+        self.evaluate_license(now);
+        if self.enforcement == LicenseEnforcement::Disabled {
+            return Err("Seat limit exceeded and grace period expired".to_string());
+        }
+        info!("EnterpriseAdminConsole::assign_seat: Assigning seat to {}", user_id);
+        self.seats.insert(
+            user_id.to_string(),
+            Seat { user_id: user_id.to_string(), assigned_at: now, devices: HashMap::new(), last_active_at: None },
+        );
+        let usage = self.monthly_usage.entry(Self::month_of(now)).or_default();
+        usage.peak_assigned_seats = usage.peak_assigned_seats.max(self.seats.len());
+        self.evaluate_license(now);
+        Ok(())
+    }
+
+    /// Release a seat
+    pub fn revoke_seat(&mut self, user_id: &str, now: i64) -> Result<(), String> {
+        self.seats.remove(user_id).ok_or(format!("No seat assigned to {}", user_id))?;
+        info!("EnterpriseAdminConsole::revoke_seat: Released seat of {}", user_id);
+        self.evaluate_license(now);
+        Ok(())
+    }
+
+    /// Record activity from a device; new devices are refused beyond the per-seat limit or while disabled
+    pub fn record_device_activity(&mut self, user_id: &str, device_id: &str, now: i64) -> Result<(), String> {
+        let enforcement = self.enforcement;
+        let max_devices = self.license_policy.max_devices_per_seat;
+        let seat = self.seats.get_mut(user_id).ok_or(format!("No seat assigned to {}", user_id))?;
+        if !seat.devices.contains_key(device_id) {
+            if enforcement == LicenseEnforcement::Disabled {
+                return Err("Seat limit exceeded and grace period expired".to_string());
+            }
+            if seat.devices.len() >= max_devices {
+                return Err(format!("Seat {} already has {} active devices", user_id, max_devices));
+            }
+        }
+        seat.devices.insert(device_id.to_string(), now);
+        seat.last_active_at = Some(now);
+
+        let usage = self.monthly_usage.entry(Self::month_of(now)).or_default();
+        usage.active_users.insert(user_id.to_string());
+        usage.active_devices.insert(format!("{}/{}", user_id, device_id));
+        Ok(())
+    }
+
+    /// Re-evaluate enforcement (warn -> grace -> disable); returns the transition if the level changed
+    pub fn evaluate_license(&mut self, now: i64) -> Option<LicenseEnforcementEvent> {
+        let policy = &self.license_policy;
+        let assigned = self.seats.len();
+        if assigned > policy.seat_limit {
+            self.over_limit_since.get_or_insert(now);
+        } else {
+            self.over_limit_since = None;
+        }
+
+        let level = match self.over_limit_since {
+            Some(since) if policy.disable_after_grace && now - since >= policy.grace_days * 86400 => LicenseEnforcement::Disabled,
+            Some(_) => LicenseEnforcement::Grace,
+            None if assigned as f64 >= policy.seat_limit as f64 * policy.warn_at_pct => LicenseEnforcement::Warn,
+            None => LicenseEnforcement::Compliant,
+        };
+        if level == self.enforcement {
+            return None;
+        }
+
+        warn!("EnterpriseAdminConsole::evaluate_license: {:?} -> {:?} ({}/{} seats)", self.enforcement, level, assigned, policy.seat_limit);
+        let event = LicenseEnforcementEvent { at: now, from: self.enforcement, to: level, assigned_seats: assigned, seat_limit: policy.seat_limit };
+        self.enforcement = level;
+        self.enforcement_log.push(event.clone());
+        Some(event)
+    }
+
+    /// Enforcement level changes, oldest first
+    pub fn get_enforcement_log(&self) -> &[LicenseEnforcementEvent] {
+        &self.enforcement_log
+    }
+
+    /// Current license state
+    pub fn get_license_status(&self, now: i64) -> LicenseStatus {
+        let cutoff = now - self.license_policy.inactive_after_days * 86400;
+        LicenseStatus {
+            seat_limit: self.license_policy.seat_limit,
+            assigned_seats: self.seats.len(),
+            active_seats: self.seats.values().filter(|s| s.last_active_at.map(|at| at >= cutoff).unwrap_or(false)).count(),
+            active_devices: self.seats.values().map(|s| s.devices.values().filter(|at| **at >= cutoff).count()).sum(),
+            enforcement: self.enforcement,
+            over_limit_since: self.over_limit_since,
+        }
+    }
+
+    /// Seat usage report for a month (YYYY-MM)
+    pub fn get_monthly_usage_report(&self, month: &str, now: i64) -> LicenseUsageReport {
+        let usage = self.monthly_usage.get(month).cloned().unwrap_or_default();
+        let cutoff = now - self.license_policy.inactive_after_days * 86400;
+        let mut inactive_seats: Vec<String> = self
+            .seats
+            .values()
+            .filter(|s| s.last_active_at.unwrap_or(s.assigned_at) < cutoff)
+            .map(|s| s.user_id.clone())
+            .collect();
+        inactive_seats.sort();
+
+        LicenseUsageReport {
+            month: month.to_string(),
+            seat_limit: self.license_policy.seat_limit,
+            assigned_seats: self.seats.len(),
+            peak_assigned_seats: usage.peak_assigned_seats,
+            active_users: usage.active_users.len(),
+            active_devices: usage.active_devices.len(),
+            inactive_seats,
+            enforcement: self.enforcement,
+        }
+    }
+
+    fn month_of(timestamp: i64) -> String {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|dt| dt.format("%Y-%m").to_string())
+            .unwrap_or_default()
+    }
+
+    /// DP-protected focus heatmap for a team; profiles from non-members are ignored
+    pub fn get_team_focus_heatmap(
+        &self,
+        team_id: &str,
+        profiles: &[MemberFocusProfile],
+        privacy: &DifferentialPrivacy,
+        config: HeatmapConfig,
+        now: i64,
+    ) -> Result<TeamFocusHeatmap, String> {
+        let members = self.teams.get(team_id).ok_or(format!("Unknown team: {}", team_id))?;
+        let team_profiles: Vec<MemberFocusProfile> = profiles
+            .iter()
+            .filter(|p| members.iter().any(|m| m.user_id == p.user_id))
+            .cloned()
+            .collect();
+        TeamFocusHeatmap::build(team_id, &team_profiles, privacy, config, now)
+    }
+
     /// Get compliance report
     pub fn get_compliance_report(&self) -> ComplianceReport {
         let total_policies = self.compliance_policies.len();
@@ -171,5 +532,91 @@ mod tests {
         assert_eq!(report.total_policies, 1);
         assert_eq!(report.enabled_policies, 1);
     }
-}
 
+    #[test]
+    fn test_record_policy_violation() {
+        let mut console = EnterpriseAdminConsole::new();
+        console.add_compliance_policy(CompliancePolicy {
+            id: "policy_002".to_string(),
+            name: "No Cloud Export".to_string(),
+            description: "Data must stay on-device".to_string(),
+            enabled: true,
+            rules: vec!["no_cloud_export".to_string()],
+        });
+        
+        let violation = console.record_policy_violation("policy_002", "Export attempted".to_string()).unwrap();
+        assert_eq!(violation.policy_name, "No Cloud Export");
+        assert!(console.record_policy_violation("unknown", "x".to_string()).is_err());
+        
+        console.set_policy_control("policy_002", false);
+        assert!(console.record_policy_violation("policy_002", "x".to_string()).is_err());
+        assert_eq!(console.get_policy_violations().len(), 1);
+    }
+
+    #[test]
+    fn test_seat_enforcement_warn_grace_disable() {
+        let mut console = EnterpriseAdminConsole::new();
+        console.set_license_policy(LicensePolicy { seat_limit: 2, warn_at_pct: 1.0, grace_days: 7, ..LicensePolicy::default() });
+        let day = 86400;
+        
+        console.assign_seat("alice", 0).unwrap();
+        console.assign_seat("bob", 0).unwrap();
+        assert_eq!(console.get_license_status(0).enforcement, LicenseEnforcement::Warn);
+        
+        console.assign_seat("carol", day).unwrap();
+        assert_eq!(console.get_license_status(day).enforcement, LicenseEnforcement::Grace);
+        
+        assert!(console.assign_seat("dave", 8 * day).is_err());
+        assert!(console.record_device_activity("alice", "laptop", 8 * day).is_err());
+        let levels: Vec<LicenseEnforcement> = console.get_enforcement_log().iter().map(|e| e.to).collect();
+        assert_eq!(levels, vec![LicenseEnforcement::Warn, LicenseEnforcement::Grace, LicenseEnforcement::Disabled]);
+        
+        console.revoke_seat("carol", 9 * day).unwrap();
+        assert_eq!(console.get_license_status(9 * day).enforcement, LicenseEnforcement::Warn);
+        console.record_device_activity("alice", "laptop", 9 * day).unwrap();
+    }
+
+    #[test]
+    fn test_device_limit_and_monthly_usage_report() {
+        let mut console = EnterpriseAdminConsole::new();
+        console.set_license_policy(LicensePolicy { max_devices_per_seat: 2, ..LicensePolicy::default() });
+        let jan = 1_704_067_200; // 2024-01-01
+        console.assign_seat("alice", jan).unwrap();
+        console.assign_seat("bob", jan).unwrap();
+        
+        console.record_device_activity("alice", "laptop", jan + 3600).unwrap();
+        console.record_device_activity("alice", "desktop", jan + 7200).unwrap();
+        assert!(console.record_device_activity("alice", "tablet", jan + 7200).is_err());
+        assert!(console.record_device_activity("mallory", "laptop", jan).is_err());
+        
+        let report = console.get_monthly_usage_report("2024-01", jan + 40 * 86400);
+        assert_eq!((report.assigned_seats, report.peak_assigned_seats), (2, 2));
+        assert_eq!((report.active_users, report.active_devices), (1, 2));
+        assert_eq!(report.inactive_seats, vec!["alice", "bob"]);
+        
+        let status = console.get_license_status(jan + 86400);
+        assert_eq!((status.active_seats, status.active_devices), (1, 2));
+    }
+
+    #[test]
+    fn test_team_focus_heatmap_uses_members_only() {
+        let mut console = EnterpriseAdminConsole::new();
+        let mut profiles = Vec::new();
+        for i in 0..5 {
+            let user_id = format!("user_{}", i);
+            profiles.push(MemberFocusProfile { user_id: user_id.clone(), scores: vec![1.0; focus_heatmap::HOURS_PER_WEEK] });
+            if i < 4 {
+                console.add_team_member("alpha".to_string(), TeamMember { user_id, name: String::new(), role: "Developer".to_string(), joined_at: 0 });
+            }
+        }
+        let privacy = DifferentialPrivacy::new(1.0);
+        
+        // The fifth profile belongs to someone outside the team
+        assert!(console.get_team_focus_heatmap("alpha", &profiles, &privacy, HeatmapConfig::default(), 0).is_err());
+        let heatmap = console
+            .get_team_focus_heatmap("alpha", &profiles, &privacy, HeatmapConfig { min_members: 4, ..HeatmapConfig::default() }, 0)
+            .unwrap();
+        assert_eq!(heatmap.members, 4);
+        assert!(console.get_team_focus_heatmap("beta", &profiles, &privacy, HeatmapConfig::default(), 0).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+/// Phase: D | Step: 9 | Source: Athenos_AI_Strategy.md#L140
+/// External Event Schema
+/// Versioned Observation/Action/Outcome payloads for webhooks and plugins, decoupled from internal types
+
+use crate::types::{Action, Observation, Outcome, PatternType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Schema name carried by every envelope
+pub const SCHEMA_NAME: &str = "athenos.event";
+
+/// Legacy payloads: internal types serialized as-is (no stability guarantee)
+pub const SCHEMA_V1: u32 = 1;
+
+/// Stable envelope with provenance and consent scope
+pub const SCHEMA_V2: u32 = 2;
+
+pub const CURRENT_SCHEMA_VERSION: u32 = SCHEMA_V2;
+
+/// Versions this daemon can produce, oldest first
+pub const SUPPORTED_SCHEMA_VERSIONS: [u32; 2] = [SCHEMA_V1, SCHEMA_V2];
+
+/// Highest version both sides support; a consumer that lists none gets the legacy format
+pub fn negotiate_schema(accepted: &[u32]) -> Result<u32, String> {
+    if accepted.is_empty() {
+        return Ok(SCHEMA_V1);
+    }
+    SUPPORTED_SCHEMA_VERSIONS
+        .iter()
+        .rev()
+        .find(|version| accepted.contains(version))
+        .copied()
+        .ok_or_else(|| format!("No common schema version: consumer accepts {:?}, daemon supports {:?}", accepted, SUPPORTED_SCHEMA_VERSIONS))
+}
+
+/// Enum label as serialized (snake_case); consumers must tolerate labels they do not know
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Observation as exposed to external consumers (v2)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObservationV2 {
+    pub id: String,
+    pub profile: String,
+    pub steps: Vec<String>,
+    pub intent: String,
+    pub metrics: BTreeMap<String, f64>,
+    pub suggested_action: ActionV2,
+    pub project: Option<String>,
+    pub observed_at: i64,
+}
+
+impl From<&Observation> for ObservationV2 {
+    fn from(observation: &Observation) -> Self {
+        Self {
+            id: observation.id.clone(),
+            profile: label(&observation.profile),
+            steps: observation.observation.clone(),
+            intent: label(&observation.intent),
+            metrics: observation.metrics.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            suggested_action: ActionV2::from(&observation.action),
+            project: observation.project.clone(),
+            observed_at: observation.timestamp,
+        }
+    }
+}
+
+/// Action as exposed to external consumers (v2)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionV2 {
+    pub action_type: String,
+    pub description: String,
+    pub confidence: String,
+    pub risk: String,
+}
+
+impl From<&Action> for ActionV2 {
+    fn from(action: &Action) -> Self {
+        Self {
+            action_type: label(&action.action_type),
+            description: action.description.clone(),
+            confidence: label(&action.confidence),
+            risk: label(&action.risk),
+        }
+    }
+}
+
+/// Outcome as exposed to external consumers (v2); the flags collapse into one result label
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutcomeV2 {
+    pub observation_id: String,
+    pub result: String, // "accepted", "modified", "ignored" or "pending"
+    pub time_saved_minutes: Option<f64>,
+    pub error_rate_change: Option<f64>,
+    pub recorded_at: i64,
+}
+
+impl From<&Outcome> for OutcomeV2 {
+    fn from(outcome: &Outcome) -> Self {
+        let result = if outcome.modified {
+            "modified"
+        } else if outcome.accepted {
+            "accepted"
+        } else if outcome.ignored {
+            "ignored"
+        } else {
+            "pending"
+        };
+        Self {
+            observation_id: outcome.observation_id.clone(),
+            result: result.to_string(),
+            time_saved_minutes: outcome.time_saved_minutes,
+            error_rate_change: outcome.error_rate_change,
+            recorded_at: outcome.timestamp,
+        }
+    }
+}
+
+/// Where a payload came from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventProvenance {
+    pub producer: String,         // Always "athenos-ai"
+    pub producer_version: String, // Daemon version that produced the payload
+    pub source: String,           // Subsystem or observation source that produced the data
+}
+
+impl EventProvenance {
+    pub fn new(source: &str) -> Self {
+        Self {
+            producer: "athenos-ai".to_string(),
+            producer_version: env!("CARGO_PKG_VERSION").to_string(),
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Consent under which a payload was shared
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsentScope {
+    pub recipient: String,         // Developer or plugin id receiving the payload
+    pub capabilities: Vec<String>, // Consents / permissions that authorized sharing it
+}
+
+/// Event body (v2)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventData {
+    PatternDetected { pattern: String, observation: ObservationV2 },
+    ActionExecuted { action_id: String, observation_id: String, action_type: String, success: bool },
+    OutcomeRecorded { outcome: OutcomeV2 },
+    ObservationBatch { observations: Vec<ObservationV2> },
+}
+
+impl EventData {
+    pub fn pattern_detected(pattern: &PatternType, observation: &Observation) -> Self {
+        EventData::PatternDetected { pattern: label(pattern), observation: ObservationV2::from(observation) }
+    }
+
+    pub fn observation_batch(observations: &[Observation]) -> Self {
+        EventData::ObservationBatch { observations: observations.iter().map(ObservationV2::from).collect() }
+    }
+}
+
+/// Versioned envelope delivered to webhooks and plugins
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventEnvelope {
+    pub schema: String,
+    pub schema_version: u32,
+    pub occurred_at: i64,
+    pub provenance: EventProvenance,
+    pub consent: ConsentScope,
+    pub data: EventData,
+}
+
+impl EventEnvelope {
+    /// Envelope at the current schema version
+    pub fn new(data: EventData, occurred_at: i64, provenance: EventProvenance, consent: ConsentScope) -> Self {
+        Self {
+            schema: SCHEMA_NAME.to_string(),
+            schema_version: CURRENT_SCHEMA_VERSION,
+            occurred_at,
+            provenance,
+            consent,
+            data,
+        }
+    }
+}
+
+/// JSON Schema published to consumers for a stable version (v1 has none)
+pub fn json_schema(version: u32) -> Result<serde_json::Value, String> {
+    if version != SCHEMA_V2 {
+        return Err(format!("Schema version {} has no published JSON Schema", version));
+    }
+    let string = serde_json::json!({ "type": "string" });
+    let action = serde_json::json!({
+        "type": "object",
+        "required": ["action_type", "description", "confidence", "risk"],
+        "properties": { "action_type": string, "description": string, "confidence": string, "risk": string }
+    });
+    let observation = serde_json::json!({
+        "type": "object",
+        "required": ["id", "profile", "steps", "intent", "metrics", "suggested_action", "observed_at"],
+        "properties": {
+            "id": string,
+            "profile": string,
+            "steps": { "type": "array", "items": string },
+            "intent": string,
+            "metrics": { "type": "object", "additionalProperties": { "type": "number" } },
+            "suggested_action": action,
+            "project": { "type": ["string", "null"] },
+            "observed_at": { "type": "integer" }
+        }
+    });
+    let outcome = serde_json::json!({
+        "type": "object",
+        "required": ["observation_id", "result", "recorded_at"],
+        "properties": {
+            "observation_id": string,
+            "result": { "enum": ["accepted", "modified", "ignored", "pending"] },
+            "time_saved_minutes": { "type": ["number", "null"] },
+            "error_rate_change": { "type": ["number", "null"] },
+            "recorded_at": { "type": "integer" }
+        }
+    });
+    let variant = |name: &str, required: &[&str], properties: serde_json::Value| {
+        let mut properties = properties;
+        properties["type"] = serde_json::json!({ "const": name });
+        let mut required: Vec<&str> = required.to_vec();
+        required.push("type");
+        serde_json::json!({ "type": "object", "required": required, "properties": properties })
+    };
+    Ok(serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("{}/v{}", SCHEMA_NAME, version),
+        "type": "object",
+        "required": ["schema", "schema_version", "occurred_at", "provenance", "consent", "data"],
+        "properties": {
+            "schema": { "const": SCHEMA_NAME },
+            "schema_version": { "const": version },
+            "occurred_at": { "type": "integer" },
+            "provenance": {
+                "type": "object",
+                "required": ["producer", "producer_version", "source"],
+                "properties": { "producer": string, "producer_version": string, "source": string }
+            },
+            "consent": {
+                "type": "object",
+                "required": ["recipient", "capabilities"],
+                "properties": { "recipient": string, "capabilities": { "type": "array", "items": string } }
+            },
+            "data": {
+                "oneOf": [
+                    variant("pattern_detected", &["pattern", "observation"], serde_json::json!({ "pattern": string, "observation": observation })),
+                    variant(
+                        "action_executed",
+                        &["action_id", "observation_id", "action_type", "success"],
+                        serde_json::json!({ "action_id": string, "observation_id": string, "action_type": string, "success": { "type": "boolean" } })
+                    ),
+                    variant("outcome_recorded", &["outcome"], serde_json::json!({ "outcome": outcome })),
+                    variant("observation_batch", &["observations"], serde_json::json!({ "observations": { "type": "array", "items": observation } }))
+                ]
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use std::collections::HashMap;
+
+    fn observation() -> Observation {
+        Observation {
+            id: "obs_1".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "IDE".to_string()],
+            metrics: [("repeat_count".to_string(), 4.0)].into_iter().collect(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Startup macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "pattern_miner".to_string(),
+            timestamp: 100,
+            project: Some("athenos".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_negotiation_prefers_highest_common_version() {
+        assert_eq!(negotiate_schema(&[]), Ok(SCHEMA_V1));
+        assert_eq!(negotiate_schema(&[1, 2, 7]), Ok(SCHEMA_V2));
+        assert_eq!(negotiate_schema(&[1]), Ok(SCHEMA_V1));
+        assert!(negotiate_schema(&[7]).is_err());
+    }
+
+    #[test]
+    fn test_v2_envelope_is_stable_json() {
+        let envelope = EventEnvelope::new(
+            EventData::pattern_detected(&PatternType::WorkflowSequence, &observation()),
+            200,
+            EventProvenance::new("pattern_miner"),
+            ConsentScope { recipient: "dev_001".to_string(), capabilities: vec!["api.hooks".to_string()] },
+        );
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["schema"], "athenos.event");
+        assert_eq!(json["schema_version"], 2);
+        assert_eq!(json["data"]["type"], "pattern_detected");
+        assert_eq!(json["data"]["pattern"], "workflow_sequence");
+        assert_eq!(json["data"]["observation"]["steps"][1], "IDE");
+        assert_eq!(json["data"]["observation"]["suggested_action"]["confidence"], "high");
+        assert_eq!(json["provenance"]["producer"], "athenos-ai");
+        assert_eq!(serde_json::from_value::<EventEnvelope>(json).unwrap(), envelope);
+
+        let schema = json_schema(SCHEMA_V2).unwrap();
+        assert_eq!(schema["properties"]["data"]["oneOf"].as_array().unwrap().len(), 4);
+        assert!(json_schema(SCHEMA_V1).is_err());
+    }
+
+    #[test]
+    fn test_outcome_result_label() {
+        let mut outcome = Outcome {
+            observation_id: "obs_1".to_string(),
+            accepted: true,
+            ignored: false,
+            modified: true,
+            time_saved_minutes: None,
+            error_rate_change: None,
+            timestamp: 5,
+        };
+        assert_eq!(OutcomeV2::from(&outcome).result, "modified");
+        outcome.modified = false;
+        assert_eq!(OutcomeV2::from(&outcome).result, "accepted");
+        outcome.accepted = false;
+        assert_eq!(OutcomeV2::from(&outcome).result, "pending");
+    }
+}
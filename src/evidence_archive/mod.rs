@@ -0,0 +1,548 @@
+/// Phase: D | Step: 6 | Source: Athenos_AI_Strategy.md#L137
+/// Write-Once Compliance Evidence Archive
+/// Audit logs and SOC2 evidence bundles sealed into append-only, checksum-chained segments on WORM storage
+
+use crate::compliance::SOC2Control;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Hash that precedes the first record and segment of a chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+fn sha256_hex(data: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, data)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Archive backend kind
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ArchiveBackend {
+    LocalDirectory { dir: PathBuf },
+    S3ObjectLock { bucket_url: String },
+}
+
+/// Archive configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    pub backend: ArchiveBackend,
+    pub records_per_segment: usize, // A segment is sealed once it holds this many records
+    pub retention_secs: i64,        // Objects cannot be deleted before this elapses
+    #[serde(default)]
+    pub head_path: Option<PathBuf>, // Local copy of the chain head, kept outside the store
+}
+
+impl ArchiveConfig {
+    /// Seal every 100 records, retain for 7 years
+    pub fn new(backend: ArchiveBackend) -> Self {
+        Self {
+            backend,
+            records_per_segment: 100,
+            retention_secs: 7 * 365 * 86400,
+            head_path: None,
+        }
+    }
+}
+
+/// Write-once object storage
+pub trait WormStore: Send {
+    /// Store a new object; fails if the name already exists
+    fn put_once(&mut self, name: &str, data: &[u8], retain_until: i64) -> Result<(), String>;
+    /// Read an object
+    fn get(&self, name: &str) -> Result<Vec<u8>, String>;
+    /// Names of stored objects, sorted
+    fn list(&self) -> Result<Vec<String>, String>;
+    /// Delete an object; fails while it is under retention
+    fn delete(&mut self, name: &str, now: i64) -> Result<(), String>;
+}
+
+/// Local directory store: files are created exclusively and made read-only
+pub struct LocalWormStore {
+    dir: PathBuf,
+    retention: HashMap<String, i64>, // name -> retain-until for objects written by this process
+}
+
+impl LocalWormStore {
+    /// Create (or reopen) store in a directory
+    pub fn new(dir: PathBuf) -> Result<Self, String> {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create archive dir: {}", e))?;
+        Ok(Self { dir, retention: HashMap::new() })
+    }
+}
+
+impl WormStore for LocalWormStore {
+    fn put_once(&mut self, name: &str, data: &[u8], retain_until: i64) -> Result<(), String> {
+        let path = self.dir.join(name);
+        info!("LocalWormStore::put_once: Writing {:?} ({} bytes)", path, data.len());
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("Refusing to write {}: {}", name, e))?;
+        file.write_all(data).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        file.sync_all().map_err(|e| format!("Failed to sync {}: {}", name, e))?;
+
+        let mut permissions = file.metadata().map_err(|e| e.to_string())?.permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&path, permissions).map_err(|e| format!("Failed to lock {}: {}", name, e))?;
+        self.retention.insert(name.to_string(), retain_until);
+        Ok(())
+    }
+
+    fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        std::fs::read(self.dir.join(name)).map_err(|e| format!("Failed to read {}: {}", name, e))
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let mut names: Vec<String> = std::fs::read_dir(&self.dir)
+            .map_err(|e| format!("Failed to list archive: {}", e))?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&mut self, name: &str, now: i64) -> Result<(), String> {
+        // Objects from earlier runs have no known retention and are treated as locked
+        match self.retention.get(name) {
+            Some(&until) if now >= until => {}
+            _ => return Err(format!("{} is under retention", name)),
+        }
+        let path = self.dir.join(name);
+        let mut permissions = std::fs::metadata(&path).map_err(|e| e.to_string())?.permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(&path, permissions).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", name, e))?;
+        self.retention.remove(name);
+        Ok(())
+    }
+}
+
+/// Object stored under S3 Object Lock
+#[derive(Debug, Clone)]
+struct LockedObject {
+    data: Vec<u8>,
+    retain_until: i64,
+}
+
+/// S3 bucket with Object Lock in compliance mode (Phase D: signed requests built, HTTP simulated)
+pub struct S3ObjectLockStore {
+    bucket_url: String,
+    objects: HashMap<String, LockedObject>,
+}
+
+impl S3ObjectLockStore {
+    /// Create S3 Object Lock store
+    pub fn new(bucket_url: String) -> Result<Self, String> {
+        if !bucket_url.starts_with("https://") {
+            return Err("S3 endpoint must use https".to_string());
+        }
+        Ok(Self { bucket_url, objects: HashMap::new() })
+    }
+
+    /// Object Lock headers sent with a PUT
+    pub fn lock_headers(retain_until: i64) -> Vec<(String, String)> {
+        let until = chrono::DateTime::from_timestamp(retain_until, 0).unwrap_or_default();
+        vec![
+            ("x-amz-object-lock-mode".to_string(), "COMPLIANCE".to_string()),
+            ("x-amz-object-lock-retain-until-date".to_string(), until.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+        ]
+    }
+}
+
+impl WormStore for S3ObjectLockStore {
+    fn put_once(&mut self, name: &str, data: &[u8], retain_until: i64) -> Result<(), String> {
+        if self.objects.contains_key(name) {
+            return Err(format!("Refusing to write {}: object exists", name));
+        }
+        info!(
+            "S3ObjectLockStore::put_once: PUT {}/{} ({} bytes, {:?})",
+            self.bucket_url,
+            name,
+            data.len(),
+            Self::lock_headers(retain_until)
+        );
+        self.objects.insert(name.to_string(), LockedObject { data: data.to_vec(), retain_until });
+        Ok(())
+    }
+
+    fn get(&self, name: &str) -> Result<Vec<u8>, String> {
+        info!("S3ObjectLockStore::get: GET {}/{}", self.bucket_url, name);
+        self.objects.get(name).map(|o| o.data.clone()).ok_or_else(|| format!("Object {} not found", name))
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let mut names: Vec<String> = self.objects.keys().cloned().collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&mut self, name: &str, now: i64) -> Result<(), String> {
+        match self.objects.get(name) {
+            Some(object) if now < object.retain_until => Err(format!("{} is under retention", name)),
+            Some(_) => {
+                info!("S3ObjectLockStore::delete: DELETE {}/{}", self.bucket_url, name);
+                self.objects.remove(name);
+                Ok(())
+            }
+            None => Err(format!("Object {} not found", name)),
+        }
+    }
+}
+
+/// Create the store for a configured backend
+pub fn store_for(config: &ArchiveConfig) -> Result<Box<dyn WormStore>, String> {
+    match &config.backend {
+        ArchiveBackend::LocalDirectory { dir } => Ok(Box::new(LocalWormStore::new(dir.clone())?)),
+        ArchiveBackend::S3ObjectLock { bucket_url } => Ok(Box::new(S3ObjectLockStore::new(bucket_url.clone())?)),
+    }
+}
+
+/// Kind of archived record
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveRecordKind {
+    AuditLog,
+    EvidenceBundle,
+}
+
+/// One archived record, chained to the record before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub seq: u64,
+    pub kind: ArchiveRecordKind,
+    pub source: String,  // Audit log or control the record came from
+    pub payload: String, // JSON of the archived entry
+    pub recorded_at: i64,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl ArchiveRecord {
+    fn compute_hash(&self) -> String {
+        sha256_hex(format!("{}|{}|{:?}|{}|{}|{}", self.prev_hash, self.seq, self.kind, self.source, self.payload, self.recorded_at).as_bytes())
+    }
+}
+
+/// Sealed segment of records, chained to the segment before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveSegment {
+    pub index: u64,
+    pub records: Vec<ArchiveRecord>,
+    pub sealed_at: i64,
+    pub prev_segment_hash: String,
+    pub segment_hash: String,
+}
+
+impl ArchiveSegment {
+    fn object_name(index: u64) -> String {
+        format!("segment-{:08}.json", index)
+    }
+
+    fn compute_hash(&self) -> String {
+        let last_record = self.records.last().map(|r| r.hash.as_str()).unwrap_or(GENESIS_HASH);
+        sha256_hex(format!("{}|{}|{}|{}|{}", self.prev_segment_hash, self.index, self.records.len(), last_record, self.sealed_at).as_bytes())
+    }
+}
+
+/// Last sealed segment as recorded locally; a store holding fewer segments was truncated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveHead {
+    segments: u64,
+    segment_hash: String,
+}
+
+/// Result of verifying the whole archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveVerification {
+    pub segments: usize,
+    pub records: usize,
+    pub head_hash: String,
+}
+
+/// Append-only evidence archive over a write-once store
+pub struct EvidenceArchive {
+    store: Box<dyn WormStore>,
+    records_per_segment: usize,
+    retention_secs: i64,
+    pending: Vec<ArchiveRecord>,
+    next_seq: u64,
+    next_segment: u64,
+    last_record_hash: String,
+    last_segment_hash: String, // Local copy of the chain head; detects truncation of the newest segments
+    head_path: Option<PathBuf>,
+}
+
+impl EvidenceArchive {
+    /// Open an archive, verifying any existing segments and resuming the chain after them
+    pub fn open(store: Box<dyn WormStore>, config: &ArchiveConfig) -> Result<Self, String> {
+        let mut archive = Self {
+            store,
+            records_per_segment: config.records_per_segment.max(1),
+            retention_secs: config.retention_secs,
+            pending: Vec::new(),
+            next_seq: 0,
+            next_segment: 0,
+            last_record_hash: GENESIS_HASH.to_string(),
+            last_segment_hash: GENESIS_HASH.to_string(),
+            head_path: config.head_path.clone(),
+        };
+        let segments = archive.verified_segments()?;
+        if let Some(head) = archive.load_head()? {
+            let stored_hash = match head.segments {
+                0 => Some(GENESIS_HASH),
+                count => segments.get(count as usize - 1).map(|s| s.segment_hash.as_str()),
+            };
+            if stored_hash != Some(head.segment_hash.as_str()) {
+                return Err(format!("Archive holds {} segments but {} were sealed (segments deleted)", segments.len(), head.segments));
+            }
+        }
+        for segment in segments {
+            if let Some(record) = segment.records.last() {
+                archive.next_seq = record.seq + 1;
+                archive.last_record_hash = record.hash.clone();
+            }
+            archive.next_segment = segment.index + 1;
+            archive.last_segment_hash = segment.segment_hash;
+        }
+        info!("EvidenceArchive::open: Resuming at segment {} (seq {})", archive.next_segment, archive.next_seq);
+        Ok(archive)
+    }
+
+    fn load_head(&self) -> Result<Option<ArchiveHead>, String> {
+        let Some(path) = &self.head_path else {
+            return Ok(None);
+        };
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(|e| format!("Invalid archive head {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read archive head {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save_head(&self) -> Result<(), String> {
+        let Some(path) = &self.head_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let head = ArchiveHead { segments: self.next_segment, segment_hash: self.last_segment_hash.clone() };
+        let json = serde_json::to_string(&head).map_err(|e| format!("Failed to encode archive head: {}", e))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+    }
+
+    /// Open the archive for a configured backend
+    pub fn from_config(config: &ArchiveConfig) -> Result<Self, String> {
+        Self::open(store_for(config)?, config)
+    }
+
+    fn append(&mut self, kind: ArchiveRecordKind, source: &str, payload: String, now: i64) -> Result<u64, String> {
+        let mut record = ArchiveRecord {
+            seq: self.next_seq,
+            kind,
+            source: source.to_string(),
+            payload,
+            recorded_at: now,
+            prev_hash: self.last_record_hash.clone(),
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash();
+        self.last_record_hash = record.hash.clone();
+        self.next_seq += 1;
+        self.pending.push(record);
+
+        if self.pending.len() >= self.records_per_segment {
+            self.seal(now)?;
+        }
+        Ok(self.next_seq - 1)
+    }
+
+    /// Archive an audit log entry; returns its sequence number
+    pub fn append_audit<T: Serialize>(&mut self, source: &str, entry: &T, now: i64) -> Result<u64, String> {
+        let payload = serde_json::to_string(entry).map_err(|e| format!("Failed to encode audit entry: {}", e))?;
+        self.append(ArchiveRecordKind::AuditLog, source, payload, now)
+    }
+
+    /// Archive a SOC2 control's evidence as one bundle; returns its sequence number
+    pub fn append_evidence_bundle(&mut self, control: &SOC2Control, now: i64) -> Result<u64, String> {
+        let payload = serde_json::to_string(control).map_err(|e| format!("Failed to encode evidence bundle: {}", e))?;
+        self.append(ArchiveRecordKind::EvidenceBundle, &control.id, payload, now)
+    }
+
+    /// Write pending records as a new write-once segment; returns the segment index, if any was written
+    pub fn seal(&mut self, now: i64) -> Result<Option<u64>, String> {
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        let mut segment = ArchiveSegment {
+            index: self.next_segment,
+            records: std::mem::take(&mut self.pending),
+            sealed_at: now,
+            prev_segment_hash: self.last_segment_hash.clone(),
+            segment_hash: String::new(),
+        };
+        segment.segment_hash = segment.compute_hash();
+
+        let data = serde_json::to_vec(&segment).map_err(|e| format!("Failed to encode segment: {}", e))?;
+        if let Err(e) = self.store.put_once(&ArchiveSegment::object_name(segment.index), &data, now + self.retention_secs) {
+            self.pending = segment.records; // Keep records so the next seal retries them
+            return Err(e);
+        }
+        info!("EvidenceArchive::seal: Sealed segment {} ({} records)", segment.index, segment.records.len());
+        self.next_segment += 1;
+        self.last_segment_hash = segment.segment_hash;
+        self.save_head()?;
+        Ok(Some(segment.index))
+    }
+
+    /// Read every stored segment, checking record and segment chains
+    fn verified_segments(&self) -> Result<Vec<ArchiveSegment>, String> {
+        let mut segments = Vec::new();
+        let mut prev_record = GENESIS_HASH.to_string();
+        let mut prev_segment = GENESIS_HASH.to_string();
+        let mut next_seq = 0;
+
+        for (expected_index, name) in self.store.list()?.iter().filter(|n| n.starts_with("segment-")).enumerate() {
+            let data = self.store.get(name)?;
+            let segment: ArchiveSegment = serde_json::from_slice(&data).map_err(|e| format!("{} is unreadable: {}", name, e))?;
+            if segment.index != expected_index as u64 || *name != ArchiveSegment::object_name(segment.index) {
+                return Err(format!("Segment {} missing or out of order at {}", expected_index, name));
+            }
+            if segment.prev_segment_hash != prev_segment || segment.compute_hash() != segment.segment_hash {
+                return Err(format!("Segment {} checksum chain broken", segment.index));
+            }
+            for record in &segment.records {
+                if record.seq != next_seq || record.prev_hash != prev_record || record.compute_hash() != record.hash {
+                    return Err(format!("Record {} in segment {} was altered or removed", next_seq, segment.index));
+                }
+                prev_record = record.hash.clone();
+                next_seq += 1;
+            }
+            prev_segment = segment.segment_hash.clone();
+            segments.push(segment);
+        }
+        Ok(segments)
+    }
+
+    /// Verify the stored archive end to end against the locally known chain head
+    pub fn verify(&self) -> Result<ArchiveVerification, String> {
+        let segments = self.verified_segments()?;
+        let head_hash = segments.last().map(|s| s.segment_hash.clone()).unwrap_or_else(|| GENESIS_HASH.to_string());
+        if head_hash != self.last_segment_hash {
+            return Err("Archive head does not match the last sealed segment (segments deleted)".to_string());
+        }
+        Ok(ArchiveVerification {
+            segments: segments.len(),
+            records: segments.iter().map(|s| s.records.len()).sum(),
+            head_hash,
+        })
+    }
+
+    /// Records not yet sealed into a segment
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::ControlType;
+    use crate::config::ConfigAuditEntry;
+
+    fn audit_entry(field: &str) -> ConfigAuditEntry {
+        ConfigAuditEntry {
+            at: 1_700_000_000,
+            field: field.to_string(),
+            old_value: Some("1.0".to_string()),
+            new_value: Some("0.5".to_string()),
+            applied: true,
+            handlers: vec!["differential_privacy".to_string()],
+            error: None,
+        }
+    }
+
+    fn s3_config() -> ArchiveConfig {
+        ArchiveConfig {
+            records_per_segment: 2,
+            ..ArchiveConfig::new(ArchiveBackend::S3ObjectLock { bucket_url: "https://evidence.example.com".to_string() })
+        }
+    }
+
+    #[test]
+    fn test_segments_are_chained_and_verified() {
+        let mut archive = EvidenceArchive::from_config(&s3_config()).unwrap();
+        archive.append_audit("config", &audit_entry("dp_epsilon"), 100).unwrap();
+        archive.append_audit("config", &audit_entry("sampling_rate"), 101).unwrap(); // Seals segment 0
+        archive.append_evidence_bundle(&SOC2Control {
+            id: "CC6.1".to_string(),
+            name: "Encryption at rest".to_string(),
+            description: "Local data encrypted".to_string(),
+            control_type: ControlType::Encryption,
+            implemented: true,
+            tested: true,
+            evidence: vec!["consent_ledger.enc".to_string()],
+        }, 102).unwrap();
+        assert_eq!(archive.pending_count(), 1);
+        assert_eq!(archive.seal(103).unwrap(), Some(1));
+
+        let verification = archive.verify().unwrap();
+        assert_eq!(verification.segments, 2);
+        assert_eq!(verification.records, 3);
+        assert!(archive.store.put_once("segment-00000000.json", b"{}", 0).is_err());
+        assert!(archive.store.delete("segment-00000001.json", 104).unwrap_err().contains("retention"));
+        assert!(archive.store.delete("segment-00000001.json", 103 + s3_config().retention_secs).is_ok());
+        assert!(archive.verify().unwrap_err().contains("segments deleted"));
+    }
+
+    #[test]
+    fn test_local_store_detects_tampering_and_resumes_chain() {
+        let dir = std::env::temp_dir().join(format!("athenos_worm_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = ArchiveConfig {
+            records_per_segment: 1,
+            head_path: Some(dir.with_extension("head.json")),
+            ..ArchiveConfig::new(ArchiveBackend::LocalDirectory { dir: dir.clone() })
+        };
+        let _ = std::fs::remove_file(dir.with_extension("head.json"));
+
+        let mut archive = EvidenceArchive::from_config(&config).unwrap();
+        archive.append_audit("config", &audit_entry("dp_epsilon"), 100).unwrap();
+        assert!(std::fs::metadata(dir.join("segment-00000000.json")).unwrap().permissions().readonly());
+
+        // Reopening resumes the chain after the existing segment
+        let mut reopened = EvidenceArchive::from_config(&config).unwrap();
+        assert_eq!(reopened.append_audit("config", &audit_entry("sampling_rate"), 200).unwrap(), 1);
+        assert_eq!(reopened.verify().unwrap().records, 2);
+
+        // Rewriting a record's payload breaks the chain
+        let path = dir.join("segment-00000001.json");
+        let mut segment: ArchiveSegment = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        segment.records[0].payload = serde_json::to_string(&audit_entry("telemetry")).unwrap();
+        let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(&path, permissions).unwrap();
+        std::fs::write(&path, serde_json::to_vec(&segment).unwrap()).unwrap();
+        assert!(reopened.verify().unwrap_err().contains("altered"));
+        assert!(EvidenceArchive::from_config(&config).is_err());
+
+        // Deleting the newest segment is caught on the next open through the locally kept head
+        std::fs::remove_file(&path).unwrap();
+        assert!(EvidenceArchive::from_config(&config).err().unwrap().contains("segments deleted"));
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(dir.with_extension("head.json"));
+    }
+}
@@ -0,0 +1,239 @@
+/// Phase: B | Source: Athenos_AI_Strategy.md#L115-117
+/// Feature Flags with Canary Cohort Routing
+/// Roll new automation behaviors out to the opted-in canary segment first and halt them when they underperform control
+
+use crate::cohort::{CohortManager, CohortSegment};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Rollout stage of a flag
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutStage {
+    Canary,  // Canary segment only
+    General, // Every cohort member
+    Halted,  // Nobody; canary underperformed control
+}
+
+/// Feature flag guarding a new automation behavior
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub stage: RolloutStage,
+    pub max_rollback_margin: f64,  // Canary rollback rate may exceed control by at most this
+    pub max_rejection_margin: f64, // Canary rejection rate may exceed control by at most this
+    pub min_samples: usize,        // Per segment, before rates are compared
+    pub halted_reason: Option<String>,
+}
+
+impl FeatureFlag {
+    /// New canary-stage flag with default halt margins
+    pub fn canary(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            stage: RolloutStage::Canary,
+            max_rollback_margin: 0.05,
+            max_rejection_margin: 0.10,
+            min_samples: 20,
+            halted_reason: None,
+        }
+    }
+}
+
+/// Outcome of an automation run under a flag
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagOutcome {
+    Accepted,
+    Rejected,
+    RolledBack, // Accepted and executed, then undone
+}
+
+/// Outcome counts for one segment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SegmentOutcomes {
+    pub accepted: usize,
+    pub rejected: usize,
+    pub rolled_back: usize,
+}
+
+impl SegmentOutcomes {
+    /// Outcomes recorded
+    pub fn total(&self) -> usize {
+        self.accepted + self.rejected + self.rolled_back
+    }
+
+    /// Share of outcomes that were rolled back
+    pub fn rollback_rate(&self) -> f64 {
+        if self.total() == 0 { 0.0 } else { self.rolled_back as f64 / self.total() as f64 }
+    }
+
+    /// Share of outcomes that were rejected
+    pub fn rejection_rate(&self) -> f64 {
+        if self.total() == 0 { 0.0 } else { self.rejected as f64 / self.total() as f64 }
+    }
+}
+
+/// Automatic halt of a canary flag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HaltEvent {
+    pub flag: String,
+    pub reason: String,
+    pub canary: SegmentOutcomes,
+    pub control: SegmentOutcomes,
+}
+
+/// Feature flag registry routed through the cohort's canary segment
+pub struct FeatureFlagRegistry {
+    flags: HashMap<String, FeatureFlag>,
+    outcomes: HashMap<String, HashMap<CohortSegment, SegmentOutcomes>>,
+}
+
+impl FeatureFlagRegistry {
+    /// Create empty registry
+    pub fn new() -> Self {
+        info!("FeatureFlagRegistry::new: Creating feature flag registry");
+        Self {
+            flags: HashMap::new(),
+            outcomes: HashMap::new(),
+        }
+    }
+
+    /// Register (or replace) a flag
+    pub fn register(&mut self, flag: FeatureFlag) {
+        info!("FeatureFlagRegistry::register: {} ({:?})", flag.name, flag.stage);
+        self.outcomes.remove(&flag.name);
+        self.flags.insert(flag.name.clone(), flag);
+    }
+
+    /// Get a flag
+    pub fn get_flag(&self, name: &str) -> Option<&FeatureFlag> {
+        self.flags.get(name)
+    }
+
+    /// Whether the behavior is enabled for a user; unknown flags and non-members are off
+    pub fn is_enabled(&self, name: &str, user_id: &str, cohort: &CohortManager) -> bool {
+        let Some(flag) = self.flags.get(name) else { return false };
+        matches!(
+            (flag.stage, cohort.segment_of(user_id)),
+            (RolloutStage::General, Some(_)) | (RolloutStage::Canary, Some(CohortSegment::Canary))
+        )
+    }
+
+    /// Record an outcome for the user's segment; control outcomes are the baseline behavior
+    /// Returns the halt event when the canary now exceeds control by more than the flag's margins
+    pub fn record_outcome(&mut self, name: &str, user_id: &str, cohort: &CohortManager, outcome: FlagOutcome) -> Option<HaltEvent> {
+        let segment = cohort.segment_of(user_id)?;
+        self.flags.get(name)?;
+        let counts = self.outcomes.entry(name.to_string()).or_default().entry(segment).or_default();
+        match outcome {
+            FlagOutcome::Accepted => counts.accepted += 1,
+            FlagOutcome::Rejected => counts.rejected += 1,
+            FlagOutcome::RolledBack => counts.rolled_back += 1,
+        }
+        self.evaluate(name)
+    }
+
+    /// Outcome counts for a flag and segment
+    pub fn get_outcomes(&self, name: &str, segment: CohortSegment) -> SegmentOutcomes {
+        self.outcomes.get(name).and_then(|o| o.get(&segment)).cloned().unwrap_or_default()
+    }
+
+    fn evaluate(&mut self, name: &str) -> Option<HaltEvent> {
+        let flag = self.flags.get(name)?;
+        if flag.stage != RolloutStage::Canary {
+            return None;
+        }
+        let canary = self.get_outcomes(name, CohortSegment::Canary);
+        let control = self.get_outcomes(name, CohortSegment::Control);
+        if canary.total() < flag.min_samples || control.total() < flag.min_samples {
+            return None;
+        }
+
+        let rollback_gap = canary.rollback_rate() - control.rollback_rate();
+        let rejection_gap = canary.rejection_rate() - control.rejection_rate();
+        let reason = if rollback_gap > flag.max_rollback_margin {
+            format!("Canary rollback rate {:.0}% exceeds control {:.0}%", canary.rollback_rate() * 100.0, control.rollback_rate() * 100.0)
+        } else if rejection_gap > flag.max_rejection_margin {
+            format!("Canary rejection rate {:.0}% exceeds control {:.0}%", canary.rejection_rate() * 100.0, control.rejection_rate() * 100.0)
+        } else {
+            return None;
+        };
+
+        warn!("FeatureFlagRegistry::evaluate: Halting {}: {}", name, reason);
+        let flag = self.flags.get_mut(name)?;
+        flag.stage = RolloutStage::Halted;
+        flag.halted_reason = Some(reason.clone());
+        Some(HaltEvent { flag: name.to_string(), reason, canary, control })
+    }
+
+    /// Roll a healthy canary flag out to everyone
+    pub fn promote(&mut self, name: &str) -> Result<(), String> {
+        let flag = self.flags.get_mut(name).ok_or(format!("Unknown flag: {}", name))?;
+        if flag.stage != RolloutStage::Canary {
+            return Err(format!("Flag {} is {:?}, not in canary", name, flag.stage));
+        }
+        info!("FeatureFlagRegistry::promote: {} -> general", name);
+        flag.stage = RolloutStage::General;
+        Ok(())
+    }
+}
+
+impl Default for FeatureFlagRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UserProfile;
+
+    fn cohort() -> CohortManager {
+        let mut cohort = CohortManager::new(10);
+        cohort.add_member("canary_user".to_string(), UserProfile::Developer);
+        cohort.add_member("control_user".to_string(), UserProfile::Developer);
+        cohort.set_canary_opt_in("canary_user", true).unwrap();
+        cohort
+    }
+
+    #[test]
+    fn test_canary_routing_and_promotion() {
+        let cohort = cohort();
+        let mut flags = FeatureFlagRegistry::new();
+        flags.register(FeatureFlag::canary("smart_batching"));
+
+        assert!(flags.is_enabled("smart_batching", "canary_user", &cohort));
+        assert!(!flags.is_enabled("smart_batching", "control_user", &cohort));
+        assert!(!flags.is_enabled("smart_batching", "stranger", &cohort));
+        assert!(!flags.is_enabled("unknown_flag", "canary_user", &cohort));
+
+        flags.promote("smart_batching").unwrap();
+        assert!(flags.is_enabled("smart_batching", "control_user", &cohort));
+        assert!(flags.promote("smart_batching").is_err());
+    }
+
+    #[test]
+    fn test_halts_when_canary_rolls_back_more_than_control() {
+        let cohort = cohort();
+        let mut flags = FeatureFlagRegistry::new();
+        flags.register(FeatureFlag { min_samples: 10, ..FeatureFlag::canary("auto_reply") });
+
+        for i in 0..10 {
+            let outcome = if i < 1 { FlagOutcome::RolledBack } else { FlagOutcome::Accepted };
+            assert!(flags.record_outcome("auto_reply", "control_user", &cohort, outcome).is_none());
+        }
+        let mut halt = None;
+        for i in 0..10 {
+            let outcome = if i < 3 { FlagOutcome::RolledBack } else { FlagOutcome::Accepted };
+            halt = halt.or(flags.record_outcome("auto_reply", "canary_user", &cohort, outcome));
+        }
+
+        let halt = halt.expect("canary should halt");
+        assert!(halt.reason.contains("rollback rate 30%"));
+        assert_eq!(flags.get_flag("auto_reply").unwrap().stage, RolloutStage::Halted);
+        assert!(!flags.is_enabled("auto_reply", "canary_user", &cohort));
+    }
+}
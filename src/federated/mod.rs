@@ -3,10 +3,17 @@
 /// Start federated learning pilot to share anonymized pattern templates
 
 use crate::types::*;
+use crate::compliance::DifferentialPrivacy;
+use crate::consent::{DataDisposition, RevocationHandler};
 use crate::privacy::ConsentLedger;
+use crate::enterprise::EnterpriseAdminConsole;
+use crate::signature::PatternSignature;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ring::agreement::{self, EphemeralPrivateKey, UnparsedPublicKey, X25519};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tracing::info;
+use std::collections::{BTreeSet, HashMap};
+use tracing::{info, warn};
 
 /// Anonymized pattern template for federated learning
 /// Source: Athenos_AI_Strategy.md#L116
@@ -17,15 +24,342 @@ pub struct AnonymizedPatternTemplate {
     pub frequency: usize,
     pub avg_time_saved_min: f64,
     pub confidence_score: f64,
+    #[serde(default)]
+    pub signature: String, // PatternSignature category key
     // No user-specific data
 }
 
+/// One team's merged templates within a tenant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamTemplates {
+    pub tenant_key: String,
+    pub team_id: String,
+    pub templates: Vec<AnonymizedPatternTemplate>,
+}
+
+/// Aggregated templates, for persisting a coordinator across restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FederationSnapshot {
+    pub aggregated: Vec<AnonymizedPatternTemplate>,
+    pub teams: Vec<TeamTemplates>,
+    pub pending_global: HashMap<String, Vec<AnonymizedPatternTemplate>>, // tenant_key -> not yet shared globally
+}
+
 /// Federated learning coordinator
 /// Source: Athenos_AI_Strategy.md#L116
 pub struct FederatedLearningCoordinator {
     consent_ledger: ConsentLedger,
     local_templates: Vec<AnonymizedPatternTemplate>,
     aggregated_templates: Vec<AnonymizedPatternTemplate>,
+    team_templates: HashMap<(String, String), Vec<AnonymizedPatternTemplate>>, // (tenant_key, team_id) -> templates
+    pending_global: HashMap<String, Vec<AnonymizedPatternTemplate>>,          // tenant_key -> not yet shared globally
+    quarantined: Vec<AnonymizedPatternTemplate>,                               // Queued before cloud_sync was revoked; never shared
+    min_round_participants: usize,                                             // Updates a secure round needs before it unmasks
+}
+
+/// Default minimum number of updates before a secure aggregation round unmasks
+pub const DEFAULT_MIN_ROUND_PARTICIPANTS: usize = 3;
+
+/// Fixed-point scale for masked values; masks cancel exactly in wrapping u64 arithmetic
+const FIXED_POINT_SCALE: f64 = 1_000_000.0;
+
+fn encode_fixed(value: f64) -> u64 {
+    (value * FIXED_POINT_SCALE).round() as i64 as u64
+}
+
+fn decode_fixed(value: u64) -> f64 {
+    value as i64 as f64 / FIXED_POINT_SCALE
+}
+
+/// Pseudorandom mask shared by two clients for one round
+fn pairwise_mask(secret: &[u8; 32], round_id: u64, len: usize) -> Vec<u64> {
+    let mut input = secret.to_vec();
+    input.extend_from_slice(&round_id.to_be_bytes());
+    let digest = ring::digest::digest(&ring::digest::SHA256, &input);
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(digest.as_ref());
+    let mut rng = StdRng::from_seed(seed);
+    (0..len).map(|_| rng.gen::<u64>()).collect()
+}
+
+/// Lifecycle of a secure aggregation round
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RoundState {
+    Collecting, // Accepting masked updates
+    Unmasking,  // Collection closed; waiting for dropped clients' seeds
+    Completed,
+    Failed,
+}
+
+/// Client update with pairwise masks applied; meaningless on its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaskedUpdate {
+    pub client_id: String,
+    pub round_id: u64,
+    pub values: Vec<u64>, // Fixed-point weighted update followed by the weight
+}
+
+/// Pairwise secret a survivor reveals so a dropped client's masks can be removed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedReveal {
+    pub survivor_id: String,
+    pub dropped_id: String,
+    pub secret: [u8; 32],
+}
+
+/// X25519 public key one client addresses to one peer; the coordinator only relays these
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyAdvert {
+    pub from: String,
+    pub to: String,
+    pub public_key: Vec<u8>,
+}
+
+/// Federated client agreeing a pairwise secret with every peer; the secrets never leave the clients
+pub struct RoundClient {
+    pub client_id: String,
+    pending_keys: HashMap<String, EphemeralPrivateKey>, // peer -> key awaiting that peer's advert
+    pairwise_secrets: HashMap<String, [u8; 32]>,
+}
+
+impl RoundClient {
+    /// Start key agreement with one ephemeral key per peer; returns the adverts to relay to the peers
+    pub fn new(client_id: &str, peers: &[String]) -> Result<(Self, Vec<KeyAdvert>), String> {
+        let rng = ring::rand::SystemRandom::new();
+        let mut pending_keys = HashMap::new();
+        let mut adverts = Vec::new();
+        for peer in peers.iter().filter(|p| p.as_str() != client_id) {
+            let private_key = EphemeralPrivateKey::generate(&X25519, &rng).map_err(|_| "Failed to generate agreement key".to_string())?;
+            let public_key = private_key.compute_public_key().map_err(|_| "Failed to derive agreement public key".to_string())?;
+            adverts.push(KeyAdvert { from: client_id.to_string(), to: peer.clone(), public_key: public_key.as_ref().to_vec() });
+            pending_keys.insert(peer.clone(), private_key);
+        }
+        Ok((Self { client_id: client_id.to_string(), pending_keys, pairwise_secrets: HashMap::new() }, adverts))
+    }
+
+    /// Finish key agreement with the adverts peers addressed to this client
+    pub fn receive_adverts(&mut self, adverts: &[KeyAdvert]) -> Result<(), String> {
+        for advert in adverts.iter().filter(|a| a.to == self.client_id) {
+            let private_key = self.pending_keys.remove(&advert.from).ok_or_else(|| format!("Unexpected key advert from {}", advert.from))?;
+            let secret = agreement::agree_ephemeral(private_key, &UnparsedPublicKey::new(&X25519, &advert.public_key), |material| {
+                let mut input = b"athenos-secure-aggregation-v1".to_vec();
+                input.extend_from_slice(material);
+                let mut secret = [0u8; 32];
+                secret.copy_from_slice(ring::digest::digest(&ring::digest::SHA256, &input).as_ref());
+                secret
+            })
+            .map_err(|_| format!("Key agreement with {} failed", advert.from))?;
+            self.pairwise_secrets.insert(advert.from.clone(), secret);
+        }
+        Ok(())
+    }
+
+    /// Run the key exchange between clients hosted in one process (pilot and tests)
+    pub fn setup(client_ids: &[String]) -> Result<Vec<RoundClient>, String> {
+        let mut clients = Vec::new();
+        let mut adverts = Vec::new();
+        for client_id in client_ids {
+            let (client, client_adverts) = Self::new(client_id, client_ids)?;
+            clients.push(client);
+            adverts.extend(client_adverts);
+        }
+        for client in &mut clients {
+            client.receive_adverts(&adverts)?;
+        }
+        Ok(clients)
+    }
+
+    /// Mask a weighted update: add masks shared with higher ids, subtract those shared with lower ids
+    pub fn mask_update(&self, round_id: u64, update: &[f64], weight: f64) -> Result<MaskedUpdate, String> {
+        if let Some(peer) = self.pending_keys.keys().next() {
+            return Err(format!("{} has not completed key agreement with {}", self.client_id, peer));
+        }
+        let mut values: Vec<u64> = update.iter().map(|v| encode_fixed(v * weight)).collect();
+        values.push(encode_fixed(weight));
+        for (peer, secret) in &self.pairwise_secrets {
+            let mask = pairwise_mask(secret, round_id, values.len());
+            for (value, m) in values.iter_mut().zip(mask) {
+                *value = if self.client_id < *peer { value.wrapping_add(m) } else { value.wrapping_sub(m) };
+            }
+        }
+        Ok(MaskedUpdate { client_id: self.client_id.clone(), round_id, values })
+    }
+
+    /// Lay templates out over the public vocabulary as (frequency, frequency x time saved) pairs and mask them
+    /// Templates outside the vocabulary stay on the device
+    pub fn mask_templates(&self, round_id: u64, vocabulary: &[(PatternType, String)], templates: &[AnonymizedPatternTemplate]) -> Result<MaskedUpdate, String> {
+        let mut update = vec![0.0; vocabulary.len() * 2];
+        for template in templates {
+            if let Some(i) = vocabulary.iter().position(|k| k.0 == template.pattern_type && k.1 == template.signature) {
+                update[i * 2] += template.frequency as f64;
+                update[i * 2 + 1] += template.frequency as f64 * template.avg_time_saved_min;
+            }
+        }
+        self.mask_update(round_id, &update, 1.0)
+    }
+
+    /// Reveal secrets shared with dropped clients only (never with survivors)
+    pub fn reveal_seeds(&self, dropped: &[String]) -> Vec<SeedReveal> {
+        dropped
+            .iter()
+            .filter_map(|id| {
+                self.pairwise_secrets.get(id).map(|secret| SeedReveal {
+                    survivor_id: self.client_id.clone(),
+                    dropped_id: id.clone(),
+                    secret: *secret,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Outcome of a completed round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundResult {
+    pub round_id: u64,
+    pub sum: Vec<f64>, // Noised weighted sum per coordinate
+    pub total_weight: f64, // Noised
+    pub contributors: usize,
+    pub dropped: Vec<String>,
+}
+
+impl RoundResult {
+    /// Federated average (weighted sum over total weight)
+    pub fn average(&self) -> Vec<f64> {
+        if self.total_weight <= 0.0 {
+            return vec![0.0; self.sum.len()];
+        }
+        self.sum.iter().map(|v| v / self.total_weight).collect()
+    }
+}
+
+/// One federated averaging round with secure aggregation
+/// The coordinator only ever learns the sum of the surviving clients' updates
+pub struct FederatedRound {
+    round_id: u64,
+    dimension: usize,
+    participants: BTreeSet<String>,
+    min_participants: usize,
+    submitted: HashMap<String, Vec<u64>>,
+    reveals: Vec<SeedReveal>,
+    state: RoundState,
+}
+
+impl FederatedRound {
+    /// Open a round for the given participants
+    pub fn new(round_id: u64, participants: &[String], dimension: usize, min_participants: usize) -> Self {
+        info!("FederatedRound::new: Round {} with {} participants", round_id, participants.len());
+        Self {
+            round_id,
+            dimension,
+            participants: participants.iter().cloned().collect(),
+            min_participants: min_participants.max(2),
+            submitted: HashMap::new(),
+            reveals: Vec::new(),
+            state: RoundState::Collecting,
+        }
+    }
+
+    /// Current round state
+    pub fn state(&self) -> RoundState {
+        self.state
+    }
+
+    /// Accept a masked update from a participant
+    pub fn submit(&mut self, update: MaskedUpdate) -> Result<(), String> {
+        if self.state != RoundState::Collecting {
+            return Err(format!("Round {} is no longer collecting", self.round_id));
+        }
+        if update.round_id != self.round_id || !self.participants.contains(&update.client_id) {
+            return Err(format!("Update from {} does not belong to round {}", update.client_id, self.round_id));
+        }
+        if update.values.len() != self.dimension + 1 {
+            return Err(format!("Expected {} values, got {}", self.dimension + 1, update.values.len()));
+        }
+        if self.submitted.contains_key(&update.client_id) {
+            return Err(format!("{} already submitted", update.client_id));
+        }
+        self.submitted.insert(update.client_id, update.values);
+        Ok(())
+    }
+
+    /// Participants that did not submit
+    pub fn dropped(&self) -> Vec<String> {
+        self.participants.iter().filter(|p| !self.submitted.contains_key(*p)).cloned().collect()
+    }
+
+    /// Stop collecting; returns dropped clients whose seeds survivors must reveal
+    pub fn close_collection(&mut self) -> Result<Vec<String>, String> {
+        if self.state != RoundState::Collecting {
+            return Err(format!("Round {} is not collecting", self.round_id));
+        }
+        if self.submitted.len() < self.min_participants {
+            warn!("FederatedRound::close_collection: Round {} failed ({} of {} required)", self.round_id, self.submitted.len(), self.min_participants);
+            self.state = RoundState::Failed;
+            return Err(format!("Only {} updates, {} required", self.submitted.len(), self.min_participants));
+        }
+        self.state = RoundState::Unmasking;
+        let dropped = self.dropped();
+        info!("FederatedRound::close_collection: Round {} closed, {} dropped", self.round_id, dropped.len());
+        Ok(dropped)
+    }
+
+    /// Accept seed reveals; only secrets between a survivor and a dropped client are accepted
+    pub fn provide_reveals(&mut self, reveals: Vec<SeedReveal>) -> Result<(), String> {
+        if self.state != RoundState::Unmasking {
+            return Err(format!("Round {} is not unmasking", self.round_id));
+        }
+        for reveal in reveals {
+            if !self.submitted.contains_key(&reveal.survivor_id) || self.submitted.contains_key(&reveal.dropped_id) {
+                return Err(format!("Reveal {} -> {} would expose a submitted update", reveal.survivor_id, reveal.dropped_id));
+            }
+            self.reveals.push(reveal);
+        }
+        Ok(())
+    }
+
+    /// Unmask the sum, add per-round differential privacy noise and complete the round
+    pub fn finalize(&mut self, privacy: &DifferentialPrivacy) -> Result<RoundResult, String> {
+        if self.state != RoundState::Unmasking {
+            return Err(format!("Round {} is not unmasking", self.round_id));
+        }
+        let dropped = self.dropped();
+        let mut sum = vec![0u64; self.dimension + 1];
+        for values in self.submitted.values() {
+            for (total, v) in sum.iter_mut().zip(values) {
+                *total = total.wrapping_add(*v);
+            }
+        }
+
+        // Remove masks each survivor shared with a dropped client
+        for survivor in self.submitted.keys() {
+            for dropped_id in &dropped {
+                let reveal = self
+                    .reveals
+                    .iter()
+                    .find(|r| r.survivor_id == *survivor && r.dropped_id == *dropped_id)
+                    .ok_or_else(|| format!("Missing seed reveal {} -> {}", survivor, dropped_id))?;
+                let mask = pairwise_mask(&reveal.secret, self.round_id, sum.len());
+                for (total, m) in sum.iter_mut().zip(mask) {
+                    // Survivor added the mask if its id sorts first, otherwise subtracted it
+                    *total = if *survivor < *dropped_id { total.wrapping_sub(m) } else { total.wrapping_add(m) };
+                }
+            }
+        }
+
+        // The total weight is noised like every other released coordinate
+        let total_weight = privacy.add_noise(decode_fixed(sum[self.dimension])).max(0.0);
+        let noised: Vec<f64> = sum[..self.dimension].iter().map(|v| privacy.add_noise(decode_fixed(*v))).collect();
+        self.state = RoundState::Completed;
+        info!("FederatedRound::finalize: Round {} completed with {} contributors", self.round_id, self.submitted.len());
+        Ok(RoundResult {
+            round_id: self.round_id,
+            sum: noised,
+            total_weight,
+            contributors: self.submitted.len(),
+            dropped,
+        })
+    }
 }
 
 impl FederatedLearningCoordinator {
@@ -36,9 +370,19 @@ impl FederatedLearningCoordinator {
             consent_ledger,
             local_templates: Vec::new(),
             aggregated_templates: Vec::new(),
+            team_templates: HashMap::new(),
+            pending_global: HashMap::new(),
+            quarantined: Vec::new(),
+            min_round_participants: DEFAULT_MIN_ROUND_PARTICIPANTS,
         }
     }
 
+    /// Require more (or fewer, but never under two) updates before a secure round unmasks
+    pub fn set_min_round_participants(&mut self, min_participants: usize) {
+        info!("FederatedLearningCoordinator::set_min_round_participants: {}", min_participants);
+        self.min_round_participants = min_participants;
+    }
+
     /// Anonymize pattern from observation
     /// Source: Athenos_AI_Strategy.md#L116
     pub fn anonymize_pattern(&self, observation: &Observation) -> Option<AnonymizedPatternTemplate> {
@@ -62,6 +406,7 @@ impl FederatedLearningCoordinator {
             frequency: observation.metrics.get("repeat_count").map(|v| *v as usize).unwrap_or(1),
             avg_time_saved_min: time_saved,
             confidence_score: confidence,
+            signature: PatternSignature::from_observation(observation).category_key(),
         })
     }
 
@@ -83,12 +428,60 @@ impl FederatedLearningCoordinator {
     /// Aggregate templates from federated learning
     pub fn aggregate_templates(&mut self, templates: Vec<AnonymizedPatternTemplate>) {
         info!("FederatedLearningCoordinator::aggregate_templates: Aggregating {} templates", templates.len());
+        Self::merge_templates(&mut self.aggregated_templates, templates);
+    }
+
+    /// Aggregate templates within an enterprise tenant's team
+    /// Tenant keys separate aggregation domains: templates never mix across tenants
+    pub fn aggregate_team_templates(&mut self, tenant_key: &str, team_id: &str, templates: Vec<AnonymizedPatternTemplate>) {
+        info!("FederatedLearningCoordinator::aggregate_team_templates: Aggregating {} templates for team {}", templates.len(), team_id);
+        
+        let team = self.team_templates
+            .entry((tenant_key.to_string(), team_id.to_string()))
+            .or_default();
+        Self::merge_templates(team, templates.clone());
+        
+        let pending = self.pending_global.entry(tenant_key.to_string()).or_default();
+        Self::merge_templates(pending, templates);
+    }
+
+    /// Share a tenant's aggregated templates globally, if enterprise policy permits
+    /// Returns the number of tenant-level templates shared
+    pub fn share_tenant_globally(&mut self, tenant_key: &str, policy: &EnterpriseAdminConsole) -> Result<usize, String> {
+        if !policy.allows_global_template_sharing() {
+            return Err("Enterprise policy does not permit global template sharing".to_string());
+        }
         
+        let pending = self.pending_global.remove(tenant_key).unwrap_or_default();
+        info!("FederatedLearningCoordinator::share_tenant_globally: Sharing {} templates", pending.len());
+        let shared = pending.len();
+        Self::merge_templates(&mut self.aggregated_templates, pending);
+        Ok(shared)
+    }
+
+    /// Get team-level templates
+    pub fn get_team_templates(&self, tenant_key: &str, team_id: &str) -> &[AnonymizedPatternTemplate] {
+        self.team_templates
+            .get(&(tenant_key.to_string(), team_id.to_string()))
+            .map(|t| t.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Merge templates into `target`, combining those of the same type and signature
+    /// Templates without a signature (older peers) combine by type and length, with signed or unsigned ones
+    fn merge_templates(target: &mut Vec<AnonymizedPatternTemplate>, templates: Vec<AnonymizedPatternTemplate>) {
         // Phase B: Simple aggregation (would use proper FL algorithms in production)
         for template in templates {
             // Find similar template or add new
-            if let Some(existing) = self.aggregated_templates.iter_mut()
-                .find(|t| t.pattern_type == template.pattern_type && t.sequence_length == template.sequence_length) {
+            if let Some(existing) = target.iter_mut()
+                .find(|t| t.pattern_type == template.pattern_type && if t.signature.is_empty() || template.signature.is_empty() {
+                    t.sequence_length == template.sequence_length
+                } else {
+                    t.signature == template.signature
+                }) {
+                if existing.signature.is_empty() {
+                    existing.signature = template.signature.clone();
+                }
                 // Update averages
                 let total_freq = existing.frequency + template.frequency;
                 existing.avg_time_saved_min = 
@@ -96,15 +489,120 @@ impl FederatedLearningCoordinator {
                      template.avg_time_saved_min * template.frequency as f64) / total_freq as f64;
                 existing.frequency = total_freq;
             } else {
-                self.aggregated_templates.push(template);
+                target.push(template);
             }
         }
     }
 
+    /// Aggregate clients' templates through a secure aggregation round
+    /// Clients mask their templates over the public `vocabulary` (RoundClient::mask_templates); the coordinator only sees
+    /// masked updates, and `request_reveals` asks survivors for the seeds they shared with the clients that dropped
+    pub fn secure_aggregate_round(
+        &mut self,
+        round_id: u64,
+        vocabulary: &[(PatternType, String)],
+        participants: &[String],
+        updates: Vec<MaskedUpdate>,
+        request_reveals: impl FnOnce(&[String]) -> Vec<SeedReveal>,
+        privacy: &DifferentialPrivacy,
+    ) -> Result<RoundResult, String> {
+        if !self.consent_ledger.opt_in_cloud_sync {
+            return Err("Federated aggregation requires cloud sync consent".to_string());
+        }
+        let mut round = FederatedRound::new(round_id, participants, vocabulary.len() * 2, self.min_round_participants);
+        for update in updates {
+            round.submit(update)?;
+        }
+        let dropped = round.close_collection()?;
+        round.provide_reveals(request_reveals(&dropped))?;
+        let result = round.finalize(privacy)?;
+
+        let templates: Vec<AnonymizedPatternTemplate> = vocabulary
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (pattern_type, signature))| {
+                let frequency = result.sum[i * 2].round();
+                (frequency >= 1.0).then(|| AnonymizedPatternTemplate {
+                    pattern_type: pattern_type.clone(),
+                    sequence_length: 0,
+                    frequency: frequency as usize,
+                    avg_time_saved_min: (result.sum[i * 2 + 1] / frequency).max(0.0),
+                    confidence_score: 0.0,
+                    signature: signature.clone(),
+                })
+            })
+            .collect();
+        self.aggregate_templates(templates);
+        Ok(result)
+    }
+
+    /// Aggregated, team and pending templates (local and quarantined templates are not included)
+    pub fn snapshot(&self) -> FederationSnapshot {
+        let mut teams: Vec<TeamTemplates> = self.team_templates
+            .iter()
+            .map(|((tenant_key, team_id), templates)| TeamTemplates {
+                tenant_key: tenant_key.clone(),
+                team_id: team_id.clone(),
+                templates: templates.clone(),
+            })
+            .collect();
+        teams.sort_by(|a, b| (&a.tenant_key, &a.team_id).cmp(&(&b.tenant_key, &b.team_id)));
+        FederationSnapshot {
+            aggregated: self.aggregated_templates.clone(),
+            teams,
+            pending_global: self.pending_global.clone(),
+        }
+    }
+
+    /// Replace aggregated, team and pending templates with a snapshot
+    pub fn restore(&mut self, snapshot: FederationSnapshot) {
+        info!("FederatedLearningCoordinator::restore: Restoring {} aggregated templates for {} teams", snapshot.aggregated.len(), snapshot.teams.len());
+        self.aggregated_templates = snapshot.aggregated;
+        self.team_templates = snapshot.teams
+            .into_iter()
+            .map(|team| ((team.tenant_key, team.team_id), team.templates))
+            .collect();
+        self.pending_global = snapshot.pending_global;
+    }
+
     /// Get aggregated templates
     pub fn get_aggregated_templates(&self) -> &[AnonymizedPatternTemplate] {
         &self.aggregated_templates
     }
+
+    /// Templates withheld after a cloud_sync revocation
+    pub fn get_quarantined(&self) -> &[AnonymizedPatternTemplate] {
+        &self.quarantined
+    }
+
+    /// Permanently delete quarantined templates; returns the number deleted
+    pub fn purge_quarantine(&mut self) -> usize {
+        info!("FederatedLearningCoordinator::purge_quarantine: Purging {} templates", self.quarantined.len());
+        std::mem::take(&mut self.quarantined).len()
+    }
+}
+
+impl RevocationHandler for FederatedLearningCoordinator {
+    fn revocation_handler_name(&self) -> &str {
+        "federated_learning"
+    }
+
+    /// Stop sharing and quarantine everything still queued for the federation
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        if capability != "cloud_sync" {
+            return Ok((DataDisposition::NotAffected, 0));
+        }
+        self.consent_ledger.revoke_consent(capability.to_string(), Some("Propagated revocation".to_string()));
+        let queued: Vec<AnonymizedPatternTemplate> = self
+            .local_templates
+            .drain(..)
+            .chain(self.pending_global.drain().flat_map(|(_, templates)| templates))
+            .collect();
+        let count = queued.len();
+        self.quarantined.extend(queued);
+        info!("FederatedLearningCoordinator::on_consent_revoked: Quarantined {} queued templates", count);
+        Ok((DataDisposition::Quarantined, count))
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +644,7 @@ mod tests {
             expected_outcome: expected,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let template = coordinator.anonymize_pattern(&observation);
@@ -175,10 +674,194 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let template = coordinator.anonymize_pattern(&observation);
         assert!(template.is_none()); // Should return None without consent
     }
-}
 
+    fn template(sequence_length: usize, frequency: usize, avg_time_saved_min: f64) -> AnonymizedPatternTemplate {
+        AnonymizedPatternTemplate {
+            pattern_type: PatternType::WorkflowSequence,
+            sequence_length,
+            frequency,
+            avg_time_saved_min,
+            confidence_score: 0.9,
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_team_aggregation_isolated_by_tenant() {
+        let mut coordinator = FederatedLearningCoordinator::new(ConsentLedger::new());
+        coordinator.aggregate_team_templates("tenant_a", "platform", vec![template(3, 2, 10.0)]);
+        coordinator.aggregate_team_templates("tenant_a", "platform", vec![template(3, 2, 20.0)]);
+        coordinator.aggregate_team_templates("tenant_b", "platform", vec![template(3, 5, 1.0)]);
+        
+        let team_a = coordinator.get_team_templates("tenant_a", "platform");
+        assert_eq!(team_a.len(), 1);
+        assert_eq!(team_a[0].frequency, 4);
+        assert_eq!(team_a[0].avg_time_saved_min, 15.0);
+        assert_eq!(coordinator.get_team_templates("tenant_b", "platform")[0].frequency, 5);
+        assert!(coordinator.get_aggregated_templates().is_empty());
+    }
+
+    #[test]
+    fn test_unsigned_templates_merge_with_signed_ones() {
+        let mut coordinator = FederatedLearningCoordinator::new(ConsentLedger::new());
+        let mut signed = template(3, 2, 10.0);
+        signed.signature = "cat:abc".to_string();
+        let mut other = template(3, 2, 10.0);
+        other.signature = "cat:def".to_string();
+        coordinator.aggregate_team_templates("tenant_a", "platform", vec![template(3, 2, 20.0), signed, other]);
+
+        let team = coordinator.get_team_templates("tenant_a", "platform");
+        assert_eq!(team.len(), 2);
+        assert_eq!(team[0].signature, "cat:abc");
+        assert_eq!(team[0].frequency, 4);
+        assert_eq!(team[0].avg_time_saved_min, 15.0);
+    }
+
+    #[test]
+    fn test_global_sharing_requires_policy() {
+        let mut coordinator = FederatedLearningCoordinator::new(ConsentLedger::new());
+        let mut console = EnterpriseAdminConsole::new();
+        coordinator.aggregate_team_templates("tenant_a", "platform", vec![template(3, 2, 10.0)]);
+        
+        assert!(coordinator.share_tenant_globally("tenant_a", &console).is_err());
+        assert!(coordinator.get_aggregated_templates().is_empty());
+        
+        console.set_global_template_sharing(true);
+        assert_eq!(coordinator.share_tenant_globally("tenant_a", &console).unwrap(), 1);
+        assert_eq!(coordinator.share_tenant_globally("tenant_a", &console).unwrap(), 0); // Not shared twice
+        assert_eq!(coordinator.get_aggregated_templates()[0].frequency, 2);
+    }
+
+    fn clients(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("client_{}", i)).collect()
+    }
+
+    #[test]
+    fn test_masked_updates_hide_values_but_sum_exactly() {
+        let ids = clients(3);
+        let round_clients = RoundClient::setup(&ids).unwrap();
+        let updates = [vec![1.5, 10.0], vec![2.5, 20.0], vec![3.0, 30.0]];
+        let mut round = FederatedRound::new(7, &ids, 2, 3);
+        for (client, update) in round_clients.iter().zip(&updates) {
+            let masked = client.mask_update(7, update, 2.0).unwrap();
+            assert_ne!(decode_fixed(masked.values[0]), update[0] * 2.0); // Individual values are hidden
+            round.submit(masked).unwrap();
+        }
+        assert!(round.submit(round_clients[0].mask_update(7, &updates[0], 2.0).unwrap()).is_err());
+
+        assert!(round.close_collection().unwrap().is_empty());
+        let result = round.finalize(&DifferentialPrivacy::new(1e9)).unwrap();
+        assert_eq!(round.state(), RoundState::Completed);
+        assert!((result.total_weight - 6.0).abs() < 1e-6);
+        let average = result.average();
+        assert!((average[0] - 7.0 / 3.0).abs() < 1e-6);
+        assert!((average[1] - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dropouts_recovered_from_survivor_reveals() {
+        let ids = clients(5);
+        let round_clients = RoundClient::setup(&ids).unwrap();
+        let mut round = FederatedRound::new(1, &ids, 1, 3);
+        for client in &round_clients[..4] {
+            round.submit(client.mask_update(1, &[10.0], 1.0).unwrap()).unwrap();
+        }
+        let dropped = round.close_collection().unwrap();
+        assert_eq!(dropped, vec!["client_4".to_string()]);
+        assert!(round.finalize(&DifferentialPrivacy::new(1e9)).is_err()); // Masks still dangling
+
+        // Survivors may not reveal secrets shared with other survivors
+        assert!(round.provide_reveals(round_clients[0].reveal_seeds(&["client_1".to_string()])).is_err());
+        let reveals = round_clients[..4].iter().flat_map(|c| c.reveal_seeds(&dropped)).collect();
+        round.provide_reveals(reveals).unwrap();
+        let result = round.finalize(&DifferentialPrivacy::new(1e9)).unwrap();
+        assert!((result.sum[0] - 40.0).abs() < 1e-6);
+        assert_eq!(result.contributors, 4);
+
+        let mut too_few = FederatedRound::new(2, &ids, 1, 3);
+        too_few.submit(round_clients[0].mask_update(2, &[1.0], 1.0).unwrap()).unwrap();
+        assert!(too_few.close_collection().is_err());
+        assert_eq!(too_few.state(), RoundState::Failed);
+    }
+
+    #[test]
+    fn test_key_agreement_keeps_secrets_on_clients() {
+        let ids = clients(3);
+        let (mut a, adverts_a) = RoundClient::new("client_0", &ids).unwrap();
+        let (mut b, adverts_b) = RoundClient::new("client_1", &ids).unwrap();
+        assert_eq!(adverts_a.len(), 2);
+        assert!(adverts_a.iter().all(|advert| advert.public_key.len() == 32));
+
+        // Agreement with client_2 is still pending, so neither may mask yet
+        let relayed: Vec<KeyAdvert> = adverts_a.into_iter().chain(adverts_b).collect();
+        a.receive_adverts(&relayed).unwrap();
+        b.receive_adverts(&relayed).unwrap();
+        assert!(a.mask_update(1, &[1.0], 1.0).is_err());
+
+        // Both ends derive the same secret without it ever being relayed
+        let from_a = a.reveal_seeds(&["client_1".to_string()]);
+        let from_b = b.reveal_seeds(&["client_0".to_string()]);
+        assert_eq!(from_a[0].secret, from_b[0].secret);
+    }
+
+    #[test]
+    fn test_coordinator_secure_round_merges_templates() {
+        let mut consent = ConsentLedger::new();
+        consent.opt_in_cloud_sync = true;
+        let mut coordinator = FederatedLearningCoordinator::new(consent);
+        let mut signed = template(3, 2, 10.0);
+        signed.signature = "workflow:ide>terminal".to_string();
+        let mut unlisted = signed.clone();
+        unlisted.signature = "workflow:private>tool".to_string();
+        let vocabulary = vec![(signed.pattern_type.clone(), signed.signature.clone())];
+
+        let ids = clients(4);
+        let round_clients = RoundClient::setup(&ids).unwrap();
+        let updates: Vec<MaskedUpdate> = round_clients[..3]
+            .iter()
+            .map(|client| client.mask_templates(1, &vocabulary, &[signed.clone(), unlisted.clone()]).unwrap())
+            .collect();
+
+        let result = coordinator
+            .secure_aggregate_round(1, &vocabulary, &ids, updates.clone(), |dropped| round_clients[..3].iter().flat_map(|c| c.reveal_seeds(dropped)).collect(), &DifferentialPrivacy::new(1e9))
+            .unwrap();
+        assert_eq!(result.dropped, vec!["client_3".to_string()]);
+        let aggregated = coordinator.get_aggregated_templates();
+        assert_eq!(aggregated.len(), 1); // Templates outside the vocabulary never left the clients
+        assert_eq!(aggregated[0].frequency, 6);
+        assert!((aggregated[0].avg_time_saved_min - 10.0).abs() < 1e-3);
+
+        // A raised minimum fails the same round
+        coordinator.set_min_round_participants(4);
+        assert!(coordinator.secure_aggregate_round(1, &vocabulary, &ids, updates.clone(), |_| Vec::new(), &DifferentialPrivacy::new(1e9)).is_err());
+
+        let mut opted_out = FederatedLearningCoordinator::new(ConsentLedger::new());
+        assert!(opted_out.secure_aggregate_round(1, &vocabulary, &ids, updates, |_| Vec::new(), &DifferentialPrivacy::new(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_cloud_sync_revocation_quarantines_queue() {
+        let mut consent = ConsentLedger::new();
+        consent.opt_in_cloud_sync = true;
+        let mut coordinator = FederatedLearningCoordinator::new(consent);
+        let mut console = EnterpriseAdminConsole::new();
+        console.set_global_template_sharing(true);
+        coordinator.aggregate_team_templates("tenant_a", "platform", vec![template(3, 2, 10.0)]);
+        coordinator.aggregate_team_templates("tenant_b", "platform", vec![template(4, 1, 5.0)]);
+
+        assert_eq!(coordinator.on_consent_revoked("emotion_detection").unwrap(), (DataDisposition::NotAffected, 0));
+        assert_eq!(coordinator.on_consent_revoked("cloud_sync").unwrap(), (DataDisposition::Quarantined, 2));
+        assert!(!coordinator.consent_ledger.opt_in_cloud_sync);
+        assert_eq!(coordinator.share_tenant_globally("tenant_a", &console).unwrap(), 0);
+        assert!(coordinator.get_aggregated_templates().is_empty());
+
+        assert_eq!(coordinator.purge_quarantine(), 2);
+        assert!(coordinator.get_quarantined().is_empty());
+    }
+}
@@ -0,0 +1,418 @@
+/// Phase: C | Step: 6 | Source: Athenos_AI_Strategy.md#L121-125
+/// Focus-session Engine
+/// Start/stop/auto-detect focus sessions with optional Pomodoro cadence; completed sessions become victories
+
+use crate::attention::AttentionService;
+use crate::categorizer::{AppCategorizer, AppCategory};
+use crate::edge::{OSEvent, OSEventType};
+use crate::emotion::FocusModeAdjustments;
+use crate::victory::{VictoryCategory, VictoryMetric, VictoryStream};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Attention source name used for session DND
+const SESSION_DND_SOURCE: &str = "focus_session";
+
+/// Pomodoro cadence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PomodoroConfig {
+    pub work_min: i64,
+    pub short_break_min: i64,
+    pub long_break_min: i64,
+    pub cycles_before_long_break: u32,
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            work_min: 25,
+            short_break_min: 5,
+            long_break_min: 15,
+            cycles_before_long_break: 4,
+        }
+    }
+}
+
+/// Focus-session configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusSessionConfig {
+    pub pomodoro: Option<PomodoroConfig>,
+    pub auto_detect_min: i64,  // Uninterrupted deep-work focus that starts a session
+    pub min_victory_min: i64,  // Shorter sessions are not recorded as victories
+}
+
+impl Default for FocusSessionConfig {
+    fn default() -> Self {
+        Self {
+            pomodoro: None,
+            auto_detect_min: 20,
+            min_victory_min: 15,
+        }
+    }
+}
+
+/// How a session started
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionTrigger {
+    Manual,
+    AutoDetected,
+}
+
+/// Pomodoro phase (sessions without Pomodoro stay in Work)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+/// Focus session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub id: String,
+    pub trigger: SessionTrigger,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    pub phase: SessionPhase,
+    pub phase_ends_at: Option<i64>,
+    pub completed_pomodoros: u32,
+    pub focus_secs: i64, // Time spent in work phases
+}
+
+/// Daily focus-session streak
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FocusStreak {
+    pub current_days: u32,
+    pub best_days: u32,
+    pub last_day: Option<i64>, // Days since epoch (UTC)
+}
+
+impl FocusStreak {
+    fn record(&mut self, now: i64) {
+        let day = now.div_euclid(86400);
+        self.current_days = match self.last_day {
+            Some(last) if last == day => self.current_days,
+            Some(last) if last == day - 1 => self.current_days + 1,
+            _ => 1,
+        };
+        self.best_days = self.best_days.max(self.current_days);
+        self.last_day = Some(day);
+    }
+}
+
+/// Focus-session engine
+pub struct FocusSessionEngine {
+    config: FocusSessionConfig,
+    attention: AttentionService,
+    categorizer: AppCategorizer,
+    active: Option<FocusSession>,
+    phase_started_at: i64,
+    deep_work_since: Option<i64>,
+    completed: Vec<FocusSession>,
+    streak: FocusStreak,
+    block_ends: Vec<i64>, // Focus blocks ended since last taken (session stop or work -> break)
+    prior_dnd: Option<(String, Option<i64>)>, // DND (source, until) another source held when the session took over
+}
+
+impl FocusSessionEngine {
+    /// Create engine sharing an attention service
+    pub fn new(config: FocusSessionConfig, attention: AttentionService) -> Self {
+        info!("FocusSessionEngine::new: Creating focus-session engine (pomodoro: {})", config.pomodoro.is_some());
+        Self {
+            config,
+            attention,
+            categorizer: AppCategorizer::new(),
+            active: None,
+            phase_started_at: 0,
+            deep_work_since: None,
+            completed: Vec::new(),
+            streak: FocusStreak::default(),
+            block_ends: Vec::new(),
+            prior_dnd: None,
+        }
+    }
+
+    /// Start a session; nudges are suppressed through attention DND while in a work phase
+    pub fn start(&mut self, trigger: SessionTrigger, now: i64) -> Result<&FocusSession, String> {
+        if self.active.is_some() {
+            return Err("A focus session is already active".to_string());
+        }
+        info!("FocusSessionEngine::start: Starting {:?} session", trigger);
+        let phase_ends_at = self.config.pomodoro.as_ref().map(|p| now + p.work_min * 60);
+        self.active = Some(FocusSession {
+            id: format!("focus_{}", now),
+            trigger,
+            started_at: now,
+            ended_at: None,
+            phase: SessionPhase::Work,
+            phase_ends_at,
+            completed_pomodoros: 0,
+            focus_secs: 0,
+        });
+        self.phase_started_at = now;
+        self.claim_dnd(phase_ends_at, now);
+        Ok(self.active.as_ref().unwrap())
+    }
+
+    /// Stop the active session; sessions long enough are recorded as victories and extend the streak
+    pub fn stop(&mut self, now: i64, victories: &mut VictoryStream) -> Result<FocusSession, String> {
+        let mut session = self.active.take().ok_or_else(|| "No active focus session".to_string())?;
+        if session.phase == SessionPhase::Work {
+            session.focus_secs += (now - self.phase_started_at).max(0);
+        }
+        session.ended_at = Some(now);
+        self.release_dnd(now);
+        self.block_ends.push(now);
+
+        let focus_min = session.focus_secs / 60;
+        if focus_min >= self.config.min_victory_min {
+            self.streak.record(now);
+            let description = match session.completed_pomodoros {
+                0 => format!("Focus streak: {} day(s)", self.streak.current_days),
+                n => format!("{} pomodoros; focus streak: {} day(s)", n, self.streak.current_days),
+            };
+            victories.record_victory(
+                format!("Focused for {} minutes!", focus_min),
+                description,
+                VictoryMetric::FocusIncrease,
+                focus_min as f64,
+                VictoryCategory::Focus,
+            );
+        }
+        info!("FocusSessionEngine::stop: Session {} ended after {} focused min", session.id, focus_min);
+        self.completed.push(session.clone());
+        Ok(session)
+    }
+
+    /// Advance Pomodoro phases; session DND is lifted during breaks and restored for work
+    pub fn tick(&mut self, now: i64) {
+        let pomodoro = match &self.config.pomodoro {
+            Some(pomodoro) => pomodoro.clone(),
+            None => return,
+        };
+        while let Some(ends_at) = self.active.as_ref().and_then(|s| s.phase_ends_at).filter(|ends_at| now >= *ends_at) {
+            let session = self.active.as_mut().unwrap();
+            match session.phase {
+                SessionPhase::Work => {
+                    session.focus_secs += ends_at - self.phase_started_at;
+                    session.completed_pomodoros += 1;
+                    let long = session.completed_pomodoros.is_multiple_of(pomodoro.cycles_before_long_break.max(1));
+                    session.phase = if long { SessionPhase::LongBreak } else { SessionPhase::ShortBreak };
+                    let break_min = if long { pomodoro.long_break_min } else { pomodoro.short_break_min };
+                    session.phase_ends_at = Some(ends_at + break_min * 60);
+                    self.release_dnd(ends_at);
+                    self.block_ends.push(ends_at);
+                }
+                SessionPhase::ShortBreak | SessionPhase::LongBreak => {
+                    session.phase = SessionPhase::Work;
+                    session.phase_ends_at = Some(ends_at + pomodoro.work_min * 60);
+                    let phase_ends_at = session.phase_ends_at;
+                    self.claim_dnd(phase_ends_at, ends_at);
+                }
+            }
+            self.phase_started_at = ends_at;
+        }
+    }
+
+    /// Follow mood-adaptive focus adjustments: a suggested break ends the current Pomodoro work phase early
+    pub fn coordinate(&mut self, adjustments: &FocusModeAdjustments, now: i64) {
+        let in_work = self.active.as_ref().map(|s| s.phase == SessionPhase::Work).unwrap_or(false);
+        if adjustments.suggest_break && in_work && self.config.pomodoro.is_some() {
+            info!("FocusSessionEngine::coordinate: Starting break early (mood adjustment)");
+            if let Some(session) = self.active.as_mut() {
+                session.phase_ends_at = Some(now);
+            }
+            self.tick(now);
+        }
+    }
+
+    /// Auto-detect sessions from sustained deep-work focus; leaving deep work ends an auto-detected session
+    pub fn observe_event(&mut self, event: &OSEvent, victories: &mut VictoryStream) {
+        if !matches!(event.event_type, OSEventType::AppLaunch | OSEventType::AppSwitch | OSEventType::WindowFocus) {
+            return;
+        }
+        let deep_work = matches!(
+            self.categorizer.categorize(&event.app_name),
+            AppCategory::CodeEditor | AppCategory::Terminal | AppCategory::Documents | AppCategory::Spreadsheet | AppCategory::Design
+        );
+        if !deep_work {
+            self.deep_work_since = None;
+            if self.active.as_ref().map(|s| s.trigger == SessionTrigger::AutoDetected).unwrap_or(false) {
+                let _ = self.stop(event.timestamp, victories);
+            }
+            return;
+        }
+
+        let since = *self.deep_work_since.get_or_insert(event.timestamp);
+        if self.active.is_none() && event.timestamp - since >= self.config.auto_detect_min * 60 {
+            let _ = self.start(SessionTrigger::AutoDetected, since);
+        }
+    }
+
+    /// Take over DND for a work phase, remembering DND the user (or another source) already had on
+    fn claim_dnd(&mut self, until: Option<i64>, now: i64) {
+        let snapshot = self.attention.current();
+        if snapshot.source != SESSION_DND_SOURCE && self.attention.is_dnd_active(now) {
+            self.prior_dnd = Some((snapshot.source, snapshot.dnd_until));
+        }
+        self.attention.enable_dnd(SESSION_DND_SOURCE, until);
+    }
+
+    /// Drop session DND, handing back any prior DND that has not expired yet
+    fn release_dnd(&mut self, now: i64) {
+        let prior = self.prior_dnd.take();
+        let snapshot = self.attention.current();
+        if !(snapshot.dnd_enabled && snapshot.source == SESSION_DND_SOURCE) {
+            return;
+        }
+        match prior {
+            Some((source, until)) if until.map(|until| now < until).unwrap_or(true) => {
+                self.attention.enable_dnd(&source, until);
+            }
+            _ => self.attention.disable_dnd(SESSION_DND_SOURCE),
+        }
+    }
+
+    /// Take the times focus blocks ended since the last call; deferred notifications are delivered at these boundaries
+    pub fn take_block_ends(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.block_ends)
+    }
+
+    /// Get active session
+    pub fn active_session(&self) -> Option<&FocusSession> {
+        self.active.as_ref()
+    }
+
+    /// Get completed sessions
+    pub fn get_completed(&self) -> &[FocusSession] {
+        &self.completed
+    }
+
+    /// Get focus streak
+    pub fn get_streak(&self) -> &FocusStreak {
+        &self.streak
+    }
+}
+
+impl Default for FocusSessionEngine {
+    fn default() -> Self {
+        Self::new(FocusSessionConfig::default(), AttentionService::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attention::InterruptionPriority;
+    use std::collections::HashMap;
+
+    fn focus(app: &str, timestamp: i64) -> OSEvent {
+        OSEvent {
+            event_type: OSEventType::AppSwitch,
+            app_name: app.to_string(),
+            window_title: None,
+            timestamp,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_manual_session_suppresses_nudges_and_records_victory() {
+        let attention = AttentionService::new();
+        let mut engine = FocusSessionEngine::new(FocusSessionConfig::default(), attention.clone());
+        let mut victories = VictoryStream::new();
+        let day = 20_000 * 86400;
+
+        engine.start(SessionTrigger::Manual, day).unwrap();
+        assert!(engine.start(SessionTrigger::Manual, day).is_err());
+        assert!(!attention.allows_interruption(InterruptionPriority::Low));
+
+        let session = engine.stop(day + 40 * 60, &mut victories).unwrap();
+        assert_eq!(session.focus_secs, 40 * 60);
+        assert!(attention.allows_interruption(InterruptionPriority::Low));
+        assert_eq!(victories.get_recent_victories(1)[0].metric, VictoryMetric::FocusIncrease);
+
+        // Next-day session extends the streak; a short one does not count
+        engine.start(SessionTrigger::Manual, day + 86400).unwrap();
+        engine.stop(day + 86400 + 30 * 60, &mut victories).unwrap();
+        engine.start(SessionTrigger::Manual, day + 86400 + 3600).unwrap();
+        engine.stop(day + 86400 + 3600 + 5 * 60, &mut victories).unwrap();
+        assert_eq!(engine.get_streak().current_days, 2);
+        assert_eq!(victories.get_recent_victories(10).len(), 2);
+    }
+
+    #[test]
+    fn test_pomodoro_cadence_and_mood_break() {
+        let attention = AttentionService::new();
+        let config = FocusSessionConfig { pomodoro: Some(PomodoroConfig::default()), ..FocusSessionConfig::default() };
+        let mut engine = FocusSessionEngine::new(config, attention.clone());
+
+        engine.start(SessionTrigger::Manual, 0).unwrap();
+        engine.tick(25 * 60);
+        let session = engine.active_session().unwrap();
+        assert_eq!(session.phase, SessionPhase::ShortBreak);
+        assert_eq!(session.completed_pomodoros, 1);
+        assert!(!attention.current().dnd_enabled);
+
+        engine.tick(30 * 60);
+        assert_eq!(engine.active_session().unwrap().phase, SessionPhase::Work);
+        assert!(attention.current().dnd_enabled);
+
+        let stressed = FocusModeAdjustments {
+            reduce_notifications: true,
+            dim_screen: true,
+            enable_zen_mode: true,
+            suggest_break: true,
+            breathing_guidance: true,
+        };
+        engine.coordinate(&stressed, 40 * 60);
+        let session = engine.active_session().unwrap();
+        assert_eq!(session.phase, SessionPhase::ShortBreak);
+        assert_eq!(session.focus_secs, 35 * 60);
+    }
+
+    #[test]
+    fn test_user_dnd_survives_session() {
+        let attention = AttentionService::new();
+        let config = FocusSessionConfig { pomodoro: Some(PomodoroConfig::default()), ..FocusSessionConfig::default() };
+        let mut engine = FocusSessionEngine::new(config, attention.clone());
+        let mut victories = VictoryStream::new();
+        attention.enable_dnd("user", None);
+
+        engine.start(SessionTrigger::Manual, 0).unwrap();
+        assert_eq!(attention.current().source, SESSION_DND_SOURCE);
+
+        // The break hands DND back to the user rather than lifting it
+        engine.tick(25 * 60);
+        assert!(attention.current().dnd_enabled);
+        assert_eq!(attention.current().source, "user");
+
+        engine.tick(30 * 60);
+        assert_eq!(attention.current().source, SESSION_DND_SOURCE);
+        engine.stop(40 * 60, &mut victories).unwrap();
+        let snapshot = attention.current();
+        assert!(snapshot.dnd_enabled);
+        assert_eq!(snapshot.source, "user");
+        assert_eq!(snapshot.dnd_until, None);
+    }
+
+    #[test]
+    fn test_auto_detected_session() {
+        let mut engine = FocusSessionEngine::default();
+        let mut victories = VictoryStream::new();
+        engine.observe_event(&focus("Visual Studio Code", 0), &mut victories);
+        engine.observe_event(&focus("Terminal", 10 * 60), &mut victories);
+        assert!(engine.active_session().is_none());
+
+        engine.observe_event(&focus("Visual Studio Code", 21 * 60), &mut victories);
+        assert_eq!(engine.active_session().unwrap().trigger, SessionTrigger::AutoDetected);
+        assert_eq!(engine.active_session().unwrap().started_at, 0);
+
+        engine.observe_event(&focus("Slack", 50 * 60), &mut victories);
+        assert!(engine.active_session().is_none());
+        assert_eq!(engine.get_completed()[0].focus_secs, 50 * 60);
+        assert_eq!(victories.get_recent_victories(1).len(), 1);
+    }
+}
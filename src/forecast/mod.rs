@@ -0,0 +1,203 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L122-124
+/// Emotional State Forecast
+/// Short-horizon (2-4h) stress/fatigue forecast from time-of-day baselines and current trajectory
+
+use crate::consent::{DataDisposition, RevocationHandler};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use tracing::info;
+
+/// Forecast for one hour of the horizon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastPoint {
+    pub start: i64,
+    pub end: i64,
+    pub stress: f64,  // 0.0 to 1.0
+    pub fatigue: f64, // 0.0 to 1.0
+    pub low_focus: bool,
+}
+
+/// Stress/fatigue sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sample {
+    timestamp: i64,
+    stress: f64,
+    fatigue: f64,
+}
+
+/// Short-horizon emotional state forecaster
+pub struct EmotionForecaster {
+    hourly_baseline: Vec<(f64, f64, usize)>, // hour -> (stress sum, fatigue sum, samples)
+    recent: VecDeque<Sample>,
+    trajectory_window_secs: i64,
+    low_focus_threshold: f64,
+}
+
+impl EmotionForecaster {
+    /// Create new forecaster
+    pub fn new() -> Self {
+        info!("EmotionForecaster::new: Creating emotion forecaster");
+        Self {
+            hourly_baseline: vec![(0.0, 0.0, 0); 24],
+            recent: VecDeque::new(),
+            trajectory_window_secs: 2 * 3600,
+            low_focus_threshold: 0.6,
+        }
+    }
+
+    /// Record a stress/fatigue observation (scores 0.0 to 1.0)
+    pub fn record(&mut self, timestamp: i64, stress: f64, fatigue: f64) {
+        let stress = stress.clamp(0.0, 1.0);
+        let fatigue = fatigue.clamp(0.0, 1.0);
+
+        let baseline = &mut self.hourly_baseline[Self::hour_of(timestamp)];
+        baseline.0 += stress;
+        baseline.1 += fatigue;
+        baseline.2 += 1;
+
+        self.recent.push_back(Sample { timestamp, stress, fatigue });
+        while self.recent.front().map(|s| timestamp - s.timestamp > self.trajectory_window_secs).unwrap_or(false) {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Forecast the next `horizon_hours` (clamped to 2-4) hours
+    /// Near-term points follow the current trajectory; later points converge to the time-of-day baseline
+    pub fn forecast(&self, now: i64, horizon_hours: u32) -> Vec<ForecastPoint> {
+        let horizon = horizon_hours.clamp(2, 4) as i64;
+        info!("EmotionForecaster::forecast: Forecasting {}h ahead", horizon);
+
+        let (current, slope) = self.trajectory(now);
+        (0..horizon)
+            .map(|h| {
+                let start = now + h * 3600;
+                let mid = start + 1800;
+                let hours_ahead = (mid - now) as f64 / 3600.0;
+                let baseline = self.baseline(mid);
+                let weight = (hours_ahead / horizon as f64).min(1.0); // Baseline weight grows with distance
+
+                let project = |idx: usize| {
+                    let (cur, rate, base) = match idx {
+                        0 => (current.0, slope.0, baseline.0),
+                        _ => (current.1, slope.1, baseline.1),
+                    };
+                    let extrapolated = (cur + rate * hours_ahead).clamp(0.0, 1.0);
+                    (extrapolated * (1.0 - weight) + base * weight).clamp(0.0, 1.0)
+                };
+                let stress = project(0);
+                let fatigue = project(1);
+
+                ForecastPoint {
+                    start,
+                    end: start + 3600,
+                    stress,
+                    fatigue,
+                    low_focus: stress >= self.low_focus_threshold || fatigue >= self.low_focus_threshold,
+                }
+            })
+            .collect()
+    }
+
+    /// Predicted low-focus windows within the horizon
+    pub fn low_focus_windows(&self, now: i64, horizon_hours: u32) -> Vec<ForecastPoint> {
+        self.forecast(now, horizon_hours).into_iter().filter(|p| p.low_focus).collect()
+    }
+
+    /// Latest (stress, fatigue) and per-hour slope over the trajectory window
+    fn trajectory(&self, now: i64) -> ((f64, f64), (f64, f64)) {
+        let samples: Vec<&Sample> = self.recent
+            .iter()
+            .filter(|s| s.timestamp <= now && now - s.timestamp <= self.trajectory_window_secs)
+            .collect();
+
+        match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) => {
+                let hours = (last.timestamp - first.timestamp) as f64 / 3600.0;
+                let slope = if hours > 0.0 {
+                    ((last.stress - first.stress) / hours, (last.fatigue - first.fatigue) / hours)
+                } else {
+                    (0.0, 0.0)
+                };
+                ((last.stress, last.fatigue), slope)
+            }
+            _ => (self.baseline(now), (0.0, 0.0)),
+        }
+    }
+
+    /// Average (stress, fatigue) for the hour of day; neutral when no history
+    fn baseline(&self, timestamp: i64) -> (f64, f64) {
+        let (stress, fatigue, count) = self.hourly_baseline[Self::hour_of(timestamp)];
+        if count == 0 {
+            (0.3, 0.3)
+        } else {
+            (stress / count as f64, fatigue / count as f64)
+        }
+    }
+
+    fn hour_of(timestamp: i64) -> usize {
+        (timestamp.rem_euclid(86400) / 3600) as usize
+    }
+}
+
+impl RevocationHandler for EmotionForecaster {
+    fn revocation_handler_name(&self) -> &str {
+        "emotion_forecaster"
+    }
+
+    /// Emotion history exists only under emotion_detection consent; purge baselines and trajectory
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        if capability != "emotion_detection" {
+            return Ok((DataDisposition::NotAffected, 0));
+        }
+        let samples = self.hourly_baseline.iter().map(|b| b.2).sum::<usize>();
+        self.hourly_baseline = vec![(0.0, 0.0, 0); 24];
+        self.recent.clear();
+        info!("EmotionForecaster::on_consent_revoked: Purged {} samples", samples);
+        Ok((DataDisposition::Purged, samples))
+    }
+}
+
+impl Default for EmotionForecaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 1_700_006_400; // 00:00 UTC
+
+    #[test]
+    fn test_forecast_horizon_clamped() {
+        let forecaster = EmotionForecaster::new();
+        assert_eq!(forecaster.forecast(DAY, 1).len(), 2);
+        assert_eq!(forecaster.forecast(DAY, 8).len(), 4);
+    }
+
+    #[test]
+    fn test_baseline_predicts_afternoon_slump() {
+        let mut forecaster = EmotionForecaster::new();
+        for day in 0..5 {
+            forecaster.record(DAY - day * 86400 + 15 * 3600, 0.4, 0.9); // 15:00 fatigue
+            forecaster.record(DAY - day * 86400 + 10 * 3600, 0.1, 0.1);
+        }
+
+        let windows = forecaster.low_focus_windows(DAY + 13 * 3600, 3);
+        assert!(windows.iter().any(|w| w.start == DAY + 15 * 3600));
+        assert!(windows.iter().all(|w| w.start != DAY + 13 * 3600));
+    }
+
+    #[test]
+    fn test_rising_trajectory_raises_near_term_stress() {
+        let mut forecaster = EmotionForecaster::new();
+        let now = DAY + 10 * 3600;
+        forecaster.record(now - 3600, 0.3, 0.2);
+        forecaster.record(now, 0.6, 0.2);
+
+        let forecast = forecaster.forecast(now, 2);
+        assert!(forecast[0].stress > 0.6);
+        assert!(forecast[0].low_focus);
+    }
+}
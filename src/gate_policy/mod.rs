@@ -0,0 +1,166 @@
+/// Phase: B | Step: 8 | Source: Athenos_AI_Strategy.md#L115
+/// Quality-Gate Policy
+/// One configurable policy (quality, risk, warnings, historical acceptance) shared by replay gating, auto-actions and shortcuts
+
+use crate::config::{AthenosConfig, ConfigListener};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// Thresholds an action must meet to pass the quality gate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct GatePolicy {
+    pub min_quality: f64,             // Replay quality score must exceed this
+    pub max_risk: RiskCategory,       // Highest risk allowed through the gate
+    pub max_warnings: usize,
+    pub max_errors: usize,
+    pub min_acceptance_rate: f64,     // Required share of past outcomes that were accepted (0.0 disables)
+    pub min_acceptance_samples: usize, // Outcomes needed before the acceptance rate is enforced
+}
+
+impl Default for GatePolicy {
+    fn default() -> Self {
+        Self {
+            min_quality: 0.6,
+            max_risk: RiskCategory::Low,
+            max_warnings: 1,
+            max_errors: 0,
+            min_acceptance_rate: 0.0,
+            min_acceptance_samples: 5,
+        }
+    }
+}
+
+/// What a consumer knows about one action when consulting the gate; unknown checks are skipped
+#[derive(Debug, Clone, Default)]
+pub struct GateInput {
+    pub quality: Option<f64>,
+    pub risk: Option<RiskCategory>,
+    pub warnings: usize,
+    pub errors: usize,
+    pub accepted: f64, // Past outcomes that were accepted; consumers may weight older ones down
+    pub decided: f64,  // Past outcomes with any decision, weighted the same way
+}
+
+impl GatePolicy {
+    /// Reject thresholds that cannot be applied
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.min_quality) {
+            return Err(format!("gate_policy.min_quality must be in [0, 1], got {}", self.min_quality));
+        }
+        if !(0.0..=1.0).contains(&self.min_acceptance_rate) {
+            return Err(format!("gate_policy.min_acceptance_rate must be in [0, 1], got {}", self.min_acceptance_rate));
+        }
+        Ok(())
+    }
+
+    pub fn allows_risk(&self, risk: &RiskCategory) -> bool {
+        *risk <= self.max_risk
+    }
+
+    /// Historical acceptance rate meets the policy (always true until enough outcomes exist)
+    pub fn allows_acceptance(&self, accepted: f64, decided: f64) -> bool {
+        decided <= 0.0 || decided < self.min_acceptance_samples as f64 || accepted / decided >= self.min_acceptance_rate
+    }
+
+    /// Reasons the input fails the gate; empty when it passes
+    pub fn violations(&self, input: &GateInput) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(quality) = input.quality.filter(|q| q.is_nan() || *q <= self.min_quality) {
+            violations.push(format!("Quality {:.2} does not exceed {:.2}", quality, self.min_quality));
+        }
+        if let Some(risk) = input.risk.as_ref().filter(|r| !self.allows_risk(r)) {
+            violations.push(format!("Risk {:?} exceeds {:?}", risk, self.max_risk));
+        }
+        if input.warnings > self.max_warnings {
+            violations.push(format!("{} warnings exceed {}", input.warnings, self.max_warnings));
+        }
+        if input.errors > self.max_errors {
+            violations.push(format!("{} errors exceed {}", input.errors, self.max_errors));
+        }
+        if !self.allows_acceptance(input.accepted, input.decided) {
+            violations.push(format!(
+                "Acceptance rate {:.2} below {:.2}",
+                input.accepted / input.decided,
+                self.min_acceptance_rate
+            ));
+        }
+        violations
+    }
+
+    pub fn passes(&self, input: &GateInput) -> bool {
+        self.violations(input).is_empty()
+    }
+}
+
+/// Shared gate policy
+/// Cloning yields a handle to the same policy, so a config reload reaches every consumer
+#[derive(Debug, Clone, Default)]
+pub struct SharedGatePolicy {
+    policy: Arc<RwLock<GatePolicy>>,
+}
+
+impl SharedGatePolicy {
+    pub fn new(policy: GatePolicy) -> Self {
+        info!("SharedGatePolicy::new: Creating gate policy (min quality {}, max risk {:?})", policy.min_quality, policy.max_risk);
+        Self { policy: Arc::new(RwLock::new(policy)) }
+    }
+
+    /// Snapshot of the current policy
+    pub fn get(&self) -> GatePolicy {
+        self.policy.read().unwrap().clone()
+    }
+
+    /// Replace the policy for every handle
+    pub fn set(&self, policy: GatePolicy) -> Result<(), String> {
+        policy.validate()?;
+        *self.policy.write().unwrap() = policy;
+        Ok(())
+    }
+}
+
+impl ConfigListener for SharedGatePolicy {
+    fn config_name(&self) -> &str {
+        "gate_policy"
+    }
+
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        if config.gate_policy != self.get() {
+            info!("SharedGatePolicy::on_config_change: Applying gate policy {:?}", config.gate_policy);
+        }
+        self.set(config.gate_policy.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_violations() {
+        let policy = GatePolicy { min_acceptance_rate: 0.5, min_acceptance_samples: 4, ..GatePolicy::default() };
+        let good = GateInput { quality: Some(0.8), risk: Some(RiskCategory::Low), warnings: 1, ..GateInput::default() };
+        assert!(policy.passes(&good));
+
+        let bad = GateInput { quality: Some(0.6), risk: Some(RiskCategory::High), warnings: 2, errors: 1, accepted: 1.0, decided: 4.0 };
+        assert_eq!(policy.violations(&bad).len(), 5);
+        // Too few outcomes to judge acceptance
+        assert!(policy.passes(&GateInput { accepted: 0.0, decided: 3.0, ..good }));
+    }
+
+    #[test]
+    fn test_config_reload_reaches_every_handle() {
+        let mut shared = SharedGatePolicy::default();
+        let consumer = shared.clone();
+        let config = AthenosConfig::from_json(r#"{"gate_policy": {"min_quality": 0.8, "max_risk": "none"}}"#).unwrap();
+        shared.on_config_change(&config).unwrap();
+        assert_eq!(consumer.get().min_quality, 0.8);
+        assert_eq!(consumer.get().max_risk, RiskCategory::None);
+        assert_eq!(consumer.get().max_errors, 0);
+
+        assert!(AthenosConfig::from_json(r#"{"gate_policy": {"min_acceptance_rate": 1.5}}"#).is_err());
+        assert!(consumer.set(GatePolicy { min_quality: -0.1, ..GatePolicy::default() }).is_err());
+    }
+}
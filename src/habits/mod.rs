@@ -0,0 +1,263 @@
+/// Phase: D | Source: Strategic_Reinforcements_Gap_Closures.md#L25
+/// Long-term Habit Tracking
+/// Identify stable habits from pattern history, track when they form/dissolve, compute habit evolution rate
+
+use crate::consent::MicroConsentManager;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use tracing::info;
+
+/// Consent capability required to keep long-term pattern history
+pub const HABITS_CAPABILITY: &str = "habits";
+
+const DAY_SECS: i64 = 86400;
+const MONTH_SECS: i64 = 30 * DAY_SECS;
+
+/// Habit state change
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HabitChangeKind {
+    Formed,
+    Dissolved,
+}
+
+/// Recorded habit formation or dissolution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HabitChange {
+    pub signature: String,
+    pub kind: HabitChangeKind,
+    pub at: i64,
+    pub active_days: usize, // In the trailing window when the change was detected
+}
+
+/// Tracked habit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Habit {
+    pub signature: String,
+    pub formed_at: i64,
+    pub dissolved_at: Option<i64>,
+}
+
+/// Habit detection thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HabitConfig {
+    pub window_days: i64,
+    pub form_min_days: usize,    // Active days in the window for a pattern to become a habit
+    pub dissolve_max_days: usize, // Active days at or below which a habit dissolves
+}
+
+impl Default for HabitConfig {
+    fn default() -> Self {
+        Self {
+            window_days: 14,
+            form_min_days: 5,
+            dissolve_max_days: 1,
+        }
+    }
+}
+
+/// Long-term habit tracker over observed pattern history
+pub struct HabitTracker {
+    config: HabitConfig,
+    active_days: HashMap<String, BTreeSet<i64>>, // signature -> day numbers seen
+    habits: HashMap<String, Habit>,
+    changes: Vec<HabitChange>,
+    last_insight_at: Option<i64>,
+}
+
+impl HabitTracker {
+    /// Create habit tracker with default thresholds
+    pub fn new() -> Self {
+        Self::with_config(HabitConfig::default())
+    }
+
+    /// Create habit tracker with custom thresholds
+    pub fn with_config(config: HabitConfig) -> Self {
+        info!("HabitTracker::new: Creating habit tracker");
+        Self {
+            config,
+            active_days: HashMap::new(),
+            habits: HashMap::new(),
+            changes: Vec::new(),
+            last_insight_at: None,
+        }
+    }
+
+    /// Pattern signature for an observation ("Teams → Gmail → IDE")
+    pub fn signature(observation: &Observation) -> String {
+        observation.observation.join(" → ")
+    }
+
+    /// Add an observation to pattern history (requires `habits` consent)
+    pub fn record_observation(&mut self, consent: &MicroConsentManager, observation: &Observation) -> bool {
+        if !consent.has_consent(HABITS_CAPABILITY) || observation.observation.is_empty() {
+            return false;
+        }
+        self.active_days
+            .entry(Self::signature(observation))
+            .or_default()
+            .insert(observation.timestamp.div_euclid(DAY_SECS));
+        true
+    }
+
+    fn days_in_window(&self, signature: &str, now: i64) -> usize {
+        let today = now.div_euclid(DAY_SECS);
+        self.active_days
+            .get(signature)
+            .map(|days| days.range(today - self.config.window_days + 1..=today).count())
+            .unwrap_or(0)
+    }
+
+    /// Re-evaluate all patterns and record habits that formed or dissolved
+    pub fn update(&mut self, now: i64) -> Vec<HabitChange> {
+        let mut signatures: Vec<String> = self.active_days.keys().cloned().collect();
+        signatures.sort();
+
+        let mut changes = Vec::new();
+        for signature in signatures {
+            let active_days = self.days_in_window(&signature, now);
+            let is_habit = self.habits.get(&signature).map(|h| h.dissolved_at.is_none()).unwrap_or(false);
+
+            let kind = if !is_habit && active_days >= self.config.form_min_days {
+                self.habits.insert(signature.clone(), Habit { signature: signature.clone(), formed_at: now, dissolved_at: None });
+                HabitChangeKind::Formed
+            } else if is_habit && active_days <= self.config.dissolve_max_days {
+                if let Some(habit) = self.habits.get_mut(&signature) {
+                    habit.dissolved_at = Some(now);
+                }
+                HabitChangeKind::Dissolved
+            } else {
+                continue;
+            };
+
+            info!("HabitTracker::update: {} {:?} ({} active days)", signature, kind, active_days);
+            changes.push(HabitChange { signature, kind, at: now, active_days });
+        }
+
+        self.changes.extend(changes.iter().cloned());
+        changes
+    }
+
+    /// Currently active habits
+    pub fn get_active_habits(&self) -> Vec<&Habit> {
+        let mut habits: Vec<&Habit> = self.habits.values().filter(|h| h.dissolved_at.is_none()).collect();
+        habits.sort_by(|a, b| a.signature.cmp(&b.signature));
+        habits
+    }
+
+    /// Habit changes in the trailing `period_secs` divided by habits tracked in that period (0.0 to 1.0)
+    pub fn habit_evolution_rate(&self, now: i64, period_secs: i64) -> f64 {
+        let since = now - period_secs;
+        let changed = self.changes.iter().filter(|c| c.at > since && c.at <= now).count();
+        let tracked = self.habits
+            .values()
+            .filter(|h| h.formed_at <= now && h.dissolved_at.map(|d| d > since).unwrap_or(true))
+            .count();
+        if tracked == 0 {
+            return 0.0;
+        }
+        (changed as f64 / tracked as f64).min(1.0)
+    }
+
+    /// Habit changes since the last monthly insight, once a month has passed
+    pub fn take_monthly_changes(&mut self, now: i64) -> Option<Vec<HabitChange>> {
+        if let Some(last) = self.last_insight_at {
+            if now - last < MONTH_SECS {
+                return None;
+            }
+        }
+        let since = self.last_insight_at.unwrap_or(i64::MIN);
+        self.last_insight_at = Some(now);
+        Some(self.changes.iter().filter(|c| c.at > since && c.at <= now).cloned().collect())
+    }
+
+    /// Get full habit change history
+    pub fn get_changes(&self) -> &[HabitChange] {
+        &self.changes
+    }
+}
+
+impl Default for HabitTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY0: i64 = 1_700_006_400;
+
+    fn consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(HABITS_CAPABILITY.to_string(), "Track long-term habits".to_string());
+        consent.grant_consent(HABITS_CAPABILITY).unwrap();
+        consent
+    }
+
+    fn observation(day: i64) -> Observation {
+        Observation {
+            id: format!("obs_{}", day),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::DetectPattern,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: DAY0 + day * DAY_SECS + 9 * 3600,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_history_requires_consent() {
+        let mut tracker = HabitTracker::new();
+        assert!(!tracker.record_observation(&MicroConsentManager::new(), &observation(0)));
+        assert!(tracker.record_observation(&consenting(), &observation(0)));
+    }
+
+    #[test]
+    fn test_habit_forms_and_dissolves() {
+        let consent = consenting();
+        let mut tracker = HabitTracker::new();
+        for day in 0..5 {
+            tracker.record_observation(&consent, &observation(day));
+        }
+
+        let formed = tracker.update(DAY0 + 4 * DAY_SECS);
+        assert_eq!(formed.len(), 1);
+        assert_eq!(formed[0].kind, HabitChangeKind::Formed);
+        assert_eq!(tracker.get_active_habits().len(), 1);
+        assert!(tracker.update(DAY0 + 5 * DAY_SECS).is_empty());
+
+        // Pattern stops; after the window only the tail remains
+        let dissolved = tracker.update(DAY0 + 17 * DAY_SECS);
+        assert_eq!(dissolved[0].kind, HabitChangeKind::Dissolved);
+        assert!(tracker.get_active_habits().is_empty());
+    }
+
+    #[test]
+    fn test_evolution_rate_and_monthly_changes() {
+        let consent = consenting();
+        let mut tracker = HabitTracker::new();
+        for day in 0..5 {
+            tracker.record_observation(&consent, &observation(day));
+        }
+        let now = DAY0 + 4 * DAY_SECS;
+        assert_eq!(tracker.habit_evolution_rate(now, MONTH_SECS), 0.0);
+        tracker.update(now);
+        assert_eq!(tracker.habit_evolution_rate(now, MONTH_SECS), 1.0);
+
+        assert_eq!(tracker.take_monthly_changes(now).unwrap().len(), 1);
+        assert!(tracker.take_monthly_changes(now + DAY_SECS).is_none());
+        assert!(tracker.take_monthly_changes(now + MONTH_SECS).unwrap().is_empty());
+    }
+}
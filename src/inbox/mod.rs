@@ -0,0 +1,343 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L140
+/// Webhook Inbox for Third-party Context
+/// Consent-gated local endpoint where CI, alerting and ticketing systems post context events that become Observations
+
+use crate::cache::BoundedLog;
+use crate::consent::MicroConsentManager;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Consent capability required to accept third-party context events
+pub const WEBHOOK_INBOX_CAPABILITY: &str = "webhook_inbox";
+
+/// Path prefix of the local inbox endpoint ("/inbox/ci")
+pub const INBOX_PATH_PREFIX: &str = "/inbox/";
+
+/// Context events kept at most, however many arrive within the window
+const MAX_INBOX_EVENTS: usize = 1_000;
+
+/// External system posting to the inbox
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxSource {
+    Ci,
+    PagerDuty,
+    Jira,
+}
+
+impl InboxSource {
+    /// Endpoint path segment
+    pub fn path_segment(&self) -> &'static str {
+        match self {
+            InboxSource::Ci => "ci",
+            InboxSource::PagerDuty => "pagerduty",
+            InboxSource::Jira => "jira",
+        }
+    }
+
+    /// Parse a path segment ("ci", "pagerduty", "jira")
+    pub fn from_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "ci" => Some(InboxSource::Ci),
+            "pagerduty" => Some(InboxSource::PagerDuty),
+            "jira" => Some(InboxSource::Jira),
+            _ => None,
+        }
+    }
+
+    fn from_path(path: &str) -> Option<Self> {
+        Self::from_segment(path.strip_prefix(INBOX_PATH_PREFIX)?.trim_end_matches('/'))
+    }
+}
+
+/// Normalized context event kind
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextEventKind {
+    CiFailure,
+    Alert,
+    Assignment,
+}
+
+/// Normalized context event (only identifiers and urgency; titles and bodies are dropped)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextEvent {
+    pub source: InboxSource,
+    pub kind: ContextEventKind,
+    pub reference: String, // Pipeline name, issue key or incident urgency
+    pub urgent: bool,
+    pub received_at: i64,
+}
+
+/// Inbox configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxConfig {
+    pub profile: UserProfile,
+    pub window_secs: i64,
+    pub spike_weight: f64, // Weighted events in the window that count as a full workload spike
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        Self {
+            profile: UserProfile::Developer,
+            window_secs: 3600,
+            spike_weight: 6.0,
+        }
+    }
+}
+
+/// Inbound webhook inbox
+pub struct WebhookInbox {
+    config: InboxConfig,
+    tokens: HashMap<String, InboxSource>, // SHA-256 of the bearer token -> source
+    events: BoundedLog<ContextEvent>, // Only events inside the window are kept
+    received: u64, // Events accepted since creation (observation ids)
+    consented: bool, // WEBHOOK_INBOX_CAPABILITY granted
+}
+
+impl WebhookInbox {
+    /// Create inbox
+    pub fn new(config: InboxConfig) -> Self {
+        info!("WebhookInbox::new: Creating webhook inbox");
+        Self {
+            config,
+            tokens: HashMap::new(),
+            events: BoundedLog::new("inbox.events", MAX_INBOX_EVENTS),
+            received: 0,
+            consented: false,
+        }
+    }
+
+    /// Sync with the consent manager; without WEBHOOK_INBOX_CAPABILITY every request is refused
+    pub fn apply_consent(&mut self, consent: &MicroConsentManager) {
+        self.consented = consent.has_consent(WEBHOOK_INBOX_CAPABILITY);
+    }
+
+    /// Whether third-party events are accepted
+    pub fn is_consented(&self) -> bool {
+        self.consented
+    }
+
+    /// Register a source; returns the bearer token to configure in the external system (shown once)
+    pub fn register_source(&mut self, source: InboxSource) -> String {
+        use rand::Rng;
+        let token_bytes: [u8; 32] = rand::thread_rng().gen();
+        let token: String = token_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        self.tokens.insert(Self::sha256_hex(&token), source);
+        info!("WebhookInbox::register_source: Registered {}{}", INBOX_PATH_PREFIX, source.path_segment());
+        token
+    }
+
+    fn sha256_hex(token: &str) -> String {
+        ring::digest::digest(&ring::digest::SHA256, token.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Source a bearer token may post to `path` as
+    pub fn authenticate(&self, path: &str, token: &str) -> Result<InboxSource, String> {
+        let source = InboxSource::from_path(path).ok_or_else(|| format!("Unknown inbox path {}", path))?;
+        if self.tokens.get(&Self::sha256_hex(token)) != Some(&source) {
+            return Err("Invalid inbox token".to_string());
+        }
+        Ok(source)
+    }
+
+    /// Handle a POST to the inbox; returns the Observation for relevant events, None for ignored ones
+    pub fn handle_request(&mut self, path: &str, token: &str, body: &str, now: i64) -> Result<Option<Observation>, String> {
+        if !self.consented {
+            return Err("Webhook inbox consent not granted".to_string());
+        }
+        let source = self.authenticate(path, token)?;
+        let payload: serde_json::Value = serde_json::from_str(body).map_err(|e| format!("Invalid payload: {}", e))?;
+        let event = match Self::parse(source, &payload, now) {
+            Some(event) => event,
+            None => return Ok(None),
+        };
+        info!("WebhookInbox::handle_request: {:?} from {:?}", event.kind, source);
+        let since = now - self.config.window_secs;
+        self.events.evict_while(|e| e.received_at < since);
+        self.events.push(event.clone());
+        self.received += 1;
+        Ok(Some(self.to_observation(&event, now)))
+    }
+
+    /// Extract the context event from a source payload
+    /// CI: {"status": "failed", "pipeline": ..}; PagerDuty v3: {"event": {"event_type": "incident.triggered", "data": {"urgency": ..}}};
+    /// Jira: {"webhookEvent": "jira:issue_updated", "issue": {"key": .., "fields": {"assignee": .., "priority": {"name": ..}}}}
+    fn parse(source: InboxSource, payload: &serde_json::Value, now: i64) -> Option<ContextEvent> {
+        let text = |pointer: &str| payload.pointer(pointer).and_then(|v| v.as_str());
+        let (kind, reference, urgent) = match source {
+            InboxSource::Ci => {
+                if !matches!(text("/status")?, "failed" | "failure" | "error") {
+                    return None;
+                }
+                let branch = text("/branch").unwrap_or("");
+                (ContextEventKind::CiFailure, text("/pipeline")?.to_string(), branch == "main" || branch == "master")
+            }
+            InboxSource::PagerDuty => {
+                if text("/event/event_type")? != "incident.triggered" {
+                    return None;
+                }
+                let urgency = text("/event/data/urgency").unwrap_or("low");
+                (ContextEventKind::Alert, urgency.to_string(), urgency == "high")
+            }
+            InboxSource::Jira => {
+                if !text("/webhookEvent")?.starts_with("jira:issue_") || payload.pointer("/issue/fields/assignee").map(|a| a.is_null()).unwrap_or(true) {
+                    return None;
+                }
+                let priority = text("/issue/fields/priority/name").unwrap_or("");
+                (ContextEventKind::Assignment, text("/issue/key")?.to_string(), matches!(priority, "Highest" | "High" | "Blocker"))
+            }
+        };
+        Some(ContextEvent { source, kind, reference, urgent, received_at: now })
+    }
+
+    /// External workload (0.0 to 1.0) over the window; urgent events weigh triple
+    pub fn external_load(&self, now: i64) -> f64 {
+        let since = now - self.config.window_secs;
+        let weight: f64 = self
+            .events
+            .iter()
+            .filter(|e| e.received_at >= since && e.received_at <= now)
+            .map(|e| if e.urgent { 3.0 } else { 1.0 })
+            .sum();
+        (weight / self.config.spike_weight).min(1.0)
+    }
+
+    fn to_observation(&self, event: &ContextEvent, now: i64) -> Observation {
+        let since = now - self.config.window_secs;
+        let count = |kind: ContextEventKind| self.events.iter().filter(|e| e.kind == kind && e.received_at >= since).count() as f64;
+        let mut metrics = HashMap::new();
+        metrics.insert("external_load".to_string(), self.external_load(now));
+        metrics.insert("ci_failure_count".to_string(), count(ContextEventKind::CiFailure));
+        metrics.insert("alert_count".to_string(), count(ContextEventKind::Alert));
+        metrics.insert("assignment_count".to_string(), count(ContextEventKind::Assignment));
+
+        let (action_type, description) = match event.kind {
+            ContextEventKind::CiFailure => (ActionType::PreemptiveDebugAssistant, format!("Offer debugging help for failed pipeline {}", event.reference)),
+            ContextEventKind::Alert => (ActionType::FocusMode, "Protect focus while an incident is open".to_string()),
+            ContextEventKind::Assignment => (ActionType::BatchingSuggestion, format!("Batch non-urgent work around new assignment {}", event.reference)),
+        };
+        Observation {
+            id: format!("inbox_{}_{}_{}", event.source.path_segment(), now, self.received),
+            profile: self.config.profile.clone(),
+            observation: vec![format!("external:{}", event.source.path_segment())],
+            metrics,
+            intent: Intent::DetectPattern,
+            action: Action {
+                action_type,
+                description,
+                confidence: if event.urgent { Confidence::High } else { Confidence::Medium },
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: format!("webhook:{}", event.source.path_segment()),
+            timestamp: now,
+            project: None,
+        }
+    }
+
+    /// Received context events still inside the window, oldest first
+    pub fn get_events(&self) -> Vec<&ContextEvent> {
+        self.events.iter().collect()
+    }
+}
+
+impl Default for WebhookInbox {
+    fn default() -> Self {
+        Self::new(InboxConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(WEBHOOK_INBOX_CAPABILITY.to_string(), "Accept CI and ticketing events".to_string());
+        consent.grant_consent(WEBHOOK_INBOX_CAPABILITY).unwrap();
+        consent
+    }
+
+    #[test]
+    fn test_requires_consent_and_token() {
+        let mut inbox = WebhookInbox::default();
+        let token = inbox.register_source(InboxSource::Ci);
+        let body = r#"{"status": "failed", "pipeline": "build"}"#;
+        assert!(inbox.handle_request("/inbox/ci", &token, body, 0).is_err());
+
+        inbox.apply_consent(&consenting());
+        assert!(inbox.handle_request("/inbox/ci", "wrong", body, 0).is_err());
+        assert!(inbox.handle_request("/inbox/jira", &token, body, 0).is_err()); // Token is per source
+        let observation = inbox.handle_request("/inbox/ci", &token, body, 0).unwrap().unwrap();
+        assert_eq!(observation.action.action_type, ActionType::PreemptiveDebugAssistant);
+        assert_eq!(observation.source, "webhook:ci");
+    }
+
+    #[test]
+    fn test_source_payloads_normalized() {
+        let mut inbox = WebhookInbox::default();
+        inbox.apply_consent(&consenting());
+        let ci = inbox.register_source(InboxSource::Ci);
+        let pagerduty = inbox.register_source(InboxSource::PagerDuty);
+        let jira = inbox.register_source(InboxSource::Jira);
+
+        assert!(inbox.handle_request("/inbox/ci", &ci, r#"{"status": "passed", "pipeline": "build"}"#, 0).unwrap().is_none());
+        let alert = r#"{"event": {"event_type": "incident.triggered", "data": {"urgency": "high", "title": "DB down on prod-3"}}}"#;
+        let observation = inbox.handle_request("/inbox/pagerduty", &pagerduty, alert, 10).unwrap().unwrap();
+        assert_eq!(observation.action.action_type, ActionType::FocusMode);
+        assert!(!serde_json::to_string(&observation).unwrap().contains("prod-3"));
+
+        let unassigned = r#"{"webhookEvent": "jira:issue_updated", "issue": {"key": "OPS-7", "fields": {"assignee": null}}}"#;
+        assert!(inbox.handle_request("/inbox/jira", &jira, unassigned, 20).unwrap().is_none());
+        let assigned = r#"{"webhookEvent": "jira:issue_updated", "issue": {"key": "OPS-7", "fields": {"assignee": {"accountId": "a1"}, "priority": {"name": "Low"}}}}"#;
+        let observation = inbox.handle_request("/inbox/jira", &jira, assigned, 30).unwrap().unwrap();
+        assert_eq!(observation.metrics["assignment_count"], 1.0);
+        assert_eq!(observation.metrics["alert_count"], 1.0);
+    }
+
+    #[test]
+    fn test_workload_spike_reranks_recommendations() {
+        let mut inbox = WebhookInbox::default();
+        inbox.apply_consent(&consenting());
+        let pagerduty = inbox.register_source(InboxSource::PagerDuty);
+        let alert = r#"{"event": {"event_type": "incident.triggered", "data": {"urgency": "high"}}}"#;
+        let mut first = inbox.handle_request("/inbox/pagerduty", &pagerduty, alert, 0).unwrap().unwrap();
+        first.expected_outcome.insert("time_saved_min".to_string(), 10.0);
+        inbox.handle_request("/inbox/pagerduty", &pagerduty, alert, 60).unwrap();
+        assert_eq!(inbox.external_load(60), 1.0);
+        assert_eq!(inbox.external_load(60 + 7200), 0.0);
+
+        let mut nudge = first.clone();
+        nudge.id = "nudge".to_string();
+        nudge.action.action_type = ActionType::MicroNudge;
+        let mut ranker = crate::models::RecommendationRanker::new();
+        let calm = ranker.rank_actions(&[nudge.clone(), first.clone()]);
+        ranker.set_external_load(inbox.external_load(60));
+        let busy = ranker.rank_actions(&[nudge, first]);
+        assert_eq!(calm[0].1, calm[1].1);
+        assert_eq!(busy[0].0.action.action_type, ActionType::FocusMode);
+        assert!(busy[0].1 > busy[1].1);
+    }
+
+    #[test]
+    fn test_events_outside_window_dropped() {
+        let mut inbox = WebhookInbox::default();
+        inbox.apply_consent(&consenting());
+        let ci = inbox.register_source(InboxSource::Ci);
+        let body = r#"{"status": "failed", "pipeline": "build"}"#;
+        let first = inbox.handle_request("/inbox/ci", &ci, body, 0).unwrap().unwrap();
+        inbox.handle_request("/inbox/ci", &ci, body, 10).unwrap();
+        let late = inbox.handle_request("/inbox/ci", &ci, body, 3_605).unwrap().unwrap();
+        assert_eq!(inbox.get_events().len(), 2); // The event at 0 left the hour window
+        assert_ne!(first.id, late.id);
+        assert_eq!(late.metrics["ci_failure_count"], 2.0);
+    }
+}
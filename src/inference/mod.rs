@@ -0,0 +1,456 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L109
+/// Shared LLM Inference Queue
+/// Prioritized, rate-limited access to the local LLM for WisdomEngine, co-pilot and summarization
+
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
+use crate::async_api::AsyncLlmBackend;
+use crate::config::{AthenosConfig, ConfigListener};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info, warn};
+
+const RATE_WINDOW_MS: i64 = 60_000;
+
+/// Component requesting inference
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum InferenceClient {
+    WisdomEngine,
+    CoPilot,
+    Summarization,
+}
+
+impl InferenceClient {
+    /// Default priority: user-facing insight first, background summarization last
+    pub fn default_priority(self) -> InferencePriority {
+        match self {
+            InferenceClient::WisdomEngine => InferencePriority::UserFacing,
+            InferenceClient::CoPilot => InferencePriority::Interactive,
+            InferenceClient::Summarization => InferencePriority::Background,
+        }
+    }
+}
+
+/// Request priority (higher variants are served first)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum InferencePriority {
+    Background,
+    Interactive,
+    UserFacing,
+}
+
+/// Queue limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceConfig {
+    pub max_concurrent: usize,
+    pub max_queue_depth: usize,
+    pub max_requests_per_minute: usize,
+    pub default_timeout_ms: i64, // From submission to completion
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 1,
+            max_queue_depth: 64,
+            max_requests_per_minute: 30,
+            default_timeout_ms: 30_000,
+        }
+    }
+}
+
+/// Queued inference request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceRequest {
+    pub id: String,
+    pub client: InferenceClient,
+    pub priority: InferencePriority,
+    pub prompt: String,
+    pub enqueued_at_ms: i64,
+    pub deadline_ms: i64,
+}
+
+/// Final request status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InferenceStatus {
+    Completed,
+    Failed,
+    TimedOut,
+}
+
+/// Result of a finished request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferenceResult {
+    pub request_id: String,
+    pub client: InferenceClient,
+    pub status: InferenceStatus,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub wait_ms: i64,    // Queued time before dispatch (or until timeout)
+    pub latency_ms: i64, // Dispatch to completion
+}
+
+/// Queue counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InferenceQueueStats {
+    pub queued: usize,
+    pub in_flight: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub timed_out: usize,
+    pub rejected: usize,
+    pub avg_wait_ms: f64,
+    pub avg_latency_ms: f64,
+}
+
+/// Local LLM backend
+pub trait LlmBackend: Send {
+    /// Generate a completion for a prompt
+    fn generate(&mut self, prompt: &str) -> Result<String, String>;
+}
+
+/// Template backend (Phase D: stub until the fine-tuned candle model is loaded)
+#[derive(Default)]
+pub struct TemplateBackend;
+
+impl LlmBackend for TemplateBackend {
+    fn generate(&mut self, prompt: &str) -> Result<String, String> {
+        let last_line = prompt.lines().rev().find(|line| !line.trim().is_empty()).ok_or("Empty prompt")?;
+        Ok(format!("Reflecting on: {}", last_line.trim()))
+    }
+}
+
+/// Shared inference queue with priorities, concurrency and rate limits
+pub struct InferenceQueue {
+    config: InferenceConfig,
+    queue: Vec<InferenceRequest>,
+    in_flight: HashMap<String, (InferenceRequest, i64)>, // id -> (request, dispatched_at_ms)
+    dispatch_times: VecDeque<i64>,
+    results: Vec<InferenceResult>,
+    rejected: usize,
+    next_id: u64,
+}
+
+impl InferenceQueue {
+    /// Create inference queue
+    pub fn new(config: InferenceConfig) -> Self {
+        info!("InferenceQueue::new: Creating inference queue (max {} concurrent)", config.max_concurrent);
+        Self {
+            config,
+            queue: Vec::new(),
+            in_flight: HashMap::new(),
+            dispatch_times: VecDeque::new(),
+            results: Vec::new(),
+            rejected: 0,
+            next_id: 0,
+        }
+    }
+
+    /// Submit a request at the client's default priority and timeout
+    pub fn submit(&mut self, client: InferenceClient, prompt: String, now_ms: i64) -> Result<String, String> {
+        let timeout_ms = self.config.default_timeout_ms;
+        self.submit_with(client, client.default_priority(), prompt, timeout_ms, now_ms)
+    }
+
+    /// Submit a request with explicit priority and timeout; rejected when the queue is full
+    pub fn submit_with(&mut self, client: InferenceClient, priority: InferencePriority, prompt: String, timeout_ms: i64, now_ms: i64) -> Result<String, String> {
+        if self.queue.len() >= self.config.max_queue_depth {
+            self.rejected += 1;
+            warn!("InferenceQueue::submit: Queue full, rejecting {:?} request", client);
+            return Err(format!("Inference queue full ({} requests)", self.queue.len()));
+        }
+        self.next_id += 1;
+        let id = format!("inf_{}", self.next_id);
+        self.queue.push(InferenceRequest {
+            id: id.clone(),
+            client,
+            priority,
+            prompt,
+            enqueued_at_ms: now_ms,
+            deadline_ms: now_ms + timeout_ms,
+        });
+        Ok(id)
+    }
+
+    fn finish(&mut self, result: InferenceResult) {
+        if result.status != InferenceStatus::Completed {
+            info!("InferenceQueue::finish: {} {:?}", result.request_id, result.status);
+        }
+        self.results.push(result);
+    }
+
+    /// Time out queued and in-flight requests past their deadline
+    pub fn expire(&mut self, now_ms: i64) {
+        let (expired, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.queue).into_iter().partition(|r| now_ms > r.deadline_ms);
+        self.queue = kept;
+        for request in expired {
+            self.finish(InferenceResult {
+                request_id: request.id,
+                client: request.client,
+                status: InferenceStatus::TimedOut,
+                output: None,
+                error: Some("Timed out in queue".to_string()),
+                wait_ms: now_ms - request.enqueued_at_ms,
+                latency_ms: 0,
+            });
+        }
+
+        let mut overdue: Vec<String> = self.in_flight.iter().filter(|(_, (r, _))| now_ms > r.deadline_ms).map(|(id, _)| id.clone()).collect();
+        overdue.sort();
+        for id in overdue {
+            if let Some((request, dispatched_at)) = self.in_flight.remove(&id) {
+                self.finish(InferenceResult {
+                    request_id: request.id,
+                    client: request.client,
+                    status: InferenceStatus::TimedOut,
+                    output: None,
+                    error: Some("Timed out during inference".to_string()),
+                    wait_ms: dispatched_at - request.enqueued_at_ms,
+                    latency_ms: now_ms - dispatched_at,
+                });
+            }
+        }
+    }
+
+    /// Hand out the highest-priority requests (FIFO within a priority) within concurrency and rate limits
+    pub fn dispatch(&mut self, now_ms: i64) -> Vec<InferenceRequest> {
+        self.expire(now_ms);
+        while self.dispatch_times.front().map(|t| now_ms - t >= RATE_WINDOW_MS).unwrap_or(false) {
+            self.dispatch_times.pop_front();
+        }
+
+        let mut dispatched = Vec::new();
+        while self.in_flight.len() < self.config.max_concurrent && self.dispatch_times.len() < self.config.max_requests_per_minute {
+            let next = self.queue
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.enqueued_at_ms.cmp(&a.enqueued_at_ms)))
+                .map(|(i, _)| i);
+            let index = match next {
+                Some(index) => index,
+                None => break,
+            };
+            let request = self.queue.remove(index);
+            self.dispatch_times.push_back(now_ms);
+            self.in_flight.insert(request.id.clone(), (request.clone(), now_ms));
+            dispatched.push(request);
+        }
+        dispatched
+    }
+
+    /// Record completion of a dispatched request; output arriving after the deadline is discarded
+    pub fn complete(&mut self, request_id: &str, output: Result<String, String>, now_ms: i64) -> Result<InferenceResult, String> {
+        let (request, dispatched_at) = self.in_flight.remove(request_id).ok_or("Request not in flight")?;
+        let (status, output, error) = if now_ms > request.deadline_ms {
+            (InferenceStatus::TimedOut, None, Some("Timed out during inference".to_string()))
+        } else {
+            match output {
+                Ok(text) => (InferenceStatus::Completed, Some(text), None),
+                Err(e) => (InferenceStatus::Failed, None, Some(e)),
+            }
+        };
+        let result = InferenceResult {
+            request_id: request.id,
+            client: request.client,
+            status,
+            output,
+            error,
+            wait_ms: dispatched_at - request.enqueued_at_ms,
+            latency_ms: now_ms - dispatched_at,
+        };
+        self.finish(result.clone());
+        Ok(result)
+    }
+
+    /// Dispatch and run requests synchronously on a backend; returns every result finished in this call
+    pub fn run(&mut self, backend: &mut dyn LlmBackend, now_ms: i64) -> Vec<InferenceResult> {
+        let before = self.results.len();
+        let mut clock_ms = now_ms;
+        loop {
+            let batch = self.dispatch(clock_ms);
+            if batch.is_empty() {
+                break;
+            }
+            for request in batch {
+                let started = Instant::now();
+                let output = backend.generate(&request.prompt);
+                clock_ms += started.elapsed().as_millis() as i64;
+                let _ = self.complete(&request.id, output, clock_ms);
+            }
+        }
+        self.results[before..].to_vec()
+    }
+
+    /// Drain the queue against an async backend; each dispatched batch runs concurrently
+    pub async fn run_async(&mut self, backend: Arc<dyn AsyncLlmBackend>, now_ms: i64) -> Vec<InferenceResult> {
+        let before = self.results.len();
+        let mut clock_ms = now_ms;
+        loop {
+            let batch = self.dispatch(clock_ms);
+            if batch.is_empty() {
+                break;
+            }
+            let started = Instant::now();
+            let ids: Vec<String> = batch.iter().map(|r| r.id.clone()).collect();
+            let mut tasks = tokio::task::JoinSet::new();
+            for request in batch {
+                let backend = backend.clone();
+                tasks.spawn(async move { (request.id, backend.generate(request.prompt).await) });
+            }
+            let mut outputs: HashMap<String, Result<String, String>> = HashMap::new();
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok((id, output)) => {
+                        outputs.insert(id, output);
+                    }
+                    Err(e) => warn!("InferenceQueue::run_async: Generation task failed: {}", e),
+                }
+            }
+            clock_ms += started.elapsed().as_millis() as i64;
+            // Complete in dispatch order; a panicked task fails its request rather than leaving it in flight
+            for id in ids {
+                let output = outputs.remove(&id).unwrap_or_else(|| Err("Generation task failed".to_string()));
+                let _ = self.complete(&id, output, clock_ms);
+            }
+        }
+        self.results[before..].to_vec()
+    }
+
+    /// Queue counters and averages over finished requests
+    pub fn stats(&self) -> InferenceQueueStats {
+        let count = |status: InferenceStatus| self.results.iter().filter(|r| r.status == status).count();
+        let average = |values: Vec<i64>| if values.is_empty() { 0.0 } else { values.iter().sum::<i64>() as f64 / values.len() as f64 };
+        let completed: Vec<&InferenceResult> = self.results.iter().filter(|r| r.status == InferenceStatus::Completed).collect();
+        InferenceQueueStats {
+            queued: self.queue.len(),
+            in_flight: self.in_flight.len(),
+            completed: completed.len(),
+            failed: count(InferenceStatus::Failed),
+            timed_out: count(InferenceStatus::TimedOut),
+            rejected: self.rejected,
+            avg_wait_ms: average(self.results.iter().map(|r| r.wait_ms).collect()),
+            avg_latency_ms: average(completed.iter().map(|r| r.latency_ms).collect()),
+        }
+    }
+
+    /// Export queue metrics to analytics
+    pub fn export_metrics(&self, analytics: &mut AnalyticsAggregator) {
+        let stats = self.stats();
+        analytics.record_metric("inference.queue_depth".to_string(), stats.queued as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.in_flight".to_string(), stats.in_flight as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.completed".to_string(), stats.completed as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.failed".to_string(), stats.failed as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.timed_out".to_string(), stats.timed_out as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.rejected".to_string(), stats.rejected as f64, MetricCategory::Operations);
+        analytics.record_metric("inference.avg_wait_ms".to_string(), stats.avg_wait_ms, MetricCategory::Operations);
+        analytics.record_metric("inference.avg_latency_ms".to_string(), stats.avg_latency_ms, MetricCategory::Operations);
+    }
+
+    /// Get finished request history
+    pub fn get_results(&self) -> &[InferenceResult] {
+        &self.results
+    }
+}
+
+impl ConfigListener for InferenceQueue {
+    fn config_name(&self) -> &str {
+        "inference_queue"
+    }
+
+    /// Apply new rate and concurrency limits; in-flight requests are unaffected
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        self.config.max_requests_per_minute = config.rate_limits.inference_requests_per_minute;
+        self.config.max_concurrent = config.rate_limits.inference_max_concurrent;
+        Ok(())
+    }
+}
+
+impl Default for InferenceQueue {
+    fn default() -> Self {
+        Self::new(InferenceConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_facing_served_before_background() {
+        let mut queue = InferenceQueue::new(InferenceConfig { max_concurrent: 1, ..InferenceConfig::default() });
+        let summary = queue.submit(InferenceClient::Summarization, "Summarize today".to_string(), 0).unwrap();
+        let copilot = queue.submit(InferenceClient::CoPilot, "Calm message".to_string(), 10).unwrap();
+        let insight = queue.submit(InferenceClient::WisdomEngine, "Insight".to_string(), 20).unwrap();
+
+        let first = queue.dispatch(30);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id, insight);
+        assert!(queue.dispatch(30).is_empty()); // Concurrency limit
+
+        queue.complete(&insight, Ok("done".to_string()), 50).unwrap();
+        assert_eq!(queue.dispatch(50)[0].id, copilot);
+        queue.complete(&copilot, Ok("done".to_string()), 60).unwrap();
+        assert_eq!(queue.dispatch(60)[0].id, summary);
+    }
+
+    #[test]
+    fn test_timeouts_rate_limit_and_rejection() {
+        let mut queue = InferenceQueue::new(InferenceConfig {
+            max_concurrent: 4,
+            max_queue_depth: 3,
+            max_requests_per_minute: 2,
+            default_timeout_ms: 1_000,
+        });
+        for i in 0..3 {
+            queue.submit(InferenceClient::Summarization, format!("chunk {}", i), 0).unwrap();
+        }
+        assert!(queue.submit(InferenceClient::Summarization, "overflow".to_string(), 0).is_err());
+
+        let dispatched = queue.dispatch(0);
+        assert_eq!(dispatched.len(), 2); // Rate limited
+        let late = queue.complete(&dispatched[0].id, Ok("late".to_string()), 2_000).unwrap();
+        assert_eq!(late.status, InferenceStatus::TimedOut);
+        assert!(late.output.is_none());
+
+        queue.expire(2_000); // Remaining queued and in-flight requests expire
+        let stats = queue.stats();
+        assert_eq!(stats.timed_out, 3);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.queued + stats.in_flight, 0);
+    }
+
+    #[test]
+    fn test_run_exports_metrics() {
+        let mut queue = InferenceQueue::default();
+        queue.submit(InferenceClient::WisdomEngine, "Context: morning\nObservation: Teams → Gmail".to_string(), 0).unwrap();
+        queue.submit(InferenceClient::CoPilot, String::new(), 0).unwrap();
+
+        let results = queue.run(&mut TemplateBackend, 0);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].output.as_deref().unwrap().contains("Teams → Gmail"));
+        assert_eq!(results[1].status, InferenceStatus::Failed);
+
+        let mut analytics = AnalyticsAggregator::new();
+        queue.export_metrics(&mut analytics);
+        assert!(analytics.get_metrics_by_category(MetricCategory::Operations).iter().any(|m| m.name == "inference.completed" && m.value == 1.0));
+    }
+
+    #[tokio::test]
+    async fn test_run_async_completes_concurrent_batch() {
+        let mut queue = InferenceQueue::new(InferenceConfig { max_concurrent: 2, ..InferenceConfig::default() });
+        queue.submit(InferenceClient::WisdomEngine, "Insight".to_string(), 0).unwrap();
+        queue.submit(InferenceClient::CoPilot, "Calm message".to_string(), 0).unwrap();
+        queue.submit(InferenceClient::Summarization, String::new(), 0).unwrap();
+
+        let backend = Arc::new(crate::async_api::AsyncManager::new("llm", TemplateBackend));
+        let results = queue.run_async(backend, 0).await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results.iter().filter(|r| r.status == InferenceStatus::Completed).count(), 2);
+        assert_eq!(results[2].status, InferenceStatus::Failed);
+        assert_eq!(queue.stats().in_flight, 0);
+    }
+}
@@ -0,0 +1,229 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L120
+/// Write-Ahead Action Journal
+/// Durable record of action intent, sandbox diff and step completion for crash recovery
+
+use crate::types::*;
+use crate::enterprise::{Approval, ApproverRole};
+use crate::auto_action::ReverseOperation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Journal record kind, written before the corresponding side effect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalRecord {
+    Intent { action: Action },
+    Approved { approver_id: String, role: ApproverRole },
+    SandboxDiff { diff: String },
+    ReverseOp { op: ReverseOperation },
+    StepCompleted { step: String },
+    Committed,
+    RolledBack,
+}
+
+/// Journal entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub action_id: String,
+    pub record: JournalRecord,
+    pub timestamp: i64,
+}
+
+/// Action that was started but neither committed nor rolled back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InFlightAction {
+    pub action_id: String,
+    pub action: Option<Action>,
+    pub sandbox_diff: Option<String>,
+    pub reverse_ops: Vec<ReverseOperation>,
+    pub completed_steps: Vec<String>,
+    pub approvals: Vec<Approval>,
+}
+
+/// Append-only write-ahead journal (JSONL, fsync per record)
+pub struct ActionJournal {
+    path: PathBuf,
+    next_seq: u64,
+}
+
+impl ActionJournal {
+    /// Open (or create) journal at path
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        info!("ActionJournal::open: Opening journal at {:?}", path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create journal dir: {}", e))?;
+        }
+
+        // Terminate a torn final record so new appends start on a fresh line
+        if let Ok(contents) = std::fs::read(&path) {
+            if !contents.is_empty() && !contents.ends_with(b"\n") {
+                let mut file = OpenOptions::new()
+                    .append(true)
+                    .open(&path)
+                    .map_err(|e| format!("Failed to open journal: {}", e))?;
+                writeln!(file).map_err(|e| format!("Failed to repair journal: {}", e))?;
+            }
+        }
+
+        let mut journal = Self { path, next_seq: 0 };
+        journal.next_seq = journal.read_entries()?.last().map(|e| e.seq + 1).unwrap_or(0);
+        Ok(journal)
+    }
+
+    /// Append a record and flush it to disk before returning
+    pub fn append(&mut self, action_id: &str, record: JournalRecord) -> Result<(), String> {
+        let entry = JournalEntry {
+            seq: self.next_seq,
+            action_id: action_id.to_string(),
+            record,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        let line = serde_json::to_string(&entry).map_err(|e| format!("Failed to encode journal entry: {}", e))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Failed to open journal: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("Failed to write journal: {}", e))?;
+        file.sync_all().map_err(|e| format!("Failed to sync journal: {}", e))?;
+
+        self.next_seq += 1;
+        Ok(())
+    }
+
+    /// Read all entries; a torn final line from a crash is ignored
+    pub fn read_entries(&self) -> Result<Vec<JournalEntry>, String> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to open journal: {}", e)),
+        };
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| format!("Failed to read journal: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalEntry>(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => info!("ActionJournal::read_entries: Skipping unreadable record: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Get actions that were in flight when the journal was last written
+    pub fn in_flight(&self) -> Result<Vec<InFlightAction>, String> {
+        let mut order: Vec<String> = Vec::new();
+        let mut actions: HashMap<String, InFlightAction> = HashMap::new();
+
+        for entry in self.read_entries()? {
+            match entry.record {
+                JournalRecord::Committed | JournalRecord::RolledBack => {
+                    actions.remove(&entry.action_id);
+                    order.retain(|id| id != &entry.action_id);
+                }
+                record => {
+                    let in_flight = actions.entry(entry.action_id.clone()).or_insert_with(|| {
+                        order.push(entry.action_id.clone());
+                        InFlightAction {
+                            action_id: entry.action_id.clone(),
+                            action: None,
+                            sandbox_diff: None,
+                            reverse_ops: Vec::new(),
+                            completed_steps: Vec::new(),
+                            approvals: Vec::new(),
+                        }
+                    });
+                    match record {
+                        JournalRecord::Intent { action } => in_flight.action = Some(action),
+                        JournalRecord::SandboxDiff { diff } => in_flight.sandbox_diff = Some(diff),
+                        JournalRecord::ReverseOp { op } => in_flight.reverse_ops.push(op),
+                        JournalRecord::StepCompleted { step } => in_flight.completed_steps.push(step),
+                        JournalRecord::Approved { approver_id, role } => in_flight.approvals.push(Approval {
+                            approver_id,
+                            role,
+                            approved_at: entry.timestamp,
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(order.into_iter().filter_map(|id| actions.remove(&id)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_journal_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("athenos_journal_{}_{}.jsonl", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn test_action() -> Action {
+        Action {
+            action_type: ActionType::AutomationMacro,
+            description: "Test macro".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+        }
+    }
+
+    #[test]
+    fn test_append_and_reopen() {
+        let path = temp_journal_path("reopen");
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("action_1", JournalRecord::Intent { action: test_action() }).unwrap();
+        journal.append("action_1", JournalRecord::Committed).unwrap();
+
+        let reopened = ActionJournal::open(path.clone()).unwrap();
+        assert_eq!(reopened.next_seq, 2);
+        assert_eq!(reopened.read_entries().unwrap().len(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_in_flight_detection() {
+        let path = temp_journal_path("in_flight");
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("done", JournalRecord::Intent { action: test_action() }).unwrap();
+        journal.append("done", JournalRecord::Committed).unwrap();
+        journal.append("pending", JournalRecord::Intent { action: test_action() }).unwrap();
+        journal.append("pending", JournalRecord::SandboxDiff { diff: "Undo action".to_string() }).unwrap();
+
+        let in_flight = journal.in_flight().unwrap();
+        assert_eq!(in_flight.len(), 1);
+        assert_eq!(in_flight[0].action_id, "pending");
+        assert_eq!(in_flight[0].sandbox_diff, Some("Undo action".to_string()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_torn_record_ignored() {
+        let path = temp_journal_path("torn");
+        let mut journal = ActionJournal::open(path.clone()).unwrap();
+        journal.append("action_1", JournalRecord::Intent { action: test_action() }).unwrap();
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"seq\":1,\"action_id\":\"act").unwrap();
+
+        assert_eq!(journal.read_entries().unwrap().len(), 1);
+        
+        let mut reopened = ActionJournal::open(path.clone()).unwrap();
+        reopened.append("action_1", JournalRecord::Committed).unwrap();
+        assert_eq!(reopened.read_entries().unwrap().len(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+}
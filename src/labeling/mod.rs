@@ -0,0 +1,291 @@
+/// Phase: D | Source: TRAINING CONCEPT.txt#L40-57
+/// Observation Labeling
+/// Human labels for PatternType and appropriate Action, stored as supervised training data
+
+use crate::trainer::ModelTrainer;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Human label for one observation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObservationLabel {
+    pub observation_id: String,
+    pub pattern_type: PatternType,
+    pub action_type: ActionType, // Action the annotator judged appropriate
+    pub annotator_id: String,
+    pub confidence: f64, // Annotator's confidence in [0, 1]
+    pub labeled_at: i64,
+}
+
+impl ObservationLabel {
+    /// Check the label is usable as training data
+    pub fn validate(&self) -> Result<(), String> {
+        if self.observation_id.trim().is_empty() {
+            return Err("Label must reference an observation".to_string());
+        }
+        if self.annotator_id.trim().is_empty() {
+            return Err("Label must name its annotator".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.confidence) {
+            return Err(format!("Confidence must be in [0, 1], got {}", self.confidence));
+        }
+        Ok(())
+    }
+
+    fn action_confidence(&self) -> Confidence {
+        if self.confidence >= 0.8 {
+            Confidence::High
+        } else if self.confidence >= 0.5 {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        }
+    }
+}
+
+/// Label store, persisted as append-only JSONL (later labels for an observation win)
+pub struct LabelStore {
+    path: Option<PathBuf>,
+    labels: HashMap<String, ObservationLabel>, // observation_id -> latest label
+}
+
+impl LabelStore {
+    /// Create in-memory label store
+    pub fn new() -> Self {
+        info!("LabelStore::new: Creating in-memory label store");
+        Self { path: None, labels: HashMap::new() }
+    }
+
+    /// Open label store at `path`; a missing file is an empty store
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        info!("LabelStore::open: Opening {}", path.display());
+        let mut labels = HashMap::new();
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                for (index, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+                    let label: ObservationLabel = serde_json::from_str(line)
+                        .map_err(|e| format!("Invalid label at {}:{}: {}", path.display(), index + 1, e))?;
+                    labels.insert(label.observation_id.clone(), label);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read labels: {}", e)),
+        }
+        Ok(Self { path: Some(path), labels })
+    }
+
+    /// Record a label, appending it to the backing file
+    pub fn record(&mut self, label: ObservationLabel) -> Result<(), String> {
+        label.validate()?;
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&label).map_err(|e| format!("Failed to encode label: {}", e))?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Failed to open labels: {}", e))?;
+            writeln!(file, "{}", line).map_err(|e| format!("Failed to write label: {}", e))?;
+        }
+        info!("LabelStore::record: {} labeled {:?} by {}", label.observation_id, label.pattern_type, label.annotator_id);
+        self.labels.insert(label.observation_id.clone(), label);
+        Ok(())
+    }
+
+    /// Label for an observation, if any
+    pub fn get_label(&self, observation_id: &str) -> Option<&ObservationLabel> {
+        self.labels.get(observation_id)
+    }
+
+    /// Number of labeled observations
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Whether no observation is labeled
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Most recent observations without a label
+    pub fn unlabeled<'a>(&self, observations: &'a [Observation], limit: usize) -> Vec<&'a Observation> {
+        let mut pending: Vec<&Observation> = observations.iter().filter(|o| !self.labels.contains_key(&o.id)).collect();
+        pending.sort_by_key(|o| std::cmp::Reverse(o.timestamp));
+        pending.truncate(limit);
+        pending
+    }
+
+    /// Observations with human actions and confidence applied where labeled, for the trainer's dataset
+    pub fn apply_labels(&self, observations: &[Observation]) -> Vec<Observation> {
+        observations
+            .iter()
+            .map(|obs| {
+                let mut labeled = obs.clone();
+                if let Some(label) = self.labels.get(&obs.id) {
+                    labeled.action.action_type = label.action_type.clone();
+                    labeled.action.confidence = label.action_confidence();
+                }
+                labeled
+            })
+            .collect()
+    }
+
+    /// Tell the trainer about every label so retraining is scheduled as labels accumulate
+    pub fn feed_trainer(&self, trainer: &mut ModelTrainer) -> usize {
+        let mut fed = 0;
+        for label in self.labels.values() {
+            if trainer.record_label(label) {
+                fed += 1;
+            }
+        }
+        info!("LabelStore::feed_trainer: {} new labels for the trainer", fed);
+        fed
+    }
+}
+
+impl Default for LabelStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_snake_case<T: serde::de::DeserializeOwned>(value: &str, kind: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).map_err(|_| format!("Unknown {}: {}", kind, value))
+}
+
+/// Parse "<pattern_type> <action_type> [confidence]" as typed by the annotator
+pub fn parse_label_input(observation_id: &str, input: &str, annotator_id: &str, now: i64) -> Result<ObservationLabel, String> {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return Err("Expected: <pattern_type> <action_type> [confidence]".to_string());
+    }
+    let confidence = match parts.get(2) {
+        Some(value) => value.parse::<f64>().map_err(|_| format!("Invalid confidence: {}", value))?,
+        None => 1.0,
+    };
+    let label = ObservationLabel {
+        observation_id: observation_id.to_string(),
+        pattern_type: parse_snake_case(parts[0], "pattern type")?,
+        action_type: parse_snake_case(parts[1], "action type")?,
+        annotator_id: annotator_id.to_string(),
+        confidence,
+        labeled_at: now,
+    };
+    label.validate()?;
+    Ok(label)
+}
+
+/// Interactive labeling: present recent unlabeled observations and record the annotator's answers
+/// Empty input skips an observation, "q" ends the session; returns the number of labels recorded
+pub fn run_session<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    observations: &[Observation],
+    store: &mut LabelStore,
+    annotator_id: &str,
+    limit: usize,
+) -> Result<usize, String> {
+    let pending: Vec<Observation> = store.unlabeled(observations, limit).into_iter().cloned().collect();
+    let io_err = |e: std::io::Error| format!("Labeling I/O failed: {}", e);
+    writeln!(output, "{} unlabeled observations. Answer: <pattern_type> <action_type> [confidence], blank to skip, q to quit", pending.len()).map_err(io_err)?;
+
+    let mut recorded = 0;
+    for obs in &pending {
+        loop {
+            writeln!(output, "\n[{}] {} ({:?})", obs.id, obs.observation.join(" → "), obs.profile).map_err(io_err)?;
+            writeln!(output, "  suggested: {:?} — {}", obs.action.action_type, obs.action.description).map_err(io_err)?;
+            write!(output, "label> ").map_err(io_err)?;
+            output.flush().map_err(io_err)?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line).map_err(io_err)? == 0 {
+                return Ok(recorded);
+            }
+            let line = line.trim();
+            match line {
+                "" => break,
+                "q" => return Ok(recorded),
+                _ => match parse_label_input(&obs.id, line, annotator_id, chrono::Utc::now().timestamp()) {
+                    Ok(label) => {
+                        store.record(label)?;
+                        recorded += 1;
+                        break;
+                    }
+                    Err(e) => writeln!(output, "  {}", e).map_err(io_err)?,
+                },
+            }
+        }
+    }
+    Ok(recorded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(id: &str, timestamp: i64) -> Observation {
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::MicroNudge,
+                description: "Nudge".to_string(),
+                confidence: Confidence::Low,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_session_records_labels_for_recent_observations() {
+        let observations = vec![observation("old", 100), observation("new", 200), observation("mid", 150)];
+        let mut store = LabelStore::new();
+        let input = "workflow_sequence automation_macro 0.9\n\nnot_a_pattern focus_mode\ndebugging_loop preemptive_debug_assistant 2\ndebugging_loop preemptive_debug_assistant 0.6\n";
+        let mut output = Vec::new();
+
+        let recorded = run_session(input.as_bytes(), &mut output, &observations, &mut store, "ann_1", 10).unwrap();
+        assert_eq!(recorded, 2);
+        assert_eq!(store.get_label("new").unwrap().pattern_type, PatternType::WorkflowSequence); // Most recent first
+        assert!(store.get_label("mid").is_none()); // Skipped
+        assert_eq!(store.get_label("old").unwrap().annotator_id, "ann_1");
+        let transcript = String::from_utf8(output).unwrap();
+        assert!(transcript.contains("Unknown pattern type: not_a_pattern"));
+        assert!(transcript.contains("Confidence must be in [0, 1]"));
+        assert_eq!(store.unlabeled(&observations, 10).len(), 1);
+    }
+
+    #[test]
+    fn test_labels_persist_and_become_training_data() {
+        let path = std::env::temp_dir().join(format!("athenos_labels_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let mut store = LabelStore::open(&path).unwrap();
+        store.record(parse_label_input("a", "workflow_sequence automation_macro 0.9", "ann_1", 1).unwrap()).unwrap();
+        store.record(parse_label_input("a", "context_switching focus_mode 0.5", "ann_2", 2).unwrap()).unwrap();
+
+        let reopened = LabelStore::open(&path).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert_eq!(reopened.get_label("a").unwrap().annotator_id, "ann_2"); // Latest label wins
+
+        let training = reopened.apply_labels(&[observation("a", 1), observation("b", 2)]);
+        assert_eq!(training[0].action.action_type, ActionType::FocusMode);
+        assert_eq!(training[0].action.confidence, Confidence::Medium);
+        assert_eq!(training[1].action.action_type, ActionType::MicroNudge);
+
+        let mut trainer = ModelTrainer::default();
+        assert_eq!(reopened.feed_trainer(&mut trainer), 1);
+        assert_eq!(reopened.feed_trainer(&mut trainer), 0); // Already counted
+        std::fs::remove_file(&path).ok();
+    }
+}
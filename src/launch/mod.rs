@@ -2,6 +2,7 @@
 /// Public Launch Preparation
 /// Prepare for public launch: marketing narrative, onboarding playbook, support ops
 
+use crate::beta::{OnboardingConfig, OnboardingConsent};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -33,6 +34,32 @@ pub struct OnboardingPlaybook {
     pub estimated_duration_min: usize,
 }
 
+impl OnboardingPlaybook {
+    /// Playbook mirroring the guided onboarding stages, one step per unlocked feature
+    pub fn guided(config: &OnboardingConfig) -> Self {
+        let steps = config
+            .stages
+            .iter()
+            .enumerate()
+            .map(|(i, stage)| {
+                let min_days = config.stage_min_days(stage);
+                let mut requirements = vec![format!("day {}", min_days + 1), format!("{} observations", stage.min_observations)];
+                requirements.extend(stage.consents.iter().map(|c| match c {
+                    OnboardingConsent::BehavioralLogging => "behavioral logging consent".to_string(),
+                    OnboardingConsent::AnyAutomationScope => "an automation scope".to_string(),
+                }));
+                OnboardingStep {
+                    step_number: i + 1,
+                    title: format!("Unlock {:?}", stage.feature),
+                    description: format!("Unlocks after {}", requirements.join(", ")),
+                    required: stage.consents.is_empty(),
+                }
+            })
+            .collect();
+        Self { steps, estimated_duration_min: 5 }
+    }
+}
+
 /// Support ticket
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SupportTicket {
@@ -197,5 +224,14 @@ mod tests {
         let checklist = manager.get_readiness_checklist();
         assert!(checklist.overall_ready);
     }
+
+    #[test]
+    fn test_guided_playbook_follows_stages() {
+        let playbook = OnboardingPlaybook::guided(&OnboardingConfig::default());
+        assert_eq!(playbook.steps.len(), 4);
+        assert!(playbook.steps[0].required);
+        assert_eq!(playbook.steps[1].description, "Unlocks after day 8, 200 observations, behavioral logging consent");
+        assert!(playbook.steps[3].description.contains("an automation scope"));
+    }
 }
 
@@ -37,4 +37,46 @@ pub mod multi_region;
 pub mod knowledge_loop;
 pub mod api;
 pub mod launch;
+pub mod attention;
+pub mod categorizer;
+pub mod journal;
+pub mod telemetry;
+pub mod model_registry;
+pub mod dataset;
+pub mod forecast;
+pub mod notify;
+pub mod suppression;
+pub mod safety_filter;
+pub mod power;
+pub mod project_context;
+pub mod approval;
+pub mod backup;
+pub mod habits;
+pub mod title_privacy;
+pub mod trainer;
+pub mod inference;
+pub mod devtools;
+pub mod config;
+pub mod analytics_export;
+pub mod inbox;
+pub mod focus_session;
+pub mod offline;
+pub mod signature;
+pub mod sampling;
+pub mod locale;
+pub mod timeline;
+pub mod feature_flags;
+pub mod os_capture;
+pub mod bus;
+pub mod labeling;
+pub mod evidence_archive;
+pub mod simulation;
+pub mod device_sync;
+pub mod async_api;
+pub mod cache;
+pub mod event_schema;
+#[cfg(feature = "aggregation-server")]
+pub mod aggregation;
+pub mod purge;
+pub mod gate_policy;
 
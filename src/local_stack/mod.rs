@@ -3,9 +3,13 @@
 /// Temporal metrics, embeddings, affect signals
 
 use crate::types::*;
+use crate::consent::{DataDisposition, RevocationHandler, BEHAVIORAL_LOGGING_CAPABILITY};
+use crate::title_privacy::RAW_WINDOW_TITLES_CAPABILITY;
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use std::path::Path;
+use tracing::{info, warn};
 
 /// Temporal metrics extracted from events
 /// Source: Athenos_AI_Strategy.md#L24
@@ -18,11 +22,205 @@ pub struct TemporalMetrics {
     pub session_duration_min: f64,
 }
 
+/// Persisted temporal metrics row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsRecord {
+    pub observation_id: String,
+    pub recorded_at: i64,
+    pub metrics: TemporalMetrics,
+}
+
+/// Persisted embedding row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingRecord {
+    pub observation_id: String,
+    pub recorded_at: i64,
+    pub embedding: Vec<f32>,
+}
+
+/// Pluggable persistence for the feature store
+/// Writes replace any earlier row for the same observation; ranges are [from, to)
+pub trait StorageBackend: Send {
+    fn backend_name(&self) -> &str;
+    fn save_metrics(&mut self, record: &MetricsRecord) -> Result<(), String>;
+    fn save_embedding(&mut self, record: &EmbeddingRecord) -> Result<(), String>;
+    fn load_metrics(&self, from: i64, to: i64) -> Result<Vec<MetricsRecord>, String>;
+    fn load_embeddings(&self, from: i64, to: i64) -> Result<Vec<EmbeddingRecord>, String>;
+    /// Delete every embedding; returns the number deleted
+    fn purge_embeddings(&mut self) -> Result<usize, String>;
+    /// Delete every metrics row; returns the number deleted
+    fn purge_metrics(&mut self) -> Result<usize, String>;
+}
+
+/// In-memory backend (tests, ephemeral sessions)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    metrics: HashMap<String, MetricsRecord>,
+    embeddings: HashMap<String, EmbeddingRecord>,
+}
+
+impl MemoryBackend {
+    /// Create empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn backend_name(&self) -> &str {
+        "memory"
+    }
+
+    fn save_metrics(&mut self, record: &MetricsRecord) -> Result<(), String> {
+        self.metrics.insert(record.observation_id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn save_embedding(&mut self, record: &EmbeddingRecord) -> Result<(), String> {
+        self.embeddings.insert(record.observation_id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn load_metrics(&self, from: i64, to: i64) -> Result<Vec<MetricsRecord>, String> {
+        let mut records: Vec<MetricsRecord> = self.metrics.values().filter(|r| r.recorded_at >= from && r.recorded_at < to).cloned().collect();
+        records.sort_by_key(|r| r.recorded_at);
+        Ok(records)
+    }
+
+    fn load_embeddings(&self, from: i64, to: i64) -> Result<Vec<EmbeddingRecord>, String> {
+        let mut records: Vec<EmbeddingRecord> = self.embeddings.values().filter(|r| r.recorded_at >= from && r.recorded_at < to).cloned().collect();
+        records.sort_by_key(|r| r.recorded_at);
+        Ok(records)
+    }
+
+    fn purge_embeddings(&mut self) -> Result<usize, String> {
+        let purged = self.embeddings.len();
+        self.embeddings.clear();
+        Ok(purged)
+    }
+
+    fn purge_metrics(&mut self) -> Result<usize, String> {
+        let purged = self.metrics.len();
+        self.metrics.clear();
+        Ok(purged)
+    }
+}
+
+/// SQLite backend; metrics are stored as JSON, embeddings as little-endian f32 blobs
+pub struct SqliteBackend {
+    conn: Connection,
+}
+
+impl SqliteBackend {
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+        }
+        let conn = Connection::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS metrics (
+                 observation_id TEXT PRIMARY KEY,
+                 recorded_at INTEGER NOT NULL,
+                 data TEXT NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS metrics_recorded_at ON metrics (recorded_at);
+             CREATE TABLE IF NOT EXISTS embeddings (
+                 observation_id TEXT PRIMARY KEY,
+                 recorded_at INTEGER NOT NULL,
+                 data BLOB NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS embeddings_recorded_at ON embeddings (recorded_at);",
+        )
+        .map_err(|e| format!("Failed to initialize feature store schema: {}", e))?;
+        info!("SqliteBackend::open: Opened feature store at {}", path.display());
+        Ok(Self { conn })
+    }
+}
+
+impl StorageBackend for SqliteBackend {
+    fn backend_name(&self) -> &str {
+        "sqlite"
+    }
+
+    fn save_metrics(&mut self, record: &MetricsRecord) -> Result<(), String> {
+        let data = serde_json::to_string(&record.metrics).map_err(|e| format!("Failed to encode metrics: {}", e))?;
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO metrics (observation_id, recorded_at, data) VALUES (?1, ?2, ?3)",
+                params![record.observation_id, record.recorded_at, data],
+            )
+            .map_err(|e| format!("Failed to save metrics for {}: {}", record.observation_id, e))?;
+        Ok(())
+    }
+
+    fn save_embedding(&mut self, record: &EmbeddingRecord) -> Result<(), String> {
+        let data: Vec<u8> = record.embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO embeddings (observation_id, recorded_at, data) VALUES (?1, ?2, ?3)",
+                params![record.observation_id, record.recorded_at, data],
+            )
+            .map_err(|e| format!("Failed to save embedding for {}: {}", record.observation_id, e))?;
+        Ok(())
+    }
+
+    fn load_metrics(&self, from: i64, to: i64) -> Result<Vec<MetricsRecord>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT observation_id, recorded_at, data FROM metrics WHERE recorded_at >= ?1 AND recorded_at < ?2 ORDER BY recorded_at")
+            .map_err(|e| format!("Failed to query metrics: {}", e))?;
+        let rows = stmt
+            .query_map(params![from, to], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?)))
+            .map_err(|e| format!("Failed to query metrics: {}", e))?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (observation_id, recorded_at, data) = row.map_err(|e| format!("Failed to read metrics row: {}", e))?;
+            let metrics = serde_json::from_str(&data).map_err(|e| format!("Corrupt metrics for {}: {}", observation_id, e))?;
+            records.push(MetricsRecord { observation_id, recorded_at, metrics });
+        }
+        Ok(records)
+    }
+
+    fn load_embeddings(&self, from: i64, to: i64) -> Result<Vec<EmbeddingRecord>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT observation_id, recorded_at, data FROM embeddings WHERE recorded_at >= ?1 AND recorded_at < ?2 ORDER BY recorded_at")
+            .map_err(|e| format!("Failed to query embeddings: {}", e))?;
+        let rows = stmt
+            .query_map(params![from, to], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, Vec<u8>>(2)?)))
+            .map_err(|e| format!("Failed to query embeddings: {}", e))?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (observation_id, recorded_at, data) = row.map_err(|e| format!("Failed to read embedding row: {}", e))?;
+            if data.len() % 4 != 0 {
+                return Err(format!("Corrupt embedding for {}: {} bytes", observation_id, data.len()));
+            }
+            let embedding = data.chunks_exact(4).map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect();
+            records.push(EmbeddingRecord { observation_id, recorded_at, embedding });
+        }
+        Ok(records)
+    }
+    fn purge_embeddings(&mut self) -> Result<usize, String> {
+        self.conn.execute("DELETE FROM embeddings", []).map_err(|e| format!("Failed to purge embeddings: {}", e))
+    }
+
+    fn purge_metrics(&mut self) -> Result<usize, String> {
+        self.conn.execute("DELETE FROM metrics", []).map_err(|e| format!("Failed to purge metrics: {}", e))
+    }
+}
+
 /// Feature store for cognitive analysis
 /// Source: Athenos_AI_Strategy.md#L24
 pub struct FeatureStore {
     metrics: HashMap<String, TemporalMetrics>,
     embeddings: HashMap<String, Vec<f32>>, // Simple embedding storage
+    observations: Vec<Observation>, // Imported observation units
+    recorded_at: HashMap<String, i64>, // Metrics timestamp per observation
+    backend: Option<Box<dyn StorageBackend>>, // Write-through persistence
 }
 
 impl FeatureStore {
@@ -32,12 +230,50 @@ impl FeatureStore {
         Self {
             metrics: HashMap::new(),
             embeddings: HashMap::new(),
+            observations: Vec::new(),
+            recorded_at: HashMap::new(),
+            backend: None,
         }
     }
 
-    /// Store temporal metrics for an observation
+    /// Create feature store backed by persistent storage, loading everything already stored
+    pub fn with_backend(backend: Box<dyn StorageBackend>) -> Result<Self, String> {
+        let metrics = backend.load_metrics(i64::MIN, i64::MAX)?;
+        let embeddings = backend.load_embeddings(i64::MIN, i64::MAX)?;
+        info!(
+            "FeatureStore::with_backend: Loaded {} metrics and {} embeddings from {}",
+            metrics.len(),
+            embeddings.len(),
+            backend.backend_name()
+        );
+        let mut store = Self::new();
+        for record in metrics {
+            store.recorded_at.insert(record.observation_id.clone(), record.recorded_at);
+            store.metrics.insert(record.observation_id, record.metrics);
+        }
+        for record in embeddings {
+            store.embeddings.insert(record.observation_id, record.embedding);
+        }
+        store.backend = Some(backend);
+        Ok(store)
+    }
+
+    /// Store temporal metrics for an observation, recorded now
     pub fn store_metrics(&mut self, observation_id: String, metrics: TemporalMetrics) {
+        self.store_metrics_at(observation_id, metrics, chrono::Utc::now().timestamp());
+    }
+
+    /// Store temporal metrics for an observation recorded at `recorded_at`
+    /// Persistence failures are logged; the in-memory copy is always kept
+    pub fn store_metrics_at(&mut self, observation_id: String, metrics: TemporalMetrics, recorded_at: i64) {
         info!("FeatureStore::store_metrics: Storing metrics for {}", observation_id);
+        if let Some(backend) = self.backend.as_mut() {
+            let record = MetricsRecord { observation_id: observation_id.clone(), recorded_at, metrics: metrics.clone() };
+            if let Err(e) = backend.save_metrics(&record) {
+                warn!("FeatureStore::store_metrics: {}", e);
+            }
+        }
+        self.recorded_at.insert(observation_id.clone(), recorded_at);
         self.metrics.insert(observation_id, metrics);
     }
 
@@ -49,6 +285,13 @@ impl FeatureStore {
     /// Store embedding vector
     pub fn store_embedding(&mut self, observation_id: String, embedding: Vec<f32>) {
         info!("FeatureStore::store_embedding: Storing embedding for {} (dim={})", observation_id, embedding.len());
+        if let Some(backend) = self.backend.as_mut() {
+            let recorded_at = self.recorded_at.get(&observation_id).copied().unwrap_or_else(|| chrono::Utc::now().timestamp());
+            let record = EmbeddingRecord { observation_id: observation_id.clone(), recorded_at, embedding: embedding.clone() };
+            if let Err(e) = backend.save_embedding(&record) {
+                warn!("FeatureStore::store_embedding: {}", e);
+            }
+        }
         self.embeddings.insert(observation_id, embedding);
     }
 
@@ -57,6 +300,47 @@ impl FeatureStore {
         self.embeddings.get(observation_id)
     }
 
+    /// Ingest an observation unit, deriving temporal metrics from its metric map
+    pub fn ingest_observation(&mut self, observation: Observation) {
+        let metric = |key: &str| observation.metrics.get(key).copied().unwrap_or(0.0);
+        let metrics = TemporalMetrics {
+            time_to_first_action_min: metric("time_to_first_code_min"),
+            focus_duration_min: metric("focus_duration_min"),
+            context_switch_count: metric("context_switch_count") as usize,
+            repeat_count: metric("repeat_count") as usize,
+            session_duration_min: metric("session_duration_min"),
+        };
+        self.store_metrics_at(observation.id.clone(), metrics, observation.timestamp);
+        self.observations.push(observation);
+    }
+
+    /// Metrics recorded in [from, to), oldest first; queries the backend when one is attached
+    pub fn metrics_in_range(&self, from: i64, to: i64) -> Result<Vec<MetricsRecord>, String> {
+        if let Some(backend) = self.backend.as_ref() {
+            return backend.load_metrics(from, to);
+        }
+        let mut records: Vec<MetricsRecord> = self
+            .metrics
+            .iter()
+            .filter_map(|(id, metrics)| {
+                let recorded_at = *self.recorded_at.get(id)?;
+                (recorded_at >= from && recorded_at < to).then(|| MetricsRecord { observation_id: id.clone(), recorded_at, metrics: metrics.clone() })
+            })
+            .collect();
+        records.sort_by_key(|r| r.recorded_at);
+        Ok(records)
+    }
+
+    /// Embeddings recorded in [from, to), oldest first; requires a backend
+    pub fn embeddings_in_range(&self, from: i64, to: i64) -> Result<Vec<EmbeddingRecord>, String> {
+        self.backend.as_ref().ok_or("Feature store has no storage backend".to_string())?.load_embeddings(from, to)
+    }
+
+    /// Get ingested observations
+    pub fn get_observations(&self) -> &[Observation] {
+        &self.observations
+    }
+
     /// Compute focus stability percentage from metrics
     /// Source: Strategic_Reinforcements_Gap_Closures.md#L25
     pub fn compute_focus_stability(&self, observation_ids: &[String]) -> f64 {
@@ -84,6 +368,38 @@ impl FeatureStore {
     }
 }
 
+impl RevocationHandler for FeatureStore {
+    fn revocation_handler_name(&self) -> &str {
+        "feature_store"
+    }
+
+    /// Embeddings can encode raw window titles; purge them (in memory and persisted) when title consent goes
+    /// Metrics and observations are derived from behavioral logs; losing that consent purges everything
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        let behavioral = capability == BEHAVIORAL_LOGGING_CAPABILITY;
+        if !behavioral && capability != RAW_WINDOW_TITLES_CAPABILITY {
+            return Ok((DataDisposition::NotAffected, 0));
+        }
+        let mut purged = self.embeddings.len();
+        self.embeddings.clear();
+        if let Some(backend) = self.backend.as_mut() {
+            purged = purged.max(backend.purge_embeddings()?);
+        }
+        if behavioral {
+            let mut metrics = self.metrics.len();
+            self.metrics.clear();
+            self.recorded_at.clear();
+            if let Some(backend) = self.backend.as_mut() {
+                metrics = metrics.max(backend.purge_metrics()?);
+            }
+            purged += metrics + self.observations.len();
+            self.observations.clear();
+        }
+        info!("FeatureStore::on_consent_revoked: Purged {} records", purged);
+        Ok((DataDisposition::Purged, purged))
+    }
+}
+
 impl Default for FeatureStore {
     fn default() -> Self {
         Self::new()
@@ -143,5 +459,59 @@ mod tests {
         // (60 + 45) / (90 + 60) * 100 = 70.0
         assert!((stability - 70.0).abs() < 0.1);
     }
-}
 
+    fn metrics(focus: f64) -> TemporalMetrics {
+        TemporalMetrics {
+            time_to_first_action_min: 1.0,
+            focus_duration_min: focus,
+            context_switch_count: 0,
+            repeat_count: 0,
+            session_duration_min: 60.0,
+        }
+    }
+
+    #[test]
+    fn test_sqlite_backend_survives_reopen() {
+        let path = std::env::temp_dir().join(format!("athenos_feature_store_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut store = FeatureStore::with_backend(Box::new(SqliteBackend::open(&path).unwrap())).unwrap();
+        store.store_metrics_at("obs_001".to_string(), metrics(30.0), 1_000);
+        store.store_embedding("obs_001".to_string(), vec![0.5, -1.25, 3.0]);
+        drop(store);
+
+        let reopened = FeatureStore::with_backend(Box::new(SqliteBackend::open(&path).unwrap())).unwrap();
+        assert_eq!(reopened.get_metrics("obs_001").unwrap().focus_duration_min, 30.0);
+        assert_eq!(reopened.get_embedding("obs_001").unwrap(), &vec![0.5, -1.25, 3.0]);
+        assert_eq!(reopened.embeddings_in_range(0, 2_000).unwrap()[0].recorded_at, 1_000);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_metrics_in_range() {
+        let mut persisted = FeatureStore::with_backend(Box::new(MemoryBackend::new())).unwrap();
+        let mut in_memory = FeatureStore::new();
+        for store in [&mut persisted, &mut in_memory] {
+            store.store_metrics_at("mon".to_string(), metrics(10.0), 100);
+            store.store_metrics_at("tue".to_string(), metrics(20.0), 200);
+            store.store_metrics_at("wed".to_string(), metrics(30.0), 300);
+
+            let ids: Vec<String> = store.metrics_in_range(100, 300).unwrap().into_iter().map(|r| r.observation_id).collect();
+            assert_eq!(ids, vec!["mon".to_string(), "tue".to_string()]);
+        }
+        assert!(in_memory.embeddings_in_range(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_title_consent_revocation_purges_embeddings() {
+        let mut store = FeatureStore::with_backend(Box::new(MemoryBackend::new())).unwrap();
+        store.store_metrics_at("obs_001".to_string(), metrics(30.0), 100);
+        store.store_embedding("obs_001".to_string(), vec![0.1, 0.2]);
+
+        assert_eq!(store.on_consent_revoked("cloud_sync").unwrap(), (DataDisposition::NotAffected, 0));
+        assert_eq!(store.on_consent_revoked(RAW_WINDOW_TITLES_CAPABILITY).unwrap(), (DataDisposition::Purged, 1));
+        assert!(store.get_embedding("obs_001").is_none());
+        assert!(store.embeddings_in_range(0, 1_000).unwrap().is_empty());
+        assert!(store.get_metrics("obs_001").is_some());
+    }
+}
@@ -0,0 +1,298 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L102
+/// Report Localization and Unit Preferences
+/// Per-user locale (dates, number formats, translated templates) and unit preferences for reports and nudges
+
+use crate::scheduling::timezone::parse_timezone;
+use serde::{Deserialize, Serialize};
+
+/// Supported locales
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    EnUs,
+    EnGb,
+    FrFr,
+    DeDe,
+    EsEs,
+}
+
+impl Locale {
+    /// Parse a BCP 47 tag ("fr-FR", "de_DE", "en"); unknown tags fall back to en-US
+    pub fn from_tag(tag: &str) -> Self {
+        match tag.to_lowercase().replace('_', "-").as_str() {
+            "en-gb" => Locale::EnGb,
+            t if t.starts_with("fr") => Locale::FrFr,
+            t if t.starts_with("de") => Locale::DeDe,
+            t if t.starts_with("es") => Locale::EsEs,
+            _ => Locale::EnUs,
+        }
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb => '.',
+            Locale::FrFr | Locale::DeDe | Locale::EsEs => ',',
+        }
+    }
+
+    fn thousands_separator(&self) -> char {
+        match self {
+            Locale::EnUs | Locale::EnGb => ',',
+            Locale::FrFr => '\u{202f}', // Narrow no-break space
+            Locale::DeDe | Locale::EsEs => '.',
+        }
+    }
+
+    fn date_pattern(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "%m/%d/%Y",
+            Locale::EnGb | Locale::FrFr | Locale::EsEs => "%d/%m/%Y",
+            Locale::DeDe => "%d.%m.%Y",
+        }
+    }
+}
+
+/// Clock display preference
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockFormat {
+    H12,
+    H24,
+}
+
+/// Duration display preference
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationUnit {
+    Minutes,
+    Hours,
+    Auto, // Minutes below an hour, hours and minutes above
+}
+
+/// Per-user locale and unit preferences
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LocalePreferences {
+    pub locale: Locale,
+    pub clock: ClockFormat,
+    pub duration_unit: DurationUnit,
+    pub timezone: String, // IANA name timestamps are shown in (the same setting the scheduler reads hours in)
+}
+
+impl Default for LocalePreferences {
+    fn default() -> Self {
+        Self {
+            locale: Locale::EnUs,
+            clock: ClockFormat::H12,
+            duration_unit: DurationUnit::Minutes,
+            timezone: "UTC".to_string(),
+        }
+    }
+}
+
+/// Formats values and fills translated templates for one user's preferences
+#[derive(Debug, Clone, Default)]
+pub struct Localizer {
+    prefs: LocalePreferences,
+}
+
+impl Localizer {
+    /// Create localizer for the given preferences
+    pub fn new(prefs: LocalePreferences) -> Self {
+        Self { prefs }
+    }
+
+    /// Active preferences
+    pub fn preferences(&self) -> &LocalePreferences {
+        &self.prefs
+    }
+
+    /// Unknown timezone names fall back to UTC
+    fn local_time(&self, timestamp: i64) -> Option<chrono::DateTime<chrono_tz::Tz>> {
+        let tz = parse_timezone(&self.prefs.timezone).unwrap_or(chrono_tz::Tz::UTC);
+        chrono::DateTime::from_timestamp(timestamp, 0).map(|dt| dt.with_timezone(&tz))
+    }
+
+    /// Date in the locale's order and separators
+    pub fn format_date(&self, timestamp: i64) -> String {
+        self.local_time(timestamp)
+            .map(|dt| dt.format(self.prefs.locale.date_pattern()).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Localize an ISO date ("2024-01-31"); unparsable input is returned unchanged
+    pub fn format_iso_date(&self, date: &str) -> String {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d.format(self.prefs.locale.date_pattern()).to_string())
+            .unwrap_or_else(|_| date.to_string())
+    }
+
+    /// Time of day on the preferred clock
+    pub fn format_time(&self, timestamp: i64) -> String {
+        self.local_time(timestamp)
+            .map(|dt| match self.prefs.clock {
+                ClockFormat::H12 => dt.format("%-I:%M %p").to_string(),
+                ClockFormat::H24 => dt.format("%H:%M").to_string(),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Number with locale decimal and thousands separators
+    pub fn format_number(&self, value: f64, decimals: usize) -> String {
+        let fixed = format!("{:.*}", decimals, value.abs());
+        let (int_part, frac_part) = match fixed.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (fixed.as_str(), None),
+        };
+
+        let mut grouped = String::new();
+        for (i, digit) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push(self.prefs.locale.thousands_separator());
+            }
+            grouped.push(digit);
+        }
+        if let Some(frac_part) = frac_part {
+            grouped.push(self.prefs.locale.decimal_separator());
+            grouped.push_str(frac_part);
+        }
+        if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            grouped.insert(0, '-');
+        }
+        grouped
+    }
+
+    /// Duration in the preferred unit
+    pub fn format_duration(&self, minutes: f64) -> String {
+        match self.prefs.duration_unit {
+            DurationUnit::Minutes => format!("{} min", self.format_number(minutes, 0)),
+            DurationUnit::Hours => format!("{} h", self.format_number(minutes / 60.0, 1)),
+            DurationUnit::Auto => {
+                let total = minutes.max(0.0).round() as i64;
+                if total < 60 {
+                    format!("{} min", total)
+                } else if total % 60 == 0 {
+                    format!("{} h", total / 60)
+                } else {
+                    format!("{} h {} min", total / 60, total % 60)
+                }
+            }
+        }
+    }
+
+    /// Fill a translated template; `{name}` placeholders are replaced from `args`
+    /// Keys missing for the locale fall back to English
+    pub fn translate(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let template = template(self.prefs.locale, key)
+            .or_else(|| template(Locale::EnUs, key))
+            .unwrap_or(key);
+        args.iter().fold(template.to_string(), |out, (name, value)| out.replace(&format!("{{{}}}", name), value))
+    }
+}
+
+fn template(locale: Locale, key: &str) -> Option<&'static str> {
+    let text = match (locale, key) {
+        (Locale::EnUs | Locale::EnGb, "report.title") => "Cognitive Report {date}",
+        (Locale::EnUs | Locale::EnGb, "report.time_saved") => "Time saved: {value}",
+        (Locale::EnUs | Locale::EnGb, "report.focus_stability") => "Focus stability: {value}",
+        (Locale::EnUs | Locale::EnGb, "report.cognitive_clarity") => "Cognitive clarity: {value}",
+        (Locale::EnUs | Locale::EnGb, "report.patterns") => "Patterns",
+        (Locale::EnUs | Locale::EnGb, "report.suggestions") => "Suggestions",
+        (Locale::EnUs | Locale::EnGb, "report.daily_title") => "Daily cognitive report ({date})",
+        (Locale::EnUs | Locale::EnGb, "report.weekly_title") => "Weekly cognitive report ({date})",
+        (Locale::EnUs | Locale::EnGb, "insight.repeated_sequence") => "Repeated sequence: {sequence}",
+        (Locale::EnUs | Locale::EnGb, "insight.expected_savings") => "Expected to save {duration}",
+        (Locale::EnUs | Locale::EnGb, "insight.meeting_load") => "Meeting load {pct}% of the working day ({duration})",
+        (Locale::EnUs | Locale::EnGb, "insight.back_to_back") => "Back-to-back meetings, longest block {duration}",
+        (Locale::EnUs | Locale::EnGb, "insight.refocus") => "{duration} on average to refocus after meetings",
+        (Locale::EnUs | Locale::EnGb, "nudge.tip") => "Tip: {tip}",
+
+        (Locale::FrFr, "report.title") => "Rapport cognitif {date}",
+        (Locale::FrFr, "report.time_saved") => "Temps gagné : {value}",
+        (Locale::FrFr, "report.focus_stability") => "Stabilité de la concentration : {value}",
+        (Locale::FrFr, "report.cognitive_clarity") => "Clarté cognitive : {value}",
+        (Locale::FrFr, "report.patterns") => "Schémas",
+        (Locale::FrFr, "report.suggestions") => "Suggestions",
+        (Locale::FrFr, "report.daily_title") => "Rapport cognitif quotidien ({date})",
+        (Locale::FrFr, "report.weekly_title") => "Rapport cognitif hebdomadaire ({date})",
+        (Locale::FrFr, "insight.repeated_sequence") => "Séquence répétée : {sequence}",
+        (Locale::FrFr, "insight.expected_savings") => "Gain estimé : {duration}",
+        (Locale::FrFr, "insight.meeting_load") => "Réunions : {pct} % de la journée de travail ({duration})",
+        (Locale::FrFr, "insight.back_to_back") => "Réunions enchaînées, bloc le plus long {duration}",
+        (Locale::FrFr, "insight.refocus") => "{duration} en moyenne pour se reconcentrer après une réunion",
+        (Locale::FrFr, "nudge.tip") => "Astuce : {tip}",
+
+        (Locale::DeDe, "report.title") => "Kognitiver Bericht {date}",
+        (Locale::DeDe, "report.time_saved") => "Gesparte Zeit: {value}",
+        (Locale::DeDe, "report.focus_stability") => "Fokusstabilität: {value}",
+        (Locale::DeDe, "report.cognitive_clarity") => "Kognitive Klarheit: {value}",
+        (Locale::DeDe, "report.patterns") => "Muster",
+        (Locale::DeDe, "report.suggestions") => "Vorschläge",
+        (Locale::DeDe, "report.daily_title") => "Täglicher kognitiver Bericht ({date})",
+        (Locale::DeDe, "report.weekly_title") => "Wöchentlicher kognitiver Bericht ({date})",
+        (Locale::DeDe, "insight.repeated_sequence") => "Wiederholte Abfolge: {sequence}",
+        (Locale::DeDe, "insight.expected_savings") => "Voraussichtlich {duration} gespart",
+        (Locale::DeDe, "insight.meeting_load") => "Meetings: {pct} % des Arbeitstags ({duration})",
+        (Locale::DeDe, "insight.back_to_back") => "Meetings ohne Pause, längster Block {duration}",
+        (Locale::DeDe, "insight.refocus") => "Durchschnittlich {duration} bis zur Refokussierung nach Meetings",
+        (Locale::DeDe, "nudge.tip") => "Tipp: {tip}",
+
+        (Locale::EsEs, "report.title") => "Informe cognitivo {date}",
+        (Locale::EsEs, "report.time_saved") => "Tiempo ahorrado: {value}",
+        (Locale::EsEs, "report.focus_stability") => "Estabilidad del enfoque: {value}",
+        (Locale::EsEs, "report.cognitive_clarity") => "Claridad cognitiva: {value}",
+        (Locale::EsEs, "report.patterns") => "Patrones",
+        (Locale::EsEs, "report.suggestions") => "Sugerencias",
+        (Locale::EsEs, "report.daily_title") => "Informe cognitivo diario ({date})",
+        (Locale::EsEs, "report.weekly_title") => "Informe cognitivo semanal ({date})",
+        (Locale::EsEs, "insight.repeated_sequence") => "Secuencia repetida: {sequence}",
+        (Locale::EsEs, "insight.expected_savings") => "Ahorro estimado: {duration}",
+        (Locale::EsEs, "insight.meeting_load") => "Reuniones: {pct} % de la jornada ({duration})",
+        (Locale::EsEs, "insight.back_to_back") => "Reuniones seguidas, bloque más largo {duration}",
+        (Locale::EsEs, "insight.refocus") => "{duration} de media para volver a concentrarse tras las reuniones",
+        (Locale::EsEs, "nudge.tip") => "Consejo: {tip}",
+
+        _ => return None,
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn localizer(locale: Locale, clock: ClockFormat, duration_unit: DurationUnit) -> Localizer {
+        Localizer::new(LocalePreferences { locale, clock, duration_unit, timezone: "UTC".to_string() })
+    }
+
+    #[test]
+    fn test_dates_numbers_and_clock() {
+        let ts = 1_706_720_400; // 2024-01-31 17:00 UTC
+        let us = localizer(Locale::EnUs, ClockFormat::H12, DurationUnit::Minutes);
+        let de = localizer(Locale::DeDe, ClockFormat::H24, DurationUnit::Minutes);
+        assert_eq!(us.format_date(ts), "01/31/2024");
+        assert_eq!(de.format_date(ts), "31.01.2024");
+        assert_eq!(de.format_iso_date("2024-01-31"), "31.01.2024");
+        assert_eq!(us.format_time(ts), "5:00 PM");
+        assert_eq!(de.format_time(ts), "17:00");
+        // Timezones follow daylight saving: 17:00 UTC is 18:00 in a Paris winter and 19:00 in a Paris summer
+        let paris = Localizer::new(LocalePreferences { timezone: "Europe/Paris".to_string(), ..de.preferences().clone() });
+        assert_eq!(paris.format_time(ts), "18:00");
+        assert_eq!(paris.format_time(ts + 182 * 86400), "19:00");
+        assert_eq!(us.format_number(1234567.891, 2), "1,234,567.89");
+        assert_eq!(de.format_number(-1234.5, 1), "-1.234,5");
+        assert_eq!(Locale::from_tag("fr_CA"), Locale::FrFr);
+    }
+
+    #[test]
+    fn test_duration_units_and_translation_fallback() {
+        assert_eq!(localizer(Locale::EnUs, ClockFormat::H12, DurationUnit::Minutes).format_duration(90.0), "90 min");
+        assert_eq!(localizer(Locale::FrFr, ClockFormat::H24, DurationUnit::Hours).format_duration(90.0), "1,5 h");
+        let auto = localizer(Locale::EnGb, ClockFormat::H24, DurationUnit::Auto);
+        assert_eq!((auto.format_duration(45.0), auto.format_duration(125.0)), ("45 min".to_string(), "2 h 5 min".to_string()));
+
+        let es = localizer(Locale::EsEs, ClockFormat::H24, DurationUnit::Auto);
+        assert_eq!(es.translate("nudge.tip", &[("tip", "Ctrl+K")]), "Consejo: Ctrl+K");
+        assert_eq!(es.translate("unknown.key", &[]), "unknown.key");
+    }
+}
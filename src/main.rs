@@ -38,6 +38,46 @@ mod multi_region;
 mod knowledge_loop;
 mod api;
 mod launch;
+mod attention;
+mod categorizer;
+mod journal;
+mod telemetry;
+mod model_registry;
+mod dataset;
+mod forecast;
+mod notify;
+mod suppression;
+mod safety_filter;
+mod power;
+mod project_context;
+mod approval;
+mod backup;
+mod habits;
+mod title_privacy;
+mod trainer;
+mod inference;
+mod devtools;
+mod config;
+mod analytics_export;
+mod inbox;
+mod focus_session;
+mod offline;
+mod signature;
+mod sampling;
+mod locale;
+mod timeline;
+mod feature_flags;
+mod os_capture;
+mod bus;
+mod labeling;
+mod evidence_archive;
+mod simulation;
+mod device_sync;
+mod async_api;
+mod cache;
+mod event_schema;
+mod purge;
+mod gate_policy;
 
 use tracing::info;
 use types::*;
@@ -49,97 +89,556 @@ fn main() {
     info!("Athenos AI starting - Phase B");
     info!("Source: Athenos_AI_Strategy.md#L107-117");
     
+    // Bulk dataset commands: athenos import <file.jsonl> | export <file.jsonl> | label <labels.json>
+    // "Forget me" commands: athenos forget <capability> (run once the consent-derived stores are up)
+    // Shortcut runs: athenos run-shortcut <shortcut_id> (an approved shortcut, run through the system OS driver)
+    // Marketplace submissions: athenos publish-shortcut <shortcut_id> (once its runs have realized savings)
+    let cli_args = match parse_cli_args(&std::env::args().skip(1).collect::<Vec<String>>()) {
+        Ok(cli_args) => cli_args,
+        Err(e) => {
+            eprintln!("{}\nUsage: athenos [import|export|label <file>]... [forget <capability>]... [run-shortcut|publish-shortcut <shortcut_id>]...", e);
+            std::process::exit(2);
+        }
+    };
+    
     // Phase A components
-    let consent_ledger = privacy::ConsentLedger::new();
+    let consent_ledger_path = std::path::PathBuf::from("./sandbox/consent_ledger.enc");
+    // The ledger key is derived from a passphrase; a ledger that failed to load is never overwritten
+    let (consent_ledger, ledger_encryption) = match std::env::var("ATHENOS_LEDGER_PASSPHRASE")
+        .map_err(|_| "ATHENOS_LEDGER_PASSPHRASE is not set".to_string())
+        .and_then(|passphrase| privacy::EncryptionManager::from_passphrase(&passphrase, "./sandbox/consent_ledger.salt"))
+        .and_then(|key| privacy::ConsentLedger::load(&consent_ledger_path, &key).map(|ledger| (ledger, key)))
+    {
+        Ok((ledger, key)) => (ledger, Some(key)),
+        Err(e) => {
+            info!("Consent ledger unavailable, consent will not persist: {}", e);
+            (privacy::ConsentLedger::new(), None)
+        }
+    };
     info!("Privacy kernel initialized - all opt-out by default");
     
     let mut edge_observer = edge::EdgeObserver::new(1000);
+    match title_privacy::WindowTitleProcessor::open(std::path::Path::new("./sandbox/title_hash.key")) {
+        Ok(title_processor) => edge_observer.set_title_processor(title_processor),
+        Err(e) => info!("Title hashing key unavailable, project hashes will change on restart: {}", e),
+    }
+    let safety_filter = safety_filter::SafetyFilter::new();
+    edge_observer.set_safety_filter(safety_filter.clone());
     info!("Edge observer initialized");
     
-    let feature_store = local_stack::FeatureStore::new();
+    let mut feature_store = match local_stack::SqliteBackend::open("./sandbox/feature_store.db")
+        .and_then(|backend| local_stack::FeatureStore::with_backend(Box::new(backend)))
+    {
+        Ok(store) => store,
+        Err(e) => {
+            info!("Feature store persistence unavailable, metrics will not survive restarts: {}", e);
+            local_stack::FeatureStore::new()
+        }
+    };
     info!("Feature store initialized");
     
+    if let Err(e) = dataset::run_commands(&cli_args.dataset_commands, &mut feature_store) {
+        info!("Dataset command failed: {}", e);
+    }
+    let imported_observations = feature_store.get_observations().to_vec();
+    
+    let config_path = std::env::var("ATHENOS_CONFIG").unwrap_or_else(|_| "./athenos_config.json".to_string());
+    let mut config_watcher = config::ConfigWatcher::open(std::path::PathBuf::from(&config_path), std::path::PathBuf::from("./sandbox/applied_config.json"), std::path::PathBuf::from("./sandbox/config_audit.jsonl"));
+    // Focus hours are learned, scheduled and reported in one timezone; read it before the config listeners exist
+    let configured_timezone = std::fs::read_to_string(&config_path)
+        .ok()
+        .and_then(|json| config::AthenosConfig::from_json(&json).ok())
+        .map(|config| config.timezone)
+        .unwrap_or_else(|| config_watcher.current().timezone.clone());
+    let mut focus_hour_learner = scheduling::focus_hours::FocusHourLearner::default();
+    if let Err(e) = focus_hour_learner.set_timezone(&configured_timezone) {
+        info!("Learning focus hours in UTC: {}", e);
+    }
+    if let Err(e) = focus_hour_learner.learn("local_user", &feature_store, chrono::Utc::now().timestamp()) {
+        info!("Focus-hour learning failed: {}", e);
+    }
+    
     let sandbox_runner = sandbox::SandboxRunner::default();
     info!("Sandbox runner initialized");
     
-    let report_generator = report::ReportGenerator::new(feature_store);
-    info!("Report generator initialized");
+    let locale_prefs = locale::LocalePreferences {
+        locale: locale::Locale::from_tag(&std::env::var("ATHENOS_LOCALE").unwrap_or_default()),
+        timezone: configured_timezone.clone(),
+        ..locale::LocalePreferences::default()
+    };
+    let mut report_generator = report::ReportGenerator::new(feature_store);
+    report_generator.set_locale(locale_prefs.clone());
+    for observation in &imported_observations {
+        report_generator.ingest_observation(observation);
+    }
+    info!("Report generator initialized (live report: {:.1} min saved so far)", report_generator.snapshot_report().time_saved_minutes);
     
     // Phase B components
     let mut pattern_detector = models::PatternDetector::new();
+    let detector_weights_path = std::path::Path::new("./sandbox/pattern_detector_weights.json");
+    if let Ok(json) = std::fs::read_to_string(detector_weights_path) {
+        match serde_json::from_str(&json).map_err(|e| e.to_string()).and_then(|weights| pattern_detector.import_weights(weights)) {
+            Ok(()) => info!("Pattern detector weights restored from {:?}", detector_weights_path),
+            Err(e) => info!("Ignoring saved pattern detector weights: {}", e),
+        }
+    }
     info!("Pattern detector initialized");
     
     let mut recommendation_ranker = models::RecommendationRanker::new();
     info!("Recommendation ranker initialized");
     
+    if !imported_observations.is_empty() {
+        pattern_detector.train(&imported_observations);
+        recommendation_ranker.train(&imported_observations);
+        info!("Trained on {} imported observations", imported_observations.len());
+        let saved = serde_json::to_string_pretty(&pattern_detector.export_weights())
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(detector_weights_path, json).map_err(|e| e.to_string()));
+        if let Err(e) = saved {
+            info!("Could not save pattern detector weights: {}", e);
+        }
+    }
+    
+    info!("Generated text safety filter initialized");
+    
     let mut wisdom_engine = wisdom::WisdomEngine::new();
+    wisdom_engine.set_safety_filter(safety_filter.clone());
+    match dataset::corpus::load_corpus_file(std::path::Path::new("./data/athenos_seed.jsonl"), &dataset::corpus::CorpusConfig::default()) {
+        Ok(corpus) => {
+            for issue in corpus.report.errors.iter().chain(&corpus.report.duplicates) {
+                info!("Seed corpus line {} skipped: {}", issue.line, issue.message);
+            }
+            // Kept for a training backend; the template engine has none, so nothing is trained at boot
+            info!("Seed corpus loaded ({} train / {} eval examples)", corpus.train.len(), corpus.eval.len());
+        }
+        Err(e) => info!("Seed corpus unavailable: {}", e),
+    }
     info!("Wisdom Engine initialized");
     
     let mut pattern_miner = pattern_miner::PatternMiner::new();
-    info!("Pattern miner initialized");
+    let mined_patterns = pattern_miner.mine_patterns(&edge_observer.get_recent_events(1000));
+    info!("Pattern miner initialized ({} patterns in recent events)", mined_patterns.len());
+    for mined in pattern_miner.frequent_sequences().iter().take(3) {
+        info!("Frequent sequence {} (support {}, {} occurrences)", mined.sequence.join(" → "), mined.support, mined.occurrences);
+    }
+    
+    // TTL grants persist next to the ledger under the same key; a file that failed to load is never overwritten
+    let micro_consents_path = std::path::PathBuf::from("./sandbox/micro_consents.enc");
+    let (mut micro_consent_manager, micro_consents_key) = match &ledger_encryption {
+        Some(key) => match consent::MicroConsentManager::load(consent_ledger.clone(), &micro_consents_path, key) {
+            Ok(manager) => (manager, Some(key)),
+            Err(e) => {
+                info!("Micro-consents unavailable, grants will not persist: {}", e);
+                (consent::MicroConsentManager::with_ledger(consent_ledger.clone()), None)
+            }
+        },
+        None => (consent::MicroConsentManager::with_ledger(consent_ledger.clone()), None),
+    };
+    let persist_consents = |manager: &consent::MicroConsentManager| {
+        if let Some(key) = &ledger_encryption {
+            if let Err(e) = manager.consent_ledger().save(&consent_ledger_path, key) {
+                info!("Failed to persist consent ledger: {}", e);
+            }
+        }
+        if let Some(key) = micro_consents_key {
+            if let Err(e) = manager.save(&micro_consents_path, key) {
+                info!("Failed to persist micro-consents: {}", e);
+            }
+        }
+    };
+    let mut renewals_prompted: std::collections::HashSet<(String, i64, consent::RenewalReason)> = std::collections::HashSet::new();
+    let expired_consents = micro_consent_manager.expire_consents(chrono::Utc::now().timestamp());
+    info!(
+        "Micro-consent manager initialized ({} expired, {} awaiting renewal)",
+        expired_consents.len(),
+        micro_consent_manager.consents_needing_renewal(chrono::Utc::now().timestamp()).len()
+    );
+    edge_observer.apply_consent(&micro_consent_manager);
+    let sensor_readings = edge_observer.poll_sensors(chrono::Utc::now().timestamp());
+    info!("Sensor inputs polled ({} readings taken with consent)", sensor_readings.len());
+    let mut emotion_estimator = emotion::EmotionEstimator::new();
+    emotion_estimator.ingest_sensor_readings(&sensor_readings);
+    
+    let mut rag_index = match rag::CandleEmbedder::load(
+        std::path::Path::new("./models/embeddings.safetensors"),
+        std::path::Path::new("./models/embeddings.vocab"),
+    ) {
+        Ok(embedder) => rag::RAGIndex::with_embedder(Box::new(embedder)),
+        Err(e) => {
+            info!("Embedding model unavailable, using hashing embeddings: {}", e);
+            rag::RAGIndex::new()
+        }
+    };
+    info!("RAG index initialized");
+    
+    let mut data_purge_coordinator = match purge::DataPurgeCoordinator::open(
+        std::path::PathBuf::from("./sandbox/purge_signing.key"),
+        std::path::PathBuf::from("./sandbox/purge_receipts.json"),
+    ) {
+        Ok(coordinator) => coordinator,
+        Err(e) => {
+            info!("Purge receipts unavailable, new receipts will not be verifiable after restart: {}", e);
+            purge::DataPurgeCoordinator::new()
+        }
+    };
+    // Purge before the miner and estimator move onto the bus, so every module holding derived data is reached
+    let purges = expired_consents.iter().map(|capability| (capability, "expired"))
+        .chain(cli_args.forget_capabilities.iter().map(|capability| (capability, "forget requested")));
+    for (capability, reason) in purges {
+        let handlers: &mut [&mut dyn consent::RevocationHandler] = &mut [&mut edge_observer, report_generator.feature_store_mut(), &mut rag_index, &mut pattern_miner, &mut emotion_estimator];
+        match data_purge_coordinator.forget(&mut micro_consent_manager, capability, Some(reason.to_string()), handlers) {
+            Ok(receipt) => info!("Purged data derived under {}: {} deleted, {} anonymized (receipt {})", capability, receipt.records_deleted, receipt.records_anonymized, receipt.id),
+            Err(e) => info!("Purge of {} failed: {}", capability, e),
+        }
+    }
+    info!("Data purge coordinator initialized ({} receipts)", data_purge_coordinator.receipts().len());
+    
+    // Live pipeline: the edge observer publishes every recorded event to the bus consumers
+    let bus_runtime = tokio::runtime::Runtime::new().expect("Failed to start event bus runtime");
+    let event_bus = bus::EventBus::default();
+    edge_observer.attach_bus(&event_bus);
+    let pattern_miner_task = event_bus.spawn_subscriber(bus_runtime.handle(), pattern_miner, 64);
+    let emotion_task = event_bus.spawn_subscriber(bus_runtime.handle(), emotion_estimator, 64);
+    let analytics_task = event_bus.spawn_subscriber(bus_runtime.handle(), analytics::AnalyticsAggregator::new(), 64);
+    info!("Event bus initialized ({} subscribers)", event_bus.subscriber_count());
+    
+    let suppression_list = match suppression::SuppressionList::open(std::path::PathBuf::from("./sandbox/suppression.json"), 30 * 86400) {
+        Ok(list) => list,
+        Err(e) => {
+            info!("Suppression list unavailable, suppressions will not persist: {}", e);
+            suppression::SuppressionList::new()
+        }
+    };
+    info!("Suppression list initialized");
+    
+    let mut gate_policy = gate_policy::SharedGatePolicy::new(gate_policy::GatePolicy::default());
+    info!("Quality-gate policy initialized");
     
     let mut shortcut_generator = shortcut::ShortcutGenerator::new();
+    shortcut_generator.set_suppression_list(suppression_list.clone());
+    shortcut_generator.set_gate_policy(gate_policy.clone());
     info!("Shortcut generator initialized");
     
-    let mut micro_consent_manager = consent::MicroConsentManager::new();
-    info!("Micro-consent manager initialized");
+    info!("Window-title processing applied (raw titles discarded without consent)");
+    match os_capture::OsEventCapture::for_platform(os_capture::CaptureConfig::default()) {
+        Some(mut os_event_capture) => {
+            let captured = os_event_capture.poll_into(&mut edge_observer, chrono::Utc::now().timestamp());
+            info!("OS event capture active ({} events captured)", captured);
+        }
+        None => info!("OS event capture unavailable (build with --features os-capture)"),
+    }
     
-    let mut mood_adaptive_focus = emotion::MoodAdaptiveFocusMode::new();
-    info!("Mood-adaptive focus mode initialized");
+    let project_detector = project_context::ProjectContextDetector::new();
+    info!("Project context detector initialized (consent-gated)");
     
-    let mut rag_index = rag::RAGIndex::new();
-    info!("RAG index initialized");
+    let attention_service = attention::AttentionService::new();
+    info!("Attention service initialized");
+    
+    let mut mood_adaptive_focus = emotion::MoodAdaptiveFocusMode::with_attention(attention_service.clone());
+    info!("Mood-adaptive focus mode initialized");
     
     let mut replay_simulator = replay::ReplaySimulator::new();
+    replay_simulator.set_gate_policy(gate_policy.clone());
     info!("Replay simulator initialized");
+    let mut replay_trace = replay::trace::ReplayTrace::new();
+    for event in edge_observer.get_recent_events(1000) {
+        replay_trace.push(replay::trace::TraceEntry::Event(event));
+    }
+    let trace_report = replay_simulator.replay_trace(&replay_trace, &pattern_detector, &replay::trace::TraceReplayConfig::default());
+    info!("Trace replay: {} events on virtual clock, divergence {:.2} (gate {})", trace_report.events_replayed, trace_report.divergence_score, if trace_report.passed { "passed" } else { "failed" });
     
     let federated_coordinator = federated::FederatedLearningCoordinator::new(consent_ledger.clone());
     info!("Federated learning coordinator initialized");
     
-    let mut cohort_manager = cohort::CohortManager::new(200);
+    let mut cohort_manager = match privacy::Pseudonymizer::open(std::path::Path::new("./sandbox/pseudonym.key")) {
+        Ok(pseudonymizer) => cohort::CohortManager::with_pseudonymizer(200, pseudonymizer),
+        Err(e) => {
+            info!("Pseudonymization key unavailable, cohort pseudonyms will change on restart: {}", e);
+            cohort::CohortManager::new(200)
+        }
+    };
     info!("Cohort manager initialized (target: 200 users)");
+    let mut experiment_manager = cohort::experiment::ExperimentManager::open(std::path::PathBuf::from("./sandbox/experiments.json"))
+        .unwrap_or_else(|e| {
+            info!("Experiment state unavailable, starting fresh: {}", e);
+            cohort::experiment::ExperimentManager::new()
+        });
+    if experiment_manager.get_experiment(microlearning::NUDGE_WORDING_EXPERIMENT).is_none() {
+        if let Err(e) = experiment_manager.create_experiment(
+            microlearning::NUDGE_WORDING_EXPERIMENT,
+            "Microlearning nudge wording A vs B",
+            vec![cohort::experiment::Variant::new("A", 1.0), cohort::experiment::Variant::new("B", 1.0)],
+        ) {
+            info!("Experiment manager unavailable: {}", e);
+        }
+    }
+    info!("Experiment manager initialized ({} variants)", experiment_manager.variant_stats(microlearning::NUDGE_WORDING_EXPERIMENT).map(|s| s.len()).unwrap_or(0));
+    
+    let feature_flags = feature_flags::FeatureFlagRegistry::new();
+    info!("Feature flag registry initialized (canary routing via cohort segments)");
     
     info!("Phase B initialization complete");
     
     // Phase C components
-    let mut auto_action_synthesizer = auto_action::AutoActionSynthesizer::new();
+    let mut resource_scheduler = power::ResourceAwareScheduler::new();
+    info!("Resource-aware background scheduler initialized");
+    
+    let mut auto_action_synthesizer = match journal::ActionJournal::open(std::path::PathBuf::from("./sandbox/action_journal.jsonl")) {
+        Ok(action_journal) => auto_action::AutoActionSynthesizer::with_journal(action_journal),
+        Err(e) => {
+            info!("Action journal unavailable, running without crash recovery: {}", e);
+            auto_action::AutoActionSynthesizer::new()
+        }
+    };
+    if let Ok(report) = auto_action_synthesizer.recover_from_journal() {
+        info!("Journal recovery: {} completed, {} rolled back, {} failed to roll back", report.completed.len(), report.rolled_back.len(), report.failed.len());
+    }
+    auto_action_synthesizer.apply_consent(micro_consent_manager.consent_ledger());
+    auto_action_synthesizer.set_gate_policy(gate_policy.clone());
     info!("Auto-action synthesizer initialized");
     
-    let mut microlearning_generator = microlearning::MicrolearningNudgeGenerator::new();
+    let mut microlearning_generator = microlearning::MicrolearningNudgeGenerator::with_attention(attention_service.clone());
+    microlearning_generator.set_suppression_list(suppression_list.clone());
+    if cohort_manager.pseudonym_of("local_user").is_none() {
+        let profile = imported_observations.last().map(|o| o.profile.clone()).unwrap_or(UserProfile::Other);
+        cohort_manager.add_member("local_user".to_string(), profile);
+    }
+    match experiment_manager.assign(microlearning::NUDGE_WORDING_EXPERIMENT, "local_user", &cohort_manager) {
+        Ok(variant) => {
+            info!("Nudge wording variant: {}", variant);
+            microlearning_generator.set_wording_variant(Some(variant));
+        }
+        Err(e) => info!("Nudge wording experiment unavailable: {}", e),
+    }
     info!("Microlearning nudge generator initialized");
     
-    let mut calendar_agent = scheduling::CalendarNegotiationAgent::new();
-    info!("Calendar negotiation agent initialized");
+    let mut calendar_agent = scheduling::CalendarNegotiationAgent::with_attention(attention_service.clone());
+    if let Err(e) = calendar_agent.set_timezone(&configured_timezone) {
+        info!("Scheduling in UTC: {}", e);
+    }
+    let focus_hour_source = focus_hour_learner.apply("local_user", &mut calendar_agent);
+    info!("Calendar negotiation agent initialized (focus hours {:?}, {:?})", calendar_agent.focus_hours(), focus_hour_source);
+    let calendar_ics_path = std::path::PathBuf::from("./sandbox/calendar.ics");
+    if let Ok(ics_text) = std::fs::read_to_string(&calendar_ics_path) {
+        match scheduling::ics::parse_ics_document(&ics_text) {
+            Ok(import) => info!(
+                "Imported {} new calendar events from {} ({} skipped, {} recurring imported as first occurrence)",
+                calendar_agent.import_events(import.events), calendar_ics_path.display(), import.skipped.len(), import.recurring.len()
+            ),
+            Err(e) => info!("Failed to import {}: {}", calendar_ics_path.display(), e),
+        }
+    }
+    if let Ok(calendar_url) = std::env::var("ATHENOS_CALDAV_URL") {
+        let caldav_config = scheduling::caldav::CalDavConfig {
+            calendar_url,
+            username: std::env::var("ATHENOS_CALDAV_USER").unwrap_or_default(),
+            password: std::env::var("ATHENOS_CALDAV_PASSWORD").unwrap_or_default(),
+        };
+        let now = chrono::Utc::now().timestamp();
+        match scheduling::caldav::CalDavSync::connect(caldav_config).and_then(|mut sync| sync.pull(&mut calendar_agent, now - 86400, now + 14 * 86400)) {
+            Ok(imported) => info!("Synced {} new calendar events over CalDAV", imported),
+            Err(e) => info!("CalDAV sync unavailable: {}", e),
+        }
+    }
+    let calendar_conflicts = calendar_agent.detect_conflicts();
+    let calendar_resolutions = calendar_agent.resolve_conflicts(chrono::Utc::now().timestamp());
+    if !calendar_conflicts.is_empty() {
+        info!("Calendar has {} conflicting event pairs ({} moves proposed)", calendar_conflicts.len(), calendar_resolutions.len());
+    }
     
     let mut reflective_loop = reflection::ReflectiveReasoningLoop::new();
     info!("Reflective reasoning loop initialized");
     
-    let mut emotional_copilot = emotional_copilot::EmotionalCoPilot::new();
+    let mut emotional_copilot = emotional_copilot::EmotionalCoPilot::with_attention(attention_service.clone());
+    emotional_copilot.set_safety_filter(safety_filter.clone());
     info!("Emotional co-pilot initialized");
     
+    let mut emotion_forecaster = forecast::EmotionForecaster::new();
+    info!("Emotion forecaster initialized");
+    
+    let mut habit_tracker = habits::HabitTracker::new();
+    info!("Habit tracker initialized");
+    
     let mut victory_stream = victory::VictoryStream::new();
     info!("Victory stream initialized");
     
+    let mut focus_session_engine = focus_session::FocusSessionEngine::new(focus_session::FocusSessionConfig::default(), attention_service.clone());
+    for event in edge_observer.get_recent_events(1000) {
+        focus_session_engine.observe_event(&event, &mut victory_stream);
+    }
+    focus_session_engine.tick(chrono::Utc::now().timestamp());
+    info!("Focus-session engine initialized ({}-day streak)", focus_session_engine.get_streak().current_days);
+    let celebrations = victory_stream.publish(&mut [&mut emotional_copilot]);
+    info!("Published {} victory milestones to the emotional co-pilot", celebrations);
+    
+    // Sync only runs once the user pairs this device (ATHENOS_SYNC_PAIRING_CODE, with the categories they opt into)
+    // and points it at a relay directory (ATHENOS_SYNC_DIR); the pairing persists across boots
+    let sync_state_path = std::path::PathBuf::from("./sandbox/device_sync.json");
+    let device_sync = match device_sync::DeviceSync::open(&sync_state_path) {
+        Ok(None) => match std::env::var("ATHENOS_SYNC_PAIRING_CODE") {
+            Ok(code) => device_sync::PairingCode::parse(&code)
+                .and_then(|code| device_sync::DeviceSync::join(&format!("device_{:016x}", rand::random::<u64>()), &code))
+                .and_then(|mut paired| {
+                    for name in std::env::var("ATHENOS_SYNC_CATEGORIES").unwrap_or_default().split(',').filter(|n| !n.trim().is_empty()) {
+                        let category: device_sync::SyncCategory = serde_json::from_value(serde_json::json!(name.trim()))
+                            .map_err(|_| format!("Unknown sync category {}", name))?;
+                        paired.set_category(category, true)?;
+                    }
+                    paired.persist_at(sync_state_path.clone())?;
+                    Ok(Some(paired))
+                }),
+            Err(_) => Ok(None),
+        },
+        other => other,
+    };
+    match (device_sync, std::env::var("ATHENOS_SYNC_DIR")) {
+        (Ok(Some(mut device_sync)), Ok(relay_dir)) => {
+            let now = chrono::Utc::now().timestamp();
+            let captured = device_sync.capture_shortcuts(&shortcut_generator, now)
+                + device_sync.capture_suppressions(&suppression_list, now)
+                + device_sync.capture_victories(&victory_stream, now);
+            let mut sync_relay = device_sync::DirectoryRelay::new(relay_dir);
+            match device_sync
+                .sync(&mut sync_relay, &consent_ledger)
+                .and_then(|report| device_sync.apply(&report.applied, &mut shortcut_generator, &suppression_list, &mut victory_stream, now).map(|applied| (report.pushed, applied)))
+            {
+                Ok((pushed, applied)) => info!("Cross-device sync complete ({} entries pushed, {} applied)", pushed, applied),
+                Err(e) => info!("Cross-device sync idle ({} entries captured): {}", captured, e),
+            }
+        }
+        (Ok(Some(_)), Err(_)) => info!("Cross-device sync paired but no relay configured"),
+        (Ok(None), _) => info!("Cross-device sync not paired (opt-in)"),
+        (Err(e), _) => info!("Cross-device sync unavailable: {}", e),
+    }
+    
     let tpm_storage = security::TPMKeyStorage::new();
     info!("TPM key storage initialized");
     
     let mut threat_monitor = security::ThreatMonitor::new();
     info!("Threat monitor initialized");
     
-    let mut analytics_aggregator = analytics::AnalyticsAggregator::new();
+    event_bus.shutdown();
+    // The miner and estimator come back from the bus so later revocations still reach them
+    let mut pattern_miner = bus_runtime.block_on(pattern_miner_task).unwrap_or_else(|e| {
+        info!("Event bus pattern miner failed: {}", e);
+        pattern_miner::PatternMiner::new()
+    });
+    info!("Event bus: {} patterns in live events", pattern_miner.latest_patterns().len());
+    let mut emotion_estimator = bus_runtime.block_on(emotion_task).unwrap_or_else(|e| {
+        info!("Event bus emotion estimator failed: {}", e);
+        emotion::EmotionEstimator::new()
+    });
+    if let Some(estimate) = emotion_estimator.latest_estimate() {
+        info!("Event bus: live emotion estimate {:?}", estimate.emotional_state);
+    }
+    let mut analytics_aggregator = bus_runtime.block_on(analytics_task).unwrap_or_else(|e| {
+        info!("Event bus analytics subscriber failed: {}", e);
+        analytics::AnalyticsAggregator::new()
+    });
     info!("Analytics aggregator initialized");
     
     let mut plugin_registry = plugin::PluginRegistry::new();
     info!("Plugin registry initialized");
     
-    let mut beta_manager = beta::BetaOnboardingManager::new();
-    info!("Beta onboarding manager initialized");
+    let devtools_plugin = devtools::DevToolsPlugin::default();
+    plugin_registry.register_plugin(plugin::Plugin::metadata(&devtools_plugin).clone());
+    for event in edge_observer.get_recent_events(1000) {
+        devtools_plugin.observe_event(&event);
+    }
+    info!("Terminal/IDE integration plugin registered");
+    
+    // One publisher key set for plugin loading and marketplace listings
+    let trusted_publishers = plugin::signing::TrustedPublishers::new();
+    plugin_registry.set_trusted_publishers(trusted_publishers.clone());
+    match trusted_publishers.trust_from_file("./plugins/trusted_publishers.json") {
+        Ok(count) => info!("Plugin signature verification enabled ({} trusted publishers)", count),
+        Err(e) => info!("Failed to load trusted plugin publishers: {}", e),
+    }
+    
+    // Plugin interventions wait in the approval queue like any other proposed action
+    let mut plugin_interventions = Vec::new();
+    match plugin::wasm::WasmPluginRuntime::new(plugin::wasm::WasmLimits::default()) {
+        Ok(mut wasm_runtime) => match wasm_runtime.load_dir("./plugins", &mut plugin_registry) {
+            Ok(loaded) => {
+                info!("WASM plugin runtime initialized ({} plugins loaded)", loaded.len());
+                for plugin_id in loaded {
+                    match wasm_runtime.run(&plugin_id, &imported_observations, &micro_consent_manager, &mut plugin_registry) {
+                        Ok(report) => {
+                            info!("Plugin {} proposed {} interventions ({} denied host calls)", plugin_id, report.interventions.len(), report.denied_calls.len());
+                            plugin_interventions.extend(report.interventions);
+                        }
+                        Err(e) => info!("Plugin {} run failed: {}", plugin_id, e),
+                    }
+                }
+            }
+            Err(e) => info!("WASM plugin loading failed: {}", e),
+        },
+        Err(e) => info!("WASM plugin runtime unavailable: {}", e),
+    }
+    
+    let mut beta_manager = beta::BetaOnboardingManager::open(std::path::PathBuf::from("./sandbox/onboarding_funnel.json")).unwrap_or_else(|e| {
+        info!("Onboarding funnel unavailable, step history will not persist: {}", e);
+        beta::BetaOnboardingManager::new()
+    });
+    info!("Beta onboarding manager initialized ({} recurring issues tracked)", beta_manager.top_issues(5).len());
+    
+    let onboarding_path = std::path::PathBuf::from("./sandbox/onboarding.json");
+    let mut guided_onboarding = beta::GuidedOnboarding::load_or_start(&onboarding_path, chrono::Utc::now().timestamp(), beta::OnboardingConfig::default())
+        .unwrap_or_else(|e| {
+            info!("Onboarding state unavailable, starting fresh: {}", e);
+            beta::GuidedOnboarding::start(chrono::Utc::now().timestamp(), beta::OnboardingConfig::default())
+        });
+    guided_onboarding.update_observations(imported_observations.len());
+    let onboarding_progress = guided_onboarding.apply(chrono::Utc::now().timestamp(), &micro_consent_manager, &mut auto_action_synthesizer, &mut microlearning_generator, &mut shortcut_generator);
+    if let Err(e) = guided_onboarding.save(&onboarding_path) {
+        info!("Failed to persist onboarding state: {}", e);
+    }
+    info!("Guided onboarding: day {}, {:.0}% complete{}", onboarding_progress.day + 1, onboarding_progress.percent_complete * 100.0, if onboarding_progress.observe_only { " (observe-only)" } else { "" });
+    beta_manager.set_onboarding_playbook(&launch::OnboardingPlaybook::guided(guided_onboarding.config()));
+    if let Err(e) = beta_manager.sync_guided_onboarding("local_user", &guided_onboarding, &onboarding_progress, chrono::Utc::now().timestamp()) {
+        info!("Failed to record onboarding steps: {}", e);
+    }
+    for outcome in microlearning_generator.take_outcomes() {
+        if let Err(e) = experiment_manager.record_outcome(microlearning::NUDGE_WORDING_EXPERIMENT, "local_user", outcome.accepted, 0.0, &mut cohort_manager) {
+            info!("Failed to record nudge outcome for {}: {}", outcome.nudge_id, e);
+        }
+    }
+    for event in beta_manager.expire_stale_onboarding(chrono::Utc::now().timestamp()) {
+        info!("Onboarding step {} abandoned by {}", event.step_number, event.user_id);
+    }
+    for step in beta_manager.get_onboarding_funnel().steps {
+        info!("Onboarding step {} ({}): {} entered, {} completed, {:.0}% drop-off", step.step_number, step.title, step.entered, step.completed, step.drop_off_rate * 100.0);
+    }
     
     info!("Phase C initialization complete");
     
     // Phase D components
-    let mut rl_policy = rl_policy::RLPolicy::new();
-    info!("RL policy initialized");
+    // A policy that failed to load runs unpersisted rather than overwriting the stored one
+    let mut rl_policy = rl_policy::RLPolicy::open(std::path::PathBuf::from("./sandbox/rl_policy.json")).unwrap_or_else(|e| {
+        info!("RL policy unavailable, learning will not persist: {}", e);
+        rl_policy::RLPolicy::new()
+    });
+    rl_policy.set_suppression_list(suppression_list.clone());
+    info!("RL policy initialized ({} learned states)", rl_policy.get_statistics().total_states);
+    
+    let mut model_registry = model_registry::ModelRegistry::new();
+    info!("Model registry initialized (replay canary gating)");
+    
+    let mut model_trainer = trainer::ModelTrainer::new(trainer::TrainerConfig::default());
+    let label_store = labeling::LabelStore::open("./sandbox/labels.jsonl").unwrap_or_else(|e| {
+        info!("Labels unavailable: {}", e);
+        labeling::LabelStore::new()
+    });
+    label_store.feed_trainer(&mut model_trainer);
+    let training_runs = model_trainer.run_if_due(
+        chrono::Utc::now().timestamp(),
+        &label_store.apply_labels(&imported_observations),
+        &mut model_registry,
+        &mut recommendation_ranker,
+        &mut analytics_aggregator,
+        &resource_scheduler,
+    );
+    info!("Model trainer initialized ({} training runs)", training_runs.len());
     
     let mut expanded_rag = rag_expanded::ExpandedRAGIndex::new();
     info!("Expanded RAG index initialized");
@@ -148,31 +647,409 @@ fn main() {
     info!("Cognitive twin manager initialized");
     
     let mut marketplace = marketplace::AutomationMarketplace::new();
-    info!("Automation marketplace initialized");
+    marketplace.set_trusted_publishers(trusted_publishers.clone());
+    info!("Automation marketplace initialized (plugin API {})", plugin::PLUGIN_API_VERSION);
     
     let mut enterprise_console = enterprise::EnterpriseAdminConsole::new();
     info!("Enterprise admin console initialized");
+    auto_action_synthesizer.set_approval_mode(enterprise_console.get_approval_mode());
+    let license_status = enterprise_console.get_license_status(chrono::Utc::now().timestamp());
+    info!("Seat licensing: {}/{} seats assigned ({:?})", license_status.assigned_seats, license_status.seat_limit, license_status.enforcement);
     
     let mut soc2_tracker = compliance::SOC2ReadinessTracker::new();
     info!("SOC2 readiness tracker initialized");
     
-    let differential_privacy = compliance::DifferentialPrivacy::new(1.0);
+    let mut differential_privacy = compliance::DifferentialPrivacy::new(1.0);
     info!("Differential privacy initialized");
     
+    let focus_profiles = vec![enterprise::focus_heatmap::MemberFocusProfile::from_sessions("local", focus_session_engine.get_completed())];
+    match enterprise_console.get_team_focus_heatmap("local_team", &focus_profiles, &differential_privacy, enterprise::focus_heatmap::HeatmapConfig::default(), chrono::Utc::now().timestamp()) {
+        Ok(heatmap) => {
+            info!("Team focus heatmap ready ({} best focus windows)", heatmap.best_focus_windows(3).len());
+            calendar_agent.set_team_heatmap(Some(heatmap));
+        }
+        Err(e) => info!("Team focus heatmap unavailable: {}", e),
+    }
+    
     let mut multi_region_orchestrator = multi_region::MultiRegionOrchestrator::new();
     info!("Multi-region orchestrator initialized");
+    let focus_minutes: Vec<(&str, f64, i64)> = focus_session_engine
+        .get_completed()
+        .iter()
+        .filter_map(|session| session.ended_at.map(|ended_at| ("local", session.focus_secs as f64 / 60.0, ended_at)))
+        .collect();
+    for aggregate in multi_region_orchestrator.aggregate_with_privacy(&focus_minutes, chrono::Utc::now().timestamp()) {
+        info!("Average focus session in {}: {:.0} min across {} users (ε={})", aggregate.region_id, aggregate.value, aggregate.user_count, aggregate.epsilon);
+    }
     
     let mut knowledge_loop = knowledge_loop::KnowledgeExpansionLoop::new();
     info!("Knowledge expansion loop initialized");
     
     let mut developer_api = api::DeveloperAPIManager::new();
-    info!("Developer API manager initialized");
+    // Without a real transport the dispatcher stays detached rather than reporting deliveries that never happened
+    match api::webhooks::https_transport().and_then(|transport| {
+        api::webhooks::WebhookDispatcher::open(
+            bus_runtime.handle(),
+            transport,
+            api::webhooks::RetryPolicy::default(),
+            std::path::PathBuf::from("./sandbox/webhook_dead_letters.json"),
+        )
+    }) {
+        Ok(dispatcher) => developer_api.attach_dispatcher(dispatcher),
+        Err(e) => info!("Webhook worker not started: {}", e),
+    }
+    info!("Developer API manager initialized (payload schema v{}, supports {:?})", event_schema::CURRENT_SCHEMA_VERSION, event_schema::SUPPORTED_SCHEMA_VERSIONS);
+    
+    let mut approval_queue = approval::ApprovalQueue::new();
+    for proposal in shortcut_generator.get_pending_proposals() {
+        let item_id = approval_queue.enqueue_shortcut(proposal);
+        if let Ok(preview) = shortcut_generator.preview(&proposal.id, &sandbox_runner) {
+            let _ = approval_queue.attach_preview(&item_id, preview);
+        }
+    }
+    // Conflict moves need the user's approval like any other schedule change
+    for resolution in &calendar_resolutions {
+        approval_queue.enqueue_schedule(resolution, chrono::Utc::now().timestamp());
+    }
+    for intervention in &plugin_interventions {
+        match imported_observations.iter().find(|o| o.id == intervention.observation_id) {
+            Some(observation) => {
+                if let Err(e) = approval_queue.enqueue_plugin_intervention(intervention, observation, chrono::Utc::now().timestamp()) {
+                    info!("Plugin intervention from {} not queued: {}", intervention.plugin_id, e);
+                }
+            }
+            None => info!("Plugin intervention from {} targets unknown observation {}", intervention.plugin_id, intervention.observation_id),
+        }
+    }
+    // Approved shortcuts run only when the user triggers them; measured savings calibrate the ranker
+    let mut shortcut_executor = shortcut::executor::ShortcutExecutor::new(Box::new(shortcut::executor::SystemOsDriver));
+    for shortcut_id in &cli_args.run_shortcuts {
+        match shortcut_executor.execute(&mut shortcut_generator, shortcut_id, chrono::Utc::now().timestamp()) {
+            Ok(run) => info!("Ran shortcut {} ({} failed steps, {:?} min saved)", shortcut_id, run.failed_steps(), run.outcome.time_saved_minutes),
+            Err(e) => info!("Shortcut {} not run: {}", shortcut_id, e),
+        }
+    }
+    let app_categorizer = categorizer::AppCategorizer::new();
+    for shortcut_id in &cli_args.publish_shortcuts {
+        match shortcut_generator.publish_to_marketplace(shortcut_id, &mut marketplace, &app_categorizer) {
+            Ok(automation) => info!("Submitted {} for marketplace review as {}", shortcut_id, automation.id),
+            Err(e) => info!("Shortcut {} not published: {}", shortcut_id, e),
+        }
+    }
+    info!("Shortcut executor initialized ({} approved shortcuts, {} runs)", shortcut_generator.get_approved_shortcuts().len(), shortcut_executor.outcomes().len());
+    
+    let now = chrono::Utc::now().timestamp();
+    info!(
+        "Approval queue initialized ({} pending, {} worth prompting at interruption cost {:.2})",
+        approval_queue.list(&approval::ApprovalFilter::default(), now).len(),
+        approval_queue.prompts_due(&attention_service, now).len(),
+        attention_service.interruption_cost(now).score
+    );
+    for observation in &imported_observations {
+        let event = api::webhooks::HookEvent::PatternDetected { pattern: pattern_detector.detect_pattern(observation), observation: observation.clone() };
+        developer_api.publish_event(&event, &micro_consent_manager, chrono::Utc::now().timestamp());
+        developer_api.evaluate_interventions(observation, &mut auto_action_synthesizer, &mut approval_queue, chrono::Utc::now().timestamp());
+    }
+    reflective_loop.reflect_on_executed(&imported_observations, &auto_action_synthesizer);
+    let mut api_server_task = None;
+    let mut served_api = None;
+    // Third-party context events arrive through the developer API server, which serves the inbox routes
+    let mut webhook_inbox = inbox::WebhookInbox::default();
+    webhook_inbox.apply_consent(&micro_consent_manager);
+    if let Some(admin_token) = std::env::var("ATHENOS_API_ADMIN_TOKEN").ok().filter(|token| !token.trim().is_empty()) {
+        let api_server = std::sync::Arc::new(
+            api::server::ApiServer::new(api::server::ApiServerConfig::new(admin_token), developer_api)
+                .with_approval_queue(approval_queue)
+                .with_webhook_inbox(webhook_inbox),
+        );
+        api_server.publish_observations(&micro_consent_manager, &imported_observations);
+        served_api = Some(api_server.clone());
+        api_server_task = Some(bus_runtime.spawn(api::server::serve(api_server)));
+        info!("Developer API server started");
+    }
     
     let mut launch_manager = launch::PublicLaunchManager::new();
+    launch_manager.set_onboarding_playbook(launch::OnboardingPlaybook::guided(guided_onboarding.config()));
     info!("Public launch manager initialized");
     
+    let mut telemetry_channel = telemetry::TelemetryChannel::new(telemetry::TelemetryConfig::default());
+    if let Some(region) = multi_region_orchestrator.get_user_region("local") {
+        telemetry_channel.apply_region_policy(&region.id, multi_region_orchestrator.policy_for_user("local"));
+    }
+    info!("Product telemetry channel initialized (opt-in)");
+    
+    let mut notification_config = notify::NotificationConfig::default();
+    notification_config.digest.enabled = true;
+    let mut notification_router = notify::NotificationRouter::new(notification_config);
+    notification_router.register_sink(Box::new(notify::DesktopSink::new()));
+    notification_router.set_observe_only(!onboarding_progress.unlocked.contains(&beta::OnboardingFeature::Nudges));
+    for nudge in microlearning_generator.get_deliverable_nudges() {
+        notification_router.dispatch(&notify::Notification::from(&nudge));
+    }
+    for victory in victory_stream.take_undelivered() {
+        notification_router.dispatch(&notify::Notification::from(&victory));
+    }
+    let digest_deliveries = notification_router.flush_at_focus_boundary(&mut focus_session_engine, chrono::Utc::now().timestamp());
+    info!("Notification router initialized (digest mode, {} digest deliveries)", digest_deliveries.len());
+    
+    let mut cloud_backup = match std::env::var("ATHENOS_BACKUP_URL") {
+        Ok(url) if micro_consent_manager.consent_ledger().can_sync_to_cloud() => std::env::var("ATHENOS_BACKUP_REGION")
+            .ok()
+            .map_or(Ok(()), |region| multi_region_orchestrator.authorize_export("local", &region).map(|_| ()))
+            .and_then(|_| backup::BackupConfig::from_url(&url))
+            .and_then(|mut config| {
+                if let Ok(region) = std::env::var("ATHENOS_BACKUP_S3_REGION") {
+                    config.s3_region = region;
+                }
+                let credentials = match (std::env::var("ATHENOS_BACKUP_KEY_ID"), std::env::var("ATHENOS_BACKUP_SECRET")) {
+                    (Ok(key_id), Ok(secret)) => backup::BackupCredentials { key_id, secret },
+                    _ => return Err("ATHENOS_BACKUP_KEY_ID and ATHENOS_BACKUP_SECRET are required".to_string()),
+                };
+                let transport = backup::transport_for(&config, credentials)?;
+                backup::CloudBackupManager::open(config, transport, std::path::PathBuf::from("./sandbox/backup_manifest.json"))
+            })
+            .map_err(|e| info!("Cloud backup unavailable: {}", e))
+            .ok(),
+        _ => None,
+    };
+    info!("Cloud backup {}", if cloud_backup.is_some() { "configured" } else { "disabled (opt-in)" });
+    if let (Some(backups), Ok(passphrase)) = (cloud_backup.as_mut(), std::env::var("ATHENOS_BACKUP_PASSPHRASE")) {
+        let scheduled = backups
+            .unlock(&passphrase)
+            .and_then(|_| backup::snapshot_archive(report_generator.feature_store()))
+            .and_then(|archive| backups.run_scheduled(micro_consent_manager.consent_ledger(), &archive, chrono::Utc::now().timestamp()));
+        match scheduled {
+            Ok(Some(record)) => info!("Cloud backup uploaded {} ({} bytes)", record.name, record.size_bytes),
+            Ok(None) => info!("Cloud backup up to date ({} backups kept)", backups.get_records().len()),
+            Err(e) => info!("Scheduled cloud backup failed: {}", e),
+        }
+    }
+    
+    let local_model = wisdom::backend::LocalModelConfig::new("./models/wisdom.gguf", "./models/wisdom_tokenizer.json");
+    match wisdom::backend::load_local_model(&local_model) {
+        Ok(backend) => wisdom_engine.set_backend(backend),
+        Err(e) => match std::env::var("ATHENOS_INSIGHT_API_URL") {
+            Ok(endpoint) => {
+                let config = wisdom::backend::RemoteInsightConfig {
+                    endpoint,
+                    model: std::env::var("ATHENOS_INSIGHT_API_MODEL").unwrap_or_else(|_| "default".to_string()),
+                    api_key: std::env::var("ATHENOS_INSIGHT_API_KEY").ok(),
+                };
+                match wisdom::backend::RemoteApiBackend::connect(config) {
+                    Ok(backend) => wisdom_engine.set_backend(Box::new(backend)),
+                    Err(e) => info!("Remote insight backend unavailable: {}", e),
+                }
+            }
+            Err(_) => info!("Local insight model unavailable, using template insights: {}", e),
+        },
+    }
+    if let Some(observation) = imported_observations.first() {
+        let insight = wisdom_engine.generate_insight_streaming(observation, "Imported history", Some(&rag_index), &micro_consent_manager, &mut |text| {
+            info!("Insight stream: {}", text);
+        });
+        info!("Wisdom insight ({}): {}", wisdom_engine.backend_name(), insight);
+        let grounded = wisdom_engine.generate_grounded_insight(observation, "Imported history", &rag_index, &micro_consent_manager);
+        let sources: Vec<&str> = grounded.citations.iter().map(|citation| citation.source.as_str()).collect();
+        info!("Grounded insight ({}, {} citations {:?}): {}", grounded.generated_by, grounded.citations.len(), sources, grounded.insight);
+    }
+    
+    let mut inference_queue = inference::InferenceQueue::new(inference::InferenceConfig::default());
+    if let Some(observation) = imported_observations.first() {
+        let prompt = wisdom_engine.build_prompt(observation, "Imported history");
+        let _ = inference_queue.submit(inference::InferenceClient::WisdomEngine, prompt, chrono::Utc::now().timestamp_millis());
+    }
+    let llm_backend = std::sync::Arc::new(async_api::AsyncManager::new("llm", inference::TemplateBackend));
+    bus_runtime.block_on(inference_queue.run_async(llm_backend, chrono::Utc::now().timestamp_millis()));
+    inference_queue.export_metrics(&mut analytics_aggregator);
+    microlearning_generator.export_metrics(&mut analytics_aggregator);
+    shortcut_generator.get_ranker().export_calibration_metrics(&mut analytics_aggregator);
+    analytics_aggregator.update_ranking_breakdown(shortcut_generator.get_ranker().rank_with_breakdown(&imported_observations));
+    info!("LLM inference queue initialized");
+    
+    let applied = config_watcher.poll(
+        chrono::Utc::now().timestamp(),
+        &mut [&mut edge_observer, &mut calendar_agent, &mut differential_privacy, &mut telemetry_channel, &mut inference_queue, &mut gate_policy],
+    );
+    info!("Config watcher initialized ({} settings applied)", applied.len());
+    
+    let archive_config = evidence_archive::ArchiveConfig {
+        head_path: Some(std::path::PathBuf::from("./sandbox/evidence_archive_head.json")),
+        ..evidence_archive::ArchiveConfig::new(evidence_archive::ArchiveBackend::LocalDirectory {
+            dir: std::path::PathBuf::from("./sandbox/evidence_archive"),
+        })
+    };
+    // Config audit entries are archived as they are recorded, at boot and on every live reload
+    let mut evidence_archive = evidence_archive::EvidenceArchive::from_config(&archive_config).map_err(|e| info!("Evidence archive unavailable: {}", e)).ok();
+    let archive_config_audit = |archive: Option<&mut evidence_archive::EvidenceArchive>, entries: &[config::ConfigAuditEntry], now: i64| {
+        let Some(archive) = archive else { return };
+        for entry in entries {
+            if let Err(e) = archive.append_audit("config", entry, now) {
+                info!("Failed to archive config audit entry: {}", e);
+            }
+        }
+        if let Err(e) = archive.seal(now) {
+            info!("Failed to seal evidence segment: {}", e);
+        }
+    };
+    archive_config_audit(evidence_archive.as_mut(), &applied, chrono::Utc::now().timestamp());
+    if let Some(archive) = &evidence_archive {
+        info!("Evidence archive initialized ({:?})", archive.verify().map(|v| v.records));
+    }
+    
+    let mut report_scheduler = report::ReportScheduler::new(report::ReportScheduleConfig {
+        locale: locale_prefs.clone(),
+        ..report::ReportScheduleConfig::default()
+    });
+    report_scheduler.run_due(
+        chrono::Utc::now().timestamp(),
+        &report_generator,
+        &imported_observations,
+        &mut notification_router,
+        &mut analytics_aggregator,
+        &mut plugin_registry,
+    );
+    info!("Report scheduler initialized");
+    
+    let week_start = chrono::Utc::now().timestamp() - reflection::SELF_EVALUATION_WINDOW_SECS;
+    let self_evaluation = reflective_loop.weekly_self_evaluation(week_start, &auto_action_synthesizer, &analytics_aggregator);
+    notification_router.dispatch(&self_evaluation.to_notification());
+    info!("Weekly self-evaluation delivered ({} suggestions, {} false-positive patterns)", self_evaluation.suggestions, self_evaluation.false_positive_patterns.len());
+    if !guided_onboarding.completed {
+        notification_router.dispatch(&onboarding_progress.to_notification(chrono::Utc::now().timestamp()));
+    }
+    
+    analytics_aggregator.record_cache_stats(&[reflective_loop.cache_stats(), emotional_copilot.cache_stats()].concat());
+    let mut analytics_exporter = analytics_export::AnalyticsExporter::new(config_watcher.current().analytics_export.clone());
+    let mut export_sink = analytics_export::sink_for(analytics_exporter.config());
+    match analytics_exporter.run_if_due(chrono::Utc::now().timestamp(), &analytics_aggregator, export_sink.as_mut()).and_then(|run| run.error) {
+        Some(e) => info!("Analytics export failed: {}", e),
+        None => info!("Analytics export initialized"),
+    }
+    
+    let timeline_exporter = timeline::TimelineExporter::new();
+    let day_replay = timeline_exporter.build_day(chrono::Utc::now().timestamp(), &edge_observer.get_recent_events(1000), &auto_action_synthesizer.get_execution_history());
+    info!("Day replay export initialized ({} blocks, {} focus segments)", day_replay.blocks.len(), day_replay.focus_segments.len());
+    
+    match &served_api {
+        Some(api_server) => {
+            shortcut_generator.set_external_load(api_server.external_load(chrono::Utc::now().timestamp()));
+            info!("Webhook inbox initialized ({})", if micro_consent_manager.has_consent(inbox::WEBHOOK_INBOX_CAPABILITY) { "accepting events" } else { "awaiting consent" });
+        }
+        None => info!("Webhook inbox not served (developer API server disabled)"),
+    }
+    
+    let offline_queue = match offline::OfflineQueue::open(std::path::PathBuf::from("./sandbox/offline_queue.json"), offline::OfflineQueueConfig::default()) {
+        Ok(queue) => queue,
+        Err(e) => {
+            info!("Offline queue unavailable, queued sync will not persist: {}", e);
+            offline::OfflineQueue::default()
+        }
+    };
+    let pending: usize = offline_queue.status().pending.values().sum();
+    info!("Offline queue initialized ({} items pending sync)", pending);
+    
+    persist_consents(&micro_consent_manager);
     info!("Phase D initialization complete");
     info!("Ready for cognitive ecosystem");
+    if let Some(mut api_server_task) = api_server_task {
+        // The server runs on bus_runtime, which would be dropped (and the server with it) when main returns
+        // Meanwhile the config file is re-read so edits apply without a restart
+        info!("Serving the developer API until interrupted (config reloaded every {}s)", config::POLL_INTERVAL_SECS);
+        let served = bus_runtime.block_on(async {
+            let mut reload = tokio::time::interval(std::time::Duration::from_secs(config::POLL_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    served = &mut api_server_task => break served,
+                    _ = reload.tick() => {
+                        let now = chrono::Utc::now().timestamp();
+                        let entries = config_watcher.poll(
+                            now,
+                            &mut [&mut edge_observer, &mut calendar_agent, &mut differential_privacy, &mut telemetry_channel, &mut inference_queue, &mut gate_policy],
+                        );
+                        archive_config_audit(evidence_archive.as_mut(), &entries, now);
+                        // Lapsed TTL grants are revoked and their derived data purged as soon as they expire
+                        let expired = micro_consent_manager.expire_consents(now);
+                        for capability in &expired {
+                            let handlers: &mut [&mut dyn consent::RevocationHandler] =
+                                &mut [&mut edge_observer, report_generator.feature_store_mut(), &mut rag_index, &mut pattern_miner, &mut emotion_estimator];
+                            match data_purge_coordinator.forget(&mut micro_consent_manager, capability, Some("expired".to_string()), handlers) {
+                                Ok(receipt) => info!("Consent for {} expired: {} deleted, {} anonymized (receipt {})", capability, receipt.records_deleted, receipt.records_anonymized, receipt.id),
+                                Err(e) => info!("Purge of expired {} failed: {}", capability, e),
+                            }
+                        }
+                        if !expired.is_empty() {
+                            edge_observer.apply_consent(&micro_consent_manager);
+                            auto_action_synthesizer.apply_consent(micro_consent_manager.consent_ledger());
+                            persist_consents(&micro_consent_manager);
+                        }
+                        for renewal in micro_consent_manager.consents_needing_renewal(now) {
+                            if renewals_prompted.insert((renewal.capability.clone(), renewal.expires_at, renewal.reason)) {
+                                notification_router.dispatch(&notify::Notification {
+                                    source: notify::NotificationSource::TimelineNotice,
+                                    severity: if renewal.reason == consent::RenewalReason::Expired { notify::NotificationSeverity::Warning } else { notify::NotificationSeverity::Info },
+                                    title: format!("Renew consent: {}", renewal.capability),
+                                    body: renewal.description,
+                                    created_at: now,
+                                });
+                            }
+                        }
+                        focus_session_engine.tick(now);
+                        notification_router.flush_at_focus_boundary(&mut focus_session_engine, now);
+                        notification_router.flush_if_stale(now);
+                        if let Some(api_server) = &served_api {
+                            shortcut_generator.set_external_load(api_server.external_load(now));
+                            for result in api_server.apply_approvals(&mut shortcut_generator, &mut calendar_agent, &mut auto_action_synthesizer, now) {
+                                if let Some(e) = result.error {
+                                    info!("Approval decision for {} not applied: {}", result.id, e);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        match served {
+            Ok(Ok(())) => info!("Developer API server stopped"),
+            Ok(Err(e)) => info!("Developer API server failed: {}", e),
+            Err(e) => info!("Developer API server task failed: {}", e),
+        }
+    }
+}
+
+/// Command-line subcommands, each with its operand
+#[derive(Debug, Default, PartialEq)]
+struct CliArgs {
+    dataset_commands: Vec<String>, // (command, file) pairs for dataset::run_commands
+    forget_capabilities: Vec<String>,
+    run_shortcuts: Vec<String>,
+    publish_shortcuts: Vec<String>,
+}
+
+/// Parse subcommands and their operands; unknown subcommands and missing operands are errors
+fn parse_cli_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut cli_args = CliArgs::default();
+    let mut iter = args.iter();
+    while let Some(command) = iter.next() {
+        match command.as_str() {
+            "forget" => {
+                let capability = iter.next().ok_or("Missing capability argument for 'forget'")?;
+                cli_args.forget_capabilities.push(capability.clone());
+            }
+            "run-shortcut" | "publish-shortcut" => {
+                let shortcut_id = iter.next().ok_or(format!("Missing shortcut id for '{}'", command))?;
+                match command.as_str() {
+                    "run-shortcut" => cli_args.run_shortcuts.push(shortcut_id.clone()),
+                    _ => cli_args.publish_shortcuts.push(shortcut_id.clone()),
+                }
+            }
+            "import" | "export" | "label" => {
+                let path = iter.next().ok_or(format!("Missing file argument for '{}'", command))?;
+                cli_args.dataset_commands.extend([command.clone(), path.clone()]);
+            }
+            other => return Err(format!("Unrecognized argument: {}", other)),
+        }
+    }
+    Ok(cli_args)
 }
 
 #[cfg(test)]
@@ -198,5 +1075,27 @@ mod integration_tests {
         let sequence = observer.get_app_sequence(10);
         assert_eq!(sequence, vec!["Teams"]);
     }
+
+    #[test]
+    fn test_cli_args_pair_commands_with_their_operands() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<String>>();
+
+        // A leading dataset command no longer shifts "forget" out of position
+        let parsed = parse_cli_args(&args(&["import", "seed.jsonl", "forget", "calendar", "forget", "email"])).unwrap();
+        assert_eq!(parsed.dataset_commands, args(&["import", "seed.jsonl"]));
+        assert_eq!(parsed.forget_capabilities, args(&["calendar", "email"]));
+        let parsed = parse_cli_args(&args(&["label", "labels.json"])).unwrap();
+        assert_eq!(parsed.dataset_commands, args(&["label", "labels.json"]));
+        let parsed = parse_cli_args(&args(&["run-shortcut", "shortcut_obs_1", "publish-shortcut", "shortcut_obs_1"])).unwrap();
+        assert_eq!(parsed.run_shortcuts, args(&["shortcut_obs_1"]));
+        assert_eq!(parsed.publish_shortcuts, args(&["shortcut_obs_1"]));
+        assert_eq!(parse_cli_args(&[]).unwrap(), CliArgs::default());
+
+        assert!(parse_cli_args(&args(&["forget"])).is_err());
+        assert!(parse_cli_args(&args(&["export"])).is_err());
+        assert!(parse_cli_args(&args(&["run-shortcut"])).is_err());
+        assert!(parse_cli_args(&args(&["--verbose"])).is_err());
+        assert!(parse_cli_args(&args(&["forget", "calendar", "extra"])).is_err());
+    }
 }
 
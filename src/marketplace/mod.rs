@@ -2,7 +2,9 @@
 /// Automation Marketplace
 /// Offer automation marketplace with curated third-party plugins
 
-use crate::plugin::PluginMetadata;
+use crate::plugin::signing::{PluginSignature, TrustedPublishers};
+use crate::plugin::{PluginCapability, PluginMetadata, PLUGIN_API_VERSION};
+use crate::categorizer::AppCategorizer;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -15,8 +17,173 @@ pub struct MarketplacePlugin {
     pub price: f64,
     pub rating: f64, // 0.0 to 5.0
     pub download_count: usize,
-    pub verified: bool,
+    pub verified: bool, // Set on listing from the publisher signature; a claimed value is not trusted
     pub category: PluginCategory,
+    #[serde(default)]
+    pub compatibility: PluginCompatibility,
+    #[serde(default)]
+    pub signature: Option<PluginSignature>,
+}
+
+/// Operating system a plugin can run on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OsTarget {
+    MacOs,
+    Windows,
+    Linux,
+}
+
+impl OsTarget {
+    /// OS the daemon was built for
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            OsTarget::MacOs
+        } else if cfg!(target_os = "windows") {
+            OsTarget::Windows
+        } else {
+            OsTarget::Linux
+        }
+    }
+}
+
+/// Compatibility declared by a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCompatibility {
+    pub min_api_version: String,         // Inclusive
+    pub max_api_version: Option<String>, // Exclusive; None = no upper bound
+    pub required_capabilities: Vec<PluginCapability>,
+    pub os_targets: Vec<OsTarget>, // Empty = every OS
+}
+
+impl PluginCompatibility {
+    pub fn new() -> Self {
+        Self {
+            min_api_version: "0.1.0".to_string(),
+            max_api_version: None,
+            required_capabilities: Vec::new(),
+            os_targets: Vec::new(),
+        }
+    }
+}
+
+impl Default for PluginCompatibility {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What the running daemon offers plugins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonEnvironment {
+    pub api_version: String,
+    pub capabilities: Vec<PluginCapability>,
+    pub os: OsTarget,
+}
+
+impl DaemonEnvironment {
+    /// Environment of this build
+    pub fn new() -> Self {
+        Self {
+            api_version: PLUGIN_API_VERSION.to_string(),
+            capabilities: vec![
+                PluginCapability::Observation,
+                PluginCapability::Intervention,
+                PluginCapability::Analysis,
+                PluginCapability::Visualization,
+            ],
+            os: OsTarget::current(),
+        }
+    }
+}
+
+impl Default for DaemonEnvironment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Single reason a plugin cannot be installed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityIssue {
+    InvalidVersion { version: String },
+    ApiTooOld { required: String, running: String },
+    ApiTooNew { below: String, running: String },
+    MissingCapability { capability: PluginCapability },
+    UnsupportedOs { os: OsTarget, supported: Vec<OsTarget> },
+}
+
+impl CompatibilityIssue {
+    /// Actionable explanation for the user
+    pub fn message(&self) -> String {
+        match self {
+            CompatibilityIssue::InvalidVersion { version } => format!("Invalid plugin API version '{}'; the supported API range cannot be checked", version),
+            CompatibilityIssue::ApiTooOld { required, running } => format!("Requires plugin API {} or newer but {} is running; update Athenos", required, running),
+            CompatibilityIssue::ApiTooNew { below, running } => format!("Supports plugin API below {} but {} is running; wait for a plugin update or install an older Athenos", below, running),
+            CompatibilityIssue::MissingCapability { capability } => format!("Requires the {:?} capability, which this daemon does not provide", capability),
+            CompatibilityIssue::UnsupportedOs { os, supported } => format!("Not available on {:?}; supported: {:?}", os, supported),
+        }
+    }
+}
+
+/// Compatibility verdict for one plugin against the running daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub plugin_id: String,
+    pub compatible: bool,
+    pub issues: Vec<CompatibilityIssue>,
+}
+
+/// Parse "major.minor.patch" (missing parts are zero)
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.trim().split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Check a plugin's declared compatibility against a daemon environment
+pub fn check_compatibility(plugin_id: &str, declared: &PluginCompatibility, environment: &DaemonEnvironment) -> CompatibilityReport {
+    let mut issues = Vec::new();
+    let running = parse_version(&environment.api_version);
+    // Without a parseable daemon version the API range cannot be checked, so the plugin is never reported compatible
+    if running.is_none() {
+        issues.push(CompatibilityIssue::InvalidVersion { version: environment.api_version.clone() });
+    }
+
+    match (parse_version(&declared.min_api_version), running) {
+        (None, _) => issues.push(CompatibilityIssue::InvalidVersion { version: declared.min_api_version.clone() }),
+        (Some(min), Some(running_version)) if running_version < min => issues.push(CompatibilityIssue::ApiTooOld {
+            required: declared.min_api_version.clone(),
+            running: environment.api_version.clone(),
+        }),
+        _ => {}
+    }
+    if let Some(max) = &declared.max_api_version {
+        match (parse_version(max), running) {
+            (None, _) => issues.push(CompatibilityIssue::InvalidVersion { version: max.clone() }),
+            (Some(below), Some(running_version)) if running_version >= below => issues.push(CompatibilityIssue::ApiTooNew {
+                below: max.clone(),
+                running: environment.api_version.clone(),
+            }),
+            _ => {}
+        }
+    }
+    for capability in &declared.required_capabilities {
+        if !environment.capabilities.contains(capability) {
+            issues.push(CompatibilityIssue::MissingCapability { capability: capability.clone() });
+        }
+    }
+    if !declared.os_targets.is_empty() && !declared.os_targets.contains(&environment.os) {
+        issues.push(CompatibilityIssue::UnsupportedOs { os: environment.os, supported: declared.os_targets.clone() });
+    }
+
+    CompatibilityReport { plugin_id: plugin_id.to_string(), compatible: issues.is_empty(), issues }
 }
 
 /// Plugin category
@@ -29,11 +196,36 @@ pub enum PluginCategory {
     Wellbeing,
 }
 
+/// Review status of a community submission
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReviewStatus {
+    PendingReview,
+    Verified,
+    Rejected,
+}
+
+/// Community automation published from a user's proven shortcut
+/// App names are replaced by category labels before submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityAutomation {
+    pub id: String,
+    pub title: String,
+    pub category_sequence: Vec<String>,
+    pub trigger: String,
+    pub realized_savings_min: f64,
+    pub review_status: ReviewStatus,
+    pub review_notes: Option<String>,
+    pub submitted_at: i64,
+}
+
 /// Automation marketplace
 /// Source: Athenos_AI_Strategy.md#L135
 pub struct AutomationMarketplace {
     plugins: HashMap<String, MarketplacePlugin>,
     curated_plugins: Vec<String>, // Plugin IDs that are curated/verified
+    community_automations: HashMap<String, CommunityAutomation>,
+    environment: DaemonEnvironment,
+    trusted_publishers: TrustedPublishers,
 }
 
 impl AutomationMarketplace {
@@ -43,14 +235,50 @@ impl AutomationMarketplace {
         Self {
             plugins: HashMap::new(),
             curated_plugins: Vec::new(),
+            community_automations: HashMap::new(),
+            environment: DaemonEnvironment::new(),
+            trusted_publishers: TrustedPublishers::new(),
+        }
+    }
+
+    /// Share a publisher key set (e.g. with the plugin registry) instead of the marketplace's own
+    pub fn set_trusted_publishers(&mut self, trusted_publishers: TrustedPublishers) {
+        self.trusted_publishers = trusted_publishers;
+    }
+
+    /// Trust a publisher's Ed25519 public key (hex) for listing verification
+    pub fn trust_publisher(&mut self, publisher_id: &str, public_key_hex: &str) -> Result<(), String> {
+        self.trusted_publishers.trust(publisher_id, public_key_hex)
+    }
+
+    /// Override the daemon environment plugins are checked against
+    pub fn set_environment(&mut self, environment: DaemonEnvironment) {
+        self.environment = environment;
+    }
+
+    /// The listing as it would be added: `verified` is set from its signature, whatever the listing claims
+    pub fn verify_listing(&self, mut plugin: MarketplacePlugin) -> MarketplacePlugin {
+        let verification = match &plugin.signature {
+            Some(signature) => self.trusted_publishers.verify(&plugin.metadata, signature).map_err(String::from),
+            None => Err("unsigned".to_string()),
+        };
+        if let Err(reason) = &verification {
+            if plugin.verified {
+                info!("AutomationMarketplace::verify_listing: {} claims verification but is not ({})", plugin.metadata.id, reason);
+            }
         }
+        plugin.verified = verification.is_ok();
+        plugin
     }
 
     /// Add plugin to marketplace
+    /// Only listings signed by a trusted publisher are marked verified and curated
     /// Source: Athenos_AI_Strategy.md#L135
     pub fn add_plugin(&mut self, plugin: MarketplacePlugin) {
         info!("AutomationMarketplace::add_plugin: Adding plugin {}", plugin.metadata.id);
+        let plugin = self.verify_listing(plugin);
         let plugin_id = plugin.metadata.id.clone();
+        self.curated_plugins.retain(|id| id != &plugin_id);
         
         if plugin.verified {
             self.curated_plugins.push(plugin_id.clone());
@@ -68,6 +296,13 @@ impl AutomationMarketplace {
             .collect()
     }
 
+    /// Every listed plugin, by plugin ID
+    pub fn catalog(&self) -> Vec<&MarketplacePlugin> {
+        let mut plugins: Vec<&MarketplacePlugin> = self.plugins.values().collect();
+        plugins.sort_by(|a, b| a.metadata.id.cmp(&b.metadata.id));
+        plugins
+    }
+
     /// Search plugins by category
     pub fn search_by_category(&self, category: PluginCategory) -> Vec<&MarketplacePlugin> {
         self.plugins
@@ -79,18 +314,116 @@ impl AutomationMarketplace {
     /// Get top-rated plugins
     pub fn get_top_rated(&self, limit: usize) -> Vec<&MarketplacePlugin> {
         let mut plugins: Vec<&MarketplacePlugin> = self.plugins.values().collect();
-        plugins.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap());
+        plugins.sort_by(|a, b| b.rating.total_cmp(&a.rating));
         plugins.into_iter().take(limit).collect()
     }
 
-    /// Install plugin (simulated)
+    /// Search plugins by category with each plugin's compatibility; compatible plugins first
+    pub fn search_with_compatibility(&self, category: PluginCategory) -> Vec<(&MarketplacePlugin, CompatibilityReport)> {
+        let mut results: Vec<(&MarketplacePlugin, CompatibilityReport)> = self
+            .search_by_category(category)
+            .into_iter()
+            .map(|p| (p, check_compatibility(&p.metadata.id, &p.compatibility, &self.environment)))
+            .collect();
+        results.sort_by(|a, b| b.1.compatible.cmp(&a.1.compatible).then(b.0.rating.total_cmp(&a.0.rating)));
+        results
+    }
+
+    /// Check a listed plugin against the running daemon
+    pub fn check_plugin_compatibility(&self, plugin_id: &str) -> Result<CompatibilityReport, String> {
+        let plugin = self.plugins.get(plugin_id).ok_or("Plugin not found")?;
+        Ok(check_compatibility(plugin_id, &plugin.compatibility, &self.environment))
+    }
+
+    /// Install plugin (simulated); incompatible plugins and listings with a bad signature are refused
+    /// Unsigned plugins may be installed; the plugin registry decides whether to load them
     pub fn install_plugin(&mut self, plugin_id: &str) -> Result<(), String> {
+        let plugin = self.plugins.get(plugin_id).ok_or("Plugin not found")?;
+        if let Some(signature) = &plugin.signature {
+            self.trusted_publishers.verify(&plugin.metadata, signature)?;
+        }
+        let report = self.check_plugin_compatibility(plugin_id)?;
+        if !report.compatible {
+            let reasons: Vec<String> = report.issues.iter().map(|i| i.message()).collect();
+            info!("AutomationMarketplace::install_plugin: Refusing {}: {}", plugin_id, reasons.join("; "));
+            return Err(format!("Plugin {} is incompatible: {}", plugin_id, reasons.join("; ")));
+        }
         if let Some(plugin) = self.plugins.get_mut(plugin_id) {
             plugin.download_count += 1;
-            Ok(())
-        } else {
-            Err("Plugin not found".to_string())
         }
+        Ok(())
+    }
+
+    /// Submit a proven shortcut as a community automation (enters review queue)
+    /// A shortcut is submitted once; resubmitting would reset a reviewed listing
+    pub fn submit_community_automation(
+        &mut self,
+        shortcut_id: &str,
+        sequence: &[String],
+        realized_savings_min: f64,
+        categorizer: &AppCategorizer,
+    ) -> Result<CommunityAutomation, String> {
+        info!("AutomationMarketplace::submit_community_automation: Submitting shortcut {}", shortcut_id);
+        
+        if sequence.is_empty() {
+            return Err("Shortcut sequence is empty".to_string());
+        }
+        if realized_savings_min <= 0.0 {
+            return Err("Shortcut has no realized savings".to_string());
+        }
+        
+        let id = format!("community_{}", shortcut_id);
+        if let Some(existing) = self.community_automations.get(&id) {
+            return Err(format!("Shortcut {} is already submitted ({:?})", shortcut_id, existing.review_status));
+        }
+        
+        let category_sequence = categorizer.anonymize_sequence(sequence);
+        let automation = CommunityAutomation {
+            id,
+            title: format!("Automate {}", category_sequence.join(" → ")),
+            trigger: format!("on_focus:{}", category_sequence[0]),
+            category_sequence,
+            realized_savings_min,
+            review_status: ReviewStatus::PendingReview,
+            review_notes: None,
+            submitted_at: chrono::Utc::now().timestamp(),
+        };
+        
+        self.community_automations.insert(automation.id.clone(), automation.clone());
+        Ok(automation)
+    }
+
+    /// Review a community submission; only verified automations are listed
+    pub fn review_community_automation(&mut self, automation_id: &str, approve: bool, notes: Option<String>) -> Result<(), String> {
+        info!("AutomationMarketplace::review_community_automation: Reviewing {} (approve: {})", automation_id, approve);
+        
+        let automation = self.community_automations
+            .get_mut(automation_id)
+            .ok_or("Community automation not found")?;
+        
+        if automation.review_status != ReviewStatus::PendingReview {
+            return Err("Community automation already reviewed".to_string());
+        }
+        
+        automation.review_status = if approve { ReviewStatus::Verified } else { ReviewStatus::Rejected };
+        automation.review_notes = notes;
+        Ok(())
+    }
+
+    /// Get submissions awaiting review
+    pub fn get_pending_reviews(&self) -> Vec<&CommunityAutomation> {
+        self.community_automations
+            .values()
+            .filter(|a| a.review_status == ReviewStatus::PendingReview)
+            .collect()
+    }
+
+    /// Get verified community automations
+    pub fn get_community_automations(&self) -> Vec<&CommunityAutomation> {
+        self.community_automations
+            .values()
+            .filter(|a| a.review_status == ReviewStatus::Verified)
+            .collect()
     }
 }
 
@@ -103,7 +436,7 @@ impl Default for AutomationMarketplace {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plugin::PluginCapability;
+    use crate::plugin::signing::PluginSigner;
 
     #[test]
     fn test_marketplace_creation() {
@@ -120,7 +453,7 @@ mod tests {
                 name: "Test Plugin".to_string(),
                 version: "1.0.0".to_string(),
                 author: "Test Author".to_string(),
-                capabilities: vec![PluginCapability::Automation],
+                capabilities: vec![PluginCapability::Intervention],
                 description: "Test".to_string(),
             },
             price: 9.99,
@@ -128,10 +461,26 @@ mod tests {
             download_count: 0,
             verified: true,
             category: PluginCategory::Productivity,
+            compatibility: PluginCompatibility::default(),
+            signature: None,
         };
         
-        marketplace.add_plugin(plugin);
+        // Claiming verification without a trusted signature does not curate the listing
+        marketplace.add_plugin(plugin.clone());
+        assert!(marketplace.get_curated_plugins().is_empty());
+
+        let (signer, _) = PluginSigner::generate("acme").unwrap();
+        marketplace.trust_publisher("acme", &signer.public_key_hex()).unwrap();
+        let signed = MarketplacePlugin { signature: Some(signer.sign(&plugin.metadata, b"module").unwrap()), ..plugin };
+        marketplace.add_plugin(signed.clone());
         assert_eq!(marketplace.get_curated_plugins().len(), 1);
+
+        // Listing metadata altered after signing is neither curated nor installable
+        let mut tampered = signed;
+        tampered.metadata.capabilities.push(PluginCapability::Visualization);
+        marketplace.add_plugin(tampered);
+        assert!(marketplace.get_curated_plugins().is_empty());
+        assert!(marketplace.install_plugin("plugin_001").unwrap_err().contains("Invalid signature"));
     }
 
     #[test]
@@ -151,6 +500,8 @@ mod tests {
             download_count: 0,
             verified: false,
             category: PluginCategory::Automation,
+            compatibility: PluginCompatibility::default(),
+            signature: None,
         };
         
         marketplace.add_plugin(plugin);
@@ -159,5 +510,106 @@ mod tests {
         let installed = marketplace.plugins.get("plugin_002").unwrap();
         assert_eq!(installed.download_count, 1);
     }
-}
 
+    #[test]
+    fn test_community_automation_review_flow() {
+        let mut marketplace = AutomationMarketplace::new();
+        let categorizer = AppCategorizer::new();
+        let sequence = vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()];
+        
+        let automation = marketplace
+            .submit_community_automation("shortcut_001", &sequence, 11.0, &categorizer)
+            .unwrap();
+        assert_eq!(automation.category_sequence, vec!["communication", "email", "code_editor"]);
+        assert!(!automation.title.contains("Teams"));
+        assert!(marketplace.get_community_automations().is_empty());
+        assert_eq!(marketplace.get_pending_reviews().len(), 1);
+        
+        marketplace.review_community_automation(&automation.id, true, None).unwrap();
+        assert_eq!(marketplace.get_community_automations().len(), 1);
+        assert!(marketplace.review_community_automation(&automation.id, false, None).is_err());
+        
+        // Resubmitting leaves the verified listing as it was
+        let error = marketplace.submit_community_automation("shortcut_001", &sequence, 20.0, &categorizer).unwrap_err();
+        assert!(error.contains("already submitted"));
+        assert_eq!(marketplace.get_community_automations()[0].realized_savings_min, 11.0);
+        assert!(marketplace.get_pending_reviews().is_empty());
+    }
+
+    fn listing(id: &str, compatibility: PluginCompatibility) -> MarketplacePlugin {
+        MarketplacePlugin {
+            metadata: PluginMetadata {
+                id: id.to_string(),
+                name: id.to_string(),
+                version: "1.0.0".to_string(),
+                author: "Author".to_string(),
+                capabilities: vec![],
+                description: "Test".to_string(),
+            },
+            price: 0.0,
+            rating: 4.0,
+            download_count: 0,
+            verified: true,
+            category: PluginCategory::Focus,
+            compatibility,
+            signature: None,
+        }
+    }
+
+    fn environment() -> DaemonEnvironment {
+        DaemonEnvironment {
+            api_version: "1.4.2".to_string(),
+            capabilities: vec![PluginCapability::Observation, PluginCapability::Analysis],
+            os: OsTarget::Linux,
+        }
+    }
+
+    #[test]
+    fn test_compatibility_matrix() {
+        let env = environment();
+        let ok = PluginCompatibility {
+            min_api_version: "1.2".to_string(),
+            max_api_version: Some("2.0.0".to_string()),
+            required_capabilities: vec![PluginCapability::Analysis],
+            os_targets: vec![OsTarget::Linux, OsTarget::MacOs],
+        };
+        assert!(check_compatibility("ok", &ok, &env).compatible);
+
+        let too_old = PluginCompatibility { min_api_version: "1.5.0".to_string(), ..ok.clone() };
+        assert!(matches!(check_compatibility("p", &too_old, &env).issues[..], [CompatibilityIssue::ApiTooOld { .. }]));
+        let too_new = PluginCompatibility { max_api_version: Some("1.4.2".to_string()), ..ok.clone() };
+        assert!(matches!(check_compatibility("p", &too_new, &env).issues[..], [CompatibilityIssue::ApiTooNew { .. }]));
+
+        let broken = PluginCompatibility {
+            min_api_version: "one".to_string(),
+            max_api_version: None,
+            required_capabilities: vec![PluginCapability::Intervention],
+            os_targets: vec![OsTarget::Windows],
+        };
+        assert_eq!(check_compatibility("p", &broken, &env).issues.len(), 3);
+
+        // An unparseable daemon version fails the check instead of skipping the API range
+        let unknown_daemon = DaemonEnvironment { api_version: "dev-build".to_string(), ..environment() };
+        let report = check_compatibility("p", &ok, &unknown_daemon);
+        assert!(!report.compatible);
+        assert_eq!(report.issues, vec![CompatibilityIssue::InvalidVersion { version: "dev-build".to_string() }]);
+    }
+
+    #[test]
+    fn test_incompatible_install_refused_and_flagged_in_search() {
+        let mut marketplace = AutomationMarketplace::new();
+        marketplace.set_environment(environment());
+        marketplace.add_plugin(listing("windows_only", PluginCompatibility { os_targets: vec![OsTarget::Windows], ..PluginCompatibility::default() }));
+        marketplace.add_plugin(listing("portable", PluginCompatibility::default()));
+
+        let err = marketplace.install_plugin("windows_only").unwrap_err();
+        assert!(err.contains("Not available on Linux"));
+        assert_eq!(marketplace.plugins["windows_only"].download_count, 0);
+        marketplace.install_plugin("portable").unwrap();
+
+        let results = marketplace.search_with_compatibility(PluginCategory::Focus);
+        assert_eq!(results[0].0.metadata.id, "portable");
+        assert!(results[0].1.compatible);
+        assert!(!results[1].1.compatible);
+    }
+}
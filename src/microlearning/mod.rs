@@ -3,10 +3,20 @@
 /// Add contextual microlearning nudges driven by error/misuse detection
 
 use crate::types::*;
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
+use crate::attention::{AttentionService, InterruptionPriority};
+use crate::suppression::SuppressionList;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+/// Analytics metric counting nudges shown to the user
+pub const NUDGE_DELIVERED_METRIC: &str = "nudge.delivered";
+/// Analytics metric counting nudges the user dismissed
+pub const NUDGE_DISMISSED_METRIC: &str = "nudge.dismissed";
+/// A/B experiment over nudge wording; variant "A" is the original wording
+pub const NUDGE_WORDING_EXPERIMENT: &str = "nudge_wording";
+
 /// Error/misuse pattern detected
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorPattern {
@@ -26,7 +36,19 @@ pub struct MicrolearningNudge {
     pub tip: String,
     pub apply_action: Option<String>,
     pub error_pattern: Option<String>,
+    pub provenance: Provenance,
     pub created_at: i64,
+    #[serde(default)]
+    pub variant: Option<String>, // Wording experiment variant it was written in
+}
+
+/// User response to a nudge, kept until the wording experiment takes it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NudgeOutcome {
+    pub nudge_id: String,
+    pub variant: Option<String>,
+    pub accepted: bool,
+    pub at: i64,
 }
 
 /// Microlearning nudge generator
@@ -34,27 +56,94 @@ pub struct MicrolearningNudge {
 pub struct MicrolearningNudgeGenerator {
     error_patterns: HashMap<String, ErrorPattern>,
     nudge_templates: HashMap<String, String>,
+    attention: AttentionService,
+    suppression: SuppressionList,
+    observe_only: bool, // Guided onboarding: no nudges until unlocked
+    wording_variant: Option<String>, // NUDGE_WORDING_EXPERIMENT variant; "B" uses the alternative wording
+    outcomes: Vec<NudgeOutcome>, // Not yet taken by take_outcomes
+    delivered: usize, // Since the last export_metrics
+    dismissed: usize, // Since the last export_metrics
 }
 
 impl MicrolearningNudgeGenerator {
     /// Create new microlearning nudge generator
     pub fn new() -> Self {
+        Self::with_attention(AttentionService::new())
+    }
+
+    /// Create nudge generator sharing an attention service
+    pub fn with_attention(attention: AttentionService) -> Self {
         info!("MicrolearningNudgeGenerator::new: Creating microlearning nudge generator");
         
         let mut nudge_templates = HashMap::new();
         nudge_templates.insert("repeated_error".to_string(), 
-            "You've repeated this error {} times. Try: {}");
+            "You've repeated this error {} times. Try: {}".to_string());
         nudge_templates.insert("inefficient_pattern".to_string(),
-            "This pattern could be optimized. Consider: {}");
+            "This pattern could be optimized. Consider: {}".to_string());
         nudge_templates.insert("misuse_detected".to_string(),
-            "There's a better way to do this. Tip: {}");
+            "There's a better way to do this. Tip: {}".to_string());
+        nudge_templates.insert("repeated_error_b".to_string(),
+            "This has come up {} times now. A quicker way: {}".to_string());
         
         Self {
             error_patterns: HashMap::new(),
             nudge_templates,
+            attention,
+            suppression: SuppressionList::new(),
+            observe_only: false,
+            wording_variant: None,
+            outcomes: Vec::new(),
+            delivered: 0,
+            dismissed: 0,
         }
     }
 
+    /// Share a suppression list; suppressed topics produce no nudges
+    pub fn set_suppression_list(&mut self, suppression: SuppressionList) {
+        self.suppression = suppression;
+    }
+
+    /// Hold every nudge while onboarding is observe-only
+    pub fn set_observe_only(&mut self, observe_only: bool) {
+        self.observe_only = observe_only;
+    }
+
+    /// Write nudges in a wording experiment variant (None or "A" keeps the original wording)
+    pub fn set_wording_variant(&mut self, variant: Option<String>) {
+        info!("MicrolearningNudgeGenerator::set_wording_variant: {:?}", variant);
+        self.wording_variant = variant;
+    }
+
+    fn alternative_wording(&self) -> bool {
+        self.wording_variant.as_deref() == Some("B")
+    }
+
+    fn is_suppressed(&self, topic: &str) -> bool {
+        self.observe_only || self.suppression.is_suppressed(&SuppressionList::nudge_signature(topic), chrono::Utc::now().timestamp())
+    }
+
+    /// Dismiss a nudge; dismissing the same topic twice suppresses it
+    /// Returns whether the topic is now suppressed
+    pub fn dismiss_nudge(&mut self, nudge: &MicrolearningNudge) -> Result<bool, String> {
+        let topic = nudge.error_pattern.as_deref().unwrap_or(&nudge.tip);
+        info!("MicrolearningNudgeGenerator::dismiss_nudge: Dismissing nudge on {}", topic);
+        let now = chrono::Utc::now().timestamp();
+        self.dismissed += 1;
+        self.outcomes.push(NudgeOutcome { nudge_id: nudge.id.clone(), variant: nudge.variant.clone(), accepted: false, at: now });
+        self.suppression.record_rejection(&SuppressionList::nudge_signature(topic), now)
+    }
+
+    /// Record that the user applied a nudge's tip
+    pub fn accept_nudge(&mut self, nudge: &MicrolearningNudge) {
+        info!("MicrolearningNudgeGenerator::accept_nudge: Accepted {}", nudge.id);
+        self.outcomes.push(NudgeOutcome { nudge_id: nudge.id.clone(), variant: nudge.variant.clone(), accepted: true, at: chrono::Utc::now().timestamp() });
+    }
+
+    /// Outcomes recorded since the last call, oldest first
+    pub fn take_outcomes(&mut self) -> Vec<NudgeOutcome> {
+        std::mem::take(&mut self.outcomes)
+    }
+
     /// Detect error/misuse pattern
     /// Source: Athenos_AI_Strategy.md#L121
     pub fn detect_error_pattern(&mut self, error_type: String, context: String) {
@@ -85,11 +174,20 @@ impl MicrolearningNudgeGenerator {
     pub fn generate_nudge(&self, error_type: &str, tip: &str) -> Option<MicrolearningNudge> {
         info!("MicrolearningNudgeGenerator::generate_nudge: Generating nudge for {}", error_type);
         
+        if self.is_suppressed(error_type) {
+            return None;
+        }
+        
         if let Some(pattern) = self.error_patterns.get(error_type) {
             if pattern.frequency >= 3 {
-                let template = self.nudge_templates.get("repeated_error")
-                    .unwrap_or(&"Try this: {}".to_string());
-                let content = template.replace("{}", &format!("{} times", pattern.frequency));
+                let key = if self.alternative_wording() { "repeated_error_b" } else { "repeated_error" };
+                let template = self.nudge_templates.get(key)
+                    .map(|t| t.as_str())
+                    .unwrap_or("Try this: {}");
+                // First placeholder is the repeat count, second the tip
+                let content = template
+                    .replacen("{}", &pattern.frequency.to_string(), 1)
+                    .replacen("{}", tip, 1);
                 
                 Some(MicrolearningNudge {
                     id: format!("nudge_{}", chrono::Utc::now().timestamp()),
@@ -98,7 +196,14 @@ impl MicrolearningNudgeGenerator {
                     tip: tip.to_string(),
                     apply_action: Some(format!("Apply tip: {}", tip)),
                     error_pattern: Some(error_type.to_string()),
+                    provenance: Provenance {
+                        triggering_pattern: format!("Repeated error: {} ({} times)", error_type, pattern.frequency),
+                        data_used: vec!["error_frequency".to_string(), "error_context".to_string()],
+                        confidence: if pattern.frequency >= 5 { Confidence::High } else { Confidence::Medium },
+                        consent_scopes: vec!["behavioral_logging".to_string()],
+                    },
                     created_at: chrono::Utc::now().timestamp(),
+                    variant: self.wording_variant.clone(),
                 })
             } else {
                 None
@@ -108,19 +213,35 @@ impl MicrolearningNudgeGenerator {
         }
     }
 
-    /// Generate nudge for inefficient pattern
-    pub fn generate_inefficiency_nudge(&self, pattern_desc: &str, suggestion: &str) -> MicrolearningNudge {
+    /// Generate nudge for inefficient pattern (None if the suggestion is suppressed)
+    pub fn generate_inefficiency_nudge(&self, pattern_desc: &str, suggestion: &str) -> Option<MicrolearningNudge> {
         info!("MicrolearningNudgeGenerator::generate_inefficiency_nudge: Generating nudge for pattern");
         
-        MicrolearningNudge {
+        if self.is_suppressed(suggestion) {
+            return None;
+        }
+        
+        let content = if self.alternative_wording() {
+            format!("{} could be quicker: {}", pattern_desc, suggestion)
+        } else {
+            format!("Pattern detected: {}. Suggestion: {}", pattern_desc, suggestion)
+        };
+        Some(MicrolearningNudge {
             id: format!("nudge_{}", chrono::Utc::now().timestamp()),
             title: "Optimization opportunity".to_string(),
-            content: format!("Pattern detected: {}. Suggestion: {}", pattern_desc, suggestion),
+            content,
             tip: suggestion.to_string(),
             apply_action: Some(format!("Apply: {}", suggestion)),
             error_pattern: None,
+            provenance: Provenance {
+                triggering_pattern: format!("Inefficient pattern: {}", pattern_desc),
+                data_used: vec!["workflow_sequence".to_string()],
+                confidence: Confidence::Medium,
+                consent_scopes: vec!["behavioral_logging".to_string()],
+            },
             created_at: chrono::Utc::now().timestamp(),
-        }
+            variant: self.wording_variant.clone(),
+        })
     }
 
     /// Get all active nudges
@@ -136,6 +257,26 @@ impl MicrolearningNudgeGenerator {
             })
             .collect()
     }
+
+    /// Get active nudges that may be delivered now (held while focused, in a meeting or DND)
+    /// The caller delivers every returned nudge, so each counts toward NUDGE_DELIVERED_METRIC
+    pub fn get_deliverable_nudges(&mut self) -> Vec<MicrolearningNudge> {
+        if !self.attention.should_interrupt(InterruptionPriority::Low) {
+            info!("MicrolearningNudgeGenerator::get_deliverable_nudges: Holding nudges ({:?})", self.attention.current().state);
+            return Vec::new();
+        }
+        let nudges = self.get_active_nudges();
+        self.delivered += nudges.len();
+        nudges
+    }
+
+    /// Export delivered and dismissed counts since the last export to analytics
+    pub fn export_metrics(&mut self, analytics: &mut AnalyticsAggregator) {
+        analytics.record_metric(NUDGE_DELIVERED_METRIC.to_string(), self.delivered as f64, MetricCategory::UserEngagement);
+        analytics.record_metric(NUDGE_DISMISSED_METRIC.to_string(), self.dismissed as f64, MetricCategory::UserEngagement);
+        self.delivered = 0;
+        self.dismissed = 0;
+    }
 }
 
 impl Default for MicrolearningNudgeGenerator {
@@ -177,7 +318,7 @@ mod tests {
         let nudge = generator.generate_nudge("repeated_mistake", "Use the correct command");
         assert!(nudge.is_some());
         let nudge = nudge.unwrap();
-        assert!(nudge.content.contains("3 times"));
+        assert_eq!(nudge.content, "You've repeated this error 3 times. Try: Use the correct command");
         assert_eq!(nudge.error_pattern, Some("repeated_mistake".to_string()));
     }
 
@@ -187,10 +328,76 @@ mod tests {
         let nudge = generator.generate_inefficiency_nudge(
             "Repeated 10-step workflow",
             "Use 3-step shortcut"
-        );
+        ).unwrap();
         
         assert!(nudge.content.contains("Repeated 10-step workflow"));
         assert_eq!(nudge.tip, "Use 3-step shortcut");
     }
-}
 
+    #[test]
+    fn test_nudges_held_during_dnd() {
+        let attention = AttentionService::new();
+        let mut generator = MicrolearningNudgeGenerator::with_attention(attention.clone());
+        
+        for _ in 0..3 {
+            generator.detect_error_pattern("repeated_mistake".to_string(), "context".to_string());
+        }
+        assert_eq!(generator.get_deliverable_nudges().len(), 1);
+        
+        attention.enable_dnd("focus_mode", None);
+        assert!(generator.get_deliverable_nudges().is_empty());
+        assert_eq!(generator.get_active_nudges().len(), 1);
+    }
+
+    #[test]
+    fn test_delivery_metrics_exported() {
+        let mut generator = MicrolearningNudgeGenerator::new();
+        for _ in 0..3 {
+            generator.detect_error_pattern("repeated_mistake".to_string(), "context".to_string());
+        }
+        let nudges = generator.get_deliverable_nudges();
+        generator.dismiss_nudge(&nudges[0]).unwrap();
+
+        let mut analytics = AnalyticsAggregator::new();
+        generator.export_metrics(&mut analytics);
+        generator.export_metrics(&mut analytics); // Counts reset after each export
+        assert_eq!(analytics.sum_metric(NUDGE_DELIVERED_METRIC, 0, i64::MAX), 1.0);
+        assert_eq!(analytics.sum_metric(NUDGE_DISMISSED_METRIC, 0, i64::MAX), 1.0);
+    }
+
+    #[test]
+    fn test_dismissed_twice_suppresses_nudges() {
+        let mut generator = MicrolearningNudgeGenerator::new();
+        for _ in 0..3 {
+            generator.detect_error_pattern("repeated_mistake".to_string(), "context".to_string());
+        }
+        
+        let nudge = generator.generate_nudge("repeated_mistake", "Use the correct command").unwrap();
+        assert!(!generator.dismiss_nudge(&nudge).unwrap());
+        assert!(generator.dismiss_nudge(&nudge).unwrap());
+        
+        assert!(generator.generate_nudge("repeated_mistake", "Use the correct command").is_none());
+        assert!(generator.get_active_nudges().is_empty());
+    }
+
+    #[test]
+    fn test_wording_variant_and_outcomes() {
+        let mut generator = MicrolearningNudgeGenerator::new();
+        for _ in 0..3 {
+            generator.detect_error_pattern("repeated_mistake".to_string(), "context".to_string());
+        }
+        let original = generator.generate_nudge("repeated_mistake", "Use the correct command").unwrap();
+        generator.set_wording_variant(Some("B".to_string()));
+        let alternative = generator.generate_nudge("repeated_mistake", "Use the correct command").unwrap();
+        assert_ne!(original.content, alternative.content);
+        assert_eq!(alternative.content, "This has come up 3 times now. A quicker way: Use the correct command");
+        assert_eq!(alternative.variant.as_deref(), Some("B"));
+
+        generator.accept_nudge(&alternative);
+        generator.dismiss_nudge(&alternative).unwrap();
+        let outcomes = generator.take_outcomes();
+        assert_eq!(outcomes.iter().map(|o| o.accepted).collect::<Vec<_>>(), vec![true, false]);
+        assert!(outcomes.iter().all(|o| o.variant.as_deref() == Some("B")));
+        assert!(generator.take_outcomes().is_empty());
+    }
+}
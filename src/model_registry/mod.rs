@@ -0,0 +1,393 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L132
+/// Model Registry with Replay Canary
+/// Promote new PatternDetector/RLPolicy versions only after replay evaluation against the incumbent
+
+use crate::models::PatternDetector;
+use crate::replay::ReplaySimulator;
+use crate::rl_policy::RLPolicy;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Detector score below which the detector proposes no intervention
+pub const MIN_DETECTOR_PROPOSAL_SCORE: f64 = 0.5;
+
+/// Kind of registered model
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ModelKind {
+    PatternDetector,
+    RLPolicy,
+}
+
+/// Registered model (enum dispatch over supported model types)
+pub enum RegisteredModel {
+    PatternDetector(PatternDetector),
+    RLPolicy(RLPolicy),
+}
+
+impl RegisteredModel {
+    /// Get model kind
+    pub fn kind(&self) -> ModelKind {
+        match self {
+            RegisteredModel::PatternDetector(_) => ModelKind::PatternDetector,
+            RegisteredModel::RLPolicy(_) => ModelKind::RLPolicy,
+        }
+    }
+
+    /// Action the model would take for an observation, if any
+    /// A detector intervenes only when it scores the pattern at least MIN_DETECTOR_PROPOSAL_SCORE,
+    /// and the proposal carries the detector's own confidence rather than the logged one
+    fn propose_action(&self, observation: &Observation) -> Option<Action> {
+        match self {
+            RegisteredModel::PatternDetector(detector) => {
+                let score = detector.score_confidence(observation);
+                if score < MIN_DETECTOR_PROPOSAL_SCORE {
+                    return None;
+                }
+                let confidence = if score >= 0.8 { Confidence::High } else { Confidence::Medium };
+                Some(Action { confidence, ..observation.action.clone() })
+            }
+            RegisteredModel::RLPolicy(policy) => Some(policy.greedy_action(observation)),
+        }
+    }
+
+    /// Predicted acceptance (0.0 to 1.0) for an observation
+    fn predict_acceptance(&self, observation: &Observation) -> f64 {
+        match self {
+            RegisteredModel::PatternDetector(detector) => detector.score_confidence(observation),
+            RegisteredModel::RLPolicy(policy) => policy.predict_acceptance(observation),
+        }
+    }
+}
+
+/// Model version status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ModelStatus {
+    Active,
+    Retired,
+    Blocked,
+}
+
+/// Model version record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVersion {
+    pub kind: ModelKind,
+    pub version: String,
+    pub status: ModelStatus,
+    pub registered_at: i64,
+}
+
+/// Replay metrics for one model over the canary window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryMetrics {
+    pub observations: usize,
+    pub proposals: usize,     // Observations the model would have acted on
+    pub gate_pass_rate: f64,  // Share of observations with a proposal that passes the gate
+    pub predicted_acceptance: f64,
+    pub safety_violations: usize,
+    #[serde(default)]
+    pub labeled: usize,                  // Observations with a recorded outcome
+    #[serde(default)]
+    pub acceptance_error: Option<f64>,   // Mean |predicted acceptance - outcome| over labeled observations
+}
+
+/// Canary comparison between candidate and incumbent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryReport {
+    pub kind: ModelKind,
+    pub candidate_version: String,
+    pub incumbent_version: Option<String>,
+    pub candidate: CanaryMetrics,
+    pub incumbent: Option<CanaryMetrics>,
+    pub regressions: Vec<String>,
+    pub promoted: bool,
+}
+
+/// Model registry gating promotion on replay canary evaluation
+pub struct ModelRegistry {
+    active: HashMap<ModelKind, (String, RegisteredModel)>,
+    versions: Vec<ModelVersion>,
+    replay_simulator: ReplaySimulator,
+    window_days: i64,
+    tolerance: f64, // Allowed drop in rates before counting as a regression
+}
+
+impl ModelRegistry {
+    /// Create new model registry (30-day canary window)
+    pub fn new() -> Self {
+        info!("ModelRegistry::new: Creating model registry");
+        Self {
+            active: HashMap::new(),
+            versions: Vec::new(),
+            replay_simulator: ReplaySimulator::new(),
+            window_days: 30,
+            tolerance: 0.02,
+        }
+    }
+
+    /// Evaluate a candidate against the incumbent on recent logged observations
+    /// and promote it only if gate pass rate, acceptance prediction, safety and agreement
+    /// with recorded outcomes (observation id -> accepted) do not regress
+    pub fn promote(
+        &mut self,
+        version: &str,
+        candidate: RegisteredModel,
+        observations: &[Observation],
+        outcomes: &HashMap<String, bool>,
+        now: i64,
+    ) -> Result<CanaryReport, String> {
+        let kind = candidate.kind();
+        info!("ModelRegistry::promote: Canary evaluation for {:?} {}", kind, version);
+
+        let window_start = now - self.window_days * 24 * 3600;
+        let recent: Vec<&Observation> = observations
+            .iter()
+            .filter(|obs| obs.timestamp >= window_start && obs.timestamp <= now)
+            .collect();
+        if recent.is_empty() {
+            return Err(format!("No logged observations in the last {} days for canary evaluation", self.window_days));
+        }
+
+        let candidate_metrics = Self::evaluate(&mut self.replay_simulator, &candidate, &recent, outcomes);
+        let incumbent_metrics = match self.active.get(&kind) {
+            Some((_, incumbent)) => Some(Self::evaluate(&mut self.replay_simulator, incumbent, &recent, outcomes)),
+            None => None,
+        };
+
+        let mut regressions = Vec::new();
+        if let Some(incumbent) = &incumbent_metrics {
+            if candidate_metrics.gate_pass_rate < incumbent.gate_pass_rate - self.tolerance {
+                regressions.push(format!(
+                    "Gate pass rate regressed: {:.2} -> {:.2}",
+                    incumbent.gate_pass_rate, candidate_metrics.gate_pass_rate
+                ));
+            }
+            if candidate_metrics.predicted_acceptance < incumbent.predicted_acceptance - self.tolerance {
+                regressions.push(format!(
+                    "Predicted acceptance regressed: {:.2} -> {:.2}",
+                    incumbent.predicted_acceptance, candidate_metrics.predicted_acceptance
+                ));
+            }
+            if let (Some(candidate_error), Some(incumbent_error)) = (candidate_metrics.acceptance_error, incumbent.acceptance_error) {
+                if candidate_error > incumbent_error + self.tolerance {
+                    regressions.push(format!("Acceptance error against outcomes regressed: {:.2} -> {:.2}", incumbent_error, candidate_error));
+                }
+            }
+            if candidate_metrics.safety_violations > incumbent.safety_violations {
+                regressions.push(format!(
+                    "Safety violations increased: {} -> {}",
+                    incumbent.safety_violations, candidate_metrics.safety_violations
+                ));
+            }
+        }
+
+        let promoted = regressions.is_empty();
+        let incumbent_version = self.active.get(&kind).map(|(v, _)| v.clone());
+
+        if promoted {
+            info!("ModelRegistry::promote: Promoting {:?} {}", kind, version);
+            for record in self.versions.iter_mut().filter(|v| v.kind == kind && v.status == ModelStatus::Active) {
+                record.status = ModelStatus::Retired;
+            }
+            self.active.insert(kind, (version.to_string(), candidate));
+        } else {
+            info!("ModelRegistry::promote: Blocking {:?} {} ({} regressions)", kind, version, regressions.len());
+        }
+
+        self.versions.push(ModelVersion {
+            kind,
+            version: version.to_string(),
+            status: if promoted { ModelStatus::Active } else { ModelStatus::Blocked },
+            registered_at: now,
+        });
+
+        Ok(CanaryReport {
+            kind,
+            candidate_version: version.to_string(),
+            incumbent_version,
+            candidate: candidate_metrics,
+            incumbent: incumbent_metrics,
+            regressions,
+            promoted,
+        })
+    }
+
+    /// Replay observations with the model's proposed actions
+    fn evaluate(simulator: &mut ReplaySimulator, model: &RegisteredModel, observations: &[&Observation], outcomes: &HashMap<String, bool>) -> CanaryMetrics {
+        let mut labeled = 0;
+        let mut error_sum = 0.0;
+        let mut proposals = 0;
+        let mut gate_passes = 0;
+        let mut safety_violations = 0;
+        let mut acceptance_sum = 0.0;
+
+        for obs in observations {
+            let predicted = model.predict_acceptance(obs);
+            acceptance_sum += predicted;
+            if let Some(accepted) = outcomes.get(&obs.id) {
+                labeled += 1;
+                error_sum += (predicted - if *accepted { 1.0 } else { 0.0 }).abs();
+            }
+            let Some(action) = model.propose_action(obs) else {
+                continue;
+            };
+            proposals += 1;
+            let mut replayed = (*obs).clone();
+            replayed.action = action;
+
+            let result = simulator.replay_action(&replayed);
+            if simulator.gate_action(&result) {
+                gate_passes += 1;
+            }
+            if !result.action_safe {
+                safety_violations += 1;
+            }
+        }
+
+        let count = observations.len().max(1) as f64;
+        CanaryMetrics {
+            observations: observations.len(),
+            proposals,
+            gate_pass_rate: gate_passes as f64 / count,
+            predicted_acceptance: acceptance_sum / count,
+            safety_violations,
+            labeled,
+            acceptance_error: (labeled > 0).then(|| error_sum / labeled as f64),
+        }
+    }
+
+    /// Get active model for kind
+    pub fn get_active(&self, kind: ModelKind) -> Option<&RegisteredModel> {
+        self.active.get(&kind).map(|(_, model)| model)
+    }
+
+    /// Get active version string for kind
+    pub fn get_active_version(&self, kind: ModelKind) -> Option<&str> {
+        self.active.get(&kind).map(|(version, _)| version.as_str())
+    }
+
+    /// Get version history
+    pub fn get_versions(&self) -> &[ModelVersion] {
+        &self.versions
+    }
+}
+
+impl Default for ModelRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn logged_observation(id: &str, repeat_count: f64, timestamp: i64) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), repeat_count);
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Logged focus block".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_first_version_promoted() {
+        let mut registry = ModelRegistry::new();
+        let observations = vec![logged_observation("obs1", 8.0, NOW - 3600)];
+
+        let report = registry
+            .promote("pd-1", RegisteredModel::PatternDetector(PatternDetector::new()), &observations, &HashMap::new(), NOW)
+            .unwrap();
+        assert!(report.promoted);
+        assert!(report.incumbent.is_none());
+        assert_eq!(registry.get_active_version(ModelKind::PatternDetector), Some("pd-1"));
+    }
+
+    #[test]
+    fn test_unsafe_policy_blocked() {
+        let mut registry = ModelRegistry::new();
+        let observations = vec![logged_observation("obs1", 8.0, NOW - 3600)];
+        registry.promote("rl-1", RegisteredModel::RLPolicy(RLPolicy::new()), &observations, &HashMap::new(), NOW).unwrap();
+
+        // Candidate learned to prefer a high-risk action for this state
+        let mut candidate = RLPolicy::new();
+        let mut risky = observations[0].clone();
+        risky.action.risk = RiskCategory::High;
+        candidate.update_from_outcome(&risky, &Outcome {
+            observation_id: "obs1".to_string(),
+            accepted: true,
+            ignored: false,
+            modified: false,
+            time_saved_minutes: Some(10.0),
+            error_rate_change: None,
+            timestamp: NOW,
+        });
+
+        let report = registry.promote("rl-2", RegisteredModel::RLPolicy(candidate), &observations, &HashMap::new(), NOW).unwrap();
+        assert!(!report.promoted);
+        assert!(report.regressions.iter().any(|r| r.contains("Safety")));
+        assert_eq!(registry.get_active_version(ModelKind::RLPolicy), Some("rl-1"));
+        assert_eq!(registry.get_versions().last().unwrap().status, ModelStatus::Blocked);
+    }
+
+    #[test]
+    fn test_detector_candidate_judged_on_its_own_proposals() {
+        let mut registry = ModelRegistry::new();
+        let observations = vec![logged_observation("obs1", 200.0, NOW - 3600), logged_observation("obs2", 250.0, NOW - 1800)];
+        let report = registry.promote("pd-1", RegisteredModel::PatternDetector(PatternDetector::new()), &observations, &HashMap::new(), NOW).unwrap();
+        assert_eq!(report.candidate.proposals, 2);
+
+        // A candidate that no longer scores these patterns would stop intervening on them
+        let mut candidate = PatternDetector::new();
+        let mut weights = candidate.export_weights();
+        weights.global.insert("repeat_count".to_string(), 0.01);
+        candidate.import_weights(weights).unwrap();
+        let report = registry.promote("pd-2", RegisteredModel::PatternDetector(candidate), &observations, &HashMap::new(), NOW).unwrap();
+        assert_eq!(report.candidate.proposals, 0);
+        assert_eq!(report.incumbent.unwrap().proposals, 2);
+        assert!(!report.promoted);
+    }
+
+    #[test]
+    fn test_candidate_blocked_when_it_disagrees_with_outcomes() {
+        let mut registry = ModelRegistry::new();
+        let observations = vec![logged_observation("obs1", 200.0, NOW - 3600), logged_observation("obs2", 250.0, NOW - 1800)];
+        let outcomes: HashMap<String, bool> = [("obs1".to_string(), true), ("obs2".to_string(), true)].into_iter().collect();
+        registry.promote("pd-1", RegisteredModel::PatternDetector(PatternDetector::new()), &observations, &outcomes, NOW).unwrap();
+
+        let mut candidate = PatternDetector::new();
+        let mut weights = candidate.export_weights();
+        weights.global.insert("repeat_count".to_string(), 0.01);
+        candidate.import_weights(weights).unwrap();
+        let report = registry.promote("pd-2", RegisteredModel::PatternDetector(candidate), &observations, &outcomes, NOW).unwrap();
+        assert_eq!(report.candidate.labeled, 2);
+        assert!(report.regressions.iter().any(|r| r.contains("Acceptance error")));
+        assert_eq!(registry.get_active_version(ModelKind::PatternDetector), Some("pd-1"));
+    }
+
+    #[test]
+    fn test_requires_recent_observations() {
+        let mut registry = ModelRegistry::new();
+        let stale = vec![logged_observation("old", 8.0, NOW - 45 * 24 * 3600)];
+
+        let result = registry.promote("pd-1", RegisteredModel::PatternDetector(PatternDetector::new()), &stale, &HashMap::new(), NOW);
+        assert!(result.is_err());
+    }
+}
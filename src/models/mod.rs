@@ -3,14 +3,69 @@
 /// Train supervised models for pattern detection and recommendation ranking
 
 use crate::types::*;
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+/// Identical mouse/keyboard micro-sequence repeats that indicate a RepetitiveGesture
+pub const GESTURE_REPEAT_THRESHOLD: f64 = 5.0;
+
+/// Attention fragmentation score (0.0 to 1.0) that indicates AttentionFragmentation
+pub const FRAGMENTATION_SCORE_THRESHOLD: f64 = 0.6;
+
+/// Keeps the normalized update finite for observations with near-zero features
+const NLMS_EPSILON: f64 = 1e-6;
+
+/// Online-learning settings for PatternDetector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorTrainingConfig {
+    pub learning_rate: f64,  // Normalized (NLMS) step size; updates are stable for 0 < rate < 2
+    pub half_life_days: f64, // An observation this old counts half as much; learned weights drift halfway back to the defaults
+    pub min_weight: f64,
+    pub max_weight: f64, // Features are metric / 100, so weights well above 1 are needed to reach high targets from small metrics
+}
+
+impl Default for DetectorTrainingConfig {
+    fn default() -> Self {
+        Self {
+            learning_rate: 0.1,
+            half_life_days: 30.0,
+            min_weight: 0.0,
+            max_weight: 10.0,
+        }
+    }
+}
+
+/// Detector weights for persistence and federated sharing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectorWeights {
+    pub global: HashMap<String, f64>,
+    pub profiles: HashMap<UserProfile, HashMap<String, f64>>, // Profiles trained separately from the global set
+    #[serde(default)]
+    pub trained_at: Option<i64>, // Wall-clock time of the last training; weight decay runs from here
+    #[serde(default)]
+    pub trained_through: Option<i64>, // Newest observation already learned from; older ones are not learned again
+}
+
+/// Labeled confidence the detector's score is trained toward
+fn confidence_target(confidence: &Confidence) -> f64 {
+    match confidence {
+        Confidence::High => 0.9,
+        Confidence::Medium => 0.6,
+        Confidence::Low => 0.3,
+    }
+}
+
 /// Pattern detection model (simplified for Phase B)
 /// Uses heuristic-based approach with feature weights
 pub struct PatternDetector {
     weights: HashMap<String, f64>,
+    profile_weights: HashMap<UserProfile, HashMap<String, f64>>, // Used instead of `weights` for profiles with training data
+    prior: HashMap<String, f64>,                                 // Untrained weights that learned ones decay back toward
+    training: DetectorTrainingConfig,
+    trained_at: Option<i64>,
+    trained_through: Option<i64>,
 }
 
 impl PatternDetector {
@@ -22,22 +77,120 @@ impl PatternDetector {
         weights.insert("time_to_first_code_min".to_string(), 0.2);
         weights.insert("context_switch_count".to_string(), 0.25);
         weights.insert("focus_fragmentation_pct".to_string(), 0.25);
-        Self { weights }
+        Self {
+            prior: weights.clone(),
+            weights,
+            profile_weights: HashMap::new(),
+            training: DetectorTrainingConfig::default(),
+            trained_at: None,
+            trained_through: None,
+        }
     }
 
-    /// Train on observations (Phase B: simple weight adjustment)
+    pub fn set_training_config(&mut self, config: DetectorTrainingConfig) {
+        self.training = config;
+    }
+
+    /// Train on observations, decaying by age relative to the current time
     /// Source: Athenos_AI_Strategy.md#L108
     pub fn train(&mut self, observations: &[Observation]) {
-        info!("PatternDetector::train: Training on {} observations", observations.len());
-        // Phase B: Simple heuristic training
-        // In production, would use proper ML training
-        for obs in observations {
-            if obs.metrics.get("repeat_count").copied().unwrap_or(0.0) > 5.0 {
-                *self.weights.get_mut("repeat_count").unwrap() *= 1.1;
+        self.train_at(observations, chrono::Utc::now().timestamp());
+    }
+
+    /// Online update toward each observation's labeled confidence, oldest first
+    /// Learned weights first decay toward the defaults for the time since the last training; observations at or before
+    /// the newest one already learned from are skipped, so retraining on the same history changes nothing
+    /// Both the global weights and the observation's profile weights learn; a profile starts from the global set
+    pub fn train_at(&mut self, observations: &[Observation], now: i64) {
+        self.decay_weights(now);
+        let mut ordered: Vec<&Observation> = observations
+            .iter()
+            .filter(|obs| self.trained_through.is_none_or(|through| obs.timestamp > through))
+            .collect();
+        ordered.sort_by_key(|obs| obs.timestamp);
+        info!("PatternDetector::train: Training on {} of {} observations", ordered.len(), observations.len());
+
+        for obs in ordered {
+            let recency = self.half_lives_factor(now - obs.timestamp);
+            let target = confidence_target(&obs.action.confidence);
+            let profile = self.profile_weights.entry(obs.profile.clone()).or_insert_with(|| self.weights.clone());
+            Self::update(profile, obs, target, recency, &self.training);
+            Self::update(&mut self.weights, obs, target, recency, &self.training);
+            self.trained_through = Some(obs.timestamp);
+        }
+    }
+
+    /// 0.5 per half-life elapsed
+    fn half_lives_factor(&self, elapsed_secs: i64) -> f64 {
+        let days = elapsed_secs.max(0) as f64 / 86400.0;
+        0.5f64.powf(days / self.training.half_life_days.max(f64::EPSILON))
+    }
+
+    /// Move every learned weight back toward its default for the time elapsed since the last training
+    fn decay_weights(&mut self, now: i64) {
+        if let Some(last) = self.trained_at.filter(|last| now > *last) {
+            let keep = self.half_lives_factor(now - last);
+            for set in std::iter::once(&mut self.weights).chain(self.profile_weights.values_mut()) {
+                for (key, weight) in set.iter_mut() {
+                    let prior = self.prior.get(key).copied().unwrap_or(*weight);
+                    *weight = prior + (*weight - prior) * keep;
+                }
+            }
+        }
+        self.trained_at = Some(self.trained_at.map_or(now, |last| last.max(now)));
+    }
+
+    /// Normalized least-mean-squares step: the step shrinks with the feature magnitude, so large metrics can't blow up the weights
+    fn update(weights: &mut HashMap<String, f64>, obs: &Observation, target: f64, recency: f64, config: &DetectorTrainingConfig) {
+        // Features on the same scale score_confidence uses
+        let features: Vec<(String, f64)> = weights
+            .keys()
+            .filter_map(|key| obs.metrics.get(key).filter(|v| v.is_finite()).map(|v| (key.clone(), v / 100.0)))
+            .collect();
+        let norm: f64 = features.iter().map(|(_, x)| x * x).sum();
+        if norm == 0.0 {
+            return;
+        }
+        let prediction: f64 = features.iter().map(|(key, x)| x * weights[key]).sum();
+        let step = config.learning_rate * recency * (target - prediction) / (norm + NLMS_EPSILON);
+        for (key, x) in features {
+            if let Some(weight) = weights.get_mut(&key) {
+                *weight = (*weight + step * x).clamp(config.min_weight, config.max_weight);
             }
         }
     }
 
+    /// Snapshot of the global and per-profile weights
+    pub fn export_weights(&self) -> DetectorWeights {
+        DetectorWeights {
+            global: self.weights.clone(),
+            profiles: self.profile_weights.clone(),
+            trained_at: self.trained_at,
+            trained_through: self.trained_through,
+        }
+    }
+
+    /// Replace all weights; rejects non-finite or out-of-range values
+    pub fn import_weights(&mut self, weights: DetectorWeights) -> Result<(), String> {
+        if weights.global.is_empty() {
+            return Err("Imported weights have no global feature weights".to_string());
+        }
+        let sets = std::iter::once(("global".to_string(), &weights.global))
+            .chain(weights.profiles.iter().map(|(profile, set)| (format!("{:?}", profile), set)));
+        for (name, set) in sets {
+            let range = self.training.min_weight..=self.training.max_weight;
+            if let Some((feature, value)) = set.iter().find(|(_, v)| !v.is_finite() || !range.contains(*v)) {
+                return Err(format!("Weight {}.{} = {} is outside [{}, {}]", name, feature, value, self.training.min_weight, self.training.max_weight));
+            }
+        }
+        info!("PatternDetector::import_weights: Imported {} features, {} profile sets", weights.global.len(), weights.profiles.len());
+        self.weights = weights.global;
+        self.profile_weights = weights.profiles;
+        self.trained_at = weights.trained_at;
+        self.trained_through = weights.trained_through;
+        Ok(())
+    }
+
     /// Detect pattern from observation
     pub fn detect_pattern(&self, observation: &Observation) -> PatternType {
         info!("PatternDetector::detect_pattern: Detecting pattern for {}", observation.id);
@@ -45,11 +198,17 @@ impl PatternDetector {
         let repeat_count = observation.metrics.get("repeat_count").copied().unwrap_or(0.0);
         let context_switches = observation.metrics.get("context_switch_count").copied().unwrap_or(0.0);
         let fragmentation = observation.metrics.get("focus_fragmentation_pct").copied().unwrap_or(0.0);
+        let gesture_repeats = observation.metrics.get("gesture_repeat_count").copied().unwrap_or(0.0);
+        let fragmentation_score = observation.metrics.get("attention_fragmentation_score").copied().unwrap_or(0.0);
         
         if repeat_count > 5.0 && observation.observation.len() >= 3 {
             PatternType::WorkflowSequence
-        } else if observation.observation.contains(&"copy_error".to_string()) {
+        } else if observation.observation.iter().any(|step| step == "copy_error" || step == "shell_error") {
             PatternType::DebuggingLoop
+        } else if gesture_repeats >= GESTURE_REPEAT_THRESHOLD {
+            PatternType::RepetitiveGesture
+        } else if fragmentation_score >= FRAGMENTATION_SCORE_THRESHOLD {
+            PatternType::AttentionFragmentation
         } else if context_switches > 5.0 || fragmentation > 50.0 {
             PatternType::ContextSwitching
         } else {
@@ -59,9 +218,11 @@ impl PatternDetector {
 
     /// Score pattern confidence (0.0 to 1.0)
     pub fn score_confidence(&self, observation: &Observation) -> f64 {
+        let weights = self.profile_weights.get(&observation.profile).unwrap_or(&self.weights);
         let mut score = 0.0;
-        for (key, weight) in &self.weights {
-            if let Some(value) = observation.metrics.get(key) {
+        for (key, weight) in weights {
+            // Missing or non-finite metrics contribute nothing
+            if let Some(value) = observation.metrics.get(key).filter(|v| v.is_finite()) {
                 score += value * weight;
             }
         }
@@ -75,10 +236,101 @@ impl Default for PatternDetector {
     }
 }
 
+/// Total-order ranking score; NaN sorts as negative infinity
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RankScore(pub f64);
+
+impl RankScore {
+    fn key(&self) -> f64 {
+        if self.0.is_nan() { f64::NEG_INFINITY } else { self.0 }
+    }
+}
+
+impl PartialEq for RankScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for RankScore {}
+
+impl PartialOrd for RankScore {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankScore {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().total_cmp(&other.key())
+    }
+}
+
+/// Weight of each new realized outcome in the savings calibration
+const CALIBRATION_DECAY: f64 = 0.3;
+/// Realized outcomes needed before a proposal type can be flagged
+const MIN_CALIBRATION_SAMPLES: usize = 3;
+/// Realized/predicted ratio below which a proposal type is chronically overestimating
+const OVERESTIMATE_RATIO: f64 = 0.7;
+/// Weight of each new user edit in the modification rate
+const MODIFICATION_DECAY: f64 = 0.3;
+
+/// Predicted vs realized savings for one proposal type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavingsCalibration {
+    pub samples: usize,
+    pub predicted_total_min: f64,
+    pub realized_total_min: f64,
+    pub ratio: f64, // Exponentially decayed realized/predicted ratio (1.0 = accurate)
+}
+
+impl Default for SavingsCalibration {
+    fn default() -> Self {
+        Self {
+            samples: 0,
+            predicted_total_min: 0.0,
+            realized_total_min: 0.0,
+            ratio: 1.0,
+        }
+    }
+}
+
+/// How heavily users edit proposals of one type before accepting them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModificationStats {
+    pub samples: usize,
+    pub edit_rate: f64, // Exponentially decayed share of each proposal the user rewrote (0.0 = accepted as-is)
+}
+
+/// Per-candidate scoring components of a ranking
+/// score = (pattern_score * 0.4 + time_saved_min / 100 * 0.6) * confidence_multiplier * risk_penalty * workload_factor * edit_factor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub observation_id: String,
+    pub action_type: ActionType,
+    pub pattern_score: f64,
+    pub predicted_time_saved_min: f64,
+    pub savings_decay: f64, // Calibration toward realized savings
+    pub time_saved_min: f64, // predicted * decay
+    pub confidence_multiplier: f64,
+    pub risk_penalty: f64,
+    pub workload_factor: f64,
+    #[serde(default = "default_edit_factor")]
+    pub edit_factor: f64, // Damping from user edits of this proposal type
+    pub score: f64,
+}
+
+fn default_edit_factor() -> f64 {
+    1.0
+}
+
 /// Recommendation ranker
 /// Source: Athenos_AI_Strategy.md#L108
 pub struct RecommendationRanker {
     pattern_detector: PatternDetector,
+    calibration: HashMap<ActionType, SavingsCalibration>,
+    modifications: HashMap<ActionType, ModificationStats>,
+    external_load: f64, // 0.0 to 1.0, from third-party context (CI, alerts, assignments)
 }
 
 impl RecommendationRanker {
@@ -87,6 +339,27 @@ impl RecommendationRanker {
         info!("RecommendationRanker::new: Creating recommendation ranker");
         Self {
             pattern_detector: PatternDetector::new(),
+            calibration: HashMap::new(),
+            modifications: HashMap::new(),
+            external_load: 0.0,
+        }
+    }
+
+    /// Set current external workload (0.0 to 1.0)
+    pub fn set_external_load(&mut self, load: f64) {
+        self.external_load = if load.is_finite() { load.clamp(0.0, 1.0) } else { 0.0 };
+    }
+
+    /// During external workload spikes, protective actions rise and interruptions are damped
+    fn workload_factor(&self, action_type: &ActionType) -> f64 {
+        let protective = matches!(
+            action_type,
+            ActionType::FocusMode | ActionType::IntelligentFocusMode | ActionType::ZenMode | ActionType::BatchingSuggestion
+        );
+        if protective {
+            1.0 + 0.5 * self.external_load
+        } else {
+            1.0 - 0.5 * self.external_load
         }
     }
 
@@ -94,36 +367,155 @@ impl RecommendationRanker {
     /// Source: Athenos_AI_Strategy.md#L108
     pub fn rank_actions(&self, observations: &[Observation]) -> Vec<(Observation, f64)> {
         info!("RecommendationRanker::rank_actions: Ranking {} observations", observations.len());
-        let mut ranked: Vec<(Observation, f64)> = observations
-            .iter()
-            .map(|obs| {
-                let pattern_score = self.pattern_detector.score_confidence(obs);
-                let time_saved = obs.expected_outcome.get("time_saved_min").copied().unwrap_or(0.0);
-                let confidence_multiplier = match obs.action.confidence {
-                    Confidence::High => 1.0,
-                    Confidence::Medium => 0.7,
-                    Confidence::Low => 0.4,
-                };
-                let risk_penalty = match obs.action.risk {
-                    RiskCategory::None => 1.0,
-                    RiskCategory::Low => 0.8,
-                    RiskCategory::High => 0.3,
-                };
-                
-                let score = (pattern_score * 0.4 + time_saved / 100.0 * 0.6) * confidence_multiplier * risk_penalty;
-                (obs.clone(), score)
-            })
-            .collect();
+        self.ranked(observations)
+            .into_iter()
+            .map(|(obs, breakdown)| (obs.clone(), breakdown.score))
+            .collect()
+    }
+
+    /// Rank actions and explain every scoring component, in ranking order
+    pub fn rank_with_breakdown(&self, observations: &[Observation]) -> Vec<ScoreBreakdown> {
+        info!("RecommendationRanker::rank_with_breakdown: Ranking {} observations", observations.len());
+        self.ranked(observations).into_iter().map(|(_, breakdown)| breakdown).collect()
+    }
+
+    fn ranked<'a>(&self, observations: &'a [Observation]) -> Vec<(&'a Observation, ScoreBreakdown)> {
+        let mut ranked: Vec<(&Observation, ScoreBreakdown)> = observations.iter().map(|obs| (obs, self.breakdown(obs))).collect();
         
-        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        // Deterministic order: score desc, then expected savings desc, then id asc
+        ranked.sort_by(|a, b| {
+            RankScore(b.1.score)
+                .cmp(&RankScore(a.1.score))
+                .then_with(|| RankScore(b.1.time_saved_min).cmp(&RankScore(a.1.time_saved_min)))
+                .then_with(|| a.0.id.cmp(&b.0.id))
+        });
         ranked
     }
 
+    fn breakdown(&self, obs: &Observation) -> ScoreBreakdown {
+        let pattern_score = self.pattern_detector.score_confidence(obs);
+        let time_saved = self.calibrated_savings(obs);
+        let confidence_multiplier = match obs.action.confidence {
+            Confidence::High => 1.0,
+            Confidence::Medium => 0.7,
+            Confidence::Low => 0.4,
+        };
+        let risk_penalty = match obs.action.risk {
+            RiskCategory::None => 1.0,
+            RiskCategory::Low => 0.8,
+            RiskCategory::High => 0.3,
+        };
+        let workload_factor = self.workload_factor(&obs.action.action_type);
+        let edit_factor = self.edit_factor(&obs.action.action_type);
+        
+        let score = (pattern_score * 0.4 + time_saved / 100.0 * 0.6)
+            * confidence_multiplier
+            * risk_penalty
+            * workload_factor
+            * edit_factor;
+        ScoreBreakdown {
+            observation_id: obs.id.clone(),
+            action_type: obs.action.action_type.clone(),
+            pattern_score,
+            predicted_time_saved_min: Self::expected_savings(obs),
+            savings_decay: self.savings_decay(&obs.action.action_type),
+            time_saved_min: time_saved,
+            confidence_multiplier,
+            risk_penalty,
+            workload_factor,
+            edit_factor,
+            score,
+        }
+    }
+
+    /// Expected time savings; missing or non-finite values count as zero
+    fn expected_savings(observation: &Observation) -> f64 {
+        observation.expected_outcome
+            .get("time_saved_min")
+            .copied()
+            .filter(|v| v.is_finite())
+            .unwrap_or(0.0)
+    }
+
+    /// Predicted savings decayed toward realized savings for the action type (never inflated)
+    fn calibrated_savings(&self, observation: &Observation) -> f64 {
+        Self::expected_savings(observation) * self.savings_decay(&observation.action.action_type)
+    }
+
+    /// Calibration factor applied to predicted savings (never inflates)
+    fn savings_decay(&self, action_type: &ActionType) -> f64 {
+        self.calibration.get(action_type).map(|c| c.ratio.min(1.0)).unwrap_or(1.0)
+    }
+
+    /// Record realized savings for an approved proposal, decaying its type's predictions toward reality
+    pub fn record_realized_outcome(&mut self, action_type: ActionType, predicted_min: f64, realized_min: f64) {
+        if !predicted_min.is_finite() || !realized_min.is_finite() || predicted_min <= 0.0 {
+            return;
+        }
+        let calibration = self.calibration.entry(action_type.clone()).or_default();
+        let observed = (realized_min.max(0.0) / predicted_min).min(2.0);
+        calibration.ratio = calibration.ratio * (1.0 - CALIBRATION_DECAY) + observed * CALIBRATION_DECAY;
+        calibration.samples += 1;
+        calibration.predicted_total_min += predicted_min;
+        calibration.realized_total_min += realized_min.max(0.0);
+        info!("RecommendationRanker::record_realized_outcome: {:?} ratio now {:.2}", action_type, calibration.ratio);
+    }
+
+    /// Get savings calibration for an action type
+    pub fn get_calibration(&self, action_type: &ActionType) -> Option<&SavingsCalibration> {
+        self.calibration.get(action_type)
+    }
+
+    /// Proposal types whose realized savings chronically undershoot predictions
+    pub fn overestimating_types(&self) -> Vec<(ActionType, f64)> {
+        let mut flagged: Vec<(ActionType, f64)> = self.calibration
+            .iter()
+            .filter(|(_, c)| c.samples >= MIN_CALIBRATION_SAMPLES && c.ratio < OVERESTIMATE_RATIO)
+            .map(|(action_type, c)| (action_type.clone(), c.ratio))
+            .collect();
+        flagged.sort_by_key(|(_, ratio)| RankScore(*ratio));
+        flagged
+    }
+
+    /// Export calibration ratios and overestimation flags to analytics
+    pub fn export_calibration_metrics(&self, analytics: &mut AnalyticsAggregator) {
+        let name = |action_type: &ActionType| action_type.consent_scope().trim_start_matches(AUTOMATION_SCOPE_PREFIX).to_string();
+        for (action_type, calibration) in &self.calibration {
+            analytics.record_metric(format!("ranking.savings_ratio.{}", name(action_type)), calibration.ratio, MetricCategory::Product);
+        }
+        for (action_type, ratio) in self.overestimating_types() {
+            info!("RecommendationRanker::export_calibration_metrics: {:?} overestimates savings (ratio {:.2})", action_type, ratio);
+            analytics.record_metric(format!("ranking.overestimating.{}", name(&action_type)), 1.0, MetricCategory::Product);
+        }
+    }
+
     /// Train ranker on observations
     pub fn train(&mut self, observations: &[Observation]) {
         info!("RecommendationRanker::train: Training ranker on {} observations", observations.len());
         self.pattern_detector.train(observations);
     }
+
+    /// Train on a user's edit of a proposal; `edit_ratio` is the share of the proposal they rewrote (0.0 to 1.0)
+    /// Types users keep rewriting rank lower until their proposals are accepted as-is again
+    pub fn record_modification(&mut self, action_type: ActionType, edit_ratio: f64) {
+        if !edit_ratio.is_finite() {
+            return;
+        }
+        let stats = self.modifications.entry(action_type.clone()).or_default();
+        stats.edit_rate = stats.edit_rate * (1.0 - MODIFICATION_DECAY) + edit_ratio.clamp(0.0, 1.0) * MODIFICATION_DECAY;
+        stats.samples += 1;
+        info!("RecommendationRanker::record_modification: {:?} edit rate now {:.2}", action_type, stats.edit_rate);
+    }
+
+    /// Get user edit statistics for an action type
+    pub fn get_modification_stats(&self, action_type: &ActionType) -> Option<&ModificationStats> {
+        self.modifications.get(action_type)
+    }
+
+    /// Score damping from user edits: a type always rewritten in full ranks at half weight
+    fn edit_factor(&self, action_type: &ActionType) -> f64 {
+        self.modifications.get(action_type).map(|m| 1.0 - 0.5 * m.edit_rate).unwrap_or(1.0)
+    }
 }
 
 impl Default for RecommendationRanker {
@@ -164,6 +556,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let pattern = detector.detect_pattern(&observation);
@@ -188,12 +581,44 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let pattern = detector.detect_pattern(&observation);
         assert_eq!(pattern, PatternType::DebuggingLoop);
     }
 
+    #[test]
+    fn test_pattern_detection_covers_taxonomy() {
+        let detector = PatternDetector::new();
+        let observation = |metric: &str, value: f64| {
+            let mut metrics = HashMap::new();
+            metrics.insert(metric.to_string(), value);
+            Observation {
+                id: "test".to_string(),
+                profile: UserProfile::Developer,
+                observation: vec!["IDE".to_string()],
+                metrics,
+                intent: Intent::DetectPattern,
+                action: Action {
+                    action_type: ActionType::AutomationMacro,
+                    description: "Test".to_string(),
+                    confidence: Confidence::High,
+                    risk: RiskCategory::None,
+                },
+                expected_outcome: HashMap::new(),
+                source: "test".to_string(),
+                timestamp: 1234567890,
+                project: None,
+            }
+        };
+        
+        assert_eq!(detector.detect_pattern(&observation("gesture_repeat_count", 6.0)), PatternType::RepetitiveGesture);
+        assert_eq!(detector.detect_pattern(&observation("attention_fragmentation_score", 0.8)), PatternType::AttentionFragmentation);
+        assert_eq!(detector.detect_pattern(&observation("context_switch_count", 9.0)), PatternType::ContextSwitching);
+        assert_eq!(detector.detect_pattern(&observation("attention_fragmentation_score", 0.2)), PatternType::TimingVariance);
+    }
+
     #[test]
     fn test_recommendation_ranking() {
         let ranker = RecommendationRanker::new();
@@ -222,6 +647,7 @@ mod tests {
             expected_outcome: expected1,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let obs2 = Observation {
@@ -239,6 +665,7 @@ mod tests {
             expected_outcome: expected2,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let ranked = ranker.rank_actions(&[obs1.clone(), obs2.clone()]);
@@ -268,11 +695,215 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
-        detector.train(&[observation]);
+        let now = observation.timestamp;
+        detector.train_at(&[observation], now);
         let new_weight = *detector.weights.get("repeat_count").unwrap();
         assert!(new_weight > initial_weight);
     }
-}
 
+    /// `observation` repeated `n` times one second apart
+    fn series(observation: &Observation, n: i64) -> Vec<Observation> {
+        (0..n)
+            .map(|i| {
+                let mut next = observation.clone();
+                next.timestamp += i;
+                next
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_training_converges_with_time_decay() {
+        let fresh = ranked_observation("fresh", 100.0, 0.0);
+        let now = fresh.timestamp + 200;
+        let mut detector = PatternDetector::new();
+        for observation in series(&fresh, 200) {
+            detector.train_at(std::slice::from_ref(&observation), now);
+            assert!(detector.weights.values().all(|w| (0.0..=10.0).contains(w)));
+        }
+        assert!((detector.score_confidence(&fresh) - 0.9).abs() < 1e-3);
+
+        // Retraining on history already learned from changes nothing
+        let trained = detector.weights.clone();
+        detector.train_at(&series(&fresh, 200), now);
+        assert_eq!(detector.weights, trained);
+
+        // Small metrics can still reach the target instead of saturating the weight
+        let small = ranked_observation("small", 10.0, 0.0);
+        let mut small_detector = PatternDetector::new();
+        small_detector.train_at(&series(&small, 500), small.timestamp + 500);
+        assert!((small_detector.score_confidence(&small) - 0.9).abs() < 1e-3);
+
+        // Two half-lives older than now: a quarter of the step, even for a single observation
+        let mut old = ranked_observation("old", 100.0, 0.0);
+        old.timestamp -= 60 * 86400;
+        let mut recent_only = PatternDetector::new();
+        recent_only.train_at(std::slice::from_ref(&fresh), fresh.timestamp);
+        let mut decayed = PatternDetector::new();
+        decayed.train_at(&[old], fresh.timestamp);
+        let recent_step = recent_only.weights["repeat_count"] - 0.3;
+        let decayed_step = decayed.weights["repeat_count"] - 0.3;
+        assert!((decayed_step - recent_step / 4.0).abs() < 1e-6);
+
+        // Learned weights drift halfway back to the defaults after a half-life without training
+        recent_only.train_at(&[], fresh.timestamp + 30 * 86400);
+        assert!((recent_only.weights["repeat_count"] - 0.3 - recent_step / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profile_weights_export_import() {
+        let developer = ranked_observation("dev", 100.0, 0.0);
+        let mut designer = ranked_observation("design", 100.0, 0.0);
+        designer.profile = UserProfile::Designer;
+        designer.action.confidence = Confidence::Low;
+        let mut detector = PatternDetector::new();
+        let history: Vec<Observation> = series(&developer, 200).into_iter().chain(series(&designer, 200)).collect();
+        detector.train_at(&history, developer.timestamp + 200);
+        assert!((detector.score_confidence(&developer) - 0.9).abs() < 1e-3);
+        assert!((detector.score_confidence(&designer) - 0.3).abs() < 1e-3);
+
+        let json = serde_json::to_string(&detector.export_weights()).unwrap();
+        let mut restored = PatternDetector::new();
+        restored.import_weights(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(restored.score_confidence(&designer), detector.score_confidence(&designer));
+        restored.train_at(&history, developer.timestamp + 200); // Restored progress: nothing is learned twice
+        assert_eq!(restored.score_confidence(&developer), detector.score_confidence(&developer));
+
+        let mut invalid = detector.export_weights();
+        invalid.global.insert("repeat_count".to_string(), 50.0);
+        assert!(restored.import_weights(invalid).is_err());
+        assert_eq!(restored.score_confidence(&developer), detector.score_confidence(&developer));
+    }
+
+    fn ranked_observation(id: &str, metric: f64, time_saved: f64) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), metric);
+        let mut expected = HashMap::new();
+        expected.insert("time_saved_min".to_string(), time_saved);
+        
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["App1".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: expected,
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_ranking_nan_safe() {
+        let ranker = RecommendationRanker::new();
+        let observations = vec![
+            ranked_observation("nan_metric", f64::NAN, 10.0),
+            ranked_observation("nan_savings", 8.0, f64::NAN),
+            ranked_observation("inf_savings", 8.0, f64::INFINITY),
+            ranked_observation("normal", 8.0, 20.0),
+        ];
+        
+        let ranked = ranker.rank_actions(&observations);
+        assert_eq!(ranked.len(), 4);
+        assert!(ranked.iter().all(|(_, score)| score.is_finite()));
+        assert_eq!(ranked[0].0.id, "normal");
+        assert!(RankScore(f64::NAN) < RankScore(-1e300));
+    }
+
+    #[test]
+    fn test_ranking_ties_deterministic() {
+        let ranker = RecommendationRanker::new();
+        let a = ranked_observation("b_obs", 0.0, 10.0);
+        let b = ranked_observation("a_obs", 0.0, 10.0);
+        let c = ranked_observation("c_obs", 0.0, 10.0);
+        
+        let forward: Vec<String> = ranker.rank_actions(&[a.clone(), b.clone(), c.clone()]).into_iter().map(|(o, _)| o.id).collect();
+        let reverse: Vec<String> = ranker.rank_actions(&[c, b, a]).into_iter().map(|(o, _)| o.id).collect();
+        assert_eq!(forward, vec!["a_obs", "b_obs", "c_obs"]);
+        assert_eq!(forward, reverse);
+    }
+
+    #[test]
+    fn test_realized_savings_decay_predictions() {
+        let mut ranker = RecommendationRanker::new();
+        let macro_obs = ranked_observation("macro", 0.0, 20.0);
+        let mut focus_obs = ranked_observation("focus", 0.0, 15.0);
+        focus_obs.action.action_type = ActionType::FocusMode;
+        assert_eq!(ranker.rank_actions(&[macro_obs.clone(), focus_obs.clone()])[0].0.id, "macro");
+        
+        for _ in 0..3 {
+            ranker.record_realized_outcome(ActionType::AutomationMacro, 20.0, 5.0);
+        }
+        ranker.record_realized_outcome(ActionType::FocusMode, 15.0, 30.0); // Beating the prediction never inflates it
+        
+        let ranked = ranker.rank_actions(&[macro_obs, focus_obs]);
+        assert_eq!(ranked[0].0.id, "focus");
+        assert_eq!(ranker.overestimating_types().len(), 1);
+        assert_eq!(ranker.overestimating_types()[0].0, ActionType::AutomationMacro);
+        
+        let mut analytics = AnalyticsAggregator::new();
+        ranker.export_calibration_metrics(&mut analytics);
+        assert!(analytics
+            .get_metrics_by_category(MetricCategory::Product)
+            .iter()
+            .any(|m| m.name == "ranking.overestimating.automation_macro"));
+    }
+
+    #[test]
+    fn test_rank_with_breakdown_explains_score() {
+        let mut ranker = RecommendationRanker::new();
+        let macro_obs = ranked_observation("macro", 0.0, 20.0);
+        let mut risky = ranked_observation("risky", 0.0, 40.0);
+        risky.action.risk = RiskCategory::High;
+        risky.action.confidence = Confidence::Medium;
+        for _ in 0..3 {
+            ranker.record_realized_outcome(ActionType::AutomationMacro, 20.0, 10.0);
+        }
+        
+        let observations = [macro_obs, risky];
+        let breakdown = ranker.rank_with_breakdown(&observations);
+        let ranked: Vec<String> = ranker.rank_actions(&observations).into_iter().map(|(o, _)| o.id).collect();
+        assert_eq!(breakdown.iter().map(|b| b.observation_id.clone()).collect::<Vec<_>>(), ranked);
+        
+        for b in &breakdown {
+            let recomputed = (b.pattern_score * 0.4 + b.predicted_time_saved_min * b.savings_decay / 100.0 * 0.6)
+                * b.confidence_multiplier
+                * b.risk_penalty
+                * b.workload_factor
+                * b.edit_factor;
+            assert!((recomputed - b.score).abs() < 1e-9);
+        }
+        let risky = breakdown.iter().find(|b| b.observation_id == "risky").unwrap();
+        assert_eq!((risky.confidence_multiplier, risky.risk_penalty), (0.7, 0.3));
+        let macro_b = breakdown.iter().find(|b| b.observation_id == "macro").unwrap();
+        assert!(macro_b.savings_decay < 1.0 && macro_b.time_saved_min < macro_b.predicted_time_saved_min);
+    }
+
+    #[test]
+    fn test_modifications_damp_ranking() {
+        let mut ranker = RecommendationRanker::new();
+        let observations = [ranked_observation("macro", 0.0, 20.0)];
+        let before = ranker.rank_actions(&observations)[0].1;
+        
+        ranker.record_modification(ActionType::AutomationMacro, 1.0);
+        ranker.record_modification(ActionType::AutomationMacro, f64::NAN);
+        let stats = ranker.get_modification_stats(&ActionType::AutomationMacro).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert!((stats.edit_rate - 0.3).abs() < 1e-9);
+        
+        let breakdown = ranker.rank_with_breakdown(&observations);
+        assert!((breakdown[0].edit_factor - 0.85).abs() < 1e-9);
+        assert!((breakdown[0].score - before * 0.85).abs() < 1e-9);
+        assert!(ranker.get_modification_stats(&ActionType::FocusMode).is_none());
+    }
+}
@@ -2,9 +2,10 @@
 /// Multi-Region Scale
 /// Scale infrastructure multi-region with latency-aware orchestration
 
+use crate::compliance::DifferentialPrivacy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Region configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,11 +17,64 @@ pub struct Region {
     pub active: bool,
 }
 
+/// Compliance policy applied to users homed in a region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionPolicy {
+    pub dp_epsilon: f64,      // Privacy budget for aggregates over these users
+    pub retention_days: u32,  // Data older than this is not aggregated or exported
+    #[serde(default)]
+    pub data_residency: Vec<String>, // Regions the data may leave to besides home; empty = home only
+}
+
+impl Default for RegionPolicy {
+    fn default() -> Self {
+        Self {
+            dp_epsilon: 1.0,
+            retention_days: 365,
+            data_residency: Vec::new(),
+        }
+    }
+}
+
+impl RegionPolicy {
+    /// Check the policy is enforceable
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.dp_epsilon.is_finite() || self.dp_epsilon <= 0.0 || self.dp_epsilon > 10.0 {
+            return Err(format!("dp_epsilon must be in (0, 10], got {}", self.dp_epsilon));
+        }
+        if self.retention_days == 0 {
+            return Err("retention_days must be at least 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether data homed in `home_region` may be sent to `destination_region`
+    pub fn allows_destination(&self, home_region: &str, destination_region: &str) -> bool {
+        destination_region == home_region || self.data_residency.iter().any(|r| r == destination_region)
+    }
+
+    /// Oldest timestamp still within retention
+    pub fn retention_cutoff(&self, now: i64) -> i64 {
+        now - self.retention_days as i64 * 86_400
+    }
+}
+
+/// DP aggregate over the users homed in one region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionAggregate {
+    pub region_id: String, // "unassigned" for users without a home region
+    pub user_count: usize,
+    pub epsilon: f64,
+    pub value: f64,
+}
+
 /// Latency-aware orchestrator
 /// Source: Athenos_AI_Strategy.md#L138
 pub struct MultiRegionOrchestrator {
     regions: HashMap<String, Region>,
     user_regions: HashMap<String, String>, // user_id -> region_id
+    region_policies: HashMap<String, RegionPolicy>, // region_id -> policy
+    default_policy: RegionPolicy, // Regions without their own policy and unassigned users
 }
 
 impl MultiRegionOrchestrator {
@@ -30,6 +84,8 @@ impl MultiRegionOrchestrator {
         Self {
             regions: HashMap::new(),
             user_regions: HashMap::new(),
+            region_policies: HashMap::new(),
+            default_policy: RegionPolicy::default(),
         }
     }
 
@@ -70,6 +126,81 @@ impl MultiRegionOrchestrator {
             .and_then(|rid| self.regions.get(rid))
     }
 
+    /// Configure the compliance policy for a region
+    pub fn set_region_policy(&mut self, region_id: &str, policy: RegionPolicy) -> Result<(), String> {
+        if !self.regions.contains_key(region_id) {
+            return Err(format!("Unknown region: {}", region_id));
+        }
+        policy.validate()?;
+        info!("MultiRegionOrchestrator::set_region_policy: {} (ε={}, retention {}d)", region_id, policy.dp_epsilon, policy.retention_days);
+        self.region_policies.insert(region_id.to_string(), policy);
+        Ok(())
+    }
+
+    /// Policy for a region (default when none is configured)
+    pub fn get_region_policy(&self, region_id: &str) -> &RegionPolicy {
+        self.region_policies.get(region_id).unwrap_or(&self.default_policy)
+    }
+
+    /// Policy for the region a user is homed in
+    pub fn policy_for_user(&self, user_id: &str) -> &RegionPolicy {
+        self.user_regions
+            .get(user_id)
+            .map(|region_id| self.get_region_policy(region_id))
+            .unwrap_or(&self.default_policy)
+    }
+
+    /// Differential privacy configured for a user's home region
+    pub fn privacy_for_user(&self, user_id: &str) -> DifferentialPrivacy {
+        DifferentialPrivacy::new(self.policy_for_user(user_id).dp_epsilon)
+    }
+
+    /// Aggregate per-user values with each home region's epsilon and retention
+    /// Values are (user_id, value, recorded_at); users never mix across regions
+    pub fn aggregate_with_privacy(&self, values: &[(&str, f64, i64)], now: i64) -> Vec<RegionAggregate> {
+        let mut by_region: HashMap<&str, Vec<f64>> = HashMap::new();
+        let mut users_by_region: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (user_id, value, recorded_at) in values {
+            if *recorded_at < self.policy_for_user(user_id).retention_cutoff(now) {
+                continue;
+            }
+            let region_id = self.user_regions.get(*user_id).map(|r| r.as_str()).unwrap_or("unassigned");
+            by_region.entry(region_id).or_default().push(*value);
+            let users = users_by_region.entry(region_id).or_default();
+            if !users.contains(user_id) {
+                users.push(user_id);
+            }
+        }
+
+        let mut aggregates: Vec<RegionAggregate> = by_region
+            .into_iter()
+            .map(|(region_id, region_values)| {
+                let policy = self.region_policies.get(region_id).unwrap_or(&self.default_policy);
+                RegionAggregate {
+                    region_id: region_id.to_string(),
+                    user_count: users_by_region[region_id].len(),
+                    epsilon: policy.dp_epsilon,
+                    value: DifferentialPrivacy::new(policy.dp_epsilon).aggregate_with_privacy(&region_values),
+                }
+            })
+            .collect();
+        aggregates.sort_by(|a, b| a.region_id.cmp(&b.region_id));
+        aggregates
+    }
+
+    /// Check a user's data may be exported to a destination region under residency rules
+    pub fn authorize_export(&self, user_id: &str, destination_region: &str) -> Result<&RegionPolicy, String> {
+        let policy = self.policy_for_user(user_id);
+        let Some(home_region) = self.user_regions.get(user_id) else {
+            return Ok(policy);
+        };
+        if !policy.allows_destination(home_region, destination_region) {
+            warn!("MultiRegionOrchestrator::authorize_export: {} data may not leave {} for {}", user_id, home_region, destination_region);
+            return Err(format!("Data residency for {} does not permit export to {}", home_region, destination_region));
+        }
+        Ok(policy)
+    }
+
     /// Get all active regions
     pub fn get_active_regions(&self) -> Vec<&Region> {
         self.regions.values().filter(|r| r.active).collect()
@@ -133,5 +264,55 @@ mod tests {
         assert!(region.is_some());
         assert_eq!(region.unwrap().id, "us-east");
     }
-}
 
+    fn region(id: &str) -> Region {
+        Region {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            endpoint: format!("https://{}.athenos.ai", id),
+            latency_ms: 50,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_region_policy_applies_to_homed_users() {
+        let mut orchestrator = MultiRegionOrchestrator::new();
+        orchestrator.add_region(region("eu-west"));
+        orchestrator.add_region(region("us-east"));
+        let strict = RegionPolicy { dp_epsilon: 0.1, retention_days: 30, data_residency: Vec::new() };
+        orchestrator.set_region_policy("eu-west", strict).unwrap();
+        assert!(orchestrator.set_region_policy("ap-south", RegionPolicy::default()).is_err());
+        assert!(orchestrator.set_region_policy("us-east", RegionPolicy { dp_epsilon: 0.0, ..RegionPolicy::default() }).is_err());
+
+        orchestrator.assign_user_to_region("anna".to_string(), "eu-west".to_string());
+        orchestrator.assign_user_to_region("bob".to_string(), "us-east".to_string());
+        assert_eq!(orchestrator.privacy_for_user("anna").epsilon(), 0.1);
+        assert_eq!(orchestrator.policy_for_user("bob").dp_epsilon, 1.0);
+
+        assert!(orchestrator.authorize_export("anna", "eu-west").is_ok());
+        assert!(orchestrator.authorize_export("anna", "us-east").is_err());
+        assert!(orchestrator.authorize_export("bob", "us-east").is_ok());
+    }
+
+    #[test]
+    fn test_aggregation_groups_by_region_and_drops_expired_data() {
+        let mut orchestrator = MultiRegionOrchestrator::new();
+        orchestrator.add_region(region("eu-west"));
+        orchestrator.add_region(region("us-east"));
+        orchestrator.set_region_policy("eu-west", RegionPolicy { dp_epsilon: 0.5, retention_days: 30, data_residency: Vec::new() }).unwrap();
+        orchestrator.assign_user_to_region("anna".to_string(), "eu-west".to_string());
+        orchestrator.assign_user_to_region("bob".to_string(), "us-east".to_string());
+
+        let now = 100 * 86_400;
+        let old = now - 60 * 86_400; // Outside EU retention, inside the default
+        let aggregates = orchestrator.aggregate_with_privacy(&[("anna", 10.0, now), ("anna", 99.0, old), ("bob", 20.0, old), ("carol", 5.0, now)], now);
+
+        let regions: Vec<&str> = aggregates.iter().map(|a| a.region_id.as_str()).collect();
+        assert_eq!(regions, vec!["eu-west", "unassigned", "us-east"]);
+        assert_eq!(aggregates[0].epsilon, 0.5);
+        assert_eq!(aggregates[0].user_count, 1);
+        assert!((aggregates[0].value - 10.0).abs() <= 2.0); // Noise bounded by 1/ε; expired 99.0 excluded
+        assert_eq!(aggregates[2].epsilon, 1.0);
+    }
+}
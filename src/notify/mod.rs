@@ -0,0 +1,730 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L126-136
+/// External Notification Sinks
+/// Unified delivery of nudges, victories, threat alerts, policy violations and reports to Slack, email and desktop
+
+use crate::enterprise::PolicyViolation;
+use crate::focus_session::FocusSessionEngine;
+use crate::locale::Localizer;
+use crate::microlearning::MicrolearningNudge;
+use crate::security::{SecurityThreat, ThreatLevel};
+use crate::victory::Victory;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Notification origin
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSource {
+    Nudge,
+    Victory,
+    ThreatAlert,
+    PolicyViolation,
+    Report,
+    TimelineNotice,
+    Digest, // Batched summary of digest-mode notifications
+}
+
+/// Notification severity
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// Notification to deliver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub source: NotificationSource,
+    pub severity: NotificationSeverity,
+    pub title: String,
+    pub body: String,
+    pub created_at: i64,
+}
+
+impl Notification {
+    /// Nudge notification with labels in the user's locale
+    pub fn from_nudge(nudge: &MicrolearningNudge, localizer: &Localizer) -> Self {
+        Self {
+            source: NotificationSource::Nudge,
+            severity: NotificationSeverity::Info,
+            title: nudge.title.clone(),
+            body: format!("{}\n{}", nudge.content, localizer.translate("nudge.tip", &[("tip", &nudge.tip)])),
+            created_at: nudge.created_at,
+        }
+    }
+}
+
+impl From<&MicrolearningNudge> for Notification {
+    fn from(nudge: &MicrolearningNudge) -> Self {
+        Self::from_nudge(nudge, &Localizer::default())
+    }
+}
+
+impl From<&Victory> for Notification {
+    fn from(victory: &Victory) -> Self {
+        Self {
+            source: NotificationSource::Victory,
+            severity: NotificationSeverity::Info,
+            title: victory.title.clone(),
+            body: victory.description.clone(),
+            created_at: victory.timestamp,
+        }
+    }
+}
+
+impl From<&SecurityThreat> for Notification {
+    fn from(threat: &SecurityThreat) -> Self {
+        let severity = match threat.level {
+            ThreatLevel::Low => NotificationSeverity::Info,
+            ThreatLevel::Medium => NotificationSeverity::Warning,
+            ThreatLevel::High | ThreatLevel::Critical => NotificationSeverity::Critical,
+        };
+        Self {
+            source: NotificationSource::ThreatAlert,
+            severity,
+            title: format!("Security threat: {}", threat.threat_type),
+            body: threat.description.clone(),
+            created_at: threat.detected_at,
+        }
+    }
+}
+
+impl From<&PolicyViolation> for Notification {
+    fn from(violation: &PolicyViolation) -> Self {
+        Self {
+            source: NotificationSource::PolicyViolation,
+            severity: NotificationSeverity::Warning,
+            title: format!("Policy violation: {}", violation.policy_name),
+            body: violation.description.clone(),
+            created_at: violation.detected_at,
+        }
+    }
+}
+
+/// Delivery backend for notifications
+pub trait NotificationSink: Send {
+    /// Channel name used by routing rules
+    fn channel(&self) -> &str;
+
+    /// Whether the sink can deliver in this build and environment; unavailable sinks are skipped by routing
+    fn available(&self) -> bool {
+        true
+    }
+
+    /// Deliver a notification
+    fn send(&mut self, notification: &Notification) -> Result<(), String>;
+}
+
+/// Slack incoming-webhook sink, POSTed over HTTPS when built with the notifications feature
+pub struct SlackWebhookSink {
+    webhook_url: String,
+    #[cfg(feature = "notifications")]
+    agent: ureq::Agent,
+}
+
+impl SlackWebhookSink {
+    /// Create Slack sink
+    pub fn new(webhook_url: String) -> Result<Self, String> {
+        if !webhook_url.starts_with("https://") {
+            return Err("Slack webhook URL must use https".to_string());
+        }
+        Ok(Self {
+            webhook_url,
+            #[cfg(feature = "notifications")]
+            agent: ureq::AgentBuilder::new().timeout(std::time::Duration::from_secs(30)).build(),
+        })
+    }
+
+    /// Incoming-webhook JSON body for a notification
+    pub fn payload(notification: &Notification) -> String {
+        serde_json::json!({
+            "text": format!("*{}*\n{}", notification.title, notification.body),
+        })
+        .to_string()
+    }
+}
+
+impl NotificationSink for SlackWebhookSink {
+    fn channel(&self) -> &str {
+        "slack"
+    }
+
+    fn available(&self) -> bool {
+        cfg!(feature = "notifications")
+    }
+
+    #[cfg(feature = "notifications")]
+    fn send(&mut self, notification: &Notification) -> Result<(), String> {
+        let payload = Self::payload(notification);
+        info!("SlackWebhookSink::send: POST {} ({} bytes)", self.webhook_url, payload.len());
+        match self.agent.post(&self.webhook_url).set("Content-Type", "application/json").send_string(&payload) {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(status, _)) => Err(format!("Slack webhook returned HTTP {}", status)),
+            Err(e) => Err(format!("Slack webhook POST failed: {}", e)),
+        }
+    }
+
+    #[cfg(not(feature = "notifications"))]
+    fn send(&mut self, _notification: &Notification) -> Result<(), String> {
+        Err(format!("Cannot POST to {}: built without the notifications feature", self.webhook_url))
+    }
+}
+
+/// SMTP email sink; messages are composed but no SMTP client is built in, so the sink reports itself unavailable
+pub struct EmailSink {
+    smtp_host: String,
+    from: String,
+    to: Vec<String>,
+}
+
+impl EmailSink {
+    /// Create email sink
+    pub fn new(smtp_host: String, from: String, to: Vec<String>) -> Result<Self, String> {
+        if to.is_empty() {
+            return Err("Email sink needs at least one recipient".to_string());
+        }
+        if let Some(address) = std::iter::once(&from).chain(&to).find(|a| a.contains(['\r', '\n'])) {
+            return Err(format!("Email address {:?} contains a line break", address));
+        }
+        Ok(Self { smtp_host, from, to })
+    }
+
+    /// RFC 5322 message for a notification
+    pub fn compose(&self, notification: &Notification) -> String {
+        // Line breaks in the subject would start new headers
+        let subject = notification.title.replace(['\r', '\n'], " ");
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: [Athenos] {}\r\n\r\n{}\r\n",
+            self.from,
+            self.to.join(", "),
+            subject,
+            notification.body
+        )
+    }
+}
+
+impl NotificationSink for EmailSink {
+    fn channel(&self) -> &str {
+        "email"
+    }
+
+    fn available(&self) -> bool {
+        false
+    }
+
+    fn send(&mut self, _notification: &Notification) -> Result<(), String> {
+        Err(format!("Cannot send via {}: no SMTP client is built in", self.smtp_host))
+    }
+}
+
+/// Native desktop notification sink, shown through notify-send (Linux) or osascript (macOS)
+pub struct DesktopSink {
+    notifier: Option<&'static str>, // Found on PATH at creation
+}
+
+impl DesktopSink {
+    /// Create desktop sink
+    pub fn new() -> Self {
+        let candidate = if cfg!(target_os = "macos") {
+            Some("osascript")
+        } else if cfg!(target_os = "linux") {
+            Some("notify-send")
+        } else {
+            None
+        };
+        let notifier = candidate.filter(|program| {
+            std::env::var_os("PATH").is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        });
+        if notifier.is_none() {
+            info!("DesktopSink::new: No desktop notifier found, desktop channel unavailable");
+        }
+        Self { notifier }
+    }
+}
+
+impl Default for DesktopSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// AppleScript string literal
+fn applescript_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl NotificationSink for DesktopSink {
+    fn channel(&self) -> &str {
+        "desktop"
+    }
+
+    fn available(&self) -> bool {
+        self.notifier.is_some()
+    }
+
+    fn send(&mut self, notification: &Notification) -> Result<(), String> {
+        let notifier = self.notifier.ok_or_else(|| "No desktop notifier available".to_string())?;
+        info!("DesktopSink::send: Showing '{}' via {}", notification.title, notifier);
+        let mut command = std::process::Command::new(notifier);
+        if notifier == "osascript" {
+            command.arg("-e").arg(format!(
+                "display notification {} with title {}",
+                applescript_quote(&notification.body),
+                applescript_quote(&notification.title)
+            ));
+        } else {
+            command.args(["--app-name", "Athenos", "--"]).arg(&notification.title).arg(&notification.body);
+        }
+        let status = command.status().map_err(|e| format!("Failed to run {}: {}", notifier, e))?;
+        if !status.success() {
+            return Err(format!("{} exited with {}", notifier, status));
+        }
+        Ok(())
+    }
+}
+
+/// Routing rule: notifications matching source (any if None) and minimum severity go to channels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingRule {
+    pub source: Option<NotificationSource>,
+    pub min_severity: NotificationSeverity,
+    pub channels: Vec<String>,
+}
+
+/// Whether a notification source interrupts or waits for the digest
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestUrgency {
+    Immediate,
+    Digest,
+}
+
+/// Digest mode: low-priority notifications are batched into one summary at focus-block boundaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    pub max_batched_severity: NotificationSeverity, // More severe notifications are delivered immediately
+    pub urgency_overrides: Vec<(NotificationSource, DigestUrgency)>, // Per-source; wins over severity
+    pub max_hold_secs: i64, // Flush even without a focus boundary once the oldest item is this old
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_batched_severity: NotificationSeverity::Info,
+            urgency_overrides: vec![
+                (NotificationSource::ThreatAlert, DigestUrgency::Immediate),
+                (NotificationSource::PolicyViolation, DigestUrgency::Immediate),
+                (NotificationSource::Report, DigestUrgency::Immediate),
+            ],
+            max_hold_secs: 2 * 3600,
+        }
+    }
+}
+
+impl DigestConfig {
+    /// Whether a notification waits for the digest
+    pub fn batches(&self, notification: &Notification) -> bool {
+        if !self.enabled || notification.source == NotificationSource::Digest {
+            return false;
+        }
+        match self.urgency_overrides.iter().find(|(source, _)| *source == notification.source) {
+            Some((_, urgency)) => *urgency == DigestUrgency::Digest,
+            None => notification.severity <= self.max_batched_severity,
+        }
+    }
+}
+
+/// Per-channel routing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    pub rules: Vec<RoutingRule>,
+    #[serde(default)]
+    pub digest: DigestConfig,
+    #[serde(default = "default_fallback_channels")]
+    pub fallback_channels: Vec<String>, // Tried in order when no routed channel delivers
+}
+
+fn default_fallback_channels() -> Vec<String> {
+    vec!["desktop".to_string(), "slack".to_string(), "email".to_string()]
+}
+
+impl NotificationConfig {
+    /// Parse routing config from JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid notification config: {}", e))
+    }
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        let rule = |source, min_severity, channels: &[&str]| RoutingRule {
+            source,
+            min_severity,
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+        };
+        Self {
+            rules: vec![
+                rule(Some(NotificationSource::Nudge), NotificationSeverity::Info, &["desktop"]),
+                rule(Some(NotificationSource::Victory), NotificationSeverity::Info, &["desktop"]),
+                rule(Some(NotificationSource::ThreatAlert), NotificationSeverity::Warning, &["desktop"]),
+                rule(Some(NotificationSource::ThreatAlert), NotificationSeverity::Critical, &["email", "slack"]),
+                rule(Some(NotificationSource::PolicyViolation), NotificationSeverity::Info, &["email", "slack"]),
+                rule(Some(NotificationSource::Report), NotificationSeverity::Info, &["email"]),
+                rule(Some(NotificationSource::TimelineNotice), NotificationSeverity::Info, &["desktop"]),
+                rule(Some(NotificationSource::Digest), NotificationSeverity::Info, &["desktop"]),
+            ],
+            digest: DigestConfig::default(),
+            fallback_channels: default_fallback_channels(),
+        }
+    }
+}
+
+/// Delivery record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    pub channel: String,
+    pub title: String,
+    pub delivered: bool,
+    pub error: Option<String>,
+}
+
+/// Routes notifications to registered sinks
+pub struct NotificationRouter {
+    config: NotificationConfig,
+    sinks: HashMap<String, Box<dyn NotificationSink>>,
+    deliveries: Vec<DeliveryRecord>,
+    digest_pending: Vec<Notification>,
+    observe_only: bool, // Guided onboarding: nudges are dropped until unlocked
+}
+
+impl NotificationRouter {
+    /// Create router with routing config
+    pub fn new(config: NotificationConfig) -> Self {
+        info!("NotificationRouter::new: Creating router with {} rules", config.rules.len());
+        Self {
+            config,
+            sinks: HashMap::new(),
+            deliveries: Vec::new(),
+            digest_pending: Vec::new(),
+            observe_only: false,
+        }
+    }
+
+    /// Drop nudge notifications while guided onboarding keeps nudges locked
+    pub fn set_observe_only(&mut self, observe_only: bool) {
+        self.observe_only = observe_only;
+    }
+
+    /// Register a sink under its channel name
+    pub fn register_sink(&mut self, sink: Box<dyn NotificationSink>) {
+        info!("NotificationRouter::register_sink: Registering {}", sink.channel());
+        self.sinks.insert(sink.channel().to_string(), sink);
+    }
+
+    /// Channels a notification routes to (deduplicated, rule order)
+    pub fn route(&self, notification: &Notification) -> Vec<String> {
+        let mut channels: Vec<String> = Vec::new();
+        for rule in &self.config.rules {
+            let source_matches = rule.source.map(|s| s == notification.source).unwrap_or(true);
+            if source_matches && notification.severity >= rule.min_severity {
+                for channel in &rule.channels {
+                    if !channels.contains(channel) {
+                        channels.push(channel.clone());
+                    }
+                }
+            }
+        }
+        channels
+    }
+
+    /// Deliver a notification to every routed channel; unregistered or unavailable channels are recorded as failures
+    /// In digest mode low-priority notifications are held for the next digest and nothing is delivered
+    pub fn dispatch(&mut self, notification: &Notification) -> Vec<DeliveryRecord> {
+        if self.observe_only && notification.source == NotificationSource::Nudge {
+            info!("NotificationRouter::dispatch: Dropping '{}', nudges not unlocked yet", notification.title);
+            return Vec::new();
+        }
+        if self.config.digest.batches(notification) {
+            info!("NotificationRouter::dispatch: Holding '{}' for digest", notification.title);
+            self.digest_pending.push(notification.clone());
+            return Vec::new();
+        }
+        self.deliver(notification)
+    }
+
+    fn deliver(&mut self, notification: &Notification) -> Vec<DeliveryRecord> {
+        let routed = self.route(notification);
+        let mut records: Vec<DeliveryRecord> = routed.iter().map(|channel| self.send_to(channel, notification)).collect();
+
+        // Fall back to the first working channel when every routed one is missing, unavailable or failed
+        if !routed.is_empty() && !records.iter().any(|r| r.delivered) {
+            for channel in self.config.fallback_channels.clone().iter().filter(|c| !routed.contains(c)) {
+                let usable = self.sinks.get(channel).map(|sink| sink.available()).unwrap_or(false);
+                if !usable {
+                    continue;
+                }
+                info!("NotificationRouter::deliver: Falling back to {} for '{}'", channel, notification.title);
+                let record = self.send_to(channel, notification);
+                let delivered = record.delivered;
+                records.push(record);
+                if delivered {
+                    break;
+                }
+            }
+        }
+
+        self.deliveries.extend(records.iter().cloned());
+        records
+    }
+
+    fn send_to(&mut self, channel: &str, notification: &Notification) -> DeliveryRecord {
+        let result = match self.sinks.get_mut(channel) {
+            Some(sink) if sink.available() => sink.send(notification),
+            Some(_) => Err(format!("Channel {} is unavailable", channel)),
+            None => Err(format!("No sink registered for channel {}", channel)),
+        };
+        DeliveryRecord {
+            channel: channel.to_string(),
+            title: notification.title.clone(),
+            delivered: result.is_ok(),
+            error: result.err(),
+        }
+    }
+
+    /// Deliver held notifications as a single summary
+    pub fn flush_digest(&mut self, now: i64) -> Vec<DeliveryRecord> {
+        if self.digest_pending.is_empty() {
+            return Vec::new();
+        }
+        let pending = std::mem::take(&mut self.digest_pending);
+        let mut counts: Vec<(NotificationSource, usize)> = Vec::new();
+        for notification in &pending {
+            match counts.iter_mut().find(|(source, _)| *source == notification.source) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((notification.source, 1)),
+            }
+        }
+        let summary: Vec<String> = counts.iter().map(|(source, count)| format!("{} {:?}", count, source)).collect();
+        let items: Vec<String> = pending.iter().map(|n| format!("- {}", n.title)).collect();
+        let digest = Notification {
+            source: NotificationSource::Digest,
+            severity: pending.iter().map(|n| n.severity).max().unwrap_or(NotificationSeverity::Info),
+            title: format!("While you were focused: {} updates", pending.len()),
+            body: format!("{}\n{}", summary.join(", "), items.join("\n")),
+            created_at: now,
+        };
+        info!("NotificationRouter::flush_digest: Delivering digest of {} notifications", pending.len());
+        self.deliver(&digest)
+    }
+
+    /// Flush the digest if a focus block ended since the last call
+    pub fn flush_at_focus_boundary(&mut self, focus: &mut FocusSessionEngine, now: i64) -> Vec<DeliveryRecord> {
+        match focus.take_block_ends().last() {
+            Some(&ended_at) => self.flush_digest(ended_at.max(now)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Flush the digest once its oldest item has waited longer than the hold limit
+    pub fn flush_if_stale(&mut self, now: i64) -> Vec<DeliveryRecord> {
+        let oldest = self.digest_pending.iter().map(|n| n.created_at).min();
+        match oldest {
+            Some(oldest) if now - oldest >= self.config.digest.max_hold_secs => self.flush_digest(now),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Notifications held for the next digest
+    pub fn pending_digest(&self) -> &[Notification] {
+        &self.digest_pending
+    }
+
+    /// Get delivery history
+    pub fn get_deliveries(&self) -> &[DeliveryRecord] {
+        &self.deliveries
+    }
+}
+
+impl Default for NotificationRouter {
+    fn default() -> Self {
+        Self::new(NotificationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attention::AttentionService;
+    use crate::focus_session::{FocusSessionConfig, SessionTrigger};
+    use crate::victory::VictoryStream;
+
+    /// Accepts every notification on one channel
+    struct RecordingSink {
+        channel: &'static str,
+    }
+
+    impl RecordingSink {
+        fn new(channel: &'static str) -> Self {
+            Self { channel }
+        }
+    }
+
+    impl NotificationSink for RecordingSink {
+        fn channel(&self) -> &str {
+            self.channel
+        }
+
+        fn send(&mut self, _notification: &Notification) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn threat(level: ThreatLevel) -> SecurityThreat {
+        SecurityThreat {
+            id: "threat_1".to_string(),
+            threat_type: "suspicious_activity".to_string(),
+            level,
+            description: "Unauthorized access attempt".to_string(),
+            detected_at: 1234567890,
+            resolved: false,
+        }
+    }
+
+    #[test]
+    fn test_threat_routing_by_severity() {
+        let router = NotificationRouter::default();
+
+        assert_eq!(router.route(&Notification::from(&threat(ThreatLevel::Low))), Vec::<String>::new());
+        assert_eq!(router.route(&Notification::from(&threat(ThreatLevel::Medium))), vec!["desktop"]);
+        assert_eq!(router.route(&Notification::from(&threat(ThreatLevel::Critical))), vec!["desktop", "email", "slack"]);
+    }
+
+    #[test]
+    fn test_dispatch_records_missing_sinks() {
+        let mut router = NotificationRouter::default();
+        router.register_sink(Box::new(RecordingSink::new("desktop")));
+        router.register_sink(Box::new(RecordingSink::new("email")));
+
+        let records = router.dispatch(&Notification::from(&threat(ThreatLevel::High)));
+        assert_eq!(records.len(), 3);
+        assert!(records.iter().filter(|r| r.channel != "slack").all(|r| r.delivered));
+        assert!(!records.iter().find(|r| r.channel == "slack").unwrap().delivered);
+        assert_eq!(router.get_deliveries().len(), 3);
+    }
+
+    #[test]
+    fn test_email_subject_cannot_inject_headers() {
+        assert!(EmailSink::new("smtp.example.com".to_string(), "athenos@example.com\r\nBcc: x@evil.test".to_string(), vec!["secops@example.com".to_string()]).is_err());
+        let sink = EmailSink::new("smtp.example.com".to_string(), "athenos@example.com".to_string(), vec!["secops@example.com".to_string()]).unwrap();
+        let message = sink.compose(&nudge_notice("Alert\r\nBcc: attacker@evil.test", 0));
+
+        let headers = message.split("\r\n\r\n").next().unwrap().to_string();
+        assert_eq!(headers.lines().count(), 3);
+        assert!(headers.contains("Subject: [Athenos] Alert  Bcc: attacker@evil.test"));
+    }
+
+    #[test]
+    fn test_unavailable_channels_fall_back_to_working_one() {
+        let mut router = NotificationRouter::default();
+        router.register_sink(Box::new(RecordingSink::new("desktop")));
+        let email = EmailSink::new("smtp.example.com".to_string(), "athenos@example.com".to_string(), vec!["me@example.com".to_string()]).unwrap();
+        assert!(!email.available());
+        router.register_sink(Box::new(email));
+
+        // Reports route to email only; the unavailable sink is skipped and desktop takes over
+        let report = Notification { source: NotificationSource::Report, ..nudge_notice("Weekly report", 100) };
+        let records = router.dispatch(&report);
+        assert_eq!(records.len(), 2);
+        assert_eq!((records[0].channel.as_str(), records[0].delivered), ("email", false));
+        assert_eq!((records[1].channel.as_str(), records[1].delivered), ("desktop", true));
+
+        // Nothing to fall back to once the routed channel works
+        assert_eq!(router.dispatch(&nudge_notice("Try a shortcut", 110)).len(), 1);
+    }
+
+    fn nudge_notice(title: &str, at: i64) -> Notification {
+        Notification {
+            source: NotificationSource::Nudge,
+            severity: NotificationSeverity::Info,
+            title: title.to_string(),
+            body: String::new(),
+            created_at: at,
+        }
+    }
+
+    #[test]
+    fn test_digest_batches_low_priority_until_focus_boundary() {
+        let mut config = NotificationConfig::default();
+        config.digest.enabled = true;
+        let mut router = NotificationRouter::new(config);
+        router.register_sink(Box::new(RecordingSink::new("desktop")));
+        router.register_sink(Box::new(RecordingSink::new("email")));
+
+        assert!(router.dispatch(&nudge_notice("Try a shortcut", 100)).is_empty());
+        assert!(router.dispatch(&Notification { source: NotificationSource::TimelineNotice, ..nudge_notice("Day replay ready", 110) }).is_empty());
+        assert_eq!(router.dispatch(&Notification::from(&threat(ThreatLevel::Low))).len(), 0); // Override: immediate, but routes nowhere
+        assert_eq!(router.dispatch(&Notification::from(&threat(ThreatLevel::High))).len(), 3);
+        assert_eq!(router.pending_digest().len(), 2);
+
+        let mut focus = FocusSessionEngine::new(FocusSessionConfig::default(), AttentionService::new());
+        let mut victories = VictoryStream::new();
+        assert!(router.flush_at_focus_boundary(&mut focus, 200).is_empty());
+        focus.start(SessionTrigger::Manual, 0).unwrap();
+        focus.stop(1500, &mut victories).unwrap();
+
+        let records = router.flush_at_focus_boundary(&mut focus, 1500);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].title, "While you were focused: 2 updates");
+        assert!(router.pending_digest().is_empty());
+        assert!(router.flush_at_focus_boundary(&mut focus, 1600).is_empty()); // Boundary consumed
+    }
+
+    #[test]
+    fn test_observe_only_drops_nudges() {
+        let mut router = NotificationRouter::new(NotificationConfig::default());
+        router.register_sink(Box::new(RecordingSink::new("desktop")));
+        router.set_observe_only(true);
+        assert!(router.dispatch(&nudge_notice("Try a shortcut", 100)).is_empty());
+        assert_eq!(router.dispatch(&Notification { source: NotificationSource::Victory, ..nudge_notice("Shortcut saved 5 min", 110) }).len(), 1);
+
+        router.set_observe_only(false);
+        assert_eq!(router.dispatch(&nudge_notice("Try a shortcut", 120)).len(), 1);
+    }
+
+    #[test]
+    fn test_digest_flushes_stale_items() {
+        let mut config = NotificationConfig::default();
+        config.digest.enabled = true;
+        config.digest.max_hold_secs = 600;
+        let mut router = NotificationRouter::new(config);
+
+        router.dispatch(&nudge_notice("Stretch break", 1000));
+        assert!(router.flush_if_stale(1500).is_empty());
+        assert_eq!(router.flush_if_stale(1600).len(), 1);
+        assert!(router.pending_digest().is_empty());
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config = NotificationConfig::from_json(
+            r#"{"rules":[{"source":null,"min_severity":"warning","channels":["slack"]}]}"#,
+        )
+        .unwrap();
+        let router = NotificationRouter::new(config);
+
+        let violation = PolicyViolation {
+            policy_id: "policy_1".to_string(),
+            policy_name: "No Cloud Export".to_string(),
+            description: "Export attempted".to_string(),
+            detected_at: 1234567890,
+        };
+        assert_eq!(router.route(&Notification::from(&violation)), vec!["slack"]);
+        assert!(NotificationConfig::from_json("not json").is_err());
+        assert!(SlackWebhookSink::new("http://insecure".to_string()).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+/// Phase: D | Source: athenos-rules.mdc#L12-15
+/// Offline Operation with Sync-on-Reconnect
+/// Durable bounded queues for network-bound components, replayed in order when connectivity returns
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Network-bound component whose outbound work is queued while offline
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineChannel {
+    Federation,
+    KnowledgeIngestion,
+    Webhook,
+    Backup,
+}
+
+impl OfflineChannel {
+    /// Every channel, in replay order
+    pub fn all() -> [OfflineChannel; 4] {
+        [OfflineChannel::Federation, OfflineChannel::KnowledgeIngestion, OfflineChannel::Webhook, OfflineChannel::Backup]
+    }
+}
+
+/// Connectivity state
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectivityState {
+    Online,
+    Offline,
+}
+
+/// Queue limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineQueueConfig {
+    pub max_items_per_channel: usize, // Oldest items are dropped beyond this
+    pub max_age_secs: i64,            // Items older than this are dropped instead of replayed
+}
+
+impl Default for OfflineQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_items_per_channel: 10_000,
+            max_age_secs: 45 * 86400,
+        }
+    }
+}
+
+/// Queued outbound item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedItem {
+    pub id: String,          // Idempotency key; receivers dedupe retried deliveries on it
+    pub key: Option<String>, // Coalescing key; a newer item with the same key replaces the older one
+    pub payload: String,
+    pub enqueued_at: i64,
+    pub attempts: u32,
+}
+
+/// Persisted queue state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct OfflineState {
+    queues: HashMap<OfflineChannel, Vec<QueuedItem>>,
+    next_seq: u64,
+    dropped: HashMap<OfflineChannel, usize>,
+    last_online_at: Option<i64>,
+}
+
+/// Connectivity status for the local API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityStatus {
+    pub state: ConnectivityState,
+    pub since: i64,
+    pub last_online_at: Option<i64>,
+    pub pending: HashMap<OfflineChannel, usize>,
+    pub dropped: HashMap<OfflineChannel, usize>,
+}
+
+/// Outcome of replaying one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub channel: OfflineChannel,
+    pub delivered: usize,
+    pub expired: usize,
+    pub remaining: usize,
+    pub error: Option<String>,
+}
+
+/// Durable offline queue
+pub struct OfflineQueue {
+    config: OfflineQueueConfig,
+    state: OfflineState,
+    path: Option<PathBuf>,
+    connectivity: ConnectivityState,
+    since: i64,
+}
+
+impl OfflineQueue {
+    /// Create in-memory queue (starts online)
+    pub fn new(config: OfflineQueueConfig) -> Self {
+        info!("OfflineQueue::new: Creating offline queue ({} items per channel)", config.max_items_per_channel);
+        Self {
+            config,
+            state: OfflineState::default(),
+            path: None,
+            connectivity: ConnectivityState::Online,
+            since: 0,
+        }
+    }
+
+    /// Open persistent queue at path (created on first write); queued items survive restarts
+    pub fn open(path: PathBuf, config: OfflineQueueConfig) -> Result<Self, String> {
+        info!("OfflineQueue::open: Opening offline queue at {:?}", path);
+        let state = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid offline queue: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => OfflineState::default(),
+            Err(e) => return Err(format!("Failed to read offline queue: {}", e)),
+        };
+        let mut queue = Self::new(config);
+        queue.state = state;
+        queue.path = Some(path);
+        Ok(queue)
+    }
+
+    /// Current connectivity
+    pub fn is_online(&self) -> bool {
+        self.connectivity == ConnectivityState::Online
+    }
+
+    /// Record a connectivity change (from a probe or a failed send)
+    pub fn set_connectivity(&mut self, state: ConnectivityState, now: i64) -> Result<(), String> {
+        if state != self.connectivity {
+            info!("OfflineQueue::set_connectivity: {:?} -> {:?}", self.connectivity, state);
+            self.connectivity = state;
+            self.since = now;
+        }
+        if state == ConnectivityState::Online {
+            self.state.last_online_at = Some(now);
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Queue an outbound item; returns its idempotency id
+    pub fn enqueue(&mut self, channel: OfflineChannel, key: Option<String>, payload: String, now: i64) -> Result<String, String> {
+        self.state.next_seq += 1;
+        let id = format!("{:?}-{}", channel, self.state.next_seq).to_lowercase();
+        let queue = self.state.queues.entry(channel).or_default();
+
+        // Coalesce: only the latest state for a key is replayed
+        if let Some(key) = &key {
+            queue.retain(|item| item.key.as_ref() != Some(key));
+        }
+        queue.push(QueuedItem { id: id.clone(), key, payload, enqueued_at: now, attempts: 0 });
+
+        let overflow = queue.len().saturating_sub(self.config.max_items_per_channel);
+        if overflow > 0 {
+            warn!("OfflineQueue::enqueue: {:?} queue full, dropping {} oldest items", channel, overflow);
+            queue.drain(..overflow);
+            *self.state.dropped.entry(channel).or_insert(0) += overflow;
+        }
+        self.save()?;
+        Ok(id)
+    }
+
+    /// Replay a channel in enqueue order; stops at the first failure so later items never overtake it
+    pub fn replay(&mut self, channel: OfflineChannel, now: i64, deliver: &mut dyn FnMut(&QueuedItem) -> Result<(), String>) -> Result<ReplayReport, String> {
+        let mut report = ReplayReport { channel, delivered: 0, expired: 0, remaining: 0, error: None };
+        if !self.is_online() {
+            report.remaining = self.pending(channel);
+            report.error = Some("Offline".to_string());
+            return Ok(report);
+        }
+
+        let max_age = self.config.max_age_secs;
+        let queue = self.state.queues.entry(channel).or_default();
+        let before = queue.len();
+        queue.retain(|item| now - item.enqueued_at <= max_age);
+        report.expired = before - queue.len();
+
+        while let Some(item) = queue.first_mut() {
+            item.attempts += 1;
+            match deliver(item) {
+                Ok(()) => {
+                    queue.remove(0);
+                    report.delivered += 1;
+                }
+                Err(e) => {
+                    report.error = Some(e);
+                    break;
+                }
+            }
+        }
+        report.remaining = queue.len();
+        *self.state.dropped.entry(channel).or_insert(0) += report.expired;
+        info!("OfflineQueue::replay: {:?} delivered {}, {} remaining", channel, report.delivered, report.remaining);
+        self.save()?;
+        Ok(report)
+    }
+
+    /// Pending items for a channel
+    pub fn pending(&self, channel: OfflineChannel) -> usize {
+        self.state.queues.get(&channel).map(|q| q.len()).unwrap_or(0)
+    }
+
+    /// Connectivity status snapshot
+    pub fn status(&self) -> ConnectivityStatus {
+        ConnectivityStatus {
+            state: self.connectivity,
+            since: self.since,
+            last_online_at: self.state.last_online_at,
+            pending: OfflineChannel::all().iter().map(|c| (*c, self.pending(*c))).collect(),
+            dropped: self.state.dropped.clone(),
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create offline queue dir: {}", e))?;
+        }
+        let json = serde_json::to_string(&self.state).map_err(|e| format!("Failed to encode offline queue: {}", e))?;
+        // Write-then-rename so a crash never leaves a truncated queue
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write offline queue: {}", e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace offline queue: {}", e))
+    }
+}
+
+impl Default for OfflineQueue {
+    fn default() -> Self {
+        Self::new(OfflineQueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_queue_coalesces_and_drops_oldest() {
+        let config = OfflineQueueConfig { max_items_per_channel: 2, ..OfflineQueueConfig::default() };
+        let mut queue = OfflineQueue::new(config);
+        queue.set_connectivity(ConnectivityState::Offline, 0).unwrap();
+
+        queue.enqueue(OfflineChannel::Federation, Some("templates".to_string()), "v1".to_string(), 0).unwrap();
+        queue.enqueue(OfflineChannel::Federation, Some("templates".to_string()), "v2".to_string(), 1).unwrap();
+        assert_eq!(queue.pending(OfflineChannel::Federation), 1);
+
+        for i in 0..3 {
+            queue.enqueue(OfflineChannel::Webhook, None, format!("event {}", i), i).unwrap();
+        }
+        let status = queue.status();
+        assert_eq!(status.state, ConnectivityState::Offline);
+        assert_eq!(status.pending[&OfflineChannel::Webhook], 2);
+        assert_eq!(status.dropped[&OfflineChannel::Webhook], 1);
+    }
+
+    #[test]
+    fn test_replay_in_order_stops_at_failure() {
+        let mut queue = OfflineQueue::default();
+        queue.set_connectivity(ConnectivityState::Offline, 0).unwrap();
+        for i in 0..3 {
+            queue.enqueue(OfflineChannel::Webhook, None, format!("event {}", i), i).unwrap();
+        }
+        let mut sent = Vec::new();
+        let report = queue.replay(OfflineChannel::Webhook, 10, &mut |item| { sent.push(item.payload.clone()); Ok(()) }).unwrap();
+        assert_eq!(report.error.as_deref(), Some("Offline"));
+        assert!(sent.is_empty());
+
+        queue.set_connectivity(ConnectivityState::Online, 20).unwrap();
+        let report = queue
+            .replay(OfflineChannel::Webhook, 20, &mut |item| {
+                if item.payload == "event 1" && item.attempts == 1 {
+                    return Err("timeout".to_string());
+                }
+                sent.push(item.payload.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!((report.delivered, report.remaining), (1, 2));
+
+        queue.replay(OfflineChannel::Webhook, 30, &mut |item| { sent.push(item.payload.clone()); Ok(()) }).unwrap();
+        assert_eq!(sent, vec!["event 0", "event 1", "event 2"]);
+    }
+
+    #[test]
+    fn test_queue_survives_restart_and_expires_stale_items() {
+        let path = std::env::temp_dir().join(format!("athenos_offline_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        {
+            let mut queue = OfflineQueue::open(path.clone(), OfflineQueueConfig::default()).unwrap();
+            queue.enqueue(OfflineChannel::Backup, None, "snapshot-old".to_string(), 0).unwrap();
+            queue.enqueue(OfflineChannel::Backup, None, "snapshot-new".to_string(), 50 * 86400).unwrap();
+        }
+        let mut queue = OfflineQueue::open(path.clone(), OfflineQueueConfig::default()).unwrap();
+        assert_eq!(queue.pending(OfflineChannel::Backup), 2);
+
+        let mut sent = Vec::new();
+        let report = queue.replay(OfflineChannel::Backup, 50 * 86400, &mut |item| { sent.push(item.payload.clone()); Ok(()) }).unwrap();
+        assert_eq!((report.expired, report.delivered), (1, 1));
+        assert_eq!(sent, vec!["snapshot-new"]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,350 @@
+/// Phase: A | Step: 2 | Source: Athenos_AI_Strategy.md#L19-21
+/// OS Event Capture
+/// Platform hooks (foreground window, app launch, idle) that stream real OSEvents into the edge observer
+
+use crate::edge::{EdgeObserver, OSEvent, OSEventType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use tracing::info;
+
+/// Foreground state read from the OS at one instant
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForegroundSnapshot {
+    pub app_name: String,
+    pub window_title: Option<String>,
+    pub idle_secs: u64, // Seconds since the last keyboard/mouse input
+}
+
+/// Platform hook reading the current foreground state
+pub trait ForegroundProbe: Send {
+    fn probe_name(&self) -> &str;
+    /// None when the foreground state cannot be read right now (locked screen, missing tool)
+    fn snapshot(&mut self) -> Option<ForegroundSnapshot>;
+}
+
+/// Capture settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureConfig {
+    pub poll_interval_ms: u64,
+    pub idle_threshold_secs: u64, // Idle time after which the user counts as away
+}
+
+impl CaptureConfig {
+    pub fn new() -> Self {
+        Self {
+            poll_interval_ms: 1000,
+            idle_threshold_secs: 300,
+        }
+    }
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turns foreground snapshots into OSEvents
+/// New apps emit AppLaunch, returning apps AppSwitch, title changes WindowFocus;
+/// crossing the idle threshold emits SystemSleep (at the last input) and input resuming SystemWake
+pub struct OsEventCapture {
+    probe: Box<dyn ForegroundProbe>,
+    config: CaptureConfig,
+    seen_apps: HashSet<String>,
+    current: Option<ForegroundSnapshot>,
+    idle: bool,
+}
+
+impl OsEventCapture {
+    /// Create capture over any probe
+    pub fn with_probe(probe: Box<dyn ForegroundProbe>, config: CaptureConfig) -> Self {
+        info!("OsEventCapture::new: Capturing OS events via {}", probe.probe_name());
+        Self {
+            probe,
+            config,
+            seen_apps: HashSet::new(),
+            current: None,
+            idle: false,
+        }
+    }
+
+    /// Create capture for the running OS; None unless built with the `os-capture` feature on Windows, macOS or Linux
+    pub fn for_platform(config: CaptureConfig) -> Option<Self> {
+        platform::probe().map(|probe| Self::with_probe(probe, config))
+    }
+
+    fn event(&self, event_type: OSEventType, app_name: &str, window_title: Option<String>, timestamp: i64) -> OSEvent {
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), self.probe.probe_name().to_string());
+        OSEvent { event_type, app_name: app_name.to_string(), window_title, timestamp, metadata }
+    }
+
+    /// Read the probe once and return the events implied by the change since the previous poll
+    pub fn poll(&mut self, now: i64) -> Vec<OSEvent> {
+        let Some(snapshot) = self.probe.snapshot() else { return Vec::new() };
+        let mut events = Vec::new();
+
+        let away = snapshot.idle_secs >= self.config.idle_threshold_secs;
+        if away && !self.idle {
+            self.idle = true;
+            events.push(self.event(OSEventType::SystemSleep, "System", None, now - snapshot.idle_secs as i64));
+        } else if !away && self.idle {
+            self.idle = false;
+            self.current = None; // Re-emit the foreground app so a new block opens
+            events.push(self.event(OSEventType::SystemWake, "System", None, now));
+        }
+        if self.idle {
+            return events;
+        }
+
+        let (app_changed, title_changed) = match &self.current {
+            Some(current) => (current.app_name != snapshot.app_name, current.window_title != snapshot.window_title),
+            None => (true, false),
+        };
+        if app_changed {
+            let event_type = if self.seen_apps.insert(snapshot.app_name.clone()) { OSEventType::AppLaunch } else { OSEventType::AppSwitch };
+            events.push(self.event(event_type, &snapshot.app_name, snapshot.window_title.clone(), now));
+        } else if title_changed {
+            events.push(self.event(OSEventType::WindowFocus, &snapshot.app_name, snapshot.window_title.clone(), now));
+        }
+        self.current = Some(snapshot);
+        events
+    }
+
+    /// Poll once and record the events in the observer; returns the number of events produced
+    pub fn poll_into(&mut self, observer: &mut EdgeObserver, now: i64) -> usize {
+        let events = self.poll(now);
+        let count = events.len();
+        for event in events {
+            observer.record_event(event);
+        }
+        count
+    }
+
+    /// Poll on a background thread at the configured interval, streaming events over a channel
+    /// The thread exits on the first send after the receiver is dropped
+    pub fn spawn(mut self) -> Receiver<OSEvent> {
+        let (sender, receiver) = mpsc::channel();
+        let interval = Duration::from_millis(self.config.poll_interval_ms);
+        std::thread::spawn(move || loop {
+            for event in self.poll(chrono::Utc::now().timestamp()) {
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+            std::thread::sleep(interval);
+        });
+        receiver
+    }
+}
+
+/// Windows: Win32 foreground window, owning process image and GetLastInputInfo
+#[cfg(all(feature = "os-capture", target_os = "windows"))]
+mod platform {
+    use super::{ForegroundProbe, ForegroundSnapshot};
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::System::Threading::{OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId};
+
+    struct Win32Probe;
+
+    impl ForegroundProbe for Win32Probe {
+        fn probe_name(&self) -> &str {
+            "win32"
+        }
+
+        fn snapshot(&mut self) -> Option<ForegroundSnapshot> {
+            unsafe {
+                let hwnd = GetForegroundWindow();
+                if hwnd.0 == 0 {
+                    return None;
+                }
+                let mut title = [0u16; 512];
+                let title_len = GetWindowTextW(hwnd, &mut title).max(0) as usize;
+                let title = String::from_utf16_lossy(&title[..title_len]);
+
+                let mut pid = 0u32;
+                GetWindowThreadProcessId(hwnd, Some(&mut pid));
+                let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+                let mut image = [0u16; 1024];
+                let mut image_len = image.len() as u32;
+                let queried = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(image.as_mut_ptr()), &mut image_len);
+                let _ = CloseHandle(process);
+                queried.ok()?;
+                let image = String::from_utf16_lossy(&image[..image_len as usize]);
+                let app_name = image.rsplit('\\').next()?.trim_end_matches(".exe").to_string();
+
+                let mut input = LASTINPUTINFO { cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32, dwTime: 0 };
+                let idle_secs = if GetLastInputInfo(&mut input).as_bool() { GetTickCount().wrapping_sub(input.dwTime) as u64 / 1000 } else { 0 };
+
+                Some(ForegroundSnapshot { app_name, window_title: (!title.is_empty()).then_some(title), idle_secs })
+            }
+        }
+    }
+
+    pub fn probe() -> Option<Box<dyn ForegroundProbe>> {
+        Some(Box::new(Win32Probe))
+    }
+}
+
+/// macOS: System Events via osascript and HIDIdleTime via ioreg (requires Accessibility permission)
+#[cfg(all(feature = "os-capture", target_os = "macos"))]
+mod platform {
+    use super::{ForegroundProbe, ForegroundSnapshot};
+    use std::process::Command;
+
+    const FRONTMOST_SCRIPT: &str = r#"tell application "System Events"
+    set frontApp to first application process whose frontmost is true
+    set appName to name of frontApp
+    set winTitle to ""
+    try
+        set winTitle to name of front window of frontApp
+    end try
+end tell
+return appName & linefeed & winTitle"#;
+
+    struct MacProbe;
+
+    fn idle_secs() -> u64 {
+        let Ok(output) = Command::new("ioreg").args(["-c", "IOHIDSystem", "-d", "4"]).output() else { return 0 };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.contains("\"HIDIdleTime\""))
+            .and_then(|line| line.rsplit('=').next())
+            .and_then(|ns| ns.trim().parse::<u64>().ok())
+            .map(|ns| ns / 1_000_000_000)
+            .unwrap_or(0)
+    }
+
+    impl ForegroundProbe for MacProbe {
+        fn probe_name(&self) -> &str {
+            "macos_system_events"
+        }
+
+        fn snapshot(&mut self) -> Option<ForegroundSnapshot> {
+            let output = Command::new("osascript").args(["-e", FRONTMOST_SCRIPT]).output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let mut lines = stdout.lines();
+            let app_name = lines.next()?.trim().to_string();
+            let title = lines.next().unwrap_or("").trim().to_string();
+            if app_name.is_empty() {
+                return None;
+            }
+            Some(ForegroundSnapshot { app_name, window_title: (!title.is_empty()).then_some(title), idle_secs: idle_secs() })
+        }
+    }
+
+    pub fn probe() -> Option<Box<dyn ForegroundProbe>> {
+        Some(Box::new(MacProbe))
+    }
+}
+
+/// Linux (X11): xdotool for the active window, /proc for its process name, xprintidle for idle time
+#[cfg(all(feature = "os-capture", target_os = "linux"))]
+mod platform {
+    use super::{ForegroundProbe, ForegroundSnapshot};
+    use std::process::Command;
+
+    struct X11Probe;
+
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    impl ForegroundProbe for X11Probe {
+        fn probe_name(&self) -> &str {
+            "x11"
+        }
+
+        fn snapshot(&mut self) -> Option<ForegroundSnapshot> {
+            let pid = run("xdotool", &["getactivewindow", "getwindowpid"])?;
+            let app_name = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?.trim().to_string();
+            let title = run("xdotool", &["getactivewindow", "getwindowname"]).filter(|t| !t.is_empty());
+            let idle_secs = run("xprintidle", &[]).and_then(|ms| ms.parse::<u64>().ok()).map(|ms| ms / 1000).unwrap_or(0);
+            Some(ForegroundSnapshot { app_name, window_title: title, idle_secs })
+        }
+    }
+
+    pub fn probe() -> Option<Box<dyn ForegroundProbe>> {
+        run("xdotool", &["version"])?;
+        Some(Box::new(X11Probe))
+    }
+}
+
+#[cfg(not(all(feature = "os-capture", any(target_os = "windows", target_os = "macos", target_os = "linux"))))]
+mod platform {
+    use super::ForegroundProbe;
+
+    pub fn probe() -> Option<Box<dyn ForegroundProbe>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct ScriptedProbe(VecDeque<Option<ForegroundSnapshot>>);
+
+    impl ForegroundProbe for ScriptedProbe {
+        fn probe_name(&self) -> &str {
+            "scripted"
+        }
+
+        fn snapshot(&mut self) -> Option<ForegroundSnapshot> {
+            self.0.pop_front().flatten()
+        }
+    }
+
+    fn snap(app: &str, title: &str, idle_secs: u64) -> Option<ForegroundSnapshot> {
+        Some(ForegroundSnapshot { app_name: app.to_string(), window_title: Some(title.to_string()), idle_secs })
+    }
+
+    fn capture(script: Vec<Option<ForegroundSnapshot>>) -> OsEventCapture {
+        OsEventCapture::with_probe(Box::new(ScriptedProbe(script.into())), CaptureConfig::default())
+    }
+
+    #[test]
+    fn test_launch_switch_and_focus_events() {
+        let mut capture = capture(vec![
+            snap("IDE", "main.rs", 0),
+            snap("IDE", "main.rs", 0),
+            snap("IDE", "lib.rs", 0),
+            None,
+            snap("Slack", "general", 0),
+            snap("IDE", "lib.rs", 0),
+        ]);
+        let types: Vec<OSEventType> = (0..6).flat_map(|i| capture.poll(1_000 + i)).map(|e| e.event_type).collect();
+        assert_eq!(
+            types,
+            vec![OSEventType::AppLaunch, OSEventType::WindowFocus, OSEventType::AppLaunch, OSEventType::AppSwitch]
+        );
+    }
+
+    #[test]
+    fn test_idle_sleep_and_wake_stream_into_observer() {
+        let mut capture = capture(vec![snap("IDE", "main.rs", 0), snap("IDE", "main.rs", 400), snap("IDE", "main.rs", 500), snap("IDE", "main.rs", 2)]);
+        let mut observer = EdgeObserver::new(100);
+        assert_eq!(capture.poll_into(&mut observer, 1_000), 1);
+
+        let sleep = capture.poll(2_000);
+        assert_eq!(sleep[0].event_type, OSEventType::SystemSleep);
+        assert_eq!(sleep[0].timestamp, 1_600);
+        assert_eq!(sleep[0].metadata["source"], "scripted");
+        assert!(capture.poll(2_100).is_empty());
+
+        let wake: Vec<OSEventType> = capture.poll(2_200).into_iter().map(|e| e.event_type).collect();
+        assert_eq!(wake, vec![OSEventType::SystemWake, OSEventType::AppSwitch]);
+        assert_eq!(observer.get_app_sequence(10), vec!["IDE"]);
+    }
+}
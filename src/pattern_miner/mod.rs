@@ -3,11 +3,20 @@
 /// Implement on-device pattern miner with causal inference heuristics
 
 use crate::types::*;
-use crate::edge::OSEvent;
+use crate::bus::EventSubscriber;
+use crate::consent::{DataDisposition, RevocationHandler, BEHAVIORAL_LOGGING_CAPABILITY};
+use crate::edge::{OSEvent, OSEventType};
+use crate::models::{FRAGMENTATION_SCORE_THRESHOLD, GESTURE_REPEAT_THRESHOLD};
+use crate::sampling;
+use crate::signature::PatternSignature;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+pub mod sequence;
+
+use sequence::{MinedSequence, SequenceMiner, SequenceMinerConfig};
+
 /// Causal relationship between events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CausalRelationship {
@@ -17,20 +26,41 @@ pub struct CausalRelationship {
     pub confidence: f64,
 }
 
+/// Shortest app sequence mined: a single transition already carries a causal link
+const MIN_SEQUENCE_LEN: usize = 2;
+
+/// Focus dwell shorter than this counts as a fragment (seconds)
+const SHORT_FOCUS_SECS: i64 = 60;
+
+/// Minimum focus segments before fragmentation is scored
+const MIN_FOCUS_SEGMENTS: usize = 4;
+
+/// Gesture micro-sequence lengths searched for repeats
+const GESTURE_NGRAM_LENGTHS: [usize; 3] = [2, 3, 4];
+
 /// Pattern miner with causal inference
 /// Source: Athenos_AI_Strategy.md#L110
 pub struct PatternMiner {
-    event_sequences: Vec<Vec<String>>,
+    sequences: SequenceMiner, // Windowed n-gram counts; raw sequences outside the window are not kept
     causal_graph: HashMap<String, Vec<CausalRelationship>>,
+    signature_counts: HashMap<String, usize>, // PatternSignature key -> times mined
+    latest_patterns: Vec<PatternType>,         // Patterns from the last bus batch
 }
 
 impl PatternMiner {
     /// Create new pattern miner
     pub fn new() -> Self {
+        Self::with_sequence_config(SequenceMinerConfig::default())
+    }
+
+    /// Create pattern miner with a custom sequence window and support thresholds
+    pub fn with_sequence_config(config: SequenceMinerConfig) -> Self {
         info!("PatternMiner::new: Creating pattern miner");
         Self {
-            event_sequences: Vec::new(),
+            sequences: SequenceMiner::new(config),
             causal_graph: HashMap::new(),
+            signature_counts: HashMap::new(),
+            latest_patterns: Vec::new(),
         }
     }
 
@@ -40,20 +70,10 @@ impl PatternMiner {
         info!("PatternMiner::mine_patterns: Mining patterns from {} events", events.len());
         
         // Extract app sequences
-        let sequence: Vec<String> = events
-            .iter()
-            .filter_map(|e| {
-                match e.event_type {
-                    crate::edge::OSEventType::AppLaunch | 
-                    crate::edge::OSEventType::AppSwitch |
-                    crate::edge::OSEventType::WindowFocus => Some(e.app_name.clone()),
-                    _ => None,
-                }
-            })
-            .collect();
+        let sequence = Self::app_sequence(events);
         
-        if sequence.len() >= 3 {
-            self.event_sequences.push(sequence.clone());
+        if sequence.len() >= MIN_SEQUENCE_LEN {
+            self.record_sequence(events, &sequence);
             
             // Infer causal relationships
             for i in 0..sequence.len().saturating_sub(1) {
@@ -61,49 +81,140 @@ impl PatternMiner {
                 let effect = sequence[i + 1].clone();
                 let strength = self.compute_causal_strength(&cause, &effect);
                 
+                let relationships = self.causal_graph.entry(cause.clone()).or_default();
+                relationships.retain(|r| r.effect != effect);
                 if strength > 0.3 {
-                    let relationship = CausalRelationship {
-                        cause: cause.clone(),
-                        effect: effect.clone(),
+                    relationships.push(CausalRelationship {
+                        cause,
+                        effect,
                         strength,
                         confidence: 0.7, // Phase B: heuristic confidence
-                    };
-                    
-                    self.causal_graph
-                        .entry(cause)
-                        .or_insert_with(Vec::new)
-                        .push(relationship);
+                    });
                 }
             }
         }
         
         // Detect pattern types
-        self.detect_pattern_types()
+        let mut patterns = self.detect_pattern_types();
+        let micro = Self::micro_pattern_metrics(events);
+        if micro.get("gesture_repeat_count").copied().unwrap_or(0.0) >= GESTURE_REPEAT_THRESHOLD {
+            patterns.push(PatternType::RepetitiveGesture);
+        }
+        if micro.get("attention_fragmentation_score").copied().unwrap_or(0.0) >= FRAGMENTATION_SCORE_THRESHOLD {
+            patterns.push(PatternType::AttentionFragmentation);
+        }
+        patterns
     }
 
-    /// Compute causal strength between two events
-    /// Source: Athenos_AI_Strategy.md#L110
-    fn compute_causal_strength(&self, cause: &str, effect: &str) -> f64 {
-        // Count co-occurrences
-        let mut co_occurrences = 0;
-        let mut cause_count = 0;
-        
-        for seq in &self.event_sequences {
-            for i in 0..seq.len().saturating_sub(1) {
-                if seq[i] == cause {
-                    cause_count += 1;
-                    if i + 1 < seq.len() && seq[i + 1] == effect {
-                        co_occurrences += 1;
-                    }
+    /// App launch/switch/focus sequence of an event batch
+    fn app_sequence(events: &[OSEvent]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|e| {
+                match e.event_type {
+                    OSEventType::AppLaunch | 
+                    OSEventType::AppSwitch |
+                    OSEventType::WindowFocus => Some(e.app_name.clone()),
+                    _ => None,
                 }
+            })
+            .collect()
+    }
+
+    /// Count a batch's sequence into the window and signature totals
+    fn record_sequence(&mut self, events: &[OSEvent], sequence: &[String]) {
+        let timestamp = events.iter().map(|e| e.timestamp).max().unwrap_or(0);
+        self.sequences.add_sequence(timestamp, sequence);
+        *self.signature_counts.entry(PatternSignature::from_sequence(sequence).key()).or_insert(0) += 1;
+    }
+
+    /// Count historical event batches in one pass and return the frequent sequences
+    /// Unlike mine_patterns, no pattern flags or causal links are computed per batch
+    pub fn mine_history(&mut self, batches: &[Vec<OSEvent>]) -> Vec<MinedSequence> {
+        info!("PatternMiner::mine_history: Mining {} event batches", batches.len());
+        for events in batches {
+            let sequence = Self::app_sequence(events);
+            if sequence.len() >= MIN_SEQUENCE_LEN {
+                self.record_sequence(events, &sequence);
             }
         }
-        
-        if cause_count > 0 {
-            co_occurrences as f64 / cause_count as f64
-        } else {
-            0.0
+        self.sequences.frequent_sequences()
+    }
+
+    /// Frequent app sequences in the current window
+    pub fn frequent_sequences(&self) -> Vec<MinedSequence> {
+        self.sequences.frequent_sequences()
+    }
+
+    /// Mouse/keyboard micro-pattern and fragmentation metrics for an event batch
+    /// Keys match PatternDetector: gesture_repeat_count, attention_fragmentation_score
+    pub fn micro_pattern_metrics(events: &[OSEvent]) -> HashMap<String, f64> {
+        let mut metrics = HashMap::new();
+        metrics.insert("gesture_repeat_count".to_string(), Self::gesture_repeat_count(events));
+        metrics.insert("attention_fragmentation_score".to_string(), Self::fragmentation_score(events));
+        metrics
+    }
+
+    /// Most frequent back-to-back repeat of an identical input micro-sequence
+    /// Each repeat counts with its sample weight (inverse inclusion probability) so sampled streams are not under-counted
+    fn gesture_repeat_count(events: &[OSEvent]) -> f64 {
+        let inputs: Vec<&OSEvent> = events
+            .iter()
+            .filter(|e| matches!(e.event_type, OSEventType::KeyPress | OSEventType::MouseClick))
+            .collect();
+        let gestures: Vec<String> = inputs
+            .iter()
+            .map(|e| {
+                // Key/button/target only; typed text is never part of the token
+                let detail = ["key", "button", "target"]
+                    .iter()
+                    .find_map(|k| e.metadata.get(*k))
+                    .map(|s| s.as_str())
+                    .unwrap_or("");
+                format!("{:?}:{}:{}", e.event_type, e.app_name, detail)
+            })
+            .collect();
+
+        let mut best: f64 = 0.0;
+        for len in GESTURE_NGRAM_LENGTHS {
+            let mut start = 0;
+            while start + len <= gestures.len() {
+                let unit = &gestures[start..start + len];
+                let mut repeats = 1;
+                while start + (repeats + 1) * len <= gestures.len() && &gestures[start + repeats * len..start + (repeats + 1) * len] == unit {
+                    repeats += 1;
+                }
+                // A single repeated gesture is a 1-gram, not a micro-sequence
+                if repeats > 1 && unit.iter().any(|g| g != &unit[0]) {
+                    let weighted: f64 = (0..repeats).map(|k| sampling::sample_weight(inputs[start + k * len])).sum();
+                    best = best.max(weighted);
+                }
+                start += 1;
+            }
         }
+        best.round()
+    }
+
+    /// Share of focus segments shorter than SHORT_FOCUS_SECS (0.0 to 1.0)
+    fn fragmentation_score(events: &[OSEvent]) -> f64 {
+        let focus: Vec<i64> = events
+            .iter()
+            .filter(|e| matches!(e.event_type, OSEventType::AppSwitch | OSEventType::WindowFocus))
+            .map(|e| e.timestamp)
+            .collect();
+        let segments: Vec<i64> = focus.windows(2).map(|w| w[1] - w[0]).collect();
+        if segments.len() < MIN_FOCUS_SEGMENTS {
+            return 0.0;
+        }
+        let short = segments.iter().filter(|d| **d < SHORT_FOCUS_SECS).count();
+        short as f64 / segments.len() as f64
+    }
+
+    /// Compute causal strength between two events
+    /// Transition counts come from the windowed prefix tree rather than a scan of every stored sequence
+    /// Source: Athenos_AI_Strategy.md#L110
+    fn compute_causal_strength(&self, cause: &str, effect: &str) -> f64 {
+        self.sequences.transition_strength(cause, effect)
     }
 
     /// Detect pattern types from sequences
@@ -111,22 +222,23 @@ impl PatternMiner {
         let mut patterns = Vec::new();
         
         // Check for workflow sequences
-        if self.event_sequences.len() > 5 {
+        if self.sequences.window_len() > 5 {
             patterns.push(PatternType::WorkflowSequence);
         }
         
         // Check for context switching
-        let avg_sequence_len: f64 = self.event_sequences.iter()
-            .map(|s| s.len() as f64)
-            .sum::<f64>() / self.event_sequences.len() as f64;
-        
-        if avg_sequence_len > 5.0 {
+        if self.sequences.mean_sequence_len() > 5.0 {
             patterns.push(PatternType::ContextSwitching);
         }
         
         patterns
     }
 
+    /// Times a pattern with this signature key has been mined
+    pub fn signature_count(&self, signature_key: &str) -> usize {
+        self.signature_counts.get(signature_key).copied().unwrap_or(0)
+    }
+
     /// Get causal relationships for an app
     pub fn get_causal_relationships(&self, app: &str) -> Vec<&CausalRelationship> {
         self.causal_graph
@@ -134,6 +246,41 @@ impl PatternMiner {
             .map(|rels| rels.iter().collect())
             .unwrap_or_else(Vec::new)
     }
+
+    /// Patterns mined from the most recent bus batch
+    pub fn latest_patterns(&self) -> &[PatternType] {
+        &self.latest_patterns
+    }
+}
+
+impl RevocationHandler for PatternMiner {
+    fn revocation_handler_name(&self) -> &str {
+        "pattern_miner"
+    }
+
+    /// Mined sequences, causal links and signature counts all come from behavioral logs
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        if capability != BEHAVIORAL_LOGGING_CAPABILITY {
+            return Ok((DataDisposition::NotAffected, 0));
+        }
+        let purged = self.sequences.window_len() + self.signature_counts.len() + self.causal_graph.values().map(Vec::len).sum::<usize>();
+        self.sequences = SequenceMiner::new(self.sequences.config().clone());
+        self.causal_graph.clear();
+        self.signature_counts.clear();
+        self.latest_patterns.clear();
+        info!("PatternMiner::on_consent_revoked: Purged {} records", purged);
+        Ok((DataDisposition::Purged, purged))
+    }
+}
+
+impl EventSubscriber for PatternMiner {
+    fn subscriber_name(&self) -> &str {
+        "pattern_miner"
+    }
+
+    fn on_events(&mut self, events: &[OSEvent]) {
+        self.latest_patterns = self.mine_patterns(events);
+    }
 }
 
 impl Default for PatternMiner {
@@ -150,7 +297,7 @@ mod tests {
     #[test]
     fn test_pattern_miner_creation() {
         let miner = PatternMiner::new();
-        assert_eq!(miner.event_sequences.len(), 0);
+        assert_eq!(miner.sequences.window_len(), 0);
     }
 
     #[test]
@@ -188,6 +335,58 @@ mod tests {
         
         let patterns = miner.mine_patterns(&events);
         assert!(patterns.contains(&PatternType::WorkflowSequence));
+        let signature = PatternSignature::from_sequence(&["teams".to_string(), "GMAIL".to_string(), "IDE".to_string()]);
+        assert_eq!(miner.signature_count(&signature.key()), 7);
+    }
+
+    fn event(event_type: OSEventType, app: &str, key: Option<&str>, timestamp: i64) -> OSEvent {
+        let mut metadata = HashMap::new();
+        if let Some(key) = key {
+            metadata.insert("key".to_string(), key.to_string());
+        }
+        OSEvent { event_type, app_name: app.to_string(), window_title: None, timestamp, metadata }
+    }
+
+    #[test]
+    fn test_repetitive_gesture_detection() {
+        let mut miner = PatternMiner::new();
+        let mut events = Vec::new();
+        for i in 0..6 {
+            events.push(event(OSEventType::KeyPress, "Excel", Some("ctrl+c"), i * 3));
+            events.push(event(OSEventType::MouseClick, "Excel", None, i * 3 + 1));
+            events.push(event(OSEventType::KeyPress, "Excel", Some("ctrl+v"), i * 3 + 2));
+        }
+        assert_eq!(PatternMiner::micro_pattern_metrics(&events)["gesture_repeat_count"], 6.0);
+        assert!(miner.mine_patterns(&events).contains(&PatternType::RepetitiveGesture));
+
+        // Sampled stream (1 in 2 kept) is scaled back by its sample weight
+        let sampled: Vec<OSEvent> = events
+            .iter()
+            .chain(events.iter())
+            .step_by(2)
+            .cloned()
+            .map(|mut e| {
+                e.metadata.insert("sample_weight".to_string(), "2".to_string());
+                e
+            })
+            .collect();
+        assert_eq!(PatternMiner::micro_pattern_metrics(&sampled)["gesture_repeat_count"], 12.0);
+
+        // Same key held down is not a micro-sequence
+        let held: Vec<OSEvent> = (0..10).map(|i| event(OSEventType::KeyPress, "Excel", Some("down"), i)).collect();
+        assert_eq!(PatternMiner::micro_pattern_metrics(&held)["gesture_repeat_count"], 0.0);
+    }
+
+    #[test]
+    fn test_attention_fragmentation_scoring() {
+        let mut miner = PatternMiner::new();
+        let apps = ["Slack", "IDE", "Gmail", "IDE", "Slack", "IDE"];
+        let fragmented: Vec<OSEvent> = apps.iter().enumerate().map(|(i, app)| event(OSEventType::AppSwitch, app, None, i as i64 * 20)).collect();
+        assert_eq!(PatternMiner::micro_pattern_metrics(&fragmented)["attention_fragmentation_score"], 1.0);
+        assert!(miner.mine_patterns(&fragmented).contains(&PatternType::AttentionFragmentation));
+
+        let focused: Vec<OSEvent> = apps.iter().enumerate().map(|(i, app)| event(OSEventType::AppSwitch, app, None, i as i64 * 1800)).collect();
+        assert_eq!(PatternMiner::micro_pattern_metrics(&focused)["attention_fragmentation_score"], 0.0);
     }
 
     #[test]
@@ -220,5 +419,30 @@ mod tests {
         assert!(!relationships.is_empty());
         assert_eq!(relationships[0].effect, "Gmail");
     }
+
+    #[test]
+    fn test_mine_history_returns_concrete_sequences() {
+        let mut miner = PatternMiner::new();
+        let batch = |apps: &[&str], start: i64| -> Vec<OSEvent> {
+            apps.iter().enumerate().map(|(i, app)| event(OSEventType::AppSwitch, app, None, start + i as i64)).collect()
+        };
+        let history = vec![
+            batch(&["Teams", "Gmail", "IDE"], 0),
+            batch(&["Slack", "Teams", "Gmail", "IDE"], 100),
+            batch(&["Teams", "Gmail", "IDE", "Terminal"], 200),
+        ];
+        let mined = miner.mine_history(&history);
+        assert_eq!(mined[0].sequence, vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()]);
+        assert_eq!(mined[0].support, 3);
+        assert_eq!(miner.signature_count(&mined[0].signature_key), 1);
+        assert_eq!(miner.frequent_sequences(), mined);
+
+        // Re-mined causal links are updated in place, not duplicated
+        miner.mine_patterns(&history[0]);
+        miner.mine_patterns(&history[0]);
+        let relationships = miner.get_causal_relationships("Teams");
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].effect, "Gmail");
+    }
 }
 
@@ -0,0 +1,276 @@
+/// Phase: B | Step: 3 | Source: Athenos_AI_Strategy.md#L110
+/// Windowed Sequence Miner
+/// Count-based frequent n-gram mining over a sliding window of app sequences, using a prefix tree with support pruning
+
+use crate::signature::PatternSignature;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tracing::info;
+
+/// Sliding window and support thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SequenceMinerConfig {
+    pub window_secs: i64,   // Batches older than this, relative to the newest, are pruned
+    pub max_batches: usize, // Hard cap on batches held in the window
+    pub min_support: usize, // Batches a sequence must appear in to be frequent
+    pub min_len: usize,     // Shortest n-gram reported
+    pub max_len: usize,     // Longest n-gram counted
+}
+
+impl Default for SequenceMinerConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 7 * 86400,
+            max_batches: 5_000,
+            min_support: 3,
+            min_len: 2,
+            max_len: 5,
+        }
+    }
+}
+
+/// Frequent app sequence found in the window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MinedSequence {
+    pub sequence: Vec<String>,
+    pub support: usize,     // Batches in the window containing the sequence
+    pub occurrences: usize, // Total occurrences in the window
+    pub signature_key: String,
+}
+
+/// Prefix tree node; the path from the root spells an n-gram
+#[derive(Debug, Clone, Default)]
+struct PrefixNode {
+    support: usize,
+    occurrences: usize,
+    children: HashMap<String, PrefixNode>,
+}
+
+impl PrefixNode {
+    fn add(&mut self, path: &[String], occurrences: usize) {
+        let child = self.children.entry(path[0].clone()).or_default();
+        if path.len() == 1 {
+            child.support += 1;
+            child.occurrences += occurrences;
+        } else {
+            child.add(&path[1..], occurrences);
+        }
+    }
+
+    /// Undo `add`; nodes no batch supports any more are dropped
+    fn remove(&mut self, path: &[String], occurrences: usize) {
+        let Some(child) = self.children.get_mut(&path[0]) else {
+            return;
+        };
+        if path.len() == 1 {
+            child.support = child.support.saturating_sub(1);
+            child.occurrences = child.occurrences.saturating_sub(occurrences);
+        } else {
+            child.remove(&path[1..], occurrences);
+        }
+        if child.support == 0 && child.children.is_empty() {
+            self.children.remove(&path[0]);
+        }
+    }
+
+    fn get(&self, path: &[String]) -> Option<&PrefixNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((head, rest)) => self.children.get(head)?.get(rest),
+        }
+    }
+
+    /// Depth-first walk; a prefix below min_support cannot have a frequent extension, so its subtree is skipped
+    fn collect(&self, path: &mut Vec<String>, config: &SequenceMinerConfig, out: &mut Vec<MinedSequence>) {
+        for (token, child) in &self.children {
+            if child.support < config.min_support {
+                continue;
+            }
+            path.push(token.clone());
+            if path.len() >= config.min_len {
+                out.push(MinedSequence {
+                    sequence: path.clone(),
+                    support: child.support,
+                    occurrences: child.occurrences,
+                    signature_key: PatternSignature::from_sequence(path).key(),
+                });
+            }
+            child.collect(path, config, out);
+            path.pop();
+        }
+    }
+}
+
+/// One mined batch held in the window
+#[derive(Debug, Clone)]
+struct WindowBatch {
+    timestamp: i64,
+    raw_len: usize,
+    ngrams: HashMap<Vec<String>, usize>, // n-gram -> occurrences in this batch
+}
+
+/// Sliding-window frequent sequence miner
+/// Each batch costs O(len × max_len) to add or prune; nothing outside the window is retained
+#[derive(Debug, Clone)]
+pub struct SequenceMiner {
+    config: SequenceMinerConfig,
+    root: PrefixNode,
+    window: VecDeque<WindowBatch>,
+    pruned: u64,
+}
+
+impl SequenceMiner {
+    pub fn new(config: SequenceMinerConfig) -> Self {
+        info!("SequenceMiner::new: Creating sequence miner (window {}s, min support {})", config.window_secs, config.min_support);
+        Self { config, root: PrefixNode::default(), window: VecDeque::new(), pruned: 0 }
+    }
+
+    pub fn config(&self) -> &SequenceMinerConfig {
+        &self.config
+    }
+
+    /// Add one batch's app sequence observed at `timestamp`; immediate repeats are collapsed
+    pub fn add_sequence(&mut self, timestamp: i64, sequence: &[String]) {
+        let mut tokens: Vec<String> = sequence.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+        tokens.dedup();
+        let mut ngrams: HashMap<Vec<String>, usize> = HashMap::new();
+        for start in 0..tokens.len() {
+            for len in 1..=self.config.max_len.min(tokens.len() - start) {
+                *ngrams.entry(tokens[start..start + len].to_vec()).or_insert(0) += 1;
+            }
+        }
+        for (ngram, occurrences) in &ngrams {
+            self.root.add(ngram, *occurrences);
+        }
+        self.window.push_back(WindowBatch { timestamp, raw_len: sequence.len(), ngrams });
+        self.prune(timestamp);
+    }
+
+    /// Add many (timestamp, sequence) batches, then mine once
+    pub fn mine_batch(&mut self, batches: &[(i64, Vec<String>)]) -> Vec<MinedSequence> {
+        info!("SequenceMiner::mine_batch: Adding {} sequences", batches.len());
+        for (timestamp, sequence) in batches {
+            self.add_sequence(*timestamp, sequence);
+        }
+        self.frequent_sequences()
+    }
+
+    /// Drop batches outside the window or over the batch cap
+    fn prune(&mut self, now: i64) {
+        let cutoff = now - self.config.window_secs;
+        while let Some(oldest) = self.window.front() {
+            if oldest.timestamp >= cutoff && self.window.len() <= self.config.max_batches {
+                break;
+            }
+            if let Some(batch) = self.window.pop_front() {
+                for (ngram, occurrences) in &batch.ngrams {
+                    self.root.remove(ngram, *occurrences);
+                }
+                self.pruned += 1;
+            }
+        }
+    }
+
+    /// Frequent sequences, most supported first (longer first on ties)
+    pub fn frequent_sequences(&self) -> Vec<MinedSequence> {
+        let mut out = Vec::new();
+        self.root.collect(&mut Vec::new(), &self.config, &mut out);
+        out.sort_by(|a, b| {
+            b.support
+                .cmp(&a.support)
+                .then(b.sequence.len().cmp(&a.sequence.len()))
+                .then(b.occurrences.cmp(&a.occurrences))
+                .then(a.sequence.cmp(&b.sequence))
+        });
+        out
+    }
+
+    /// Share of `cause` occurrences directly followed by `effect` in the window (0.0 to 1.0)
+    pub fn transition_strength(&self, cause: &str, effect: &str) -> f64 {
+        let Some(node) = self.root.get(&[cause.to_string()]) else {
+            return 0.0;
+        };
+        let followed: usize = node.children.values().map(|child| child.occurrences).sum();
+        if followed == 0 {
+            return 0.0;
+        }
+        node.children.get(effect).map(|child| child.occurrences as f64 / followed as f64).unwrap_or(0.0)
+    }
+
+    /// Batches currently in the window
+    pub fn window_len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Mean raw sequence length in the window (0.0 when empty)
+    pub fn mean_sequence_len(&self) -> f64 {
+        if self.window.is_empty() {
+            return 0.0;
+        }
+        self.window.iter().map(|b| b.raw_len as f64).sum::<f64>() / self.window.len() as f64
+    }
+
+    /// Batches pruned from the window so far
+    pub fn pruned_batches(&self) -> u64 {
+        self.pruned
+    }
+}
+
+impl Default for SequenceMiner {
+    fn default() -> Self {
+        Self::new(SequenceMinerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seq(apps: &[&str]) -> Vec<String> {
+        apps.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_frequent_ngrams_with_min_support() {
+        let mut miner = SequenceMiner::new(SequenceMinerConfig { min_support: 3, ..SequenceMinerConfig::default() });
+        let batches: Vec<(i64, Vec<String>)> = vec![
+            (10, seq(&["Teams", "Gmail", "IDE", "Terminal"])),
+            (20, seq(&["Slack", "Teams", "Gmail", "IDE"])),
+            (30, seq(&["Teams", "Teams", "Gmail", "IDE", "Browser"])),
+            (40, seq(&["Browser", "IDE"])),
+        ];
+        let mined = miner.mine_batch(&batches);
+
+        assert_eq!(mined[0].sequence, seq(&["Teams", "Gmail", "IDE"]));
+        assert_eq!(mined[0].support, 3);
+        assert_eq!(mined[0].signature_key, PatternSignature::from_sequence(&mined[0].sequence).key());
+        assert!(mined.iter().any(|m| m.sequence == seq(&["Gmail", "IDE"])));
+        // Below support: seen in only one or two batches
+        assert!(!mined.iter().any(|m| m.sequence.contains(&"Terminal".to_string()) || m.sequence.contains(&"Browser".to_string())));
+        assert!(mined.iter().all(|m| m.sequence.len() >= 2 && m.support >= 3));
+    }
+
+    #[test]
+    fn test_window_prunes_old_batches() {
+        let config = SequenceMinerConfig { window_secs: 100, min_support: 2, ..SequenceMinerConfig::default() };
+        let mut miner = SequenceMiner::new(config);
+        miner.add_sequence(0, &seq(&["A", "B", "C"]));
+        miner.add_sequence(50, &seq(&["A", "B", "C"]));
+        assert_eq!(miner.frequent_sequences()[0].sequence, seq(&["A", "B", "C"]));
+
+        miner.add_sequence(160, &seq(&["X", "Y"]));
+        assert_eq!(miner.window_len(), 1);
+        assert_eq!(miner.pruned_batches(), 2);
+        assert!(miner.frequent_sequences().is_empty());
+        assert!(miner.root.children.keys().all(|k| k == "X" || k == "Y"));
+    }
+
+    #[test]
+    fn test_transition_strength() {
+        let mut miner = SequenceMiner::default();
+        miner.add_sequence(0, &seq(&["Teams", "Gmail", "Teams", "IDE"]));
+        assert_eq!(miner.transition_strength("Teams", "Gmail"), 0.5);
+        assert_eq!(miner.transition_strength("Gmail", "Teams"), 1.0);
+        assert_eq!(miner.transition_strength("IDE", "Teams"), 0.0);
+    }
+}
@@ -3,10 +3,20 @@
 /// Prepare plugin SDK for internal teams; prototype external partner integration
 
 use crate::types::*;
+use crate::report::ReportCadence;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use tracing::info;
 
+pub mod signing;
+pub mod wasm;
+
+use signing::{PluginError, PluginSignature, TrustedPublishers};
+
+/// Plugin API version exposed by this daemon; plugins declare the range they support
+pub const PLUGIN_API_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// Plugin capability
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum PluginCapability {
@@ -28,6 +38,110 @@ pub struct PluginMetadata {
     pub description: String,
 }
 
+/// Per-plugin resource usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginUsage {
+    pub cpu_time_ms: f64,
+    pub memory_high_water_bytes: u64,
+    pub action_count: u64,
+    pub error_count: u64,
+}
+
+impl PluginUsage {
+    /// Fraction of actions that failed
+    pub fn error_rate(&self) -> f64 {
+        if self.action_count == 0 {
+            0.0
+        } else {
+            self.error_count as f64 / self.action_count as f64
+        }
+    }
+}
+
+/// Resource budget; a plugin exceeding any limit is auto-disabled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginBudget {
+    pub max_cpu_time_ms: f64,
+    pub max_memory_bytes: u64,
+    pub max_actions: u64,
+    pub max_error_rate: f64,
+    pub min_actions_for_error_rate: u64, // Avoid disabling on the first failure
+}
+
+impl Default for PluginBudget {
+    fn default() -> Self {
+        Self {
+            max_cpu_time_ms: 60_000.0,
+            max_memory_bytes: 256 * 1024 * 1024,
+            max_actions: 10_000,
+            max_error_rate: 0.25,
+            min_actions_for_error_rate: 20,
+        }
+    }
+}
+
+/// Typed data for a plugin-contributed report section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReportSectionData {
+    Metric { label: String, value: f64, unit: Option<String> },
+    Table { columns: Vec<String>, rows: Vec<Vec<String>> },
+    Series { label: String, points: Vec<(i64, f64)> },
+    Text { text: String },
+}
+
+/// How a report renderer should present a section
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderHint {
+    Inline,
+    Table,
+    Chart,
+    Callout,
+}
+
+/// Report section registered by a Visualization plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSection {
+    pub plugin_id: String,
+    pub title: String,
+    pub data: ReportSectionData,
+    pub hint: RenderHint,
+    pub cadences: Vec<ReportCadence>,
+}
+
+/// Size limits for plugin-contributed report sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSectionLimits {
+    pub max_sections_per_plugin: usize,
+    pub max_title_chars: usize,
+    pub max_data_bytes: usize, // Serialized JSON size
+    pub max_rows: usize,       // Table rows / series points
+    pub max_rendered_chars: usize,
+}
+
+impl Default for ReportSectionLimits {
+    fn default() -> Self {
+        Self {
+            max_sections_per_plugin: 3,
+            max_title_chars: 80,
+            max_data_bytes: 8 * 1024,
+            max_rows: 50,
+            max_rendered_chars: 4000,
+        }
+    }
+}
+
+/// Plugin section rendered for inclusion in a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedSection {
+    pub plugin_id: String,
+    pub title: String,
+    pub hint: RenderHint,
+    pub data: ReportSectionData,
+    pub markdown: String, // Escaped; plugin content cannot inject markup
+}
+
 /// Plugin interface trait (stub)
 /// Note: In production, would use proper trait objects or enum dispatch
 pub trait Plugin: Send + Sync {
@@ -39,6 +153,16 @@ pub trait Plugin: Send + Sync {
 /// Source: Athenos_AI_Strategy.md#L128
 pub struct PluginRegistry {
     metadata: HashMap<String, PluginMetadata>,
+    usage: HashMap<String, PluginUsage>,
+    budgets: HashMap<String, PluginBudget>,
+    default_budget: PluginBudget,
+    disabled: HashMap<String, String>, // plugin_id -> reason
+    report_sections: HashMap<String, Vec<ReportSection>>, // plugin_id -> sections
+    section_limits: ReportSectionLimits,
+    trusted_publishers: TrustedPublishers,
+    publishers: HashMap<String, String>, // plugin_id -> publisher_id of its verified signature
+    first_party: HashSet<String>,        // Plugin IDs compiled into the daemon; third parties cannot claim them
+    require_signatures: bool,            // Third-party plugins must be signed by a trusted publisher
 }
 
 impl PluginRegistry {
@@ -47,16 +171,116 @@ impl PluginRegistry {
         info!("PluginRegistry::new: Creating plugin registry");
         Self {
             metadata: HashMap::new(),
+            usage: HashMap::new(),
+            budgets: HashMap::new(),
+            default_budget: PluginBudget::default(),
+            disabled: HashMap::new(),
+            report_sections: HashMap::new(),
+            section_limits: ReportSectionLimits::default(),
+            trusted_publishers: TrustedPublishers::new(),
+            publishers: HashMap::new(),
+            first_party: HashSet::new(),
+            require_signatures: true,
         }
     }
 
-    /// Register plugin
+    /// Register a first-party plugin compiled into the daemon
     /// Source: Athenos_AI_Strategy.md#L128
     pub fn register_plugin(&mut self, metadata: PluginMetadata) {
         info!("PluginRegistry::register_plugin: Registering plugin {}", metadata.id);
+        self.publishers.remove(&metadata.id);
+        self.first_party.insert(metadata.id.clone());
         self.metadata.insert(metadata.id.clone(), metadata);
     }
 
+    /// Register a third-party plugin after verifying its signature over the metadata and artifact
+    /// Unsigned plugins are only accepted when signatures are not required
+    pub fn register_signed_plugin(
+        &mut self,
+        metadata: PluginMetadata,
+        signature: Option<&PluginSignature>,
+        artifact: &[u8],
+    ) -> Result<(), PluginError> {
+        info!("PluginRegistry::register_signed_plugin: Verifying plugin {}", metadata.id);
+        if self.first_party.contains(&metadata.id) {
+            return Err(PluginError::InvalidSignature { plugin_id: metadata.id.clone(), reason: "ID belongs to a first-party plugin".to_string() });
+        }
+        let publisher_id = match signature {
+            Some(signature) => {
+                self.trusted_publishers.verify_artifact(&metadata, signature, artifact)?;
+                Some(signature.publisher_id.clone())
+            }
+            None if self.require_signatures => {
+                return Err(PluginError::InvalidSignature { plugin_id: metadata.id.clone(), reason: "plugin is unsigned".to_string() });
+            }
+            None => None,
+        };
+        // A plugin already registered under one publisher cannot be taken over by another
+        if let Some(owner) = self.publishers.get(&metadata.id).filter(|owner| Some(*owner) != publisher_id.as_ref()) {
+            return Err(PluginError::InvalidSignature {
+                plugin_id: metadata.id.clone(),
+                reason: format!("ID is registered to publisher {}", owner),
+            });
+        }
+        let plugin_id = metadata.id.clone();
+        self.publishers.remove(&plugin_id);
+        self.metadata.insert(plugin_id.clone(), metadata);
+        if let Some(publisher_id) = publisher_id {
+            self.publishers.insert(plugin_id, publisher_id);
+        }
+        Ok(())
+    }
+
+    /// Share a publisher key set (e.g. with the marketplace) instead of the registry's own
+    pub fn set_trusted_publishers(&mut self, trusted_publishers: TrustedPublishers) {
+        self.trusted_publishers = trusted_publishers;
+    }
+
+    /// Trust a publisher's Ed25519 public key (hex)
+    pub fn trust_publisher(&mut self, publisher_id: &str, public_key_hex: &str) -> Result<(), String> {
+        self.trusted_publishers.trust(publisher_id, public_key_hex)
+    }
+
+    /// Trust publishers listed in a JSON file of publisher_id -> public key (hex); a missing file trusts none
+    pub fn trust_publishers_from_file(&mut self, path: impl AsRef<std::path::Path>) -> Result<usize, String> {
+        self.trusted_publishers.trust_from_file(path)
+    }
+
+    /// Revoke a publisher and disable every plugin registered under its signature
+    pub fn revoke_publisher(&mut self, publisher_id: &str) -> usize {
+        self.trusted_publishers.revoke(publisher_id);
+        let revoked: Vec<String> = self.publishers.iter().filter(|(_, p)| p.as_str() == publisher_id).map(|(id, _)| id.clone()).collect();
+        for plugin_id in &revoked {
+            info!("PluginRegistry::revoke_publisher: Disabling {} (publisher {} revoked)", plugin_id, publisher_id);
+            self.disabled.insert(plugin_id.clone(), format!("Publisher {} revoked", publisher_id));
+        }
+        revoked.len()
+    }
+
+    /// Accept unsigned third-party plugins (development only)
+    pub fn set_require_signatures(&mut self, require: bool) {
+        info!("PluginRegistry::set_require_signatures: {}", require);
+        self.require_signatures = require;
+    }
+
+    /// Publisher whose signature a plugin was registered under (None for first-party or unsigned plugins)
+    pub fn publisher(&self, plugin_id: &str) -> Option<&str> {
+        self.publishers.get(plugin_id).map(|p| p.as_str())
+    }
+
+    /// Check at execution time that an enabled plugin declared `capability`
+    pub fn check_permission(&self, plugin_id: &str, capability: PluginCapability) -> Result<(), PluginError> {
+        let metadata = self.metadata.get(plugin_id).ok_or_else(|| PluginError::NotFound(plugin_id.to_string()))?;
+        if let Some(reason) = self.disabled.get(plugin_id) {
+            return Err(PluginError::Disabled { plugin_id: plugin_id.to_string(), reason: reason.clone() });
+        }
+        if !metadata.capabilities.contains(&capability) {
+            info!("PluginRegistry::check_permission: {} denied {:?}", plugin_id, capability);
+            return Err(PluginError::PermissionDenied { plugin_id: plugin_id.to_string(), capability });
+        }
+        Ok(())
+    }
+
     /// Get plugin metadata
     pub fn get_plugin_metadata(&self, plugin_id: &str) -> Option<&PluginMetadata> {
         self.metadata.get(plugin_id)
@@ -68,14 +292,218 @@ impl PluginRegistry {
     }
 
     /// Execute plugin (stub)
-    pub fn execute_plugin(&self, plugin_id: &str, input: &str) -> Result<String, String> {
+    /// Execution is accounted against the plugin's budget; disabled plugins are refused
+    pub fn execute_plugin(&mut self, plugin_id: &str, input: &str) -> Result<String, String> {
         info!("PluginRegistry::execute_plugin: Executing plugin {}", plugin_id);
         
-        if self.metadata.contains_key(plugin_id) {
-            Ok(format!("Plugin {} executed with input: {}", plugin_id, input))
+        if !self.metadata.contains_key(plugin_id) {
+            return Err("Plugin not found".to_string());
+        }
+        if let Some(reason) = self.disabled.get(plugin_id) {
+            return Err(format!("Plugin {} is disabled: {}", plugin_id, reason));
+        }
+        
+        let started = Instant::now();
+        let output = format!("Plugin {} executed with input: {}", plugin_id, input);
+        let cpu_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let memory_bytes = (input.len() + output.len()) as u64; // Stub: buffer sizes until plugins run in a sandboxed process
+        
+        self.record_execution(plugin_id, cpu_time_ms, memory_bytes, true)?;
+        Ok(output)
+    }
+
+    /// Record one plugin action; auto-disables the plugin if it now exceeds its budget
+    pub fn record_execution(&mut self, plugin_id: &str, cpu_time_ms: f64, memory_bytes: u64, success: bool) -> Result<(), String> {
+        if !self.metadata.contains_key(plugin_id) {
+            return Err("Plugin not found".to_string());
+        }
+        
+        let usage = self.usage.entry(plugin_id.to_string()).or_default();
+        usage.cpu_time_ms += cpu_time_ms.max(0.0);
+        usage.memory_high_water_bytes = usage.memory_high_water_bytes.max(memory_bytes);
+        usage.action_count += 1;
+        if !success {
+            usage.error_count += 1;
+        }
+        
+        let budget = self.budgets.get(plugin_id).unwrap_or(&self.default_budget);
+        let exceeded = if usage.cpu_time_ms > budget.max_cpu_time_ms {
+            Some(format!("CPU time {:.0}ms exceeds budget {:.0}ms", usage.cpu_time_ms, budget.max_cpu_time_ms))
+        } else if usage.memory_high_water_bytes > budget.max_memory_bytes {
+            Some(format!("Memory {} bytes exceeds budget {} bytes", usage.memory_high_water_bytes, budget.max_memory_bytes))
+        } else if usage.action_count > budget.max_actions {
+            Some(format!("Action count {} exceeds budget {}", usage.action_count, budget.max_actions))
+        } else if usage.action_count >= budget.min_actions_for_error_rate && usage.error_rate() > budget.max_error_rate {
+            Some(format!("Error rate {:.2} exceeds budget {:.2}", usage.error_rate(), budget.max_error_rate))
         } else {
-            Err("Plugin not found".to_string())
+            None
+        };
+        
+        if let Some(reason) = exceeded {
+            info!("PluginRegistry::record_execution: Auto-disabling {}: {}", plugin_id, reason);
+            self.disabled.insert(plugin_id.to_string(), reason);
         }
+        Ok(())
+    }
+
+    /// Set a per-plugin budget (overrides the default)
+    pub fn set_budget(&mut self, plugin_id: &str, budget: PluginBudget) {
+        info!("PluginRegistry::set_budget: Setting budget for {}", plugin_id);
+        self.budgets.insert(plugin_id.to_string(), budget);
+    }
+
+    /// Get resource usage for a plugin
+    pub fn get_usage(&self, plugin_id: &str) -> Option<&PluginUsage> {
+        self.usage.get(plugin_id)
+    }
+
+    /// Get resource usage for all plugins
+    pub fn get_all_usage(&self) -> &HashMap<String, PluginUsage> {
+        &self.usage
+    }
+
+    /// Reason a plugin was disabled, if it was
+    pub fn disabled_reason(&self, plugin_id: &str) -> Option<&str> {
+        self.disabled.get(plugin_id).map(|r| r.as_str())
+    }
+
+    /// Re-enable a disabled plugin and reset its usage counters
+    pub fn enable_plugin(&mut self, plugin_id: &str) -> Result<(), String> {
+        info!("PluginRegistry::enable_plugin: Re-enabling {}", plugin_id);
+        if self.disabled.remove(plugin_id).is_none() {
+            return Err("Plugin is not disabled".to_string());
+        }
+        self.usage.remove(plugin_id);
+        Ok(())
+    }
+
+    /// Register a report section for a plugin with the Visualization capability
+    /// Re-registering a section with the same title replaces it
+    pub fn register_report_section(&mut self, section: ReportSection) -> Result<(), String> {
+        info!("PluginRegistry::register_report_section: {} registers '{}'", section.plugin_id, section.title);
+        self.check_permission(&section.plugin_id, PluginCapability::Visualization)?;
+        if section.title.trim().is_empty() || section.title.chars().count() > self.section_limits.max_title_chars {
+            return Err(format!("Section title must be 1-{} characters", self.section_limits.max_title_chars));
+        }
+        let rows = match &section.data {
+            ReportSectionData::Table { rows, .. } => rows.len(),
+            ReportSectionData::Series { points, .. } => points.len(),
+            _ => 0,
+        };
+        if rows > self.section_limits.max_rows {
+            return Err(format!("Section has {} rows, limit is {}", rows, self.section_limits.max_rows));
+        }
+        let size = serde_json::to_vec(&section.data).map_err(|e| format!("Failed to encode section: {}", e))?.len();
+        if size > self.section_limits.max_data_bytes {
+            return Err(format!("Section data is {} bytes, limit is {}", size, self.section_limits.max_data_bytes));
+        }
+        
+        let sections = self.report_sections.entry(section.plugin_id.clone()).or_default();
+        sections.retain(|s| s.title != section.title);
+        if sections.len() >= self.section_limits.max_sections_per_plugin {
+            return Err(format!("Plugin may register at most {} report sections", self.section_limits.max_sections_per_plugin));
+        }
+        sections.push(section);
+        Ok(())
+    }
+
+    /// Render sections for a report cadence (disabled plugins are skipped)
+    /// Rendering is accounted against the plugin's budget like any other plugin action
+    pub fn render_report_sections(&mut self, cadence: ReportCadence) -> Vec<RenderedSection> {
+        let mut plugin_ids: Vec<String> = self.report_sections.keys().cloned().collect();
+        plugin_ids.sort();
+        
+        let mut rendered = Vec::new();
+        for plugin_id in plugin_ids {
+            if self.check_permission(&plugin_id, PluginCapability::Visualization).is_err() {
+                continue;
+            }
+            let sections: Vec<ReportSection> = self.report_sections[&plugin_id]
+                .iter()
+                .filter(|s| s.cadences.contains(&cadence))
+                .cloned()
+                .collect();
+            for section in sections {
+                let started = Instant::now();
+                let markdown = Self::render_section_markdown(&section, &self.section_limits);
+                let cpu_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+                let _ = self.record_execution(&plugin_id, cpu_time_ms, markdown.len() as u64, true);
+                if self.disabled.contains_key(&plugin_id) {
+                    break;
+                }
+                rendered.push(RenderedSection {
+                    plugin_id: plugin_id.clone(),
+                    title: section.title.clone(),
+                    hint: section.hint,
+                    data: section.data,
+                    markdown,
+                });
+            }
+        }
+        rendered
+    }
+
+    /// Escape Markdown/HTML control characters and flatten newlines in plugin text
+    pub fn escape_markdown(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '(' | ')' | '<' | '>' | '#' | '|' | '!' => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                '\n' | '\r' | '\t' => escaped.push(' '),
+                c if c.is_control() => {}
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    /// Render section data to Markdown using only escaped plugin content, truncated to the limit
+    fn render_section_markdown(section: &ReportSection, limits: &ReportSectionLimits) -> String {
+        let mut out = String::new();
+        match &section.data {
+            ReportSectionData::Metric { label, value, unit } => {
+                let unit = unit.as_deref().map(|u| format!(" {}", Self::escape_markdown(u))).unwrap_or_default();
+                out.push_str(&format!("- {}: {:.2}{}\n", Self::escape_markdown(label), value, unit));
+            }
+            ReportSectionData::Table { columns, rows } => {
+                let row = |cells: &[String]| format!("| {} |\n", cells.iter().map(|c| Self::escape_markdown(c)).collect::<Vec<_>>().join(" | "));
+                out.push_str(&row(columns));
+                out.push_str(&format!("|{}\n", " --- |".repeat(columns.len().max(1))));
+                for cells in rows.iter().take(limits.max_rows) {
+                    out.push_str(&row(cells));
+                }
+            }
+            ReportSectionData::Series { label, points } => {
+                let values: Vec<f64> = points.iter().map(|(_, v)| *v).collect();
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                match values.last() {
+                    Some(last) => out.push_str(&format!(
+                        "- {}: {} points, min {:.2}, max {:.2}, latest {:.2}\n",
+                        Self::escape_markdown(label), values.len(), min, max, last
+                    )),
+                    None => out.push_str(&format!("- {}: no data\n", Self::escape_markdown(label))),
+                }
+            }
+            ReportSectionData::Text { text } => {
+                let prefix = if section.hint == RenderHint::Callout { "> " } else { "" };
+                out.push_str(&format!("{}{}\n", prefix, Self::escape_markdown(text)));
+            }
+        }
+        
+        if out.chars().count() > limits.max_rendered_chars {
+            out = out.chars().take(limits.max_rendered_chars).collect();
+            out.push_str("…\n");
+        }
+        out
+    }
+
+    /// IDs of auto-disabled plugins
+    pub fn get_disabled_plugins(&self) -> HashSet<&str> {
+        self.disabled.keys().map(|id| id.as_str()).collect()
     }
 }
 
@@ -148,5 +576,139 @@ mod tests {
         let result = registry.execute_plugin(&metadata.id, "test input");
         assert!(result.is_ok());
     }
-}
 
+    #[test]
+    fn test_usage_accounting() {
+        let mut registry = PluginRegistry::new();
+        let plugin = InternalPlugin::new("Test Plugin".to_string(), "Test Author".to_string());
+        let id = plugin.metadata().id.clone();
+        registry.register_plugin(plugin.metadata().clone());
+        
+        registry.execute_plugin(&id, "input").unwrap();
+        registry.record_execution(&id, 5.0, 4096, false).unwrap();
+        registry.record_execution(&id, 5.0, 1024, true).unwrap();
+        
+        let usage = registry.get_usage(&id).unwrap();
+        assert_eq!(usage.action_count, 3);
+        assert_eq!(usage.error_count, 1);
+        assert_eq!(usage.memory_high_water_bytes, 4096);
+        assert!(usage.cpu_time_ms >= 10.0);
+    }
+
+    #[test]
+    fn test_budget_auto_disable() {
+        let mut registry = PluginRegistry::new();
+        let plugin = InternalPlugin::new("Greedy".to_string(), "Test Author".to_string());
+        let id = plugin.metadata().id.clone();
+        registry.register_plugin(plugin.metadata().clone());
+        registry.set_budget(&id, PluginBudget { max_memory_bytes: 1024, ..PluginBudget::default() });
+        
+        registry.record_execution(&id, 1.0, 2048, true).unwrap();
+        assert!(registry.disabled_reason(&id).unwrap().contains("Memory"));
+        assert!(registry.execute_plugin(&id, "input").is_err());
+        
+        registry.enable_plugin(&id).unwrap();
+        assert!(registry.get_usage(&id).is_none());
+        assert!(registry.execute_plugin(&id, "input").is_ok());
+    }
+
+    fn visualization_plugin(registry: &mut PluginRegistry) -> String {
+        let mut metadata = InternalPlugin::new("Charts".to_string(), "Test Author".to_string()).metadata().clone();
+        metadata.capabilities.push(PluginCapability::Visualization);
+        let id = metadata.id.clone();
+        registry.register_plugin(metadata);
+        id
+    }
+
+    fn section(plugin_id: &str, title: &str, data: ReportSectionData) -> ReportSection {
+        ReportSection {
+            plugin_id: plugin_id.to_string(),
+            title: title.to_string(),
+            data,
+            hint: RenderHint::Table,
+            cadences: vec![ReportCadence::Daily],
+        }
+    }
+
+    #[test]
+    fn test_report_section_registration_limits() {
+        let mut registry = PluginRegistry::new();
+        let plugin = InternalPlugin::new("Plain".to_string(), "Test Author".to_string());
+        registry.register_plugin(plugin.metadata().clone());
+        let text = ReportSectionData::Text { text: "hello".to_string() };
+        assert!(registry.register_report_section(section(&plugin.metadata().id, "Plain", text.clone())).is_err());
+        
+        let id = visualization_plugin(&mut registry);
+        let huge = ReportSectionData::Table { columns: vec!["n".to_string()], rows: vec![vec!["1".to_string()]; 51] };
+        assert!(registry.register_report_section(section(&id, "Huge", huge)).is_err());
+        for title in ["A", "B", "C"] {
+            registry.register_report_section(section(&id, title, text.clone())).unwrap();
+        }
+        registry.register_report_section(section(&id, "A", text.clone())).unwrap(); // Replaces
+        assert!(registry.register_report_section(section(&id, "D", text)).is_err());
+    }
+
+    #[test]
+    fn test_report_sections_rendered_escaped() {
+        let mut registry = PluginRegistry::new();
+        let id = visualization_plugin(&mut registry);
+        registry.register_report_section(section(&id, "Builds", ReportSectionData::Table {
+            columns: vec!["Repo".to_string(), "Status".to_string()],
+            rows: vec![vec!["athenos".to_string(), "<script>[x](http://evil)".to_string()]],
+        })).unwrap();
+        
+        let rendered = registry.render_report_sections(ReportCadence::Daily);
+        assert_eq!(rendered.len(), 1);
+        assert!(rendered[0].markdown.contains("\\<script\\>\\[x\\]\\(http://evil\\)"));
+        assert!(registry.render_report_sections(ReportCadence::Weekly).is_empty());
+        assert_eq!(registry.get_usage(&id).unwrap().action_count, 1);
+    }
+
+    #[test]
+    fn test_signed_registration_and_permissions() {
+        let (signer, _) = signing::PluginSigner::generate("acme").unwrap();
+        let metadata = InternalPlugin::new("Partner".to_string(), "Acme".to_string()).metadata().clone();
+        let signature = signer.sign(&metadata, b"artifact").unwrap();
+        let mut registry = PluginRegistry::new();
+
+        assert!(matches!(registry.register_signed_plugin(metadata.clone(), None, b"artifact"), Err(PluginError::InvalidSignature { .. })));
+        assert!(matches!(
+            registry.register_signed_plugin(metadata.clone(), Some(&signature), b"artifact"),
+            Err(PluginError::UntrustedPublisher { .. })
+        ));
+        registry.trust_publisher("acme", &signer.public_key_hex()).unwrap();
+        registry.register_signed_plugin(metadata.clone(), Some(&signature), b"artifact").unwrap();
+        assert_eq!(registry.publisher(&metadata.id), Some("acme"));
+
+        assert!(registry.check_permission(&metadata.id, PluginCapability::Analysis).is_ok());
+        let denied = registry.check_permission(&metadata.id, PluginCapability::Visualization).unwrap_err();
+        assert_eq!(denied, PluginError::PermissionDenied { plugin_id: metadata.id.clone(), capability: PluginCapability::Visualization });
+        assert!(String::from(denied).starts_with("Permission denied"));
+
+        assert_eq!(registry.revoke_publisher("acme"), 1);
+        assert!(matches!(registry.check_permission(&metadata.id, PluginCapability::Analysis), Err(PluginError::Disabled { .. })));
+    }
+
+    #[test]
+    fn test_signed_plugins_cannot_take_over_registered_ids() {
+        let trusted = signing::TrustedPublishers::new();
+        let mut registry = PluginRegistry::new();
+        registry.set_trusted_publishers(trusted.clone());
+        let (acme, _) = signing::PluginSigner::generate("acme").unwrap();
+        let (rival, _) = signing::PluginSigner::generate("rival").unwrap();
+        trusted.trust("acme", &acme.public_key_hex()).unwrap();
+        trusted.trust("rival", &rival.public_key_hex()).unwrap();
+
+        let first_party = InternalPlugin::new("DevTools".to_string(), "Athenos".to_string()).metadata().clone();
+        registry.register_plugin(first_party.clone());
+        let forged = acme.sign(&first_party, b"artifact").unwrap();
+        assert!(registry.register_signed_plugin(first_party.clone(), Some(&forged), b"artifact").is_err());
+        assert_eq!(registry.publisher(&first_party.id), None);
+
+        let partner = InternalPlugin::new("Partner".to_string(), "Acme".to_string()).metadata().clone();
+        registry.register_signed_plugin(partner.clone(), Some(&acme.sign(&partner, b"v1").unwrap()), b"v1").unwrap();
+        assert!(registry.register_signed_plugin(partner.clone(), Some(&rival.sign(&partner, b"v2").unwrap()), b"v2").is_err());
+        registry.register_signed_plugin(partner.clone(), Some(&acme.sign(&partner, b"v2").unwrap()), b"v2").unwrap();
+        assert_eq!(registry.publisher(&partner.id), Some("acme"));
+    }
+}
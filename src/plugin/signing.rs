@@ -0,0 +1,291 @@
+/// Phase: C | Step: 9 | Source: Athenos_AI_Strategy.md#L128
+/// Plugin Signing
+/// Ed25519 signatures over plugin metadata and artifacts, verified against trusted publisher keys
+
+use crate::plugin::{PluginCapability, PluginMetadata};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// Domain separator so plugin signatures cannot be replayed as other signed payloads
+const SIGNING_CONTEXT: &str = "athenos-plugin-signature-v1";
+
+/// Ed25519 public key length in bytes
+const PUBLIC_KEY_LEN: usize = 32;
+
+/// Plugin authorization failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    NotFound(String),
+    Disabled { plugin_id: String, reason: String },
+    PermissionDenied { plugin_id: String, capability: PluginCapability },
+    UntrustedPublisher { plugin_id: String, publisher_id: String },
+    InvalidSignature { plugin_id: String, reason: String },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::NotFound(plugin_id) => write!(f, "Plugin {} not found", plugin_id),
+            PluginError::Disabled { plugin_id, reason } => write!(f, "Plugin {} is disabled: {}", plugin_id, reason),
+            PluginError::PermissionDenied { plugin_id, capability } => {
+                write!(f, "Permission denied: plugin {} did not declare the {:?} capability", plugin_id, capability)
+            }
+            PluginError::UntrustedPublisher { plugin_id, publisher_id } => {
+                write!(f, "Plugin {} is signed by untrusted publisher {}", plugin_id, publisher_id)
+            }
+            PluginError::InvalidSignature { plugin_id, reason } => write!(f, "Invalid signature for plugin {}: {}", plugin_id, reason),
+        }
+    }
+}
+
+impl From<PluginError> for String {
+    fn from(error: PluginError) -> Self {
+        error.to_string()
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, String> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return Err("Malformed hex".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| "Malformed hex".to_string()))
+        .collect()
+}
+
+/// SHA-256 of a plugin artifact (WASM module), hex encoded
+pub fn artifact_digest(artifact: &[u8]) -> String {
+    to_hex(ring::digest::digest(&ring::digest::SHA256, artifact).as_ref())
+}
+
+/// Detached signature shipped alongside a plugin
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PluginSignature {
+    pub publisher_id: String,
+    pub artifact_sha256: String, // Digest of the signed artifact; covers the code, not just the listing
+    pub signature: String,       // Ed25519, hex
+}
+
+/// Bytes covered by the signature: context, publisher, every metadata field and the artifact digest
+/// Each field is length-prefixed in a fixed order, so the encoding cannot shift with serializer changes
+fn signing_payload(metadata: &PluginMetadata, publisher_id: &str, artifact_sha256: &str) -> Result<Vec<u8>, String> {
+    let capabilities: Vec<&str> = metadata
+        .capabilities
+        .iter()
+        .map(|capability| match capability {
+            PluginCapability::Observation => "observation",
+            PluginCapability::Intervention => "intervention",
+            PluginCapability::Analysis => "analysis",
+            PluginCapability::Visualization => "visualization",
+        })
+        .collect();
+    let capabilities = capabilities.join(",");
+    let fields = [
+        SIGNING_CONTEXT,
+        publisher_id,
+        &metadata.id,
+        &metadata.name,
+        &metadata.version,
+        &metadata.author,
+        &capabilities,
+        &metadata.description,
+        artifact_sha256,
+    ];
+    let mut payload = Vec::new();
+    for field in fields {
+        payload.extend_from_slice(format!("{}:", field.len()).as_bytes());
+        payload.extend_from_slice(field.as_bytes());
+        payload.push(b'\n');
+    }
+    Ok(payload)
+}
+
+/// Publisher signing key (held by plugin publishers, not the daemon)
+pub struct PluginSigner {
+    publisher_id: String,
+    key_pair: Ed25519KeyPair,
+}
+
+impl PluginSigner {
+    /// Generate a key; returns the signer and its PKCS#8 encoding for safekeeping
+    pub fn generate(publisher_id: &str) -> Result<(Self, Vec<u8>), String> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).map_err(|_| "Failed to generate signing key".to_string())?;
+        let signer = Self::from_pkcs8(publisher_id, pkcs8.as_ref())?;
+        Ok((signer, pkcs8.as_ref().to_vec()))
+    }
+
+    /// Load a signer from a PKCS#8 document
+    pub fn from_pkcs8(publisher_id: &str, pkcs8: &[u8]) -> Result<Self, String> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8).map_err(|_| "Invalid PKCS#8 signing key".to_string())?;
+        Ok(Self { publisher_id: publisher_id.to_string(), key_pair })
+    }
+
+    /// Public key to register as a trusted publisher, hex
+    pub fn public_key_hex(&self) -> String {
+        to_hex(self.key_pair.public_key().as_ref())
+    }
+
+    /// Sign plugin metadata together with its artifact
+    pub fn sign(&self, metadata: &PluginMetadata, artifact: &[u8]) -> Result<PluginSignature, String> {
+        info!("PluginSigner::sign: {} signing {}", self.publisher_id, metadata.id);
+        let artifact_sha256 = artifact_digest(artifact);
+        let payload = signing_payload(metadata, &self.publisher_id, &artifact_sha256)?;
+        Ok(PluginSignature {
+            publisher_id: self.publisher_id.clone(),
+            artifact_sha256,
+            signature: to_hex(self.key_pair.sign(&payload).as_ref()),
+        })
+    }
+}
+
+/// Publisher keys the daemon accepts plugin signatures from
+/// Clones share one key set, so the plugin registry and marketplace trust and revoke the same publishers
+#[derive(Debug, Clone, Default)]
+pub struct TrustedPublishers {
+    keys: Arc<RwLock<HashMap<String, String>>>, // publisher_id -> Ed25519 public key, hex
+}
+
+impl TrustedPublishers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust publishers listed in a JSON file of publisher_id -> public key (hex); a missing file trusts none
+    pub fn trust_from_file(&self, path: impl AsRef<std::path::Path>) -> Result<usize, String> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(0);
+        }
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let keys: HashMap<String, String> = serde_json::from_str(&json).map_err(|e| format!("Invalid publisher list {}: {}", path.display(), e))?;
+        for (publisher_id, public_key_hex) in &keys {
+            self.trust(publisher_id, public_key_hex)?;
+        }
+        Ok(keys.len())
+    }
+
+    /// Trust a publisher's public key (replaces any previous key)
+    pub fn trust(&self, publisher_id: &str, public_key_hex: &str) -> Result<(), String> {
+        info!("TrustedPublishers::trust: Trusting publisher {}", publisher_id);
+        let key = from_hex(public_key_hex).map_err(|e| format!("Invalid public key for {}: {}", publisher_id, e))?;
+        if key.len() != PUBLIC_KEY_LEN {
+            return Err(format!("Invalid public key for {}: expected {} bytes, got {}", publisher_id, PUBLIC_KEY_LEN, key.len()));
+        }
+        self.keys.write().unwrap().insert(publisher_id.to_string(), public_key_hex.to_lowercase());
+        Ok(())
+    }
+
+    /// Stop trusting a publisher; returns whether it was trusted
+    pub fn revoke(&self, publisher_id: &str) -> bool {
+        info!("TrustedPublishers::revoke: Revoking publisher {}", publisher_id);
+        self.keys.write().unwrap().remove(publisher_id).is_some()
+    }
+
+    pub fn is_trusted(&self, publisher_id: &str) -> bool {
+        self.keys.read().unwrap().contains_key(publisher_id)
+    }
+
+    /// Verify a signature over metadata and the artifact digest it names
+    pub fn verify(&self, metadata: &PluginMetadata, signature: &PluginSignature) -> Result<(), PluginError> {
+        let invalid = |reason: &str| PluginError::InvalidSignature { plugin_id: metadata.id.clone(), reason: reason.to_string() };
+        let key = self.keys.read().unwrap().get(&signature.publisher_id).cloned().ok_or_else(|| PluginError::UntrustedPublisher {
+            plugin_id: metadata.id.clone(),
+            publisher_id: signature.publisher_id.clone(),
+        })?;
+        let key = from_hex(&key).map_err(|e| invalid(&e))?;
+        let signature_bytes = from_hex(&signature.signature).map_err(|e| invalid(&e))?;
+        let payload = signing_payload(metadata, &signature.publisher_id, &signature.artifact_sha256).map_err(|e| invalid(&e))?;
+        UnparsedPublicKey::new(&ED25519, key)
+            .verify(&payload, &signature_bytes)
+            .map_err(|_| invalid("signature does not match metadata"))
+    }
+
+    /// Verify the signature and that `artifact` is the signed artifact
+    pub fn verify_artifact(&self, metadata: &PluginMetadata, signature: &PluginSignature, artifact: &[u8]) -> Result<(), PluginError> {
+        self.verify(metadata, signature)?;
+        if artifact_digest(artifact) != signature.artifact_sha256 {
+            return Err(PluginError::InvalidSignature {
+                plugin_id: metadata.id.clone(),
+                reason: "artifact does not match signed digest".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata() -> PluginMetadata {
+        PluginMetadata {
+            id: "focus_guard".to_string(),
+            name: "Focus Guard".to_string(),
+            version: "1.0.0".to_string(),
+            author: "Acme".to_string(),
+            capabilities: vec![PluginCapability::Observation],
+            description: "Flags focus breaks".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let (signer, pkcs8) = PluginSigner::generate("acme").unwrap();
+        let trusted = TrustedPublishers::new();
+        trusted.trust("acme", &signer.public_key_hex()).unwrap();
+
+        let signature = signer.sign(&metadata(), b"\0asm module").unwrap();
+        assert!(trusted.verify_artifact(&metadata(), &signature, b"\0asm module").is_ok());
+
+        // Reloaded key produces signatures the same public key accepts
+        let reloaded = PluginSigner::from_pkcs8("acme", &pkcs8).unwrap();
+        assert_eq!(reloaded.public_key_hex(), signer.public_key_hex());
+    }
+
+    #[test]
+    fn test_tampering_and_untrusted_publishers_rejected() {
+        let (signer, _) = PluginSigner::generate("acme").unwrap();
+        let trusted = TrustedPublishers::new();
+        let signature = signer.sign(&metadata(), b"module").unwrap();
+        assert!(matches!(trusted.verify(&metadata(), &signature), Err(PluginError::UntrustedPublisher { .. })));
+
+        trusted.trust("acme", &signer.public_key_hex()).unwrap();
+        let mut escalated = metadata();
+        escalated.capabilities.push(PluginCapability::Intervention);
+        assert!(matches!(trusted.verify(&escalated, &signature), Err(PluginError::InvalidSignature { .. })));
+        assert!(trusted.verify_artifact(&metadata(), &signature, b"patched module").is_err());
+
+        assert!(trusted.revoke("acme"));
+        assert!(trusted.verify(&metadata(), &signature).is_err());
+        assert!(trusted.trust("acme", "abcd").is_err());
+    }
+
+    #[test]
+    fn test_payload_is_canonical_and_clones_share_keys() {
+        // Moving text between adjacent fields changes the signed bytes
+        let mut shifted = metadata();
+        shifted.name = "Focus Guar".to_string();
+        shifted.version = "d1.0.0".to_string();
+        assert_ne!(signing_payload(&metadata(), "acme", "00").unwrap(), signing_payload(&shifted, "acme", "00").unwrap());
+        assert!(String::from_utf8(signing_payload(&metadata(), "acme", "00").unwrap()).unwrap().contains("\n11:observation\n"));
+
+        let (signer, _) = PluginSigner::generate("acme").unwrap();
+        let registry_keys = TrustedPublishers::new();
+        let marketplace_keys = registry_keys.clone();
+        registry_keys.trust("acme", &signer.public_key_hex()).unwrap();
+        let signature = signer.sign(&metadata(), b"module").unwrap();
+        assert!(marketplace_keys.verify(&metadata(), &signature).is_ok());
+        registry_keys.revoke("acme");
+        assert!(!marketplace_keys.is_trusted("acme"));
+    }
+}
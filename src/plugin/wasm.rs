@@ -0,0 +1,647 @@
+/// Phase: C | Step: 9 | Source: Athenos_AI_Strategy.md#L128
+/// WASM Plugin Runtime
+/// Runs plugins as sandboxed WASM modules with a capability-gated host API, resource limits and per-plugin consent
+
+use crate::types::*;
+use crate::consent::MicroConsentManager;
+use crate::event_schema::{self, ConsentScope, EventData, EventEnvelope, EventProvenance};
+use crate::plugin::signing::PluginSignature;
+use crate::plugin::{PluginCapability, PluginMetadata, PluginRegistry};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// Host import module name plugins link against
+pub const HOST_MODULE: &str = "athenos";
+
+/// Host call results returned to the guest
+pub const HOST_OK: i32 = 0;
+pub const HOST_DENIED: i32 = -1;
+pub const HOST_INVALID: i32 = -2;
+
+/// Host API a plugin may call, each behind its own consent
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HostCapability {
+    ReadObservations,
+    EmitInterventions,
+}
+
+impl HostCapability {
+    fn name(&self) -> &'static str {
+        match self {
+            HostCapability::ReadObservations => "read_observations",
+            HostCapability::EmitInterventions => "emit_interventions",
+        }
+    }
+
+    /// Plugin capability the metadata must declare
+    fn required_capability(&self) -> PluginCapability {
+        match self {
+            HostCapability::ReadObservations => PluginCapability::Observation,
+            HostCapability::EmitInterventions => PluginCapability::Intervention,
+        }
+    }
+
+    /// Micro-consent capability granting this host API to one plugin
+    pub fn consent_scope(&self, plugin_id: &str) -> String {
+        format!("plugin:{}:{}", plugin_id, self.name())
+    }
+}
+
+/// Per-run sandbox limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmLimits {
+    pub fuel: u64,                      // Instruction budget per run
+    pub max_memory_bytes: usize,        // Linear memory cap
+    pub max_input_bytes: usize,         // Serialized observations handed to the guest
+    pub max_interventions: usize,       // Per run
+    pub max_intervention_bytes: usize,  // Per emitted intervention
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 10_000_000,
+            max_memory_bytes: 16 * 1024 * 1024,
+            max_input_bytes: 1024 * 1024,
+            max_interventions: 10,
+            max_intervention_bytes: 4 * 1024,
+        }
+    }
+}
+
+/// Plugin package: metadata plus the host APIs it asks for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmPluginManifest {
+    pub metadata: PluginMetadata,
+    pub host_capabilities: Vec<HostCapability>,
+    #[serde(default)]
+    pub signature: Option<PluginSignature>, // Publisher signature over the metadata and module
+    #[serde(default)]
+    pub schema_versions: Vec<u32>, // Observation payload schemas the plugin accepts; empty = legacy v1
+}
+
+/// Intervention proposed by a plugin; routed through the normal approval flow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginIntervention {
+    #[serde(default)]
+    pub plugin_id: String, // Set by the host; a guest-supplied value is overwritten
+    pub observation_id: String,
+    pub action: Action,
+    pub rationale: String,
+}
+
+/// Outcome of one plugin run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmRunReport {
+    pub plugin_id: String,
+    pub exit_code: i32,
+    pub interventions: Vec<PluginIntervention>,
+    pub denied_calls: Vec<String>,
+    pub fuel_consumed: u64,
+    pub memory_bytes: u64,
+    pub cpu_time_ms: f64,
+}
+
+/// Guest-visible host state for one run
+/// Every host import goes through these methods, so gating is enforced whatever the engine
+#[derive(Debug)]
+pub struct HostState {
+    plugin_id: String,
+    granted: HashSet<HostCapability>,
+    observations: Vec<Observation>,
+    limits: WasmLimits,
+    schema_version: u32, // Negotiated payload schema for read_observations
+    interventions: Vec<PluginIntervention>,
+    denied_calls: Vec<String>,
+}
+
+impl HostState {
+    pub fn new(plugin_id: &str, granted: HashSet<HostCapability>, observations: Vec<Observation>, limits: WasmLimits) -> Self {
+        Self {
+            plugin_id: plugin_id.to_string(),
+            granted,
+            observations,
+            limits,
+            schema_version: event_schema::SCHEMA_V1,
+            interventions: Vec::new(),
+            denied_calls: Vec::new(),
+        }
+    }
+
+    /// Serve observations in a negotiated schema version instead of the legacy format
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = schema_version;
+        self
+    }
+
+    fn check(&mut self, capability: HostCapability) -> Result<(), i32> {
+        if self.granted.contains(&capability) {
+            Ok(())
+        } else {
+            info!("HostState::check: {} denied {}", self.plugin_id, capability.name());
+            self.denied_calls.push(capability.name().to_string());
+            Err(HOST_DENIED)
+        }
+    }
+
+    /// `read_observations` import: observations as JSON (a v2+ envelope, or the raw list for legacy plugins)
+    pub fn read_observations(&mut self) -> Result<Vec<u8>, i32> {
+        self.check(HostCapability::ReadObservations)?;
+        let json = if self.schema_version == event_schema::SCHEMA_V1 {
+            serde_json::to_vec(&self.observations)
+        } else {
+            let mut capabilities: Vec<String> = self.granted.iter().map(|c| c.consent_scope(&self.plugin_id)).collect();
+            capabilities.sort();
+            let consent = ConsentScope { recipient: self.plugin_id.clone(), capabilities };
+            let timestamp = self.observations.iter().map(|o| o.timestamp).max().unwrap_or(0);
+            let envelope = EventEnvelope::new(EventData::observation_batch(&self.observations), timestamp, EventProvenance::new("plugin_host"), consent);
+            serde_json::to_vec(&envelope)
+        }
+        .map_err(|_| HOST_INVALID)?;
+        if json.len() > self.limits.max_input_bytes {
+            return Err(HOST_INVALID);
+        }
+        Ok(json)
+    }
+
+    /// `emit_intervention` import: one intervention as JSON, for an observation the plugin was shown
+    pub fn emit_intervention(&mut self, bytes: &[u8]) -> i32 {
+        if let Err(code) = self.check(HostCapability::EmitInterventions) {
+            return code;
+        }
+        if bytes.len() > self.limits.max_intervention_bytes || self.interventions.len() >= self.limits.max_interventions {
+            return HOST_INVALID;
+        }
+        let mut intervention: PluginIntervention = match serde_json::from_slice(bytes) {
+            Ok(intervention) => intervention,
+            Err(_) => return HOST_INVALID,
+        };
+        if !self.observations.iter().any(|o| o.id == intervention.observation_id) {
+            return HOST_INVALID;
+        }
+        intervention.plugin_id = self.plugin_id.clone();
+        self.interventions.push(intervention);
+        HOST_OK
+    }
+}
+
+/// Binary header check used when modules are not compiled at load time
+#[cfg(not(feature = "wasm-plugins"))]
+fn check_header(plugin_id: &str, wasm: &[u8]) -> Result<(), String> {
+    if wasm.starts_with(b"\0asm") {
+        Ok(())
+    } else {
+        Err(format!("Invalid WASM module {}: missing header", plugin_id))
+    }
+}
+
+/// Loaded plugin module
+struct LoadedPlugin {
+    manifest: WasmPluginManifest,
+    #[cfg(feature = "wasm-plugins")]
+    module: wasmtime::Module,
+}
+
+/// WASM plugin runtime
+/// Source: Athenos_AI_Strategy.md#L128
+pub struct WasmPluginRuntime {
+    limits: WasmLimits,
+    plugins: HashMap<String, LoadedPlugin>,
+    #[cfg(feature = "wasm-plugins")]
+    engine: wasmtime::Engine,
+}
+
+impl WasmPluginRuntime {
+    /// Create runtime applying `limits` to every run
+    pub fn new(limits: WasmLimits) -> Result<Self, String> {
+        info!("WasmPluginRuntime::new: Creating WASM plugin runtime");
+        Ok(Self {
+            limits,
+            plugins: HashMap::new(),
+            #[cfg(feature = "wasm-plugins")]
+            engine: engine::new_engine()?,
+        })
+    }
+
+    /// Validate, compile and register a plugin module
+    /// The registry verifies the publisher signature against the module bytes
+    pub fn load(&mut self, manifest: WasmPluginManifest, wasm: &[u8], registry: &mut PluginRegistry) -> Result<(), String> {
+        let plugin_id = manifest.metadata.id.clone();
+        info!("WasmPluginRuntime::load: Loading plugin {} ({} bytes)", plugin_id, wasm.len());
+        event_schema::negotiate_schema(&manifest.schema_versions).map_err(|e| format!("Plugin {}: {}", plugin_id, e))?;
+        for capability in &manifest.host_capabilities {
+            if !manifest.metadata.capabilities.contains(&capability.required_capability()) {
+                return Err(format!(
+                    "Plugin {} requests {} without declaring the {:?} capability",
+                    plugin_id,
+                    capability.name(),
+                    capability.required_capability()
+                ));
+            }
+        }
+        #[cfg(feature = "wasm-plugins")]
+        let module = wasmtime::Module::new(&self.engine, wasm).map_err(|e| format!("Invalid WASM module {}: {}", plugin_id, e))?;
+        #[cfg(not(feature = "wasm-plugins"))]
+        check_header(&plugin_id, wasm)?;
+
+        registry.register_signed_plugin(manifest.metadata.clone(), manifest.signature.as_ref(), wasm)?;
+        self.plugins.insert(plugin_id, LoadedPlugin {
+            manifest,
+            #[cfg(feature = "wasm-plugins")]
+            module,
+        });
+        Ok(())
+    }
+
+    /// Load a plugin module from disk
+    pub fn load_file(&mut self, manifest: WasmPluginManifest, path: impl AsRef<Path>, registry: &mut PluginRegistry) -> Result<(), String> {
+        let path = path.as_ref();
+        let wasm = std::fs::read(path).map_err(|e| format!("Failed to read plugin {}: {}", path.display(), e))?;
+        self.load(manifest, &wasm, registry)
+    }
+
+    /// Load every `<name>.json` manifest with a sibling `<name>.wasm` module in `dir`
+    /// Returns the loaded plugin ids; a missing directory loads nothing, and a plugin that fails to load is skipped
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>, registry: &mut PluginRegistry) -> Result<Vec<String>, String> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let mut loaded = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match self.load_manifest_file(&path, registry) {
+                Ok(plugin_id) => loaded.push(plugin_id),
+                Err(e) => warn!("WasmPluginRuntime::load_dir: Skipping {}: {}", path.display(), e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    fn load_manifest_file(&mut self, path: &Path, registry: &mut PluginRegistry) -> Result<String, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let manifest: WasmPluginManifest =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid manifest {}: {}", path.display(), e))?;
+        let plugin_id = manifest.metadata.id.clone();
+        self.load_file(manifest, path.with_extension("wasm"), registry)?;
+        Ok(plugin_id)
+    }
+
+    /// Loaded plugin ids
+    pub fn plugin_ids(&self) -> Vec<String> {
+        self.plugins.keys().cloned().collect()
+    }
+
+    /// Host APIs the plugin requested and the user consented to
+    pub fn granted_capabilities(&self, plugin_id: &str, consent: &MicroConsentManager) -> HashSet<HostCapability> {
+        self.plugins
+            .get(plugin_id)
+            .map(|plugin| {
+                plugin
+                    .manifest
+                    .host_capabilities
+                    .iter()
+                    .filter(|capability| consent.has_consent(&capability.consent_scope(plugin_id)))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Run a plugin over observations; usage is accounted against its registry budget
+    pub fn run(
+        &self,
+        plugin_id: &str,
+        observations: &[Observation],
+        consent: &MicroConsentManager,
+        registry: &mut PluginRegistry,
+    ) -> Result<WasmRunReport, String> {
+        info!("WasmPluginRuntime::run: Running plugin {} over {} observations", plugin_id, observations.len());
+        let plugin = self.plugins.get(plugin_id).ok_or("Plugin not loaded")?;
+        if let Some(reason) = registry.disabled_reason(plugin_id) {
+            return Err(format!("Plugin {} is disabled: {}", plugin_id, reason));
+        }
+        // Host APIs need both user consent and the capability declared in the registered metadata
+        let granted = self
+            .granted_capabilities(plugin_id, consent)
+            .into_iter()
+            .filter(|capability| registry.check_permission(plugin_id, capability.required_capability()).is_ok())
+            .collect();
+        let schema_version = event_schema::negotiate_schema(&plugin.manifest.schema_versions)?;
+        let host = HostState::new(plugin_id, granted, observations.to_vec(), self.limits.clone()).with_schema_version(schema_version);
+
+        let started = Instant::now();
+        let result = self.execute(plugin, host);
+        let cpu_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        match result {
+            Ok(mut report) => {
+                report.cpu_time_ms = cpu_time_ms;
+                registry.record_execution(plugin_id, cpu_time_ms, report.memory_bytes, report.exit_code == 0)?;
+                Ok(report)
+            }
+            Err(e) => {
+                registry.record_execution(plugin_id, cpu_time_ms, 0, false)?;
+                Err(e)
+            }
+        }
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    fn execute(&self, plugin: &LoadedPlugin, host: HostState) -> Result<WasmRunReport, String> {
+        engine::execute(&self.engine, &plugin.module, host)
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    fn execute(&self, plugin: &LoadedPlugin, _host: HostState) -> Result<WasmRunReport, String> {
+        Err(format!("Cannot run {}: built without the wasm-plugins feature", plugin.manifest.metadata.id))
+    }
+}
+
+#[cfg(feature = "wasm-plugins")]
+mod engine {
+    use super::*;
+    use wasmtime::{Caller, Config, Engine, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+    struct StoreData {
+        host: HostState,
+        limits: StoreLimits,
+    }
+
+    pub(super) fn new_engine() -> Result<Engine, String> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        Engine::new(&config).map_err(|e| format!("Failed to create WASM engine: {}", e))
+    }
+
+    fn guest_memory(caller: &mut Caller<'_, StoreData>) -> Option<Memory> {
+        caller.get_export("memory").and_then(|export| export.into_memory())
+    }
+
+    /// Guest ABI: exports `memory` and `run() -> i32`; imports from HOST_MODULE:
+    /// `read_observations(ptr, cap) -> len | error`, `emit_intervention(ptr, len) -> status`
+    pub(super) fn execute(engine: &Engine, module: &Module, host: HostState) -> Result<WasmRunReport, String> {
+        let fuel = host.limits.fuel;
+        let limits = StoreLimitsBuilder::new().memory_size(host.limits.max_memory_bytes).instances(1).build();
+        let mut store = Store::new(engine, StoreData { host, limits });
+        store.limiter(|data| &mut data.limits);
+        store.set_fuel(fuel).map_err(|e| e.to_string())?;
+
+        let mut linker: Linker<StoreData> = Linker::new(engine);
+        linker
+            .func_wrap(HOST_MODULE, "read_observations", |mut caller: Caller<'_, StoreData>, ptr: i32, cap: i32| -> i32 {
+                // Guest pointers and lengths are unsigned 32-bit values carried in i32
+                let (ptr, cap) = (ptr as u32 as usize, cap as u32 as usize);
+                let json = match caller.data_mut().host.read_observations() {
+                    Ok(json) => json,
+                    Err(code) => return code,
+                };
+                if json.len() > cap {
+                    return HOST_INVALID;
+                }
+                match guest_memory(&mut caller) {
+                    Some(memory) if memory.write(&mut caller, ptr, &json).is_ok() => json.len() as i32,
+                    _ => HOST_INVALID,
+                }
+            })
+            .map_err(|e| e.to_string())?;
+        linker
+            .func_wrap(HOST_MODULE, "emit_intervention", |mut caller: Caller<'_, StoreData>, ptr: i32, len: i32| -> i32 {
+                let (ptr, len) = (ptr as u32 as usize, len as u32 as usize);
+                // Size is checked before the copy buffer is allocated
+                if len > caller.data().host.limits.max_intervention_bytes {
+                    return HOST_INVALID;
+                }
+                let mut bytes = vec![0u8; len];
+                let memory = match guest_memory(&mut caller) {
+                    Some(memory) => memory,
+                    None => return HOST_INVALID,
+                };
+                if memory.read(&caller, ptr, &mut bytes).is_err() {
+                    return HOST_INVALID;
+                }
+                caller.data_mut().host.emit_intervention(&bytes)
+            })
+            .map_err(|e| e.to_string())?;
+
+        let instance = linker.instantiate(&mut store, module).map_err(|e| format!("Failed to instantiate plugin: {}", e))?;
+        let run = instance.get_typed_func::<(), i32>(&mut store, "run").map_err(|e| format!("Plugin has no run export: {}", e))?;
+        let exit_code = run.call(&mut store, ()).map_err(|e| format!("Plugin trapped: {}", e))?;
+
+        let memory_bytes = instance.get_memory(&mut store, "memory").map(|m| m.data_size(&store) as u64).unwrap_or(0);
+        let fuel_consumed = fuel - store.get_fuel().unwrap_or(0);
+        let data = store.into_data();
+        Ok(WasmRunReport {
+            plugin_id: data.host.plugin_id,
+            exit_code,
+            interventions: data.host.interventions,
+            denied_calls: data.host.denied_calls,
+            fuel_consumed,
+            memory_bytes,
+            cpu_time_ms: 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(capabilities: Vec<PluginCapability>, host_capabilities: Vec<HostCapability>) -> WasmPluginManifest {
+        WasmPluginManifest {
+            metadata: PluginMetadata {
+                id: "focus_coach".to_string(),
+                name: "Focus Coach".to_string(),
+                version: "0.1.0".to_string(),
+                author: "Partner".to_string(),
+                capabilities,
+                description: "Suggests focus blocks".to_string(),
+            },
+            host_capabilities,
+            signature: None,
+            schema_versions: Vec::new(),
+        }
+    }
+
+    fn observation(id: &str) -> Observation {
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Slack".to_string(), "IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::MoodIntervention,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Focus".to_string(),
+                confidence: Confidence::Medium,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 0,
+            project: None,
+        }
+    }
+
+    fn intervention_json(observation_id: &str) -> Vec<u8> {
+        serde_json::to_vec(&PluginIntervention {
+            plugin_id: "spoofed".to_string(),
+            observation_id: observation_id.to_string(),
+            action: observation(observation_id).action,
+            rationale: "Fragmented morning".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_host_api_is_capability_gated() {
+        let granted: HashSet<HostCapability> = [HostCapability::EmitInterventions].into_iter().collect();
+        let mut host = HostState::new("focus_coach", granted, vec![observation("obs_1")], WasmLimits { max_interventions: 1, ..WasmLimits::default() });
+
+        assert_eq!(host.read_observations().unwrap_err(), HOST_DENIED);
+        assert_eq!(host.emit_intervention(&intervention_json("unknown")), HOST_INVALID);
+        assert_eq!(host.emit_intervention(b"not json"), HOST_INVALID);
+        assert_eq!(host.emit_intervention(&intervention_json("obs_1")), HOST_OK);
+        assert_eq!(host.emit_intervention(&intervention_json("obs_1")), HOST_INVALID); // Over the per-run limit
+        assert_eq!(host.interventions[0].plugin_id, "focus_coach");
+        assert_eq!(host.denied_calls, vec!["read_observations".to_string()]);
+    }
+
+    #[test]
+    fn test_observations_served_in_negotiated_schema() {
+        let granted: HashSet<HostCapability> = [HostCapability::ReadObservations].into_iter().collect();
+        let mut legacy = HostState::new("focus_coach", granted.clone(), vec![observation("obs_1")], WasmLimits::default());
+        let raw: Vec<Observation> = serde_json::from_slice(&legacy.read_observations().unwrap()).unwrap();
+        assert_eq!(raw[0].id, "obs_1");
+
+        let mut current = HostState::new("focus_coach", granted, vec![observation("obs_1")], WasmLimits::default()).with_schema_version(event_schema::SCHEMA_V2);
+        let envelope: EventEnvelope = serde_json::from_slice(&current.read_observations().unwrap()).unwrap();
+        assert_eq!(envelope.consent.capabilities, vec!["plugin:focus_coach:read_observations".to_string()]);
+        assert!(matches!(envelope.data, EventData::ObservationBatch { ref observations } if observations[0].intent == "mood_intervention"));
+    }
+
+    #[test]
+    fn test_load_requires_declared_capabilities_and_consent() {
+        let module = b"\0asm\x01\0\0\0";
+        let mut registry = PluginRegistry::new();
+        let mut runtime = WasmPluginRuntime::new(WasmLimits::default()).unwrap();
+        let undeclared = manifest(vec![PluginCapability::Observation], vec![HostCapability::EmitInterventions]);
+        assert!(runtime.load(undeclared, module, &mut registry).unwrap_err().contains("Intervention"));
+
+        let both = vec![HostCapability::ReadObservations, HostCapability::EmitInterventions];
+        let mut signed = manifest(vec![PluginCapability::Observation, PluginCapability::Intervention], both);
+        assert!(runtime.load(signed.clone(), module, &mut registry).unwrap_err().contains("unsigned"));
+        let (signer, _) = crate::plugin::signing::PluginSigner::generate("partner").unwrap();
+        registry.trust_publisher("partner", &signer.public_key_hex()).unwrap();
+        signed.signature = Some(signer.sign(&signed.metadata, module).unwrap());
+        assert!(runtime.load(signed.clone(), b"\0asm\x01\0\0\x01", &mut registry).unwrap_err().contains("digest"));
+        runtime.load(signed, module, &mut registry).unwrap();
+        assert!(registry.get_plugin_metadata("focus_coach").is_some());
+
+        let mut consent = MicroConsentManager::new();
+        assert!(runtime.granted_capabilities("focus_coach", &consent).is_empty());
+        let scope = HostCapability::ReadObservations.consent_scope("focus_coach");
+        consent.request_consent(scope.clone(), "Let Focus Coach read observations".to_string());
+        consent.grant_consent(&scope).unwrap();
+        let granted = runtime.granted_capabilities("focus_coach", &consent);
+        assert_eq!(granted.into_iter().collect::<Vec<_>>(), vec![HostCapability::ReadObservations]);
+    }
+
+    #[test]
+    fn test_load_dir_skips_bad_manifests() {
+        let module = b"\0asm\x01\0\0\0";
+        let dir = std::env::temp_dir().join(format!("athenos_wasm_plugins_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut registry = PluginRegistry::new();
+        let (signer, _) = crate::plugin::signing::PluginSigner::generate("partner").unwrap();
+        registry.trust_publisher("partner", &signer.public_key_hex()).unwrap();
+        let mut good = manifest(vec![PluginCapability::Observation], vec![HostCapability::ReadObservations]);
+        good.signature = Some(signer.sign(&good.metadata, module).unwrap());
+        std::fs::write(dir.join("a_broken.json"), "{ not a manifest").unwrap();
+        std::fs::write(dir.join("b_missing_module.json"), serde_json::to_string(&good).unwrap()).unwrap();
+        std::fs::write(dir.join("c_good.json"), serde_json::to_string(&good).unwrap()).unwrap();
+        std::fs::write(dir.join("c_good.wasm"), module).unwrap();
+
+        let mut runtime = WasmPluginRuntime::new(WasmLimits::default()).unwrap();
+        let loaded = runtime.load_dir(&dir, &mut registry).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded, vec!["focus_coach".to_string()]);
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    #[test]
+    fn test_wasm_plugin_emits_intervention_within_limits() {
+        // Reads observations into memory, then emits the intervention stored in its data segment
+        let payload = String::from_utf8(intervention_json("obs_1")).unwrap().replace('\\', "\\\\").replace('"', "\\\"");
+        let wat = format!(
+            r#"(module
+                (import "athenos" "read_observations" (func $read (param i32 i32) (result i32)))
+                (import "athenos" "emit_intervention" (func $emit (param i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{payload}")
+                (func (export "run") (result i32)
+                    (drop (call $read (i32.const 4096) (i32.const 60000)))
+                    (call $emit (i32.const 0) (i32.const {len}))))"#,
+            payload = payload,
+            len = intervention_json("obs_1").len()
+        );
+        let mut registry = PluginRegistry::new();
+        registry.set_require_signatures(false);
+        let mut runtime = WasmPluginRuntime::new(WasmLimits::default()).unwrap();
+        let both = vec![HostCapability::ReadObservations, HostCapability::EmitInterventions];
+        runtime
+            .load(manifest(vec![PluginCapability::Observation, PluginCapability::Intervention], both), wat.as_bytes(), &mut registry)
+            .unwrap();
+
+        let mut consent = MicroConsentManager::new();
+        let scope = HostCapability::EmitInterventions.consent_scope("focus_coach");
+        consent.request_consent(scope.clone(), "Let Focus Coach suggest interventions".to_string());
+        consent.grant_consent(&scope).unwrap();
+
+        let report = runtime.run("focus_coach", &[observation("obs_1")], &consent, &mut registry).unwrap();
+        assert_eq!(report.exit_code, HOST_OK);
+        assert_eq!(report.interventions.len(), 1);
+        assert_eq!(report.denied_calls, vec!["read_observations".to_string()]);
+        assert!(report.fuel_consumed > 0);
+        assert_eq!(registry.get_usage("focus_coach").unwrap().action_count, 1);
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    #[test]
+    fn test_wasm_plugin_oversized_intervention_rejected() {
+        // -1 is 0xFFFFFFFF as an unsigned length; both calls exceed max_intervention_bytes
+        let wat = format!(
+            r#"(module
+                (import "athenos" "emit_intervention" (func $emit (param i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (func (export "run") (result i32)
+                    (i32.add
+                        (call $emit (i32.const 0) (i32.const -1))
+                        (call $emit (i32.const 0) (i32.const {len})))))"#,
+            len = WasmLimits::default().max_intervention_bytes + 1
+        );
+        let mut registry = PluginRegistry::new();
+        registry.set_require_signatures(false);
+        let mut runtime = WasmPluginRuntime::new(WasmLimits::default()).unwrap();
+        runtime
+            .load(manifest(vec![PluginCapability::Intervention], vec![HostCapability::EmitInterventions]), wat.as_bytes(), &mut registry)
+            .unwrap();
+
+        let mut consent = MicroConsentManager::new();
+        let scope = HostCapability::EmitInterventions.consent_scope("focus_coach");
+        consent.request_consent(scope.clone(), "Let Focus Coach suggest interventions".to_string());
+        consent.grant_consent(&scope).unwrap();
+
+        let report = runtime.run("focus_coach", &[observation("obs_1")], &consent, &mut registry).unwrap();
+        assert_eq!(report.exit_code, 2 * HOST_INVALID);
+        assert!(report.interventions.is_empty());
+    }
+}
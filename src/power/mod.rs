@@ -0,0 +1,235 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L97-103
+/// Energy-Aware Background Scheduler
+/// Defers heavy background work (mining, embedding, inference) to AC-power / idle windows
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use tracing::info;
+
+/// Power source
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+/// Device resource state (reported by the platform layer)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceState {
+    pub power_source: PowerSource,
+    pub battery_pct: Option<f64>, // 0.0 to 100.0, None on desktops
+    pub thermal_throttled: bool,
+    pub user_idle: bool,
+}
+
+impl Default for ResourceState {
+    fn default() -> Self {
+        Self {
+            power_source: PowerSource::Ac,
+            battery_pct: None,
+            thermal_throttled: false,
+            user_idle: false,
+        }
+    }
+}
+
+/// Background job class
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum JobClass {
+    PatternMining,
+    Embedding,
+    LlmInference,
+    Maintenance,
+}
+
+/// Per-class scheduling policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobPolicy {
+    pub requires_ac: bool,
+    pub min_battery_pct: f64, // On battery, run only above this charge (when AC not required)
+    pub requires_idle: bool,
+    pub allow_when_throttled: bool,
+    pub max_batch: usize,
+}
+
+impl JobPolicy {
+    /// Heavy work: AC power, not throttled
+    pub fn heavy() -> Self {
+        Self {
+            requires_ac: true,
+            min_battery_pct: 100.0,
+            requires_idle: false,
+            allow_when_throttled: false,
+            max_batch: 10,
+        }
+    }
+
+    /// Light work: runs on battery above 20%
+    pub fn light() -> Self {
+        Self {
+            requires_ac: false,
+            min_battery_pct: 20.0,
+            requires_idle: false,
+            allow_when_throttled: true,
+            max_batch: 50,
+        }
+    }
+
+    fn permits(&self, state: &ResourceState) -> bool {
+        let power_ok = match state.power_source {
+            PowerSource::Ac => true,
+            PowerSource::Battery => !self.requires_ac && state.battery_pct.map(|pct| pct >= self.min_battery_pct).unwrap_or(false),
+        };
+        power_ok && (!self.requires_idle || state.user_idle) && (!state.thermal_throttled || self.allow_when_throttled)
+    }
+}
+
+/// Queued background job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundJob {
+    pub id: String,
+    pub class: JobClass,
+    pub enqueued_at: i64,
+}
+
+/// Resource-aware background job scheduler
+pub struct ResourceAwareScheduler {
+    policies: HashMap<JobClass, JobPolicy>,
+    queue: VecDeque<BackgroundJob>,
+    state: ResourceState,
+}
+
+impl ResourceAwareScheduler {
+    /// Create scheduler with default policies (mining/embedding/inference are heavy)
+    pub fn new() -> Self {
+        info!("ResourceAwareScheduler::new: Creating resource-aware scheduler");
+        let mut policies = HashMap::new();
+        policies.insert(JobClass::PatternMining, JobPolicy::heavy());
+        policies.insert(JobClass::Embedding, JobPolicy::heavy());
+        policies.insert(JobClass::LlmInference, JobPolicy { requires_idle: true, max_batch: 3, ..JobPolicy::heavy() });
+        policies.insert(JobClass::Maintenance, JobPolicy::light());
+
+        Self {
+            policies,
+            queue: VecDeque::new(),
+            state: ResourceState::default(),
+        }
+    }
+
+    /// Configure policy for a job class
+    pub fn set_policy(&mut self, class: JobClass, policy: JobPolicy) {
+        info!("ResourceAwareScheduler::set_policy: Updating policy for {:?}", class);
+        self.policies.insert(class, policy);
+    }
+
+    /// Update device resource state
+    pub fn update_state(&mut self, state: ResourceState) {
+        info!("ResourceAwareScheduler::update_state: {:?}, throttled: {}, idle: {}", state.power_source, state.thermal_throttled, state.user_idle);
+        self.state = state;
+    }
+
+    /// Queue a background job
+    pub fn enqueue(&mut self, id: String, class: JobClass, now: i64) {
+        info!("ResourceAwareScheduler::enqueue: Queuing {} ({:?})", id, class);
+        self.queue.push_back(BackgroundJob { id, class, enqueued_at: now });
+    }
+
+    /// Whether a job class may run in the current resource state
+    pub fn can_run(&self, class: JobClass) -> bool {
+        self.policies.get(&class).map(|p| p.permits(&self.state)).unwrap_or(false)
+    }
+
+    /// Take the next batch of runnable jobs (FIFO, up to `max_batch` per class)
+    /// Jobs whose class may not run now stay queued
+    pub fn take_runnable(&mut self) -> Vec<BackgroundJob> {
+        let mut taken: HashMap<JobClass, usize> = HashMap::new();
+        let mut runnable = Vec::new();
+        let mut deferred = VecDeque::new();
+
+        while let Some(job) = self.queue.pop_front() {
+            let batch_limit = self.policies.get(&job.class).map(|p| p.max_batch).unwrap_or(0);
+            let count = taken.entry(job.class).or_insert(0);
+            if self.can_run(job.class) && *count < batch_limit {
+                *count += 1;
+                runnable.push(job);
+            } else {
+                deferred.push_back(job);
+            }
+        }
+        self.queue = deferred;
+
+        if !self.queue.is_empty() {
+            info!("ResourceAwareScheduler::take_runnable: Running {}, deferring {}", runnable.len(), self.queue.len());
+        }
+        runnable
+    }
+
+    /// Number of queued jobs
+    pub fn pending_count(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+impl Default for ResourceAwareScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_battery(pct: f64) -> ResourceState {
+        ResourceState {
+            power_source: PowerSource::Battery,
+            battery_pct: Some(pct),
+            thermal_throttled: false,
+            user_idle: false,
+        }
+    }
+
+    #[test]
+    fn test_heavy_jobs_deferred_on_battery() {
+        let mut scheduler = ResourceAwareScheduler::new();
+        scheduler.update_state(on_battery(80.0));
+        scheduler.enqueue("mine_1".to_string(), JobClass::PatternMining, 0);
+        scheduler.enqueue("vacuum_1".to_string(), JobClass::Maintenance, 0);
+
+        let runnable = scheduler.take_runnable();
+        assert_eq!(runnable.len(), 1);
+        assert_eq!(runnable[0].id, "vacuum_1");
+        assert_eq!(scheduler.pending_count(), 1);
+
+        scheduler.update_state(ResourceState::default());
+        assert_eq!(scheduler.take_runnable()[0].id, "mine_1");
+    }
+
+    #[test]
+    fn test_throttling_and_idle_requirements() {
+        let mut scheduler = ResourceAwareScheduler::new();
+        scheduler.update_state(ResourceState { thermal_throttled: true, ..ResourceState::default() });
+        assert!(!scheduler.can_run(JobClass::Embedding));
+        assert!(scheduler.can_run(JobClass::Maintenance));
+
+        scheduler.update_state(ResourceState::default());
+        assert!(!scheduler.can_run(JobClass::LlmInference)); // Requires idle
+        scheduler.update_state(ResourceState { user_idle: true, ..ResourceState::default() });
+        assert!(scheduler.can_run(JobClass::LlmInference));
+    }
+
+    #[test]
+    fn test_batches_limited_per_class() {
+        let mut scheduler = ResourceAwareScheduler::new();
+        scheduler.set_policy(JobClass::Embedding, JobPolicy { max_batch: 2, ..JobPolicy::heavy() });
+        for i in 0..5 {
+            scheduler.enqueue(format!("embed_{}", i), JobClass::Embedding, i);
+        }
+
+        let batch: Vec<String> = scheduler.take_runnable().into_iter().map(|j| j.id).collect();
+        assert_eq!(batch, vec!["embed_0", "embed_1"]);
+        assert_eq!(scheduler.pending_count(), 3);
+    }
+}
@@ -5,7 +5,8 @@
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 /// Consent ledger tracks granular user permissions
 /// Source: athenos-rules.mdc#L13
@@ -14,7 +15,11 @@ pub struct ConsentLedger {
     pub opt_in_cloud_sync: bool,
     pub opt_in_behavioral_logging: bool,
     pub opt_in_emotion_detection: bool,
-    pub opt_in_automation: bool,
+    pub opt_in_automation: bool, // Legacy blanket flag; true while any automation scope is granted
+    #[serde(default)]
+    pub automation_scopes: Vec<ActionType>, // Action types the user allows to be automated
+    #[serde(default)]
+    pub automation_scopes_migrated: bool,
     pub consent_timestamp: i64,
     pub revocation_history: Vec<ConsentRevocation>,
 }
@@ -27,6 +32,22 @@ pub struct ConsentRevocation {
     pub reason: Option<String>,
 }
 
+/// Current on-disk consent ledger schema
+/// v1: bare ledger JSON (legacy blanket automation flag); v2: versioned envelope with per-ActionType scopes
+pub const CONSENT_LEDGER_SCHEMA_VERSION: u32 = 2;
+
+/// PBKDF2-HMAC-SHA256 rounds for passphrase-derived local keys
+const PASSPHRASE_KDF_ITERATIONS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+
+/// Encrypted consent ledger file contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConsentLedgerFile {
+    schema_version: u32,
+    saved_at: i64,
+    ledger: serde_json::Value,
+}
+
 impl ConsentLedger {
     /// Create default consent ledger (all opt-out)
     /// Source: athenos-rules.mdc#L12 - Default: 100% on-device
@@ -37,6 +58,8 @@ impl ConsentLedger {
             opt_in_behavioral_logging: false,
             opt_in_emotion_detection: false,
             opt_in_automation: false,
+            automation_scopes: Vec::new(),
+            automation_scopes_migrated: true,
             consent_timestamp: chrono::Utc::now().timestamp(),
             revocation_history: Vec::new(),
         }
@@ -50,8 +73,16 @@ impl ConsentLedger {
             "cloud_sync" => self.opt_in_cloud_sync = false,
             "behavioral_logging" => self.opt_in_behavioral_logging = false,
             "emotion_detection" => self.opt_in_emotion_detection = false,
-            "automation" => self.opt_in_automation = false,
-            _ => {}
+            "automation" => {
+                self.opt_in_automation = false;
+                self.automation_scopes.clear();
+            }
+            scope => {
+                if let Some(action_type) = ActionType::from_consent_scope(scope) {
+                    self.automation_scopes.retain(|a| *a != action_type);
+                    self.opt_in_automation = !self.automation_scopes.is_empty();
+                }
+            }
         }
         self.revocation_history.push(ConsentRevocation {
             capability,
@@ -60,6 +91,93 @@ impl ConsentLedger {
         });
     }
 
+    /// Load a persisted ledger, migrating the legacy automation flag to per-ActionType scopes
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let mut ledger: Self = serde_json::from_str(json).map_err(|e| format!("Invalid consent ledger: {}", e))?;
+        ledger.migrate_automation_scopes();
+        Ok(ledger)
+    }
+
+    /// Load the ledger from an encrypted file, migrating older schemas
+    /// A missing file yields the default (all opt-out) ledger; a tampered or foreign file is an error
+    pub fn load(path: impl AsRef<Path>, encryption: &EncryptionManager) -> Result<Self, String> {
+        let path = path.as_ref();
+        let encrypted = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                info!("ConsentLedger::load: No ledger at {}, starting fresh", path.display());
+                return Ok(Self::new());
+            }
+            Err(e) => return Err(format!("Failed to read consent ledger {}: {}", path.display(), e)),
+        };
+        let plaintext = encryption
+            .decrypt(&encrypted)
+            .map_err(|e| format!("Consent ledger {} failed verification: {}", path.display(), e))?;
+        let ledger = Self::from_versioned_json(&plaintext)?;
+        info!("ConsentLedger::load: Loaded ledger with {} revocations", ledger.revocation_history.len());
+        Ok(ledger)
+    }
+
+    /// Decode a decrypted ledger file of any supported schema version
+    fn from_versioned_json(bytes: &[u8]) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_slice(bytes).map_err(|e| format!("Invalid consent ledger: {}", e))?;
+        let (version, ledger) = match value.get("schema_version").and_then(|v| v.as_u64()) {
+            Some(version) => (version as u32, value.get("ledger").cloned().ok_or("Consent ledger file has no ledger")?),
+            None => (1, value),
+        };
+        if version > CONSENT_LEDGER_SCHEMA_VERSION {
+            return Err(format!(
+                "Consent ledger schema v{} is newer than supported v{}; update Athenos",
+                version, CONSENT_LEDGER_SCHEMA_VERSION
+            ));
+        }
+        let mut ledger: Self = serde_json::from_value(ledger).map_err(|e| format!("Invalid consent ledger (v{}): {}", version, e))?;
+        // v1 -> v2: expand the legacy blanket automation flag to per-ActionType scopes
+        ledger.migrate_automation_scopes();
+        Ok(ledger)
+    }
+
+    /// Save the ledger encrypted at the current schema version (atomic replace)
+    pub fn save(&self, path: impl AsRef<Path>, encryption: &EncryptionManager) -> Result<(), String> {
+        let path = path.as_ref();
+        let file = ConsentLedgerFile {
+            schema_version: CONSENT_LEDGER_SCHEMA_VERSION,
+            saved_at: chrono::Utc::now().timestamp(),
+            ledger: serde_json::to_value(self).map_err(|e| format!("Failed to encode consent ledger: {}", e))?,
+        };
+        let plaintext = serde_json::to_vec(&file).map_err(|e| format!("Failed to encode consent ledger: {}", e))?;
+        let encrypted = encryption.encrypt(&plaintext)?;
+        write_atomic(path, &encrypted)?;
+        info!("ConsentLedger::save: Saved ledger to {}", path.display());
+        Ok(())
+    }
+
+    /// One-time migration: a ledger that opted into blanket automation keeps every action type
+    pub fn migrate_automation_scopes(&mut self) {
+        if self.automation_scopes_migrated {
+            return;
+        }
+        if self.opt_in_automation && self.automation_scopes.is_empty() {
+            info!("ConsentLedger::migrate_automation_scopes: Expanding legacy automation consent to all action types");
+            self.automation_scopes = ActionType::all();
+        }
+        self.automation_scopes_migrated = true;
+    }
+
+    /// Grant automation for a single action type
+    pub fn grant_automation_scope(&mut self, action_type: ActionType) {
+        info!("ConsentLedger::grant_automation_scope: Granting {}", action_type.consent_scope());
+        if !self.automation_scopes.contains(&action_type) {
+            self.automation_scopes.push(action_type);
+        }
+        self.opt_in_automation = true;
+    }
+
+    /// Check if an action type may be automated
+    pub fn allows_action(&self, action_type: &ActionType) -> bool {
+        self.automation_scopes.contains(action_type)
+    }
+
     /// Check if cloud sync is allowed
     pub fn can_sync_to_cloud(&self) -> bool {
         self.opt_in_cloud_sync
@@ -72,6 +190,18 @@ impl Default for ConsentLedger {
     }
 }
 
+/// Write via a temporary file and rename so a crash never leaves a partial file
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
 /// Encryption manager using sodiumoxide
 /// Source: athenos-rules.mdc#L14
 pub struct EncryptionManager {
@@ -89,6 +219,40 @@ impl EncryptionManager {
         })
     }
 
+    /// Initialize encryption with existing key material (e.g. derived from a passphrase)
+    pub fn from_key(key: &[u8]) -> Result<Self, String> {
+        sodiumoxide::init().map_err(|e| format!("Failed to init sodiumoxide: {:?}", e))?;
+        if key.len() != sodiumoxide::crypto::secretbox::KEYBYTES {
+            return Err(format!("Key must be {} bytes", sodiumoxide::crypto::secretbox::KEYBYTES));
+        }
+        Ok(Self { key: key.to_vec() })
+    }
+
+    /// Derive the key from a passphrase (PBKDF2-HMAC-SHA256); only the non-secret salt is kept on disk
+    /// The salt at `salt_path` is generated on first use, so the same passphrase always yields the same key
+    pub fn from_passphrase(passphrase: &str, salt_path: impl AsRef<Path>) -> Result<Self, String> {
+        if passphrase.len() < 8 {
+            return Err("Passphrase must be at least 8 characters".to_string());
+        }
+        let salt_path = salt_path.as_ref();
+        let salt = match std::fs::read(salt_path) {
+            Ok(salt) if salt.len() == SALT_LEN => salt,
+            Ok(_) => return Err(format!("Salt {} is corrupt", salt_path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                use rand::Rng;
+                let salt: [u8; SALT_LEN] = rand::thread_rng().gen();
+                write_atomic(salt_path, &salt)?;
+                info!("EncryptionManager::from_passphrase: Generated salt at {}", salt_path.display());
+                salt.to_vec()
+            }
+            Err(e) => return Err(format!("Failed to read salt {}: {}", salt_path.display(), e)),
+        };
+        let iterations = std::num::NonZeroU32::new(PASSPHRASE_KDF_ITERATIONS).ok_or("KDF iterations must be non-zero")?;
+        let mut key = [0u8; sodiumoxide::crypto::secretbox::KEYBYTES];
+        ring::pbkdf2::derive(ring::pbkdf2::PBKDF2_HMAC_SHA256, iterations, &salt, passphrase.as_bytes(), &mut key);
+        Self::from_key(&key)
+    }
+
     /// Encrypt data locally
     /// Source: athenos-rules.mdc#L14
     pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, String> {
@@ -122,10 +286,175 @@ impl EncryptionManager {
     }
 }
 
+/// Read a 32-byte local secret key, generating it on first use (owner-only on Unix)
+pub fn load_or_create_key(key_path: &Path) -> Result<Vec<u8>, String> {
+    match std::fs::read(key_path) {
+        Ok(key_bytes) if key_bytes.len() >= 32 => Ok(key_bytes),
+        Ok(_) => Err(format!("Key {} is too short", key_path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            use rand::Rng;
+            let key_bytes: [u8; 32] = rand::thread_rng().gen();
+            write_private(key_path, &key_bytes)?;
+            info!("load_or_create_key: Generated key at {}", key_path.display());
+            Ok(key_bytes.to_vec())
+        }
+        Err(e) => Err(format!("Failed to read key {}: {}", key_path.display(), e)),
+    }
+}
+
+/// Atomically replace a file readable only by its owner (on Unix)
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict {}: {}", tmp.display(), e))?;
+    }
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+/// Keyed pseudonymizer for user identifiers (HMAC-SHA256)
+/// The pseudonym -> user id lookup never leaves the privacy module
+pub struct Pseudonymizer {
+    key: ring::hmac::Key,
+    lookup: HashMap<String, String>, // pseudonym -> real user id
+    lookup_path: Option<PathBuf>,    // Rewritten whenever the lookup changes
+}
+
+impl Pseudonymizer {
+    /// Create pseudonymizer with a freshly generated local key
+    pub fn new() -> Self {
+        info!("Pseudonymizer::new: Generating pseudonymization key");
+        use rand::Rng;
+        let key_bytes: [u8; 32] = rand::thread_rng().gen();
+        Self::with_key(&key_bytes)
+    }
+
+    /// Create pseudonymizer from an existing key (stable pseudonyms across restarts)
+    pub fn with_key(key_bytes: &[u8]) -> Self {
+        Self {
+            key: ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key_bytes),
+            lookup: HashMap::new(),
+            lookup_path: None,
+        }
+    }
+
+    /// Open a pseudonymizer whose key persists at `key_path`, generated on first use
+    /// The lookup is kept next to the key (same stem, `.lookup`) and is owner-only on Unix
+    pub fn open(key_path: &Path) -> Result<Self, String> {
+        info!("Pseudonymizer::open: Loading pseudonymization key from {}", key_path.display());
+        let mut pseudonymizer = Self::with_key(&load_or_create_key(key_path)?);
+        let lookup_path = key_path.with_extension("lookup");
+        pseudonymizer.lookup = match std::fs::read(&lookup_path) {
+            Ok(contents) => serde_json::from_slice(&contents).map_err(|e| format!("Invalid pseudonym lookup: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", lookup_path.display(), e)),
+        };
+        pseudonymizer.lookup_path = Some(lookup_path);
+        Ok(pseudonymizer)
+    }
+
+    /// Map a user id to its stable pseudonym, recording it in the lookup
+    pub fn pseudonymize(&mut self, user_id: &str) -> String {
+        let pseudonym = self.pseudonym_of(user_id);
+        if self.resolve(&pseudonym).is_none() {
+            self.lookup.insert(pseudonym.clone(), user_id.to_string());
+            if let Err(e) = self.save() {
+                warn!("Pseudonymizer::pseudonymize: Lookup not persisted: {}", e);
+            }
+        }
+        pseudonym
+    }
+
+    /// Compute a user's pseudonym without recording it in the lookup
+    pub fn pseudonym_of(&self, user_id: &str) -> String {
+        let tag = ring::hmac::sign(&self.key, user_id.as_bytes());
+        std::iter::once("pu_".to_string())
+            .chain(tag.as_ref()[..16].iter().map(|b| format!("{:02x}", b)))
+            .collect()
+    }
+
+    /// Drop the lookup entry for a user (right to erasure); the pseudonym becomes unlinkable
+    pub fn forget(&mut self, user_id: &str) -> Result<(), String> {
+        info!("Pseudonymizer::forget: Removing pseudonym lookup entry");
+        let pseudonym = self.pseudonym_of(user_id);
+        if self.resolve(&pseudonym).is_none() {
+            return Ok(());
+        }
+        self.lookup.remove(&pseudonym);
+        self.save()
+    }
+
+    /// Resolve a pseudonym back to the real user id (privacy module only)
+    fn resolve(&self, pseudonym: &str) -> Option<&str> {
+        self.lookup.get(pseudonym).map(|s| s.as_str())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.lookup_path else {
+            return Ok(());
+        };
+        let contents = serde_json::to_vec(&self.lookup).map_err(|e| format!("Failed to encode pseudonym lookup: {}", e))?;
+        write_private(path, &contents)
+    }
+}
+
+impl Default for Pseudonymizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pseudonyms_stable_and_keyed() {
+        let pseudonymizer = Pseudonymizer::with_key(b"local-test-key");
+        let first = pseudonymizer.pseudonym_of("user_001");
+        
+        assert!(first.starts_with("pu_"));
+        assert!(!first.contains("user_001"));
+        assert_eq!(first, pseudonymizer.pseudonym_of("user_001"));
+        assert_ne!(first, pseudonymizer.pseudonym_of("user_002"));
+        assert_ne!(first, Pseudonymizer::with_key(b"other-key").pseudonym_of("user_001"));
+    }
+
+    #[test]
+    fn test_lookup_resolves_until_forgotten() {
+        let mut pseudonymizer = Pseudonymizer::with_key(b"local-test-key");
+        let first = pseudonymizer.pseudonymize("user_001");
+        assert_eq!(first, pseudonymizer.pseudonym_of("user_001"));
+        assert_eq!(pseudonymizer.resolve(&first), Some("user_001"));
+        
+        pseudonymizer.forget("user_001").unwrap();
+        assert_eq!(pseudonymizer.resolve(&first), None);
+    }
+
+    #[test]
+    fn test_opened_key_and_lookup_persist() {
+        let path = std::env::temp_dir().join(format!("athenos_pseudonym_{}.key", std::process::id()));
+        let lookup_path = path.with_extension("lookup");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&lookup_path);
+        let first = Pseudonymizer::open(&path).unwrap().pseudonymize("user_001");
+        
+        let mut reopened = Pseudonymizer::open(&path).unwrap();
+        assert_eq!(first, reopened.pseudonym_of("user_001"));
+        assert_eq!(reopened.resolve(&first), Some("user_001"));
+        
+        reopened.forget("user_001").unwrap();
+        assert_eq!(Pseudonymizer::open(&path).unwrap().resolve(&first), None);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&lookup_path);
+    }
+
     #[test]
     fn test_consent_ledger_default() {
         let ledger = ConsentLedger::new();
@@ -145,6 +474,35 @@ mod tests {
         assert_eq!(ledger.revocation_history.len(), 1);
     }
 
+    #[test]
+    fn test_automation_scopes_per_action_type() {
+        let mut ledger = ConsentLedger::new();
+        assert!(!ledger.allows_action(&ActionType::FocusMode));
+        
+        ledger.grant_automation_scope(ActionType::FocusMode);
+        assert!(ledger.allows_action(&ActionType::FocusMode));
+        assert!(!ledger.allows_action(&ActionType::AutomationMacro));
+        
+        ledger.revoke_consent(ActionType::FocusMode.consent_scope(), None);
+        assert!(!ledger.allows_action(&ActionType::FocusMode));
+        assert!(!ledger.opt_in_automation);
+        assert_eq!(ledger.revocation_history[0].capability, "automation:focus_mode");
+    }
+
+    #[test]
+    fn test_legacy_automation_flag_migrates() {
+        let legacy = r#"{"opt_in_cloud_sync":false,"opt_in_behavioral_logging":true,"opt_in_emotion_detection":false,"opt_in_automation":true,"consent_timestamp":0,"revocation_history":[]}"#;
+        let mut ledger = ConsentLedger::from_json(legacy).unwrap();
+        assert!(ledger.automation_scopes_migrated);
+        assert!(ActionType::all().iter().all(|a| ledger.allows_action(a)));
+        
+        // Migration runs once; later narrowing is kept
+        ledger.revoke_consent(ActionType::AutomationMacro.consent_scope(), None);
+        let reloaded = ConsentLedger::from_json(&serde_json::to_string(&ledger).unwrap()).unwrap();
+        assert!(!reloaded.allows_action(&ActionType::AutomationMacro));
+        assert!(reloaded.allows_action(&ActionType::FocusMode));
+    }
+
     #[test]
     fn test_encryption_roundtrip() {
         sodiumoxide::init().unwrap();
@@ -156,5 +514,56 @@ mod tests {
         
         assert_eq!(data, decrypted.as_slice());
     }
-}
 
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("athenos_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_consent_ledger_persists_encrypted() {
+        let path = temp_path("consent_ledger.enc");
+        let salt_path = temp_path("consent_ledger.salt");
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&salt_path);
+        let encryption = EncryptionManager::from_passphrase("correct horse battery", &salt_path).unwrap();
+
+        assert!(ConsentLedger::load(&path, &encryption).unwrap().revocation_history.is_empty());
+        let mut ledger = ConsentLedger::new();
+        ledger.opt_in_cloud_sync = true;
+        ledger.grant_automation_scope(ActionType::FocusMode);
+        ledger.revoke_consent("cloud_sync".to_string(), Some("Privacy concern".to_string()));
+        ledger.save(&path, &encryption).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&bytes).contains("Privacy concern"));
+        let reloaded = ConsentLedger::load(&path, &EncryptionManager::from_passphrase("correct horse battery", &salt_path).unwrap()).unwrap();
+        assert!(!reloaded.can_sync_to_cloud());
+        assert!(reloaded.allows_action(&ActionType::FocusMode));
+        assert_eq!(reloaded.revocation_history[0].reason.as_deref(), Some("Privacy concern"));
+
+        // Tampering or another key fails verification instead of yielding a default ledger
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        std::fs::write(&path, &tampered).unwrap();
+        assert!(ConsentLedger::load(&path, &encryption).unwrap_err().contains("verification"));
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(ConsentLedger::load(&path, &EncryptionManager::new().unwrap()).is_err());
+        assert!(ConsentLedger::load(&path, &EncryptionManager::from_passphrase("wrong horse battery", &salt_path).unwrap()).is_err());
+        assert!(EncryptionManager::from_passphrase("short", &salt_path).is_err());
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&salt_path);
+    }
+
+    #[test]
+    fn test_consent_ledger_schema_migration() {
+        let v1 = br#"{"opt_in_cloud_sync":false,"opt_in_behavioral_logging":true,"opt_in_emotion_detection":false,"opt_in_automation":true,"consent_timestamp":0,"revocation_history":[{"capability":"cloud_sync","revoked_at":5,"reason":null}]}"#;
+        let migrated = ConsentLedger::from_versioned_json(v1).unwrap();
+        assert!(ActionType::all().iter().all(|a| migrated.allows_action(a)));
+        assert_eq!(migrated.revocation_history.len(), 1);
+
+        let future = br#"{"schema_version":99,"saved_at":0,"ledger":{}}"#;
+        assert!(ConsentLedger::from_versioned_json(future).unwrap_err().contains("newer than supported"));
+    }
+}
@@ -0,0 +1,182 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L97-103
+/// Project Context Detection
+/// Detect the active project (git repo, document set, design file family) from window titles and paths
+
+use crate::consent::MicroConsentManager;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+
+/// Consent capability required to read window titles and paths for project detection
+pub const PROJECT_CONTEXT_CAPABILITY: &str = "project_context";
+
+const DESIGN_EXTENSIONS: [&str; 5] = ["fig", "sketch", "psd", "xd", "ai"];
+const DOCUMENT_EXTENSIONS: [&str; 8] = ["doc", "docx", "pdf", "md", "txt", "xlsx", "pptx", "odt"];
+
+/// Project context kind
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectKind {
+    GitRepo,
+    DocumentSet,
+    DesignFamily,
+}
+
+/// Detected project context
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectContext {
+    pub kind: ProjectKind,
+    pub name: String,
+}
+
+impl ProjectContext {
+    /// Stable key stored on observations ("git:athenos-ai")
+    pub fn key(&self) -> String {
+        let prefix = match self.kind {
+            ProjectKind::GitRepo => "git",
+            ProjectKind::DocumentSet => "docs",
+            ProjectKind::DesignFamily => "design",
+        };
+        format!("{}:{}", prefix, self.name.to_lowercase())
+    }
+}
+
+/// Project context detector
+pub struct ProjectContextDetector;
+
+impl ProjectContextDetector {
+    /// Create new detector
+    pub fn new() -> Self {
+        info!("ProjectContextDetector::new: Creating project context detector");
+        Self
+    }
+
+    /// Detect project context from the focused window title and/or file path
+    /// Returns None without `project_context` consent
+    pub fn detect(&self, consent: &MicroConsentManager, window_title: Option<&str>, path: Option<&str>) -> Option<ProjectContext> {
+        if !consent.has_consent(PROJECT_CONTEXT_CAPABILITY) {
+            return None;
+        }
+
+        path.and_then(|p| self.detect_from_path(Path::new(p)))
+            .or_else(|| window_title.and_then(|t| self.detect_from_title(t)))
+    }
+
+    /// Detect project and attach its key to the observation
+    pub fn attach(&self, consent: &MicroConsentManager, observation: &mut Observation, window_title: Option<&str>, path: Option<&str>) {
+        if let Some(context) = self.detect(consent, window_title, path) {
+            info!("ProjectContextDetector::attach: {} -> {}", observation.id, context.key());
+            observation.project = Some(context.key());
+        }
+    }
+
+    /// Paths: nearest ancestor with a `.git` directory, design file family, or document folder
+    fn detect_from_path(&self, path: &Path) -> Option<ProjectContext> {
+        if let Some(repo) = path.ancestors().find(|dir| dir.join(".git").is_dir()) {
+            let name = repo.file_name()?.to_string_lossy().to_string();
+            return Some(ProjectContext { kind: ProjectKind::GitRepo, name });
+        }
+
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        if DESIGN_EXTENSIONS.contains(&extension.as_str()) {
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            return Some(ProjectContext { kind: ProjectKind::DesignFamily, name: Self::family_name(&stem) });
+        }
+        if DOCUMENT_EXTENSIONS.contains(&extension.as_str()) {
+            let folder = path.parent()?.file_name()?.to_string_lossy().to_string();
+            return Some(ProjectContext { kind: ProjectKind::DocumentSet, name: folder });
+        }
+        None
+    }
+
+    /// Window titles: "file - workspace - Visual Studio Code", "workspace – file" (JetBrains), "name – Figma"
+    pub(crate) fn detect_from_title(&self, title: &str) -> Option<ProjectContext> {
+        let normalized = title.replace(" – ", " - ").replace(" — ", " - ");
+        let segments: Vec<&str> = normalized.split(" - ").map(str::trim).filter(|s| !s.is_empty()).collect();
+        let app = segments.last()?.to_lowercase();
+
+        if app == "visual studio code" && segments.len() >= 3 {
+            return Some(ProjectContext { kind: ProjectKind::GitRepo, name: segments[segments.len() - 2].to_string() });
+        }
+        if ["intellij idea", "pycharm", "webstorm", "rustrover", "clion"].iter().any(|ide| app.starts_with(ide)) && segments.len() >= 2 {
+            return Some(ProjectContext { kind: ProjectKind::GitRepo, name: segments[0].to_string() });
+        }
+        if (app == "figma" || app == "sketch") && segments.len() >= 2 {
+            return Some(ProjectContext { kind: ProjectKind::DesignFamily, name: Self::family_name(segments[0]) });
+        }
+        None
+    }
+
+    /// Strip version suffixes so "Checkout Flow v3" and "checkout-flow_final" share a family
+    fn family_name(stem: &str) -> String {
+        let words: Vec<String> = stem
+            .split([' ', '-', '_'])
+            .map(|w| w.to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let is_version = |w: &str| {
+            ["final", "copy", "draft", "latest"].contains(&w)
+                || w.chars().all(|c| c.is_ascii_digit())
+                || (w.starts_with('v') && w.len() > 1 && w[1..].chars().all(|c| c.is_ascii_digit() || c == '.'))
+        };
+
+        let end = words.iter().rposition(|w| !is_version(w)).map(|i| i + 1).unwrap_or(words.len());
+        words[..end].join(" ")
+    }
+}
+
+impl Default for ProjectContextDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consenting() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(PROJECT_CONTEXT_CAPABILITY.to_string(), "Detect active project".to_string());
+        consent.grant_consent(PROJECT_CONTEXT_CAPABILITY).unwrap();
+        consent
+    }
+
+    #[test]
+    fn test_detection_requires_consent() {
+        let detector = ProjectContextDetector::new();
+        let title = Some("main.rs - athenos-ai - Visual Studio Code");
+        assert!(detector.detect(&MicroConsentManager::new(), title, None).is_none());
+
+        let context = detector.detect(&consenting(), title, None).unwrap();
+        assert_eq!(context.key(), "git:athenos-ai");
+    }
+
+    #[test]
+    fn test_git_repo_from_path() {
+        let root = std::env::temp_dir().join(format!("athenos_project_{}", std::process::id()));
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let file = root.join("src").join("lib.rs");
+
+        let context = ProjectContextDetector::new().detect(&consenting(), None, file.to_str()).unwrap();
+        assert_eq!(context.kind, ProjectKind::GitRepo);
+        assert_eq!(context.name, root.file_name().unwrap().to_string_lossy());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_design_family_and_document_set() {
+        let detector = ProjectContextDetector::new();
+        let consent = consenting();
+
+        let a = detector.detect(&consent, Some("Checkout Flow v3 – Figma"), None).unwrap();
+        let b = detector.detect(&consent, None, Some("/tmp/no_repo/checkout-flow_final.sketch")).unwrap();
+        assert_eq!(a.key(), "design:checkout flow");
+        assert_eq!(a.key(), b.key());
+
+        let docs = detector.detect(&consent, None, Some("/tmp/no_repo/Q3 Planning/budget.xlsx")).unwrap();
+        assert_eq!(docs.key(), "docs:q3 planning");
+    }
+}
@@ -0,0 +1,315 @@
+/// Phase: B | Step: 5 | Source: Strategic_Reinforcements_Gap_Closures.md#L14
+/// Consent-Scoped Data Purge ("Forget Me")
+/// On revocation, deletes or anonymizes everything derived under the capability and issues a signed purge receipt
+
+use crate::consent::{DataDisposition, HandlerReceipt, MicroConsentManager, RevocationHandler};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Signed record of one consent-scoped purge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeReceipt {
+    pub id: String,
+    pub capability: String,
+    pub reason: Option<String>,
+    pub purged_at: i64,
+    pub handlers: Vec<HandlerReceipt>,
+    pub records_deleted: usize,
+    pub records_anonymized: usize,
+    pub complete: bool,    // Every handler succeeded
+    pub signature: String, // Hex HMAC-SHA256 over every other field
+}
+
+impl PurgeReceipt {
+    fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let mut unsigned = self.clone();
+        unsigned.signature = String::new();
+        serde_json::to_vec(&unsigned).map_err(|e| format!("Failed to encode purge receipt: {}", e))
+    }
+}
+
+/// Coordinates "forget me" purges across every module holding consent-derived data
+pub struct DataPurgeCoordinator {
+    signing_key: ring::hmac::Key,
+    receipts: Vec<PurgeReceipt>,
+    receipts_path: Option<PathBuf>, // Receipts are rewritten here after every purge
+}
+
+impl DataPurgeCoordinator {
+    /// Create coordinator with a freshly generated local signing key
+    pub fn new() -> Self {
+        use rand::Rng;
+        let key_bytes: [u8; 32] = rand::thread_rng().gen();
+        Self::with_key(&key_bytes)
+    }
+
+    /// Create coordinator from an existing key (receipts stay verifiable across restarts)
+    pub fn with_key(key_bytes: &[u8]) -> Self {
+        info!("DataPurgeCoordinator::new: Creating data purge coordinator");
+        Self {
+            signing_key: ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key_bytes),
+            receipts: Vec::new(),
+            receipts_path: None,
+        }
+    }
+
+    /// Open a coordinator whose signing key and receipts persist, so receipts verify after a restart
+    /// The key is generated on first use and is owner-only on Unix
+    pub fn open(key_path: PathBuf, receipts_path: PathBuf) -> Result<Self, String> {
+        info!("DataPurgeCoordinator::open: Opening purge receipts at {}", receipts_path.display());
+        let key_bytes = match std::fs::read(&key_path) {
+            Ok(key_bytes) if key_bytes.len() >= 32 => key_bytes,
+            Ok(_) => return Err(format!("Purge signing key {} is too short", key_path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                use rand::Rng;
+                let key_bytes: [u8; 32] = rand::thread_rng().gen();
+                write_atomic(&key_path, &key_bytes)?;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))
+                        .map_err(|e| format!("Failed to restrict {}: {}", key_path.display(), e))?;
+                }
+                info!("DataPurgeCoordinator::open: Generated signing key at {}", key_path.display());
+                key_bytes.to_vec()
+            }
+            Err(e) => return Err(format!("Failed to read purge signing key {}: {}", key_path.display(), e)),
+        };
+        let receipts = match std::fs::read_to_string(&receipts_path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid purge receipts: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read purge receipts: {}", e)),
+        };
+
+        let mut coordinator = Self::with_key(&key_bytes);
+        coordinator.receipts = receipts;
+        coordinator.receipts_path = Some(receipts_path);
+        Ok(coordinator)
+    }
+
+    /// Revoke `capability`, purge or anonymize derived records in every module, and sign a receipt
+    /// Data kept from earlier sessions is purged even when the grant was never requested in this one
+    /// The receipt is recorded on the consent manager's transparency timeline
+    pub fn forget(
+        &mut self,
+        consent: &mut MicroConsentManager,
+        capability: &str,
+        reason: Option<String>,
+        handlers: &mut [&mut dyn RevocationHandler],
+    ) -> Result<PurgeReceipt, String> {
+        info!("DataPurgeCoordinator::forget: Purging data derived under {}", capability);
+        if let Err(e) = consent.revoke_consent(capability, reason.clone()) {
+            info!("DataPurgeCoordinator::forget: {} for {}, revoking in the ledger only", e, capability);
+            consent.revoke_ledger_consent(capability, reason.clone());
+        }
+        let revocation = consent.propagate_revocation(capability, handlers);
+        let count = |disposition: DataDisposition| {
+            revocation.handlers.iter().filter(|h| h.disposition == disposition).map(|h| h.items).sum::<usize>()
+        };
+        let mut receipt = PurgeReceipt {
+            id: format!("purge_{}_{}", capability, revocation.revoked_at),
+            capability: capability.to_string(),
+            reason,
+            purged_at: revocation.revoked_at,
+            records_deleted: count(DataDisposition::Purged),
+            records_anonymized: count(DataDisposition::Anonymized),
+            complete: revocation.complete,
+            handlers: revocation.handlers,
+            signature: String::new(),
+        };
+        receipt.signature = self.sign(&receipt)?;
+
+        consent.add_timeline_entry(
+            "data_purged".to_string(),
+            format!(
+                "Deleted {} and anonymized {} records derived under {}{}",
+                receipt.records_deleted,
+                receipt.records_anonymized,
+                capability,
+                if receipt.complete { "" } else { " (some modules failed)" }
+            ),
+            vec![capability.to_string()],
+            Some(format!("Receipt {} signed {}", receipt.id, receipt.signature)),
+        );
+        self.receipts.push(receipt.clone());
+        self.save()?;
+        Ok(receipt)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.receipts_path else {
+            return Ok(());
+        };
+        let json = serde_json::to_vec(&self.receipts).map_err(|e| format!("Failed to encode purge receipts: {}", e))?;
+        write_atomic(path, &json)
+    }
+
+    fn sign(&self, receipt: &PurgeReceipt) -> Result<String, String> {
+        let tag = ring::hmac::sign(&self.signing_key, &receipt.signing_payload()?);
+        Ok(tag.as_ref().iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Check a receipt was issued by this coordinator and has not been altered
+    pub fn verify(&self, receipt: &PurgeReceipt) -> bool {
+        let Ok(payload) = receipt.signing_payload() else {
+            return false;
+        };
+        let Ok(signature) = (0..receipt.signature.len())
+            .step_by(2)
+            .map(|i| receipt.signature.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok()).ok_or(()))
+            .collect::<Result<Vec<u8>, ()>>()
+        else {
+            return false;
+        };
+        ring::hmac::verify(&self.signing_key, &payload, &signature).is_ok()
+    }
+
+    /// Receipts issued so far, oldest first
+    pub fn receipts(&self) -> &[PurgeReceipt] {
+        &self.receipts
+    }
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+impl Default for DataPurgeCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consent::BEHAVIORAL_LOGGING_CAPABILITY;
+    use crate::edge::{EdgeObserver, OSEvent, OSEventType};
+    use crate::local_stack::{FeatureStore, MemoryBackend, TemporalMetrics};
+    use crate::pattern_miner::PatternMiner;
+    use crate::rag::{DocumentChunk, RAGIndex, CONSENT_SCOPE_METADATA};
+    use std::collections::HashMap;
+
+    fn event(app: &str, timestamp: i64) -> OSEvent {
+        OSEvent {
+            event_type: OSEventType::AppSwitch,
+            app_name: app.to_string(),
+            window_title: None,
+            timestamp,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn chunk(id: &str, scope: Option<&str>) -> DocumentChunk {
+        let mut metadata = HashMap::new();
+        if let Some(scope) = scope {
+            metadata.insert(CONSENT_SCOPE_METADATA.to_string(), scope.to_string());
+        }
+        DocumentChunk {
+            id: id.to_string(),
+            content: format!("{} notes on focus", id),
+            source: "notes".to_string(),
+            embedding: Vec::new(),
+            metadata,
+        }
+    }
+
+    #[test]
+    fn test_forget_purges_every_module_and_signs_receipt() {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(BEHAVIORAL_LOGGING_CAPABILITY.to_string(), "Log workflow patterns".to_string());
+        consent.grant_consent(BEHAVIORAL_LOGGING_CAPABILITY).unwrap();
+
+        let mut observer = EdgeObserver::new(100);
+        let events: Vec<OSEvent> = ["Teams", "Gmail", "IDE"].iter().enumerate().map(|(i, app)| event(app, 100 + i as i64)).collect();
+        for e in &events {
+            observer.record_event(e.clone());
+        }
+        let mut miner = PatternMiner::new();
+        miner.mine_patterns(&events);
+        let mut store = FeatureStore::with_backend(Box::new(MemoryBackend::new())).unwrap();
+        store.store_metrics("obs_1".to_string(), TemporalMetrics {
+            time_to_first_action_min: 1.0,
+            focus_duration_min: 30.0,
+            context_switch_count: 2,
+            repeat_count: 6,
+            session_duration_min: 45.0,
+        });
+        let mut rag = RAGIndex::new();
+        rag.index_chunk(chunk("doc", None));
+        rag.index_chunk(chunk("journal", Some(BEHAVIORAL_LOGGING_CAPABILITY)));
+
+        let mut coordinator = DataPurgeCoordinator::with_key(b"test-key");
+        let receipt = coordinator
+            .forget(&mut consent, BEHAVIORAL_LOGGING_CAPABILITY, Some("Forget me".to_string()), &mut [&mut observer, &mut store, &mut rag, &mut miner])
+            .unwrap();
+
+        assert!(receipt.complete);
+        assert_eq!(receipt.handlers.len(), 4);
+        assert!(receipt.handlers.iter().all(|h| h.disposition == DataDisposition::Purged));
+        assert!(receipt.records_deleted >= 5); // 3 events, 1 metrics row, 1 chunk, plus mined sequences
+        assert!(observer.get_recent_events(10).is_empty());
+        assert!(store.get_metrics("obs_1").is_none());
+        assert_eq!(rag.get_by_source("notes").len(), 1);
+        assert!(receipt.handlers.iter().all(|h| h.items > 0));
+        assert!(miner.get_causal_relationships("Teams").is_empty());
+        assert!(!consent.has_consent(BEHAVIORAL_LOGGING_CAPABILITY));
+        assert_eq!(consent.get_timeline(Some(1))[0].event_type, "data_purged");
+
+        assert!(coordinator.verify(&receipt));
+        let mut tampered = receipt.clone();
+        tampered.records_deleted = 0;
+        assert!(!coordinator.verify(&tampered));
+        assert!(!DataPurgeCoordinator::with_key(b"other-key").verify(&receipt));
+    }
+
+    #[test]
+    fn test_bus_subscribers_are_purged_after_they_return() {
+        let mut consent = MicroConsentManager::new();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let bus = crate::bus::EventBus::default();
+        let miner_task = bus.spawn_subscriber(runtime.handle(), PatternMiner::new(), 2);
+        for (i, app) in ["Teams", "Gmail", "IDE", "Teams", "Gmail", "IDE"].iter().enumerate() {
+            bus.publish(event(app, 100 + i as i64));
+        }
+        bus.shutdown();
+        let mut miner = runtime.block_on(miner_task).unwrap();
+
+        let mut coordinator = DataPurgeCoordinator::with_key(b"test-key");
+        let receipt = coordinator.forget(&mut consent, BEHAVIORAL_LOGGING_CAPABILITY, Some("expired".to_string()), &mut [&mut miner]).unwrap();
+        assert_eq!(receipt.handlers[0].handler, "pattern_miner");
+        assert_eq!(receipt.handlers[0].disposition, DataDisposition::Purged);
+        assert!(receipt.records_deleted > 0);
+        assert!(miner.frequent_sequences().is_empty());
+    }
+
+    #[test]
+    fn test_receipts_survive_restart_and_grants_from_earlier_sessions_are_purged() {
+        let dir = std::env::temp_dir().join(format!("athenos_purge_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let key_path = dir.join("purge.key");
+        let receipts_path = dir.join("purge_receipts.json");
+
+        // No micro-consent was requested this session; the data on disk still has to go
+        let mut consent = MicroConsentManager::new();
+        let mut rag = RAGIndex::new();
+        rag.index_user_chunk(BEHAVIORAL_LOGGING_CAPABILITY, chunk("journal", None));
+        let mut coordinator = DataPurgeCoordinator::open(key_path.clone(), receipts_path.clone()).unwrap();
+        let receipt = coordinator.forget(&mut consent, BEHAVIORAL_LOGGING_CAPABILITY, None, &mut [&mut rag]).unwrap();
+        assert_eq!(receipt.records_deleted, 1);
+        assert!(rag.get_by_source("notes").is_empty());
+
+        let reopened = DataPurgeCoordinator::open(key_path, receipts_path).unwrap();
+        assert_eq!(reopened.receipts().len(), 1);
+        assert!(reopened.verify(&reopened.receipts()[0]));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
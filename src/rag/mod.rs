@@ -2,9 +2,20 @@
 /// RAG Stack - Index docs + neuroscience excerpts
 /// Deploy RAG stack with documentation, neuroscience excerpts, workflow playbooks
 
+use crate::consent::{DataDisposition, RevocationHandler};
+use candle_core::{DType, Device, Tensor};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use tracing::info;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Chunks scoring below this cosine similarity are not returned
+pub const MIN_SIMILARITY: f32 = 0.05;
+
+/// Chunk metadata key naming the consent capability the chunk was derived under (user data, not documentation)
+pub const CONSENT_SCOPE_METADATA: &str = "consent_scope";
 
 /// Document chunk for RAG
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,64 +23,228 @@ pub struct DocumentChunk {
     pub id: String,
     pub content: String,
     pub source: String,
-    pub embedding: Vec<f32>, // Simplified: would use proper embeddings in production
+    pub embedding: Vec<f32>, // Unit-length; filled in by the index's embedding provider when empty
     pub metadata: HashMap<String, String>,
 }
 
+/// Turns text into fixed-size embedding vectors
+pub trait EmbeddingProvider: Send {
+    fn provider_name(&self) -> &str;
+    fn dimension(&self) -> usize;
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String>;
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+        .collect()
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// Cosine similarity of two vectors (0.0 when either is zero or sizes differ)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|v| v * v).sum::<f32>().sqrt() * b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norms > 0.0 { dot / norms } else { 0.0 }
+}
+
+/// Local feature-hashing embedder over words and word bigrams; needs no model files
+pub struct HashingEmbedder {
+    dimension: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimension: usize) -> Self {
+        Self { dimension: dimension.max(1) }
+    }
+
+    fn add_feature(&self, vector: &mut [f32], feature: &str, weight: f32) {
+        let mut hasher = DefaultHasher::new();
+        feature.hash(&mut hasher);
+        let hash = hasher.finish();
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[(hash % self.dimension as u64) as usize] += sign * weight;
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl EmbeddingProvider for HashingEmbedder {
+    fn provider_name(&self) -> &str {
+        "hashing"
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        let tokens = tokenize(text);
+        let mut vector = vec![0.0; self.dimension];
+        for token in &tokens {
+            self.add_feature(&mut vector, token, 1.0);
+        }
+        for pair in tokens.windows(2) {
+            self.add_feature(&mut vector, &format!("{} {}", pair[0], pair[1]), 0.5);
+        }
+        Ok(normalize(vector))
+    }
+}
+
+/// Local static-embedding model run with candle: mean-pooled token vectors
+/// Loads an `embeddings` [vocab, dim] tensor from safetensors and a vocabulary file (one token per line)
+pub struct CandleEmbedder {
+    embeddings: Tensor,
+    vocab: HashMap<String, u32>,
+    dimension: usize,
+}
+
+impl CandleEmbedder {
+    /// Load model weights and vocabulary from disk
+    pub fn load(weights_path: &Path, vocab_path: &Path) -> Result<Self, String> {
+        info!("CandleEmbedder::load: Loading {}", weights_path.display());
+        let mut tensors = candle_core::safetensors::load(weights_path, &Device::Cpu)
+            .map_err(|e| format!("Failed to load embedding weights: {}", e))?;
+        let embeddings = tensors
+            .remove("embeddings")
+            .ok_or("Embedding weights have no 'embeddings' tensor")?
+            .to_dtype(DType::F32)
+            .map_err(|e| e.to_string())?;
+        let (rows, dimension) = embeddings.dims2().map_err(|e| format!("Embeddings must be [vocab, dim]: {}", e))?;
+
+        let vocab_text = std::fs::read_to_string(vocab_path).map_err(|e| format!("Failed to read vocabulary: {}", e))?;
+        let vocab: HashMap<String, u32> = vocab_text
+            .lines()
+            .enumerate()
+            .map(|(i, token)| (token.trim().to_lowercase(), i as u32))
+            .collect();
+        if vocab.len() > rows {
+            return Err(format!("Vocabulary has {} tokens but model only {} rows", vocab.len(), rows));
+        }
+        Ok(Self { embeddings, vocab, dimension })
+    }
+}
+
+impl EmbeddingProvider for CandleEmbedder {
+    fn provider_name(&self) -> &str {
+        "candle"
+    }
+
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        let ids: Vec<u32> = tokenize(text).iter().filter_map(|token| self.vocab.get(token).copied()).collect();
+        if ids.is_empty() {
+            return Ok(vec![0.0; self.dimension]); // No known tokens: matches nothing
+        }
+        let ids = Tensor::new(ids.as_slice(), &Device::Cpu).map_err(|e| e.to_string())?;
+        let pooled = self.embeddings
+            .index_select(&ids, 0)
+            .and_then(|rows| rows.mean(0))
+            .and_then(|mean| mean.to_vec1::<f32>())
+            .map_err(|e| format!("Embedding failed: {}", e))?;
+        Ok(normalize(pooled))
+    }
+}
+
 /// RAG index for retrieval-augmented generation
+/// Flat cosine-similarity index: exact top-k over unit vectors, fine into the tens of thousands of chunks
 /// Source: Athenos_AI_Strategy.md#L114
 pub struct RAGIndex {
     chunks: Vec<DocumentChunk>,
     source_index: HashMap<String, Vec<usize>>,
+    embedder: Box<dyn EmbeddingProvider>,
 }
 
 impl RAGIndex {
-    /// Create new RAG index
+    /// Create new RAG index with the local hashing embedder
     pub fn new() -> Self {
-        info!("RAGIndex::new: Creating RAG index");
+        Self::with_embedder(Box::new(HashingEmbedder::default()))
+    }
+
+    /// Create RAG index with a specific embedding provider
+    pub fn with_embedder(embedder: Box<dyn EmbeddingProvider>) -> Self {
+        info!("RAGIndex::new: Creating RAG index ({} embeddings, dim={})", embedder.provider_name(), embedder.dimension());
         Self {
             chunks: Vec::new(),
             source_index: HashMap::new(),
+            embedder,
         }
     }
 
-    /// Index a document chunk
+    /// Index a document chunk, embedding it unless it already carries a usable embedding
     /// Source: Athenos_AI_Strategy.md#L114
-    pub fn index_chunk(&mut self, chunk: DocumentChunk) {
+    pub fn index_chunk(&mut self, mut chunk: DocumentChunk) {
         info!("RAGIndex::index_chunk: Indexing chunk {} from {}", chunk.id, chunk.source);
+        let usable = chunk.embedding.len() == self.embedder.dimension() && chunk.embedding.iter().any(|v| *v != 0.0);
+        if usable {
+            chunk.embedding = normalize(chunk.embedding);
+        } else {
+            chunk.embedding = self.embedder.embed(&chunk.content).unwrap_or_else(|e| {
+                warn!("RAGIndex::index_chunk: Could not embed {}: {}", chunk.id, e);
+                vec![0.0; self.embedder.dimension()]
+            });
+        }
+
         let idx = self.chunks.len();
-        self.chunks.push(chunk.clone());
-        
         self.source_index
             .entry(chunk.source.clone())
             .or_insert_with(Vec::new)
             .push(idx);
+        self.chunks.push(chunk);
     }
 
-    /// Search for relevant chunks (simplified similarity)
+    /// Index a chunk derived from user data, tagged so revoking `capability` purges it
+    pub fn index_user_chunk(&mut self, capability: &str, mut chunk: DocumentChunk) {
+        chunk.metadata.insert(CONSENT_SCOPE_METADATA.to_string(), capability.to_string());
+        self.index_chunk(chunk);
+    }
+
+    /// Search for the most similar chunks
     /// Source: Athenos_AI_Strategy.md#L114
     pub fn search(&self, query: &str, limit: usize) -> Vec<&DocumentChunk> {
+        self.search_scored(query, limit)
+            .into_iter()
+            .map(|(chunk, _)| chunk)
+            .collect()
+    }
+
+    /// Top-k chunks by cosine similarity to the query, with scores
+    pub fn search_scored(&self, query: &str, limit: usize) -> Vec<(&DocumentChunk, f32)> {
         info!("RAGIndex::search: Searching for '{}' (limit: {})", query, limit);
-        
-        // Phase B: Simple keyword matching (would use vector similarity in production)
-        let query_lower = query.to_lowercase();
-        let mut scored: Vec<(&DocumentChunk, usize)> = self.chunks
+        let query_embedding = match self.embedder.embed(query) {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                warn!("RAGIndex::search: Could not embed query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut scored: Vec<(&DocumentChunk, f32)> = self.chunks
             .iter()
-            .map(|chunk| {
-                let score = chunk.content.to_lowercase()
-                    .split_whitespace()
-                    .filter(|word| query_lower.contains(word))
-                    .count();
-                (chunk, score)
-            })
-            .filter(|(_, score)| *score > 0)
+            .map(|chunk| (chunk, cosine_similarity(&query_embedding, &chunk.embedding)))
+            .filter(|(_, score)| *score >= MIN_SIMILARITY)
             .collect();
-        
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
-        scored.into_iter()
-            .take(limit)
-            .map(|(chunk, _)| chunk)
-            .collect()
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+        scored
     }
 
     /// Get chunks by source
@@ -87,23 +262,43 @@ impl RAGIndex {
         
         // Split into chunks (simplified: 500 char chunks)
         let chunk_size = 500;
-        let mut chunk_id = 0;
         
         for (i, chunk_text) in content.as_bytes().chunks(chunk_size).enumerate() {
             let chunk = DocumentChunk {
                 id: format!("{}_{}", source, i),
                 content: String::from_utf8_lossy(chunk_text).to_string(),
                 source: source.to_string(),
-                embedding: vec![0.0; 128], // Placeholder
+                embedding: Vec::new(), // Embedded on indexing
                 metadata: HashMap::new(),
             };
             
             self.index_chunk(chunk);
-            chunk_id = i;
         }
     }
 }
 
+impl RevocationHandler for RAGIndex {
+    fn revocation_handler_name(&self) -> &str {
+        "rag_index"
+    }
+
+    /// Drop chunks indexed under the revoked capability; documentation chunks carry no scope and stay
+    fn on_consent_revoked(&mut self, capability: &str) -> Result<(DataDisposition, usize), String> {
+        let before = self.chunks.len();
+        self.chunks.retain(|c| c.metadata.get(CONSENT_SCOPE_METADATA).map(String::as_str) != Some(capability));
+        let purged = before - self.chunks.len();
+        if purged == 0 {
+            return Ok((DataDisposition::NotAffected, 0));
+        }
+        self.source_index.clear();
+        for (idx, chunk) in self.chunks.iter().enumerate() {
+            self.source_index.entry(chunk.source.clone()).or_default().push(idx);
+        }
+        info!("RAGIndex::on_consent_revoked: Purged {} chunks", purged);
+        Ok((DataDisposition::Purged, purged))
+    }
+}
+
 impl Default for RAGIndex {
     fn default() -> Self {
         Self::new()
@@ -149,5 +344,52 @@ mod tests {
         let chunks = index.get_by_source("test.md");
         assert!(chunks.len() >= 2); // Should be split into multiple chunks
     }
-}
 
+    #[test]
+    fn test_vector_search_ranks_by_similarity() {
+        let mut index = RAGIndex::new();
+        for (id, content) in [
+            ("focus", "Deep focus blocks protect attention from meeting interruptions"),
+            ("sleep", "Sleep debt lowers working memory and mood the next day"),
+            ("email", "Batch email twice a day to avoid constant context switching"),
+        ] {
+            index.index_chunk(DocumentChunk {
+                id: id.to_string(),
+                content: content.to_string(),
+                source: "playbook.md".to_string(),
+                embedding: Vec::new(),
+                metadata: HashMap::new(),
+            });
+        }
+
+        let results = index.search_scored("how do I protect deep focus from interruptions", 2);
+        assert_eq!(results[0].0.id, "focus");
+        assert!(results.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(index.chunks.iter().all(|chunk| chunk.embedding.len() == 256));
+        assert!(index.search("quantum chromodynamics", 3).iter().all(|chunk| chunk.id != "sleep"));
+    }
+
+    #[test]
+    fn test_candle_embedder_mean_pools_known_tokens() {
+        let dir = std::env::temp_dir().join(format!("athenos_rag_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let weights = dir.join("model.safetensors");
+        let vocab = dir.join("vocab.txt");
+        let matrix = Tensor::new(&[[1.0f32, 0.0], [0.0, 1.0], [1.0, 1.0]], &Device::Cpu).unwrap();
+        matrix.save_safetensors("embeddings", &weights).unwrap();
+        std::fs::write(&vocab, "focus\nsleep\nbreak\n").unwrap();
+
+        let embedder = CandleEmbedder::load(&weights, &vocab).unwrap();
+        assert_eq!(embedder.dimension(), 2);
+        let pooled = embedder.embed("Focus, then SLEEP").unwrap();
+        assert!((pooled[0] - pooled[1]).abs() < 1e-6 && (pooled[0] - 0.70710677).abs() < 1e-5);
+        assert_eq!(embedder.embed("unknown words").unwrap(), vec![0.0, 0.0]);
+
+        let mut index = RAGIndex::with_embedder(Box::new(embedder));
+        index.load_documentation("notes.md", "sleep");
+        index.load_documentation("other.md", "focus");
+        assert_eq!(index.search("sleep", 5)[0].source, "notes.md");
+        assert!(CandleEmbedder::load(&dir.join("missing"), &vocab).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
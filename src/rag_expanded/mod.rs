@@ -2,7 +2,8 @@
 /// Expanded RAG Corpus
 /// Expand RAG corpus with industry-specific workflows; enable personalization
 
-use crate::rag::RAGIndex;
+use crate::project_context::PROJECT_CONTEXT_CAPABILITY;
+use crate::rag::{DocumentChunk, RAGIndex};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -20,9 +21,10 @@ pub struct IndustryWorkflow {
 /// Personalized RAG index
 /// Source: Athenos_AI_Strategy.md#L133
 pub struct ExpandedRAGIndex {
-    base_index: RAGIndex,
+    pub(crate) base_index: RAGIndex,
     industry_workflows: HashMap<String, Vec<IndustryWorkflow>>,
     user_preferences: HashMap<String, Vec<String>>, // user_id -> preferred industries
+    project_preferences: HashMap<String, Vec<String>>, // project key -> industries
 }
 
 impl ExpandedRAGIndex {
@@ -33,6 +35,7 @@ impl ExpandedRAGIndex {
             base_index: RAGIndex::new(),
             industry_workflows: HashMap::new(),
             user_preferences: HashMap::new(),
+            project_preferences: HashMap::new(),
         }
     }
 
@@ -57,17 +60,44 @@ impl ExpandedRAGIndex {
             .map(|v| v.as_slice())
             .unwrap_or(&[]);
         
+        self.search_with_industries(preferred_industries, None, query, limit)
+    }
+
+    /// Personalize search for the user's active project
+    /// Project preferences replace user preferences; chunks tagged with another project are excluded
+    pub fn personalized_search_for_project(&self, user_id: &str, project: Option<&str>, query: &str, limit: usize) -> Vec<String> {
+        let Some(project) = project else {
+            return self.personalized_search(user_id, query, limit);
+        };
+        info!("ExpandedRAGIndex::personalized_search_for_project: Search for user {} in {}", user_id, project);
+        
+        let preferred_industries = self.project_preferences
+            .get(project)
+            .or_else(|| self.user_preferences.get(user_id))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+        
+        self.search_with_industries(preferred_industries, Some(project), query, limit)
+    }
+
+    fn search_with_industries(&self, preferred_industries: &[String], project: Option<&str>, query: &str, limit: usize) -> Vec<String> {
         // Search base index
         let base_results: Vec<String> = self.base_index
-            .search(query, limit)
+            .search(query, usize::MAX)
             .iter()
+            .filter(|c| match (c.metadata.get("project"), project) {
+                (Some(chunk_project), Some(project)) => chunk_project == project,
+                (Some(_), None) => false, // Project-specific chunks stay out of global search
+                (None, _) => true,
+            })
             .map(|c| c.content.clone())
+            .take(limit)
             .collect();
         
         // Add industry-specific results if user has preferences
         let mut results = base_results;
         for industry in preferred_industries {
-            if let Some(workflows) = self.industry_workflows.get(*industry) {
+            if let Some(workflows) = self.industry_workflows.get(industry) {
                 for workflow in workflows {
                     if query.to_lowercase().contains(&workflow.workflow_name.to_lowercase()) {
                         results.push(format!("Industry workflow: {} - {}", workflow.workflow_name, workflow.steps.join(" → ")));
@@ -79,6 +109,18 @@ impl ExpandedRAGIndex {
         results.into_iter().take(limit).collect()
     }
 
+    /// Set industries for a project
+    pub fn set_project_preferences(&mut self, project: String, industries: Vec<String>) {
+        info!("ExpandedRAGIndex::set_project_preferences: Setting preferences for project {}", project);
+        self.project_preferences.insert(project, industries);
+    }
+
+    /// Index a project-specific document chunk; it is purged when project-context consent is revoked
+    pub fn index_project_chunk(&mut self, project: &str, mut chunk: DocumentChunk) {
+        chunk.metadata.insert("project".to_string(), project.to_string());
+        self.base_index.index_user_chunk(PROJECT_CONTEXT_CAPABILITY, chunk);
+    }
+
     /// Set user preferences
     pub fn set_user_preferences(&mut self, user_id: String, industries: Vec<String>) {
         info!("ExpandedRAGIndex::set_user_preferences: Setting preferences for user {}", user_id);
@@ -103,6 +145,7 @@ impl Default for ExpandedRAGIndex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::consent::RevocationHandler;
 
     #[test]
     fn test_expanded_rag_creation() {
@@ -142,5 +185,35 @@ mod tests {
         let results = index.personalized_search("user_001", "code review", 5);
         assert!(!results.is_empty());
     }
-}
 
+    #[test]
+    fn test_project_scoped_search() {
+        let mut index = ExpandedRAGIndex::new();
+        let chunk = |id: &str, content: &str| DocumentChunk {
+            id: id.to_string(),
+            content: content.to_string(),
+            source: "notes".to_string(),
+            embedding: vec![],
+            metadata: HashMap::new(),
+        };
+        index.index_project_chunk("git:athenos-ai", chunk("a", "release checklist for athenos"));
+        index.index_project_chunk("git:other", chunk("b", "release checklist for other"));
+        index.set_project_preferences("git:athenos-ai".to_string(), vec!["software".to_string()]);
+        index.add_industry_workflow(IndustryWorkflow {
+            industry: "software".to_string(),
+            workflow_name: "Release".to_string(),
+            steps: vec!["Tag".to_string(), "Publish".to_string()],
+            best_practices: vec![],
+            common_pitfalls: vec![],
+        });
+        
+        let results = index.personalized_search_for_project("user_001", Some("git:athenos-ai"), "release checklist", 5);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].contains("athenos"));
+        assert!(results[1].starts_with("Industry workflow: Release"));
+        assert!(index.personalized_search("user_001", "release checklist", 5).is_empty());
+
+        assert_eq!(index.base_index.on_consent_revoked(PROJECT_CONTEXT_CAPABILITY).unwrap().1, 2);
+        assert!(index.base_index.get_by_source("notes").is_empty());
+    }
+}
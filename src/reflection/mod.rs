@@ -3,7 +3,13 @@
 /// Build reflective reasoning loop (self-critique of recommendations)
 
 use crate::types::*;
+use crate::analytics::AnalyticsAggregator;
+use crate::auto_action::AutoActionSynthesizer;
+use crate::cache::{BoundedCache, BoundedLog, CacheStats, InstrumentedCache};
+use crate::microlearning::{NUDGE_DELIVERED_METRIC, NUDGE_DISMISSED_METRIC};
 use crate::models::RecommendationRanker;
+use crate::notify::{Notification, NotificationSeverity, NotificationSource};
+use crate::signature::PatternSignature;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
@@ -20,11 +26,138 @@ pub struct SelfCritique {
     pub confidence_adjustment: f64, // Adjustment to original confidence
 }
 
+/// Seconds in the self-evaluation window
+pub const SELF_EVALUATION_WINDOW_SECS: i64 = 7 * 24 * 3600;
+
+/// Calibration bins used for the expected calibration error
+const CALIBRATION_BINS: usize = 10;
+
+/// Outcomes a pattern needs before it can be flagged as a false positive
+const MIN_FALSE_POSITIVE_OUTCOMES: usize = 2;
+
+/// Critiques kept for adjustment and outcome reflection
+const CRITIQUE_CAPACITY: usize = 1_000;
+
+/// Evaluated suggestions kept for self-evaluation (several weeks at typical volume)
+const EVALUATION_CAPACITY: usize = 5_000;
+
+/// A critiqued suggestion and how the user responded to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluatedSuggestion {
+    pub recommendation_id: String,
+    pub signature: String,       // Local pattern signature key
+    pub pattern_label: String,   // Normalized steps, for display
+    pub predicted_score: f64,    // Critique score before the outcome was known
+    pub accepted: bool,
+    pub ignored: bool,
+    pub evaluated_at: i64,
+}
+
+/// Critique context kept until the outcome arrives
+#[derive(Debug, Clone)]
+struct PendingEvaluation {
+    signature: String,
+    pattern_label: String,
+    predicted_score: f64,
+}
+
+/// Pattern whose suggestions were never accepted during the window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FalsePositivePattern {
+    pub signature: String,
+    pub pattern_label: String,
+    pub suggestions: usize,
+    pub mean_predicted_score: f64,
+}
+
+/// Weekly report on the assistant's own suggestion quality
+/// Source: Athenos_AI_Strategy.md#L123
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfEvaluationReport {
+    pub week_start: i64,
+    pub week_end: i64,
+    pub suggestions: usize,
+    pub accepted: usize,
+    pub acceptance_rate: f64,
+    pub false_positive_patterns: Vec<FalsePositivePattern>,
+    pub actions_executed: usize,
+    pub rollbacks: usize,
+    pub rollback_rate: f64,
+    pub calibration_error: f64, // Expected calibration error of critique scores vs. acceptance
+    pub nudges_delivered: usize,
+    pub nudges_dismissed: usize,
+    pub nudge_dismissal_rate: f64,
+}
+
+impl SelfEvaluationReport {
+    /// Plain-language summary shown to the user
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            "How Athenos did this week".to_string(),
+            format!("- Suggestions accepted: {}/{} ({:.0}%)", self.accepted, self.suggestions, self.acceptance_rate * 100.0),
+            format!("- Automations rolled back: {}/{} ({:.0}%)", self.rollbacks, self.actions_executed, self.rollback_rate * 100.0),
+            format!("- Confidence calibration error: {:.2}", self.calibration_error),
+            format!("- Nudges dismissed: {}/{} ({:.0}%)", self.nudges_dismissed, self.nudges_delivered, self.nudge_dismissal_rate * 100.0),
+        ];
+        if self.false_positive_patterns.is_empty() {
+            lines.push("- No pattern was repeatedly suggested without being accepted".to_string());
+        } else {
+            lines.push("- Patterns I kept suggesting that you never accepted:".to_string());
+            for pattern in &self.false_positive_patterns {
+                lines.push(format!("  - {} ({} suggestions)", pattern.pattern_label, pattern.suggestions));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Report notification for the user's configured sinks
+    pub fn to_notification(&self) -> Notification {
+        Notification {
+            source: NotificationSource::Report,
+            severity: NotificationSeverity::Info,
+            title: "Weekly self-evaluation".to_string(),
+            body: self.render(),
+            created_at: self.week_end,
+        }
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Expected calibration error: bin-weighted gap between predicted score and acceptance rate
+fn expected_calibration_error(evaluations: &[&EvaluatedSuggestion]) -> f64 {
+    if evaluations.is_empty() {
+        return 0.0;
+    }
+    let mut bins = vec![(0usize, 0.0f64, 0usize); CALIBRATION_BINS];
+    for evaluation in evaluations {
+        let bin = ((evaluation.predicted_score * CALIBRATION_BINS as f64) as usize).min(CALIBRATION_BINS - 1);
+        bins[bin].0 += 1;
+        bins[bin].1 += evaluation.predicted_score;
+        bins[bin].2 += evaluation.accepted as usize;
+    }
+    bins.iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, predicted, accepted)| {
+            let gap = (predicted / *count as f64 - ratio(*accepted, *count)).abs();
+            gap * *count as f64 / evaluations.len() as f64
+        })
+        .sum()
+}
+
 /// Reflective reasoning loop
 /// Source: Athenos_AI_Strategy.md#L123
 pub struct ReflectiveReasoningLoop {
     ranker: RecommendationRanker,
-    critiques: HashMap<String, SelfCritique>,
+    critiques: BoundedCache<String, SelfCritique>,
+    pending: BoundedCache<String, PendingEvaluation>, // Awaiting an outcome
+    evaluations: BoundedLog<EvaluatedSuggestion>,
 }
 
 impl ReflectiveReasoningLoop {
@@ -33,7 +166,9 @@ impl ReflectiveReasoningLoop {
         info!("ReflectiveReasoningLoop::new: Creating reflective reasoning loop");
         Self {
             ranker: RecommendationRanker::new(),
-            critiques: HashMap::new(),
+            critiques: BoundedCache::new("reflection.critiques", CRITIQUE_CAPACITY),
+            pending: BoundedCache::new("reflection.pending", CRITIQUE_CAPACITY),
+            evaluations: BoundedLog::new("reflection.evaluations", EVALUATION_CAPACITY),
         }
     }
 
@@ -45,7 +180,7 @@ impl ReflectiveReasoningLoop {
         let mut strengths = Vec::new();
         let mut weaknesses = Vec::new();
         let mut alternative_approaches = Vec::new();
-        let mut critique_score = 0.5;
+        let mut critique_score: f64 = 0.5;
         
         // Analyze confidence
         match observation.action.confidence {
@@ -109,13 +244,19 @@ impl ReflectiveReasoningLoop {
             confidence_adjustment,
         };
         
+        let signature = PatternSignature::from_observation(observation);
+        self.pending.insert(observation.id.clone(), PendingEvaluation {
+            signature: signature.key(),
+            pattern_label: signature.sequence.join(" > "),
+            predicted_score: critique_score,
+        });
         self.critiques.insert(observation.id.clone(), critique.clone());
         critique
     }
 
     /// Get adjusted recommendation based on critique
     pub fn get_adjusted_recommendation(&self, observation: &Observation) -> Option<Action> {
-        if let Some(critique) = self.critiques.get(&observation.id) {
+        if let Some(critique) = self.critiques.peek(&observation.id) {
             if critique.critique_score < 0.4 {
                 // Suggest alternative or require approval
                 let mut adjusted_action = observation.action.clone();
@@ -133,6 +274,18 @@ impl ReflectiveReasoningLoop {
     pub fn reflect_on_outcome(&mut self, observation_id: &str, outcome: &Outcome) {
         info!("ReflectiveReasoningLoop::reflect_on_outcome: Reflecting on outcome for {}", observation_id);
         
+        if let Some(pending) = self.pending.remove(observation_id) {
+            self.evaluations.push(EvaluatedSuggestion {
+                recommendation_id: observation_id.to_string(),
+                signature: pending.signature,
+                pattern_label: pending.pattern_label,
+                predicted_score: pending.predicted_score,
+                accepted: outcome.accepted,
+                ignored: outcome.ignored,
+                evaluated_at: outcome.timestamp,
+            });
+        }
+        
         if let Some(critique) = self.critiques.get_mut(observation_id) {
             if outcome.accepted {
                 critique.critique_score += 0.1;
@@ -145,6 +298,107 @@ impl ReflectiveReasoningLoop {
             critique.critique_score = critique.critique_score.min(1.0).max(0.0);
         }
     }
+
+    /// Critique each executed auto-action's observation and reflect on how it turned out
+    /// A rolled-back action counts as rejected; actions without a matching observation are skipped
+    pub fn reflect_on_executed(&mut self, observations: &[Observation], synthesizer: &AutoActionSynthesizer) -> usize {
+        let mut reflected = 0;
+        for executed in synthesizer.get_all_executed() {
+            let observation = match observations.iter().find(|o| executed.id == format!("action_{}", o.id)) {
+                Some(observation) => observation,
+                None => continue,
+            };
+            let timestamp = match executed.rolled_back_at.or(executed.executed_at) {
+                Some(timestamp) => timestamp,
+                None => continue,
+            };
+            self.critique_recommendation(observation);
+            self.reflect_on_outcome(&observation.id, &Outcome {
+                observation_id: observation.id.clone(),
+                accepted: executed.rolled_back_at.is_none(),
+                ignored: false,
+                modified: false,
+                time_saved_minutes: None,
+                error_rate_change: None,
+                timestamp,
+            });
+            reflected += 1;
+        }
+        info!("ReflectiveReasoningLoop::reflect_on_executed: Reflected on {} executed actions", reflected);
+        reflected
+    }
+
+    /// Suggestions whose outcome has been reflected on, oldest first
+    pub fn get_evaluations(&self) -> Vec<&EvaluatedSuggestion> {
+        self.evaluations.iter().collect()
+    }
+
+    /// Cache gauges for analytics
+    pub fn cache_stats(&self) -> Vec<CacheStats> {
+        vec![self.critiques.cache_stats(), self.pending.cache_stats(), self.evaluations.cache_stats()]
+    }
+
+    /// Build the weekly self-evaluation for the window starting at `week_start`
+    /// Source: Athenos_AI_Strategy.md#L123
+    pub fn weekly_self_evaluation(
+        &self,
+        week_start: i64,
+        synthesizer: &AutoActionSynthesizer,
+        analytics: &AnalyticsAggregator,
+    ) -> SelfEvaluationReport {
+        let week_end = week_start + SELF_EVALUATION_WINDOW_SECS;
+        let in_window = |ts: i64| ts >= week_start && ts < week_end;
+        info!("ReflectiveReasoningLoop::weekly_self_evaluation: Evaluating week starting {}", week_start);
+        
+        let evaluations: Vec<&EvaluatedSuggestion> = self.evaluations.iter().filter(|e| in_window(e.evaluated_at)).collect();
+        let accepted = evaluations.iter().filter(|e| e.accepted).count();
+        
+        let mut by_pattern: HashMap<&str, Vec<&EvaluatedSuggestion>> = HashMap::new();
+        for evaluation in &evaluations {
+            by_pattern.entry(evaluation.signature.as_str()).or_default().push(evaluation);
+        }
+        let mut false_positive_patterns: Vec<FalsePositivePattern> = by_pattern
+            .into_iter()
+            .filter(|(_, group)| group.len() >= MIN_FALSE_POSITIVE_OUTCOMES && group.iter().all(|e| !e.accepted))
+            .map(|(signature, group)| FalsePositivePattern {
+                signature: signature.to_string(),
+                pattern_label: group[0].pattern_label.clone(),
+                suggestions: group.len(),
+                mean_predicted_score: group.iter().map(|e| e.predicted_score).sum::<f64>() / group.len() as f64,
+            })
+            .collect();
+        false_positive_patterns.sort_by(|a, b| b.suggestions.cmp(&a.suggestions).then_with(|| a.signature.cmp(&b.signature)));
+        
+        let history = synthesizer.get_all_executed();
+        let actions_executed = history.iter().filter(|a| a.executed_at.is_some_and(in_window)).count();
+        let rollbacks = history.iter().filter(|a| a.rolled_back_at.is_some_and(in_window)).count();
+        
+        let nudges_delivered = analytics.sum_metric(NUDGE_DELIVERED_METRIC, week_start, week_end) as usize;
+        let nudges_dismissed = analytics.sum_metric(NUDGE_DISMISSED_METRIC, week_start, week_end) as usize;
+        
+        let report = SelfEvaluationReport {
+            week_start,
+            week_end,
+            suggestions: evaluations.len(),
+            accepted,
+            acceptance_rate: ratio(accepted, evaluations.len()),
+            false_positive_patterns,
+            actions_executed,
+            rollbacks,
+            rollback_rate: ratio(rollbacks, actions_executed),
+            calibration_error: expected_calibration_error(&evaluations),
+            nudges_delivered,
+            nudges_dismissed,
+            nudge_dismissal_rate: ratio(nudges_dismissed, nudges_delivered),
+        };
+        info!(
+            "ReflectiveReasoningLoop::weekly_self_evaluation: {} suggestions, {:.0}% accepted, {} false-positive patterns",
+            report.suggestions,
+            report.acceptance_rate * 100.0,
+            report.false_positive_patterns.len()
+        );
+        report
+    }
 }
 
 impl Default for ReflectiveReasoningLoop {
@@ -157,6 +411,14 @@ impl Default for ReflectiveReasoningLoop {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use crate::privacy::ConsentLedger;
+
+    fn consented(mut synthesizer: AutoActionSynthesizer) -> AutoActionSynthesizer {
+        let mut ledger = ConsentLedger::new();
+        ledger.automation_scopes = ActionType::all();
+        synthesizer.apply_consent(&ledger);
+        synthesizer
+    }
 
     #[test]
     fn test_reflective_loop_creation() {
@@ -182,6 +444,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let critique = loop_ref.critique_recommendation(&observation);
@@ -212,10 +475,11 @@ mod tests {
             expected_outcome: expected,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         loop_ref.critique_recommendation(&observation);
-        let initial_score = loop_ref.critiques.get("test_002").unwrap().critique_score;
+        let initial_score = loop_ref.critiques.peek("test_002").unwrap().critique_score;
         
         let outcome = Outcome {
             observation_id: "test_002".to_string(),
@@ -228,8 +492,112 @@ mod tests {
         };
         
         loop_ref.reflect_on_outcome("test_002", &outcome);
-        let updated_score = loop_ref.critiques.get("test_002").unwrap().critique_score;
+        let updated_score = loop_ref.critiques.peek("test_002").unwrap().critique_score;
         assert!(updated_score >= initial_score);
     }
-}
 
+    fn suggestion(id: &str, steps: &[&str], confidence: Confidence) -> Observation {
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: steps.iter().map(|s| s.to_string()).collect(),
+            metrics: HashMap::new(),
+            intent: Intent::AutomateAction,
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Test".to_string(),
+                confidence,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1_000,
+            project: None,
+        }
+    }
+
+    fn respond(loop_ref: &mut ReflectiveReasoningLoop, id: &str, accepted: bool, timestamp: i64) {
+        loop_ref.reflect_on_outcome(id, &Outcome {
+            observation_id: id.to_string(),
+            accepted,
+            ignored: !accepted,
+            modified: false,
+            time_saved_minutes: None,
+            error_rate_change: None,
+            timestamp,
+        });
+    }
+
+    #[test]
+    fn test_weekly_self_evaluation_flags_false_positives() {
+        let mut loop_ref = ReflectiveReasoningLoop::new();
+        for (i, accepted) in [true, true, false].into_iter().enumerate() {
+            let id = format!("good_{}", i);
+            loop_ref.critique_recommendation(&suggestion(&id, &["IDE", "Terminal"], Confidence::High));
+            respond(&mut loop_ref, &id, accepted, 1_000 + i as i64);
+        }
+        for i in 0..2 {
+            let id = format!("noise_{}", i);
+            loop_ref.critique_recommendation(&suggestion(&id, &["Slack", "Gmail"], Confidence::High));
+            respond(&mut loop_ref, &id, false, 2_000);
+        }
+        // Outside the window
+        loop_ref.critique_recommendation(&suggestion("old", &["Slack", "Gmail"], Confidence::High));
+        respond(&mut loop_ref, "old", true, 1_000 + SELF_EVALUATION_WINDOW_SECS);
+        
+        let mut analytics = AnalyticsAggregator::new();
+        analytics.record_metric_at(NUDGE_DELIVERED_METRIC.to_string(), 4.0, crate::analytics::MetricCategory::UserEngagement, 1_500);
+        analytics.record_metric_at(NUDGE_DISMISSED_METRIC.to_string(), 1.0, crate::analytics::MetricCategory::UserEngagement, 1_600);
+        
+        let report = loop_ref.weekly_self_evaluation(0, &AutoActionSynthesizer::new(), &analytics);
+        assert_eq!(report.suggestions, 5);
+        assert_eq!(report.accepted, 2);
+        assert!((report.acceptance_rate - 0.4).abs() < 1e-9);
+        assert_eq!(report.false_positive_patterns.len(), 1);
+        assert_eq!(report.false_positive_patterns[0].pattern_label, "slack > gmail");
+        assert_eq!(report.false_positive_patterns[0].suggestions, 2);
+        assert_eq!(report.nudge_dismissal_rate, 0.25);
+        // High-confidence scores (0.8) against 40% acceptance are poorly calibrated
+        assert!((report.calibration_error - 0.4).abs() < 1e-9);
+        assert!(report.render().contains("slack > gmail (2 suggestions)"));
+    }
+
+    #[test]
+    fn test_weekly_self_evaluation_counts_rollbacks() {
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        synthesizer.synthesize_and_execute(&suggestion("kept", &["IDE"], Confidence::High)).unwrap();
+        synthesizer.synthesize_and_execute(&suggestion("undone", &["IDE"], Confidence::High)).unwrap();
+        synthesizer.rollback_last().unwrap();
+        
+        let now = chrono::Utc::now().timestamp();
+        let report = ReflectiveReasoningLoop::new().weekly_self_evaluation(now - 60, &synthesizer, &AnalyticsAggregator::new());
+        assert_eq!(report.actions_executed, 2);
+        assert_eq!(report.rollbacks, 1);
+        assert_eq!(report.rollback_rate, 0.5);
+        assert_eq!(report.suggestions, 0);
+        assert_eq!(report.calibration_error, 0.0);
+    }
+
+    #[test]
+    fn test_reflect_on_executed_actions() {
+        let mut synthesizer = consented(AutoActionSynthesizer::new());
+        let observations = vec![
+            suggestion("kept", &["IDE"], Confidence::High),
+            suggestion("undone", &["IDE"], Confidence::High),
+            suggestion("never_run", &["IDE"], Confidence::High),
+        ];
+        synthesizer.synthesize_and_execute(&observations[0]).unwrap();
+        synthesizer.synthesize_and_execute(&observations[1]).unwrap();
+        synthesizer.rollback_last().unwrap();
+        
+        let mut loop_ref = ReflectiveReasoningLoop::new();
+        assert_eq!(loop_ref.reflect_on_executed(&observations, &synthesizer), 2);
+        let evaluations = loop_ref.get_evaluations();
+        assert_eq!(evaluations.len(), 2);
+        assert!(evaluations.iter().any(|e| e.recommendation_id == "kept" && e.accepted));
+        assert!(evaluations.iter().any(|e| e.recommendation_id == "undone" && !e.accepted));
+        
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(loop_ref.weekly_self_evaluation(now - 60, &synthesizer, &AnalyticsAggregator::new()).suggestions, 2);
+    }
+}
@@ -4,10 +4,13 @@
 
 use crate::types::*;
 use crate::sandbox::SandboxRunner;
+use crate::gate_policy::{GateInput, SharedGatePolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+pub mod trace;
+
 /// Replay simulation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplayResult {
@@ -23,18 +26,28 @@ pub struct ReplayResult {
 pub struct ReplaySimulator {
     sandbox_runner: SandboxRunner,
     historical_outcomes: HashMap<String, Outcome>,
+    gate_policy: SharedGatePolicy,
 }
 
 impl ReplaySimulator {
     /// Create new replay simulator
     pub fn new() -> Self {
         info!("ReplaySimulator::new: Creating replay simulator");
+        // Replays never execute anything; judge every action type as if automation were consented
+        let mut sandbox_runner = SandboxRunner::default();
+        sandbox_runner.set_consent_scopes(ActionType::all());
         Self {
-            sandbox_runner: SandboxRunner::default(),
+            sandbox_runner,
             historical_outcomes: HashMap::new(),
+            gate_policy: SharedGatePolicy::default(),
         }
     }
 
+    /// Share the gate policy consulted by gate_action
+    pub fn set_gate_policy(&mut self, gate_policy: SharedGatePolicy) {
+        self.gate_policy = gate_policy;
+    }
+
     /// Simulate action replay from historical data
     /// Source: Athenos_AI_Strategy.md#L115
     pub fn replay_action(&mut self, observation: &Observation) -> ReplayResult {
@@ -44,7 +57,7 @@ impl ReplaySimulator {
         let sandbox_result = self.sandbox_runner.test_automation(&observation.action);
         
         // Check historical outcomes for similar patterns
-        let mut quality_score = 0.5; // Default
+        let mut quality_score: f64 = 0.5; // Default
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
         
@@ -72,7 +85,7 @@ impl ReplaySimulator {
         
         ReplayResult {
             observation_id: observation.id.clone(),
-            action_safe: sandbox_result.success && observation.action.risk <= RiskCategory::Low,
+            action_safe: sandbox_result.success && self.gate_policy.get().allows_risk(&observation.action.risk),
             quality_score,
             errors,
             warnings,
@@ -95,9 +108,17 @@ impl ReplaySimulator {
             .collect()
     }
 
-    /// Gate actions based on replay results
+    /// Gate actions based on replay results and the historical acceptance rate, using the shared gate policy
     pub fn gate_action(&self, result: &ReplayResult) -> bool {
-        result.action_safe && result.quality_score > 0.6 && result.errors.is_empty()
+        let input = GateInput {
+            quality: Some(result.quality_score),
+            warnings: result.warnings.len(),
+            errors: result.errors.len(),
+            accepted: self.historical_outcomes.values().filter(|o| o.accepted).count() as f64,
+            decided: self.historical_outcomes.len() as f64,
+            ..GateInput::default()
+        };
+        result.action_safe && self.gate_policy.get().passes(&input)
     }
 }
 
@@ -128,14 +149,15 @@ mod tests {
             metrics: HashMap::new(),
             intent: Intent::SuggestShortcut,
             action: Action {
-                action_type: ActionType::AutomationMacro,
-                description: "Safe macro".to_string(),
+                action_type: ActionType::FocusMode,
+                description: "Safe focus block".to_string(),
                 confidence: Confidence::High,
                 risk: RiskCategory::None,
             },
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let result = simulator.replay_action(&observation);
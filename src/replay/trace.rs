@@ -0,0 +1,355 @@
+/// Phase: B | Step: 8 | Source: Athenos_AI_Strategy.md#L115
+/// Trace-Driven Replay
+/// Replays recorded OSEvent/Observation/Outcome traces on a deterministic virtual clock and scores divergence from history
+
+use super::ReplaySimulator;
+use crate::edge::OSEvent;
+use crate::models::PatternDetector;
+use crate::shortcut::ShortcutGenerator;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Clock that only moves when the replay advances it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualClock {
+    now: i64,
+}
+
+impl VirtualClock {
+    pub fn new(start: i64) -> Self {
+        Self { now: start }
+    }
+
+    pub fn now(&self) -> i64 {
+        self.now
+    }
+
+    /// Move forward to `timestamp`; earlier timestamps leave the clock unchanged
+    pub fn advance_to(&mut self, timestamp: i64) -> i64 {
+        self.now = self.now.max(timestamp);
+        self.now
+    }
+
+    pub fn advance_by(&mut self, secs: i64) -> i64 {
+        self.now += secs.max(0);
+        self.now
+    }
+}
+
+/// One recorded trace entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TraceEntry {
+    Event(OSEvent),
+    Observation(Observation),
+    Outcome(Outcome), // What the user actually did with the suggestion for observation_id
+}
+
+impl TraceEntry {
+    pub fn timestamp(&self) -> i64 {
+        match self {
+            TraceEntry::Event(event) => event.timestamp,
+            TraceEntry::Observation(observation) => observation.timestamp,
+            TraceEntry::Outcome(outcome) => outcome.timestamp,
+        }
+    }
+}
+
+/// Recorded trace, replayed in timestamp order (ties keep recording order)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayTrace {
+    entries: Vec<TraceEntry>,
+}
+
+impl ReplayTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Parse one JSON entry per line; blank lines are skipped
+    pub fn from_jsonl(content: &str) -> Result<Self, String> {
+        let mut trace = Self::new();
+        for (index, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let entry = serde_json::from_str(line).map_err(|e| format!("Invalid trace entry on line {}: {}", index + 1, e))?;
+            trace.push(entry);
+        }
+        Ok(trace)
+    }
+
+    pub fn to_jsonl(&self) -> Result<String, String> {
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(&serde_json::to_string(entry).map_err(|e| format!("Failed to encode trace entry: {}", e))?);
+            content.push('\n');
+        }
+        Ok(content)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn ordered(&self) -> Vec<&TraceEntry> {
+        let mut entries: Vec<&TraceEntry> = self.entries.iter().collect();
+        entries.sort_by_key(|e| e.timestamp());
+        entries
+    }
+}
+
+/// Regression gate for trace replays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceReplayConfig {
+    pub max_divergence: f64,        // Highest divergence score that still passes
+    pub min_scored_outcomes: usize, // Fewer scored outcomes than this never passes
+}
+
+impl Default for TraceReplayConfig {
+    fn default() -> Self {
+        Self { max_divergence: 0.2, min_scored_outcomes: 5 }
+    }
+}
+
+/// Prediction made for one replayed observation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub observation_id: String,
+    pub replayed_at: i64,             // Virtual clock when the observation was replayed
+    pub pattern: PatternType,
+    pub confidence: f64,              // PatternDetector::score_confidence
+    pub proposal_id: Option<String>,  // Shortcut offered by the replay, if any
+    pub predicted_saved_min: f64,
+    pub historical: Option<Outcome>,
+    pub divergence: Option<f64>,      // 0.0 agrees with history .. 1.0 contradicts it; None until an outcome arrives
+}
+
+/// Result of replaying a whole trace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceReplayReport {
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub events_replayed: usize,
+    pub observations_replayed: usize,
+    pub outcomes_scored: usize,
+    pub unmatched_outcomes: usize, // Outcomes for observations not in the trace
+    pub divergence_score: f64,     // Mean divergence over scored outcomes
+    pub passed: bool,
+    pub steps: Vec<TraceStep>,
+}
+
+/// Divergence between a replayed prediction and the recorded outcome
+/// Offering what the user declined (or withholding what they took) is 1.0; otherwise the relative savings error
+fn step_divergence(step: &TraceStep, outcome: &Outcome) -> f64 {
+    let historically_taken = outcome.accepted || outcome.modified;
+    if step.proposal_id.is_some() != historically_taken {
+        return 1.0;
+    }
+    match outcome.time_saved_minutes {
+        Some(realized) if historically_taken && realized.is_finite() => {
+            let scale = realized.abs().max(step.predicted_saved_min.abs());
+            if scale > 0.0 { ((step.predicted_saved_min - realized).abs() / scale).min(1.0) } else { 0.0 }
+        }
+        _ => 0.0,
+    }
+}
+
+impl ReplaySimulator {
+    /// Replay `trace` against `detector` and a fresh ShortcutGenerator
+    /// Observations are offered a shortcut when the detector sees a workflow sequence and the generator proposes one;
+    /// recorded outcomes then approve or reject that proposal at their own virtual time, so suppression replays faithfully
+    pub fn replay_trace(&self, trace: &ReplayTrace, detector: &PatternDetector, config: &TraceReplayConfig) -> TraceReplayReport {
+        info!("ReplaySimulator::replay_trace: Replaying trace of {} entries", trace.len());
+        let entries = trace.ordered();
+        let started_at = entries.first().map(|e| e.timestamp()).unwrap_or(0);
+        let mut clock = VirtualClock::new(started_at);
+        let mut generator = ShortcutGenerator::new();
+        let mut steps: Vec<TraceStep> = Vec::new();
+        let mut step_index: HashMap<String, usize> = HashMap::new();
+        let mut events_replayed = 0;
+        let mut unmatched_outcomes = 0;
+
+        for entry in entries {
+            let now = clock.advance_to(entry.timestamp());
+            match entry {
+                TraceEntry::Event(_) => events_replayed += 1,
+                TraceEntry::Observation(observation) => {
+                    let pattern = detector.detect_pattern(observation);
+                    let proposal = if pattern == PatternType::WorkflowSequence {
+                        generator.generate_shortcut_at(observation, now)
+                    } else {
+                        None
+                    };
+                    step_index.insert(observation.id.clone(), steps.len());
+                    steps.push(TraceStep {
+                        observation_id: observation.id.clone(),
+                        replayed_at: now,
+                        pattern,
+                        confidence: detector.score_confidence(observation),
+                        predicted_saved_min: proposal.as_ref().map(|p| p.expected_time_saved_min).unwrap_or(0.0),
+                        proposal_id: proposal.map(|p| p.id),
+                        historical: None,
+                        divergence: None,
+                    });
+                }
+                TraceEntry::Outcome(outcome) => {
+                    let Some(step) = step_index.get(&outcome.observation_id).map(|&i| &mut steps[i]) else {
+                        unmatched_outcomes += 1;
+                        continue;
+                    };
+                    if let Some(proposal_id) = &step.proposal_id {
+                        let applied = if outcome.accepted || outcome.modified {
+                            generator.approve_shortcut_at(proposal_id, now)
+                        } else if !outcome.ignored {
+                            generator.reject_shortcut_at(proposal_id, now)
+                        } else {
+                            Ok(())
+                        };
+                        if let Err(e) = applied {
+                            info!("ReplaySimulator::replay_trace: Could not apply outcome for {}: {}", outcome.observation_id, e);
+                        }
+                    }
+                    step.divergence = Some(step_divergence(step, outcome));
+                    step.historical = Some(outcome.clone());
+                }
+            }
+        }
+
+        let scored: Vec<f64> = steps.iter().filter_map(|s| s.divergence).collect();
+        let divergence_score = if scored.is_empty() { 0.0 } else { scored.iter().sum::<f64>() / scored.len() as f64 };
+        let passed = scored.len() >= config.min_scored_outcomes && divergence_score <= config.max_divergence;
+        info!(
+            "ReplaySimulator::replay_trace: {} outcomes scored, divergence {:.3} ({})",
+            scored.len(),
+            divergence_score,
+            if passed { "passed" } else { "failed" }
+        );
+        TraceReplayReport {
+            started_at,
+            ended_at: clock.now(),
+            events_replayed,
+            observations_replayed: steps.len(),
+            outcomes_scored: scored.len(),
+            unmatched_outcomes,
+            divergence_score,
+            passed,
+            steps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(id: &str, sequence: &[&str], repeat_count: f64, timestamp: i64) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), repeat_count);
+        let mut expected_outcome = HashMap::new();
+        expected_outcome.insert("time_saved_min".to_string(), 10.0);
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: sequence.iter().map(|s| s.to_string()).collect(),
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Automate sequence".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::Low,
+            },
+            expected_outcome,
+            source: "trace".to_string(),
+            timestamp,
+            project: None,
+        }
+    }
+
+    fn outcome(id: &str, accepted: bool, saved: Option<f64>, timestamp: i64) -> Outcome {
+        Outcome {
+            observation_id: id.to_string(),
+            accepted,
+            ignored: false,
+            modified: false,
+            time_saved_minutes: saved,
+            error_rate_change: None,
+            timestamp,
+        }
+    }
+
+    fn recorded_trace() -> ReplayTrace {
+        let mut trace = ReplayTrace::new();
+        // Recorded out of order; replay sorts by timestamp
+        trace.push(TraceEntry::Outcome(outcome("obs_a", true, Some(10.0), 1_100)));
+        trace.push(TraceEntry::Observation(observation("obs_a", &["Teams", "Gmail", "Jira"], 8.0, 1_000)));
+        trace.push(TraceEntry::Event(OSEvent {
+            event_type: crate::edge::OSEventType::AppSwitch,
+            app_name: "Teams".to_string(),
+            window_title: None,
+            timestamp: 990,
+            metadata: HashMap::new(),
+        }));
+        trace.push(TraceEntry::Observation(observation("obs_b", &["IDE", "Terminal"], 2.0, 2_000)));
+        trace.push(TraceEntry::Outcome(outcome("obs_b", false, None, 2_100)));
+        trace.push(TraceEntry::Observation(observation("obs_c", &["Slack", "Notion", "Figma"], 9.0, 3_000)));
+        trace.push(TraceEntry::Outcome(outcome("obs_c", true, Some(5.0), 3_100)));
+        trace
+    }
+
+    #[test]
+    fn test_virtual_clock_is_monotonic() {
+        let mut clock = VirtualClock::new(100);
+        assert_eq!(clock.advance_to(150), 150);
+        assert_eq!(clock.advance_to(120), 150);
+        assert_eq!(clock.advance_by(-5), 150);
+        assert_eq!(clock.advance_by(10), 160);
+    }
+
+    #[test]
+    fn test_replay_is_deterministic_and_scores_divergence() {
+        let trace = ReplayTrace::from_jsonl(&recorded_trace().to_jsonl().unwrap()).unwrap();
+        let config = TraceReplayConfig { max_divergence: 0.2, min_scored_outcomes: 3 };
+        let report = ReplaySimulator::new().replay_trace(&trace, &PatternDetector::new(), &config);
+
+        assert_eq!((report.started_at, report.ended_at), (990, 3_100));
+        assert_eq!((report.events_replayed, report.observations_replayed, report.outcomes_scored), (1, 3, 3));
+        assert_eq!(report.steps[0].replayed_at, 1_000);
+        assert_eq!(report.steps[0].divergence, Some(0.0)); // Offered and taken, savings as predicted
+        assert_eq!(report.steps[1].divergence, Some(0.0)); // Not offered, not taken
+        assert_eq!(report.steps[2].divergence, Some(0.5)); // Predicted 10 min, realized 5
+        assert!((report.divergence_score - 0.5 / 3.0).abs() < 1e-9);
+        assert!(report.passed);
+
+        let again = ReplaySimulator::new().replay_trace(&trace, &PatternDetector::new(), &config);
+        assert_eq!(serde_json::to_string(&again.steps).unwrap(), serde_json::to_string(&report.steps).unwrap());
+    }
+
+    #[test]
+    fn test_contradicted_history_fails_gate() {
+        let mut trace = ReplayTrace::new();
+        for i in 0..3 {
+            let id = format!("obs_{}", i);
+            let ts = 1_000 + i * 1_000;
+            trace.push(TraceEntry::Observation(observation(&id, &["Teams", "Gmail", "Jira"], 8.0, ts)));
+            trace.push(TraceEntry::Outcome(outcome(&id, false, None, ts + 100)));
+        }
+        trace.push(TraceEntry::Outcome(outcome("unknown", true, None, 9_000)));
+        let report = ReplaySimulator::new().replay_trace(&trace, &PatternDetector::new(), &TraceReplayConfig { max_divergence: 0.2, min_scored_outcomes: 3 });
+
+        // First offer is declined; the second re-offer is declined too and suppresses the sequence,
+        // so the third observation agrees with history
+        let divergences: Vec<Option<f64>> = report.steps.iter().map(|s| s.divergence).collect();
+        assert_eq!(divergences, vec![Some(1.0), Some(1.0), Some(0.0)]);
+        assert_eq!(report.unmatched_outcomes, 1);
+        assert!(!report.passed);
+    }
+}
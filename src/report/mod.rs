@@ -4,7 +4,14 @@
 
 use crate::types::*;
 use crate::local_stack::FeatureStore;
+use crate::notify::{Notification, NotificationRouter, NotificationSeverity, NotificationSource};
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
+use crate::scheduling::MeetingLoadStats;
+use crate::habits::HabitTracker;
+use crate::plugin::{PluginRegistry, RenderedSection};
+use crate::locale::{LocalePreferences, Localizer};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tracing::info;
 
 /// Daily cognitive report
@@ -17,6 +24,99 @@ pub struct DailyReport {
     pub suggestions: Vec<ActionSuggestion>,
     pub time_saved_minutes: f64,
     pub focus_stability_pct: f64,
+    pub project: Option<String>,
+    #[serde(default)]
+    pub plugin_sections: Vec<RenderedSection>,
+}
+
+/// Report export format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+}
+
+impl DailyReport {
+    /// Render report in the given export format (en-US, minutes)
+    pub fn render(&self, format: ReportFormat) -> Result<String, String> {
+        self.render_localized(format, &Localizer::default())
+    }
+
+    /// Render report with the user's locale and unit preferences
+    /// JSON stays machine-readable (ISO dates, raw numbers); Markdown is fully localized
+    pub fn render_localized(&self, format: ReportFormat, localizer: &Localizer) -> Result<String, String> {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self).map_err(|e| format!("Failed to encode report: {}", e)),
+            ReportFormat::Markdown => {
+                let title = localizer.translate("report.title", &[("date", &localizer.format_iso_date(&self.date))]);
+                let mut out = match &self.project {
+                    Some(project) => format!("# {} ({})\n\n", title, project),
+                    None => format!("# {}\n\n", title),
+                };
+                let time_saved = localizer.format_duration(self.time_saved_minutes);
+                let focus = format!("{}%", localizer.format_number(self.focus_stability_pct, 1));
+                let clarity = localizer.format_number(self.metrics.cognitive_clarity_index, 2);
+                out.push_str(&format!("- {}\n", localizer.translate("report.time_saved", &[("value", &time_saved)])));
+                out.push_str(&format!("- {}\n", localizer.translate("report.focus_stability", &[("value", &focus)])));
+                out.push_str(&format!("- {}\n", localizer.translate("report.cognitive_clarity", &[("value", &clarity)])));
+                if !self.patterns_detected.is_empty() {
+                    out.push_str(&format!("\n## {}\n", localizer.translate("report.patterns", &[])));
+                    for pattern in &self.patterns_detected {
+                        out.push_str(&format!("- {} (x{})\n", pattern.description, pattern.frequency));
+                    }
+                }
+                if !self.suggestions.is_empty() {
+                    out.push_str(&format!("\n## {}\n", localizer.translate("report.suggestions", &[])));
+                    for suggestion in &self.suggestions {
+                        out.push_str(&format!("- {}: {}\n", suggestion.action.description, suggestion.expected_benefit));
+                    }
+                }
+                for section in &self.plugin_sections {
+                    // Title and id come from the plugin, like the section body
+                    let title = PluginRegistry::escape_markdown(&section.title);
+                    out.push_str(&format!("\n## {} ({})\n", title, PluginRegistry::escape_markdown(&section.plugin_id)));
+                    out.push_str(&section.markdown);
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    /// Set habit evolution rate from the habit tracker (trailing 30 days)
+    pub fn apply_habit_evolution(&mut self, tracker: &HabitTracker, now: i64) {
+        self.metrics.habit_evolution_rate = tracker.habit_evolution_rate(now, 30 * 86400);
+    }
+
+    /// Add meeting load insights (heavy load, back-to-back streaks, slow refocus)
+    pub fn add_meeting_insights(&mut self, stats: &MeetingLoadStats, localizer: &Localizer) {
+        if stats.load_pct >= 50.0 {
+            self.patterns_detected.push(PatternInsight {
+                pattern_type: PatternType::AttentionFragmentation,
+                description: localizer.translate("insight.meeting_load", &[
+                    ("pct", &localizer.format_number(stats.load_pct, 0)),
+                    ("duration", &localizer.format_duration(stats.meeting_minutes)),
+                ]),
+                frequency: stats.meeting_count,
+                impact_score: stats.meeting_minutes,
+            });
+        }
+        if stats.back_to_back_streaks > 0 {
+            self.patterns_detected.push(PatternInsight {
+                pattern_type: PatternType::AttentionFragmentation,
+                description: localizer.translate("insight.back_to_back", &[("duration", &localizer.format_duration(stats.longest_block_minutes))]),
+                frequency: stats.back_to_back_streaks,
+                impact_score: stats.longest_block_minutes,
+            });
+        }
+        if let Some(refocus) = stats.avg_refocus_minutes.filter(|m| *m >= 10.0) {
+            self.patterns_detected.push(PatternInsight {
+                pattern_type: PatternType::AttentionFragmentation,
+                description: localizer.translate("insight.refocus", &[("duration", &localizer.format_duration(refocus))]),
+                frequency: stats.meeting_count,
+                impact_score: refocus,
+            });
+        }
+    }
 }
 
 /// Pattern insight from rule-based analysis
@@ -36,17 +136,48 @@ pub struct ActionSuggestion {
     pub confidence: Confidence,
 }
 
+/// Running totals for the report of the day in progress
+#[derive(Debug, Clone, Default)]
+struct LiveReportState {
+    date: Option<String>,
+    observation_count: usize,
+    time_saved: f64,
+    focus_minutes: f64,
+    session_minutes: f64,
+    patterns: Vec<PatternInsight>,
+    pattern_index: HashMap<String, usize>, // Sequence key -> index into patterns
+    suggestions: Vec<ActionSuggestion>,
+}
+
 /// Report generator using rule-based logic
 /// Source: Athenos_AI_Strategy.md#L102
 pub struct ReportGenerator {
     feature_store: FeatureStore,
+    localizer: Localizer,
+    live: LiveReportState,
 }
 
 impl ReportGenerator {
     /// Create new report generator
     pub fn new(feature_store: FeatureStore) -> Self {
         info!("ReportGenerator::new: Creating report generator");
-        Self { feature_store }
+        Self { feature_store, localizer: Localizer::default(), live: LiveReportState::default() }
+    }
+
+    /// Observations the report is built from (also the source of backup snapshots)
+    pub fn feature_store(&self) -> &FeatureStore {
+        &self.feature_store
+    }
+
+    /// Mutable access for purging consent-derived metrics
+    pub fn feature_store_mut(&mut self) -> &mut FeatureStore {
+        &mut self.feature_store
+    }
+
+    /// Generate insight text in the user's locale and units
+    pub fn set_locale(&mut self, prefs: LocalePreferences) {
+        info!("ReportGenerator::set_locale: {:?}", prefs.locale);
+        self.localizer = Localizer::new(prefs);
     }
 
     /// Generate daily report from observations
@@ -60,28 +191,11 @@ impl ReportGenerator {
         
         // Rule-based pattern detection
         for obs in observations {
-            // Detect workflow sequence pattern
-            if obs.observation.len() >= 3 {
-                patterns.push(PatternInsight {
-                    pattern_type: PatternType::WorkflowSequence,
-                    description: format!("Repeated sequence: {}", obs.observation.join(" → ")),
-                    frequency: obs.metrics.get("repeat_count").map(|v| *v as usize).unwrap_or(1),
-                    impact_score: obs.metrics.get("time_to_first_code_min").copied().unwrap_or(0.0),
-                });
-            }
-            
-            // Generate suggestions based on confidence and risk
-            if obs.action.confidence >= Confidence::Medium && obs.action.risk <= RiskCategory::Low {
-                if let Some(saved) = obs.expected_outcome.get("time_saved_min") {
-                    time_saved += saved;
-                }
-                
-                suggestions.push(ActionSuggestion {
-                    action: obs.action.clone(),
-                    expected_benefit: format!("Expected to save {} minutes", 
-                        obs.expected_outcome.get("time_saved_min").copied().unwrap_or(0.0)),
-                    confidence: obs.action.confidence.clone(),
-                });
+            let (pattern, suggestion) = self.observation_insights(obs);
+            patterns.extend(pattern);
+            if let Some((suggestion, saved)) = suggestion {
+                time_saved += saved;
+                suggestions.push(suggestion);
             }
         }
         
@@ -89,30 +203,331 @@ impl ReportGenerator {
         let obs_ids: Vec<String> = observations.iter().map(|o| o.id.clone()).collect();
         let focus_stability = self.feature_store.compute_focus_stability(&obs_ids);
         
+        Self::build_report(chrono::Utc::now().format("%Y-%m-%d").to_string(), patterns, suggestions, time_saved, focus_stability)
+    }
+
+    /// Rule-based insights for one observation: a pattern, and a suggestion with its time saved
+    fn observation_insights(&self, obs: &Observation) -> (Option<PatternInsight>, Option<(ActionSuggestion, f64)>) {
+        // Detect workflow sequence pattern
+        let pattern = (obs.observation.len() >= 3).then(|| PatternInsight {
+            pattern_type: PatternType::WorkflowSequence,
+            description: self.localizer.translate("insight.repeated_sequence", &[("sequence", &obs.observation.join(" → "))]),
+            frequency: obs.metrics.get("repeat_count").map(|v| *v as usize).unwrap_or(1),
+            impact_score: obs.metrics.get("time_to_first_code_min").copied().unwrap_or(0.0),
+        });
+        
+        // Generate suggestions based on confidence and risk
+        let suggestion = (obs.action.confidence >= Confidence::Medium && obs.action.risk <= RiskCategory::Low).then(|| {
+            let saved = obs.expected_outcome.get("time_saved_min").copied().unwrap_or(0.0);
+            let suggestion = ActionSuggestion {
+                action: obs.action.clone(),
+                expected_benefit: self.localizer.translate("insight.expected_savings", &[("duration", &self.localizer.format_duration(saved))]),
+                confidence: obs.action.confidence.clone(),
+            };
+            (suggestion, saved)
+        });
+        (pattern, suggestion)
+    }
+
+    fn build_report(date: String, patterns: Vec<PatternInsight>, suggestions: Vec<ActionSuggestion>, time_saved: f64, focus_stability: f64) -> DailyReport {
         // Compute cognitive metrics (rule-based estimates)
         let metrics = CognitiveMetrics {
             cognitive_clarity_index: focus_stability / 100.0 * 0.8, // Simplified
             emotional_resilience_score: 0.7, // Placeholder
-            habit_evolution_rate: 0.1, // Placeholder
+            habit_evolution_rate: 0.0, // Set from long-term history via apply_habit_evolution
             focus_stability_pct: focus_stability,
             time_saved_minutes: time_saved,
         };
         
         DailyReport {
-            date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+            date,
             metrics,
             patterns_detected: patterns,
             suggestions,
             time_saved_minutes: time_saved,
             focus_stability_pct: focus_stability,
+            project: None,
+            plugin_sections: Vec::new(),
+        }
+    }
+
+    /// Fold one observation into today's live report
+    /// Observations from a later day start a new report, earlier days are ignored; repeated sequences accumulate frequency
+    pub fn ingest_observation(&mut self, obs: &Observation) {
+        let date = chrono::DateTime::from_timestamp(obs.timestamp, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        match self.live.date.as_ref() {
+            Some(current) if date > *current => {
+                info!("ReportGenerator::ingest_observation: New day {}, resetting live report", date);
+                self.live = LiveReportState::default();
+            }
+            Some(current) if date < *current => return, // Belongs to a report already closed
+            _ => {}
+        }
+        self.live.date.get_or_insert(date);
+        self.live.observation_count += 1;
+
+        if let Some(metrics) = self.feature_store.get_metrics(&obs.id) {
+            self.live.focus_minutes += metrics.focus_duration_min;
+            self.live.session_minutes += metrics.session_duration_min;
+        }
+
+        let (pattern, suggestion) = self.observation_insights(obs);
+        if let Some(pattern) = pattern {
+            let key = obs.observation.join("\u{1f}");
+            match self.live.pattern_index.get(&key) {
+                Some(&idx) => {
+                    let existing = &mut self.live.patterns[idx];
+                    existing.frequency += pattern.frequency;
+                    existing.impact_score = existing.impact_score.max(pattern.impact_score);
+                }
+                None => {
+                    self.live.pattern_index.insert(key, self.live.patterns.len());
+                    self.live.patterns.push(pattern);
+                }
+            }
+        }
+        if let Some((suggestion, saved)) = suggestion {
+            self.live.time_saved += saved;
+            self.live.suggestions.push(suggestion);
+        }
+    }
+
+    /// Current state of today's report, from running totals
+    pub fn snapshot_report(&self) -> DailyReport {
+        info!("ReportGenerator::snapshot_report: Live report over {} observations", self.live.observation_count);
+        let focus_stability = if self.live.session_minutes > 0.0 {
+            self.live.focus_minutes / self.live.session_minutes * 100.0
+        } else {
+            0.0
+        };
+        let date = self.live.date.clone().unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
+        Self::build_report(date, self.live.patterns.clone(), self.live.suggestions.clone(), self.live.time_saved, focus_stability)
+    }
+
+    /// Generate one report per project (observations without a project form the global report)
+    pub fn generate_project_reports(&self, observations: &[Observation]) -> Vec<DailyReport> {
+        let mut projects: Vec<Option<String>> = observations.iter().map(|o| o.project.clone()).collect();
+        projects.sort();
+        projects.dedup();
+        
+        projects
+            .into_iter()
+            .map(|project| {
+                let scoped: Vec<Observation> = observations.iter().filter(|o| o.project == project).cloned().collect();
+                let mut report = self.generate_daily_report(&scoped);
+                report.project = project;
+                report
+            })
+            .collect()
+    }
+}
+
+/// Report delivery cadence
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCadence {
+    Daily,
+    Weekly, // Mondays
+}
+
+/// Report schedule configuration (hours are UTC)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportScheduleConfig {
+    pub daily_hour: Option<u32>,
+    pub weekly_hour: Option<u32>,
+    pub format: ReportFormat,
+    #[serde(default)]
+    pub locale: LocalePreferences,
+}
+
+impl Default for ReportScheduleConfig {
+    fn default() -> Self {
+        Self {
+            daily_hour: Some(18),
+            weekly_hour: Some(9),
+            format: ReportFormat::Markdown,
+            locale: LocalePreferences::default(),
+        }
+    }
+}
+
+/// Delivery outcome
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportDeliveryStatus {
+    Delivered,
+    Skipped, // No observations in the report window
+    Failed,
+}
+
+/// Record of one scheduled report run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportDelivery {
+    pub cadence: ReportCadence,
+    pub scheduled_for: i64,
+    pub status: ReportDeliveryStatus,
+    pub channels: Vec<String>,
+}
+
+/// Scheduled report generation and delivery through notification sinks
+pub struct ReportScheduler {
+    config: ReportScheduleConfig,
+    last_run: HashMap<ReportCadence, i64>, // cadence -> last slot handled
+    deliveries: Vec<ReportDelivery>,
+}
+
+impl ReportScheduler {
+    /// Create report scheduler
+    pub fn new(config: ReportScheduleConfig) -> Self {
+        info!("ReportScheduler::new: daily {:?}, weekly {:?}", config.daily_hour, config.weekly_hour);
+        Self {
+            config,
+            last_run: HashMap::new(),
+            deliveries: Vec::new(),
+        }
+    }
+
+    /// Most recent scheduled slot at or before `now` for the cadence
+    fn latest_slot(&self, cadence: ReportCadence, now: i64) -> Option<i64> {
+        let day_start = now - now.rem_euclid(86400);
+        match cadence {
+            ReportCadence::Daily => {
+                let slot = day_start + self.config.daily_hour? as i64 * 3600;
+                Some(if slot > now { slot - 86400 } else { slot })
+            }
+            ReportCadence::Weekly => {
+                let days_since_monday = (now.div_euclid(86400) + 3).rem_euclid(7); // 1970-01-01 was a Thursday
+                let slot = day_start - days_since_monday * 86400 + self.config.weekly_hour? as i64 * 3600;
+                Some(if slot > now { slot - 7 * 86400 } else { slot })
+            }
+        }
+    }
+
+    /// Generate and deliver every report whose slot has passed since the last run
+    /// Reports include sections contributed by Visualization plugins for the cadence
+    pub fn run_due(
+        &mut self,
+        now: i64,
+        generator: &ReportGenerator,
+        observations: &[Observation],
+        router: &mut NotificationRouter,
+        analytics: &mut AnalyticsAggregator,
+        plugins: &mut PluginRegistry,
+    ) -> Vec<ReportDelivery> {
+        let mut runs = Vec::new();
+
+        for cadence in [ReportCadence::Daily, ReportCadence::Weekly] {
+            let Some(slot) = self.latest_slot(cadence, now) else { continue };
+            if self.last_run.get(&cadence).map(|last| *last >= slot).unwrap_or(false) {
+                continue;
+            }
+            self.last_run.insert(cadence, slot);
+
+            let window = match cadence {
+                ReportCadence::Daily => 86400,
+                ReportCadence::Weekly => 7 * 86400,
+            };
+            let in_window: Vec<Observation> = observations
+                .iter()
+                .filter(|o| o.timestamp >= slot - window && o.timestamp < slot)
+                .cloned()
+                .collect();
+
+            let delivery = if in_window.is_empty() {
+                info!("ReportScheduler::run_due: Skipping {:?} report, no data", cadence);
+                ReportDelivery { cadence, scheduled_for: slot, status: ReportDeliveryStatus::Skipped, channels: Vec::new() }
+            } else {
+                let mut report = generator.generate_daily_report(&in_window);
+                report.plugin_sections = plugins.render_report_sections(cadence);
+                self.deliver(cadence, slot, report, router)
+            };
+
+            let status = match delivery.status {
+                ReportDeliveryStatus::Delivered => "delivered",
+                ReportDeliveryStatus::Skipped => "skipped",
+                ReportDeliveryStatus::Failed => "failed",
+            };
+            let cadence_name = match cadence {
+                ReportCadence::Daily => "daily",
+                ReportCadence::Weekly => "weekly",
+            };
+            analytics.record_metric(format!("report.{}.{}", cadence_name, status), 1.0, MetricCategory::Operations);
+
+            self.deliveries.push(delivery.clone());
+            runs.push(delivery);
+        }
+
+        runs
+    }
+
+    fn deliver(&self, cadence: ReportCadence, slot: i64, report: DailyReport, router: &mut NotificationRouter) -> ReportDelivery {
+        let failed = |channels| ReportDelivery { cadence, scheduled_for: slot, status: ReportDeliveryStatus::Failed, channels };
+
+        let localizer = Localizer::new(self.config.locale.clone());
+        let body = match report.render_localized(self.config.format, &localizer) {
+            Ok(body) => body,
+            Err(e) => {
+                info!("ReportScheduler::deliver: {}", e);
+                return failed(Vec::new());
+            }
+        };
+        let notification = Notification {
+            source: NotificationSource::Report,
+            severity: NotificationSeverity::Info,
+            title: localizer.translate(
+                match cadence {
+                    ReportCadence::Daily => "report.daily_title",
+                    ReportCadence::Weekly => "report.weekly_title",
+                },
+                &[("date", &localizer.format_iso_date(&report.date))],
+            ),
+            body,
+            created_at: slot,
+        };
+
+        let delivered: Vec<String> = router
+            .dispatch(&notification)
+            .into_iter()
+            .filter(|r| r.delivered)
+            .map(|r| r.channel)
+            .collect();
+        if delivered.is_empty() {
+            return failed(delivered);
         }
+        ReportDelivery { cadence, scheduled_for: slot, status: ReportDeliveryStatus::Delivered, channels: delivered }
+    }
+
+    /// Get delivery history
+    pub fn get_deliveries(&self) -> &[ReportDelivery] {
+        &self.deliveries
+    }
+}
+
+impl Default for ReportScheduler {
+    fn default() -> Self {
+        Self::new(ReportScheduleConfig::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::notify::NotificationSink;
+
+    /// Accepts every notification on the email channel
+    struct AcceptingEmailSink;
+
+    impl NotificationSink for AcceptingEmailSink {
+        fn channel(&self) -> &str {
+            "email"
+        }
+
+        fn send(&mut self, _notification: &Notification) -> Result<(), String> {
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_report_generation() {
@@ -141,6 +556,7 @@ mod tests {
             expected_outcome: expected,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         }];
         
         let report = generator.generate_daily_report(&observations);
@@ -148,5 +564,210 @@ mod tests {
         assert_eq!(report.time_saved_minutes, 11.0);
         assert!(!report.patterns_detected.is_empty());
     }
-}
 
+    #[test]
+    fn test_project_reports() {
+        let generator = ReportGenerator::new(FeatureStore::new());
+        let mut scoped = observation_at(1_700_000_000);
+        scoped.project = Some("git:athenos-ai".to_string());
+        let observations = vec![observation_at(1_700_000_100), scoped.clone(), scoped];
+        
+        let reports = generator.generate_project_reports(&observations);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].project, None);
+        assert_eq!(reports[1].project, Some("git:athenos-ai".to_string()));
+        assert_eq!(reports[1].suggestions.len(), 2);
+        assert!(reports[1].render(ReportFormat::Markdown).unwrap().contains("(git:athenos-ai)"));
+    }
+
+    #[test]
+    fn test_meeting_insights() {
+        let mut report = ReportGenerator::new(FeatureStore::new()).generate_daily_report(&[]);
+        report.add_meeting_insights(&MeetingLoadStats {
+            day_start: 0,
+            meeting_count: 6,
+            meeting_minutes: 300.0,
+            load_pct: 62.5,
+            back_to_back_streaks: 2,
+            longest_block_minutes: 150.0,
+            avg_refocus_minutes: Some(4.0),
+        }, &Localizer::default());
+
+        assert_eq!(report.patterns_detected.len(), 2);
+        assert!(report.render(ReportFormat::Markdown).unwrap().contains("longest block 150 min"));
+    }
+
+    #[test]
+    fn test_localized_markdown_report() {
+        use crate::locale::{ClockFormat, DurationUnit, Locale};
+        let mut generator = ReportGenerator::new(FeatureStore::new());
+        let prefs = LocalePreferences { locale: Locale::DeDe, clock: ClockFormat::H24, duration_unit: DurationUnit::Hours, timezone: "Europe/Berlin".to_string() };
+        generator.set_locale(prefs.clone());
+        let mut observation = observation_at(0);
+        observation.expected_outcome.insert("time_saved_min".to_string(), 11.0);
+        let mut report = generator.generate_daily_report(&[observation]);
+        report.date = "2024-01-31".to_string();
+        
+        let markdown = report.render_localized(ReportFormat::Markdown, &Localizer::new(prefs)).unwrap();
+        assert!(markdown.starts_with("# Kognitiver Bericht 31.01.2024"));
+        assert!(markdown.contains("- Gesparte Zeit: 0,2 h"));
+        assert!(markdown.contains("## Vorschläge"));
+        assert_eq!(report.suggestions[0].expected_benefit, "Voraussichtlich 0,2 h gespart");
+        
+        // Default rendering is unchanged en-US
+        assert!(report.render(ReportFormat::Markdown).unwrap().contains("- Time saved: 11 min"));
+    }
+
+    #[test]
+    fn test_plugin_sections_rendered() {
+        use crate::plugin::{PluginCapability, PluginMetadata, RenderHint, ReportSection, ReportSectionData};
+        let mut plugins = PluginRegistry::new();
+        plugins.register_plugin(PluginMetadata {
+            id: "ci_status".to_string(),
+            name: "CI Status".to_string(),
+            version: "1.0.0".to_string(),
+            author: "Test".to_string(),
+            capabilities: vec![PluginCapability::Visualization],
+            description: "Build health".to_string(),
+        });
+        plugins.register_report_section(ReportSection {
+            plugin_id: "ci_status".to_string(),
+            title: "Build health".to_string(),
+            data: ReportSectionData::Metric { label: "Green builds".to_string(), value: 0.92, unit: Some("ratio".to_string()) },
+            hint: RenderHint::Inline,
+            cadences: vec![ReportCadence::Weekly],
+        }).unwrap();
+
+        let mut report = ReportGenerator::new(FeatureStore::new()).generate_daily_report(&[]);
+        report.plugin_sections = plugins.render_report_sections(ReportCadence::Weekly);
+        let markdown = report.render(ReportFormat::Markdown).unwrap();
+        assert!(markdown.contains("## Build health (ci\\_status)\n- Green builds: 0.92 ratio"));
+
+        report.plugin_sections[0].title = "[Build](https://evil.example)\n# Injected".to_string();
+        let markdown = report.render(ReportFormat::Markdown).unwrap();
+        assert!(markdown.contains("## \\[Build\\]\\(https://evil.example\\) \\# Injected (ci\\_status)\n"));
+    }
+
+    fn observation_at(timestamp: i64) -> Observation {
+        Observation {
+            id: format!("obs_{}", timestamp),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_live_report_matches_batch_totals() {
+        let mut store = FeatureStore::new();
+        let mut morning = observation_at(1_699_950_000); // 2023-11-14 08:20 UTC
+        morning.observation = vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()];
+        morning.expected_outcome.insert("time_saved_min".to_string(), 11.0);
+        let mut noon = morning.clone();
+        noon.id = "obs_noon".to_string();
+        noon.timestamp += 3 * 3600;
+        for (id, focus) in [(&morning.id, 30.0), (&noon.id, 10.0)] {
+            store.store_metrics(id.clone(), crate::local_stack::TemporalMetrics {
+                time_to_first_action_min: 0.0,
+                focus_duration_min: focus,
+                context_switch_count: 0,
+                repeat_count: 1,
+                session_duration_min: 40.0,
+            });
+        }
+        let mut generator = ReportGenerator::new(store);
+
+        generator.ingest_observation(&morning);
+        assert_eq!(generator.snapshot_report().time_saved_minutes, 11.0);
+        generator.ingest_observation(&noon);
+
+        let live = generator.snapshot_report();
+        let batch = generator.generate_daily_report(&[morning, noon]);
+        assert_eq!(live.time_saved_minutes, batch.time_saved_minutes);
+        assert_eq!(live.focus_stability_pct, batch.focus_stability_pct);
+        assert_eq!(live.suggestions.len(), 2);
+        assert_eq!(live.patterns_detected.len(), 1); // Same sequence merged
+        assert_eq!(live.patterns_detected[0].frequency, 2);
+        assert_eq!(live.date, "2023-11-14");
+    }
+
+    #[test]
+    fn test_live_report_resets_on_new_day() {
+        let mut generator = ReportGenerator::new(FeatureStore::new());
+        let mut today = observation_at(1_700_000_000);
+        today.expected_outcome.insert("time_saved_min".to_string(), 5.0);
+        generator.ingest_observation(&today);
+
+        let mut tomorrow = today.clone();
+        tomorrow.timestamp += 86_400;
+        generator.ingest_observation(&tomorrow);
+        generator.ingest_observation(&today); // Late arrival for a closed day
+
+        let live = generator.snapshot_report();
+        assert_eq!(live.date, "2023-11-15");
+        assert_eq!(live.time_saved_minutes, 5.0);
+    }
+
+    #[test]
+    fn test_scheduled_delivery_and_skip() {
+        const MONDAY: i64 = 1_700_438_400; // 2023-11-20 00:00 UTC
+        let generator = ReportGenerator::new(FeatureStore::new());
+        let mut router = NotificationRouter::default();
+        router.register_sink(Box::new(AcceptingEmailSink));
+        let mut analytics = AnalyticsAggregator::new();
+        let mut plugins = PluginRegistry::new();
+        let mut scheduler = ReportScheduler::new(ReportScheduleConfig {
+            daily_hour: Some(18),
+            weekly_hour: Some(9),
+            format: ReportFormat::Markdown,
+            locale: LocalePreferences::default(),
+        });
+        let observations = vec![observation_at(MONDAY + 10 * 3600)];
+
+        // Monday 10:00: daily slot is Sunday 18:00 (no data), weekly slot is Monday 09:00 (no data)
+        let runs = scheduler.run_due(MONDAY + 10 * 3600, &generator, &observations, &mut router, &mut analytics, &mut plugins);
+        assert!(runs.iter().all(|r| r.status == ReportDeliveryStatus::Skipped));
+        assert_eq!(runs.len(), 2);
+
+        // Same slot is not re-run
+        assert!(scheduler.run_due(MONDAY + 11 * 3600, &generator, &observations, &mut router, &mut analytics, &mut plugins).is_empty());
+
+        // Monday 18:00 daily report covers the observation
+        let runs = scheduler.run_due(MONDAY + 18 * 3600, &generator, &observations, &mut router, &mut analytics, &mut plugins);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].cadence, ReportCadence::Daily);
+        assert_eq!(runs[0].status, ReportDeliveryStatus::Delivered);
+        assert_eq!(runs[0].channels, vec!["email"]);
+        assert!(analytics.get_metrics_by_category(MetricCategory::Operations).iter().any(|m| m.name == "report.daily.delivered"));
+    }
+
+    #[test]
+    fn test_undeliverable_report_fails() {
+        let generator = ReportGenerator::new(FeatureStore::new());
+        let mut router = NotificationRouter::default(); // No sinks registered
+        let mut analytics = AnalyticsAggregator::new();
+        let mut plugins = PluginRegistry::new();
+        let mut scheduler = ReportScheduler::new(ReportScheduleConfig {
+            daily_hour: Some(0),
+            weekly_hour: None,
+            format: ReportFormat::Json,
+            locale: LocalePreferences::default(),
+        });
+
+        let now = 1_700_438_400 + 3600;
+        let runs = scheduler.run_due(now, &generator, &[observation_at(now - 7200)], &mut router, &mut analytics, &mut plugins);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ReportDeliveryStatus::Failed);
+    }
+}
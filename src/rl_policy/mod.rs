@@ -3,10 +3,130 @@
 /// Deploy reinforcement learning policies tuned by real user outcomes
 
 use crate::types::*;
+use crate::signature::PatternSignature;
+use crate::suppression::SuppressionList;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+/// Current persisted policy schema
+/// v1: Q-table, hyperparameters and the reward weights it was trained under; v2: selection strategy and bandit arms
+pub const RL_POLICY_SCHEMA_VERSION: u32 = 2;
+
+/// Observation metrics forming the bandit context (log-scaled, plus a bias term)
+pub const CONTEXT_FEATURES: [&str; 4] = ["context_switch_count", "focus_duration_min", "error_count", "repeat_count"];
+
+/// Context vector length
+const CONTEXT_DIM: usize = CONTEXT_FEATURES.len() + 1;
+
+/// How the policy picks an action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum SelectionStrategy {
+    /// Flat Q-table keyed by signature, intent and profile
+    #[default]
+    EpsilonGreedy,
+    /// LinUCB contextual bandit over observation metrics; `alpha` scales the exploration bonus
+    LinUcb { alpha: f64 },
+}
+
+/// LinUCB arm: one action type within an intent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BanditArm {
+    pub action: Action,
+    pub a_inv: Vec<Vec<f64>>, // Inverse of the ridge design matrix (starts as identity)
+    pub b: Vec<f64>,          // Reward-weighted context sum
+    pub pulls: usize,
+}
+
+impl BanditArm {
+    fn new(action: Action) -> Self {
+        let a_inv = (0..CONTEXT_DIM).map(|i| (0..CONTEXT_DIM).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+        Self { action, a_inv, b: vec![0.0; CONTEXT_DIM], pulls: 0 }
+    }
+
+    fn a_inv_times(&self, x: &[f64]) -> Vec<f64> {
+        self.a_inv.iter().map(|row| row.iter().zip(x).map(|(a, v)| a * v).sum()).collect()
+    }
+
+    /// Expected reward for a context
+    fn estimate(&self, x: &[f64]) -> f64 {
+        let theta = self.a_inv_times(&self.b);
+        theta.iter().zip(x).map(|(t, v)| t * v).sum()
+    }
+
+    /// Expected reward plus the upper-confidence exploration bonus
+    fn upper_bound(&self, x: &[f64], alpha: f64) -> f64 {
+        let variance: f64 = self.a_inv_times(x).iter().zip(x).map(|(a, v)| a * v).sum();
+        self.estimate(x) + alpha * variance.max(0.0).sqrt()
+    }
+
+    /// Rank-one update of A⁻¹ (Sherman-Morrison) and b
+    fn update(&mut self, x: &[f64], reward: f64) {
+        let a_inv_x = self.a_inv_times(x);
+        let denominator = 1.0 + a_inv_x.iter().zip(x).map(|(a, v)| a * v).sum::<f64>();
+        for (i, row) in self.a_inv.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell -= a_inv_x[i] * a_inv_x[j] / denominator;
+            }
+        }
+        for (b, v) in self.b.iter_mut().zip(x) {
+            *b += reward * v;
+        }
+        self.pulls += 1;
+    }
+}
+
+/// Reward weights applied to user outcomes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardConfig {
+    pub accept_reward: f64,         // Suggestion accepted
+    pub ignore_penalty: f64,        // Subtracted when a suggestion is ignored
+    pub time_saved_weight: f64,     // Per minute saved
+    pub error_reduction_bonus: f64, // Error rate went down
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        Self {
+            accept_reward: 10.0,
+            ignore_penalty: 2.0,
+            time_saved_weight: 0.5,
+            error_reduction_bonus: 5.0,
+        }
+    }
+}
+
+impl RewardConfig {
+    /// Reject weights that would invert the learning signal
+    pub fn validate(&self) -> Result<(), String> {
+        let weights = [self.accept_reward, self.ignore_penalty, self.time_saved_weight, self.error_reduction_bonus];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err("Reward weights must be finite and non-negative".to_string());
+        }
+        if self.accept_reward == 0.0 {
+            return Err("accept_reward must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Persisted policy file contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicySnapshot {
+    pub schema_version: u32,
+    pub saved_at: i64,
+    pub learning_rate: f64,
+    pub discount_factor: f64,
+    pub epsilon: f64,
+    pub reward: RewardConfig,
+    pub q_table: HashMap<String, PolicyAction>,
+    #[serde(default)]
+    pub strategy: SelectionStrategy,
+    #[serde(default)]
+    pub arms: HashMap<String, BanditArm>,
+}
+
 /// Policy action with Q-value
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyAction {
@@ -22,6 +142,11 @@ pub struct RLPolicy {
     learning_rate: f64,
     discount_factor: f64,
     epsilon: f64, // Exploration rate
+    reward: RewardConfig,
+    strategy: SelectionStrategy,
+    arms: HashMap<String, BanditArm>, // LinUCB arms keyed by intent and action type
+    suppression: SuppressionList,
+    path: Option<PathBuf>, // Saved here after every update when opened from a file
 }
 
 impl RLPolicy {
@@ -33,7 +158,140 @@ impl RLPolicy {
             learning_rate: 0.1,
             discount_factor: 0.9,
             epsilon: 0.1, // 10% exploration
+            reward: RewardConfig::default(),
+            strategy: SelectionStrategy::EpsilonGreedy,
+            arms: HashMap::new(),
+            suppression: SuppressionList::new(),
+            path: None,
+        }
+    }
+
+    /// Create policy with an explicit selection strategy
+    pub fn with_strategy(strategy: SelectionStrategy) -> Self {
+        info!("RLPolicy::with_strategy: Using {:?}", strategy);
+        let mut policy = Self::new();
+        policy.strategy = strategy;
+        policy
+    }
+
+    /// Current selection strategy
+    pub fn strategy(&self) -> &SelectionStrategy {
+        &self.strategy
+    }
+
+    /// Create policy with tuned reward weights
+    pub fn with_reward_config(reward: RewardConfig) -> Result<Self, String> {
+        reward.validate()?;
+        let mut policy = Self::new();
+        policy.reward = reward;
+        Ok(policy)
+    }
+
+    /// Replace reward weights; existing Q-values keep the scale they were learned under
+    pub fn set_reward_config(&mut self, reward: RewardConfig) -> Result<(), String> {
+        reward.validate()?;
+        info!("RLPolicy::set_reward_config: Updating reward weights");
+        self.reward = reward;
+        Ok(())
+    }
+
+    /// Current reward weights
+    pub fn reward_config(&self) -> &RewardConfig {
+        &self.reward
+    }
+
+    /// Snapshot the learned policy for persistence or shipping to another device
+    pub fn snapshot(&self, now: i64) -> PolicySnapshot {
+        PolicySnapshot {
+            schema_version: RL_POLICY_SCHEMA_VERSION,
+            saved_at: now,
+            learning_rate: self.learning_rate,
+            discount_factor: self.discount_factor,
+            epsilon: self.epsilon,
+            reward: self.reward.clone(),
+            q_table: self.q_table.clone(),
+            strategy: self.strategy.clone(),
+            arms: self.arms.clone(),
+        }
+    }
+
+    /// Restore a policy from a snapshot; the suppression list is not part of the snapshot
+    pub fn from_snapshot(snapshot: PolicySnapshot) -> Result<Self, String> {
+        if snapshot.schema_version > RL_POLICY_SCHEMA_VERSION {
+            return Err(format!(
+                "RL policy schema v{} is newer than supported v{}; update Athenos",
+                snapshot.schema_version, RL_POLICY_SCHEMA_VERSION
+            ));
+        }
+        snapshot.reward.validate()?;
+        info!("RLPolicy::from_snapshot: Restoring {} states (v{})", snapshot.q_table.len(), snapshot.schema_version);
+        Ok(Self {
+            q_table: snapshot.q_table,
+            learning_rate: snapshot.learning_rate,
+            discount_factor: snapshot.discount_factor,
+            epsilon: snapshot.epsilon,
+            reward: snapshot.reward,
+            strategy: snapshot.strategy,
+            arms: snapshot.arms,
+            suppression: SuppressionList::new(),
+            path: None,
+        })
+    }
+
+    /// Encode the policy as versioned JSON
+    pub fn to_json(&self, now: i64) -> Result<String, String> {
+        serde_json::to_string(&self.snapshot(now)).map_err(|e| format!("Failed to encode RL policy: {}", e))
+    }
+
+    /// Decode a policy from versioned JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let snapshot: PolicySnapshot = serde_json::from_str(json).map_err(|e| format!("Invalid RL policy: {}", e))?;
+        Self::from_snapshot(snapshot)
+    }
+
+    /// Load a persisted policy; a missing file yields a fresh policy
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_json(&json),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                info!("RLPolicy::load: No policy at {}, starting fresh", path.display());
+                Ok(Self::new())
+            }
+            Err(e) => Err(format!("Failed to read RL policy {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Load the policy at `path` and keep it saved there after every update
+    pub fn open(path: PathBuf) -> Result<Self, String> {
+        let mut policy = Self::load(&path)?;
+        policy.path = Some(path);
+        Ok(policy)
+    }
+
+    /// Save the policy at the current schema version (atomic replace)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
         }
+        let json = self.to_json(chrono::Utc::now().timestamp())?;
+        // Write-then-rename so a crash never leaves a truncated policy
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+        info!("RLPolicy::save: Saved {} states to {}", self.q_table.len(), path.display());
+        Ok(())
+    }
+
+    /// Share a suppression list; suppressed sequences are never suggested
+    pub fn set_suppression_list(&mut self, suppression: SuppressionList) {
+        self.suppression = suppression;
+    }
+
+    fn is_suppressed(&self, observation: &Observation) -> bool {
+        let signature = SuppressionList::sequence_signature(&observation.observation);
+        self.suppression.is_suppressed(&signature, chrono::Utc::now().timestamp())
     }
 
     /// Update policy from user outcome
@@ -52,6 +310,14 @@ impl RLPolicy {
         
         let new_q = current_q + self.learning_rate * (reward - current_q);
         
+        // Explicit rejection (not accepted, ignored or modified) counts towards suppression
+        if !outcome.accepted && !outcome.ignored && !outcome.modified {
+            let signature = SuppressionList::sequence_signature(&observation.observation);
+            if let Err(e) = self.suppression.record_rejection(&signature, chrono::Utc::now().timestamp()) {
+                info!("RLPolicy::update_from_outcome: Failed to record rejection: {}", e);
+            }
+        }
+        
         let policy_action = PolicyAction {
             action: observation.action.clone(),
             q_value: new_q,
@@ -62,11 +328,28 @@ impl RLPolicy {
         };
         
         self.q_table.insert(state_key, policy_action);
+        
+        // Bandit arms learn in every mode so switching strategy keeps the history
+        let context = Self::context_vector(observation);
+        let scaled_reward = reward / self.reward.accept_reward;
+        self.arms
+            .entry(Self::arm_key(&observation.intent, &observation.action))
+            .or_insert_with(|| BanditArm::new(observation.action.clone()))
+            .update(&context, scaled_reward);
+
+        if let Some(path) = &self.path {
+            if let Err(e) = self.save(path) {
+                info!("RLPolicy::update_from_outcome: Failed to persist policy: {}", e);
+            }
+        }
     }
 
     /// Select action using epsilon-greedy policy
     /// Source: Athenos_AI_Strategy.md#L132
     pub fn select_action(&self, observation: &Observation) -> Action {
+        if let SelectionStrategy::LinUcb { alpha } = self.strategy {
+            return self.bandit_action(observation, alpha);
+        }
         let state_key = self.get_state_key(observation);
         
         // Epsilon-greedy: explore with probability epsilon
@@ -83,26 +366,96 @@ impl RLPolicy {
         }
     }
 
+    /// Select action for a suggestion, or None if the observed sequence is suppressed
+    pub fn suggest_action(&self, observation: &Observation) -> Option<Action> {
+        if self.is_suppressed(observation) {
+            info!("RLPolicy::suggest_action: Suppressed suggestion for {}", observation.id);
+            return None;
+        }
+        Some(self.select_action(observation))
+    }
+
+    /// Select best known action without exploration (deterministic, used for replay evaluation)
+    pub fn greedy_action(&self, observation: &Observation) -> Action {
+        if let SelectionStrategy::LinUcb { .. } = self.strategy {
+            return self.bandit_action(observation, 0.0);
+        }
+        self.q_table
+            .get(&self.get_state_key(observation))
+            .map(|pa| pa.action.clone())
+            .unwrap_or_else(|| observation.action.clone())
+    }
+
+    /// Predict acceptance probability (0.0 to 1.0) from the learned Q-value
+    pub fn predict_acceptance(&self, observation: &Observation) -> f64 {
+        if self.is_suppressed(observation) {
+            return 0.0;
+        }
+        if let SelectionStrategy::LinUcb { .. } = self.strategy {
+            if let Some(arm) = self.arms.get(&Self::arm_key(&observation.intent, &observation.action)) {
+                return arm.estimate(&Self::context_vector(observation)).clamp(0.0, 1.0);
+            }
+        }
+        self.q_table
+            .get(&self.get_state_key(observation))
+            .map(|pa| (pa.q_value / self.reward.accept_reward).clamp(0.0, 1.0))
+            .unwrap_or(0.5)
+    }
+
+    /// Highest upper-bound arm for the observation's intent; the observed action competes as an untried arm
+    fn bandit_action(&self, observation: &Observation, alpha: f64) -> Action {
+        let context = Self::context_vector(observation);
+        let prefix = format!("{:?}/", observation.intent);
+        let untried = BanditArm::new(observation.action.clone());
+        let own_key = Self::arm_key(&observation.intent, &observation.action);
+        let mut candidates: Vec<(&String, &BanditArm)> = self.arms.iter().filter(|(key, _)| key.starts_with(&prefix)).collect();
+        if !self.arms.contains_key(&own_key) {
+            candidates.push((&own_key, &untried));
+        }
+        // Sort by key first so ties resolve deterministically
+        candidates.sort_by(|a, b| a.0.cmp(b.0));
+        candidates
+            .into_iter()
+            .map(|(_, arm)| (arm.upper_bound(&context, alpha), arm))
+            .fold(None, |best: Option<(f64, &BanditArm)>, (score, arm)| match best {
+                Some((best_score, _)) if best_score >= score => best,
+                _ => Some((score, arm)),
+            })
+            .map(|(_, arm)| arm.action.clone())
+            .unwrap_or_else(|| observation.action.clone())
+    }
+
+    fn arm_key(intent: &Intent, action: &Action) -> String {
+        format!("{:?}/{:?}", intent, action.action_type)
+    }
+
+    /// Bias term followed by log-scaled context metrics (missing metrics count as zero)
+    fn context_vector(observation: &Observation) -> Vec<f64> {
+        std::iter::once(1.0)
+            .chain(CONTEXT_FEATURES.iter().map(|name| observation.metrics.get(*name).copied().unwrap_or(0.0).max(0.0).ln_1p()))
+            .collect()
+    }
+
     fn get_state_key(&self, observation: &Observation) -> String {
-        format!("{:?}_{:?}", observation.intent, observation.profile)
+        format!("{}_{:?}_{:?}", PatternSignature::from_observation(observation).key(), observation.intent, observation.profile)
     }
 
     fn compute_reward(&self, outcome: &Outcome) -> f64 {
         let mut reward = 0.0;
         
         if outcome.accepted {
-            reward += 10.0;
+            reward += self.reward.accept_reward;
         } else if outcome.ignored {
-            reward -= 2.0;
+            reward -= self.reward.ignore_penalty;
         }
         
         if let Some(time_saved) = outcome.time_saved_minutes {
-            reward += time_saved * 0.5; // Time saved bonus
+            reward += time_saved * self.reward.time_saved_weight; // Time saved bonus
         }
         
         if let Some(error_change) = outcome.error_rate_change {
             if error_change < 0.0 {
-                reward += 5.0; // Error reduction bonus
+                reward += self.reward.error_reduction_bonus; // Error reduction bonus
             }
         }
         
@@ -171,6 +524,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let outcome = Outcome {
@@ -205,10 +559,209 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let selected = policy.select_action(&observation);
         assert_eq!(selected.action_type, ActionType::AutomationMacro);
     }
-}
 
+    #[test]
+    fn test_rejections_suppress_suggestions() {
+        let mut policy = RLPolicy::new();
+        let observation = Observation {
+            id: "test_003".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let rejected = Outcome {
+            observation_id: "test_003".to_string(),
+            accepted: false,
+            ignored: false,
+            modified: false,
+            time_saved_minutes: None,
+            error_rate_change: None,
+            timestamp: 1234567890,
+        };
+        
+        policy.update_from_outcome(&observation, &rejected);
+        assert!(policy.suggest_action(&observation).is_some());
+        policy.update_from_outcome(&observation, &rejected);
+        assert!(policy.suggest_action(&observation).is_none());
+        assert_eq!(policy.predict_acceptance(&observation), 0.0);
+    }
+
+    fn accepted_outcome(time_saved: f64) -> Outcome {
+        Outcome {
+            observation_id: "test_004".to_string(),
+            accepted: true,
+            ignored: false,
+            modified: false,
+            time_saved_minutes: Some(time_saved),
+            error_rate_change: None,
+            timestamp: 1234567890,
+        }
+    }
+
+    fn shortcut_observation() -> Observation {
+        Observation {
+            id: "test_004".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string(), "Terminal".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::SandboxPatch,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        }
+    }
+
+    #[test]
+    fn test_reward_config_shapes_updates() {
+        let mut policy = RLPolicy::with_reward_config(RewardConfig {
+            accept_reward: 4.0,
+            time_saved_weight: 0.0,
+            ..RewardConfig::default()
+        })
+        .unwrap();
+        policy.update_from_outcome(&shortcut_observation(), &accepted_outcome(30.0));
+        
+        // Single update: learning_rate * accept_reward, time saved ignored
+        assert!((policy.get_statistics().avg_q_value - 0.4).abs() < 1e-9);
+        assert!((policy.predict_acceptance(&shortcut_observation()) - 0.1).abs() < 1e-9);
+        assert!(RLPolicy::with_reward_config(RewardConfig { ignore_penalty: -1.0, ..RewardConfig::default() }).is_err());
+    }
+
+    #[test]
+    fn test_policy_round_trips_through_file() {
+        let path = std::env::temp_dir().join(format!("athenos_rl_policy_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(RLPolicy::load(&path).unwrap().get_statistics().total_states, 0);
+        
+        let mut policy = RLPolicy::with_reward_config(RewardConfig { accept_reward: 20.0, ..RewardConfig::default() }).unwrap();
+        policy.update_from_outcome(&shortcut_observation(), &accepted_outcome(11.0));
+        policy.save(&path).unwrap();
+        
+        let restored = RLPolicy::load(&path).unwrap();
+        assert_eq!(restored.reward_config(), policy.reward_config());
+        assert_eq!(restored.get_statistics().avg_q_value, policy.get_statistics().avg_q_value);
+        assert_eq!(restored.greedy_action(&shortcut_observation()).action_type, ActionType::SandboxPatch);
+        std::fs::remove_file(&path).unwrap();
+
+        // An opened policy persists every update without an explicit save
+        let mut opened = RLPolicy::open(path.clone()).unwrap();
+        opened.update_from_outcome(&shortcut_observation(), &accepted_outcome(11.0));
+        assert_eq!(RLPolicy::load(&path).unwrap().get_statistics().total_states, 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let mut snapshot = RLPolicy::new().snapshot(0);
+        snapshot.schema_version = RL_POLICY_SCHEMA_VERSION + 1;
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(RLPolicy::from_json(&json).err().unwrap().contains("newer than supported"));
+        assert!(RLPolicy::from_json("{}").is_err());
+    }
+
+    fn contextual_observation(id: &str, switches: f64, action_type: ActionType) -> Observation {
+        let mut metrics = HashMap::new();
+        metrics.insert("context_switch_count".to_string(), switches);
+        metrics.insert("focus_duration_min".to_string(), if switches > 10.0 { 5.0 } else { 60.0 });
+        Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["IDE".to_string(), "Slack".to_string()],
+            metrics,
+            intent: Intent::MoodIntervention,
+            action: Action {
+                action_type,
+                description: "Test".to_string(),
+                confidence: Confidence::Medium,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        }
+    }
+
+    fn outcome(accepted: bool) -> Outcome {
+        Outcome {
+            observation_id: "ctx".to_string(),
+            accepted,
+            ignored: !accepted,
+            modified: false,
+            time_saved_minutes: None,
+            error_rate_change: None,
+            timestamp: 1234567890,
+        }
+    }
+
+    #[test]
+    fn test_lin_ucb_adapts_to_context() {
+        let mut policy = RLPolicy::with_strategy(SelectionStrategy::LinUcb { alpha: 0.1 });
+        // Fragmented sessions welcome focus mode, calm sessions welcome micro-breaks
+        for i in 0..20 {
+            let fragmented = i % 2 == 0;
+            let switches = if fragmented { 20.0 } else { 1.0 };
+            let id = format!("ctx_{}", i);
+            policy.update_from_outcome(&contextual_observation(&id, switches, ActionType::FocusMode), &outcome(fragmented));
+            policy.update_from_outcome(&contextual_observation(&id, switches, ActionType::MicroBreakSuggestion), &outcome(!fragmented));
+        }
+        
+        let fragmented = contextual_observation("now", 22.0, ActionType::MicroBreakSuggestion);
+        let calm = contextual_observation("now", 0.0, ActionType::FocusMode);
+        assert_eq!(policy.greedy_action(&fragmented).action_type, ActionType::FocusMode);
+        assert_eq!(policy.greedy_action(&calm).action_type, ActionType::MicroBreakSuggestion);
+        assert!(policy.predict_acceptance(&contextual_observation("now", 22.0, ActionType::FocusMode)) > 0.5);
+        
+        // The flat table only remembers the last action for the shared state key
+        let mut flat = RLPolicy::new();
+        flat.update_from_outcome(&contextual_observation("a", 20.0, ActionType::FocusMode), &outcome(true));
+        flat.update_from_outcome(&contextual_observation("b", 1.0, ActionType::MicroBreakSuggestion), &outcome(true));
+        assert_eq!(flat.greedy_action(&fragmented).action_type, flat.greedy_action(&calm).action_type);
+    }
+
+    #[test]
+    fn test_lin_ucb_explores_untried_arm_and_persists() {
+        let mut policy = RLPolicy::with_strategy(SelectionStrategy::LinUcb { alpha: 2.0 });
+        for i in 0..5 {
+            policy.update_from_outcome(&contextual_observation(&format!("ig_{}", i), 5.0, ActionType::FocusMode), &outcome(false));
+        }
+        // A poorly rewarded arm loses to the untried observed action under a large exploration bonus
+        let observed = contextual_observation("new", 5.0, ActionType::ZenMode);
+        assert_eq!(policy.select_action(&observed).action_type, ActionType::ZenMode);
+        
+        let restored = RLPolicy::from_json(&policy.to_json(0).unwrap()).unwrap();
+        assert_eq!(restored.strategy(), &SelectionStrategy::LinUcb { alpha: 2.0 });
+        assert_eq!(restored.arms["MoodIntervention/FocusMode"].pulls, 5);
+        
+        // v1 files predate strategies and load as epsilon-greedy
+        let mut v1 = serde_json::to_value(RLPolicy::new().snapshot(0)).unwrap();
+        v1["schema_version"] = serde_json::json!(1);
+        v1.as_object_mut().unwrap().remove("strategy");
+        v1.as_object_mut().unwrap().remove("arms");
+        assert_eq!(RLPolicy::from_json(&v1.to_string()).unwrap().strategy(), &SelectionStrategy::EpsilonGreedy);
+    }
+}
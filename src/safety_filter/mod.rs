@@ -0,0 +1,173 @@
+/// Phase: B | Source: Athenos_AI_Strategy.md#L85-89
+/// Generated Text Safety Filter
+/// Sanity checks for LLM-generated insights and co-pilot messages before they reach the user
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::info;
+
+/// Terms implying medical or clinical claims
+const CLINICAL_TERMS: [&str; 14] = [
+    "diagnos", "disorder", "depression", "depressed", "anxiety", "adhd", "burnout syndrome",
+    "medication", "prescri", "therapy", "therapist", "clinical", "mental illness", "symptom",
+];
+
+/// Safety violation kind
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyViolationKind {
+    TooLong,
+    Empty,
+    ClinicalClaim,
+    PersonalDataEcho,
+}
+
+/// Logged safety violation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyViolation {
+    pub source: String, // Component that produced the text
+    pub kind: SafetyViolationKind,
+    pub detail: String,
+    pub detected_at: i64,
+}
+
+/// Safety filter for generated text
+/// Cloning yields a handle sharing registered window titles and the violation log
+#[derive(Debug, Clone)]
+pub struct SafetyFilter {
+    max_chars: usize,
+    window_titles: Arc<RwLock<Vec<String>>>, // Lowercased titles and title segments
+    violations: Arc<Mutex<Vec<SafetyViolation>>>,
+}
+
+impl SafetyFilter {
+    /// Create safety filter (600 character limit)
+    pub fn new() -> Self {
+        Self::with_max_chars(600)
+    }
+
+    /// Create safety filter with a length limit
+    pub fn with_max_chars(max_chars: usize) -> Self {
+        info!("SafetyFilter::new: Creating safety filter ({} chars max)", max_chars);
+        Self {
+            max_chars,
+            window_titles: Arc::new(RwLock::new(Vec::new())),
+            violations: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Register a window title whose contents must never be echoed back
+    /// Titles are also split on common separators ("Inbox - alice@example.com - Gmail");
+    /// short segments and the app name itself are too generic to block
+    pub fn register_window_title(&self, title: &str, app_name: &str) {
+        let title = title.trim().to_lowercase();
+        let app_name = app_name.trim().to_lowercase();
+        let segments: Vec<String> = title
+            .replace(" | ", " - ")
+            .replace(" — ", " - ")
+            .split(" - ")
+            .map(str::trim)
+            .map(str::to_string)
+            .collect();
+
+        let mut titles = self.window_titles.write().unwrap();
+        for fragment in std::iter::once(title.clone()).chain(segments) {
+            if fragment.chars().count() >= 8 && fragment != app_name && !titles.contains(&fragment) {
+                titles.push(fragment);
+            }
+        }
+    }
+
+    /// Check text against all rules
+    pub fn check(&self, text: &str) -> Vec<(SafetyViolationKind, String)> {
+        let mut violations = Vec::new();
+        let lower = text.to_lowercase();
+
+        if text.trim().is_empty() {
+            violations.push((SafetyViolationKind::Empty, "Generated text is empty".to_string()));
+        }
+        let chars = text.chars().count();
+        if chars > self.max_chars {
+            violations.push((SafetyViolationKind::TooLong, format!("{} chars exceeds limit of {}", chars, self.max_chars)));
+        }
+        if let Some(term) = CLINICAL_TERMS.iter().find(|t| lower.contains(*t)) {
+            violations.push((SafetyViolationKind::ClinicalClaim, format!("Contains clinical term '{}'", term)));
+        }
+        if let Some(fragment) = self.window_titles.read().unwrap().iter().find(|t| lower.contains(t.as_str())) {
+            // Log the length only; the fragment itself is personal data
+            violations.push((SafetyViolationKind::PersonalDataEcho, format!("Echoes window title content ({} chars)", fragment.len())));
+        }
+
+        violations
+    }
+
+    /// Return `text` if it passes, otherwise log the violations and return `fallback`
+    pub fn filter(&self, source: &str, text: &str, fallback: &str) -> String {
+        let violations = self.check(text);
+        if violations.is_empty() {
+            return text.to_string();
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut log = self.violations.lock().unwrap();
+        for (kind, detail) in violations {
+            info!("SafetyFilter::filter: {:?} violation from {}: {}", kind, source, detail);
+            log.push(SafetyViolation {
+                source: source.to_string(),
+                kind,
+                detail,
+                detected_at: now,
+            });
+        }
+        fallback.to_string()
+    }
+
+    /// Get logged violations
+    pub fn get_violations(&self) -> Vec<SafetyViolation> {
+        self.violations.lock().unwrap().clone()
+    }
+}
+
+impl Default for SafetyFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_text_passes() {
+        let filter = SafetyFilter::new();
+        let text = "You switch between Teams and Gmail often. A startup macro could save time.";
+        assert_eq!(filter.filter("test", text, "fallback"), text);
+        assert!(filter.get_violations().is_empty());
+    }
+
+    #[test]
+    fn test_clinical_and_length_violations_fall_back() {
+        let filter = SafetyFilter::with_max_chars(40);
+        let text = "Your typing pattern suggests you may be depressed; consider therapy.";
+
+        assert_eq!(filter.filter("wisdom", text, "fallback"), "fallback");
+        let kinds: Vec<SafetyViolationKind> = filter.get_violations().into_iter().map(|v| v.kind).collect();
+        assert!(kinds.contains(&SafetyViolationKind::TooLong));
+        assert!(kinds.contains(&SafetyViolationKind::ClinicalClaim));
+    }
+
+    #[test]
+    fn test_window_title_echo_blocked() {
+        let filter = SafetyFilter::new();
+        filter.register_window_title("Inbox - alice@example.com - Gmail", "Gmail");
+
+        let kinds: Vec<SafetyViolationKind> = filter
+            .check("I see you're reading mail for alice@example.com again.")
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect();
+        assert_eq!(kinds, vec![SafetyViolationKind::PersonalDataEcho]);
+        assert!(filter.check("You open your Gmail inbox often.").is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+/// Phase: A | Source: athenos-rules.mdc#L12-15
+/// Adaptive OS Event Sampling
+/// Keep low-information events (key presses, mouse clicks) from 1 in N time windows while always keeping app transitions
+
+use crate::consent::MicroConsentManager;
+use crate::edge::{OSEvent, OSEventType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Consent capability that allows capturing every input event
+pub const FULL_INPUT_CAPTURE_CAPABILITY: &str = "full_input_capture";
+
+/// Metadata key holding the inverse sampling probability of a kept event
+const META_SAMPLE_WEIGHT: &str = "sample_weight";
+
+/// Sampling configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SamplingConfig {
+    pub consented_rate: u32, // Keep 1 in N low-information events with full-capture consent
+    pub default_rate: u32,   // Keep 1 in N without it
+    pub max_rate: u32,       // Upper bound after storage pressure is applied
+    #[serde(default = "default_window_secs")]
+    pub window_secs: i64, // Windows are kept or dropped whole, so short micro-sequences stay intact
+}
+
+fn default_window_secs() -> i64 {
+    10
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            consented_rate: 1,
+            default_rate: 4,
+            max_rate: 64,
+            window_secs: default_window_secs(),
+        }
+    }
+}
+
+/// Per-type sampling counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SamplingStats {
+    pub seen: u64,
+    pub kept: u64,
+}
+
+/// Event sampling stage applied before events are stored
+pub struct EventSampler {
+    config: SamplingConfig,
+    full_capture: bool,
+    rate: u32,
+    windows_seen: u64,
+    window: Option<(i64, Option<u32>)>, // Current window and the rate it was kept at (None = dropped)
+    stats: HashMap<String, SamplingStats>,
+}
+
+impl EventSampler {
+    /// Create sampler (no full-capture consent, default rate)
+    pub fn new(config: SamplingConfig) -> Self {
+        info!("EventSampler::new: Creating event sampler (1 in {} low-information events)", config.default_rate);
+        Self {
+            rate: config.default_rate.max(1),
+            config,
+            full_capture: false,
+            windows_seen: 0,
+            window: None,
+            stats: HashMap::new(),
+        }
+    }
+
+    /// Key presses and mouse clicks carry little information individually
+    pub fn is_low_information(event_type: &OSEventType) -> bool {
+        matches!(event_type, OSEventType::KeyPress | OSEventType::MouseClick)
+    }
+
+    /// Base rate follows `full_input_capture` consent
+    pub fn apply_consent(&mut self, consent: &MicroConsentManager) {
+        self.full_capture = consent.has_consent(FULL_INPUT_CAPTURE_CAPABILITY);
+    }
+
+    /// Adapt the rate to storage pressure (stored / budget); the rate doubles at 75% and again at 90%
+    pub fn adapt(&mut self, stored: usize, budget: usize) {
+        let base = if self.full_capture { self.config.consented_rate } else { self.config.default_rate }.max(1);
+        let fill = if budget == 0 { 1.0 } else { stored as f64 / budget as f64 };
+        let pressure = if fill >= 0.9 {
+            4
+        } else if fill >= 0.75 {
+            2
+        } else {
+            1
+        };
+        let rate = (base * pressure).min(self.config.max_rate.max(1));
+        if rate != self.rate {
+            info!("EventSampler::adapt: Sampling 1 in {} low-information events ({:.0}% of budget used)", rate, fill * 100.0);
+            self.rate = rate;
+        }
+    }
+
+    /// Current rate (1 in N)
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Decide whether to keep an event; kept low-information events carry their sample weight
+    /// Low-information events are kept or dropped per time window, each window with probability 1/rate
+    pub fn sample(&mut self, event: &mut OSEvent) -> bool {
+        let stats = self.stats.entry(format!("{:?}", event.event_type)).or_default();
+        stats.seen += 1;
+
+        if !Self::is_low_information(&event.event_type) {
+            stats.kept += 1;
+            return true;
+        }
+
+        let window = event.timestamp.div_euclid(self.config.window_secs.max(1));
+        let kept_at = match self.window {
+            Some((current, kept_at)) if current == window => kept_at,
+            _ => {
+                let kept_at = self.windows_seen.is_multiple_of(self.rate as u64).then_some(self.rate);
+                self.windows_seen += 1;
+                self.window = Some((window, kept_at));
+                kept_at
+            }
+        };
+        let Some(rate) = kept_at else {
+            return false;
+        };
+        stats.kept += 1;
+        if rate > 1 {
+            event.metadata.insert(META_SAMPLE_WEIGHT.to_string(), rate.to_string());
+        }
+        true
+    }
+
+    /// Seen/kept counts per event type
+    pub fn get_stats(&self) -> &HashMap<String, SamplingStats> {
+        &self.stats
+    }
+}
+
+impl Default for EventSampler {
+    fn default() -> Self {
+        Self::new(SamplingConfig::default())
+    }
+}
+
+/// Inverse sampling probability of a stored event (1.0 when it was not sampled)
+pub fn sample_weight(event: &OSEvent) -> f64 {
+    event
+        .metadata
+        .get(META_SAMPLE_WEIGHT)
+        .and_then(|w| w.parse::<f64>().ok())
+        .filter(|w| w.is_finite() && *w >= 1.0)
+        .unwrap_or(1.0)
+}
+
+/// Bias-corrected count of the events matching a predicate
+pub fn estimated_count(events: &[OSEvent], predicate: impl Fn(&OSEvent) -> bool) -> f64 {
+    events.iter().filter(|e| predicate(e)).map(sample_weight).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: OSEventType) -> OSEvent {
+        OSEvent {
+            event_type,
+            app_name: "Excel".to_string(),
+            window_title: None,
+            timestamp: 0,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_keeps_transitions_and_samples_input() {
+        let mut sampler = EventSampler::default();
+        let mut kept = Vec::new();
+        for i in 0..20 {
+            let mut e = event(if i % 10 == 0 { OSEventType::AppSwitch } else { OSEventType::KeyPress });
+            e.timestamp = i * 10; // One sampling window per event
+            if sampler.sample(&mut e) {
+                kept.push(e);
+            }
+        }
+        assert_eq!(kept.iter().filter(|e| e.event_type == OSEventType::AppSwitch).count(), 2);
+        assert_eq!(kept.iter().filter(|e| e.event_type == OSEventType::KeyPress).count(), 5);
+        // Bias correction recovers the true number of key presses
+        assert_eq!(estimated_count(&kept, |e| e.event_type == OSEventType::KeyPress), 20.0);
+        assert_eq!(sampler.get_stats()["KeyPress"].seen, 18);
+    }
+
+    #[test]
+    fn test_micro_sequences_kept_whole() {
+        let mut sampler = EventSampler::default();
+        let mut kept = Vec::new();
+        for window in 0..8 {
+            for (offset, event_type) in [OSEventType::KeyPress, OSEventType::MouseClick, OSEventType::KeyPress].into_iter().enumerate() {
+                let mut e = event(event_type);
+                e.timestamp = window * 10 + offset as i64;
+                if sampler.sample(&mut e) {
+                    kept.push(e);
+                }
+            }
+        }
+        // Two of eight windows kept, each with its full copy/click/paste sequence
+        assert_eq!(kept.len(), 6);
+        assert_eq!(kept.iter().map(|e| e.timestamp / 10).collect::<Vec<_>>(), vec![0, 0, 0, 4, 4, 4]);
+        assert_eq!(estimated_count(&kept, |e| e.event_type == OSEventType::KeyPress), 16.0);
+    }
+
+    #[test]
+    fn test_rate_follows_consent_and_storage_budget() {
+        let mut sampler = EventSampler::default();
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(FULL_INPUT_CAPTURE_CAPABILITY.to_string(), "Capture every input event".to_string());
+        consent.grant_consent(FULL_INPUT_CAPTURE_CAPABILITY).unwrap();
+        sampler.apply_consent(&consent);
+
+        sampler.adapt(100, 1000);
+        assert_eq!(sampler.rate(), 1);
+        let mut e = event(OSEventType::MouseClick);
+        assert!(sampler.sample(&mut e));
+        assert_eq!(sample_weight(&e), 1.0);
+
+        sampler.adapt(950, 1000);
+        assert_eq!(sampler.rate(), 4);
+        sampler.apply_consent(&MicroConsentManager::new());
+        sampler.adapt(950, 1000);
+        assert_eq!(sampler.rate(), 16);
+    }
+}
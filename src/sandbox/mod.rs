@@ -4,9 +4,15 @@
 
 use crate::types::*;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::path::PathBuf;
-use tracing::info;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
 /// Sandbox test result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,49 +23,461 @@ pub struct SandboxResult {
     pub diff_log: Option<String>,
 }
 
+/// One command of a macro, run as a child process inside the sandbox
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MacroCommand {
+    pub program: String, // Bare program name; must be on the policy's command whitelist
+    pub args: Vec<String>,
+}
+
+impl MacroCommand {
+    pub fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+/// Resource limits for isolated macro execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IsolationLimits {
+    pub timeout_ms: u64,        // Wall clock per command; the child is killed past it
+    pub cpu_secs: u64,          // RLIMIT_CPU (unix)
+    pub max_file_bytes: u64,    // RLIMIT_FSIZE (unix)
+    pub max_memory_bytes: u64,  // RLIMIT_AS (unix)
+    pub max_output_bytes: usize, // Captured stdout/stderr per stream
+}
+
+impl Default for IsolationLimits {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 5_000,
+            cpu_secs: 5,
+            max_file_bytes: 1024 * 1024,
+            max_memory_bytes: 512 * 1024 * 1024,
+            max_output_bytes: 64 * 1024,
+        }
+    }
+}
+
+fn default_allowed_commands() -> Vec<String> {
+    ["echo", "printf", "cat", "ls", "mkdir", "touch", "cp", "mv", "rm", "sort", "head", "tail", "wc", "grep"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// Sandbox policy; changing it invalidates cached results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxPolicy {
+    pub max_macro_risk: RiskCategory, // Highest risk a macro may carry and still pass
+    #[serde(default = "default_allowed_commands")]
+    pub allowed_commands: Vec<String>,
+    #[serde(default)]
+    pub limits: IsolationLimits,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            max_macro_risk: RiskCategory::Low,
+            allowed_commands: default_allowed_commands(),
+            limits: IsolationLimits::default(),
+        }
+    }
+}
+
+/// Outcome of one child process
+struct CommandRun {
+    exit: Result<i32, String>, // Exit code, or why it did not exit normally
+    stdout: String,
+    stderr: String,
+}
+
+/// Read a child stream on its own thread so a full pipe cannot stall the child
+fn capture<R: Read + Send + 'static>(stream: Option<R>, max_bytes: usize) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(stream) = stream {
+            let _ = stream.take(max_bytes as u64).read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).to_string()
+    })
+}
+
+/// Every path an argument can carry: the argument itself, a `--opt=value` value, or a value attached to a
+/// (possibly clustered) short option such as `-f/etc/passwd` or `-xf../secret`
+fn arg_paths(arg: &str) -> Vec<&str> {
+    let mut paths = vec![arg];
+    if let Some((_, value)) = arg.split_once('=') {
+        paths.push(value);
+    }
+    if let Some(flags) = arg.strip_prefix('-').filter(|rest| !rest.starts_with('-')) {
+        paths.extend(flags.char_indices().skip(1).map(|(i, _)| &flags[i..]));
+    }
+    paths
+}
+
+/// Relative path -> (size, content hash) for every file under `root`
+pub fn snapshot_dir(root: &Path) -> HashMap<String, (u64, u64)> {
+    let mut files = HashMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(content) = std::fs::read(&path) {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().to_string();
+                files.insert(relative, (content.len() as u64, hasher.finish()));
+            }
+        }
+    }
+    files
+}
+
+/// Sandbox result cache statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SandboxCacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub saved_ms: u64, // Sandbox execution time avoided by cache hits
+}
+
+/// Cached sandbox result
+#[derive(Debug, Clone)]
+struct CachedResult {
+    result: SandboxResult,
+    cached_at: i64,
+}
+
 /// Sandbox runner for automation testing
 /// Source: athenos-rules.mdc#L50-52
 pub struct SandboxRunner {
     sandbox_dir: PathBuf,
+    policy: SandboxPolicy,
+    policy_version: u64,
+    cache_ttl_secs: i64,
+    cache: Mutex<HashMap<String, CachedResult>>, // fingerprint -> result
+    cache_stats: Mutex<SandboxCacheStats>,
+    consent_scopes: Vec<ActionType>, // Consented action types; empty (nothing allowed) until consent is applied
 }
 
 impl SandboxRunner {
     /// Create new sandbox runner
     pub fn new(sandbox_dir: PathBuf) -> Self {
         info!("SandboxRunner::new: Creating sandbox runner at {:?}", sandbox_dir);
-        Self { sandbox_dir }
+        Self {
+            sandbox_dir,
+            policy: SandboxPolicy::default(),
+            policy_version: 0,
+            cache_ttl_secs: 300,
+            cache: Mutex::new(HashMap::new()),
+            cache_stats: Mutex::new(SandboxCacheStats::default()),
+            consent_scopes: Vec::new(),
+        }
     }
 
-    /// Test an automation in sandbox
+    /// Set result cache TTL
+    pub fn with_cache_ttl(mut self, ttl_secs: i64) -> Self {
+        self.cache_ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Replace sandbox policy and invalidate cached results
+    pub fn set_policy(&mut self, policy: SandboxPolicy) {
+        info!("SandboxRunner::set_policy: Updating policy {:?}, invalidating cache", policy);
+        self.policy = policy;
+        self.policy_version += 1;
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Restrict automations to consented action types and invalidate cached results
+    pub fn set_consent_scopes(&mut self, scopes: Vec<ActionType>) {
+        info!("SandboxRunner::set_consent_scopes: {} action types consented", scopes.len());
+        self.consent_scopes = scopes;
+        self.policy_version += 1;
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Whether the user consented to automating this action type
+    pub fn has_consent_for(&self, action_type: &ActionType) -> bool {
+        self.consent_scopes.contains(action_type)
+    }
+
+    /// Get current sandbox policy
+    pub fn get_policy(&self) -> &SandboxPolicy {
+        &self.policy
+    }
+
+    /// Fingerprint of an action under the current policy
+    pub fn fingerprint(&self, action: &Action) -> String {
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", action.action_type).hash(&mut hasher);
+        action.description.hash(&mut hasher);
+        format!("{:?}|{:?}", action.confidence, action.risk).hash(&mut hasher);
+        self.policy_version.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Test an automation in sandbox, reusing a cached result for identical actions within the TTL
+    /// Macros carry no commands here and fail; they are tested by running their commands through `test_macro`
     /// Source: athenos-rules.mdc#L50
     pub fn test_automation(&self, action: &Action) -> SandboxResult {
+        let fingerprint = self.fingerprint(action);
+        let now = chrono::Utc::now().timestamp();
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&fingerprint) {
+            if now - cached.cached_at < self.cache_ttl_secs {
+                info!("SandboxRunner::test_automation: Cache hit for {:?} ({})", action.action_type, fingerprint);
+                let mut stats = self.cache_stats.lock().unwrap();
+                stats.hits += 1;
+                stats.saved_ms += cached.result.execution_time_ms;
+                return cached.result.clone();
+            }
+        }
+
+        let result = self.run_automation(action, false, &[]);
+        self.cache_stats.lock().unwrap().misses += 1;
+        self.cache.lock().unwrap().insert(fingerprint, CachedResult {
+            result: result.clone(),
+            cached_at: now,
+        });
+        result
+    }
+
+    /// Test an automation a person approved; the approval stands in for the macro risk ceiling
+    /// Not cached, so the approved result never satisfies an unapproved run
+    pub fn test_approved_automation(&self, action: &Action) -> SandboxResult {
+        self.run_automation(action, true, &[])
+    }
+
+    /// Get cache statistics
+    pub fn cache_stats(&self) -> SandboxCacheStats {
+        self.cache_stats.lock().unwrap().clone()
+    }
+
+    /// Consent and risk gate, then the test itself: macros run their commands in isolation
+    fn run_automation(&self, action: &Action, approved: bool, commands: &[MacroCommand]) -> SandboxResult {
         info!("SandboxRunner::test_automation: Testing {:?}", action.action_type);
+        let started = Instant::now();
+        let failed = |message: String| SandboxResult {
+            success: false,
+            error_message: Some(message),
+            execution_time_ms: started.elapsed().as_millis() as u64,
+            diff_log: None,
+        };
         
-        // For Phase A, we simulate sandbox testing
-        // In production, this would execute in isolated environment
+        if !self.has_consent_for(&action.action_type) {
+            return failed(format!("No automation consent for {:?}", action.action_type));
+        }
         
-        match action.action_type {
-            ActionType::AutomationMacro => {
-                // Simulate macro test
-                SandboxResult {
-                    success: action.risk <= RiskCategory::Low,
-                    error_message: if action.risk > RiskCategory::Low {
-                        Some("High risk action requires manual approval".to_string())
-                    } else {
-                        None
-                    },
-                    execution_time_ms: 100,
-                    diff_log: Some(format!("Would execute: {}", action.description)),
+        if action.action_type == ActionType::AutomationMacro {
+            if !approved && action.risk > self.policy.max_macro_risk {
+                return failed("High risk action requires manual approval".to_string());
+            }
+            if commands.is_empty() {
+                return failed("Macro has no commands to run in the sandbox".to_string());
+            }
+        }
+        if commands.is_empty() {
+            return SandboxResult {
+                success: true,
+                error_message: None,
+                execution_time_ms: started.elapsed().as_millis() as u64,
+                diff_log: Some(format!("Tested: {}", action.description)),
+            };
+        }
+        self.run_isolated(commands, started)
+    }
+
+    /// Reject commands that are not whitelisted or that reach outside the working directory
+    fn check_command(&self, command: &MacroCommand) -> Result<(), String> {
+        if command.program.contains('/') || command.program.contains('\\') || !self.policy.allowed_commands.contains(&command.program) {
+            return Err(format!("Command not whitelisted: {}", command.program));
+        }
+        let escapes = |path: &str| path.starts_with('/') || path.starts_with('~') || path.split(['/', '\\']).any(|part| part == "..");
+        if let Some(arg) = command.args.iter().find(|a| arg_paths(a).into_iter().any(escapes)) {
+            return Err(format!("Argument escapes the sandbox: {}", arg));
+        }
+        Ok(())
+    }
+
+    /// Spawn one command with a clean environment and resource limits, killing it past the timeout
+    /// Network access is not isolated; the command whitelist is what keeps macros off the network
+    fn spawn_limited(&self, command: &MacroCommand, work_dir: &Path) -> CommandRun {
+        let limits = &self.policy.limits;
+        // Limits are applied through `ulimit`; refuse to run unlimited where that is unavailable
+        if !cfg!(unix) {
+            let exit = Err("Resource limits are not supported on this platform".to_string());
+            return CommandRun { exit, stdout: String::new(), stderr: String::new() };
+        }
+        // A fixed `sh` launcher applies rlimits, then execs the whitelisted program
+        let mut child_command = Command::new("sh");
+        child_command
+            .arg("-c")
+            .arg(format!(
+                "ulimit -t {} && ulimit -f {} && ulimit -v {} && exec \"$0\" \"$@\"",
+                limits.cpu_secs.max(1),
+                (limits.max_file_bytes / 512).max(1),
+                (limits.max_memory_bytes / 1024).max(1),
+            ))
+            .arg(&command.program)
+            .args(&command.args)
+            .current_dir(work_dir)
+            .env_clear()
+            .env("PATH", "/usr/bin:/bin")
+            .env("HOME", work_dir)
+            .env("TMPDIR", work_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = match child_command.spawn() {
+            Ok(child) => child,
+            Err(e) => return CommandRun { exit: Err(format!("Failed to start: {}", e)), stdout: String::new(), stderr: String::new() },
+        };
+        let stdout = capture(child.stdout.take(), limits.max_output_bytes);
+        let stderr = capture(child.stderr.take(), limits.max_output_bytes);
+
+        let deadline = Instant::now() + Duration::from_millis(limits.timeout_ms);
+        let exit = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status.code().ok_or_else(|| "Killed by signal (resource limit)".to_string()),
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break Err(format!("Timed out after {}ms", limits.timeout_ms));
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+                Err(e) => break Err(format!("Failed to wait: {}", e)),
+            }
+        };
+        CommandRun {
+            exit,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        }
+    }
+
+    /// Run commands in order until one fails; returns the output log and the failure, if any
+    fn run_commands(&self, commands: &[MacroCommand], work_dir: &Path) -> (String, Option<String>) {
+        let mut log = String::new();
+        let mut error = None;
+        for command in commands {
+            let run = self.spawn_limited(command, work_dir);
+            log.push_str(&format!("$ {} {}\n", command.program, command.args.join(" ")));
+            for (stream, output) in [("stdout", &run.stdout), ("stderr", &run.stderr)] {
+                for line in output.lines() {
+                    log.push_str(&format!("{}: {}\n", stream, line));
                 }
             }
-            _ => {
-                SandboxResult {
-                    success: true,
-                    error_message: None,
-                    execution_time_ms: 50,
-                    diff_log: Some(format!("Tested: {}", action.description)),
+            match run.exit {
+                Ok(0) => log.push_str("[exit 0]\n"),
+                Ok(code) => {
+                    log.push_str(&format!("[exit {}]\n", code));
+                    error = Some(format!("{} exited with {}", command.program, code));
+                }
+                Err(reason) => {
+                    log.push_str(&format!("[{}]\n", reason));
+                    error = Some(format!("{}: {}", command.program, reason));
                 }
             }
+            if error.is_some() {
+                break;
+            }
+        }
+        (log, error)
+    }
+
+    /// Test a macro by executing its commands in a fresh temporary directory under the sandbox dir
+    /// The diff log captures each command's exit, stdout and stderr plus the files it left behind
+    pub fn test_macro(&self, action: &Action, commands: &[MacroCommand]) -> SandboxResult {
+        self.run_automation(action, false, commands)
+    }
+
+    /// Test a macro a person approved; the approval stands in for the macro risk ceiling
+    pub fn test_approved_macro(&self, action: &Action, commands: &[MacroCommand]) -> SandboxResult {
+        self.run_automation(action, true, commands)
+    }
+
+    fn run_isolated(&self, commands: &[MacroCommand], started: Instant) -> SandboxResult {
+        let failed = |message: String, diff_log: Option<String>| SandboxResult {
+            success: false,
+            error_message: Some(message),
+            execution_time_ms: started.elapsed().as_millis() as u64,
+            diff_log,
+        };
+        if let Some(error) = commands.iter().find_map(|c| self.check_command(c).err()) {
+            return failed(error, None);
+        }
+
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+        let work_dir = self.sandbox_dir.join(format!("run_{}_{}", std::process::id(), nanos));
+        if let Err(e) = std::fs::create_dir_all(&work_dir) {
+            return failed(format!("Failed to create sandbox directory: {}", e), None);
+        }
+        info!("SandboxRunner::test_macro: Running {} commands in {:?}", commands.len(), work_dir);
+
+        let before = snapshot_dir(&work_dir);
+        let (mut log, error) = self.run_commands(commands, &work_dir);
+
+        let after = snapshot_dir(&work_dir);
+        let mut changes: Vec<String> = after
+            .iter()
+            .filter_map(|(path, (size, hash))| match before.get(path) {
+                None => Some(format!("+ {} ({} bytes)", path, size)),
+                Some((_, old_hash)) if old_hash != hash => Some(format!("~ {} ({} bytes)", path, size)),
+                _ => None,
+            })
+            .chain(before.keys().filter(|path| !after.contains_key(*path)).map(|path| format!("- {}", path)))
+            .collect();
+        changes.sort_by(|a, b| a[2..].cmp(&b[2..]));
+        log.push_str("files:\n");
+        for change in changes {
+            log.push_str(&format!("{}\n", change));
+        }
+
+        if let Err(e) = std::fs::remove_dir_all(&work_dir) {
+            warn!("SandboxRunner::test_macro: Failed to clean up {:?}: {}", work_dir, e);
+        }
+        match error {
+            Some(message) => failed(message, Some(log)),
+            None => SandboxResult {
+                success: true,
+                error_message: None,
+                execution_time_ms: started.elapsed().as_millis() as u64,
+                diff_log: Some(log),
+            },
+        }
+    }
+
+    /// Run whitelisted commands under the isolation limits in an existing directory
+    /// Used for real macro execution once an automation has passed its sandbox test; refused without consent
+    pub fn execute_in(&self, action_type: &ActionType, commands: &[MacroCommand], work_dir: &Path) -> Result<String, String> {
+        if !self.has_consent_for(action_type) {
+            return Err(format!("No automation consent for {:?}", action_type));
+        }
+        self.run_in(commands, work_dir)
+    }
+
+    /// Run the inverse commands of an executed macro; undo stays available after consent is withdrawn
+    pub fn undo_in(&self, commands: &[MacroCommand], work_dir: &Path) -> Result<String, String> {
+        self.run_in(commands, work_dir)
+    }
+
+    fn run_in(&self, commands: &[MacroCommand], work_dir: &Path) -> Result<String, String> {
+        if let Some(error) = commands.iter().find_map(|c| self.check_command(c).err()) {
+            return Err(error);
+        }
+        info!("SandboxRunner::execute_in: Running {} commands in {:?}", commands.len(), work_dir);
+        match self.run_commands(commands, work_dir) {
+            (log, None) => Ok(log),
+            (_, Some(error)) => Err(error),
         }
     }
 
@@ -73,7 +491,7 @@ impl SandboxRunner {
     /// Check if action is safe to auto-execute
     /// Source: athenos-rules.mdc#L51
     pub fn is_safe_to_auto_execute(&self, action: &Action) -> bool {
-        action.confidence >= Confidence::High && action.risk == RiskCategory::None
+        action.confidence >= Confidence::High && action.risk == RiskCategory::None && self.has_consent_for(&action.action_type)
     }
 }
 
@@ -95,10 +513,10 @@ mod tests {
 
     #[test]
     fn test_safe_automation() {
-        let runner = SandboxRunner::default();
+        let runner = consented(SandboxRunner::default());
         let action = Action {
-            action_type: ActionType::AutomationMacro,
-            description: "Safe macro".to_string(),
+            action_type: ActionType::FocusMode,
+            description: "Enable focus mode".to_string(),
             confidence: Confidence::High,
             risk: RiskCategory::None,
         };
@@ -106,11 +524,17 @@ mod tests {
         let result = runner.test_automation(&action);
         assert!(result.success);
         assert!(runner.is_safe_to_auto_execute(&action));
+        
+        // A macro is only tested by running its commands
+        let safe_macro = Action { action_type: ActionType::AutomationMacro, ..action };
+        let result = runner.test_automation(&safe_macro);
+        assert!(!result.success);
+        assert!(result.error_message.unwrap().contains("no commands"));
     }
 
     #[test]
     fn test_high_risk_automation() {
-        let runner = SandboxRunner::default();
+        let runner = consented(SandboxRunner::default());
         let action = Action {
             action_type: ActionType::AutomationMacro,
             description: "Risky macro".to_string(),
@@ -125,7 +549,7 @@ mod tests {
 
     #[test]
     fn test_undo_generation() {
-        let runner = SandboxRunner::default();
+        let runner = consented(SandboxRunner::default());
         let action = Action {
             action_type: ActionType::AutomationMacro,
             description: "Test action".to_string(),
@@ -136,5 +560,153 @@ mod tests {
         let undo = runner.generate_undo(&action);
         assert!(undo.contains("Undo"));
     }
-}
 
+    #[test]
+    fn test_result_cached_by_fingerprint() {
+        let runner = consented(SandboxRunner::default());
+        let action = Action {
+            action_type: ActionType::FocusMode,
+            description: "Enable focus mode".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+        };
+        
+        let first = runner.test_automation(&action);
+        runner.test_automation(&action);
+        let stats = runner.cache_stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.saved_ms, first.execution_time_ms);
+        
+        let expired = consented(SandboxRunner::default()).with_cache_ttl(0);
+        expired.test_automation(&action);
+        expired.test_automation(&action);
+        assert_eq!(expired.cache_stats().hits, 0);
+    }
+
+    #[test]
+    fn test_policy_change_invalidates_cache() {
+        let mut runner = isolated_runner("policy");
+        let action = Action {
+            action_type: ActionType::AutomationMacro,
+            description: "Risky macro".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::High,
+        };
+        let commands = [MacroCommand::new("echo", &["hi"])];
+        
+        assert!(!runner.test_automation(&action).success);
+        assert!(runner.test_macro(&action, &commands).error_message.unwrap().contains("manual approval"));
+        runner.set_policy(SandboxPolicy { max_macro_risk: RiskCategory::High, ..SandboxPolicy::default() });
+        assert!(!runner.test_automation(&action).success);
+        assert_eq!(runner.cache_stats().hits, 0);
+        assert_eq!(runner.cache_stats().misses, 2);
+        if cfg!(unix) {
+            assert!(runner.test_macro(&action, &commands).success);
+        }
+        std::fs::remove_dir_all(&runner.sandbox_dir).ok();
+    }
+
+    #[test]
+    fn test_consent_scopes_gate_automation() {
+        let mut runner = SandboxRunner::default();
+        let action = Action {
+            action_type: ActionType::FocusMode,
+            description: "Enable focus mode".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+        };
+        // Nothing is automated before consent is applied
+        assert!(!runner.test_automation(&action).success);
+        assert!(!runner.is_safe_to_auto_execute(&action));
+        runner.set_consent_scopes(vec![ActionType::FocusMode]);
+        assert!(runner.test_automation(&action).success);
+        
+        runner.set_consent_scopes(vec![ActionType::AutomationMacro]);
+        let result = runner.test_automation(&action);
+        assert!(!result.success);
+        assert!(result.error_message.unwrap().contains("FocusMode"));
+        assert!(!runner.is_safe_to_auto_execute(&action));
+        assert!(runner.is_safe_to_auto_execute(&Action { action_type: ActionType::AutomationMacro, ..action }));
+        
+        // Real execution applies the same gate, whoever the caller is
+        let error = runner.execute_in(&ActionType::FocusMode, &[MacroCommand::new("touch", &["x"])], Path::new("./missing_dir")).unwrap_err();
+        assert!(error.contains("No automation consent for FocusMode"));
+    }
+
+    fn consented(mut runner: SandboxRunner) -> SandboxRunner {
+        runner.set_consent_scopes(vec![ActionType::AutomationMacro, ActionType::FocusMode]);
+        runner
+    }
+
+    fn status_post() -> Action {
+        Action {
+            action_type: ActionType::FocusMode,
+            description: "Post focus status".to_string(),
+            confidence: Confidence::High,
+            risk: RiskCategory::Low,
+        }
+    }
+
+    fn isolated_runner(name: &str) -> SandboxRunner {
+        consented(SandboxRunner::new(std::env::temp_dir().join(format!("athenos_sandbox_{}_{}", name, std::process::id()))))
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_macro_runs_isolated_and_logs_file_diff() {
+        let runner = isolated_runner("diff");
+        let commands = vec![
+            MacroCommand::new("touch", &["notes.txt"]),
+            MacroCommand::new("mkdir", &["out"]),
+            MacroCommand::new("cp", &["notes.txt", "out/copy.txt"]),
+            MacroCommand::new("rm", &["notes.txt"]),
+            MacroCommand::new("echo", &["done"]),
+        ];
+
+        let result = runner.test_macro(&status_post(), &commands);
+        assert!(result.success, "{:?}", result);
+        let log = result.diff_log.unwrap();
+        assert!(log.contains("$ echo done\nstdout: done\n[exit 0]"));
+        assert!(log.contains("+ out/copy.txt (0 bytes)"));
+        assert!(!log.contains("notes.txt ("), "{}", log); // Created then removed: no net change
+        assert_eq!(std::fs::read_dir(&runner.sandbox_dir).unwrap().count(), 0); // Work dir cleaned up
+        std::fs::remove_dir_all(&runner.sandbox_dir).ok();
+    }
+
+    #[test]
+    fn test_macro_rejects_unlisted_commands_and_escapes() {
+        let runner = isolated_runner("reject");
+        let curl = runner.test_macro(&status_post(), &[MacroCommand::new("curl", &["https://example.com"])]);
+        assert!(curl.error_message.unwrap().contains("not whitelisted"));
+        let escape = runner.test_macro(&status_post(), &[MacroCommand::new("cat", &["../../etc/passwd"])]);
+        assert!(escape.error_message.unwrap().contains("escapes the sandbox"));
+        for attached in ["--target-directory=/tmp", "-f/etc/passwd", "-xf../secret", "of=/dev/sda"] {
+            let escape = runner.test_macro(&status_post(), &[MacroCommand::new("cp", &["a", attached])]);
+            assert!(escape.error_message.unwrap().contains("escapes the sandbox"), "{}", attached);
+        }
+        let absolute = runner.test_macro(&status_post(), &[MacroCommand::new("/bin/echo", &["hi"])]);
+        assert!(!absolute.success);
+        assert!(!runner.sandbox_dir.exists()); // Rejected before anything ran
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_macro_timeout_and_failure_reported() {
+        let mut runner = isolated_runner("timeout");
+        let mut policy = SandboxPolicy::default();
+        policy.allowed_commands.push("sleep".to_string());
+        policy.limits.timeout_ms = 200;
+        runner.set_policy(policy);
+
+        let slow = runner.test_macro(&status_post(), &[MacroCommand::new("sleep", &["5"]), MacroCommand::new("echo", &["never"])]);
+        assert!(slow.error_message.unwrap().contains("Timed out after 200ms"));
+        assert!(slow.execution_time_ms < 2_000);
+        assert!(!slow.diff_log.unwrap().contains("never"));
+
+        let missing = runner.test_macro(&status_post(), &[MacroCommand::new("cat", &["missing.txt"])]);
+        assert!(missing.error_message.unwrap().contains("cat exited with 1"));
+        assert!(missing.diff_log.unwrap().contains("stderr: cat:"));
+        std::fs::remove_dir_all(&runner.sandbox_dir).ok();
+    }
+}
@@ -0,0 +1,400 @@
+/// Phase: C | Step: 3 | Source: Athenos_AI_Strategy.md#L122
+/// CalDAV Sync
+/// Pull events from a CalDAV calendar collection and push accepted suggestions back, moving existing events in place
+
+use super::{ics, CalendarEvent, CalendarNegotiationAgent, ScheduleSuggestion};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+/// Raw HTTP response from a CalDAV server
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+    pub etag: Option<String>,
+}
+
+/// HTTP transport for CalDAV requests
+/// The network implementation requires the `caldav` feature; tests supply their own
+pub trait CalDavTransport: Send + Sync {
+    fn send(&self, method: &str, url: &str, headers: &[(String, String)], body: &str) -> Result<HttpResponse, String>;
+}
+
+/// CalDAV calendar collection and credentials
+#[derive(Clone)]
+pub struct CalDavConfig {
+    pub calendar_url: String, // Collection URL, e.g. https://dav.example.com/calendars/me/work/
+    pub username: String,
+    pub password: String,
+}
+
+/// Server copy of one event resource
+#[derive(Debug, Clone)]
+struct Resource {
+    href: String,
+    etag: Option<String>,
+    calendar_data: String, // Full iCalendar text, rewritten in place so other properties survive a move
+    recurring: bool,
+}
+
+/// Two-way sync between a CalDAV collection and the calendar agent
+pub struct CalDavSync {
+    config: CalDavConfig,
+    transport: Box<dyn CalDavTransport>,
+    resources: HashMap<String, Resource>, // Event UID -> resource last seen on the server
+}
+
+impl CalDavSync {
+    pub fn new(config: CalDavConfig, transport: Box<dyn CalDavTransport>) -> Self {
+        info!("CalDavSync::new: Syncing with {}", config.calendar_url);
+        Self { config, transport, resources: HashMap::new() }
+    }
+
+    /// Sync over HTTPS
+    #[cfg(feature = "caldav")]
+    pub fn connect(config: CalDavConfig) -> Result<Self, String> {
+        if !config.calendar_url.starts_with("https://") {
+            return Err(format!("CalDAV calendar URL must use https: {}", config.calendar_url));
+        }
+        Ok(Self::new(config, Box::new(http::HttpTransport::new())))
+    }
+
+    #[cfg(not(feature = "caldav"))]
+    pub fn connect(config: CalDavConfig) -> Result<Self, String> {
+        Err(format!("Cannot sync {}: built without the caldav feature", config.calendar_url))
+    }
+
+    fn headers(&self, extra: &[(&str, String)]) -> Vec<(String, String)> {
+        let credentials = base64_encode(format!("{}:{}", self.config.username, self.config.password).as_bytes());
+        let mut headers = vec![("Authorization".to_string(), format!("Basic {}", credentials))];
+        headers.extend(extra.iter().map(|(k, v)| (k.to_string(), v.clone())));
+        headers
+    }
+
+    /// Resolve a server href against the collection URL
+    fn resolve(&self, href: &str) -> String {
+        let base = &self.config.calendar_url;
+        if href.starts_with("http://") || href.starts_with("https://") {
+            return href.to_string();
+        }
+        if href.starts_with('/') {
+            let origin_end = base.find("://").map(|i| i + 3).and_then(|start| base[start..].find('/').map(|i| start + i)).unwrap_or(base.len());
+            return format!("{}{}", &base[..origin_end], href);
+        }
+        format!("{}/{}", base.trim_end_matches('/'), href)
+    }
+
+    /// Events overlapping [window_start, window_end) via a calendar-query REPORT
+    pub fn fetch_events(&mut self, window_start: i64, window_end: i64) -> Result<Vec<CalendarEvent>, String> {
+        let format = |t: i64| chrono::DateTime::from_timestamp(t, 0).map(|dt| dt.format("%Y%m%dT%H%M%SZ").to_string()).unwrap_or_default();
+        let body = format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="utf-8"?>"#,
+                r#"<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">"#,
+                r#"<D:prop><D:getetag/><C:calendar-data/></D:prop>"#,
+                r#"<C:filter><C:comp-filter name="VCALENDAR"><C:comp-filter name="VEVENT">"#,
+                r#"<C:time-range start="{}" end="{}"/>"#,
+                r#"</C:comp-filter></C:comp-filter></C:filter></C:calendar-query>"#
+            ),
+            format(window_start),
+            format(window_end)
+        );
+        let headers = self.headers(&[("Depth", "1".to_string()), ("Content-Type", "application/xml; charset=utf-8".to_string())]);
+        let response = self.transport.send("REPORT", &self.config.calendar_url, &headers, &body)?;
+        if response.status != 207 {
+            return Err(format!("CalDAV REPORT returned {}", response.status));
+        }
+
+        let mut events = Vec::new();
+        for item in xml_elements(&response.body, "response") {
+            let (Some(href), Some(data)) = (xml_elements(item, "href").first().copied(), xml_elements(item, "calendar-data").first().copied()) else {
+                continue;
+            };
+            let etag = xml_elements(item, "getetag").first().map(|e| xml_unescape(e).trim().to_string());
+            let href = xml_unescape(href).trim().to_string();
+            let data = xml_unescape(data);
+            match ics::parse_ics_document(&data) {
+                Ok(parsed) => {
+                    for (uid, e) in &parsed.skipped {
+                        warn!("CalDavSync::fetch_events: Skipping {} in {}: {}", uid, href, e);
+                    }
+                    for event in parsed.events {
+                        let recurring = parsed.recurring.contains(&event.id);
+                        self.resources.insert(event.id.clone(), Resource { href: href.clone(), etag: etag.clone(), calendar_data: data.clone(), recurring });
+                        events.push(event);
+                    }
+                }
+                Err(e) => warn!("CalDavSync::fetch_events: Skipping {}: {}", href, e),
+            }
+        }
+        info!("CalDavSync::fetch_events: Fetched {} events", events.len());
+        Ok(events)
+    }
+
+    /// Import the window's events into the agent; returns how many were new
+    pub fn pull(&mut self, agent: &mut CalendarNegotiationAgent, window_start: i64, window_end: i64) -> Result<usize, String> {
+        let events = self.fetch_events(window_start, window_end)?;
+        Ok(agent.import_events(events))
+    }
+
+    /// Write one accepted suggestion to the server
+    /// Known events are moved inside their server copy (attendees, alarms and status kept) only if unchanged since the
+    /// last fetch; recurring events are refused. New slots are created as tentative events.
+    pub fn push_change(&mut self, suggestion: &ScheduleSuggestion, current: Option<&CalendarEvent>, now: i64) -> Result<(), String> {
+        let (url, precondition, body) = match self.resources.get(&suggestion.event_id) {
+            Some(resource) if resource.recurring => {
+                return Err(format!("Event {} is recurring; move it from your calendar client", suggestion.event_id));
+            }
+            Some(resource) => (
+                self.resolve(&resource.href),
+                resource.etag.clone().map(|etag| ("If-Match", etag)),
+                ics::reschedule_ics(&resource.calendar_data, &suggestion.event_id, suggestion.suggested_start, suggestion.suggested_end, now)?,
+            ),
+            None => (
+                self.resolve(&format!("{}.ics", percent_encode(&suggestion.event_id))),
+                Some(("If-None-Match", "*".to_string())),
+                ics::proposed_change_ics(suggestion, current, now),
+            ),
+        };
+        let mut extra = vec![("Content-Type", "text/calendar; charset=utf-8".to_string())];
+        extra.extend(precondition);
+        let headers = self.headers(&extra);
+        let response = self.transport.send("PUT", &url, &headers, &body)?;
+        match response.status {
+            200..=299 => {
+                info!("CalDavSync::push_change: Proposed new time for {}", suggestion.event_id);
+                let href = self.resources.get(&suggestion.event_id).map(|r| r.href.clone()).unwrap_or(url);
+                self.resources.insert(suggestion.event_id.clone(), Resource { href, etag: response.etag, calendar_data: body, recurring: false });
+                Ok(())
+            }
+            412 => Err(format!("Event {} changed on the server; fetch before proposing again", suggestion.event_id)),
+            status => Err(format!("CalDAV PUT for {} returned {}", suggestion.event_id, status)),
+        }
+    }
+
+    /// Write back every change the agent applied since the last push
+    /// Failed changes stay queued on the agent; returns (event_id, error) for each failure
+    pub fn push_pending(&mut self, agent: &mut CalendarNegotiationAgent, now: i64) -> Vec<(String, String)> {
+        let mut failures = Vec::new();
+        for (suggestion, current) in agent.take_pending_changes() {
+            if let Err(e) = self.push_change(&suggestion, current.as_ref(), now) {
+                warn!("CalDavSync::push_pending: {}", e);
+                failures.push((suggestion.event_id.clone(), e));
+                agent.requeue_change(suggestion);
+            }
+        }
+        failures
+    }
+}
+
+/// Contents of every element with the given local name (namespace prefix ignored)
+/// Nested elements of the same name are not supported; multistatus responses never nest them
+fn xml_elements<'a>(xml: &'a str, local_name: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        let after = &rest[open + 1..];
+        let Some(tag_end) = after.find('>') else {
+            break;
+        };
+        let name = after[..tag_end].split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        if !name.is_empty() && name.rsplit(':').next() == Some(local_name) {
+            if after[..tag_end].ends_with('/') {
+                found.push("");
+            } else {
+                let content = &after[tag_end + 1..];
+                let close = format!("</{}>", name);
+                let Some(end) = content.find(&close) else {
+                    break;
+                };
+                found.push(&content[..end]);
+                rest = &content[end + close.len()..];
+                continue;
+            }
+        }
+        rest = &after[tag_end + 1..];
+    }
+    found
+}
+
+fn xml_unescape(text: &str) -> String {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix("<![CDATA[").and_then(|t| t.strip_suffix("]]>")) {
+        return inner.to_string();
+    }
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&#13;", "\r").replace("&amp;", "&")
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters, so an event id can't add path segments or a query
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = ((chunk[0] as u32) << 16) | ((*chunk.get(1).unwrap_or(&0) as u32) << 8) | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(feature = "caldav")]
+mod http {
+    use super::{CalDavTransport, HttpResponse};
+    use std::time::Duration;
+
+    /// Blocking HTTPS transport
+    pub struct HttpTransport {
+        agent: ureq::Agent,
+    }
+
+    impl HttpTransport {
+        pub fn new() -> Self {
+            Self { agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build() }
+        }
+    }
+
+    impl CalDavTransport for HttpTransport {
+        fn send(&self, method: &str, url: &str, headers: &[(String, String)], body: &str) -> Result<HttpResponse, String> {
+            let mut request = self.agent.request(method, url);
+            for (name, value) in headers {
+                request = request.set(name, value);
+            }
+            let response = match request.send_string(body) {
+                Ok(response) => response,
+                Err(ureq::Error::Status(_, response)) => response,
+                Err(e) => return Err(format!("CalDAV {} {} failed: {}", method, url, e)),
+            };
+            let status = response.status();
+            let etag = response.header("ETag").map(str::to_string);
+            let body = response.into_string().map_err(|e| format!("Failed to read CalDAV response: {}", e))?;
+            Ok(HttpResponse { status, body, etag })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Confidence, Provenance};
+    use std::sync::{Arc, Mutex};
+
+    /// In-memory collection: href -> (etag, ics)
+    #[derive(Clone, Default)]
+    struct FakeServer {
+        resources: Arc<Mutex<HashMap<String, (String, String)>>>,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CalDavTransport for FakeServer {
+        fn send(&self, method: &str, url: &str, headers: &[(String, String)], body: &str) -> Result<HttpResponse, String> {
+            self.requests.lock().unwrap().push(format!("{} {}", method, url));
+            let header = |name: &str| headers.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+            assert_eq!(header("Authorization").as_deref(), Some("Basic bWU6c2VjcmV0"));
+            let mut resources = self.resources.lock().unwrap();
+            let respond = |status: u16, body: String, etag: Option<String>| -> Result<HttpResponse, String> { Ok(HttpResponse { status, body, etag }) };
+            match method {
+                "REPORT" => {
+                    let items: String = resources
+                        .iter()
+                        .map(|(href, (etag, ics))| {
+                            let ics = ics.replace('&', "&amp;").replace('<', "&lt;");
+                            format!("<d:response><d:href>{}</d:href><d:propstat><d:prop><d:getetag>\"{}\"</d:getetag><cal:calendar-data>{}</cal:calendar-data></d:prop></d:propstat></d:response>", href, etag, ics)
+                        })
+                        .collect();
+                    respond(207, format!("<?xml version=\"1.0\"?><d:multistatus xmlns:d=\"DAV:\" xmlns:cal=\"urn:ietf:params:xml:ns:caldav\">{}</d:multistatus>", items), None)
+                }
+                "PUT" => {
+                    let href = url.trim_start_matches("https://dav.example.com").to_string();
+                    let current = resources.get(&href).map(|(etag, _)| format!("\"{}\"", etag));
+                    if (header("If-None-Match").is_some() && current.is_some()) || header("If-Match").is_some_and(|m| Some(m) != current) {
+                        return respond(412, String::new(), None);
+                    }
+                    let etag = format!("v{}", resources.len() + 1);
+                    resources.insert(href, (etag.clone(), body.to_string()));
+                    respond(201, String::new(), Some(format!("\"{}\"", etag)))
+                }
+                _ => respond(405, String::new(), None),
+            }
+        }
+    }
+
+    fn config() -> CalDavConfig {
+        CalDavConfig { calendar_url: "https://dav.example.com/cal/me/".to_string(), username: "me".to_string(), password: "secret".to_string() }
+    }
+
+    fn suggestion(event_id: &str, start: i64) -> ScheduleSuggestion {
+        ScheduleSuggestion {
+            event_id: event_id.to_string(),
+            suggested_start: start,
+            suggested_end: start + 1800,
+            reason: "Outside focus hours".to_string(),
+            expected_benefit: "Protects deep work".to_string(),
+            requires_approval: true,
+            provenance: Provenance { triggering_pattern: "test".to_string(), data_used: Vec::new(), confidence: Confidence::Medium, consent_scopes: Vec::new() },
+        }
+    }
+
+    #[test]
+    fn test_pull_and_push_accepted_suggestions() {
+        let server = FakeServer::default();
+        let standup = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:standup\r\nDTSTART:20240102T090000Z\r\nDTEND:20240102T093000Z\r\nSUMMARY:Standup & planning\r\nATTENDEE;CN=Ana:mailto:ana@example.com\r\nSEQUENCE:2\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let weekly = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:weekly\r\nDTSTART:20240103T090000Z\r\nDTEND:20240103T100000Z\r\nRRULE:FREQ=WEEKLY\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        server.resources.lock().unwrap().insert("/cal/me/weekly.ics".to_string(), ("v1".to_string(), weekly.to_string()));
+        server.resources.lock().unwrap().insert("/cal/me/standup.ics".to_string(), ("v1".to_string(), standup.to_string()));
+        let mut sync = CalDavSync::new(config(), Box::new(server.clone()));
+        let mut agent = CalendarNegotiationAgent::new();
+
+        assert_eq!(sync.pull(&mut agent, 0, 2_000_000_000).unwrap(), 2);
+        assert_eq!(agent.get_event("standup").unwrap().title, "Standup & planning");
+
+        agent.apply_suggestion(&suggestion("standup", 1_704_193_200));
+        agent.apply_suggestion(&suggestion("recovery/1?x", 1_704_200_000));
+        assert!(sync.push_pending(&mut agent, 0).is_empty());
+        assert!(agent.take_pending_changes().is_empty());
+        let stored = server.resources.lock().unwrap().clone();
+        let moved = ics::parse_ics(&stored["/cal/me/standup.ics"].1).unwrap();
+        assert_eq!((moved[0].start_time, moved[0].title.as_str()), (1_704_193_200, "Standup & planning"));
+        let rewritten = &stored["/cal/me/standup.ics"].1;
+        assert!(rewritten.contains("ATTENDEE;CN=Ana:mailto:ana@example.com\r\n") && rewritten.contains("SEQUENCE:3\r\n"));
+        assert!(!rewritten.contains("TENTATIVE"));
+        assert!(stored["/cal/me/recovery%2F1%3Fx.ics"].1.contains("STATUS:TENTATIVE"));
+
+        // Moving the master of a recurring series would move every occurrence
+        agent.apply_suggestion(&suggestion("weekly", 1_704_290_400));
+        let failures = sync.push_pending(&mut agent, 0);
+        assert!(failures[0].1.contains("recurring"));
+        agent.take_pending_changes();
+
+        // Someone else edited the event: the stale ETag is rejected and the change stays queued
+        server.resources.lock().unwrap().get_mut("/cal/me/standup.ics").unwrap().0 = "v9".to_string();
+        agent.apply_suggestion(&suggestion("standup", 1_704_196_800));
+        let failures = sync.push_pending(&mut agent, 0);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].1.contains("changed on the server"));
+        assert_eq!(agent.take_pending_changes().len(), 1);
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(base64_encode(b"me:secret"), "bWU6c2VjcmV0");
+        assert_eq!(base64_encode(b"ab"), "YWI=");
+        assert_eq!(xml_elements("<a:x>1</a:x><y/><x attr=\"v\">2</x><x/>", "x"), vec!["1", "2", ""]);
+        let sync = CalDavSync::new(config(), Box::new(FakeServer::default()));
+        assert_eq!(sync.resolve("/cal/me/a.ics"), "https://dav.example.com/cal/me/a.ics");
+        assert_eq!(sync.resolve("a.ics"), "https://dav.example.com/cal/me/a.ics");
+    }
+}
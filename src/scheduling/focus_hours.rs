@@ -0,0 +1,250 @@
+/// Phase: C | Step: 3 | Source: Athenos_AI_Strategy.md#L122
+/// Focus-Hour Learning
+/// Learn each user's optimal focus windows from focus_duration metrics by hour of day, with confidence and manual overrides
+
+use super::timezone::{local_date, local_hour, parse_timezone};
+use super::CalendarNegotiationAgent;
+use crate::local_stack::FeatureStore;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+/// Where a user's focus windows come from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusHourSource {
+    Default, // Not enough evidence yet
+    Learned,
+    Manual,
+}
+
+/// Learner tuning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusLearnerConfig {
+    pub lookback_weeks: i64,
+    pub min_samples_per_hour: usize, // Hours with fewer observations are ignored
+    pub peak_fraction: f64,          // Hours scoring at least this share of the best hour qualify
+    pub max_windows: usize,
+    pub min_confidence: f64,         // Learned windows below this confidence are not applied
+    pub target_days_per_week: f64,   // Active days per week that count as full coverage
+}
+
+impl Default for FocusLearnerConfig {
+    fn default() -> Self {
+        Self {
+            lookback_weeks: 4,
+            min_samples_per_hour: 3,
+            peak_fraction: 0.75,
+            max_windows: 2,
+            min_confidence: 0.5,
+            target_days_per_week: 5.0,
+        }
+    }
+}
+
+/// Focus profile learned for one user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusHourProfile {
+    pub windows: Vec<(u8, u8)>,    // (start_hour, end_hour), local to the learner's timezone
+    pub confidence: f64,           // 0.0..=1.0
+    pub hourly_focus: Vec<f64>,    // Mean focus minutes per observation for each local hour
+    pub hourly_samples: Vec<usize>,
+    pub learned_at: i64,
+}
+
+/// Learns optimal focus windows per user and keeps manual overrides
+pub struct FocusHourLearner {
+    config: FocusLearnerConfig,
+    timezone: Tz, // Hours are bucketed in local time so DST shifts don't smear them
+    default_windows: Vec<(u8, u8)>,
+    profiles: HashMap<String, FocusHourProfile>,
+    overrides: HashMap<String, Vec<(u8, u8)>>,
+}
+
+fn validate_windows(windows: &[(u8, u8)]) -> Result<(), String> {
+    match windows.iter().find(|(start, end)| start >= end || *end > 24) {
+        Some((start, end)) => Err(format!("Focus window ({}, {}) must satisfy start < end <= 24", start, end)),
+        None => Ok(()),
+    }
+}
+
+impl FocusHourLearner {
+    pub fn new(config: FocusLearnerConfig) -> Self {
+        info!("FocusHourLearner::new: Creating focus-hour learner ({} week lookback)", config.lookback_weeks);
+        Self {
+            config,
+            timezone: Tz::UTC,
+            default_windows: vec![(9, 11), (14, 16)],
+            profiles: HashMap::new(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Bucket observations by local hour in an IANA timezone
+    pub fn set_timezone(&mut self, name: &str) -> Result<(), String> {
+        self.timezone = parse_timezone(name)?;
+        Ok(())
+    }
+
+    /// Windows used until enough behavior has been observed
+    pub fn set_default_windows(&mut self, windows: Vec<(u8, u8)>) -> Result<(), String> {
+        validate_windows(&windows)?;
+        self.default_windows = windows;
+        Ok(())
+    }
+
+    /// Analyze the last `lookback_weeks` of focus metrics and store the user's profile
+    pub fn learn(&mut self, user_id: &str, store: &FeatureStore, now: i64) -> Result<&FocusHourProfile, String> {
+        let lookback_days = self.config.lookback_weeks * 7;
+        let records = store.metrics_in_range(now - lookback_days * 86400, now)?;
+        let mut totals = [0.0; 24];
+        let mut samples = vec![0usize; 24];
+        let mut active_days = HashSet::new();
+        for record in &records {
+            let hour = local_hour(&self.timezone, record.recorded_at) as usize;
+            totals[hour] += record.metrics.focus_duration_min;
+            samples[hour] += 1;
+            active_days.insert(local_date(&self.timezone, record.recorded_at));
+        }
+        let hourly_focus: Vec<f64> = totals.iter().zip(&samples).map(|(total, n)| if *n > 0 { total / *n as f64 } else { 0.0 }).collect();
+        let eligible = |hour: usize| samples[hour] >= self.config.min_samples_per_hour;
+        let best = (0..24).filter(|h| eligible(*h)).map(|h| hourly_focus[h]).fold(0.0, f64::max);
+
+        // Contiguous runs of peak hours, ranked by total focus
+        let peak: Vec<bool> = (0..24).map(|h| best > 0.0 && eligible(h) && hourly_focus[h] >= best * self.config.peak_fraction).collect();
+        let mut windows: Vec<(u8, u8)> = Vec::new();
+        let mut hour = 0;
+        while hour < 24 {
+            if peak[hour] {
+                let start = hour;
+                while hour < 24 && peak[hour] {
+                    hour += 1;
+                }
+                windows.push((start as u8, hour as u8));
+            } else {
+                hour += 1;
+            }
+        }
+        let window_score = |w: &(u8, u8)| (w.0..w.1).map(|h| hourly_focus[h as usize]).sum::<f64>();
+        windows.sort_by(|a, b| window_score(b).total_cmp(&window_score(a)).then(a.0.cmp(&b.0)));
+        windows.truncate(self.config.max_windows);
+        windows.sort();
+
+        // Confidence: enough active days in the lookback, and peak hours that clearly beat the rest
+        let coverage = (active_days.len() as f64 / (self.config.lookback_weeks as f64 * self.config.target_days_per_week).max(1.0)).min(1.0);
+        let mean = |selected: bool| {
+            let values: Vec<f64> = (0..24)
+                .filter(|h| eligible(*h) && windows.iter().any(|w| (w.0..w.1).contains(&(*h as u8))) == selected)
+                .map(|h| hourly_focus[h])
+                .collect();
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+        let contrast = match (mean(true), mean(false)) {
+            (Some(peak), Some(rest)) if peak > 0.0 => ((peak - rest) / peak).clamp(0.0, 1.0),
+            (Some(_), None) => 0.5, // Only peak hours observed: nothing to compare against
+            _ => 0.0,
+        };
+        let confidence = coverage * contrast;
+        info!("FocusHourLearner::learn: {} windows {:?} (confidence {:.2}) from {} records", user_id, windows, confidence, records.len());
+
+        let profile = FocusHourProfile { windows, confidence, hourly_focus, hourly_samples: samples, learned_at: now };
+        self.profiles.insert(user_id.to_string(), profile);
+        Ok(&self.profiles[user_id])
+    }
+
+    pub fn get_profile(&self, user_id: &str) -> Option<&FocusHourProfile> {
+        self.profiles.get(user_id)
+    }
+
+    /// Pin a user's focus windows; learning continues but no longer changes them
+    pub fn set_override(&mut self, user_id: &str, windows: Vec<(u8, u8)>) -> Result<(), String> {
+        validate_windows(&windows)?;
+        info!("FocusHourLearner::set_override: {} pinned to {:?}", user_id, windows);
+        self.overrides.insert(user_id.to_string(), windows);
+        Ok(())
+    }
+
+    /// Return the user to learned windows
+    pub fn clear_override(&mut self, user_id: &str) -> bool {
+        self.overrides.remove(user_id).is_some()
+    }
+
+    /// Effective windows: manual override, then confident learned windows, then defaults
+    pub fn focus_hours(&self, user_id: &str) -> (Vec<(u8, u8)>, FocusHourSource) {
+        if let Some(windows) = self.overrides.get(user_id) {
+            return (windows.clone(), FocusHourSource::Manual);
+        }
+        match self.profiles.get(user_id) {
+            Some(profile) if !profile.windows.is_empty() && profile.confidence >= self.config.min_confidence => {
+                (profile.windows.clone(), FocusHourSource::Learned)
+            }
+            _ => (self.default_windows.clone(), FocusHourSource::Default),
+        }
+    }
+
+    /// Set the agent's protected focus hours to the user's effective windows
+    pub fn apply(&self, user_id: &str, agent: &mut CalendarNegotiationAgent) -> FocusHourSource {
+        let (windows, source) = self.focus_hours(user_id);
+        agent.set_focus_hours(windows);
+        source
+    }
+}
+
+impl Default for FocusHourLearner {
+    fn default() -> Self {
+        Self::new(FocusLearnerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_stack::TemporalMetrics;
+
+    fn store_with_focus(days: i64, focus_by_hour: &[(i64, f64)]) -> FeatureStore {
+        let mut store = FeatureStore::new();
+        for day in 0..days {
+            for (hour, focus) in focus_by_hour {
+                let metrics = TemporalMetrics {
+                    time_to_first_action_min: 0.0,
+                    focus_duration_min: *focus,
+                    context_switch_count: 0,
+                    repeat_count: 0,
+                    session_duration_min: 60.0,
+                };
+                store.store_metrics_at(format!("obs_{}_{}", day, hour), metrics, day * 86400 + hour * 3600 + 600);
+            }
+        }
+        store
+    }
+
+    #[test]
+    fn test_learns_peak_windows() {
+        let hours = [(7, 50.0), (8, 48.0), (10, 15.0), (13, 10.0), (20, 45.0), (21, 12.0)];
+        let store = store_with_focus(28, &hours);
+        let mut learner = FocusHourLearner::default();
+        let profile = learner.learn("u1", &store, 28 * 86400).unwrap();
+        assert_eq!(profile.windows, vec![(7, 9), (20, 21)]);
+        assert!(profile.confidence > 0.6, "confidence {}", profile.confidence);
+
+        let mut agent = CalendarNegotiationAgent::new();
+        assert_eq!(learner.apply("u1", &mut agent), FocusHourSource::Learned);
+        assert_eq!(agent.focus_hours(), &[(7, 9), (20, 21)]);
+    }
+
+    #[test]
+    fn test_low_confidence_and_overrides() {
+        let mut learner = FocusHourLearner::new(FocusLearnerConfig { min_samples_per_hour: 1, ..FocusLearnerConfig::default() });
+        // Two days of data is not enough to move the defaults
+        let sparse = store_with_focus(2, &[(6, 50.0), (12, 10.0)]);
+        learner.learn("u1", &sparse, 2 * 86400).unwrap();
+        assert_eq!(learner.focus_hours("u1"), (vec![(9, 11), (14, 16)], FocusHourSource::Default));
+
+        assert!(learner.set_override("u1", vec![(16, 15)]).is_err());
+        learner.set_override("u1", vec![(13, 15)]).unwrap();
+        assert_eq!(learner.focus_hours("u1"), (vec![(13, 15)], FocusHourSource::Manual));
+        assert!(learner.clear_override("u1"));
+        assert_eq!(learner.focus_hours("u1").1, FocusHourSource::Default);
+    }
+}
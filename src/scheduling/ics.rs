@@ -0,0 +1,501 @@
+/// Phase: C | Step: 3 | Source: Athenos_AI_Strategy.md#L122
+/// ICS Import/Export
+/// Parse iCalendar (RFC 5545) VEVENTs into CalendarEvents and write accepted suggestions back as tentative changes
+
+use super::timezone::{local_timestamp, parse_timezone};
+use super::{CalendarEvent, EventPriority, ScheduleSuggestion};
+use chrono::{NaiveDate, NaiveDateTime};
+use tracing::{info, warn};
+
+/// Content lines are folded after this many octets
+const MAX_LINE_OCTETS: usize = 75;
+
+/// One property line: NAME;PARAM=VALUE:value
+struct ContentLine {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn parse(line: &str) -> Option<Self> {
+        // The value starts at the first colon outside a quoted parameter value
+        let mut quoted = false;
+        let colon = line.char_indices().find(|(_, c)| {
+            if *c == '"' {
+                quoted = !quoted;
+            }
+            *c == ':' && !quoted
+        })?.0;
+        let mut head = line[..colon].split(';');
+        let name = head.next()?.trim().to_uppercase();
+        let params = head
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (k.trim().to_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+        Some(Self { name, params, value: line[colon + 1..].to_string() })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+}
+
+/// Join continuation lines (leading space or tab) onto the previous line
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if raw.is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn escape_text(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets without splitting a character
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// DATE or DATE-TIME value as a Unix timestamp
+/// TZID-qualified times are resolved with the IANA zone database; floating times and unknown zones are read as UTC
+fn parse_datetime(line: &ContentLine) -> Result<i64, String> {
+    let value = line.value.trim();
+    if line.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp())
+            .map_err(|e| format!("Invalid {} date '{}': {}", line.name, value, e));
+    }
+    let naive = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map_err(|e| format!("Invalid {} '{}': {}", line.name, value, e))?;
+    match line.param("TZID") {
+        Some(tzid) if !value.ends_with('Z') => match parse_timezone(tzid.trim_start_matches('/')) {
+            Ok(tz) => Ok(local_timestamp(&tz, naive)),
+            Err(e) => {
+                warn!("ics::parse_datetime: {}, reading {} as UTC", e, value);
+                Ok(naive.and_utc().timestamp())
+            }
+        },
+        _ => Ok(naive.and_utc().timestamp()),
+    }
+}
+
+/// DURATION value (e.g. PT1H30M, P1D, P2W) in seconds
+fn parse_duration(value: &str) -> Result<i64, String> {
+    let (sign, rest) = match value.trim().strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.trim().trim_start_matches('+')),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(|| format!("Invalid duration '{}'", value))?;
+    let mut total = 0i64;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let n: i64 = number.parse().map_err(|_| format!("Invalid duration '{}'", value))?;
+                total += n * match c {
+                    'W' => 7 * 86400,
+                    'D' => 86400,
+                    'H' => 3600,
+                    'M' => 60,
+                    _ => 1,
+                };
+                number.clear();
+            }
+            _ => return Err(format!("Invalid duration '{}'", value)),
+        }
+    }
+    Ok(sign * total)
+}
+
+fn format_datetime(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_default()
+}
+
+/// RFC 5545 PRIORITY (1 highest, 9 lowest, 0 undefined)
+fn priority_from_ics(value: u8) -> EventPriority {
+    match value {
+        1 => EventPriority::Critical,
+        2..=4 => EventPriority::High,
+        6..=9 => EventPriority::Low,
+        _ => EventPriority::Medium,
+    }
+}
+
+fn priority_to_ics(priority: &EventPriority) -> u8 {
+    match priority {
+        EventPriority::Critical => 1,
+        EventPriority::High => 3,
+        EventPriority::Medium => 5,
+        EventPriority::Low => 7,
+    }
+}
+
+/// Events read from one iCalendar document
+#[derive(Debug, Clone, Default)]
+pub struct IcsImport {
+    pub events: Vec<CalendarEvent>,
+    pub recurring: Vec<String>,         // UIDs with an RRULE; recurrences are not expanded, only the first occurrence is imported
+    pub skipped: Vec<(String, String)>, // (UID, or "#n" for the n-th VEVENT, error) of events that could not be read
+}
+
+/// Parse every VEVENT in an iCalendar document
+pub fn parse_ics(text: &str) -> Result<Vec<CalendarEvent>, String> {
+    Ok(parse_ics_document(text)?.events)
+}
+
+/// Parse every VEVENT, skipping (and reporting) the ones that can't be read instead of failing the document
+/// Cancelled events are skipped; transparent (free) events are treated as flexible
+pub fn parse_ics_document(text: &str) -> Result<IcsImport, String> {
+    let mut import = IcsImport::default();
+    let mut position = 0;
+    let mut current: Option<Vec<ContentLine>> = None;
+    let mut nested = 0; // Depth of components inside the VEVENT (e.g. VALARM)
+    for raw in unfold(text) {
+        let Some(line) = ContentLine::parse(&raw) else {
+            continue;
+        };
+        let (name, value) = (line.name.clone(), line.value.trim().to_uppercase());
+        match (name.as_str(), value.as_str(), current.as_mut()) {
+            ("BEGIN", "VEVENT", None) => current = Some(Vec::new()),
+            ("BEGIN", _, Some(_)) => nested += 1,
+            ("END", "VEVENT", Some(_)) if nested == 0 => {
+                let props = current.take().unwrap_or_default();
+                position += 1;
+                match to_event(&props) {
+                    Ok(Some(event)) => {
+                        if props.iter().any(|p| p.name == "RRULE") {
+                            warn!("ics::parse_ics: {} recurs; only its first occurrence is imported", event.id);
+                            import.recurring.push(event.id.clone());
+                        }
+                        import.events.push(event);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let uid = props.iter().find(|p| p.name == "UID").map(|p| p.value.trim().to_string()).unwrap_or_else(|| format!("#{}", position));
+                        warn!("ics::parse_ics: Skipping {}: {}", uid, e);
+                        import.skipped.push((uid, e));
+                    }
+                }
+            }
+            ("END", _, Some(_)) => nested -= 1,
+            (_, _, Some(props)) if nested == 0 => props.push(line),
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err("Unterminated VEVENT".to_string());
+    }
+    info!("ics::parse_ics: Parsed {} events ({} skipped, {} recurring)", import.events.len(), import.skipped.len(), import.recurring.len());
+    Ok(import)
+}
+
+fn to_event(props: &[ContentLine]) -> Result<Option<CalendarEvent>, String> {
+    let prop = |name: &str| props.iter().find(|p| p.name == name);
+    if prop("STATUS").is_some_and(|s| s.value.trim().eq_ignore_ascii_case("CANCELLED")) {
+        return Ok(None);
+    }
+    let uid = prop("UID").map(|p| p.value.trim().to_string()).ok_or("VEVENT without UID")?;
+    let start_line = prop("DTSTART").ok_or_else(|| format!("VEVENT {} without DTSTART", uid))?;
+    let start_time = parse_datetime(start_line)?;
+    let end_time = match (prop("DTEND"), prop("DURATION")) {
+        (Some(end), _) => parse_datetime(end)?,
+        (None, Some(duration)) => start_time + parse_duration(&duration.value)?,
+        // All-day events last a day, timed events without an end are instants
+        (None, None) if start_line.param("VALUE") == Some("DATE") || start_line.value.trim().len() == 8 => start_time + 86400,
+        (None, None) => start_time,
+    };
+    if end_time < start_time {
+        return Err(format!("VEVENT {} ends before it starts", uid));
+    }
+    Ok(Some(CalendarEvent {
+        id: uid,
+        title: prop("SUMMARY").map(|p| unescape_text(&p.value)).unwrap_or_default(),
+        start_time,
+        end_time,
+        priority: prop("PRIORITY").and_then(|p| p.value.trim().parse().ok()).map(priority_from_ics).unwrap_or(EventPriority::Medium),
+        is_flexible: prop("TRANSP").is_some_and(|t| t.value.trim().eq_ignore_ascii_case("TRANSPARENT")),
+    }))
+}
+
+fn write_event(out: &mut String, event: &CalendarEvent, now: i64, extra: &[String]) {
+    out.push_str("BEGIN:VEVENT\r\n");
+    out.push_str(&fold(&format!("UID:{}", event.id)));
+    out.push_str(&format!("DTSTAMP:{}\r\n", format_datetime(now)));
+    out.push_str(&format!("DTSTART:{}\r\n", format_datetime(event.start_time)));
+    out.push_str(&format!("DTEND:{}\r\n", format_datetime(event.end_time)));
+    out.push_str(&fold(&format!("SUMMARY:{}", escape_text(&event.title))));
+    out.push_str(&format!("PRIORITY:{}\r\n", priority_to_ics(&event.priority)));
+    out.push_str(if event.is_flexible { "TRANSP:TRANSPARENT\r\n" } else { "TRANSP:OPAQUE\r\n" });
+    for line in extra {
+        out.push_str(&fold(line));
+    }
+    out.push_str("END:VEVENT\r\n");
+}
+
+fn calendar(body: &str) -> String {
+    format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Athenos//Calendar Agent//EN\r\n{}END:VCALENDAR\r\n", body)
+}
+
+/// Export events as an iCalendar document
+pub fn to_ics(events: &[CalendarEvent], now: i64) -> String {
+    let mut body = String::new();
+    for event in events {
+        write_event(&mut body, event, now, &[]);
+    }
+    calendar(&body)
+}
+
+/// Accepted suggestion as a tentative VEVENT
+/// `current` is the event being moved, if the calendar already has it; otherwise the suggestion books a new slot
+pub fn proposed_change(suggestion: &ScheduleSuggestion, current: Option<&CalendarEvent>) -> CalendarEvent {
+    let mut event = current.cloned().unwrap_or_else(|| CalendarEvent {
+        id: suggestion.event_id.clone(),
+        title: suggestion.reason.clone(),
+        start_time: suggestion.suggested_start,
+        end_time: suggestion.suggested_end,
+        priority: EventPriority::Low,
+        is_flexible: true,
+    });
+    event.start_time = suggestion.suggested_start;
+    event.end_time = suggestion.suggested_end;
+    event
+}
+
+/// Move one event inside a server copy of its iCalendar resource, keeping every other property
+/// Attendees, location, description, alarms and status are untouched; the time is rewritten in UTC and SEQUENCE bumped.
+/// Recurring events are refused: moving the master would move the whole series.
+pub fn reschedule_ics(original: &str, uid: &str, start: i64, end: i64, now: i64) -> Result<String, String> {
+    let lines = unfold(original);
+    let mut out: Vec<String> = Vec::new();
+    let mut component: Option<Vec<String>> = None;
+    let mut nested = 0;
+    let mut found = false;
+    for raw in lines {
+        let Some(line) = ContentLine::parse(&raw) else {
+            match component.as_mut() {
+                Some(component) => component.push(raw),
+                None => out.push(raw),
+            }
+            continue;
+        };
+        let value = line.value.trim().to_uppercase();
+        match (line.name.as_str(), value.as_str(), component.as_mut()) {
+            ("BEGIN", "VEVENT", None) => component = Some(vec![raw]),
+            ("BEGIN", _, Some(lines)) => {
+                nested += 1;
+                lines.push(raw);
+            }
+            ("END", "VEVENT", Some(lines)) if nested == 0 => {
+                lines.push(raw);
+                let lines = component.take().unwrap_or_default();
+                let props: Vec<ContentLine> = lines.iter().filter_map(|l| ContentLine::parse(l)).collect();
+                let is_target = props.iter().any(|p| p.name == "UID" && p.value.trim() == uid) && !props.iter().any(|p| p.name == "RECURRENCE-ID");
+                if !is_target {
+                    out.extend(lines);
+                    continue;
+                }
+                if props.iter().any(|p| p.name == "RRULE") {
+                    return Err(format!("Event {} is recurring; propose the change from your calendar client", uid));
+                }
+                found = true;
+                out.extend(rescheduled(&lines, start, end, now));
+            }
+            ("END", _, Some(lines)) => {
+                nested -= 1;
+                lines.push(raw);
+            }
+            (_, _, Some(lines)) => lines.push(raw),
+            _ => out.push(raw),
+        }
+    }
+    if !found {
+        return Err(format!("Event {} not found in its calendar resource", uid));
+    }
+    Ok(out.iter().map(|line| fold(line)).collect())
+}
+
+/// VEVENT lines with new times; only top-level properties are rewritten, nested alarms are copied as-is
+fn rescheduled(lines: &[String], start: i64, end: i64, now: i64) -> Vec<String> {
+    let mut out = Vec::with_capacity(lines.len() + 2);
+    let mut depth = 0;
+    let mut sequence = 0;
+    for raw in lines {
+        let Some(line) = ContentLine::parse(raw) else {
+            out.push(raw.clone());
+            continue;
+        };
+        match line.name.as_str() {
+            "BEGIN" => depth += 1,
+            "END" => depth -= 1,
+            _ => {}
+        }
+        if depth != 1 || line.name == "BEGIN" {
+            out.push(raw.clone());
+            continue;
+        }
+        match line.name.as_str() {
+            "DTSTART" => out.push(format!("DTSTART:{}", format_datetime(start))),
+            "DTEND" | "DURATION" | "SEQUENCE" | "DTSTAMP" | "LAST-MODIFIED" => {
+                if line.name == "SEQUENCE" {
+                    sequence = line.value.trim().parse::<u32>().unwrap_or(0);
+                }
+            }
+            _ => out.push(raw.clone()),
+        }
+    }
+    // Re-add the dropped timing properties just before END:VEVENT
+    let end_line = out.pop().unwrap_or_else(|| "END:VEVENT".to_string());
+    out.push(format!("DTEND:{}", format_datetime(end)));
+    out.push(format!("DTSTAMP:{}", format_datetime(now)));
+    out.push(format!("LAST-MODIFIED:{}", format_datetime(now)));
+    out.push(format!("SEQUENCE:{}", sequence + 1));
+    out.push(end_line);
+    out
+}
+
+/// Single-event iCalendar document proposing one accepted suggestion
+pub fn proposed_change_ics(suggestion: &ScheduleSuggestion, current: Option<&CalendarEvent>, now: i64) -> String {
+    let mut body = String::new();
+    write_proposed(&mut body, suggestion, current, now);
+    calendar(&body)
+}
+
+/// Export accepted suggestions as tentative events for the user's calendar client to confirm
+pub fn proposed_changes_to_ics(changes: &[(ScheduleSuggestion, Option<CalendarEvent>)], now: i64) -> String {
+    let mut body = String::new();
+    for (suggestion, current) in changes {
+        write_proposed(&mut body, suggestion, current.as_ref(), now);
+    }
+    calendar(&body)
+}
+
+fn write_proposed(out: &mut String, suggestion: &ScheduleSuggestion, current: Option<&CalendarEvent>, now: i64) {
+    let event = proposed_change(suggestion, current);
+    let description = format!("{} ({})", suggestion.reason, suggestion.expected_benefit);
+    write_event(
+        out,
+        &event,
+        now,
+        &["STATUS:TENTATIVE".to_string(), format!("DESCRIPTION:{}", escape_text(&description)), "X-ATHENOS-PROPOSED:TRUE".to_string()],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Confidence, Provenance};
+
+    const SAMPLE: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:standup-1\r\nDTSTART:20240102T090000Z\r\nDTEND:20240102T091500Z\r\nSUMMARY:Daily standup\\, team A\r\nPRIORITY:2\r\nBEGIN:VALARM\r\nTRIGGER:-PT5M\r\nEND:VALARM\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nUID:review-2\r\nDTSTART;TZID=\"Europe/Paris\":20240102T140000\r\nDURATION:PT1H30M\r\nSUMMARY:Design review with a very long title that has to be folded across \r\n several lines\r\nTRANSP:TRANSPARENT\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nUID:cancelled-3\r\nDTSTART;VALUE=DATE:20240103\r\nSTATUS:CANCELLED\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_ics() {
+        let events = parse_ics(SAMPLE).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, "standup-1");
+        assert_eq!(events[0].title, "Daily standup, team A");
+        assert_eq!(events[0].end_time - events[0].start_time, 900);
+        assert_eq!(events[0].priority, EventPriority::High);
+        assert!(!events[0].is_flexible);
+        assert_eq!(events[1].title, "Design review with a very long title that has to be folded across several lines");
+        assert_eq!(events[1].end_time - events[1].start_time, 5400);
+        assert!(events[1].is_flexible);
+        // 14:00 Paris (CET, UTC+1) is 13:00 UTC
+        assert_eq!(events[1].start_time, 1_704_200_400);
+
+        // A broken event is skipped and reported; only an unterminated document fails
+        assert!(parse_ics("BEGIN:VEVENT\r\nUID:x\r\n").is_err());
+        let import = parse_ics_document(&SAMPLE.replace("END:VCALENDAR", "BEGIN:VEVENT\r\nUID:x\r\nEND:VEVENT\r\nEND:VCALENDAR")).unwrap();
+        assert_eq!(import.events.len(), 2);
+        assert_eq!(import.skipped.len(), 1);
+        assert_eq!(import.skipped[0].0, "x");
+
+        let recurring = parse_ics_document("BEGIN:VEVENT\r\nUID:weekly\r\nDTSTART:20240102T090000Z\r\nRRULE:FREQ=WEEKLY\r\nEND:VEVENT\r\n").unwrap();
+        assert_eq!(recurring.recurring, vec!["weekly".to_string()]);
+    }
+
+    #[test]
+    fn test_reschedule_keeps_other_properties() {
+        let moved = reschedule_ics(SAMPLE, "review-2", 1_704_207_600, 1_704_211_200, 0).unwrap();
+        assert!(moved.contains("DTSTART:20240102T150000Z\r\n") && moved.contains("DTEND:20240102T160000Z\r\n"));
+        assert!(moved.contains("TRANSP:TRANSPARENT\r\n") && moved.contains("SEQUENCE:1\r\n"));
+        assert!(!moved.contains("DURATION") && !moved.contains("TENTATIVE"));
+        // Other events and their alarms are copied unchanged
+        assert!(moved.contains("DTSTART:20240102T090000Z\r\n") && moved.contains("TRIGGER:-PT5M\r\n"));
+        let events = parse_ics(&moved).unwrap();
+        assert_eq!((events[1].start_time, events[1].title.as_str()), (1_704_207_600, "Design review with a very long title that has to be folded across several lines"));
+
+        assert!(reschedule_ics(SAMPLE, "missing", 0, 1, 0).is_err());
+        let weekly = "BEGIN:VEVENT\r\nUID:weekly\r\nDTSTART:20240102T090000Z\r\nRRULE:FREQ=WEEKLY\r\nEND:VEVENT\r\n";
+        assert!(reschedule_ics(weekly, "weekly", 0, 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_export_round_trips() {
+        let events = parse_ics(SAMPLE).unwrap();
+        let exported = to_ics(&events, 0);
+        assert!(exported.lines().all(|l| l.len() <= MAX_LINE_OCTETS + 1));
+        let reparsed = parse_ics(&exported).unwrap();
+        assert_eq!(reparsed.len(), 2);
+        assert_eq!((reparsed[1].title.as_str(), reparsed[1].start_time), (events[1].title.as_str(), events[1].start_time));
+
+        let suggestion = ScheduleSuggestion {
+            event_id: "standup-1".to_string(),
+            suggested_start: events[0].start_time + 3600,
+            suggested_end: events[0].end_time + 3600,
+            reason: "Conflicts with focus hours".to_string(),
+            expected_benefit: "Protects deep work".to_string(),
+            requires_approval: true,
+            provenance: Provenance {
+                triggering_pattern: "test".to_string(),
+                data_used: Vec::new(),
+                confidence: Confidence::Medium,
+                consent_scopes: Vec::new(),
+            },
+        };
+        let proposed = proposed_change_ics(&suggestion, events.first(), 0);
+        assert!(proposed.contains("STATUS:TENTATIVE\r\n"));
+        let moved = parse_ics(&proposed).unwrap();
+        assert_eq!((moved[0].id.as_str(), moved[0].start_time), ("standup-1", suggestion.suggested_start));
+        assert_eq!(moved[0].title, "Daily standup, team A");
+    }
+}
@@ -3,10 +3,23 @@
 /// Implement anticipatory scheduling and calendar negotiation agent
 
 use crate::types::*;
+use crate::attention::{AttentionService, AttentionState, InterruptionPriority};
+use crate::edge::{OSEvent, OSEventType};
+use crate::forecast::ForecastPoint;
+use crate::config::{AthenosConfig, ConfigListener};
+use crate::enterprise::focus_heatmap::TeamFocusHeatmap;
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::info;
 
+pub mod caldav;
+pub mod focus_hours;
+pub mod ics;
+pub mod timezone;
+
+use timezone::{local_weekday, overlaps_local_hours, parse_timezone, within_local_hours};
+
 /// Calendar event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarEvent {
@@ -36,31 +49,294 @@ pub struct ScheduleSuggestion {
     pub reason: String,
     pub expected_benefit: String,
     pub requires_approval: bool,
+    pub provenance: Provenance,
+}
+
+/// Busy interval from an attendee's free/busy data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusyInterval {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Source of attendee free/busy data
+pub trait CalendarProvider {
+    fn free_busy(&self, attendee_id: &str, window_start: i64, window_end: i64) -> Result<Vec<BusyInterval>, String>;
+}
+
+/// In-memory calendar provider (tests and simulation)
+#[derive(Debug, Clone, Default)]
+pub struct StaticCalendarProvider {
+    busy: HashMap<String, Vec<BusyInterval>>,
+}
+
+impl StaticCalendarProvider {
+    /// Create empty provider
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add busy interval for attendee
+    pub fn add_busy(&mut self, attendee_id: &str, start: i64, end: i64) {
+        self.busy.entry(attendee_id.to_string()).or_default().push(BusyInterval { start, end });
+    }
+}
+
+impl CalendarProvider for StaticCalendarProvider {
+    fn free_busy(&self, attendee_id: &str, window_start: i64, window_end: i64) -> Result<Vec<BusyInterval>, String> {
+        Ok(self.busy
+            .get(attendee_id)
+            .map(|intervals| intervals.iter().filter(|b| b.start < window_end && b.end > window_start).cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Meeting attendee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attendee {
+    pub id: String,
+    pub required: bool,
+    pub focus_hours: Vec<(u8, u8)>, // (start_hour, end_hour), local to the attendee's timezone
+    #[serde(default)]
+    pub timezone: Option<String>, // IANA name; UTC when unset
+}
+
+/// Candidate slot that works for all required attendees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotProposal {
+    pub start: i64,
+    pub end: i64,
+    pub focus_hour_conflicts: usize, // Attendees (incl. organizer) whose focus hours the slot overlaps
+    pub optional_available: Vec<String>,
+    #[serde(default)]
+    pub team_focus_score: f64, // Collective focus the slot would interrupt, from the team heatmap (0.0 without one)
+}
+
+/// Counter-proposal status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CounterProposalStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+/// Counter-proposal from an attendee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounterProposal {
+    pub id: String,
+    pub event_id: String,
+    pub from_attendee: String,
+    pub proposed_start: i64,
+    pub proposed_end: i64,
+    pub reason: String,
+    pub status: CounterProposalStatus,
+    pub created_at: i64,
+}
+
+/// Two stored events whose times overlap
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventConflict {
+    pub first_event_id: String, // Earlier-starting event
+    pub second_event_id: String,
+    pub overlap_start: i64,
+    pub overlap_end: i64,
+}
+
+impl EventConflict {
+    pub fn overlap_minutes(&self) -> f64 {
+        (self.overlap_end - self.overlap_start) as f64 / 60.0
+    }
+}
+
+/// Meetings separated by at most this gap count as back-to-back
+const BACK_TO_BACK_GAP_SECS: i64 = 5 * 60;
+/// Working day used for meeting load percentage
+const WORKDAY_MINUTES: f64 = 8.0 * 60.0;
+/// Local hours conflict resolutions may move events into
+const WORKING_HOURS: (u8, u8) = (8, 18);
+/// How far ahead conflict resolution looks for a free slot
+const RESOLUTION_HORIZON_SECS: i64 = 7 * 86400;
+/// Counter-proposals kept; resolved or orphaned ones go first, then the oldest pending
+const MAX_COUNTER_PROPOSALS: usize = 500;
+/// Id prefixes of breaks and recovery buffers Athenos books for the user
+const HOLD_ID_PREFIXES: [&str; 2] = ["break_", "recovery_"];
+/// Apps whose activity does not count as refocusing after a meeting
+const MEETING_APPS: [&str; 5] = ["zoom", "teams", "meet", "webex", "slack"];
+
+/// Contiguous run of back-to-back meetings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingBlock {
+    pub event_ids: Vec<String>,
+    pub start: i64,
+    pub end: i64,
+}
+
+impl MeetingBlock {
+    /// Block length in minutes
+    pub fn duration_minutes(&self) -> f64 {
+        (self.end - self.start) as f64 / 60.0
+    }
+}
+
+/// Daily meeting load and recovery statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeetingLoadStats {
+    pub day_start: i64,
+    pub meeting_count: usize,
+    pub meeting_minutes: f64,
+    pub load_pct: f64, // Share of an 8-hour working day
+    pub back_to_back_streaks: usize, // Blocks of two or more meetings
+    pub longest_block_minutes: f64,
+    pub avg_refocus_minutes: Option<f64>, // Meeting end to first focused work event
 }
 
 /// Calendar negotiation agent
 /// Source: Athenos_AI_Strategy.md#L122
 pub struct CalendarNegotiationAgent {
     events: HashMap<String, CalendarEvent>,
-    optimal_focus_hours: Vec<(u8, u8)>, // (start_hour, end_hour)
+    optimal_focus_hours: Vec<(u8, u8)>, // (start_hour, end_hour), local to `timezone`
+    timezone: Tz,
+    attention: AttentionService,
+    counter_proposals: HashMap<String, CounterProposal>,
+    next_counter_id: u64, // Counter-proposal ids stay unique after pruning
+    team_heatmap: Option<TeamFocusHeatmap>,
+    pending_changes: Vec<ScheduleSuggestion>, // Applied suggestions not yet written back to the external calendar
+    holds: HashMap<String, CalendarEvent>, // Booked breaks and recovery buffers: block new slots but are not meetings
 }
 
 impl CalendarNegotiationAgent {
     /// Create new calendar negotiation agent
     pub fn new() -> Self {
+        Self::with_attention(AttentionService::new())
+    }
+
+    /// Create calendar negotiation agent sharing an attention service
+    pub fn with_attention(attention: AttentionService) -> Self {
         info!("CalendarNegotiationAgent::new: Creating calendar negotiation agent");
         Self {
             events: HashMap::new(),
             optimal_focus_hours: vec![(9, 11), (14, 16)], // Default optimal hours
+            timezone: Tz::UTC,
+            attention,
+            counter_proposals: HashMap::new(),
+            next_counter_id: 0,
+            team_heatmap: None,
+            pending_changes: Vec::new(),
+            holds: HashMap::new(),
         }
     }
 
+    /// Use a team focus heatmap when ranking team meeting slots
+    pub fn set_team_heatmap(&mut self, heatmap: Option<TeamFocusHeatmap>) {
+        self.team_heatmap = heatmap;
+    }
+
+    /// Replace protected focus hours (e.g. learned or manually pinned windows)
+    pub fn set_focus_hours(&mut self, hours: Vec<(u8, u8)>) {
+        info!("CalendarNegotiationAgent::set_focus_hours: Protecting {:?}", hours);
+        self.optimal_focus_hours = hours;
+    }
+
+    pub fn focus_hours(&self) -> &[(u8, u8)] {
+        &self.optimal_focus_hours
+    }
+
+    /// Interpret focus and working hours in an IANA timezone (e.g. "America/New_York")
+    pub fn set_timezone(&mut self, name: &str) -> Result<(), String> {
+        self.timezone = parse_timezone(name)?;
+        info!("CalendarNegotiationAgent::set_timezone: Using {}", self.timezone);
+        Ok(())
+    }
+
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
     /// Add calendar event
     pub fn add_event(&mut self, event: CalendarEvent) {
         info!("CalendarNegotiationAgent::add_event: Adding event {}", event.id);
         self.events.insert(event.id.clone(), event);
     }
 
+    /// Add or replace events imported from an external calendar; returns how many were new
+    /// Breaks and buffers booked earlier come back from the server as holds, not meetings
+    pub fn import_events(&mut self, events: Vec<CalendarEvent>) -> usize {
+        let new = events.iter().filter(|e| !self.events.contains_key(&e.id) && !self.holds.contains_key(&e.id)).count();
+        info!("CalendarNegotiationAgent::import_events: Importing {} events ({} new)", events.len(), new);
+        for event in events {
+            if Self::is_hold(&event.id) || self.holds.contains_key(&event.id) {
+                self.holds.insert(event.id.clone(), event);
+            } else {
+                self.events.insert(event.id.clone(), event);
+            }
+        }
+        new
+    }
+
+    fn is_hold(event_id: &str) -> bool {
+        HOLD_ID_PREFIXES.iter().any(|prefix| event_id.starts_with(prefix))
+    }
+
+    /// Calendar event or booked hold with this id
+    pub fn get_event(&self, event_id: &str) -> Option<&CalendarEvent> {
+        self.events.get(event_id).or_else(|| self.holds.get(event_id))
+    }
+
+    /// Booked breaks and recovery buffers
+    pub fn holds(&self) -> Vec<&CalendarEvent> {
+        let mut holds: Vec<&CalendarEvent> = self.holds.values().collect();
+        holds.sort_by_key(|h| h.start_time);
+        holds
+    }
+
+    /// Whether any meeting or hold overlaps [start, end)
+    fn is_booked(&self, start: i64, end: i64) -> bool {
+        self.events.values().chain(self.holds.values()).any(|e| e.start_time < end && e.end_time > start)
+    }
+
+    /// Applied suggestions awaiting write-back, with the event each one now describes
+    pub fn take_pending_changes(&mut self) -> Vec<(ScheduleSuggestion, Option<CalendarEvent>)> {
+        std::mem::take(&mut self.pending_changes)
+            .into_iter()
+            .map(|suggestion| {
+                let event = self.get_event(&suggestion.event_id).cloned();
+                (suggestion, event)
+            })
+            .collect()
+    }
+
+    /// Put back a change whose write-back failed so the next sync retries it (unless a newer change superseded it)
+    pub fn requeue_change(&mut self, suggestion: ScheduleSuggestion) {
+        if !self.pending_changes.iter().any(|pending| pending.event_id == suggestion.event_id) {
+            self.pending_changes.push(suggestion);
+        }
+    }
+
+    /// Apply an approved suggestion: move the existing event, or book the slot as a hold (breaks, buffers)
+    /// Holds keep the time free of new bookings without counting as meetings; the change is queued for write-back
+    pub fn apply_suggestion(&mut self, suggestion: &ScheduleSuggestion) {
+        info!("CalendarNegotiationAgent::apply_suggestion: Applying suggestion for {}", suggestion.event_id);
+        self.pending_changes.retain(|pending| pending.event_id != suggestion.event_id);
+        self.pending_changes.push(suggestion.clone());
+        match self.events.get_mut(&suggestion.event_id).or_else(|| self.holds.get_mut(&suggestion.event_id)) {
+            Some(event) => {
+                event.start_time = suggestion.suggested_start;
+                event.end_time = suggestion.suggested_end;
+            }
+            None => {
+                info!("CalendarNegotiationAgent::apply_suggestion: Booking hold {}", suggestion.event_id);
+                self.holds.insert(suggestion.event_id.clone(), CalendarEvent {
+                    id: suggestion.event_id.clone(),
+                    title: suggestion.reason.clone(),
+                    start_time: suggestion.suggested_start,
+                    end_time: suggestion.suggested_end,
+                    priority: EventPriority::Low,
+                    is_flexible: true,
+                });
+            }
+        }
+    }
+
     /// Analyze schedule and suggest optimizations
     /// Source: Athenos_AI_Strategy.md#L122
     pub fn analyze_schedule(&self, date: i64) -> Vec<ScheduleSuggestion> {
@@ -81,13 +357,109 @@ impl CalendarNegotiationAgent {
                         self.optimal_focus_hours[0].0, self.optimal_focus_hours[0].1),
                     expected_benefit: "Preserve 2 hours of peak focus time".to_string(),
                     requires_approval: event.priority >= EventPriority::Medium,
+                    provenance: self.focus_conflict_provenance(event),
                 });
             }
         }
         
+        // Resolve double bookings for events not already being moved
+        let moving: HashSet<String> = suggestions.iter().map(|s| s.event_id.clone()).collect();
+        suggestions.extend(self.resolve_conflicts(date).into_iter().filter(|s| !moving.contains(&s.event_id)));
         suggestions
     }
 
+    /// Every pair of stored events whose times overlap, ordered by start time
+    pub fn detect_conflicts(&self) -> Vec<EventConflict> {
+        let mut events: Vec<&CalendarEvent> = self.events.values().filter(|e| e.end_time > e.start_time).collect();
+        events.sort_by(|a, b| a.start_time.cmp(&b.start_time).then_with(|| a.id.cmp(&b.id)));
+        let mut conflicts = Vec::new();
+        for (i, first) in events.iter().enumerate() {
+            for second in events[i + 1..].iter().take_while(|e| e.start_time < first.end_time) {
+                conflicts.push(EventConflict {
+                    first_event_id: first.id.clone(),
+                    second_event_id: second.id.clone(),
+                    overlap_start: second.start_time,
+                    overlap_end: first.end_time.min(second.end_time),
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Suggest moving one event of each conflicting pair to the next free working-hours slot after `now`
+    /// The flexible, then lower-priority, then later event moves; inflexible critical events are never moved
+    pub fn resolve_conflicts(&self, now: i64) -> Vec<ScheduleSuggestion> {
+        let conflicts = self.detect_conflicts();
+        info!("CalendarNegotiationAgent::resolve_conflicts: {} conflicts", conflicts.len());
+        let mut occupied: HashMap<String, (i64, i64)> = self.events.values().chain(self.holds.values()).map(|e| (e.id.clone(), (e.start_time, e.end_time))).collect();
+        let mut moved = HashSet::new();
+        let mut suggestions = Vec::new();
+        for conflict in conflicts {
+            if moved.contains(&conflict.first_event_id) || moved.contains(&conflict.second_event_id) {
+                continue; // An earlier move already separated these
+            }
+            let (first, second) = (&self.events[&conflict.first_event_id], &self.events[&conflict.second_event_id]);
+            let movable = |e: &CalendarEvent| e.is_flexible || e.priority < EventPriority::Critical;
+            let (mover, keeper) = match (movable(first), movable(second)) {
+                (false, false) => continue,
+                (true, false) => (first, second),
+                (false, true) => (second, first),
+                // Prefer flexible, then lower priority, then the later event
+                _ => match (first.is_flexible, second.is_flexible) {
+                    (true, false) => (first, second),
+                    (false, true) => (second, first),
+                    _ if first.priority < second.priority => (first, second),
+                    _ => (second, first),
+                },
+            };
+            occupied.remove(&mover.id);
+            let Some((start, end)) = self.find_free_slot(mover.end_time - mover.start_time, keeper.end_time.max(now), &occupied) else {
+                occupied.insert(mover.id.clone(), (mover.start_time, mover.end_time));
+                continue;
+            };
+            occupied.insert(mover.id.clone(), (start, end));
+            moved.insert(mover.id.clone());
+            suggestions.push(ScheduleSuggestion {
+                event_id: mover.id.clone(),
+                suggested_start: start,
+                suggested_end: end,
+                reason: format!("Overlaps '{}' by {:.0} min", keeper.title, conflict.overlap_minutes()),
+                expected_benefit: "Removes a double booking".to_string(),
+                requires_approval: true,
+                provenance: Provenance {
+                    triggering_pattern: format!("'{}' and '{}' are booked at the same time", mover.title, keeper.title),
+                    data_used: vec!["calendar_events".to_string()],
+                    confidence: Confidence::High,
+                    consent_scopes: vec!["calendar_access".to_string()],
+                },
+            });
+        }
+        suggestions
+    }
+
+    /// First 15-minute-aligned weekday slot at or after `earliest` inside local working hours that overlaps nothing in `occupied`
+    /// Slots outside focus hours are preferred; a focus-hour slot is used only when the horizon has no other
+    fn find_free_slot(&self, duration: i64, earliest: i64, occupied: &HashMap<String, (i64, i64)>) -> Option<(i64, i64)> {
+        let step = 15 * 60;
+        let first = earliest + (step - earliest.rem_euclid(step)) % step;
+        let candidates = (0..)
+            .map(|i| first + i * step)
+            .take_while(|start| *start < earliest + RESOLUTION_HORIZON_SECS)
+            .filter(|start| {
+                local_weekday(&self.timezone, *start) < 5
+                    && within_local_hours(&self.timezone, WORKING_HOURS, *start, start + duration)
+                    && occupied.values().all(|(s, e)| *e <= *start || *s >= start + duration)
+            });
+        let mut fallback = None;
+        for start in candidates {
+            if !overlaps_local_hours(&self.timezone, &self.optimal_focus_hours, start, start + duration) {
+                return Some((start, start + duration));
+            }
+            fallback.get_or_insert((start, start + duration));
+        }
+        fallback
+    }
+
     /// Anticipatory scheduling - predict and suggest
     /// Source: Athenos_AI_Strategy.md#L122
     pub fn anticipatory_schedule(&self, new_event: &CalendarEvent) -> Option<ScheduleSuggestion> {
@@ -104,24 +476,320 @@ impl CalendarNegotiationAgent {
                 reason: "Schedule outside focus hours to maximize productivity".to_string(),
                 expected_benefit: "Preserve cognitive peak performance window".to_string(),
                 requires_approval: new_event.priority >= EventPriority::Medium,
+                provenance: self.focus_conflict_provenance(new_event),
             })
         } else {
             None
         }
     }
 
-    fn conflicts_with_focus_hours(&self, event: &CalendarEvent) -> bool {
-        let event_start_hour = chrono::DateTime::from_timestamp(event.start_time, 0)
-            .map(|dt| dt.hour())
-            .unwrap_or(0);
-        let event_end_hour = chrono::DateTime::from_timestamp(event.end_time, 0)
-            .map(|dt| dt.hour())
-            .unwrap_or(0);
+    fn focus_conflict_provenance(&self, event: &CalendarEvent) -> Provenance {
+        Provenance {
+            triggering_pattern: format!("Flexible event '{}' overlaps optimal focus hours", event.title),
+            data_used: vec!["calendar_events".to_string(), "optimal_focus_hours".to_string()],
+            confidence: Confidence::Medium,
+            consent_scopes: vec!["calendar_access".to_string()],
+        }
+    }
+
+    /// Publish in-meeting state for events in progress
+    pub fn sync_attention(&self, now: i64) {
+        let in_meeting = self.events
+            .values()
+            .any(|e| e.start_time <= now && now < e.end_time);
+        let snapshot = self.attention.current();
+        
+        if in_meeting && snapshot.state != AttentionState::InMeeting {
+            self.attention.set_state(AttentionState::InMeeting, "calendar");
+        } else if !in_meeting && snapshot.state == AttentionState::InMeeting && snapshot.source == "calendar" {
+            let state = if snapshot.dnd_enabled { AttentionState::Focused } else { AttentionState::Available };
+            self.attention.set_state(state, "calendar");
+        }
+    }
+
+    /// Suggestions that may be surfaced now without breaking focus or a meeting
+    pub fn deliverable_suggestions(&self, date: i64) -> Vec<ScheduleSuggestion> {
+        if !self.attention.should_interrupt(InterruptionPriority::Normal) {
+            info!("CalendarNegotiationAgent::deliverable_suggestions: Deferring suggestions ({:?})", self.attention.current().state);
+            return Vec::new();
+        }
+        self.analyze_schedule(date)
+    }
+
+    /// Suggest short breaks ahead of predicted low-focus windows that are not already booked
+    pub fn suggest_breaks(&self, forecast: &[ForecastPoint]) -> Vec<ScheduleSuggestion> {
+        info!("CalendarNegotiationAgent::suggest_breaks: Checking {} forecast windows", forecast.len());
+        let break_len = 15 * 60;
+        
+        forecast
+            .iter()
+            .filter(|point| point.low_focus)
+            .filter(|point| !self.is_booked(point.start, point.start + break_len))
+            .map(|point| ScheduleSuggestion {
+                event_id: format!("{}{}", HOLD_ID_PREFIXES[0], point.start),
+                suggested_start: point.start,
+                suggested_end: point.start + break_len,
+                reason: format!(
+                    "Predicted low focus (stress {:.0}%, fatigue {:.0}%)",
+                    point.stress * 100.0,
+                    point.fatigue * 100.0
+                ),
+                expected_benefit: "Recover before a predicted dip instead of pushing through it".to_string(),
+                requires_approval: true,
+                provenance: Provenance {
+                    triggering_pattern: "Emotional state forecast: low-focus window".to_string(),
+                    data_used: vec!["stress_forecast".to_string(), "fatigue_forecast".to_string(), "calendar_events".to_string()],
+                    confidence: Confidence::Medium,
+                    consent_scopes: vec!["emotion_detection".to_string(), "calendar_access".to_string()],
+                },
+            })
+            .collect()
+    }
+
+    /// Meetings on the UTC day containing `day_start`, merged into back-to-back blocks
+    pub fn meeting_blocks(&self, day_start: i64) -> Vec<MeetingBlock> {
+        let day_start = day_start - day_start.rem_euclid(86400);
+        let mut events: Vec<&CalendarEvent> = self.events
+            .values()
+            .filter(|e| e.start_time >= day_start && e.start_time < day_start + 86400)
+            .collect();
+        events.sort_by_key(|e| (e.start_time, e.end_time));
+
+        let mut blocks: Vec<MeetingBlock> = Vec::new();
+        for event in events {
+            match blocks.last_mut() {
+                Some(block) if event.start_time - block.end <= BACK_TO_BACK_GAP_SECS => {
+                    block.end = block.end.max(event.end_time);
+                    block.event_ids.push(event.id.clone());
+                }
+                _ => blocks.push(MeetingBlock {
+                    event_ids: vec![event.id.clone()],
+                    start: event.start_time,
+                    end: event.end_time,
+                }),
+            }
+        }
+        blocks
+    }
+
+    /// Compute meeting load, back-to-back streaks and post-meeting refocus time for a day
+    /// Refocus is the gap between a block ending and the first keypress or focus change
+    /// in a non-meeting app, before the next block starts
+    pub fn meeting_load(&self, day_start: i64, os_events: &[OSEvent]) -> MeetingLoadStats {
+        let blocks = self.meeting_blocks(day_start);
+        let meeting_minutes: f64 = blocks.iter().map(|b| b.duration_minutes()).sum();
+
+        let mut refocus_minutes = Vec::new();
+        for (i, block) in blocks.iter().enumerate() {
+            let next_start = blocks.get(i + 1).map(|b| b.start).unwrap_or(i64::MAX);
+            let refocus = os_events
+                .iter()
+                .filter(|e| e.timestamp >= block.end && e.timestamp < next_start)
+                .filter(|e| matches!(e.event_type, OSEventType::KeyPress | OSEventType::WindowFocus | OSEventType::AppSwitch))
+                .filter(|e| {
+                    let app = e.app_name.to_lowercase();
+                    !MEETING_APPS.iter().any(|m| app.contains(m))
+                })
+                .map(|e| e.timestamp)
+                .min();
+            if let Some(at) = refocus {
+                refocus_minutes.push((at - block.end) as f64 / 60.0);
+            }
+        }
+
+        let stats = MeetingLoadStats {
+            day_start: day_start - day_start.rem_euclid(86400),
+            meeting_count: blocks.iter().map(|b| b.event_ids.len()).sum(),
+            meeting_minutes,
+            load_pct: (meeting_minutes / WORKDAY_MINUTES * 100.0).min(100.0),
+            back_to_back_streaks: blocks.iter().filter(|b| b.event_ids.len() > 1).count(),
+            longest_block_minutes: blocks.iter().map(|b| b.duration_minutes()).fold(0.0, f64::max),
+            avg_refocus_minutes: if refocus_minutes.is_empty() {
+                None
+            } else {
+                Some(refocus_minutes.iter().sum::<f64>() / refocus_minutes.len() as f64)
+            },
+        };
+        info!("CalendarNegotiationAgent::meeting_load: {:.0} min in meetings ({:.0}%), {} back-to-back streaks",
+            stats.meeting_minutes, stats.load_pct, stats.back_to_back_streaks);
+        stats
+    }
+
+    /// Suggest recovery buffers after meeting blocks of at least `min_block_minutes`
+    /// Only proposes buffers where the calendar is free
+    pub fn suggest_recovery_buffers(&self, day_start: i64, min_block_minutes: f64, buffer_minutes: i64) -> Vec<ScheduleSuggestion> {
+        let buffer_len = buffer_minutes * 60;
+
+        self.meeting_blocks(day_start)
+            .into_iter()
+            .filter(|block| block.duration_minutes() >= min_block_minutes)
+            .filter(|block| !self.is_booked(block.end, block.end + buffer_len))
+            .map(|block| ScheduleSuggestion {
+                event_id: format!("{}{}", HOLD_ID_PREFIXES[1], block.end),
+                suggested_start: block.end,
+                suggested_end: block.end + buffer_len,
+                reason: format!(
+                    "{:.0} min meeting block ({} meetings) with no break afterwards",
+                    block.duration_minutes(),
+                    block.event_ids.len()
+                ),
+                expected_benefit: "Recover and refocus before returning to deep work".to_string(),
+                requires_approval: true,
+                provenance: Provenance {
+                    triggering_pattern: "Long meeting block without recovery time".to_string(),
+                    data_used: vec!["calendar_events".to_string()],
+                    confidence: Confidence::Medium,
+                    consent_scopes: vec!["calendar_access".to_string()],
+                },
+            })
+            .collect()
+    }
+
+    /// Propose slots within the window where every required attendee is free
+    /// Ranked by focus-hour conflicts, then team heatmap focus score, then optional attendee availability, then start time
+    pub fn propose_meeting_slots(
+        &self,
+        event: &CalendarEvent,
+        attendees: &[Attendee],
+        provider: &dyn CalendarProvider,
+        window_start: i64,
+        window_end: i64,
+        max_results: usize,
+    ) -> Result<Vec<SlotProposal>, String> {
+        info!("CalendarNegotiationAgent::propose_meeting_slots: Negotiating {} with {} attendees", event.id, attendees.len());
+        
+        let duration = event.end_time - event.start_time;
+        if duration <= 0 {
+            return Err(format!("Event {} has no duration", event.id));
+        }
+        
+        let zones = attendees
+            .iter()
+            .map(|a| a.timezone.as_deref().map(parse_timezone).transpose().map(|tz| tz.unwrap_or(Tz::UTC)))
+            .collect::<Result<Vec<Tz>, String>>()?;
+        let mut busy: HashMap<&str, Vec<BusyInterval>> = HashMap::new();
+        for attendee in attendees {
+            busy.insert(attendee.id.as_str(), provider.free_busy(&attendee.id, window_start, window_end)?);
+        }
+        let is_free = |id: &str, start: i64, end: i64| {
+            busy.get(id).map(|b| b.iter().all(|i| i.end <= start || i.start >= end)).unwrap_or(true)
+        };
+        
+        let step = 30 * 60;
+        let mut proposals = Vec::new();
+        let mut start = window_start;
+        while start + duration <= window_end {
+            let end = start + duration;
+            let required_free = attendees.iter().filter(|a| a.required).all(|a| is_free(&a.id, start, end));
+            
+            if required_free {
+                let organizer_conflict = overlaps_local_hours(&self.timezone, &self.optimal_focus_hours, start, end) as usize;
+                let attendee_conflicts = attendees
+                    .iter()
+                    .zip(&zones)
+                    .filter(|(a, tz)| overlaps_local_hours(tz, &a.focus_hours, start, end))
+                    .count();
+                
+                proposals.push(SlotProposal {
+                    start,
+                    end,
+                    focus_hour_conflicts: organizer_conflict + attendee_conflicts,
+                    optional_available: attendees
+                        .iter()
+                        .filter(|a| !a.required && is_free(&a.id, start, end))
+                        .map(|a| a.id.clone())
+                        .collect(),
+                    team_focus_score: self.team_heatmap.as_ref().map(|h| h.mean_score(start, end)).unwrap_or(0.0),
+                });
+            }
+            start += step;
+        }
+        
+        proposals.sort_by(|a, b| {
+            a.focus_hour_conflicts
+                .cmp(&b.focus_hour_conflicts)
+                .then_with(|| a.team_focus_score.partial_cmp(&b.team_focus_score).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| b.optional_available.len().cmp(&a.optional_available.len()))
+                .then_with(|| a.start.cmp(&b.start))
+        });
+        proposals.truncate(max_results);
+        Ok(proposals)
+    }
+
+    /// Record a counter-proposal from an attendee
+    pub fn record_counter_proposal(&mut self, event_id: &str, from_attendee: &str, proposed_start: i64, proposed_end: i64, reason: String) -> Result<CounterProposal, String> {
+        if !self.events.contains_key(event_id) {
+            return Err(format!("Unknown event: {}", event_id));
+        }
+        if proposed_end <= proposed_start {
+            return Err("Counter-proposal must end after it starts".to_string());
+        }
+        
+        info!("CalendarNegotiationAgent::record_counter_proposal: {} proposes new time for {}", from_attendee, event_id);
+        let proposal = CounterProposal {
+            id: format!("counter_{}_{}", event_id, self.next_counter_id),
+            event_id: event_id.to_string(),
+            from_attendee: from_attendee.to_string(),
+            proposed_start,
+            proposed_end,
+            reason,
+            status: CounterProposalStatus::Pending,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        self.next_counter_id += 1;
+        self.counter_proposals.insert(proposal.id.clone(), proposal.clone());
+        self.prune_counter_proposals();
+        Ok(proposal)
+    }
+
+    /// Drop resolved or orphaned, then the oldest pending, counter-proposals until at most MAX_COUNTER_PROPOSALS remain
+    fn prune_counter_proposals(&mut self) {
+        if self.counter_proposals.len() <= MAX_COUNTER_PROPOSALS {
+            return;
+        }
+        let mut evictable: Vec<(bool, i64, String)> = self.counter_proposals
+            .values()
+            .map(|p| {
+                let live = p.status == CounterProposalStatus::Pending && self.events.contains_key(&p.event_id);
+                (live, p.created_at, p.id.clone())
+            })
+            .collect();
+        evictable.sort();
+        let excess = self.counter_proposals.len() - MAX_COUNTER_PROPOSALS;
+        for (_, _, id) in evictable.into_iter().take(excess) {
+            self.counter_proposals.remove(&id);
+        }
+    }
+
+    /// Accept or decline a counter-proposal; accepting moves the event
+    pub fn resolve_counter_proposal(&mut self, proposal_id: &str, accept: bool) -> Result<CounterProposal, String> {
+        let proposal = self.counter_proposals
+            .get_mut(proposal_id)
+            .ok_or(format!("Unknown counter-proposal: {}", proposal_id))?;
+        if proposal.status != CounterProposalStatus::Pending {
+            return Err(format!("Counter-proposal {} already resolved", proposal_id));
+        }
         
-        self.optimal_focus_hours.iter().any(|(start, end)| {
-            event_start_hour >= *start && event_start_hour < *end ||
-            event_end_hour > *start && event_end_hour <= *end
-        })
+        proposal.status = if accept { CounterProposalStatus::Accepted } else { CounterProposalStatus::Declined };
+        if accept {
+            if let Some(event) = self.events.get_mut(&proposal.event_id) {
+                event.start_time = proposal.proposed_start;
+                event.end_time = proposal.proposed_end;
+            }
+        }
+        Ok(proposal.clone())
+    }
+
+    /// Get pending counter-proposals for an event
+    pub fn get_pending_counter_proposals(&self, event_id: &str) -> Vec<&CounterProposal> {
+        self.counter_proposals
+            .values()
+            .filter(|p| p.event_id == event_id && p.status == CounterProposalStatus::Pending)
+            .collect()
+    }
+
+    fn conflicts_with_focus_hours(&self, event: &CalendarEvent) -> bool {
+        overlaps_local_hours(&self.timezone, &self.optimal_focus_hours, event.start_time, event.end_time)
     }
 
     fn find_optimal_slot(&self, event: &CalendarEvent) -> (i64, i64) {
@@ -132,6 +800,19 @@ impl CalendarNegotiationAgent {
     }
 }
 
+impl ConfigListener for CalendarNegotiationAgent {
+    fn config_name(&self) -> &str {
+        "calendar_agent"
+    }
+
+    /// Replace protected focus hours and the timezone they are read in
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        self.timezone = parse_timezone(&config.timezone)?;
+        self.optimal_focus_hours = config.focus_hours.clone();
+        Ok(())
+    }
+}
+
 impl Default for CalendarNegotiationAgent {
     fn default() -> Self {
         Self::new()
@@ -183,5 +864,290 @@ mod tests {
         // May or may not suggest based on timing
         assert!(suggestion.is_some() || suggestion.is_none());
     }
-}
 
+    #[test]
+    fn test_sync_attention_during_meeting() {
+        let attention = AttentionService::new();
+        let mut agent = CalendarNegotiationAgent::with_attention(attention.clone());
+        
+        agent.add_event(CalendarEvent {
+            id: "meeting_002".to_string(),
+            title: "Design Review".to_string(),
+            start_time: 1000,
+            end_time: 2000,
+            priority: EventPriority::High,
+            is_flexible: false,
+        });
+        
+        agent.sync_attention(1500);
+        assert_eq!(attention.current().state, AttentionState::InMeeting);
+        assert!(agent.deliverable_suggestions(1500).is_empty());
+        
+        agent.sync_attention(2500);
+        assert_eq!(attention.current().state, AttentionState::Available);
+    }
+
+    #[test]
+    fn test_propose_slots_respects_required_attendees() {
+        let agent = CalendarNegotiationAgent::new();
+        let day = 1_700_006_400; // 00:00 UTC
+        let event = CalendarEvent {
+            id: "sync".to_string(),
+            title: "Sync".to_string(),
+            start_time: 0,
+            end_time: 3600,
+            priority: EventPriority::Medium,
+            is_flexible: true,
+        };
+        let attendees = vec![
+            Attendee { id: "alex".to_string(), required: true, focus_hours: vec![(8, 10)], timezone: None },
+            Attendee { id: "sam".to_string(), required: false, focus_hours: Vec::new(), timezone: None },
+        ];
+        let mut provider = StaticCalendarProvider::new();
+        provider.add_busy("alex", day + 12 * 3600, day + 13 * 3600);
+        provider.add_busy("sam", day + 11 * 3600, day + 12 * 3600);
+        
+        let slots = agent
+            .propose_meeting_slots(&event, &attendees, &provider, day + 8 * 3600, day + 14 * 3600, 3)
+            .unwrap();
+        assert!(!slots.is_empty());
+        assert!(slots.iter().all(|s| s.end <= day + 12 * 3600 || s.start >= day + 13 * 3600));
+        // 11:00-12:00 and 13:00-14:00 avoid every focus window; 13:00 also suits the optional attendee
+        assert_eq!(slots[0].start, day + 13 * 3600);
+        assert_eq!(slots[0].focus_hour_conflicts, 0);
+        assert_eq!(slots[0].optional_available, vec!["sam"]);
+    }
+
+    #[test]
+    fn test_team_heatmap_steers_slots_away_from_collective_focus() {
+        use crate::compliance::DifferentialPrivacy;
+        use crate::enterprise::focus_heatmap::{HeatmapConfig, MemberFocusProfile, HOURS_PER_WEEK};
+        
+        let mut agent = CalendarNegotiationAgent::new();
+        let day = 1_700_006_400; // Wednesday 00:00 UTC
+        // Every member is focused on Wednesdays 13:00-14:00
+        let mut scores = vec![0.0; HOURS_PER_WEEK];
+        scores[2 * 24 + 13] = 1.0;
+        let profiles: Vec<MemberFocusProfile> = (0..5)
+            .map(|i| MemberFocusProfile { user_id: format!("user_{}", i), scores: scores.clone() })
+            .collect();
+        let heatmap = TeamFocusHeatmap::build("alpha", &profiles, &DifferentialPrivacy::new(1_000_000.0), HeatmapConfig::default(), day).unwrap();
+        agent.set_team_heatmap(Some(heatmap));
+        
+        let event = CalendarEvent {
+            id: "sync".to_string(),
+            title: "Sync".to_string(),
+            start_time: 0,
+            end_time: 3600,
+            priority: EventPriority::Medium,
+            is_flexible: true,
+        };
+        let attendees = vec![
+            Attendee { id: "alex".to_string(), required: true, focus_hours: vec![(8, 10)], timezone: None },
+            Attendee { id: "sam".to_string(), required: false, focus_hours: Vec::new(), timezone: None },
+        ];
+        let mut provider = StaticCalendarProvider::new();
+        provider.add_busy("alex", day + 12 * 3600, day + 13 * 3600);
+        provider.add_busy("sam", day + 11 * 3600, day + 12 * 3600);
+        
+        let slots = agent
+            .propose_meeting_slots(&event, &attendees, &provider, day + 8 * 3600, day + 14 * 3600, 3)
+            .unwrap();
+        // 13:00 suits the optional attendee but interrupts the team's focus window
+        assert_eq!(slots[0].start, day + 11 * 3600);
+        assert!(slots[0].team_focus_score < slots[1].team_focus_score);
+    }
+
+    #[test]
+    fn test_counter_proposal_flow() {
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.add_event(CalendarEvent {
+            id: "review".to_string(),
+            title: "Review".to_string(),
+            start_time: 1000,
+            end_time: 4600,
+            priority: EventPriority::Medium,
+            is_flexible: true,
+        });
+        
+        let proposal = agent.record_counter_proposal("review", "sam", 8000, 11600, "Conflicts with deep work".to_string()).unwrap();
+        assert_eq!(agent.get_pending_counter_proposals("review").len(), 1);
+        
+        agent.resolve_counter_proposal(&proposal.id, true).unwrap();
+        assert_eq!(agent.events.get("review").unwrap().start_time, 8000);
+        assert!(agent.resolve_counter_proposal(&proposal.id, false).is_err());
+    }
+
+    #[test]
+    fn test_counter_proposals_bounded_keeping_pending() {
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.add_event(CalendarEvent {
+            id: "review".to_string(),
+            title: "Review".to_string(),
+            start_time: 1000,
+            end_time: 4600,
+            priority: EventPriority::Medium,
+            is_flexible: true,
+        });
+        
+        let pending = agent.record_counter_proposal("review", "sam", 8000, 11600, "Later".to_string()).unwrap();
+        for _ in 0..MAX_COUNTER_PROPOSALS + 10 {
+            let declined = agent.record_counter_proposal("review", "alex", 8000, 11600, "Later".to_string()).unwrap();
+            agent.resolve_counter_proposal(&declined.id, false).unwrap();
+        }
+        assert!(agent.counter_proposals.len() <= MAX_COUNTER_PROPOSALS);
+        assert_eq!(agent.get_pending_counter_proposals("review")[0].id, pending.id);
+        // Ids are not reused after pruning
+        let next = agent.record_counter_proposal("review", "sam", 8000, 11600, "Later".to_string()).unwrap();
+        assert_eq!(next.id, format!("counter_review_{}", MAX_COUNTER_PROPOSALS + 11));
+    }
+
+    #[test]
+    fn test_suggest_breaks_for_low_focus_windows() {
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.add_event(CalendarEvent {
+            id: "busy".to_string(),
+            title: "Busy".to_string(),
+            start_time: 7200,
+            end_time: 10800,
+            priority: EventPriority::High,
+            is_flexible: false,
+        });
+        let forecast = vec![
+            ForecastPoint { start: 0, end: 3600, stress: 0.2, fatigue: 0.2, low_focus: false },
+            ForecastPoint { start: 3600, end: 7200, stress: 0.7, fatigue: 0.4, low_focus: true },
+            ForecastPoint { start: 7200, end: 10800, stress: 0.8, fatigue: 0.8, low_focus: true },
+        ];
+        
+        let breaks = agent.suggest_breaks(&forecast);
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].suggested_start, 3600);
+    }
+
+    fn meeting(id: &str, start: i64, end: i64) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            title: id.to_string(),
+            start_time: start,
+            end_time: end,
+            priority: EventPriority::Medium,
+            is_flexible: false,
+        }
+    }
+
+    fn os_event(event_type: OSEventType, app: &str, timestamp: i64) -> OSEvent {
+        OSEvent {
+            event_type,
+            app_name: app.to_string(),
+            window_title: None,
+            timestamp,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_meeting_load_and_refocus_time() {
+        let day = 1_700_006_400; // Midnight UTC
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.add_event(meeting("standup", day + 9 * 3600, day + 9 * 3600 + 1800));
+        agent.add_event(meeting("review", day + 9 * 3600 + 1800, day + 10 * 3600 + 1800));
+        agent.add_event(meeting("1on1", day + 14 * 3600, day + 14 * 3600 + 1800));
+
+        let events = vec![
+            os_event(OSEventType::KeyPress, "Slack", day + 10 * 3600 + 1800 + 60), // Still in chat
+            os_event(OSEventType::KeyPress, "VS Code", day + 10 * 3600 + 1800 + 600),
+            os_event(OSEventType::WindowFocus, "VS Code", day + 14 * 3600 + 1800 + 1200),
+        ];
+        let stats = agent.meeting_load(day + 3600, &events);
+
+        assert_eq!(stats.meeting_count, 3);
+        assert_eq!(stats.meeting_minutes, 120.0);
+        assert_eq!(stats.load_pct, 25.0);
+        assert_eq!(stats.back_to_back_streaks, 1);
+        assert_eq!(stats.longest_block_minutes, 90.0);
+        assert_eq!(stats.avg_refocus_minutes, Some(15.0));
+    }
+
+    #[test]
+    fn test_recovery_buffers_after_long_blocks() {
+        let day = 1_700_006_400;
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.add_event(meeting("a", day + 9 * 3600, day + 10 * 3600));
+        agent.add_event(meeting("b", day + 10 * 3600, day + 11 * 3600));
+        agent.add_event(meeting("c", day + 13 * 3600, day + 15 * 3600));
+        agent.add_event(meeting("d", day + 15 * 3600 + 600, day + 15 * 3600 + 1800)); // Buffer would collide
+        agent.add_event(meeting("short", day + 17 * 3600, day + 17 * 3600 + 1800));
+
+        let buffers = agent.suggest_recovery_buffers(day, 90.0, 15);
+        assert_eq!(buffers.len(), 1);
+        assert_eq!(buffers[0].suggested_start, day + 11 * 3600);
+        assert_eq!(buffers[0].suggested_end, day + 11 * 3600 + 900);
+        assert!(buffers[0].requires_approval);
+
+        // A booked buffer is a hold: not a meeting, not re-suggested, and never puts the user "in a meeting"
+        agent.apply_suggestion(&buffers[0]);
+        assert_eq!(agent.holds().len(), 1);
+        assert_eq!(agent.meeting_load(day, &[]).meeting_count, 5);
+        assert!(agent.suggest_recovery_buffers(day, 90.0, 15).is_empty());
+        agent.sync_attention(day + 11 * 3600 + 60);
+        assert_ne!(agent.attention.current().state, AttentionState::InMeeting);
+        assert_eq!(agent.take_pending_changes()[0].1.as_ref().map(|e| e.id.as_str()), Some(buffers[0].event_id.as_str()));
+    }
+
+    #[test]
+    fn test_detect_and_resolve_conflicts() {
+        let mut agent = CalendarNegotiationAgent::new();
+        agent.set_timezone("America/New_York").unwrap();
+        // Monday 2024-07-15 14:00 UTC = 10:00 EDT
+        let ten_am = 1_721_052_000;
+        let event = |id: &str, start: i64, minutes: i64, priority: EventPriority, is_flexible: bool| CalendarEvent {
+            id: id.to_string(),
+            title: id.to_string(),
+            start_time: start,
+            end_time: start + minutes * 60,
+            priority,
+            is_flexible,
+        };
+        agent.add_event(event("board", ten_am, 60, EventPriority::Critical, false));
+        agent.add_event(event("one_on_one", ten_am + 1800, 30, EventPriority::Medium, true));
+        agent.add_event(event("lunch", ten_am + 7200, 60, EventPriority::Low, false)); // 12:00-13:00, no overlap
+        agent.add_event(event("offsite", ten_am, 90, EventPriority::Critical, false));
+
+        let conflicts = agent.detect_conflicts();
+        assert_eq!(conflicts.len(), 3);
+        assert!(conflicts.iter().any(|c| c.second_event_id == "one_on_one" && c.overlap_minutes() == 30.0));
+
+        let suggestions = agent.resolve_conflicts(ten_am - 86400);
+        // Two inflexible critical events are left for a human; the 1:1 moves once
+        assert_eq!(suggestions.len(), 1);
+        let moved = &suggestions[0];
+        assert_eq!(moved.event_id, "one_on_one");
+        // Next free weekday slot after the offsite, outside 14:00-16:00 focus hours and lunch: 11:30 EDT
+        assert_eq!(moved.suggested_start, ten_am + 5400);
+        assert_eq!(moved.suggested_end - moved.suggested_start, 1800);
+        assert!(agent.analyze_schedule(ten_am - 86400).iter().any(|s| s.event_id == "one_on_one"));
+    }
+
+    #[test]
+    fn test_attendee_focus_hours_use_their_timezone() {
+        let agent = CalendarNegotiationAgent::new();
+        let day = 1_721_001_600; // 2024-07-15 00:00 UTC
+        let event = CalendarEvent {
+            id: "sync".to_string(),
+            title: "Sync".to_string(),
+            start_time: 0,
+            end_time: 3600,
+            priority: EventPriority::Medium,
+            is_flexible: true,
+        };
+        // 09:00-10:00 Tokyo is 00:00-01:00 UTC
+        let attendees = vec![Attendee { id: "kei".to_string(), required: true, focus_hours: vec![(9, 10)], timezone: Some("Asia/Tokyo".to_string()) }];
+        let slots = agent.propose_meeting_slots(&event, &attendees, &StaticCalendarProvider::new(), day, day + 2 * 3600, 4).unwrap();
+        let midnight = slots.iter().find(|s| s.start == day).unwrap();
+        assert_eq!(midnight.focus_hour_conflicts, 1);
+        assert_eq!(slots[0].focus_hour_conflicts, 0);
+
+        let bad = vec![Attendee { timezone: Some("Nowhere/Land".to_string()), ..attendees[0].clone() }];
+        assert!(agent.propose_meeting_slots(&event, &bad, &StaticCalendarProvider::new(), day, day + 7200, 4).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+/// Phase: C | Step: 3 | Source: Athenos_AI_Strategy.md#L122
+/// Timezone Handling
+/// Convert between UTC timestamps and users' local hours, DST-correct via the IANA database
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use chrono_tz::Tz;
+
+/// Parse an IANA timezone name
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.parse::<Tz>().map_err(|e| format!("Unknown timezone '{}': {}", name, e))
+}
+
+/// Local calendar date of a timestamp
+pub fn local_date(tz: &Tz, timestamp: i64) -> NaiveDate {
+    tz.timestamp_opt(timestamp, 0).single().map(|dt| dt.date_naive()).unwrap_or_default()
+}
+
+/// Local hour of day (0-23) of a timestamp
+pub fn local_hour(tz: &Tz, timestamp: i64) -> u32 {
+    tz.timestamp_opt(timestamp, 0).single().map(|dt| dt.hour()).unwrap_or(0)
+}
+
+/// Timestamp of a local wall-clock time
+/// Times skipped by a DST jump resolve to the first instant after the gap; repeated times to the first occurrence
+pub fn local_timestamp(tz: &Tz, naive: NaiveDateTime) -> i64 {
+    // DST gaps are at most a few hours; step forward until the local time exists
+    (0..=4)
+        .find_map(|skip| tz.from_local_datetime(&(naive + Duration::hours(skip))).earliest())
+        .map(|dt| dt.timestamp())
+        .unwrap_or_else(|| naive.and_utc().timestamp())
+}
+
+/// Timestamp of `hour`:00 local time on `date` (hour 24 is midnight of the next day)
+pub fn local_hour_start(tz: &Tz, date: NaiveDate, hour: u8) -> i64 {
+    let (date, hour) = if hour >= 24 { (date.succ_opt().unwrap_or(date), 0) } else { (date, hour as u32) };
+    local_timestamp(tz, date.and_hms_opt(hour, 0, 0).unwrap_or_default())
+}
+
+/// Whether [start, end) overlaps any local-hour window on any local day it touches
+pub fn overlaps_local_hours(tz: &Tz, hours: &[(u8, u8)], start: i64, end: i64) -> bool {
+    let (first, last) = (local_date(tz, start), local_date(tz, end));
+    first.iter_days().take_while(|day| *day <= last).any(|day| {
+        hours.iter().any(|(h_start, h_end)| start < local_hour_start(tz, day, *h_end) && end > local_hour_start(tz, day, *h_start))
+    })
+}
+
+/// Whether [start, end) lies inside one local-hour window on a single day
+pub fn within_local_hours(tz: &Tz, window: (u8, u8), start: i64, end: i64) -> bool {
+    let day = local_date(tz, start);
+    start >= local_hour_start(tz, day, window.0) && end <= local_hour_start(tz, day, window.1)
+}
+
+/// Day of week of a timestamp in local time (Monday = 0)
+pub fn local_weekday(tz: &Tz, timestamp: i64) -> u32 {
+    tz.timestamp_opt(timestamp, 0).single().map(|dt| dt.weekday().num_days_from_monday()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dst_correct_local_hours() {
+        let paris = parse_timezone("Europe/Paris").unwrap();
+        let winter = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let summer = NaiveDate::from_ymd_opt(2024, 7, 15).unwrap();
+        // 09:00 Paris is 08:00 UTC in winter and 07:00 UTC in summer
+        assert_eq!(local_hour(&chrono_tz::Tz::UTC, local_hour_start(&paris, winter, 9)), 8);
+        assert_eq!(local_hour(&chrono_tz::Tz::UTC, local_hour_start(&paris, summer, 9)), 7);
+        // 02:00 does not exist on the spring-forward day; it resolves to 03:00 local (01:00 UTC)
+        let spring = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        assert_eq!(local_hour(&paris, local_hour_start(&paris, spring, 2)), 3);
+
+        let nine = local_hour_start(&paris, summer, 9);
+        assert!(overlaps_local_hours(&paris, &[(9, 11)], nine + 1800, nine + 3600));
+        assert!(!overlaps_local_hours(&paris, &[(9, 11)], nine - 3600, nine));
+        assert!(within_local_hours(&paris, (8, 18), nine, nine + 3600));
+        assert!(parse_timezone("Not/AZone").is_err());
+    }
+}
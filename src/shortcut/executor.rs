@@ -0,0 +1,429 @@
+/// Phase: B | Step: 4 | Source: Athenos_AI_Strategy.md#L111
+/// Shortcut Execution Engine
+/// Turns approved shortcut sequences into launch/focus/open steps and runs them through an OS-action driver
+
+use super::{is_file_entry, ShortcutGenerator, ShortcutProposal};
+use crate::cache::BoundedLog;
+use crate::types::Outcome;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::Instant;
+use tracing::info;
+
+/// Runs kept for inspection
+const RUN_HISTORY_CAPACITY: usize = 500;
+
+/// Concrete OS action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum OsAction {
+    Focus { app: String },
+    Launch { executable: String, args: Vec<String> },
+    OpenFile { path: String },
+}
+
+/// OS-action abstraction the executor drives
+pub trait OsActionDriver: Send {
+    fn driver_name(&self) -> &str;
+    fn perform(&mut self, action: &OsAction) -> Result<(), String>;
+}
+
+/// How an app entry in a shortcut sequence is brought up
+/// Templates are the launch allowlist: an app without one is only ever focused, never started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepTemplate {
+    pub app: String,        // Sequence entry this template applies to (case-insensitive)
+    pub executable: String, // Launched when the app cannot be focused; never taken from observed or synced data
+    pub args: Vec<String>,
+    pub continue_on_error: bool, // A failed optional step does not abort the shortcut
+}
+
+impl StepTemplate {
+    /// Template that launches an executable named like the app, aborting the shortcut on failure
+    pub fn for_app(app: &str) -> Self {
+        Self { app: app.to_string(), executable: app.to_string(), args: Vec::new(), continue_on_error: false }
+    }
+}
+
+/// One planned step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutStep {
+    pub index: usize,
+    pub action: OsAction,
+    pub fallback: Option<OsAction>, // Tried when the action fails (launch an app that is not running)
+    pub continue_on_error: bool,
+}
+
+/// Result of one step
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Succeeded,
+    RecoveredWithFallback,
+    Failed { error: String },
+    Skipped, // Not run because an earlier required step failed
+}
+
+/// Step with its result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    pub step: ShortcutStep,
+    pub status: StepStatus,
+}
+
+/// One execution of a shortcut
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutRun {
+    pub shortcut_id: String,
+    pub driver: String,
+    pub steps: Vec<StepResult>,
+    pub succeeded: bool, // No required step failed
+    pub outcome: Outcome,
+}
+
+impl ShortcutRun {
+    pub fn failed_steps(&self) -> usize {
+        self.steps.iter().filter(|s| matches!(s.status, StepStatus::Failed { .. })).count()
+    }
+}
+
+/// Executes approved shortcuts step by step
+pub struct ShortcutExecutor {
+    driver: Box<dyn OsActionDriver>,
+    templates: HashMap<String, StepTemplate>, // Lowercased app -> template
+    runs: BoundedLog<ShortcutRun>,
+}
+
+impl ShortcutExecutor {
+    /// Create executor over any OS-action driver
+    pub fn new(driver: Box<dyn OsActionDriver>) -> Self {
+        info!("ShortcutExecutor::new: Executing shortcuts via {}", driver.driver_name());
+        Self {
+            driver,
+            templates: HashMap::new(),
+            runs: BoundedLog::new("shortcut.runs", RUN_HISTORY_CAPACITY),
+        }
+    }
+
+    /// Register or replace the template for an app
+    pub fn set_template(&mut self, template: StepTemplate) {
+        self.templates.insert(template.app.to_lowercase(), template);
+    }
+
+    /// Concrete steps for a proposal: files are opened, apps focused with a launch fallback from their template
+    /// Immediate repeats are collapsed; switching to the app already in front is a no-op
+    pub fn plan(&self, proposal: &ShortcutProposal) -> Vec<ShortcutStep> {
+        let mut entries: Vec<&String> = proposal.sequence.iter().filter(|e| !e.trim().is_empty()).collect();
+        entries.dedup();
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                if is_file_entry(entry) {
+                    return ShortcutStep { index, action: OsAction::OpenFile { path: entry.clone() }, fallback: None, continue_on_error: false };
+                }
+                match self.templates.get(&entry.to_lowercase()) {
+                    Some(template) => ShortcutStep {
+                        index,
+                        action: OsAction::Focus { app: entry.clone() },
+                        fallback: Some(OsAction::Launch { executable: template.executable.clone(), args: template.args.clone() }),
+                        continue_on_error: template.continue_on_error,
+                    },
+                    None => ShortcutStep { index, action: OsAction::Focus { app: entry.clone() }, fallback: None, continue_on_error: false },
+                }
+            })
+            .collect()
+    }
+
+    /// Execute an approved shortcut, recording the run and its outcome
+    /// Time saved is the proposal's manual baseline minus the run's elapsed time, and is fed back to the generator;
+    /// without a baseline nothing is claimed
+    pub fn execute(&mut self, generator: &mut ShortcutGenerator, shortcut_id: &str, now: i64) -> Result<ShortcutRun, String> {
+        let proposal = generator.get_proposal(shortcut_id).cloned().ok_or("Shortcut not found")?;
+        if !generator.is_approved(shortcut_id) {
+            return Err(format!("Shortcut {} is not approved", shortcut_id));
+        }
+        info!("ShortcutExecutor::execute: Running {} ({} entries)", shortcut_id, proposal.sequence.len());
+
+        let started = Instant::now();
+        let mut aborted = false;
+        let mut steps = Vec::new();
+        for step in self.plan(&proposal) {
+            if aborted {
+                steps.push(StepResult { step, status: StepStatus::Skipped });
+                continue;
+            }
+            let status = match self.driver.perform(&step.action) {
+                Ok(()) => StepStatus::Succeeded,
+                Err(error) => match step.fallback.as_ref().map(|fallback| self.driver.perform(fallback)) {
+                    Some(Ok(())) => StepStatus::RecoveredWithFallback,
+                    Some(Err(fallback_error)) => StepStatus::Failed { error: format!("{}; fallback: {}", error, fallback_error) },
+                    None => StepStatus::Failed { error },
+                },
+            };
+            if let StepStatus::Failed { error } = &status {
+                info!("ShortcutExecutor::execute: Step {} of {} failed: {}", step.index + 1, shortcut_id, error);
+                aborted = !step.continue_on_error;
+            }
+            steps.push(StepResult { step, status });
+        }
+
+        let succeeded = !aborted;
+        let elapsed_min = started.elapsed().as_secs_f64() / 60.0;
+        let time_saved_minutes = proposal.manual_duration_min.filter(|_| succeeded).map(|manual| manual - elapsed_min);
+        if let Some(minutes) = time_saved_minutes {
+            generator.record_realized_savings(shortcut_id, minutes)?;
+        }
+        let run = ShortcutRun {
+            shortcut_id: shortcut_id.to_string(),
+            driver: self.driver.driver_name().to_string(),
+            succeeded,
+            outcome: Outcome {
+                observation_id: shortcut_id.to_string(),
+                accepted: succeeded,
+                ignored: false,
+                modified: false,
+                time_saved_minutes,
+                error_rate_change: None,
+                timestamp: now,
+            },
+            steps,
+        };
+        self.runs.push(run.clone());
+        Ok(run)
+    }
+
+    /// Most recent runs, oldest first
+    pub fn recent_runs(&self, limit: usize) -> Vec<&ShortcutRun> {
+        self.runs.recent(limit).collect()
+    }
+
+    /// Outcomes of every recorded run, oldest first
+    pub fn outcomes(&self) -> Vec<Outcome> {
+        self.runs.iter().map(|run| run.outcome.clone()).collect()
+    }
+}
+
+/// Driver that records actions instead of performing them (tests and simulation)
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedOsDriver {
+    pub performed: Vec<OsAction>,
+    running: Vec<String>, // Lowercased apps that can be focused
+    failing: Vec<String>, // Executables and paths whose actions fail
+}
+
+impl SimulatedOsDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark an app as running so focusing it succeeds
+    pub fn with_running(mut self, app: &str) -> Self {
+        self.running.push(app.to_lowercase());
+        self
+    }
+
+    /// Make launching `target` or opening it as a file fail
+    pub fn failing(mut self, target: &str) -> Self {
+        self.failing.push(target.to_string());
+        self
+    }
+}
+
+impl OsActionDriver for SimulatedOsDriver {
+    fn driver_name(&self) -> &str {
+        "simulated"
+    }
+
+    fn perform(&mut self, action: &OsAction) -> Result<(), String> {
+        info!("SimulatedOsDriver::perform: {:?}", action);
+        let result = match action {
+            OsAction::Focus { app } if self.running.contains(&app.to_lowercase()) => Ok(()),
+            OsAction::Focus { app } => Err(format!("{} is not running", app)),
+            OsAction::Launch { executable, .. } | OsAction::OpenFile { path: executable } if self.failing.contains(executable) => {
+                Err(format!("Failed to start {}", executable))
+            }
+            OsAction::Launch { executable, .. } => {
+                self.running.push(executable.to_lowercase());
+                Ok(())
+            }
+            OsAction::OpenFile { .. } => Ok(()),
+        };
+        if result.is_ok() {
+            self.performed.push(action.clone());
+        }
+        result
+    }
+}
+
+/// Driver that performs actions with the platform's launcher and window tools
+/// Focus: AppActivate (Windows), osascript (macOS), wmctrl (Linux); files open in the default handler
+pub struct SystemOsDriver;
+
+impl SystemOsDriver {
+    fn run(command: &mut Command) -> Result<(), String> {
+        let status = command.status().map_err(|e| format!("Failed to run {:?}: {}", command.get_program(), e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{:?} exited with {}", command.get_program(), status))
+        }
+    }
+
+    fn focus_command(app: &str) -> Command {
+        if cfg!(target_os = "windows") {
+            let mut command = Command::new("powershell");
+            command.args(["-NoProfile", "-Command", &format!("if (-not (New-Object -ComObject WScript.Shell).AppActivate('{}')) {{ exit 1 }}", app.replace('\'', "''"))]);
+            command
+        } else if cfg!(target_os = "macos") {
+            let mut command = Command::new("osascript");
+            command.args(["-e", &format!("tell application \"{}\" to activate", app.replace('"', ""))]);
+            command
+        } else {
+            let mut command = Command::new("wmctrl");
+            command.args(["-a", app]);
+            command
+        }
+    }
+
+    /// The path is always a single argv element; nothing goes through a shell
+    fn open_command(path: &str) -> Command {
+        if cfg!(target_os = "windows") {
+            let mut command = Command::new("explorer.exe");
+            command.arg(path);
+            command
+        } else if cfg!(target_os = "macos") {
+            let mut command = Command::new("open");
+            command.arg(path);
+            command
+        } else {
+            let mut command = Command::new("xdg-open");
+            command.arg(path);
+            command
+        }
+    }
+}
+
+impl OsActionDriver for SystemOsDriver {
+    fn driver_name(&self) -> &str {
+        "system"
+    }
+
+    fn perform(&mut self, action: &OsAction) -> Result<(), String> {
+        info!("SystemOsDriver::perform: {:?}", action);
+        match action {
+            OsAction::Focus { app } => Self::run(&mut Self::focus_command(app)),
+            // Launched apps keep running after the shortcut; only spawning is checked
+            OsAction::Launch { executable, args } => Command::new(executable)
+                .args(args)
+                .spawn()
+                .map(|_| ())
+                .map_err(|e| format!("Failed to launch {}: {}", executable, e)),
+            // A leading '-' would be read as an option by the opener
+            OsAction::OpenFile { path } if path.starts_with('-') => Err(format!("Refusing to open {}", path)),
+            // explorer.exe exits non-zero even when it opens the file; only spawning is checked there
+            OsAction::OpenFile { path } if cfg!(target_os = "windows") => Self::open_command(path)
+                .spawn()
+                .map(|_| ())
+                .map_err(|e| format!("Failed to open {}: {}", path, e)),
+            OsAction::OpenFile { path } => Self::run(&mut Self::open_command(path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    fn approved(generator: &mut ShortcutGenerator, sequence: &[&str]) -> String {
+        let proposal = ShortcutProposal {
+            id: "shortcut_obs_1".to_string(),
+            description: "Morning routine".to_string(),
+            sequence: sequence.iter().map(|s| s.to_string()).collect(),
+            expected_time_saved_min: 4.0,
+            confidence: Confidence::High,
+            risk: RiskCategory::None,
+            requires_approval: true,
+            provenance: Provenance {
+                triggering_pattern: "test".to_string(),
+                data_used: Vec::new(),
+                confidence: Confidence::High,
+                consent_scopes: Vec::new(),
+            },
+            project: None,
+            created_at: 0,
+            signature: String::new(),
+            manual_duration_min: Some(4.0),
+        };
+        assert!(generator.import_approved(proposal, 0));
+        "shortcut_obs_1".to_string()
+    }
+
+    #[test]
+    fn test_plan_uses_templates_and_fallbacks() {
+        let mut generator = ShortcutGenerator::new();
+        let id = approved(&mut generator, &["Teams", "Teams", "notes/today.md", "IDE"]);
+        let mut executor = ShortcutExecutor::new(Box::new(SimulatedOsDriver::new()));
+        executor.set_template(StepTemplate { app: "ide".to_string(), executable: "code".to_string(), args: vec!["--reuse-window".to_string()], continue_on_error: true });
+
+        let steps = executor.plan(generator.get_proposal(&id).unwrap());
+        assert_eq!(steps.len(), 3);
+        // No template: the observed app name is never launched
+        assert_eq!(steps[0].fallback, None);
+        assert_eq!(steps[1].action, OsAction::OpenFile { path: "notes/today.md".to_string() });
+        assert_eq!(steps[2].fallback, Some(OsAction::Launch { executable: "code".to_string(), args: vec!["--reuse-window".to_string()] }));
+        assert!(steps[2].continue_on_error);
+    }
+
+    #[test]
+    fn test_execute_records_outcome_and_aborts_on_required_failure() {
+        let mut generator = ShortcutGenerator::new();
+        let id = approved(&mut generator, &["Teams", "Gmail", "IDE"]);
+        let driver = SimulatedOsDriver::new().with_running("teams").failing("Gmail");
+        let mut executor = ShortcutExecutor::new(Box::new(driver));
+        executor.set_template(StepTemplate::for_app("Gmail"));
+        executor.set_template(StepTemplate::for_app("IDE"));
+
+        let run = executor.execute(&mut generator, &id, 100).unwrap();
+        assert!(!run.succeeded);
+        assert_eq!(run.steps[0].status, StepStatus::Succeeded);
+        assert!(matches!(run.steps[1].status, StepStatus::Failed { .. }));
+        assert_eq!(run.steps[2].status, StepStatus::Skipped);
+        assert_eq!(run.failed_steps(), 1);
+        assert_eq!(run.outcome.time_saved_minutes, None);
+        assert!(!run.outcome.accepted);
+
+        // Optional step failures let the rest of the shortcut run
+        executor.set_template(StepTemplate { continue_on_error: true, ..StepTemplate::for_app("Gmail") });
+        let run = executor.execute(&mut generator, &id, 200).unwrap();
+        assert!(run.succeeded);
+        assert_eq!(run.steps[2].status, StepStatus::RecoveredWithFallback);
+        assert!(run.outcome.accepted);
+        assert_eq!(executor.outcomes().len(), 2);
+        // Saved time is the manual baseline less the measured run, not the proposal's prediction
+        let saved = run.outcome.time_saved_minutes.unwrap();
+        assert!(saved > 3.9 && saved < 4.0);
+        assert_eq!(generator.realized_savings.get(&id), Some(&saved));
+        assert_eq!(generator.get_ranker().get_calibration(&ActionType::AutomationMacro).unwrap().samples, 1);
+
+        // Without a manual baseline there is nothing to measure against
+        let mut proposal = generator.get_proposal(&id).unwrap().clone();
+        proposal.id = "shortcut_obs_2".to_string();
+        proposal.manual_duration_min = None;
+        assert!(generator.import_approved(proposal, 0));
+        let run = executor.execute(&mut generator, "shortcut_obs_2", 300).unwrap();
+        assert!(run.succeeded);
+        assert_eq!(run.outcome.time_saved_minutes, None);
+        assert!(!generator.realized_savings.contains_key("shortcut_obs_2"));
+    }
+
+    #[test]
+    fn test_only_approved_shortcuts_execute() {
+        let mut generator = ShortcutGenerator::new();
+        let mut executor = ShortcutExecutor::new(Box::new(SimulatedOsDriver::new()));
+        assert!(executor.execute(&mut generator, "missing", 0).is_err());
+        assert!(executor.recent_runs(5).is_empty());
+    }
+}
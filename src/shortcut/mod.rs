@@ -5,10 +5,37 @@
 use crate::types::*;
 use crate::models::RecommendationRanker;
 use crate::pattern_miner::PatternMiner;
+use crate::marketplace::{AutomationMarketplace, CommunityAutomation};
+use crate::categorizer::AppCategorizer;
+use crate::suppression::SuppressionList;
+use crate::sandbox::SandboxRunner;
+use crate::gate_policy::{GateInput, SharedGatePolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::info;
 
+pub mod executor;
+
+/// Approval decisions count half as much toward the gate's acceptance rate after this long
+const DECISION_HALF_LIFE_SECS: f64 = 14.0 * 86400.0;
+
+/// Proposals kept in memory; rejected ones go first, then the oldest pending, approved and edited ones stay
+const MAX_PROPOSALS: usize = 1_000;
+
+/// Sequence entries with a path separator or file extension are files, the rest are apps
+pub(crate) fn is_file_entry(entry: &str) -> bool {
+    entry.contains('/') || entry.contains('\\') || std::path::Path::new(entry).extension().is_some()
+}
+
+/// Gate quality of a proposal: its labeled confidence on the ranker's scale
+fn confidence_quality(confidence: &Confidence) -> f64 {
+    match confidence {
+        Confidence::High => 1.0,
+        Confidence::Medium => 0.7,
+        Confidence::Low => 0.4,
+    }
+}
+
 /// Shortcut proposal awaiting approval
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShortcutProposal {
@@ -19,7 +46,97 @@ pub struct ShortcutProposal {
     pub confidence: Confidence,
     pub risk: RiskCategory,
     pub requires_approval: bool,
+    pub provenance: Provenance,
+    pub project: Option<String>,
     pub created_at: i64,
+    #[serde(default)]
+    pub signature: String, // PatternSignature key of the sequence
+    #[serde(default)]
+    pub manual_duration_min: Option<f64>, // Observed time to step through the sequence by hand; the baseline for realized savings
+}
+
+/// Dry-run preview of what an approved shortcut would do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutPreview {
+    pub proposal_id: String,
+    pub steps: Vec<String>,
+    pub apps_touched: Vec<String>,
+    pub files_touched: Vec<String>,
+    pub undo_plan: Vec<String>,
+    pub sandbox_passed: bool,
+    pub sandbox_error: Option<String>,
+}
+
+/// Step-level difference between a generated sequence and the user's edit
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SequenceDiff {
+    pub kept: Vec<String>,    // Longest run of steps kept in order
+    pub added: Vec<String>,   // Steps only in the edit
+    pub removed: Vec<String>, // Steps only in the original
+    pub moved: Vec<String>,   // Steps in both, but reordered
+}
+
+impl SequenceDiff {
+    /// Diff two sequences via their longest common subsequence
+    pub fn between(original: &[String], edited: &[String]) -> Self {
+        let (n, m) = (original.len(), edited.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if original[i] == edited[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+            }
+        }
+        let mut diff = Self::default();
+        let mut dropped = Vec::new();
+        let mut inserted = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && original[i] == edited[j] {
+                diff.kept.push(original[i].clone());
+                i += 1;
+                j += 1;
+            } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                dropped.push(original[i].clone());
+                i += 1;
+            } else {
+                inserted.push(edited[j].clone());
+                j += 1;
+            }
+        }
+        // A step dropped in one place and inserted in another was moved
+        for step in dropped {
+            if let Some(pos) = inserted.iter().position(|s| *s == step) {
+                inserted.remove(pos);
+                diff.moved.push(step);
+            } else {
+                diff.removed.push(step);
+            }
+        }
+        diff.added = inserted;
+        diff
+    }
+
+    /// Share of steps the user changed (0.0 = identical, 1.0 = fully rewritten)
+    pub fn edit_ratio(&self) -> f64 {
+        let changed = self.added.len() + self.removed.len() + self.moved.len();
+        let total = changed + self.kept.len();
+        if total == 0 { 0.0 } else { changed as f64 / total as f64 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+/// One user edit of a proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalModification {
+    pub proposal_id: String,
+    pub original_sequence: Vec<String>, // Sequence as first generated
+    pub edited_sequence: Vec<String>,
+    pub diff: SequenceDiff,             // Original vs this edit
+    pub note: Option<String>,
+    pub modified_at: i64,
 }
 
 /// Approval status
@@ -38,6 +155,12 @@ pub struct ShortcutGenerator {
     pattern_miner: PatternMiner,
     proposals: HashMap<String, ShortcutProposal>,
     approvals: HashMap<String, ApprovalStatus>,
+    realized_savings: HashMap<String, f64>, // shortcut_id -> minutes actually saved
+    modifications: HashMap<String, Vec<ProposalModification>>, // shortcut_id -> edits, oldest first
+    suppression: SuppressionList,
+    gate_policy: SharedGatePolicy,
+    decisions: Vec<(i64, bool)>, // (decided_at, accepted) for each approval, edit or rejection
+    observe_only: bool, // Guided onboarding: no new proposals until unlocked
 }
 
 impl ShortcutGenerator {
@@ -49,26 +172,103 @@ impl ShortcutGenerator {
             pattern_miner: PatternMiner::new(),
             proposals: HashMap::new(),
             approvals: HashMap::new(),
+            realized_savings: HashMap::new(),
+            modifications: HashMap::new(),
+            suppression: SuppressionList::new(),
+            gate_policy: SharedGatePolicy::default(),
+            decisions: Vec::new(),
+            observe_only: false,
         }
     }
 
+    /// Share the gate policy; proposals must pass its risk and quality thresholds, and new proposals stop while the
+    /// decayed approval rate is below its required acceptance rate
+    pub fn set_gate_policy(&mut self, gate_policy: SharedGatePolicy) {
+        self.gate_policy = gate_policy;
+    }
+
+    /// Withhold new proposals while guided onboarding keeps shortcut suggestions locked
+    pub fn set_observe_only(&mut self, observe_only: bool) {
+        self.observe_only = observe_only;
+    }
+
+    /// Share a suppression list; suppressed sequences are never proposed
+    pub fn set_suppression_list(&mut self, suppression: SuppressionList) {
+        self.suppression = suppression;
+    }
+
     /// Generate predictive shortcut from observation
     /// Source: Athenos_AI_Strategy.md#L111
     pub fn generate_shortcut(&mut self, observation: &Observation) -> Option<ShortcutProposal> {
+        self.generate_shortcut_at(observation, chrono::Utc::now().timestamp())
+    }
+
+    /// Generate shortcut as of `now` (suppression checks and created_at use it; replay passes a virtual clock)
+    pub fn generate_shortcut_at(&mut self, observation: &Observation, now: i64) -> Option<ShortcutProposal> {
         info!("ShortcutGenerator::generate_shortcut: Generating shortcut for {}", observation.id);
         
+        if self.observe_only {
+            info!("ShortcutGenerator::generate_shortcut: Shortcut suggestions not unlocked yet");
+            return None;
+        }
+        
         // Check if pattern suggests shortcut creation
         if observation.observation.len() < 3 {
             return None;
         }
         
+        let signature = SuppressionList::sequence_signature(&observation.observation);
+        if self.suppression.is_suppressed(&signature, now) {
+            info!("ShortcutGenerator::generate_shortcut: {} is suppressed", signature);
+            return None;
+        }
+        
+        // Same pattern already proposed, approved or edited by the user for this project
+        let duplicate = self.proposals.values().any(|p| {
+            let edited_from = self.modifications
+                .get(&p.id)
+                .and_then(|edits| edits.first())
+                .is_some_and(|edit| SuppressionList::sequence_signature(&edit.original_sequence) == signature);
+            (p.signature == signature || edited_from)
+                && p.project == observation.project
+                && matches!(
+                    self.approvals.get(&p.id),
+                    Some(ApprovalStatus::Pending) | Some(ApprovalStatus::Approved) | Some(ApprovalStatus::Modified)
+                )
+        });
+        if duplicate {
+            info!("ShortcutGenerator::generate_shortcut: {} already proposed", signature);
+            return None;
+        }
+        
         let repeat_count = observation.metrics.get("repeat_count").copied().unwrap_or(0.0);
         if repeat_count < 5.0 {
             return None; // Not enough repetition
         }
         
+        // Old decisions fade, so a run of rejections withholds proposals for a while rather than for good
+        let (accepted, decided) = self.decisions.iter().fold((0.0, 0.0), |(accepted, decided), (at, was_accepted)| {
+            let weight = 0.5f64.powf((now - at).max(0) as f64 / DECISION_HALF_LIFE_SECS);
+            (accepted + if *was_accepted { weight } else { 0.0 }, decided + weight)
+        });
+        let input = GateInput {
+            quality: Some(confidence_quality(&observation.action.confidence)),
+            risk: Some(observation.action.risk.clone()),
+            accepted,
+            decided,
+            ..GateInput::default()
+        };
+        let violations = self.gate_policy.get().violations(&input);
+        if !violations.is_empty() {
+            info!("ShortcutGenerator::generate_shortcut: Withheld by gate policy: {}", violations.join("; "));
+            return None;
+        }
+        
         let expected_saved = observation.expected_outcome.get("time_saved_min").copied().unwrap_or(0.0);
         
+        let mut data_used: Vec<String> = observation.metrics.keys().cloned().collect();
+        data_used.sort();
+        
         let proposal = ShortcutProposal {
             id: format!("shortcut_{}", observation.id),
             description: format!("Automate sequence: {}", observation.observation.join(" → ")),
@@ -77,43 +277,207 @@ impl ShortcutGenerator {
             confidence: observation.action.confidence.clone(),
             risk: observation.action.risk.clone(),
             requires_approval: observation.action.risk != RiskCategory::None || observation.action.confidence < Confidence::High,
-            created_at: chrono::Utc::now().timestamp(),
+            provenance: Provenance {
+                triggering_pattern: format!("Repeated sequence: {}", observation.observation.join(" → ")),
+                data_used,
+                confidence: observation.action.confidence.clone(),
+                consent_scopes: vec!["behavioral_logging".to_string(), ActionType::AutomationMacro.consent_scope()],
+            },
+            project: observation.project.clone(),
+            created_at: now,
+            signature,
+            manual_duration_min: observation.metrics.get("sequence_duration_min").copied(),
         };
         
         self.proposals.insert(proposal.id.clone(), proposal.clone());
         self.approvals.insert(proposal.id.clone(), ApprovalStatus::Pending);
+        self.prune_proposals();
         
         Some(proposal)
     }
 
+    /// Drop rejected, then the oldest pending, proposals until at most MAX_PROPOSALS remain
+    fn prune_proposals(&mut self) {
+        if self.proposals.len() <= MAX_PROPOSALS {
+            return;
+        }
+        let mut evictable: Vec<(bool, i64, String)> = self.proposals
+            .values()
+            .filter_map(|p| match self.approvals.get(&p.id) {
+                Some(ApprovalStatus::Rejected) => Some((false, p.created_at, p.id.clone())),
+                Some(ApprovalStatus::Pending) => Some((true, p.created_at, p.id.clone())),
+                _ => None,
+            })
+            .collect();
+        evictable.sort();
+        let excess = self.proposals.len() - MAX_PROPOSALS;
+        for (_, _, id) in evictable.into_iter().take(excess) {
+            info!("ShortcutGenerator::prune_proposals: Dropping {}", id);
+            self.proposals.remove(&id);
+            self.approvals.remove(&id);
+            self.modifications.remove(&id);
+            self.realized_savings.remove(&id);
+        }
+    }
+
     /// Approve shortcut proposal
     pub fn approve_shortcut(&mut self, shortcut_id: &str) -> Result<(), String> {
+        self.approve_shortcut_at(shortcut_id, chrono::Utc::now().timestamp())
+    }
+
+    /// Approve shortcut as of `now`
+    pub fn approve_shortcut_at(&mut self, shortcut_id: &str, now: i64) -> Result<(), String> {
         info!("ShortcutGenerator::approve_shortcut: Approving {}", shortcut_id);
         if let Some(status) = self.approvals.get_mut(shortcut_id) {
             *status = ApprovalStatus::Approved;
+            self.decisions.push((now, true));
             Ok(())
         } else {
             Err("Shortcut not found".to_string())
         }
     }
 
+    /// Add a shortcut approved elsewhere (e.g. synced from another device)
+    /// Returns false when its sequence is suppressed here; the suppression wins over the remote approval
+    pub fn import_approved(&mut self, mut proposal: ShortcutProposal, now: i64) -> bool {
+        proposal.signature = SuppressionList::sequence_signature(&proposal.sequence);
+        if self.suppression.is_suppressed(&proposal.signature, now) {
+            info!("ShortcutGenerator::import_approved: {} is suppressed, not importing {}", proposal.signature, proposal.id);
+            return false;
+        }
+        info!("ShortcutGenerator::import_approved: Importing approved shortcut {}", proposal.id);
+        self.approvals.insert(proposal.id.clone(), ApprovalStatus::Approved);
+        self.proposals.insert(proposal.id.clone(), proposal);
+        self.prune_proposals();
+        true
+    }
+
     /// Reject shortcut proposal
+    /// Repeated rejections of the same sequence suppress it from future proposals
     pub fn reject_shortcut(&mut self, shortcut_id: &str) -> Result<(), String> {
+        self.reject_shortcut_at(shortcut_id, chrono::Utc::now().timestamp())
+    }
+
+    /// Reject shortcut as of `now`; suppression periods start at `now`
+    pub fn reject_shortcut_at(&mut self, shortcut_id: &str, now: i64) -> Result<(), String> {
         info!("ShortcutGenerator::reject_shortcut: Rejecting {}", shortcut_id);
-        if let Some(status) = self.approvals.get_mut(shortcut_id) {
-            *status = ApprovalStatus::Rejected;
-            Ok(())
-        } else {
-            Err("Shortcut not found".to_string())
+        // Look the proposal up first so a failed rejection records no decision
+        let (Some(status), Some(proposal)) = (self.approvals.get_mut(shortcut_id), self.proposals.get(shortcut_id)) else {
+            return Err("Shortcut not found".to_string());
+        };
+        *status = ApprovalStatus::Rejected;
+        self.decisions.push((now, false));
+        let signature = SuppressionList::sequence_signature(&proposal.sequence);
+        self.suppression.record_rejection(&signature, now)?;
+        Ok(())
+    }
+
+    /// Replace a proposal's sequence with the user's edit
+    /// The proposal returns to the approval queue as Modified, and the edit trains the ranker
+    pub fn propose_modification(&mut self, shortcut_id: &str, edited_sequence: Vec<String>, note: Option<String>) -> Result<ProposalModification, String> {
+        info!("ShortcutGenerator::propose_modification: Modifying {}", shortcut_id);
+        if self.approvals.get(shortcut_id) == Some(&ApprovalStatus::Rejected) {
+            return Err("Rejected shortcuts cannot be modified".to_string());
+        }
+        let proposal = self.proposals.get_mut(shortcut_id).ok_or("Shortcut not found")?;
+        let edited_sequence: Vec<String> = edited_sequence.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+        if edited_sequence.len() < 2 {
+            return Err("A shortcut needs at least two steps".to_string());
+        }
+        if edited_sequence == proposal.sequence {
+            return Err("Edited sequence is unchanged".to_string());
+        }
+        
+        let edits = self.modifications.entry(shortcut_id.to_string()).or_default();
+        let original_sequence = edits.first().map(|e| e.original_sequence.clone()).unwrap_or_else(|| proposal.sequence.clone());
+        let diff = SequenceDiff::between(&original_sequence, &edited_sequence);
+        
+        proposal.description = format!("Automate sequence: {}", edited_sequence.join(" → "));
+        proposal.signature = SuppressionList::sequence_signature(&edited_sequence);
+        proposal.sequence = edited_sequence.clone();
+        proposal.requires_approval = true;
+        
+        let modification = ProposalModification {
+            proposal_id: shortcut_id.to_string(),
+            original_sequence,
+            edited_sequence,
+            diff,
+            note,
+            modified_at: chrono::Utc::now().timestamp(),
+        };
+        self.decisions.push((modification.modified_at, true));
+        edits.push(modification.clone());
+        self.approvals.insert(shortcut_id.to_string(), ApprovalStatus::Modified);
+        self.ranker.record_modification(ActionType::AutomationMacro, modification.diff.edit_ratio());
+        Ok(modification)
+    }
+
+    /// User edits of a proposal, oldest first
+    pub fn get_modifications(&self, shortcut_id: &str) -> &[ProposalModification] {
+        self.modifications.get(shortcut_id).map(|edits| edits.as_slice()).unwrap_or(&[])
+    }
+
+    /// Dry-run a proposal in the sandbox and describe its steps, touched apps/files and undo plan
+    pub fn preview(&self, proposal_id: &str, sandbox: &SandboxRunner) -> Result<ShortcutPreview, String> {
+        info!("ShortcutGenerator::preview: Previewing {}", proposal_id);
+        let proposal = self.proposals.get(proposal_id).ok_or("Shortcut not found")?;
+        
+        let mut steps = Vec::new();
+        let mut apps_touched: Vec<String> = Vec::new();
+        let mut files_touched: Vec<String> = Vec::new();
+        for (i, entry) in proposal.sequence.iter().enumerate() {
+            if is_file_entry(entry) {
+                steps.push(format!("{}. Open file {}", i + 1, entry));
+                if !files_touched.contains(entry) {
+                    files_touched.push(entry.clone());
+                }
+            } else {
+                steps.push(format!("{}. Switch to {}", i + 1, entry));
+                if !apps_touched.contains(entry) {
+                    apps_touched.push(entry.clone());
+                }
+            }
         }
+        
+        let action = Action {
+            action_type: ActionType::AutomationMacro,
+            description: proposal.description.clone(),
+            confidence: proposal.confidence.clone(),
+            risk: proposal.risk.clone(),
+        };
+        let result = sandbox.test_automation(&action);
+        
+        let mut undo_plan: Vec<String> = files_touched.iter().map(|f| format!("Close {} without saving shortcut changes", f)).collect();
+        undo_plan.push(format!("Restore window focus to the app active before {}", proposal.sequence.first().map(|s| s.as_str()).unwrap_or("the shortcut")));
+        undo_plan.push(sandbox.generate_undo(&action));
+        
+        Ok(ShortcutPreview {
+            proposal_id: proposal_id.to_string(),
+            steps,
+            apps_touched,
+            files_touched,
+            undo_plan,
+            sandbox_passed: result.success,
+            sandbox_error: result.error_message,
+        })
     }
 
-    /// Get pending proposals requiring approval
+    /// Look up a proposal in any status
+    pub fn get_proposal(&self, shortcut_id: &str) -> Option<&ShortcutProposal> {
+        self.proposals.get(shortcut_id)
+    }
+
+    /// Whether a proposal has been approved for execution
+    pub fn is_approved(&self, shortcut_id: &str) -> bool {
+        self.approvals.get(shortcut_id) == Some(&ApprovalStatus::Approved)
+    }
+
+    /// Get pending proposals requiring approval, including user-modified ones awaiting confirmation
     pub fn get_pending_proposals(&self) -> Vec<&ShortcutProposal> {
         self.proposals
             .values()
             .filter(|p| {
-                self.approvals.get(&p.id) == Some(&ApprovalStatus::Pending) && p.requires_approval
+                matches!(self.approvals.get(&p.id), Some(ApprovalStatus::Pending) | Some(ApprovalStatus::Modified)) && p.requires_approval
             })
             .collect()
     }
@@ -125,6 +489,57 @@ impl ShortcutGenerator {
             .filter(|p| self.approvals.get(&p.id) == Some(&ApprovalStatus::Approved))
             .collect()
     }
+
+    /// Get approved shortcuts for a project (project-less shortcuts apply everywhere)
+    pub fn get_approved_shortcuts_for_project(&self, project: Option<&str>) -> Vec<&ShortcutProposal> {
+        self.get_approved_shortcuts()
+            .into_iter()
+            .filter(|p| p.project.is_none() || p.project.as_deref() == project)
+            .collect()
+    }
+
+    /// Record minutes actually saved by running an approved shortcut
+    pub fn record_realized_savings(&mut self, shortcut_id: &str, minutes: f64) -> Result<(), String> {
+        info!("ShortcutGenerator::record_realized_savings: {} saved {} min", shortcut_id, minutes);
+        let proposal = self.proposals.get(shortcut_id).ok_or("Shortcut not found")?;
+        if self.approvals.get(shortcut_id) == Some(&ApprovalStatus::Approved) {
+            self.ranker.record_realized_outcome(ActionType::AutomationMacro, proposal.expected_time_saved_min, minutes);
+        }
+        *self.realized_savings.entry(shortcut_id.to_string()).or_insert(0.0) += minutes;
+        Ok(())
+    }
+
+    /// Ranker calibrated with realized shortcut savings
+    pub fn get_ranker(&self) -> &RecommendationRanker {
+        &self.ranker
+    }
+
+    /// Account for external workload (CI failures, alerts, assignments) when ranking
+    pub fn set_external_load(&mut self, load: f64) {
+        self.ranker.set_external_load(load);
+    }
+
+    /// Publish an approved, proven shortcut to the marketplace for review
+    pub fn publish_to_marketplace(
+        &self,
+        shortcut_id: &str,
+        marketplace: &mut AutomationMarketplace,
+        categorizer: &AppCategorizer,
+    ) -> Result<CommunityAutomation, String> {
+        info!("ShortcutGenerator::publish_to_marketplace: Publishing {}", shortcut_id);
+        
+        let proposal = self.proposals.get(shortcut_id).ok_or("Shortcut not found")?;
+        if self.approvals.get(shortcut_id) != Some(&ApprovalStatus::Approved) {
+            return Err("Only approved shortcuts can be published".to_string());
+        }
+        
+        let realized = self.realized_savings.get(shortcut_id).copied().unwrap_or(0.0);
+        if realized <= 0.0 {
+            return Err("Shortcut has not proven any savings yet".to_string());
+        }
+        
+        marketplace.submit_community_automation(shortcut_id, &proposal.sequence, realized, categorizer)
+    }
 }
 
 impl Default for ShortcutGenerator {
@@ -167,6 +582,7 @@ mod tests {
             expected_outcome: expected,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let proposal = generator.generate_shortcut(&observation);
@@ -174,6 +590,8 @@ mod tests {
         let proposal = proposal.unwrap();
         assert_eq!(proposal.sequence.len(), 3);
         assert_eq!(proposal.expected_time_saved_min, 11.0);
+        assert_eq!(proposal.provenance.data_used, vec!["repeat_count"]);
+        assert!(proposal.provenance.consent_scopes.contains(&"behavioral_logging".to_string()));
     }
 
     #[test]
@@ -197,6 +615,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let proposal = generator.generate_shortcut(&observation);
@@ -226,6 +645,7 @@ mod tests {
             expected_outcome: expected,
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let proposal = generator.generate_shortcut(&observation).unwrap();
@@ -237,5 +657,345 @@ mod tests {
         let approved = generator.get_approved_shortcuts();
         assert_eq!(approved.len(), 1);
     }
-}
 
+    #[test]
+    fn test_publish_requires_approval_and_savings() {
+        let mut generator = ShortcutGenerator::new();
+        let mut marketplace = AutomationMarketplace::new();
+        let categorizer = AppCategorizer::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let observation = Observation {
+            id: "test_004".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        let proposal = generator.generate_shortcut(&observation).unwrap();
+        assert!(generator.publish_to_marketplace(&proposal.id, &mut marketplace, &categorizer).is_err());
+        
+        generator.approve_shortcut(&proposal.id).unwrap();
+        assert!(generator.publish_to_marketplace(&proposal.id, &mut marketplace, &categorizer).is_err());
+        
+        generator.record_realized_savings(&proposal.id, 9.5).unwrap();
+        let automation = generator.publish_to_marketplace(&proposal.id, &mut marketplace, &categorizer).unwrap();
+        assert_eq!(automation.realized_savings_min, 9.5);
+        assert_eq!(automation.trigger, "on_focus:communication");
+    }
+
+    #[test]
+    fn test_rejected_twice_suppresses_sequence() {
+        let mut generator = ShortcutGenerator::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let mut observation = Observation {
+            id: "test_005".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        let first = generator.generate_shortcut(&observation).unwrap();
+        generator.reject_shortcut(&first.id).unwrap();
+        observation.id = "test_006".to_string();
+        let second = generator.generate_shortcut(&observation).unwrap();
+        generator.reject_shortcut(&second.id).unwrap();
+        
+        observation.id = "test_007".to_string();
+        assert!(generator.generate_shortcut(&observation).is_none());
+    }
+
+    #[test]
+    fn test_reject_unknown_shortcut_errors() {
+        let mut generator = ShortcutGenerator::new();
+        assert!(generator.reject_shortcut("shortcut_missing").is_err());
+        assert!(generator.approve_shortcut("shortcut_missing").is_err());
+
+        // An approval entry whose proposal is gone must not panic
+        generator.approvals.insert("shortcut_orphan".to_string(), ApprovalStatus::Pending);
+        assert!(generator.reject_shortcut("shortcut_orphan").is_err());
+        assert!(generator.reject_shortcut_at("shortcut_orphan", 0).is_err());
+        assert_eq!(generator.approvals["shortcut_orphan"], ApprovalStatus::Pending);
+        assert!(generator.decisions.is_empty());
+    }
+
+    #[test]
+    fn test_gate_policy_withholds_proposals_after_rejections() {
+        use crate::gate_policy::{GatePolicy, SharedGatePolicy};
+        let mut generator = ShortcutGenerator::new();
+        let policy = SharedGatePolicy::new(GatePolicy { min_acceptance_rate: 0.5, min_acceptance_samples: 2, ..GatePolicy::default() });
+        generator.set_gate_policy(policy.clone());
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        let observation = |id: &str, apps: &[&str]| Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: apps.iter().map(|a| a.to_string()).collect(),
+            metrics: metrics.clone(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        let first = generator.generate_shortcut(&observation("test_020", &["Teams", "Gmail", "IDE"])).unwrap();
+        generator.reject_shortcut(&first.id).unwrap();
+        let second = generator.generate_shortcut(&observation("test_021", &["Slack", "Jira", "IDE"])).unwrap();
+        generator.reject_shortcut(&second.id).unwrap();
+        
+        let third = observation("test_022", &["Figma", "Notion", "Slack"]);
+        assert!(generator.generate_shortcut(&third).is_none());
+        // The rejections fade, so proposals resume without any new decision
+        let later = chrono::Utc::now().timestamp() + 30 * 86400;
+        assert!(generator.generate_shortcut_at(&third, later).is_some());
+
+        policy.set(GatePolicy::default()).unwrap();
+        let mut risky = observation("test_023", &["Terminal", "Browser", "Notes"]);
+        risky.action.risk = RiskCategory::High;
+        assert!(generator.generate_shortcut(&risky).is_none());
+        let mut unsure = observation("test_024", &["Terminal", "Browser", "Notes"]);
+        unsure.action.confidence = Confidence::Low;
+        assert!(generator.generate_shortcut(&unsure).is_none());
+        assert!(generator.generate_shortcut(&observation("test_025", &["Terminal", "Browser", "Notes"])).is_some());
+    }
+
+    #[test]
+    fn test_duplicate_signature_not_reproposed() {
+        let mut generator = ShortcutGenerator::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let mut observation = Observation {
+            id: "test_010".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let first = generator.generate_shortcut(&observation).unwrap();
+        
+        observation.id = "test_011".to_string();
+        observation.observation = vec!["teams.exe".to_string(), "gmail".to_string(), "Gmail".to_string(), "ide".to_string()];
+        assert!(generator.generate_shortcut(&observation).is_none());
+        
+        generator.reject_shortcut(&first.id).unwrap();
+        assert!(generator.generate_shortcut(&observation).is_some());
+    }
+
+    #[test]
+    fn test_proposals_bounded_keeping_approved() {
+        let mut generator = ShortcutGenerator::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        let mut observation = Observation {
+            id: "bounded_0".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "app_0".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let first = generator.generate_shortcut_at(&observation, 0).unwrap();
+        generator.approve_shortcut_at(&first.id, 0).unwrap();
+        
+        for i in 1..MAX_PROPOSALS + 5 {
+            observation.id = format!("bounded_{}", i);
+            observation.observation[2] = format!("app_{}", i);
+            assert!(generator.generate_shortcut_at(&observation, i as i64).is_some());
+        }
+        assert_eq!(generator.proposals.len(), MAX_PROPOSALS);
+        assert!(generator.get_proposal(&first.id).is_some());
+        // The oldest pending proposals were dropped
+        assert!(generator.get_proposal("shortcut_bounded_5").is_none());
+        assert!(generator.get_proposal("shortcut_bounded_6").is_some());
+    }
+
+    #[test]
+    fn test_approved_shortcuts_scoped_to_project() {
+        let mut generator = ShortcutGenerator::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let mut observation = Observation {
+            id: "test_008".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Terminal".to_string(), "IDE".to_string(), "Browser".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: Some("git:athenos-ai".to_string()),
+        };
+        let scoped = generator.generate_shortcut(&observation).unwrap();
+        generator.approve_shortcut(&scoped.id).unwrap();
+        
+        observation.id = "test_009".to_string();
+        observation.project = None;
+        let global = generator.generate_shortcut(&observation).unwrap();
+        generator.approve_shortcut(&global.id).unwrap();
+        
+        assert_eq!(generator.get_approved_shortcuts_for_project(Some("git:athenos-ai")).len(), 2);
+        assert_eq!(generator.get_approved_shortcuts_for_project(Some("git:other")).len(), 1);
+    }
+
+    #[test]
+    fn test_preview_lists_steps_and_undo() {
+        use crate::gate_policy::{GatePolicy, SharedGatePolicy};
+        let mut generator = ShortcutGenerator::new();
+        generator.set_gate_policy(SharedGatePolicy::new(GatePolicy { max_risk: RiskCategory::High, ..GatePolicy::default() }));
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let observation = Observation {
+            id: "test_010".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Terminal".to_string(), "src/main.rs".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::High,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let proposal = generator.generate_shortcut(&observation).unwrap();
+        
+        let preview = generator.preview(&proposal.id, &SandboxRunner::default()).unwrap();
+        assert_eq!(preview.steps, vec!["1. Switch to Terminal", "2. Open file src/main.rs", "3. Switch to IDE"]);
+        assert_eq!(preview.apps_touched, vec!["Terminal", "IDE"]);
+        assert_eq!(preview.files_touched, vec!["src/main.rs"]);
+        assert_eq!(preview.undo_plan.len(), 3);
+        assert!(!preview.sandbox_passed); // High risk fails the default sandbox policy
+        assert!(generator.preview("missing", &SandboxRunner::default()).is_err());
+    }
+
+    #[test]
+    fn test_sequence_diff() {
+        let seq = |apps: &[&str]| -> Vec<String> { apps.iter().map(|a| a.to_string()).collect() };
+        let diff = SequenceDiff::between(&seq(&["Teams", "Gmail", "IDE"]), &seq(&["Gmail", "Slack", "Teams", "IDE"]));
+        assert_eq!(diff.kept, seq(&["Gmail", "IDE"]));
+        assert_eq!(diff.added, seq(&["Slack"]));
+        assert_eq!(diff.moved, seq(&["Teams"]));
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.edit_ratio(), 0.5);
+        assert!(SequenceDiff::between(&seq(&["A", "B"]), &seq(&["A", "B"])).is_empty());
+    }
+
+    #[test]
+    fn test_modification_workflow() {
+        let mut generator = ShortcutGenerator::new();
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        
+        let mut observation = Observation {
+            id: "test_012".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let proposal = generator.generate_shortcut(&observation).unwrap();
+        generator.approve_shortcut(&proposal.id).unwrap();
+        
+        let edited = vec!["Teams".to_string(), "IDE".to_string(), "Terminal".to_string()];
+        assert!(generator.propose_modification(&proposal.id, proposal.sequence.clone(), None).is_err());
+        assert!(generator.propose_modification(&proposal.id, vec!["IDE".to_string(), " ".to_string()], None).is_err());
+        let modification = generator.propose_modification(&proposal.id, edited.clone(), Some("Skip email".to_string())).unwrap();
+        assert_eq!(modification.diff.removed, vec!["Gmail"]);
+        assert_eq!(modification.diff.added, vec!["Terminal"]);
+        
+        // Edited sequences need confirmation before they run
+        assert!(!generator.is_approved(&proposal.id));
+        assert_eq!(generator.get_pending_proposals().len(), 1);
+        assert_eq!(generator.get_proposal(&proposal.id).unwrap().sequence, edited);
+        assert_eq!(generator.get_ranker().get_modification_stats(&ActionType::AutomationMacro).unwrap().samples, 1);
+        
+        // Later edits are still diffed against the generated sequence
+        let second = generator.propose_modification(&proposal.id, vec!["Teams".to_string(), "Gmail".to_string()], None).unwrap();
+        assert_eq!(second.original_sequence, proposal.sequence);
+        assert_eq!(second.diff.removed, vec!["IDE"]);
+        assert_eq!(generator.get_modifications(&proposal.id).len(), 2);
+        
+        // The original pattern is not re-proposed while the edited version is live
+        observation.id = "test_013".to_string();
+        assert!(generator.generate_shortcut(&observation).is_none());
+        
+        generator.approve_shortcut(&proposal.id).unwrap();
+        assert_eq!(generator.get_approved_shortcuts()[0].sequence, vec!["Teams", "Gmail"]);
+        generator.reject_shortcut(&proposal.id).unwrap();
+        assert!(generator.propose_modification(&proposal.id, edited, None).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+/// Phase: B | Source: TRAINING CONCEPT.txt#L27
+/// Pattern Signature
+/// Canonical pattern identity (normalized sequence + category hash) shared across modules
+
+use crate::categorizer::AppCategorizer;
+use crate::types::Observation;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Hex characters kept from the SHA-256 digest
+const HASH_LEN: usize = 16;
+
+fn default_categorizer() -> &'static AppCategorizer {
+    static CATEGORIZER: OnceLock<AppCategorizer> = OnceLock::new();
+    CATEGORIZER.get_or_init(AppCategorizer::new)
+}
+
+/// Canonical pattern signature
+/// Sequences that differ only in case, whitespace, executable suffix or immediate repeats share a signature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PatternSignature {
+    pub sequence: Vec<String>,   // Normalized steps
+    pub categories: Vec<String>, // App category labels per step
+    pub hash: String,            // Over sequence and categories
+    pub category_hash: String,   // Over categories only; contains no app names
+}
+
+impl PatternSignature {
+    /// Signature for a raw step sequence using the default categorizer
+    pub fn from_sequence(sequence: &[String]) -> Self {
+        Self::with_categorizer(sequence, default_categorizer())
+    }
+
+    /// Signature for an observation's step sequence
+    pub fn from_observation(observation: &Observation) -> Self {
+        Self::from_sequence(&observation.observation)
+    }
+
+    /// Signature using a custom categorizer
+    pub fn with_categorizer(sequence: &[String], categorizer: &AppCategorizer) -> Self {
+        let mut normalized: Vec<String> = Vec::new();
+        for step in sequence {
+            let step = Self::normalize_step(step);
+            if !step.is_empty() && normalized.last() != Some(&step) {
+                normalized.push(step);
+            }
+        }
+        let categories: Vec<String> = normalized.iter().map(|step| categorizer.categorize(step).label().to_string()).collect();
+        let hash = Self::digest(&format!("{}\u{1e}{}", normalized.join("\u{1f}"), categories.join("\u{1f}")));
+        let category_hash = Self::digest(&categories.join("\u{1f}"));
+        Self { sequence: normalized, categories, hash, category_hash }
+    }
+
+    /// Lowercase, collapse whitespace and drop executable suffixes ("Slack.exe " -> "slack")
+    fn normalize_step(step: &str) -> String {
+        let lower = step.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        [".exe", ".app"]
+            .iter()
+            .find_map(|suffix| lower.strip_suffix(suffix))
+            .map(str::to_string)
+            .unwrap_or(lower)
+    }
+
+    fn digest(input: &str) -> String {
+        ring::digest::digest(&ring::digest::SHA256, input.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()[..HASH_LEN]
+            .to_string()
+    }
+
+    /// Key for local state (suppression lists, RL state, shortcut dedup, miner counts)
+    pub fn key(&self) -> String {
+        format!("sig:{}", self.hash)
+    }
+
+    /// Key safe to share off-device (federation templates)
+    pub fn category_key(&self) -> String {
+        format!("cat:{}", self.category_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seq(steps: &[&str]) -> Vec<String> {
+        steps.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_equivalent_sequences_share_signature() {
+        let a = PatternSignature::from_sequence(&seq(&["Teams", "Gmail", "IDE"]));
+        let b = PatternSignature::from_sequence(&seq(&["teams.exe", " Gmail ", "Gmail", "ide"]));
+        assert_eq!(a, b);
+        assert_eq!(a.sequence, seq(&["teams", "gmail", "ide"]));
+        assert_eq!(a.categories, seq(&["communication", "email", "code_editor"]));
+        assert!(a.key().starts_with("sig:") && a.key().len() == 4 + HASH_LEN);
+    }
+
+    #[test]
+    fn test_category_key_hides_app_names() {
+        let slack = PatternSignature::from_sequence(&seq(&["Slack", "Outlook", "IDE"]));
+        let teams = PatternSignature::from_sequence(&seq(&["Teams", "Gmail", "IDE"]));
+        assert_ne!(slack.key(), teams.key());
+        assert_eq!(slack.category_key(), teams.category_key());
+        assert!(!slack.category_key().contains("slack"));
+    }
+}
@@ -0,0 +1,234 @@
+/// Phase: C | Source: Athenos_AI_Strategy.md#L111-121
+/// Pattern Suppression List
+/// "Don't suggest this again": suppress pattern signatures after repeated rejection
+
+use crate::signature::PatternSignature;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// Persisted suppression state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SuppressionState {
+    rejections: HashMap<String, u32>,       // signature -> rejections since last suppression
+    suppressed_until: HashMap<String, i64>, // signature -> expiry timestamp
+}
+
+/// Shared suppression list
+/// Cloning yields a handle to the same underlying list
+#[derive(Debug, Clone)]
+pub struct SuppressionList {
+    state: Arc<RwLock<SuppressionState>>,
+    path: Option<PathBuf>,
+    rejection_threshold: u32,
+    period_secs: i64,
+}
+
+impl SuppressionList {
+    /// Create in-memory suppression list (30-day period)
+    pub fn new() -> Self {
+        Self::with_period(30 * 86400)
+    }
+
+    /// Create in-memory suppression list with a suppression period
+    pub fn with_period(period_secs: i64) -> Self {
+        info!("SuppressionList::new: Creating suppression list ({}s period)", period_secs);
+        Self {
+            state: Arc::new(RwLock::new(SuppressionState::default())),
+            path: None,
+            rejection_threshold: 2,
+            period_secs,
+        }
+    }
+
+    /// Open persistent suppression list at path (created on first write)
+    pub fn open(path: PathBuf, period_secs: i64) -> Result<Self, String> {
+        info!("SuppressionList::open: Opening suppression list at {:?}", path);
+        let mut state: SuppressionState = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(|e| format!("Invalid suppression list: {}", e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => SuppressionState::default(),
+            Err(e) => return Err(format!("Failed to read suppression list: {}", e)),
+        };
+        let migrated = Self::migrate_legacy_keys(&mut state);
+
+        let mut list = Self::with_period(period_secs);
+        list.state = Arc::new(RwLock::new(state));
+        list.path = Some(path);
+        if migrated > 0 {
+            info!("SuppressionList::open: Migrated {} legacy sequence signatures", migrated);
+            list.save()?;
+        }
+        Ok(list)
+    }
+
+    /// Rewrite pre-PatternSignature keys ("seq:teams>gmail") to canonical signatures; returns the number rewritten
+    fn migrate_legacy_keys(state: &mut SuppressionState) -> usize {
+        let canonical = |key: &str| {
+            key.strip_prefix("seq:").map(|steps| Self::sequence_signature(&steps.split('>').map(str::to_string).collect::<Vec<_>>()))
+        };
+        let legacy: Vec<String> = state.rejections.keys().chain(state.suppressed_until.keys()).filter(|k| k.starts_with("seq:")).cloned().collect();
+        for key in &legacy {
+            let Some(signature) = canonical(key) else { continue };
+            if let Some(count) = state.rejections.remove(key) {
+                *state.rejections.entry(signature.clone()).or_insert(0) += count;
+            }
+            if let Some(until) = state.suppressed_until.remove(key) {
+                let existing = state.suppressed_until.entry(signature).or_insert(until);
+                *existing = (*existing).max(until);
+            }
+        }
+        legacy.len()
+    }
+
+    /// Signature for an observed app/action sequence
+    pub fn sequence_signature(sequence: &[String]) -> String {
+        PatternSignature::from_sequence(sequence).key()
+    }
+
+    /// Signature for a microlearning nudge topic
+    pub fn nudge_signature(topic: &str) -> String {
+        format!("nudge:{}", topic.to_lowercase())
+    }
+
+    /// Record a rejection; the signature is suppressed once it reaches the threshold
+    /// Returns whether the signature is now suppressed
+    pub fn record_rejection(&self, signature: &str, now: i64) -> Result<bool, String> {
+        let suppressed = {
+            let mut state = self.state.write().unwrap();
+            let count = state.rejections.entry(signature.to_string()).or_insert(0);
+            *count += 1;
+            if *count >= self.rejection_threshold {
+                state.rejections.remove(signature);
+                state.suppressed_until.insert(signature.to_string(), now + self.period_secs);
+                true
+            } else {
+                false
+            }
+        };
+
+        if suppressed {
+            info!("SuppressionList::record_rejection: Suppressing {} for {}s", signature, self.period_secs);
+        }
+        self.save()?;
+        Ok(suppressed)
+    }
+
+    /// Suppress a signature immediately ("don't suggest this again")
+    pub fn suppress(&self, signature: &str, now: i64) -> Result<(), String> {
+        info!("SuppressionList::suppress: Suppressing {}", signature);
+        {
+            let mut state = self.state.write().unwrap();
+            state.rejections.remove(signature);
+            state.suppressed_until.insert(signature.to_string(), now + self.period_secs);
+        }
+        self.save()
+    }
+
+    /// Suppress a signature until an explicit expiry (e.g. synced from another device)
+    pub fn suppress_until(&self, signature: &str, until: i64) -> Result<(), String> {
+        info!("SuppressionList::suppress_until: Suppressing {} until {}", signature, until);
+        {
+            let mut state = self.state.write().unwrap();
+            state.rejections.remove(signature);
+            state.suppressed_until.insert(signature.to_string(), until);
+        }
+        self.save()
+    }
+
+    /// Lift a suppression
+    pub fn unsuppress(&self, signature: &str) -> Result<(), String> {
+        info!("SuppressionList::unsuppress: Lifting suppression of {}", signature);
+        self.state.write().unwrap().suppressed_until.remove(signature);
+        self.save()
+    }
+
+    /// Check whether a signature is currently suppressed
+    pub fn is_suppressed(&self, signature: &str, now: i64) -> bool {
+        self.state
+            .read()
+            .unwrap()
+            .suppressed_until
+            .get(signature)
+            .map(|until| now < *until)
+            .unwrap_or(false)
+    }
+
+    /// Currently suppressed signatures with their expiry, soonest first
+    pub fn list_suppressed(&self, now: i64) -> Vec<(String, i64)> {
+        let mut suppressed: Vec<(String, i64)> = self.state
+            .read()
+            .unwrap()
+            .suppressed_until
+            .iter()
+            .filter(|(_, until)| now < **until)
+            .map(|(signature, until)| (signature.clone(), *until))
+            .collect();
+        suppressed.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        suppressed
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create suppression dir: {}", e))?;
+        }
+        let json = serde_json::to_string(&*self.state.read().unwrap()).map_err(|e| format!("Failed to encode suppression list: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write suppression list: {}", e))
+    }
+}
+
+impl Default for SuppressionList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suppressed_after_two_rejections() {
+        let list = SuppressionList::with_period(3600);
+        let signature = SuppressionList::sequence_signature(&["Teams".to_string(), "Gmail".to_string()]);
+
+        assert!(!list.record_rejection(&signature, 1000).unwrap());
+        assert!(!list.is_suppressed(&signature, 1000));
+        assert!(list.record_rejection(&signature, 1000).unwrap());
+        assert!(list.is_suppressed(&signature, 2000));
+        assert!(!list.is_suppressed(&signature, 1000 + 3600));
+    }
+
+    #[test]
+    fn test_persistence() {
+        let path = std::env::temp_dir().join(format!("athenos_suppression_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let list = SuppressionList::open(path.clone(), 3600).unwrap();
+        list.suppress("nudge:shortcut_keys", 1000).unwrap();
+
+        let reopened = SuppressionList::open(path.clone(), 3600).unwrap();
+        assert!(reopened.is_suppressed("nudge:shortcut_keys", 1500));
+        assert_eq!(reopened.list_suppressed(1500).len(), 1);
+
+        reopened.unsuppress("nudge:shortcut_keys").unwrap();
+        assert!(!SuppressionList::open(path.clone(), 3600).unwrap().is_suppressed("nudge:shortcut_keys", 1500));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_legacy_sequence_keys_migrated_on_open() {
+        let path = std::env::temp_dir().join(format!("athenos_suppression_legacy_{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"rejections":{"seq:slack>ide":1},"suppressed_until":{"seq:teams>gmail>ide":5000,"nudge:focus":5000}}"#).unwrap();
+
+        let list = SuppressionList::open(path.clone(), 3600).unwrap();
+        let sequence: Vec<String> = ["Teams", "Gmail", "IDE"].iter().map(|s| s.to_string()).collect();
+        assert!(list.is_suppressed(&SuppressionList::sequence_signature(&sequence), 1000));
+        assert!(list.is_suppressed("nudge:focus", 1000));
+        assert!(list.record_rejection(&SuppressionList::sequence_signature(&["Slack".to_string(), "IDE".to_string()]), 1000).unwrap());
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("seq:"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
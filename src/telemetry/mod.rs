@@ -0,0 +1,286 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L137
+/// Product Telemetry
+/// Strictly opt-in, rate-limited, DP-noised usage counters (features used, never content)
+
+use crate::compliance::DifferentialPrivacy;
+use crate::consent::MicroConsentManager;
+use crate::config::{AthenosConfig, ConfigListener};
+use crate::multi_region::RegionPolicy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Micro-consent capability required for any telemetry collection
+pub const TELEMETRY_CAPABILITY: &str = "product_telemetry";
+
+/// Telemetry configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    pub endpoint: Option<String>, // None = telemetry disabled
+    pub min_interval_secs: i64,
+    pub max_sends_per_day: usize,
+    pub epsilon: f64,
+    pub bucket_size: u64, // Counters are rounded to multiples of this
+    #[serde(default)]
+    pub endpoint_region: Option<String>, // Region hosting the endpoint, checked against data residency
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            min_interval_secs: 6 * 3600,
+            max_sends_per_day: 2,
+            epsilon: 1.0,
+            bucket_size: 5,
+            endpoint_region: None,
+        }
+    }
+}
+
+/// Payload sent to the telemetry endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryPayload {
+    pub endpoint: String,
+    pub counters: HashMap<String, u64>, // feature -> noised, bucketed use count
+    pub window_start: i64,
+    pub window_end: i64,
+}
+
+/// Consent-gated telemetry channel
+pub struct TelemetryChannel {
+    config: TelemetryConfig,
+    privacy: DifferentialPrivacy,
+    counters: HashMap<String, u64>,
+    window_start: i64,
+    last_sent_at: Option<i64>,
+    sends_by_day: HashMap<String, usize>,
+    sent: Vec<TelemetryPayload>,
+    region_policy: Option<(String, RegionPolicy)>, // (home region, policy) of the local user
+}
+
+impl TelemetryChannel {
+    /// Create new telemetry channel
+    pub fn new(config: TelemetryConfig) -> Self {
+        info!("TelemetryChannel::new: Creating telemetry channel (endpoint: {:?})", config.endpoint);
+        Self {
+            privacy: DifferentialPrivacy::new(config.epsilon),
+            config,
+            counters: HashMap::new(),
+            window_start: chrono::Utc::now().timestamp(),
+            last_sent_at: None,
+            sends_by_day: HashMap::new(),
+            sent: Vec::new(),
+            region_policy: None,
+        }
+    }
+
+    /// Effective epsilon: the home region's epsilon caps the configured one
+    fn effective_epsilon(&self) -> f64 {
+        match &self.region_policy {
+            Some((_, policy)) => self.config.epsilon.min(policy.dp_epsilon),
+            None => self.config.epsilon,
+        }
+    }
+
+    /// Apply the compliance policy of the user's home region to noise and destination checks
+    pub fn apply_region_policy(&mut self, home_region: &str, policy: &RegionPolicy) {
+        info!("TelemetryChannel::apply_region_policy: {} (ε={})", home_region, policy.dp_epsilon);
+        self.region_policy = Some((home_region.to_string(), policy.clone()));
+        self.privacy = DifferentialPrivacy::new(self.effective_epsilon());
+    }
+
+    /// Record a feature use; dropped unless the user opted in
+    /// Feature names must be snake_case identifiers so no free-form content can leak
+    pub fn record_feature_use(&mut self, consent: &MicroConsentManager, feature: &str) -> Result<(), String> {
+        if self.config.endpoint.is_none() || !consent.has_consent(TELEMETRY_CAPABILITY) {
+            return Ok(());
+        }
+
+        let valid = !feature.is_empty()
+            && feature.len() <= 64
+            && feature.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(format!("Invalid telemetry feature name: {}", feature));
+        }
+
+        *self.counters.entry(feature.to_string()).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Send accumulated counters if consent, endpoint and rate limits allow
+    /// Every send is recorded in the transparency timeline
+    pub fn flush(&mut self, consent: &mut MicroConsentManager, now: i64) -> Result<Option<TelemetryPayload>, String> {
+        let endpoint = match &self.config.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => return Ok(None),
+        };
+
+        if !consent.has_consent(TELEMETRY_CAPABILITY) {
+            // Consent may have been revoked since collection: discard
+            self.counters.clear();
+            return Ok(None);
+        }
+
+        if self.counters.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some((home_region, policy)) = &self.region_policy {
+            let permitted = self.config.endpoint_region
+                .as_deref()
+                .map(|region| policy.allows_destination(home_region, region))
+                .unwrap_or(false);
+            if !permitted {
+                // Residency cannot be honoured for this endpoint: keep the buffer until the endpoint or policy changes
+                return Err(format!("Data residency for {} does not permit telemetry to {:?}", home_region, self.config.endpoint_region));
+            }
+        }
+
+        if let Some(last) = self.last_sent_at {
+            if now - last < self.config.min_interval_secs {
+                return Ok(None);
+            }
+        }
+
+        let day = chrono::DateTime::from_timestamp(now, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let sends_today = self.sends_by_day.get(&day).copied().unwrap_or(0);
+        if sends_today >= self.config.max_sends_per_day {
+            return Ok(None);
+        }
+
+        info!("TelemetryChannel::flush: Sending {} counters to {}", self.counters.len(), endpoint);
+
+        let bucket = self.config.bucket_size.max(1) as f64;
+        let counters: HashMap<String, u64> = self.counters
+            .drain()
+            .map(|(feature, count)| {
+                let noised = self.privacy.add_noise(count as f64).max(0.0);
+                (feature, ((noised / bucket).round() * bucket) as u64)
+            })
+            .collect();
+
+        let payload = TelemetryPayload {
+            endpoint: endpoint.clone(),
+            counters,
+            window_start: self.window_start,
+            window_end: now,
+        };
+
+        let mut features: Vec<String> = payload.counters.keys().cloned().collect();
+        features.sort();
+        consent.add_timeline_entry(
+            "telemetry_sent".to_string(),
+            format!("Sent {} coarse usage counters to {}", features.len(), endpoint),
+            features,
+            Some("telemetry_send".to_string()),
+        );
+
+        self.window_start = now;
+        self.last_sent_at = Some(now);
+        self.sends_by_day.insert(day, sends_today + 1);
+        self.sent.push(payload.clone());
+        Ok(Some(payload))
+    }
+
+    /// Get history of sent payloads
+    pub fn get_sent(&self) -> &[TelemetryPayload] {
+        &self.sent
+    }
+}
+
+impl ConfigListener for TelemetryChannel {
+    fn config_name(&self) -> &str {
+        "telemetry"
+    }
+
+    /// Apply new DP epsilon and daily send limit
+    fn on_config_change(&mut self, config: &AthenosConfig) -> Result<(), String> {
+        if (config.dp_epsilon - self.config.epsilon).abs() > f64::EPSILON {
+            self.config.epsilon = config.dp_epsilon;
+            self.privacy = DifferentialPrivacy::new(self.effective_epsilon());
+        }
+        self.config.max_sends_per_day = config.rate_limits.telemetry_max_sends_per_day;
+        Ok(())
+    }
+}
+
+impl Default for TelemetryChannel {
+    fn default() -> Self {
+        Self::new(TelemetryConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_config() -> TelemetryConfig {
+        TelemetryConfig {
+            endpoint: Some("https://telemetry.example.com/v1".to_string()),
+            ..TelemetryConfig::default()
+        }
+    }
+
+    fn opted_in_consent() -> MicroConsentManager {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(TELEMETRY_CAPABILITY.to_string(), "Share coarse usage counters".to_string());
+        consent.grant_consent(TELEMETRY_CAPABILITY).unwrap();
+        consent
+    }
+
+    #[test]
+    fn test_no_collection_without_consent() {
+        let mut channel = TelemetryChannel::new(enabled_config());
+        let mut consent = MicroConsentManager::new();
+
+        channel.record_feature_use(&consent, "shortcut_approved").unwrap();
+        assert!(channel.counters.is_empty());
+        assert!(channel.flush(&mut consent, 1_000_000).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_flush_records_timeline_and_rate_limits() {
+        let mut channel = TelemetryChannel::new(enabled_config());
+        let mut consent = opted_in_consent();
+
+        for _ in 0..20 {
+            channel.record_feature_use(&consent, "shortcut_approved").unwrap();
+        }
+        let payload = channel.flush(&mut consent, 1_000_000).unwrap().unwrap();
+        assert_eq!(payload.counters.get("shortcut_approved").unwrap() % 5, 0);
+        assert!(consent.get_timeline(None).iter().any(|e| e.event_type == "telemetry_sent"));
+
+        channel.record_feature_use(&consent, "focus_mode_enabled").unwrap();
+        assert!(channel.flush(&mut consent, 1_000_060).unwrap().is_none()); // Within min interval
+        assert_eq!(channel.get_sent().len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_free_form_content() {
+        let mut channel = TelemetryChannel::new(enabled_config());
+        let consent = opted_in_consent();
+
+        assert!(channel.record_feature_use(&consent, "Opened Q3 salary spreadsheet").is_err());
+        assert!(channel.counters.is_empty());
+    }
+
+    #[test]
+    fn test_region_policy_caps_epsilon_and_enforces_residency() {
+        let mut channel = TelemetryChannel::new(TelemetryConfig { endpoint_region: Some("us-east".to_string()), ..enabled_config() });
+        let mut consent = opted_in_consent();
+        channel.apply_region_policy("eu-west", &RegionPolicy { dp_epsilon: 0.2, ..RegionPolicy::default() });
+        assert_eq!(channel.privacy.epsilon(), 0.2);
+
+        channel.record_feature_use(&consent, "shortcut_approved").unwrap();
+        assert!(channel.flush(&mut consent, 1_000_000).is_err());
+        assert_eq!(channel.counters.get("shortcut_approved"), Some(&1)); // Buffer kept on error
+
+        channel.apply_region_policy("eu-west", &RegionPolicy { data_residency: vec!["us-east".to_string()], ..RegionPolicy::default() });
+        channel.record_feature_use(&consent, "shortcut_approved").unwrap();
+        assert!(channel.flush(&mut consent, 1_000_000).unwrap().is_some());
+        assert!(channel.counters.is_empty());
+    }
+}
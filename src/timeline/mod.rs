@@ -0,0 +1,269 @@
+/// Phase: C | Source: athenos-rules.mdc#L12-15
+/// Day Replay Timeline Export
+/// Anonymized, timeline-ready view of a day (app category blocks, focus segments, intervention markers)
+
+use crate::auto_action::{ActionState, ExecutedAction};
+use crate::categorizer::{AppCategorizer, AppCategory};
+use crate::edge::{OSEvent, OSEventType};
+use crate::types::ActionType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Minimum uninterrupted deep-work time that forms a focus segment
+const FOCUS_SEGMENT_MIN_SECS: i64 = 10 * 60;
+
+/// Time spent in one app category (no app names or titles)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategoryBlock {
+    pub category: AppCategory,
+    pub start: i64,
+    pub end: i64,
+    pub app_switches: usize, // Switches between apps of the same category inside the block
+}
+
+/// Sustained deep-work interval
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FocusSegment {
+    pub start: i64,
+    pub end: i64,
+    pub categories: Vec<AppCategory>,
+}
+
+/// Intervention executed during the day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterventionMarker {
+    pub at: i64,
+    pub action_type: ActionType,
+    pub state: ActionState,
+}
+
+/// Day totals for the replay header
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineSummary {
+    pub minutes_by_category: BTreeMap<String, f64>,
+    pub focus_minutes: f64,
+    pub category_switches: usize,
+    pub interventions: usize,
+}
+
+/// Timeline-ready day replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayTimeline {
+    pub day_start: i64,
+    pub day_end: i64,
+    pub blocks: Vec<CategoryBlock>,
+    pub focus_segments: Vec<FocusSegment>,
+    pub interventions: Vec<InterventionMarker>,
+    pub summary: TimelineSummary,
+}
+
+/// Builds day replays from the local event stream
+pub struct TimelineExporter {
+    categorizer: AppCategorizer,
+}
+
+impl TimelineExporter {
+    /// Create exporter with the default app categorizer
+    pub fn new() -> Self {
+        Self::with_categorizer(AppCategorizer::new())
+    }
+
+    /// Create exporter with a custom app categorizer
+    pub fn with_categorizer(categorizer: AppCategorizer) -> Self {
+        info!("TimelineExporter::new: Creating day replay exporter");
+        Self { categorizer }
+    }
+
+    fn is_deep_work(category: AppCategory) -> bool {
+        matches!(
+            category,
+            AppCategory::CodeEditor | AppCategory::Terminal | AppCategory::Documents | AppCategory::Spreadsheet | AppCategory::Design
+        )
+    }
+
+    /// Build the replay for the UTC day containing `day_start`
+    /// Only categories and timings leave this function; app names, titles and metadata are dropped
+    pub fn build_day(&self, day_start: i64, events: &[OSEvent], executed: &[&ExecutedAction]) -> DayTimeline {
+        let day_start = day_start - day_start.rem_euclid(86400);
+        let day_end = day_start + 86400;
+        let mut day_events: Vec<&OSEvent> = events.iter().filter(|e| e.timestamp >= day_start && e.timestamp < day_end).collect();
+        day_events.sort_by_key(|e| e.timestamp);
+        let last_seen = day_events.last().map(|e| e.timestamp).unwrap_or(day_start);
+
+        // Category blocks: each focus change opens a block, sleep closes it
+        let mut blocks: Vec<CategoryBlock> = Vec::new();
+        let mut open: Option<(CategoryBlock, String)> = None;
+        for event in &day_events {
+            match event.event_type {
+                OSEventType::AppLaunch | OSEventType::AppSwitch | OSEventType::WindowFocus => {
+                    let category = self.categorizer.categorize(&event.app_name);
+                    let app = event.app_name.to_lowercase();
+                    match open.as_mut() {
+                        Some((block, current_app)) if block.category == category => {
+                            if *current_app != app {
+                                block.app_switches += 1;
+                                *current_app = app;
+                            }
+                        }
+                        _ => {
+                            if let Some((mut block, _)) = open.take() {
+                                block.end = event.timestamp;
+                                blocks.push(block);
+                            }
+                            open = Some((CategoryBlock { category, start: event.timestamp, end: event.timestamp, app_switches: 0 }, app));
+                        }
+                    }
+                }
+                OSEventType::SystemSleep => {
+                    if let Some((mut block, _)) = open.take() {
+                        block.end = event.timestamp;
+                        blocks.push(block);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some((mut block, _)) = open.take() {
+            block.end = last_seen;
+            blocks.push(block);
+        }
+        blocks.retain(|b| b.end > b.start);
+
+        // Focus segments: adjacent deep-work blocks lasting long enough
+        let mut focus_segments: Vec<FocusSegment> = Vec::new();
+        let mut current: Option<FocusSegment> = None;
+        for block in &blocks {
+            let contiguous = current.as_ref().map(|s| s.end == block.start).unwrap_or(false);
+            if Self::is_deep_work(block.category) {
+                match current.as_mut() {
+                    Some(segment) if contiguous => {
+                        segment.end = block.end;
+                        if !segment.categories.contains(&block.category) {
+                            segment.categories.push(block.category);
+                        }
+                    }
+                    _ => {
+                        focus_segments.extend(current.take());
+                        current = Some(FocusSegment { start: block.start, end: block.end, categories: vec![block.category] });
+                    }
+                }
+            } else {
+                focus_segments.extend(current.take());
+            }
+        }
+        focus_segments.extend(current.take());
+        focus_segments.retain(|s| s.end - s.start >= FOCUS_SEGMENT_MIN_SECS);
+
+        let mut interventions: Vec<InterventionMarker> = executed
+            .iter()
+            .filter_map(|a| {
+                let at = a.executed_at?;
+                (at >= day_start && at < day_end).then(|| InterventionMarker { at, action_type: a.action.action_type.clone(), state: a.state.clone() })
+            })
+            .collect();
+        interventions.sort_by_key(|m| m.at);
+
+        let mut summary = TimelineSummary {
+            focus_minutes: focus_segments.iter().map(|s| (s.end - s.start) as f64 / 60.0).sum(),
+            category_switches: blocks.len().saturating_sub(1),
+            interventions: interventions.len(),
+            ..TimelineSummary::default()
+        };
+        for block in &blocks {
+            *summary.minutes_by_category.entry(block.category.label().to_string()).or_insert(0.0) += (block.end - block.start) as f64 / 60.0;
+        }
+
+        info!(
+            "TimelineExporter::build_day: {} blocks, {} focus segments, {} interventions",
+            blocks.len(),
+            focus_segments.len(),
+            interventions.len()
+        );
+        DayTimeline { day_start, day_end, blocks, focus_segments, interventions, summary }
+    }
+
+    /// Day replay as JSON for UI clients
+    pub fn export_json(&self, day_start: i64, events: &[OSEvent], executed: &[&ExecutedAction]) -> Result<String, String> {
+        serde_json::to_string(&self.build_day(day_start, events, executed)).map_err(|e| format!("Failed to encode day replay: {}", e))
+    }
+}
+
+impl Default for TimelineExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, Confidence, RiskCategory};
+    use std::collections::HashMap;
+
+    const DAY: i64 = 1_700_438_400; // 2023-11-20 00:00 UTC
+
+    fn event(event_type: OSEventType, app: &str, minute: i64) -> OSEvent {
+        OSEvent {
+            event_type,
+            app_name: app.to_string(),
+            window_title: Some(format!("secret roadmap - {}", app)),
+            timestamp: DAY + 9 * 3600 + minute * 60,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn day_events() -> Vec<OSEvent> {
+        vec![
+            event(OSEventType::AppLaunch, "Slack", 0),
+            event(OSEventType::AppSwitch, "IDE", 5),
+            event(OSEventType::KeyPress, "IDE", 10),
+            event(OSEventType::AppSwitch, "Terminal", 20),
+            event(OSEventType::AppSwitch, "Gmail", 30),
+            event(OSEventType::AppSwitch, "Outlook", 32),
+            event(OSEventType::SystemSleep, "System", 35),
+        ]
+    }
+
+    #[test]
+    fn test_blocks_and_focus_segments() {
+        let timeline = TimelineExporter::new().build_day(DAY + 3600, &day_events(), &[]);
+        let categories: Vec<AppCategory> = timeline.blocks.iter().map(|b| b.category).collect();
+        assert_eq!(categories, vec![AppCategory::Communication, AppCategory::CodeEditor, AppCategory::Terminal, AppCategory::Email]);
+        assert_eq!(timeline.blocks[3].app_switches, 1);
+        assert_eq!(timeline.blocks[3].end, DAY + 9 * 3600 + 35 * 60);
+
+        // IDE + terminal form one 25-minute focus segment
+        assert_eq!(timeline.focus_segments.len(), 1);
+        assert_eq!(timeline.focus_segments[0].categories, vec![AppCategory::CodeEditor, AppCategory::Terminal]);
+        assert_eq!(timeline.summary.focus_minutes, 25.0);
+        assert_eq!(timeline.summary.minutes_by_category["email"], 5.0);
+    }
+
+    #[test]
+    fn test_export_is_anonymized_with_intervention_markers() {
+        let executed = ExecutedAction {
+            id: "action_1".to_string(),
+            action: Action {
+                action_type: ActionType::FocusMode,
+                description: "Mute Slack during roadmap work".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            state: ActionState::Completed,
+            execution_result: None,
+            rollback_diff: None,
+            reverse_ops: Vec::new(),
+            executed_at: Some(DAY + 9 * 3600 + 6 * 60),
+            rolled_back_at: None,
+            approval_chain: Vec::new(),
+        };
+        let json = TimelineExporter::new().export_json(DAY, &day_events(), &[&executed]).unwrap();
+        for leaked in ["Slack", "IDE", "Gmail", "roadmap", "action_1"] {
+            assert!(!json.contains(leaked), "{} leaked", leaked);
+        }
+        let timeline: DayTimeline = serde_json::from_str(&json).unwrap();
+        assert_eq!(timeline.interventions.len(), 1);
+        assert_eq!(timeline.interventions[0].action_type, ActionType::FocusMode);
+    }
+}
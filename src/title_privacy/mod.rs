@@ -0,0 +1,234 @@
+/// Phase: A | Source: athenos-rules.mdc#L12-15
+/// Privacy-Preserving Window-Title Processing
+/// Reduce window titles to coarse features (app context, document type, project hash) and discard the raw title
+
+use crate::categorizer::AppCategorizer;
+use crate::consent::MicroConsentManager;
+use crate::edge::OSEvent;
+use crate::privacy::load_or_create_key;
+use crate::project_context::ProjectContextDetector;
+use crate::safety_filter::SafetyFilter;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::info;
+
+/// Consent capability required to keep raw window titles
+pub const RAW_WINDOW_TITLES_CAPABILITY: &str = "raw_window_titles";
+
+const META_APP_CONTEXT: &str = "title.app_context";
+const META_DOCUMENT_TYPE: &str = "title.document_type";
+const META_PROJECT_HASH: &str = "title.project_hash";
+
+/// Coarse features extracted from a window title
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TitleFeatures {
+    pub app_context: String,
+    pub document_type: Option<String>, // Lowercased file extension
+    pub project_hash: Option<String>,  // Keyed hash of the detected project
+}
+
+impl TitleFeatures {
+    /// Write features into event metadata
+    pub fn write_metadata(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(META_APP_CONTEXT.to_string(), self.app_context.clone());
+        if let Some(document_type) = &self.document_type {
+            metadata.insert(META_DOCUMENT_TYPE.to_string(), document_type.clone());
+        }
+        if let Some(project_hash) = &self.project_hash {
+            metadata.insert(META_PROJECT_HASH.to_string(), project_hash.clone());
+        }
+    }
+
+    /// Read features back from event metadata
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> Option<Self> {
+        Some(Self {
+            app_context: metadata.get(META_APP_CONTEXT)?.clone(),
+            document_type: metadata.get(META_DOCUMENT_TYPE).cloned(),
+            project_hash: metadata.get(META_PROJECT_HASH).cloned(),
+        })
+    }
+}
+
+/// Window-title processing stage applied before events are stored
+pub struct WindowTitleProcessor {
+    categorizer: AppCategorizer,
+    project_detector: ProjectContextDetector,
+    hash_key: ring::hmac::Key,
+    retain_raw: bool,
+    safety_filter: Option<SafetyFilter>, // Learns raw titles before they are discarded
+}
+
+impl WindowTitleProcessor {
+    /// Create processor with a freshly generated local hashing key (raw titles discarded)
+    pub fn new() -> Self {
+        use rand::Rng;
+        let key_bytes: [u8; 32] = rand::thread_rng().gen();
+        Self::with_key(&key_bytes)
+    }
+
+    /// Create processor from an existing key (stable project hashes across restarts)
+    pub fn with_key(key_bytes: &[u8]) -> Self {
+        info!("WindowTitleProcessor::new: Creating window-title processor (raw titles discarded)");
+        Self {
+            categorizer: AppCategorizer::new(),
+            project_detector: ProjectContextDetector::new(),
+            hash_key: ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key_bytes),
+            retain_raw: false,
+            safety_filter: None,
+        }
+    }
+
+    /// Open a processor whose hashing key persists, so project hashes stay stable across restarts
+    /// The key is generated on first use and is owner-only on Unix
+    pub fn open(key_path: &Path) -> Result<Self, String> {
+        info!("WindowTitleProcessor::open: Loading title hashing key from {}", key_path.display());
+        Ok(Self::with_key(&load_or_create_key(key_path)?))
+    }
+
+    /// Register raw titles with the generated-text safety filter before they are discarded
+    pub fn set_safety_filter(&mut self, safety_filter: SafetyFilter) {
+        self.safety_filter = Some(safety_filter);
+    }
+
+    /// Retain raw titles only while `raw_window_titles` consent is granted
+    pub fn apply_consent(&mut self, consent: &MicroConsentManager) {
+        let retain = consent.has_consent(RAW_WINDOW_TITLES_CAPABILITY);
+        if retain != self.retain_raw {
+            info!("WindowTitleProcessor::apply_consent: Raw title retention {}", if retain { "enabled" } else { "disabled" });
+        }
+        self.retain_raw = retain;
+    }
+
+    /// Whether raw titles are currently retained
+    pub fn retains_raw_titles(&self) -> bool {
+        self.retain_raw
+    }
+
+    /// Extract coarse features from a title
+    pub fn extract(&self, app_name: &str, title: &str) -> TitleFeatures {
+        let document_type = title
+            .split(|c: char| c.is_whitespace() || c == '|' || c == '—' || c == '–')
+            .filter_map(|token| token.rsplit_once('.'))
+            .map(|(stem, ext)| (stem, ext.trim_matches(|c: char| !c.is_ascii_alphanumeric()).to_lowercase()))
+            .find(|(stem, ext)| !stem.is_empty() && (1..=5).contains(&ext.len()) && ext.chars().all(|c| c.is_ascii_alphanumeric()) && !ext.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, ext)| ext);
+
+        let project_hash = self.project_detector.detect_from_title(title).map(|context| {
+            let tag = ring::hmac::sign(&self.hash_key, context.key().as_bytes());
+            tag.as_ref()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+        });
+
+        TitleFeatures {
+            app_context: self.categorizer.categorize(app_name).label().to_string(),
+            document_type,
+            project_hash,
+        }
+    }
+
+    /// Replace the event's raw title with coarse features (unless retention is consented)
+    pub fn process(&self, event: &mut OSEvent) {
+        let title = match &event.window_title {
+            Some(title) => title.clone(),
+            None => return,
+        };
+        self.extract(&event.app_name, &title).write_metadata(&mut event.metadata);
+        if let Some(safety_filter) = &self.safety_filter {
+            safety_filter.register_window_title(&title, &event.app_name);
+        }
+        if !self.retain_raw {
+            event.window_title = None;
+        }
+    }
+}
+
+impl Default for WindowTitleProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::OSEventType;
+
+    fn titled_event(app: &str, title: &str) -> OSEvent {
+        OSEvent {
+            event_type: OSEventType::WindowFocus,
+            app_name: app.to_string(),
+            window_title: Some(title.to_string()),
+            timestamp: 0,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_raw_title_discarded_by_default() {
+        let processor = WindowTitleProcessor::with_key(b"local-test-key");
+        let mut event = titled_event("Code", "payroll.rs - athenos-ai - Visual Studio Code");
+        processor.process(&mut event);
+
+        assert!(event.window_title.is_none());
+        let features = TitleFeatures::from_metadata(&event.metadata).unwrap();
+        assert_eq!(features.app_context, "code_editor");
+        assert_eq!(features.document_type.as_deref(), Some("rs"));
+        let hash = features.project_hash.unwrap();
+        assert_eq!(hash.len(), 16);
+        assert!(!hash.contains("athenos"));
+    }
+
+    #[test]
+    fn test_consent_retains_raw_title() {
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(RAW_WINDOW_TITLES_CAPABILITY.to_string(), "Keep raw titles".to_string());
+        consent.grant_consent(RAW_WINDOW_TITLES_CAPABILITY).unwrap();
+        let mut processor = WindowTitleProcessor::with_key(b"local-test-key");
+        processor.apply_consent(&consent);
+
+        let mut event = titled_event("Word", "Q3 Budget.docx - Word");
+        processor.process(&mut event);
+        assert_eq!(event.window_title.as_deref(), Some("Q3 Budget.docx - Word"));
+        assert_eq!(event.metadata.get("title.document_type").map(String::as_str), Some("docx"));
+
+        consent.revoke_consent(RAW_WINDOW_TITLES_CAPABILITY, None).unwrap();
+        processor.apply_consent(&consent);
+        assert!(!processor.retains_raw_titles());
+    }
+
+    #[test]
+    fn test_stripped_titles_still_reach_safety_filter() {
+        let mut processor = WindowTitleProcessor::with_key(b"local-test-key");
+        let filter = SafetyFilter::new();
+        processor.set_safety_filter(filter.clone());
+
+        let mut event = titled_event("Word", "Q3 layoffs plan - Word");
+        processor.process(&mut event);
+        assert!(event.window_title.is_none());
+        assert!(!filter.check("Reviewing the Q3 layoffs plan now").is_empty());
+    }
+
+    #[test]
+    fn test_opened_key_persists() {
+        let path = std::env::temp_dir().join(format!("athenos_title_key_{}.key", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let title = "main.rs - athenos-ai - Visual Studio Code";
+
+        let first = WindowTitleProcessor::open(&path).unwrap().extract("Code", title).project_hash;
+        let second = WindowTitleProcessor::open(&path).unwrap().extract("Code", title).project_hash;
+        assert!(first.is_some());
+        assert_eq!(first, second);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_project_hash_stable_per_key() {
+        let a = WindowTitleProcessor::with_key(b"key-a");
+        let b = WindowTitleProcessor::with_key(b"key-b");
+        let title = "main.rs - athenos-ai - Visual Studio Code";
+
+        assert_eq!(a.extract("Code", title).project_hash, a.extract("Code", "lib.rs - athenos-ai - Visual Studio Code").project_hash);
+        assert_ne!(a.extract("Code", title).project_hash, b.extract("Code", title).project_hash);
+        assert_eq!(a.extract("Chrome", "Inbox (3) - Gmail").document_type, None);
+    }
+}
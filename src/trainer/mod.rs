@@ -0,0 +1,293 @@
+/// Phase: D | Source: Athenos_AI_Strategy.md#L132
+/// Model Training Scheduler
+/// Retrain PatternDetector/ranker when enough new labeled outcomes accumulate, gated by replay canary
+
+use crate::analytics::{AnalyticsAggregator, MetricCategory};
+use crate::labeling::ObservationLabel;
+use crate::model_registry::{CanaryReport, ModelKind, ModelRegistry, RegisteredModel};
+use crate::models::{PatternDetector, RecommendationRanker};
+use crate::power::{JobClass, ResourceAwareScheduler};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use tracing::info;
+
+/// Retraining thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainerConfig {
+    pub min_new_outcomes: usize,
+    pub min_interval_secs: i64,
+}
+
+impl Default for TrainerConfig {
+    fn default() -> Self {
+        Self {
+            min_new_outcomes: 50,
+            min_interval_secs: 6 * 3600,
+        }
+    }
+}
+
+/// Model retrained by the trainer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TrainedModel {
+    PatternDetector,
+    RecommendationRanker,
+}
+
+/// Record of one training run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingRun {
+    pub model: TrainedModel,
+    pub version: String,
+    pub started_at: i64,
+    pub dataset_size: usize,
+    pub new_outcomes: usize,
+    pub duration_ms: f64,
+    pub promoted: bool,
+    pub canary: Option<CanaryReport>,
+    pub error: Option<String>,
+}
+
+/// Background trainer triggered by labeled-data freshness
+pub struct ModelTrainer {
+    config: TrainerConfig,
+    labeled_ids: HashSet<String>,
+    outcomes: HashMap<String, bool>, // observation_id -> accepted, scored against by the canary
+    new_outcomes: usize,
+    last_trained_at: Option<i64>,
+    runs: Vec<TrainingRun>,
+}
+
+impl ModelTrainer {
+    /// Create trainer
+    pub fn new(config: TrainerConfig) -> Self {
+        info!("ModelTrainer::new: Retrain after {} new outcomes", config.min_new_outcomes);
+        Self {
+            config,
+            labeled_ids: HashSet::new(),
+            outcomes: HashMap::new(),
+            new_outcomes: 0,
+            last_trained_at: None,
+            runs: Vec::new(),
+        }
+    }
+
+    /// Record a labeled outcome
+    pub fn record_outcome(&mut self, outcome: &Outcome) {
+        self.labeled_ids.insert(outcome.observation_id.clone());
+        self.outcomes.insert(outcome.observation_id.clone(), outcome.accepted);
+        self.new_outcomes += 1;
+    }
+
+    /// Record a human label as a labeled outcome; returns false if the observation was already counted
+    pub fn record_label(&mut self, label: &ObservationLabel) -> bool {
+        if !self.labeled_ids.insert(label.observation_id.clone()) {
+            return false;
+        }
+        self.new_outcomes += 1;
+        true
+    }
+
+    /// Whether enough fresh outcomes have accumulated since the last run
+    pub fn is_due(&self, now: i64) -> bool {
+        let interval_ok = self.last_trained_at.map(|last| now - last >= self.config.min_interval_secs).unwrap_or(true);
+        self.new_outcomes >= self.config.min_new_outcomes && interval_ok
+    }
+
+    /// Retrain when due and resources allow; candidates are promoted only if the replay canary passes,
+    /// including agreement with the accepted/rejected outcomes recorded so far
+    /// The ranker scores with its own detector, so it is swapped in only alongside a promoted detector
+    pub fn run_if_due(
+        &mut self,
+        now: i64,
+        observations: &[Observation],
+        registry: &mut ModelRegistry,
+        ranker: &mut RecommendationRanker,
+        analytics: &mut AnalyticsAggregator,
+        resources: &ResourceAwareScheduler,
+    ) -> Vec<TrainingRun> {
+        if !self.is_due(now) {
+            return Vec::new();
+        }
+        if !resources.can_run(JobClass::PatternMining) {
+            info!("ModelTrainer::run_if_due: Deferring training until resources allow");
+            return Vec::new();
+        }
+
+        let dataset: Vec<Observation> = observations
+            .iter()
+            .filter(|o| self.labeled_ids.contains(&o.id))
+            .cloned()
+            .collect();
+        let new_outcomes = self.new_outcomes;
+        let run_number = self.runs.len() / 2 + 1;
+        info!("ModelTrainer::run_if_due: Training on {} labeled observations ({} new outcomes)", dataset.len(), new_outcomes);
+
+        let started = Instant::now();
+        let mut detector = PatternDetector::new();
+        detector.train(&dataset);
+        let (promoted, canary, error) = match registry.promote(&format!("pd-{}", run_number), RegisteredModel::PatternDetector(detector), observations, &self.outcomes, now) {
+            Ok(report) => (report.promoted, Some(report), None),
+            Err(e) => (false, None, Some(e)),
+        };
+        let detector_run = TrainingRun {
+            model: TrainedModel::PatternDetector,
+            version: format!("pd-{}", run_number),
+            started_at: now,
+            dataset_size: dataset.len(),
+            new_outcomes,
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            promoted,
+            canary,
+            error,
+        };
+
+        let started = Instant::now();
+        let mut candidate = RecommendationRanker::new();
+        candidate.train(&dataset);
+        if promoted {
+            *ranker = candidate;
+        }
+        let ranker_run = TrainingRun {
+            model: TrainedModel::RecommendationRanker,
+            version: format!("ranker-{}", run_number),
+            started_at: now,
+            dataset_size: dataset.len(),
+            new_outcomes,
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            promoted,
+            canary: None,
+            error: if promoted { None } else { Some("Detector canary did not pass".to_string()) },
+        };
+
+        self.new_outcomes = 0;
+        self.last_trained_at = Some(now);
+        let runs = vec![detector_run, ranker_run];
+        for run in &runs {
+            Self::record_run(analytics, run);
+        }
+        self.runs.extend(runs.iter().cloned());
+        runs
+    }
+
+    fn record_run(analytics: &mut AnalyticsAggregator, run: &TrainingRun) {
+        let prefix = match run.model {
+            TrainedModel::PatternDetector => "training.pattern_detector",
+            TrainedModel::RecommendationRanker => "training.ranker",
+        };
+        analytics.record_metric(format!("{}.dataset_size", prefix), run.dataset_size as f64, MetricCategory::Operations);
+        analytics.record_metric(format!("{}.duration_ms", prefix), run.duration_ms, MetricCategory::Operations);
+        analytics.record_metric(format!("{}.promoted", prefix), if run.promoted { 1.0 } else { 0.0 }, MetricCategory::Operations);
+        if let Some(canary) = &run.canary {
+            analytics.record_metric(format!("{}.gate_pass_rate", prefix), canary.candidate.gate_pass_rate, MetricCategory::Safety);
+            analytics.record_metric(format!("{}.predicted_acceptance", prefix), canary.candidate.predicted_acceptance, MetricCategory::Product);
+        }
+    }
+
+    /// Active detector version promoted by the trainer, if any
+    pub fn active_version<'a>(&self, registry: &'a ModelRegistry) -> Option<&'a str> {
+        registry.get_active_version(ModelKind::PatternDetector)
+    }
+
+    /// Get training run history
+    pub fn get_runs(&self) -> &[TrainingRun] {
+        &self.runs
+    }
+}
+
+impl Default for ModelTrainer {
+    fn default() -> Self {
+        Self::new(TrainerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::{PowerSource, ResourceState};
+    use std::collections::HashMap;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn labeled(id: &str) -> (Observation, Outcome) {
+        let mut metrics = HashMap::new();
+        metrics.insert("repeat_count".to_string(), 8.0);
+        let observation = Observation {
+            id: id.to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics,
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Logged macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: NOW - 3600,
+            project: None,
+        };
+        let outcome = Outcome {
+            observation_id: id.to_string(),
+            accepted: true,
+            ignored: false,
+            modified: false,
+            time_saved_minutes: Some(5.0),
+            error_rate_change: None,
+            timestamp: NOW - 1800,
+        };
+        (observation, outcome)
+    }
+
+    #[test]
+    fn test_retrains_after_threshold() {
+        let mut trainer = ModelTrainer::new(TrainerConfig { min_new_outcomes: 2, min_interval_secs: 3600 });
+        let mut registry = ModelRegistry::new();
+        let mut ranker = RecommendationRanker::new();
+        let mut analytics = AnalyticsAggregator::new();
+        let resources = ResourceAwareScheduler::new();
+        let (obs1, out1) = labeled("obs1");
+        let (obs2, out2) = labeled("obs2");
+        let observations = vec![obs1, obs2];
+
+        trainer.record_outcome(&out1);
+        assert!(trainer.run_if_due(NOW, &observations, &mut registry, &mut ranker, &mut analytics, &resources).is_empty());
+
+        trainer.record_outcome(&out2);
+        let runs = trainer.run_if_due(NOW, &observations, &mut registry, &mut ranker, &mut analytics, &resources);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].dataset_size, 2);
+        assert!(runs[0].promoted);
+        assert_eq!(runs[0].canary.as_ref().unwrap().candidate.labeled, 2); // Scored against the recorded outcomes
+        assert_eq!(trainer.active_version(&registry), Some("pd-1"));
+        assert!(analytics.get_metrics_by_category(MetricCategory::Operations).iter().any(|m| m.name == "training.pattern_detector.dataset_size"));
+        assert!(!trainer.is_due(NOW));
+    }
+
+    #[test]
+    fn test_interval_and_resources_gate_training() {
+        let mut trainer = ModelTrainer::new(TrainerConfig { min_new_outcomes: 1, min_interval_secs: 3600 });
+        let mut registry = ModelRegistry::new();
+        let mut ranker = RecommendationRanker::new();
+        let mut analytics = AnalyticsAggregator::new();
+        let mut resources = ResourceAwareScheduler::new();
+        resources.update_state(ResourceState { power_source: PowerSource::Battery, battery_pct: Some(90.0), ..ResourceState::default() });
+        let (obs1, out1) = labeled("obs1");
+        let observations = vec![obs1];
+
+        trainer.record_outcome(&out1);
+        assert!(trainer.run_if_due(NOW, &observations, &mut registry, &mut ranker, &mut analytics, &resources).is_empty());
+        assert!(trainer.is_due(NOW)); // Still pending
+
+        resources.update_state(ResourceState::default());
+        assert_eq!(trainer.run_if_due(NOW, &observations, &mut registry, &mut ranker, &mut analytics, &resources).len(), 2);
+        trainer.record_outcome(&out1);
+        assert!(!trainer.is_due(NOW + 60));
+        assert!(trainer.is_due(NOW + 3600));
+    }
+}
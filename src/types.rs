@@ -29,7 +29,7 @@ pub enum PatternType {
 
 /// Action types for interventions
 /// Source: TRAINING CONCEPT.txt#L28
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum ActionType {
     AutomationMacro,
@@ -40,6 +40,56 @@ pub enum ActionType {
     FocusMode,
     ZenMode,
     SystemHygiene,
+    // Seed dataset action types
+    AutoCuration,
+    BatchingSuggestion,
+    CircadianOptimization,
+    CognitiveScheduling,
+    IntelligentFocusMode,
+    MicroBreakSuggestion,
+    PreemptiveErrorAssistant,
+    SmartResize,
+    WorkflowCompression,
+}
+
+/// Consent capability prefix for per-ActionType automation scopes ("automation:focus_mode")
+pub const AUTOMATION_SCOPE_PREFIX: &str = "automation:";
+
+impl ActionType {
+    /// Every action type (legacy blanket automation consent grants all of them)
+    pub fn all() -> Vec<ActionType> {
+        vec![
+            ActionType::AutomationMacro,
+            ActionType::MicroNudge,
+            ActionType::ScheduleChange,
+            ActionType::SandboxPatch,
+            ActionType::PreemptiveDebugAssistant,
+            ActionType::FocusMode,
+            ActionType::ZenMode,
+            ActionType::SystemHygiene,
+            ActionType::AutoCuration,
+            ActionType::BatchingSuggestion,
+            ActionType::CircadianOptimization,
+            ActionType::CognitiveScheduling,
+            ActionType::IntelligentFocusMode,
+            ActionType::MicroBreakSuggestion,
+            ActionType::PreemptiveErrorAssistant,
+            ActionType::SmartResize,
+            ActionType::WorkflowCompression,
+        ]
+    }
+
+    /// Consent capability for executing this action type
+    pub fn consent_scope(&self) -> String {
+        let name = serde_json::to_value(self).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+        format!("{}{}", AUTOMATION_SCOPE_PREFIX, name)
+    }
+
+    /// Parse a consent capability back into an action type
+    pub fn from_consent_scope(capability: &str) -> Option<ActionType> {
+        let name = capability.strip_prefix(AUTOMATION_SCOPE_PREFIX)?;
+        serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+    }
 }
 
 /// Confidence levels for action execution
@@ -76,7 +126,7 @@ pub enum EmotionalState {
 }
 
 /// User profile types
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum UserProfile {
     Developer,
@@ -100,6 +150,8 @@ pub struct Observation {
     pub expected_outcome: HashMap<String, f64>,
     pub source: String,
     pub timestamp: i64,
+    #[serde(default)]
+    pub project: Option<String>, // Active project context, if detected with consent
 }
 
 /// Action definition for interventions
@@ -124,6 +176,28 @@ pub struct Outcome {
     pub timestamp: i64,
 }
 
+/// "Why am I seeing this?" explanation attached to user-visible artifacts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    pub triggering_pattern: String,
+    pub data_used: Vec<String>, // Signals read, never raw content
+    pub confidence: Confidence,
+    pub consent_scopes: Vec<String>, // Consent capabilities the artifact relies on
+}
+
+impl Provenance {
+    /// Human-readable explanation
+    pub fn explain(&self) -> String {
+        format!(
+            "Shown because of {} (confidence: {:?}), based on {}. Consent used: {}.",
+            self.triggering_pattern,
+            self.confidence,
+            if self.data_used.is_empty() { "no personal data".to_string() } else { self.data_used.join(", ") },
+            if self.consent_scopes.is_empty() { "none".to_string() } else { self.consent_scopes.join(", ") },
+        )
+    }
+}
+
 /// Cognitive metrics for daily reports
 /// Source: Strategic_Reinforcements_Gap_Closures.md#L25
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -167,6 +241,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         assert_eq!(observation.id, "test_001");
@@ -184,5 +259,20 @@ mod tests {
         assert!(RiskCategory::High > RiskCategory::Low);
         assert!(RiskCategory::Low > RiskCategory::None);
     }
+
+    #[test]
+    fn test_provenance_explanation() {
+        let provenance = Provenance {
+            triggering_pattern: "Repeated sequence: Teams → Gmail".to_string(),
+            data_used: vec!["repeat_count".to_string()],
+            confidence: Confidence::High,
+            consent_scopes: vec!["behavioral_logging".to_string()],
+        };
+        
+        let explanation = provenance.explain();
+        assert!(explanation.contains("Repeated sequence"));
+        assert!(explanation.contains("repeat_count"));
+        assert!(explanation.contains("behavioral_logging"));
+    }
 }
 
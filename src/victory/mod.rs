@@ -5,7 +5,7 @@
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Daily victory/win
 /// Source: Athenos_AI_Strategy.md#L125
@@ -40,11 +40,48 @@ pub enum VictoryCategory {
     Wellbeing,
 }
 
+/// Total victory counts that count as a milestone
+const VICTORY_COUNT_MILESTONES: [usize; 5] = [1, 10, 25, 50, 100];
+/// Cumulative minutes saved that count as a milestone
+const TIME_SAVED_MILESTONES_MIN: [f64; 3] = [60.0, 300.0, 1000.0];
+/// Consecutive days with a victory that count as a streak
+const STREAK_MILESTONE_DAYS: [u32; 4] = [3, 7, 14, 30];
+
+/// Kind of milestone published on the victory event bus
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum VictoryEventKind {
+    VictoryCount,
+    TimeSaved,
+    Streak,
+}
+
+/// Milestone or streak reached by the victory stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VictoryEvent {
+    pub kind: VictoryEventKind,
+    pub value: f64, // Victory count, minutes saved or streak days
+    pub victory_id: String,
+    pub at: i64,
+}
+
+/// Module that reacts to victory milestones and streaks
+pub trait VictoryListener {
+    /// Module name for logging
+    fn listener_name(&self) -> &str;
+
+    /// Handle a published victory event
+    fn on_victory_event(&mut self, event: &VictoryEvent) -> Result<(), String>;
+}
+
 /// Victory stream manager
 /// Source: Athenos_AI_Strategy.md#L125
 pub struct VictoryStream {
     victories: Vec<Victory>,
     daily_victories: HashMap<String, Vec<Victory>>, // date -> victories
+    total_time_saved_min: f64,
+    pending_events: Vec<VictoryEvent>,
+    undelivered: Vec<Victory>, // Recorded here and not yet taken for notification
 }
 
 impl VictoryStream {
@@ -54,30 +91,99 @@ impl VictoryStream {
         Self {
             victories: Vec::new(),
             daily_victories: HashMap::new(),
+            total_time_saved_min: 0.0,
+            pending_events: Vec::new(),
+            undelivered: Vec::new(),
         }
     }
 
     /// Record a victory
     /// Source: Athenos_AI_Strategy.md#L125
     pub fn record_victory(&mut self, title: String, description: String, metric: VictoryMetric, value: f64, category: VictoryCategory) {
+        self.record_victory_at(title, description, metric, value, category, chrono::Utc::now().timestamp());
+    }
+
+    /// Record a victory at a given time; milestones and streaks it reaches are queued for `publish`
+    pub fn record_victory_at(&mut self, title: String, description: String, metric: VictoryMetric, value: f64, category: VictoryCategory, now: i64) {
         info!("VictoryStream::record_victory: Recording victory: {}", title);
         
         let victory = Victory {
-            id: format!("victory_{}", chrono::Utc::now().timestamp()),
+            id: format!("victory_{}", now),
             title,
             description,
             metric: metric.clone(),
             value,
-            timestamp: chrono::Utc::now().timestamp(),
+            timestamp: now,
             category,
         };
         
-        let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let date = Self::date_of(now);
+        let new_day = !self.daily_victories.contains_key(&date);
         self.victories.push(victory.clone());
         self.daily_victories
             .entry(date)
             .or_insert_with(Vec::new)
-            .push(victory);
+            .push(victory.clone());
+        self.queue_milestones(&victory, new_day);
+        self.undelivered.push(victory);
+    }
+
+    /// Victories recorded on this device since the last call, oldest first (imports are never included)
+    pub fn take_undelivered(&mut self) -> Vec<Victory> {
+        std::mem::take(&mut self.undelivered)
+    }
+
+    fn date_of(timestamp: i64) -> String {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    }
+
+    fn queue_milestones(&mut self, victory: &Victory, new_day: bool) {
+        let mut reached = Vec::new();
+        if VICTORY_COUNT_MILESTONES.contains(&self.victories.len()) {
+            reached.push((VictoryEventKind::VictoryCount, self.victories.len() as f64));
+        }
+        if victory.metric == VictoryMetric::TimeSaved {
+            let before = self.total_time_saved_min;
+            self.total_time_saved_min += victory.value;
+            if let Some(milestone) = TIME_SAVED_MILESTONES_MIN.iter().rev().find(|m| before < **m && self.total_time_saved_min >= **m) {
+                reached.push((VictoryEventKind::TimeSaved, *milestone));
+            }
+        }
+        // A streak can only grow on the first victory of a day
+        if new_day {
+            let streak = self.streak_days(victory.timestamp);
+            if STREAK_MILESTONE_DAYS.contains(&streak) {
+                reached.push((VictoryEventKind::Streak, streak as f64));
+            }
+        }
+        for (kind, value) in reached {
+            info!("VictoryStream::queue_milestones: {:?} milestone reached ({})", kind, value);
+            self.pending_events.push(VictoryEvent { kind, value, victory_id: victory.id.clone(), at: victory.timestamp });
+        }
+    }
+
+    /// Consecutive days with at least one victory, ending on the day of `now`
+    pub fn streak_days(&self, now: i64) -> u32 {
+        let mut days = 0;
+        while self.daily_victories.contains_key(&Self::date_of(now - days as i64 * 86400)) {
+            days += 1;
+        }
+        days
+    }
+
+    /// Deliver queued milestone events to every listener; returns the number of events published
+    pub fn publish(&mut self, listeners: &mut [&mut dyn VictoryListener]) -> usize {
+        let events: Vec<VictoryEvent> = self.pending_events.drain(..).collect();
+        for event in &events {
+            for listener in listeners.iter_mut() {
+                if let Err(e) = listener.on_victory_event(event) {
+                    warn!("VictoryStream::publish: {} failed to handle {:?}: {}", listener.listener_name(), event.kind, e);
+                }
+            }
+        }
+        events.len()
     }
 
     /// Record victory from observation outcome
@@ -127,6 +233,22 @@ impl VictoryStream {
         }
     }
 
+    /// Import a victory recorded on another device; returns false if it is already known
+    /// Imported victories count toward totals but queue no milestones
+    pub fn import_victory(&mut self, victory: Victory) -> bool {
+        if self.victories.iter().any(|v| v.timestamp == victory.timestamp && v.title == victory.title) {
+            return false;
+        }
+        info!("VictoryStream::import_victory: Importing victory: {}", victory.title);
+        if victory.metric == VictoryMetric::TimeSaved {
+            self.total_time_saved_min += victory.value;
+        }
+        let position = self.victories.iter().position(|v| v.timestamp > victory.timestamp).unwrap_or(self.victories.len());
+        self.victories.insert(position, victory.clone());
+        self.daily_victories.entry(Self::date_of(victory.timestamp)).or_default().push(victory);
+        true
+    }
+
     /// Get recent victories
     pub fn get_recent_victories(&self, limit: usize) -> Vec<&Victory> {
         let start = self.victories.len().saturating_sub(limit);
@@ -203,6 +325,7 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         stream.record_from_outcome(&outcome, &observation);
@@ -225,5 +348,19 @@ mod tests {
         assert_eq!(summary.total_victories, 1);
         assert_eq!(summary.total_time_saved_min, 11.0);
     }
+
+    #[test]
+    fn test_only_local_victories_undelivered() {
+        let mut stream = VictoryStream::new();
+        stream.record_victory_at("Local".to_string(), "Test".to_string(), VictoryMetric::FocusIncrease, 1.0, VictoryCategory::Focus, 1_000);
+        let mut remote = stream.victories[0].clone();
+        remote.title = "Remote".to_string();
+        remote.timestamp = 2_000;
+        assert!(stream.import_victory(remote));
+        
+        let undelivered = stream.take_undelivered();
+        assert_eq!(undelivered.iter().map(|v| v.title.as_str()).collect::<Vec<_>>(), vec!["Local"]);
+        assert!(stream.take_undelivered().is_empty());
+    }
 }
 
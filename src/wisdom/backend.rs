@@ -0,0 +1,312 @@
+/// Phase: B | Step: 2 | Source: Athenos_AI_Strategy.md#L109
+/// Insight Backends
+/// Generate Wisdom Engine insights with a local GGUF model or a consented remote API, streaming text as it is produced
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::info;
+
+/// Micro-consent capability required before prompts (which describe the user's behavior) leave the device
+pub const REMOTE_INSIGHT_CAPABILITY: &str = "remote_insight_generation";
+
+/// Sampling settings for one generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationParams {
+    pub max_tokens: usize,
+    pub temperature: f64,
+    pub top_p: f64,
+    pub seed: u64, // Local sampling only
+}
+
+impl Default for GenerationParams {
+    fn default() -> Self {
+        Self {
+            max_tokens: 160,
+            temperature: 0.7,
+            top_p: 0.9,
+            seed: 42,
+        }
+    }
+}
+
+/// Language model that turns an assembled prompt into insight text
+pub trait InsightBackend: Send {
+    fn backend_name(&self) -> &str;
+
+    /// Capability the user must have granted before this backend may see a prompt
+    fn required_capability(&self) -> Option<&str> {
+        None
+    }
+
+    /// Generate a completion, calling `on_token` with each piece of text as it arrives; returns the full text
+    fn generate_stream(&mut self, prompt: &str, params: &GenerationParams, on_token: &mut dyn FnMut(&str)) -> Result<String, String>;
+}
+
+/// Quantized model on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModelConfig {
+    pub model_path: PathBuf,     // GGUF weights (llama-architecture)
+    pub tokenizer_path: PathBuf, // HuggingFace tokenizer.json
+    pub eos_token: String,
+}
+
+impl LocalModelConfig {
+    pub fn new(model_path: impl Into<PathBuf>, tokenizer_path: impl Into<PathBuf>) -> Self {
+        Self {
+            model_path: model_path.into(),
+            tokenizer_path: tokenizer_path.into(),
+            eos_token: "</s>".to_string(),
+        }
+    }
+}
+
+/// Load a local GGUF model with candle
+#[cfg(feature = "local-llm")]
+pub fn load_local_model(config: &LocalModelConfig) -> Result<Box<dyn InsightBackend>, String> {
+    Ok(Box::new(local::LocalModelBackend::load(config)?))
+}
+
+#[cfg(not(feature = "local-llm"))]
+pub fn load_local_model(config: &LocalModelConfig) -> Result<Box<dyn InsightBackend>, String> {
+    Err(format!("Cannot load {}: built without the local-llm feature", config.model_path.display()))
+}
+
+/// Streams a POST response line by line
+pub trait InsightTransport: Send {
+    fn post_stream(&self, url: &str, headers: &[(String, String)], body: &str, on_line: &mut dyn FnMut(&str)) -> Result<(), String>;
+}
+
+/// OpenAI-compatible completions endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteInsightConfig {
+    pub endpoint: String, // e.g. https://api.example.com/v1/completions
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+/// Remote completions API; only used once the user has granted REMOTE_INSIGHT_CAPABILITY
+pub struct RemoteApiBackend {
+    config: RemoteInsightConfig,
+    transport: Box<dyn InsightTransport>,
+}
+
+impl RemoteApiBackend {
+    pub fn new(config: RemoteInsightConfig, transport: Box<dyn InsightTransport>) -> Result<Self, String> {
+        if !config.endpoint.starts_with("https://") {
+            return Err(format!("Insight API endpoint must use https: {}", config.endpoint));
+        }
+        info!("RemoteApiBackend::new: Using {} ({})", config.endpoint, config.model);
+        Ok(Self { config, transport })
+    }
+
+    /// Connect over HTTPS
+    #[cfg(feature = "remote-insights")]
+    pub fn connect(config: RemoteInsightConfig) -> Result<Self, String> {
+        Self::new(config, Box::new(http::HttpTransport::new()))
+    }
+
+    #[cfg(not(feature = "remote-insights"))]
+    pub fn connect(config: RemoteInsightConfig) -> Result<Self, String> {
+        Err(format!("Cannot reach {}: built without the remote-insights feature", config.endpoint))
+    }
+}
+
+/// Text carried by one server-sent event: completion `text` or chat `delta.content`
+fn event_text(line: &str) -> Option<String> {
+    let data = line.strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+    let event: serde_json::Value = serde_json::from_str(data).ok()?;
+    let choice = event.get("choices")?.get(0)?;
+    choice.get("text").or_else(|| choice.get("delta")?.get("content"))?.as_str().map(str::to_string)
+}
+
+impl InsightBackend for RemoteApiBackend {
+    fn backend_name(&self) -> &str {
+        "remote_api"
+    }
+
+    fn required_capability(&self) -> Option<&str> {
+        Some(REMOTE_INSIGHT_CAPABILITY)
+    }
+
+    fn generate_stream(&mut self, prompt: &str, params: &GenerationParams, on_token: &mut dyn FnMut(&str)) -> Result<String, String> {
+        let body = serde_json::json!({
+            "model": self.config.model,
+            "prompt": prompt,
+            "max_tokens": params.max_tokens,
+            "temperature": params.temperature,
+            "top_p": params.top_p,
+            "stream": true,
+        })
+        .to_string();
+        let mut headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "text/event-stream".to_string()),
+        ];
+        if let Some(key) = &self.config.api_key {
+            headers.push(("Authorization".to_string(), format!("Bearer {}", key)));
+        }
+
+        let mut text = String::new();
+        self.transport.post_stream(&self.config.endpoint, &headers, &body, &mut |line| {
+            if let Some(token) = event_text(line).filter(|token| !token.is_empty()) {
+                on_token(&token);
+                text.push_str(&token);
+            }
+        })?;
+        Ok(text)
+    }
+}
+
+#[cfg(feature = "local-llm")]
+mod local {
+    use super::{GenerationParams, InsightBackend, LocalModelConfig};
+    use candle_core::quantized::gguf_file;
+    use candle_core::{Device, Tensor};
+    use candle_transformers::generation::LogitsProcessor;
+    use candle_transformers::models::quantized_llama::ModelWeights;
+    use tokenizers::Tokenizer;
+    use tracing::info;
+
+    /// Quantized llama-family model run on the CPU
+    pub struct LocalModelBackend {
+        model: ModelWeights,
+        tokenizer: Tokenizer,
+        eos_token_id: u32,
+        device: Device,
+    }
+
+    impl LocalModelBackend {
+        pub fn load(config: &LocalModelConfig) -> Result<Self, String> {
+            info!("LocalModelBackend::load: Loading {}", config.model_path.display());
+            let device = Device::Cpu;
+            let mut file = std::fs::File::open(&config.model_path)
+                .map_err(|e| format!("Failed to open {}: {}", config.model_path.display(), e))?;
+            let content = gguf_file::Content::read(&mut file).map_err(|e| format!("Invalid GGUF file: {}", e))?;
+            let model = ModelWeights::from_gguf(content, &mut file, &device).map_err(|e| format!("Failed to load model weights: {}", e))?;
+            let tokenizer = Tokenizer::from_file(&config.tokenizer_path).map_err(|e| format!("Failed to load tokenizer: {}", e))?;
+            let eos_token_id = tokenizer
+                .token_to_id(&config.eos_token)
+                .ok_or_else(|| format!("Tokenizer has no '{}' token", config.eos_token))?;
+            Ok(Self { model, tokenizer, eos_token_id, device })
+        }
+    }
+
+    impl InsightBackend for LocalModelBackend {
+        fn backend_name(&self) -> &str {
+            "local_gguf"
+        }
+
+        fn generate_stream(&mut self, prompt: &str, params: &GenerationParams, on_token: &mut dyn FnMut(&str)) -> Result<String, String> {
+            let encoding = self.tokenizer.encode(prompt, true).map_err(|e| format!("Tokenization failed: {}", e))?;
+            let mut input = encoding.get_ids().to_vec();
+            let mut logits_processor = LogitsProcessor::new(params.seed, Some(params.temperature), Some(params.top_p));
+            let mut generated: Vec<u32> = Vec::new();
+            let mut text = String::new();
+            let mut index_pos = 0; // 0 resets the model's KV cache
+
+            for _ in 0..params.max_tokens {
+                let logits = Tensor::new(input.as_slice(), &self.device)
+                    .and_then(|ids| ids.unsqueeze(0))
+                    .and_then(|ids| self.model.forward(&ids, index_pos))
+                    .and_then(|logits| logits.squeeze(0))
+                    .map_err(|e| format!("Forward pass failed: {}", e))?;
+                index_pos += input.len();
+                let next = logits_processor.sample(&logits).map_err(|e| format!("Sampling failed: {}", e))?;
+                if next == self.eos_token_id {
+                    break;
+                }
+                generated.push(next);
+
+                // Decode the whole completion and emit the new suffix, so word spacing and multi-token characters survive
+                let decoded = self.tokenizer.decode(&generated, true).map_err(|e| format!("Detokenization failed: {}", e))?;
+                if decoded.len() > text.len() && decoded.starts_with(text.as_str()) {
+                    on_token(&decoded[text.len()..]);
+                    text = decoded;
+                }
+                input = vec![next];
+            }
+            Ok(text)
+        }
+    }
+}
+
+#[cfg(feature = "remote-insights")]
+mod http {
+    use super::InsightTransport;
+    use std::io::{BufRead, BufReader};
+    use std::time::Duration;
+
+    /// Blocking HTTPS transport
+    pub struct HttpTransport {
+        agent: ureq::Agent,
+    }
+
+    impl HttpTransport {
+        pub fn new() -> Self {
+            Self { agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(60)).build() }
+        }
+    }
+
+    impl InsightTransport for HttpTransport {
+        fn post_stream(&self, url: &str, headers: &[(String, String)], body: &str, on_line: &mut dyn FnMut(&str)) -> Result<(), String> {
+            let mut request = self.agent.post(url);
+            for (name, value) in headers {
+                request = request.set(name, value);
+            }
+            let response = request.send_string(body).map_err(|e| format!("Insight API request failed: {}", e))?;
+            for line in BufReader::new(response.into_reader()).lines() {
+                on_line(&line.map_err(|e| format!("Failed to read insight stream: {}", e))?);
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct FakeTransport {
+        lines: Vec<&'static str>,
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl InsightTransport for FakeTransport {
+        fn post_stream(&self, _url: &str, _headers: &[(String, String)], body: &str, on_line: &mut dyn FnMut(&str)) -> Result<(), String> {
+            self.sent.lock().unwrap().push(body.to_string());
+            self.lines.iter().for_each(|line| on_line(line));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_remote_backend_streams_events() {
+        let config = RemoteInsightConfig { endpoint: "https://llm.example.com/v1/completions".to_string(), model: "mentor".to_string(), api_key: None };
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let transport = FakeTransport {
+            lines: vec![
+                "data: {\"choices\":[{\"text\":\"Notice\"}]}",
+                "",
+                "data: {\"choices\":[{\"delta\":{\"content\":\" the loop.\"}}]}",
+                ": keep-alive",
+                "data: [DONE]",
+            ],
+            sent: sent.clone(),
+        };
+        let mut backend = RemoteApiBackend::new(config.clone(), Box::new(transport)).unwrap();
+        assert_eq!(backend.required_capability(), Some(REMOTE_INSIGHT_CAPABILITY));
+
+        let mut tokens = Vec::new();
+        let text = backend.generate_stream("prompt", &GenerationParams::default(), &mut |token| tokens.push(token.to_string())).unwrap();
+        assert_eq!(tokens, vec!["Notice", " the loop."]);
+        assert_eq!(text, "Notice the loop.");
+        assert!(sent.lock().unwrap()[0].contains("\"stream\":true"));
+
+        let insecure = RemoteInsightConfig { endpoint: "http://llm.example.com".to_string(), ..config };
+        assert!(RemoteApiBackend::new(insecure, Box::new(FakeTransport { lines: vec![], sent })).is_err());
+    }
+}
@@ -3,14 +3,44 @@
 /// Fine-tune Wisdom Engine LLM on curated corpus (insights, philosophy, tone)
 
 use crate::types::*;
+use crate::consent::MicroConsentManager;
+use crate::rag::{DocumentChunk, RAGIndex};
+use crate::safety_filter::SafetyFilter;
+use crate::habits::{HabitChange, HabitChangeKind, HabitTracker};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
+
+pub mod backend;
+
+use backend::{GenerationParams, InsightBackend};
+
+/// Knowledge chunks retrieved into each prompt
+pub const RAG_CONTEXT_CHUNKS: usize = 3;
+
+/// Knowledge chunk an insight was generated from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub chunk_id: String,
+    pub source: String,
+    pub score: f32, // Retrieval similarity
+}
+
+/// Insight with the provenance the UI shows alongside it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundedInsight {
+    pub insight: String,
+    pub citations: Vec<Citation>, // Empty when the insight did not come from the retrieved knowledge
+    pub generated_by: String,     // Backend name, or "template"
+}
 
 /// Wisdom Engine prompt template
 /// Source: Athenos_AI_Strategy.md#L85-89
 pub struct WisdomEngine {
     prompt_template: String,
+    safety_filter: SafetyFilter,
+    backend: Option<Box<dyn InsightBackend>>, // None: template insights only
+    generation: GenerationParams,
 }
 
 impl WisdomEngine {
@@ -35,7 +65,31 @@ Provide insight in 2-3 sentences that:
 
 Insight:"#.to_string();
         
-        Self { prompt_template }
+        Self {
+            prompt_template,
+            safety_filter: SafetyFilter::new(),
+            backend: None,
+            generation: GenerationParams::default(),
+        }
+    }
+
+    /// Share a safety filter (e.g. one that knows the user's window titles)
+    pub fn set_safety_filter(&mut self, safety_filter: SafetyFilter) {
+        self.safety_filter = safety_filter;
+    }
+
+    /// Generate insights with a language model instead of the template
+    pub fn set_backend(&mut self, backend: Box<dyn InsightBackend>) {
+        info!("WisdomEngine::set_backend: Using {} backend", backend.backend_name());
+        self.backend = Some(backend);
+    }
+
+    pub fn set_generation_params(&mut self, params: GenerationParams) {
+        self.generation = params;
+    }
+
+    pub fn backend_name(&self) -> &str {
+        self.backend.as_ref().map(|backend| backend.backend_name()).unwrap_or("template")
     }
 
     /// Generate insight from observation
@@ -48,12 +102,181 @@ Insight:"#.to_string();
         let observation_desc = observation.observation.join(" → ");
         let pattern = self.detect_pattern_type(&observation);
         
-        format!(
+        let generated = format!(
             "I've noticed you frequently follow the pattern: {}. This {} pattern suggests your mind is operating on autopilot. Consider pausing to reflect: could this workflow be streamlined? The suggested action ({}) aligns with your cognitive rhythm and may help you transcend this habitual loop.",
             observation_desc,
             pattern,
             observation.action.description
-        )
+        );
+        self.surface_insight(&generated, observation)
+    }
+
+    /// Fill the prompt template for submission to the shared inference queue
+    pub fn build_prompt(&self, observation: &Observation, context: &str) -> String {
+        self.assemble_prompt(observation, context, None)
+    }
+
+    /// Fill the prompt template, adding the knowledge chunks most relevant to the observation to the context
+    pub fn assemble_prompt(&self, observation: &Observation, context: &str, rag: Option<&RAGIndex>) -> String {
+        let chunks = rag.map(|index| self.retrieve(observation, index)).unwrap_or_default();
+        self.fill_prompt(observation, context, &chunks)
+    }
+
+    /// Top-k knowledge chunks for an observation's steps and suggested action
+    fn retrieve<'a>(&self, observation: &Observation, rag: &'a RAGIndex) -> Vec<(&'a DocumentChunk, f32)> {
+        let query = format!("{} {}", observation.observation.join(" "), observation.action.description);
+        rag.search_scored(&query, RAG_CONTEXT_CHUNKS)
+    }
+
+    fn fill_prompt(&self, observation: &Observation, context: &str, chunks: &[(&DocumentChunk, f32)]) -> String {
+        let mut context = context.to_string();
+        if !chunks.is_empty() {
+            context.push_str("\n\nRelevant knowledge:");
+            for (chunk, _) in chunks {
+                context.push_str(&format!("\n- [{}] {}", chunk.source, chunk.content.trim()));
+            }
+        }
+        self.prompt_template
+            .replace("{context}", &context)
+            .replace("{observation}", &observation.observation.join(" → "))
+    }
+
+    /// Generate an insight with the configured backend, streaming it to `on_text` sentence by sentence
+    /// Each sentence is released only after the text so far passes the safety filter; on a violation streaming
+    /// stops and the fallback is returned, so callers should display the returned insight once generation ends.
+    /// Without a backend, or without the consent the backend requires, the template insight is produced instead.
+    pub fn generate_insight_streaming(
+        &mut self,
+        observation: &Observation,
+        context: &str,
+        rag: Option<&RAGIndex>,
+        consent: &MicroConsentManager,
+        on_text: &mut dyn FnMut(&str),
+    ) -> String {
+        let prompt = self.assemble_prompt(observation, context, rag);
+        match self.run_backend(&prompt, observation, consent, on_text) {
+            Some((insight, _)) => insight,
+            None => {
+                let insight = self.generate_insight(observation, context);
+                on_text(&insight);
+                insight
+            }
+        }
+    }
+
+    /// Generate an insight from the knowledge most relevant to the observation, citing the chunks it was given
+    /// Citations are only attached when the backend's own text is surfaced; template and fallback insights don't use the chunks.
+    pub fn generate_grounded_insight(
+        &mut self,
+        observation: &Observation,
+        context: &str,
+        rag: &RAGIndex,
+        consent: &MicroConsentManager,
+    ) -> GroundedInsight {
+        let chunks = self.retrieve(observation, rag);
+        let prompt = self.fill_prompt(observation, context, &chunks);
+        match self.run_backend(&prompt, observation, consent, &mut |_| {}) {
+            Some((insight, grounded)) => {
+                let citations: Vec<Citation> = if grounded {
+                    chunks.iter().map(|(chunk, score)| Citation { chunk_id: chunk.id.clone(), source: chunk.source.clone(), score: *score }).collect()
+                } else {
+                    Vec::new()
+                };
+                info!("WisdomEngine::generate_grounded_insight: {} grounded in {} of {} retrieved chunks", observation.id, citations.len(), chunks.len());
+                GroundedInsight { insight, citations, generated_by: self.backend_name().to_string() }
+            }
+            None => GroundedInsight {
+                insight: self.generate_insight(observation, context),
+                citations: Vec::new(),
+                generated_by: "template".to_string(),
+            },
+        }
+    }
+
+    /// Stream a prompt through the backend; None when there is no usable backend or it produced nothing.
+    /// The flag is false when the safety filter replaced the generated text with the fallback.
+    fn run_backend(&mut self, prompt: &str, observation: &Observation, consent: &MicroConsentManager, on_text: &mut dyn FnMut(&str)) -> Option<(String, bool)> {
+        let backend = match self.backend.as_mut() {
+            Some(backend) if backend.required_capability().is_none_or(|capability| consent.has_consent(capability)) => backend,
+            Some(backend) => {
+                info!("WisdomEngine::run_backend: No consent for {}, using template", backend.backend_name());
+                return None;
+            }
+            None => return None,
+        };
+        info!("WisdomEngine::run_backend: Generating insight for {} with {}", observation.id, backend.backend_name());
+        let safety_filter = &self.safety_filter;
+        let mut text = String::new();
+        let mut released = 0;
+        let mut blocked = false;
+        let result = backend.generate_stream(prompt, &self.generation, &mut |token| {
+            text.push_str(token);
+            if blocked {
+                return;
+            }
+            // Release up to the last sentence boundary once everything before it is safe
+            if let Some(end) = text.rfind(['.', '!', '?', '\n']).map(|i| i + 1).filter(|end| *end > released) {
+                if safety_filter.check(&text[..end]).is_empty() {
+                    on_text(&text[released..end]);
+                    released = end;
+                } else {
+                    blocked = true;
+                }
+            }
+        });
+        match result {
+            Ok(_) if !text.trim().is_empty() => {
+                let insight = self.surface_insight(text.trim(), observation);
+                let passed = !blocked && insight == text.trim();
+                if passed && !text[released..].trim().is_empty() {
+                    on_text(&text[released..]);
+                }
+                Some((insight, passed))
+            }
+            Ok(_) => {
+                warn!("WisdomEngine::run_backend: {} returned no text, using template", self.backend_name());
+                None
+            }
+            Err(e) => {
+                warn!("WisdomEngine::run_backend: {} failed, using template: {}", self.backend_name(), e);
+                None
+            }
+        }
+    }
+
+    /// Pass generated insight text through the safety filter, falling back to a fixed template
+    pub fn surface_insight(&self, generated: &str, observation: &Observation) -> String {
+        let fallback = format!(
+            "This {} repeats often. Consider pausing to reflect on whether it could be streamlined.",
+            self.detect_pattern_type(observation)
+        );
+        self.safety_filter.filter("wisdom_engine", generated, &fallback)
+    }
+
+    /// Monthly "habit changed" insights; None until a month has passed since the last batch
+    pub fn monthly_habit_insights(&self, tracker: &mut HabitTracker, now: i64) -> Option<Vec<String>> {
+        let changes = tracker.take_monthly_changes(now)?;
+        info!("WisdomEngine::monthly_habit_insights: {} habit changes this month", changes.len());
+        Some(changes.iter().map(|change| self.habit_insight(change)).collect())
+    }
+
+    /// Insight for a single habit formation or dissolution
+    pub fn habit_insight(&self, change: &HabitChange) -> String {
+        let generated = match change.kind {
+            HabitChangeKind::Formed => format!(
+                "A new habit has taken root: {}. It showed up on {} of your recent days. Notice whether it serves you before it becomes automatic.",
+                change.signature, change.active_days
+            ),
+            HabitChangeKind::Dissolved => format!(
+                "The habit {} has faded from your days. Whether you let it go deliberately or it simply drifted away, take a moment to notice what replaced it.",
+                change.signature
+            ),
+        };
+        let fallback = match change.kind {
+            HabitChangeKind::Formed => "A new habit has formed this month.".to_string(),
+            HabitChangeKind::Dissolved => "One of your habits has faded this month.".to_string(),
+        };
+        self.safety_filter.filter("wisdom_engine", &generated, &fallback)
     }
 
     /// Fine-tune on seed data
@@ -118,11 +341,16 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         };
         
         let insight = engine.generate_insight(&observation, "Morning routine");
         assert!(insight.contains("Teams"));
         assert!(insight.contains("workflow sequence"));
+        
+        let prompt = engine.build_prompt(&observation, "Morning routine");
+        assert!(prompt.contains("Context: Morning routine"));
+        assert!(prompt.contains("Observation: Teams → Gmail → IDE"));
     }
 
     #[test]
@@ -143,11 +371,186 @@ mod tests {
             expected_outcome: HashMap::new(),
             source: "test".to_string(),
             timestamp: 1234567890,
+            project: None,
         }; 15]; // 15 observations
         
         let result = engine.fine_tune(&observations);
         assert!(result.is_ok());
         assert!(engine.prompt_template.contains("Fine-tuned"));
     }
-}
 
+    #[test]
+    fn test_unsafe_insight_falls_back() {
+        let mut engine = WisdomEngine::new();
+        let filter = SafetyFilter::new();
+        engine.set_safety_filter(filter.clone());
+        
+        let observation = Observation {
+            id: "test".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["App1".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::DetectPattern,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Test".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        
+        let insight = engine.surface_insight("This loop looks like a symptom of an anxiety disorder.", &observation);
+        assert!(insight.starts_with("This behavioral pattern repeats often"));
+        assert_eq!(filter.get_violations().len(), 1);
+    }
+
+    #[test]
+    fn test_monthly_habit_insights() {
+        let engine = WisdomEngine::new();
+        let mut tracker = HabitTracker::new();
+        let change = HabitChange {
+            signature: "Teams → Gmail → IDE".to_string(),
+            kind: HabitChangeKind::Dissolved,
+            at: 0,
+            active_days: 1,
+        };
+        assert!(engine.habit_insight(&change).contains("Teams → Gmail → IDE"));
+        
+        assert!(engine.monthly_habit_insights(&mut tracker, 0).unwrap().is_empty());
+        assert!(engine.monthly_habit_insights(&mut tracker, 3600).is_none());
+    }
+
+    struct ScriptedBackend {
+        tokens: Vec<&'static str>,
+        prompts: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl InsightBackend for ScriptedBackend {
+        fn backend_name(&self) -> &str {
+            "scripted"
+        }
+
+        fn required_capability(&self) -> Option<&str> {
+            Some(backend::REMOTE_INSIGHT_CAPABILITY)
+        }
+
+        fn generate_stream(&mut self, prompt: &str, _params: &GenerationParams, on_token: &mut dyn FnMut(&str)) -> Result<String, String> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            self.tokens.iter().for_each(|token| on_token(token));
+            Ok(self.tokens.concat())
+        }
+    }
+
+    #[test]
+    fn test_backend_streams_consented_insight_with_rag_context() {
+        let observation = Observation {
+            id: "test".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Create startup macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let mut rag = RAGIndex::new();
+        rag.index_chunk(crate::rag::DocumentChunk {
+            id: "doc_1".to_string(),
+            content: "Startup macro batches Teams and Gmail into one launch.".to_string(),
+            source: "guide".to_string(),
+            embedding: Vec::new(),
+            metadata: HashMap::new(),
+        });
+        let prompts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut engine = WisdomEngine::new();
+        engine.set_backend(Box::new(ScriptedBackend {
+            tokens: vec!["You open", " Teams first.", " A macro", " could help."],
+            prompts: prompts.clone(),
+        }));
+        let mut consent = MicroConsentManager::new();
+
+        // No consent: the prompt never reaches the backend
+        let mut streamed = Vec::new();
+        let insight = engine.generate_insight_streaming(&observation, "Morning", Some(&rag), &consent, &mut |text| streamed.push(text.to_string()));
+        assert!(insight.contains("workflow sequence"));
+        assert_eq!(streamed, vec![insight]);
+        assert!(prompts.lock().unwrap().is_empty());
+
+        consent.request_consent(backend::REMOTE_INSIGHT_CAPABILITY.to_string(), "Generate insights remotely".to_string());
+        consent.grant_consent(backend::REMOTE_INSIGHT_CAPABILITY).unwrap();
+        let mut streamed = Vec::new();
+        let insight = engine.generate_insight_streaming(&observation, "Morning", Some(&rag), &consent, &mut |text| streamed.push(text.to_string()));
+        assert_eq!(insight, "You open Teams first. A macro could help.");
+        assert_eq!(streamed, vec!["You open Teams first.", " A macro could help."]);
+        assert!(prompts.lock().unwrap()[0].contains("Relevant knowledge:\n- [guide] Startup macro"));
+
+        // A violation mid-stream stops streaming and returns the fallback
+        engine.set_backend(Box::new(ScriptedBackend {
+            tokens: vec!["Fine so far.", " This is a symptom", " of stress."],
+            prompts,
+        }));
+        let mut streamed = Vec::new();
+        let insight = engine.generate_insight_streaming(&observation, "Morning", None, &consent, &mut |text| streamed.push(text.to_string()));
+        assert!(insight.starts_with("This workflow sequence repeats often"));
+        assert_eq!(streamed, vec!["Fine so far."]);
+    }
+
+    #[test]
+    fn test_grounded_insight_cites_retrieved_chunks() {
+        let observation = Observation {
+            id: "test".to_string(),
+            profile: UserProfile::Developer,
+            observation: vec!["Teams".to_string(), "Gmail".to_string(), "IDE".to_string()],
+            metrics: HashMap::new(),
+            intent: Intent::SuggestShortcut,
+            action: Action {
+                action_type: ActionType::AutomationMacro,
+                description: "Create startup macro".to_string(),
+                confidence: Confidence::High,
+                risk: RiskCategory::None,
+            },
+            expected_outcome: HashMap::new(),
+            source: "test".to_string(),
+            timestamp: 1234567890,
+            project: None,
+        };
+        let mut rag = RAGIndex::new();
+        rag.index_chunk(crate::rag::DocumentChunk {
+            id: "doc_1".to_string(),
+            content: "Startup macro batches Teams and Gmail into one launch.".to_string(),
+            source: "guide".to_string(),
+            embedding: Vec::new(),
+            metadata: HashMap::new(),
+        });
+        let mut consent = MicroConsentManager::new();
+        consent.request_consent(backend::REMOTE_INSIGHT_CAPABILITY.to_string(), "Generate insights remotely".to_string());
+        consent.grant_consent(backend::REMOTE_INSIGHT_CAPABILITY).unwrap();
+
+        // Template insights don't use the knowledge, so they cite nothing
+        let mut engine = WisdomEngine::new();
+        let grounded = engine.generate_grounded_insight(&observation, "Morning", &rag, &consent);
+        assert_eq!(grounded.generated_by, "template");
+        assert!(grounded.citations.is_empty());
+
+        let prompts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        engine.set_backend(Box::new(ScriptedBackend { tokens: vec!["One launch", " covers all three."], prompts: prompts.clone() }));
+        let grounded = engine.generate_grounded_insight(&observation, "Morning", &rag, &consent);
+        assert_eq!(grounded.insight, "One launch covers all three.");
+        assert_eq!(grounded.generated_by, "scripted");
+        assert_eq!(grounded.citations.len(), 1);
+        assert_eq!((grounded.citations[0].chunk_id.as_str(), grounded.citations[0].source.as_str()), ("doc_1", "guide"));
+        assert!(grounded.citations[0].score > 0.0);
+        assert!(prompts.lock().unwrap()[0].contains("[guide] Startup macro"));
+    }
+}